@@ -0,0 +1,560 @@
+//! Transcribes audio files, or audio piped in on stdin, without the app, for
+//! scripts and pipelines such as
+//!
+//! ```sh
+//! handy-cli transcribe meeting.m4a --model ggml-small.bin --json
+//! arecord -f S16_LE -r 16000 -c 1 | handy-cli transcribe - --model ggml-small.bin
+//! ```
+//!
+//! Files are read whole, in any format `read_audio_file_with_rate` knows.
+//! Stdin is a WAV stream, told apart by its header, or raw 16-bit
+//! little-endian PCM at `--rate` and `--channels`, and each utterance is
+//! printed as it ends at a pause or after `MAX_UTTERANCE_SECS`. With
+//! `--json` the output is JSON lines with start and end in seconds.
+//!
+//! A `--model` that isn't a path is looked up among the models Handy has
+//! downloaded.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use handy_transcription::{
+    audio::{downmix, read_audio_file_with_rate, rms, FrameResampler},
+    constants::WHISPER_SAMPLE_RATE,
+    deepgram,
+    local::{self, LocalEngine, LocalModel, ModelKind},
+    suppress_hallucinations, wyoming, Engine, Request, Transcript,
+};
+
+const FRAME_MS: usize = 30;
+/// RMS level below which a frame counts as quiet
+const SILENCE_RMS: f32 = 0.01;
+/// Quiet after speech that ends an utterance
+const PAUSE_MS: usize = 700;
+/// Utterances are cut here even without a pause
+const MAX_UTTERANCE_SECS: usize = 30;
+/// Raw samples read from stdin at a time
+const READ_SAMPLES: usize = 4096;
+
+/// Handy's app identifier, which names its data directory
+const APP_IDENTIFIER: &str = "com.pais.handy";
+/// Handy's settings, in its data directory
+const SETTINGS_STORE: &str = "settings_store.json";
+
+const USAGE: &str = "Usage: handy-cli transcribe FILE|- ENGINE [options]
+
+Transcribes FILE, or audio piped in on stdin when FILE is -. --stdin is
+short for `transcribe -`.
+
+Engines:
+  --model PATH|NAME     A local model, Whisper unless --kind says otherwise.
+                        A NAME such as ggml-small.bin is looked up among the
+                        models Handy has downloaded
+  --kind KIND           whisper, parakeet or moonshine
+  --wyoming HOST:PORT   A Wyoming speech-to-text server
+  --deepgram            Deepgram, with the key in DEEPGRAM_API_KEY
+
+Options:
+  --rate HZ             Sample rate of raw stdin input (default 16000)
+  --channels N          Channels of raw stdin input (default 1)
+  --language TAG        Language spoken, e.g. en or de (default: detect)
+  --json                Print JSON lines with timestamps";
+
+#[derive(Debug, PartialEq)]
+enum EngineArg {
+    Local(PathBuf, ModelKind),
+    Wyoming(String),
+    Deepgram,
+}
+
+#[derive(Debug, PartialEq)]
+enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    input: Input,
+    engine: EngineArg,
+    rate: u32,
+    channels: u16,
+    language: Option<String>,
+    json: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<Args> {
+    let mut input = None;
+    let mut model = None;
+    let mut kind = ModelKind::Whisper;
+    let mut wyoming = None;
+    let mut deepgram = false;
+    let mut rate = WHISPER_SAMPLE_RATE;
+    let mut channels = 1;
+    let mut language = None;
+    let mut json = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| anyhow!("{} needs a value", arg))
+        };
+        match arg.as_str() {
+            "--stdin" => input = Some(Input::Stdin),
+            "transcribe" if input.is_none() => {
+                input = Some(match value()?.as_str() {
+                    "-" => Input::Stdin,
+                    path => Input::File(PathBuf::from(path)),
+                })
+            }
+            "--model" => model = Some(value()?),
+            "--kind" => {
+                kind = match value()?.as_str() {
+                    "whisper" => ModelKind::Whisper,
+                    "parakeet" => ModelKind::Parakeet,
+                    "moonshine" => ModelKind::Moonshine,
+                    other => return Err(anyhow!("Unknown model kind: {}", other)),
+                }
+            }
+            "--wyoming" => wyoming = Some(value()?),
+            "--deepgram" => deepgram = true,
+            "--rate" => rate = value()?.parse()?,
+            "--channels" => channels = value()?.parse()?,
+            "--language" => language = Some(value()?),
+            "--json" => json = true,
+            other => return Err(anyhow!("Unknown argument: {}", other)),
+        }
+    }
+
+    let input = input.ok_or_else(|| anyhow!("Nothing to transcribe"))?;
+    if rate == 0 || channels == 0 {
+        return Err(anyhow!("--rate and --channels must be above 0"));
+    }
+    let engine = match (model, wyoming, deepgram) {
+        (Some(model), None, false) => EngineArg::Local(find_model(&model), kind),
+        (None, Some(address), false) => EngineArg::Wyoming(address),
+        (None, None, true) => EngineArg::Deepgram,
+        _ => return Err(anyhow!("Choose one of --model, --wyoming or --deepgram")),
+    };
+    Ok(Args {
+        input,
+        engine,
+        rate,
+        channels,
+        language,
+        json,
+    })
+}
+
+/// Where Handy keeps the models it downloads, and the shared directory an
+/// administrator can put them in, as set in Handy's settings or else the
+/// platform's system-wide one
+fn models_dirs() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let data = if cfg!(target_os = "macos") {
+        home.map(|home| home.join("Library/Application Support"))
+    } else if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home.map(|home| home.join(".local/share")))
+    }
+    .map(|dir| dir.join(APP_IDENTIFIER));
+    let shared = data
+        .as_deref()
+        .and_then(configured_shared_dir)
+        .or_else(|| local::default_shared_models_dir(APP_IDENTIFIER));
+    data.map(|dir| dir.join("models"))
+        .into_iter()
+        .chain(shared)
+        .collect()
+}
+
+/// The `shared_models_dir` saved in the settings store in Handy's data
+/// directory `data`, if one is set
+fn configured_shared_dir(data: &Path) -> Option<PathBuf> {
+    let store = std::fs::read_to_string(data.join(SETTINGS_STORE)).ok()?;
+    let store: serde_json::Value = serde_json::from_str(&store).ok()?;
+    store["settings"]["shared_models_dir"]
+        .as_str()
+        .map(PathBuf::from)
+}
+
+/// `model` as given if it exists, otherwise the downloaded model of that name
+fn find_model(model: &str) -> PathBuf {
+    let path = PathBuf::from(model);
+    if path.exists() {
+        return path;
+    }
+    models_dirs()
+        .into_iter()
+        .map(|dir| dir.join(model))
+        .find(|candidate| candidate.exists())
+        .unwrap_or(path)
+}
+
+fn open_engine(engine: &EngineArg) -> Result<Box<dyn Engine>> {
+    Ok(match engine {
+        EngineArg::Local(path, kind) => Box::new(LocalModel::new(LocalEngine::open(*kind, path)?)),
+        EngineArg::Wyoming(address) => Box::new(wyoming::Client {
+            address: address.clone(),
+        }),
+        EngineArg::Deepgram => Box::new(deepgram::Client {
+            config: deepgram::Config {
+                api_key: std::env::var("DEEPGRAM_API_KEY")
+                    .map_err(|_| anyhow!("Set DEEPGRAM_API_KEY to use Deepgram"))?,
+                ..Default::default()
+            },
+            on_retry: &report_retry,
+        }),
+    })
+}
+
+fn report_retry(retry: u32, max_retries: u32, delay: Duration) {
+    eprintln!(
+        "handy-cli: request failed, retry {} of {} in {:.0}s",
+        retry,
+        max_retries,
+        delay.as_secs_f32()
+    );
+}
+
+/// Cuts a stream of 16 kHz audio into utterances at pauses. Quiet before
+/// the first speech of each utterance is dropped.
+struct Utterances {
+    pending: Vec<f32>,
+    /// Samples of the stream before `pending`
+    offset: usize,
+    quiet_frames: usize,
+    heard_speech: bool,
+}
+
+/// An utterance and where it starts in the stream, in samples
+struct Utterance {
+    start: usize,
+    samples: Vec<f32>,
+}
+
+impl Utterance {
+    fn span_secs(&self) -> (f32, f32) {
+        let rate = WHISPER_SAMPLE_RATE as f32;
+        (
+            self.start as f32 / rate,
+            (self.start + self.samples.len()) as f32 / rate,
+        )
+    }
+}
+
+impl Utterances {
+    fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            offset: 0,
+            quiet_frames: 0,
+            heard_speech: false,
+        }
+    }
+
+    /// Adds one frame of `FRAME_MS`, returning the utterance it ends if any
+    fn push_frame(&mut self, frame: &[f32]) -> Option<Utterance> {
+        let quiet = rms(frame) < SILENCE_RMS;
+        if quiet && !self.heard_speech {
+            self.offset += frame.len();
+            return None;
+        }
+        self.pending.extend_from_slice(frame);
+        self.heard_speech = true;
+        self.quiet_frames = if quiet { self.quiet_frames + 1 } else { 0 };
+
+        let paused = self.quiet_frames * FRAME_MS >= PAUSE_MS;
+        let full = self.pending.len() >= MAX_UTTERANCE_SECS * WHISPER_SAMPLE_RATE as usize;
+        (paused || full).then(|| self.take())
+    }
+
+    fn take(&mut self) -> Utterance {
+        let samples = std::mem::take(&mut self.pending);
+        let start = self.offset;
+        self.offset += samples.len();
+        self.quiet_frames = 0;
+        self.heard_speech = false;
+        Utterance { start, samples }
+    }
+
+    /// What's left once the stream ends
+    fn finish(&mut self) -> Option<Utterance> {
+        self.heard_speech.then(|| self.take())
+    }
+}
+
+fn pcm16_samples(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]) as f32 / i16::MAX as f32)
+        .collect()
+}
+
+/// Calls `on_samples` with interleaved samples from `input` as they come in,
+/// returning the sample rate and channel count. WAV input is recognized by
+/// its header, anything else is taken as raw PCM at `rate` and `channels`.
+fn read_input(
+    input: impl Read,
+    rate: u32,
+    channels: u16,
+    mut on_samples: impl FnMut(u32, u16, &[f32]) -> Result<()>,
+) -> Result<()> {
+    let mut input = BufReader::new(input);
+    if input.fill_buf()?.starts_with(b"RIFF") {
+        let mut reader = hound::WavReader::new(input)?;
+        let spec = reader.spec();
+        let scale = (1i64 << (spec.bits_per_sample.max(1) - 1)) as f32;
+        let samples: Box<dyn Iterator<Item = hound::Result<f32>>> = match spec.sample_format {
+            hound::SampleFormat::Float => Box::new(reader.samples::<f32>()),
+            hound::SampleFormat::Int => Box::new(
+                reader
+                    .samples::<i32>()
+                    .map(move |s| s.map(|s| s as f32 / scale)),
+            ),
+        };
+        let mut chunk = Vec::with_capacity(READ_SAMPLES);
+        for sample in samples {
+            chunk.push(sample?);
+            if chunk.len() == READ_SAMPLES {
+                on_samples(spec.sample_rate, spec.channels, &chunk)?;
+                chunk.clear();
+            }
+        }
+        return on_samples(spec.sample_rate, spec.channels, &chunk);
+    }
+
+    let mut buffer = vec![0u8; READ_SAMPLES * 2];
+    let mut carry = Vec::new();
+    loop {
+        let read = input.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(());
+        }
+        carry.extend_from_slice(&buffer[..read]);
+        let whole = carry.len() - carry.len() % (2 * channels as usize);
+        on_samples(rate, channels, &pcm16_samples(&carry[..whole]))?;
+        carry.drain(..whole);
+    }
+}
+
+#[derive(Serialize)]
+struct Line<'a> {
+    start: f32,
+    end: f32,
+    text: &'a str,
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args = match parse_args(&args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = run(&args) {
+        eprintln!("handy-cli: {:#}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: &Args) -> Result<()> {
+    let engine = open_engine(&args.engine)?;
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    let transcribe = |samples: &[f32]| -> Result<Transcript> {
+        let request = Request {
+            language: args.language.as_deref(),
+            ..Request::new(samples)
+        };
+        runtime.block_on(engine.transcribe(request))
+    };
+    let mut stdout = io::stdout().lock();
+    match &args.input {
+        Input::File(path) => transcribe_file(args, path, transcribe, &mut stdout),
+        Input::Stdin => transcribe_stdin(args, transcribe, &mut stdout),
+    }
+}
+
+fn print_line(args: &Args, out: &mut dyn Write, start: f32, end: f32, text: &str) -> Result<()> {
+    if args.json {
+        let line = Line { start, end, text };
+        writeln!(out, "{}", serde_json::to_string(&line)?)?;
+    } else {
+        writeln!(out, "{}", text)?;
+    }
+    Ok(out.flush()?)
+}
+
+/// Prints the whole file's transcript, or its segments with `--json` if the
+/// engine timed them
+fn transcribe_file(
+    args: &Args,
+    path: &Path,
+    transcribe: impl Fn(&[f32]) -> Result<Transcript>,
+    out: &mut impl Write,
+) -> Result<()> {
+    let (samples, _) = read_audio_file_with_rate(path)?;
+    let transcript = transcribe(&samples)?;
+    if args.json && !transcript.segments.is_empty() {
+        for segment in &transcript.segments {
+            print_line(args, out, segment.start, segment.end, segment.text.trim())?;
+        }
+        return Ok(());
+    }
+    let text = suppress_hallucinations(transcript.text.trim());
+    let duration = samples.len() as f32 / WHISPER_SAMPLE_RATE as f32;
+    print_line(args, out, 0.0, duration, &text)
+}
+
+/// Prints each utterance on stdin as it ends
+fn transcribe_stdin(
+    args: &Args,
+    transcribe: impl Fn(&[f32]) -> Result<Transcript>,
+    out: &mut impl Write,
+) -> Result<()> {
+    let mut utterances = Utterances::new();
+    let mut resampler: Option<FrameResampler> = None;
+
+    let emit = |utterance: Utterance, out: &mut dyn Write| -> Result<()> {
+        let transcript = transcribe(&utterance.samples)?;
+        let text = suppress_hallucinations(transcript.text.trim());
+        if text.is_empty() {
+            return Ok(());
+        }
+        let (start, end) = utterance.span_secs();
+        print_line(args, out, start, end, &text)
+    };
+
+    let mut ended = Vec::new();
+    read_input(
+        io::stdin().lock(),
+        args.rate,
+        args.channels,
+        |rate, channels, samples| {
+            let resampler = resampler.get_or_insert_with(|| {
+                FrameResampler::new(
+                    rate as usize,
+                    WHISPER_SAMPLE_RATE as usize,
+                    Duration::from_millis(FRAME_MS as u64),
+                )
+            });
+            resampler.push(&downmix(samples, channels as usize), |frame| {
+                ended.extend(utterances.push_frame(frame));
+            });
+            for utterance in ended.drain(..) {
+                emit(utterance, out)?;
+            }
+            Ok(())
+        },
+    )?;
+
+    if let Some(resampler) = resampler.as_mut() {
+        resampler.finish(|frame| {
+            ended.extend(utterances.push_frame(frame));
+        });
+    }
+    ended.extend(utterances.finish());
+    for utterance in ended {
+        emit(utterance, out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Result<Args> {
+        parse_args(
+            &line
+                .split_whitespace()
+                .map(String::from)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn parses_engines_and_input_format() {
+        let parsed = args("--stdin --model small.bin --kind parakeet --rate 48000 --json").unwrap();
+        assert_eq!(
+            parsed.engine,
+            EngineArg::Local(PathBuf::from("small.bin"), ModelKind::Parakeet)
+        );
+        assert_eq!(
+            (parsed.rate, parsed.channels, parsed.json),
+            (48000, 1, true)
+        );
+        assert_eq!(
+            args("--stdin --wyoming localhost:10300").unwrap().engine,
+            EngineArg::Wyoming("localhost:10300".to_string())
+        );
+        assert_eq!(
+            args("transcribe talk.m4a --deepgram").unwrap().input,
+            Input::File(PathBuf::from("talk.m4a"))
+        );
+        assert_eq!(args("transcribe - --deepgram").unwrap().input, Input::Stdin);
+        assert!(args("--model small.bin").is_err());
+        assert!(args("--stdin --model a.bin --deepgram").is_err());
+        assert!(args("--stdin --deepgram --rate").is_err());
+    }
+
+    #[test]
+    fn cuts_utterances_at_pauses() {
+        let frame_len = WHISPER_SAMPLE_RATE as usize * FRAME_MS / 1000;
+        let speech = vec![0.2; frame_len];
+        let quiet = vec![0.0; frame_len];
+        let mut utterances = Utterances::new();
+
+        // Leading quiet is skipped, but counts towards the start time
+        for _ in 0..10 {
+            assert!(utterances.push_frame(&quiet).is_none());
+        }
+        for _ in 0..20 {
+            assert!(utterances.push_frame(&speech).is_none());
+        }
+        let pause = PAUSE_MS.div_ceil(FRAME_MS);
+        let ended: Vec<Utterance> = (0..pause)
+            .filter_map(|_| utterances.push_frame(&quiet))
+            .collect();
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].start, 10 * frame_len);
+        assert_eq!(ended[0].samples.len(), (20 + pause) * frame_len);
+
+        assert!(utterances.finish().is_none());
+        utterances.push_frame(&speech);
+        assert_eq!(
+            utterances.finish().map(|u| u.start),
+            Some((30 + pause) * frame_len)
+        );
+    }
+
+    #[test]
+    fn reads_raw_pcm_in_whole_frames() {
+        let bytes: Vec<u8> = [i16::MAX, 0, i16::MIN + 1, 0]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let mut seen = Vec::new();
+        read_input(&bytes[..], 8000, 2, |rate, channels, samples| {
+            assert_eq!((rate, channels), (8000, 2));
+            seen.extend_from_slice(samples);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(seen, [1.0, 0.0, -1.0, 0.0]);
+        assert_eq!(downmix(&seen, 2), [0.5, -0.5]);
+    }
+}
@@ -0,0 +1,115 @@
+//! Reading compressed audio and the sound of video files, so any recording
+//! can be transcribed, not only WAV.
+
+use anyhow::{anyhow, Result};
+use log::debug;
+use std::fs::File;
+use std::path::Path;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as DecodeError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::audio::utils::{read_wav_file_with_rate, to_whisper_format};
+
+/// Extensions of the files `read_audio_file_with_rate` reads
+pub const AUDIO_EXTENSIONS: &[&str] = &[
+    "wav", "mp3", "m4a", "mp4", "m4v", "mov", "aac", "flac", "ogg", "oga",
+];
+
+/// Whether `path` looks like a file `read_audio_file_with_rate` can read
+pub fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Reads the first audio track of an audio or video file as 16kHz mono,
+/// along with the sample rate it was recorded at.
+pub fn read_audio_file_with_rate<P: AsRef<Path>>(file_path: P) -> Result<(Vec<f32>, u32)> {
+    let path = file_path.as_ref();
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    if extension.as_deref() == Some("wav") {
+        return read_wav_file_with_rate(path);
+    }
+
+    let source = MediaSourceStream::new(Box::new(File::open(path)?), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = &extension {
+        hint.with_extension(extension);
+    }
+    let mut format = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| anyhow!("Unsupported file {:?}: {}", path, e))?
+        .format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| anyhow!("{:?} has no audio track", path))?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| anyhow!("Can't decode the audio in {:?}: {}", path, e))?;
+
+    let mut interleaved = Vec::new();
+    let mut spec = None;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(DecodeError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A damaged packet is skipped rather than failing the file
+            Err(DecodeError::DecodeError(e)) => {
+                debug!("Skipping a packet of {:?}: {}", path, e);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let packet_spec = *decoded.spec();
+        let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, packet_spec);
+        buffer.copy_interleaved_ref(decoded);
+        interleaved.extend_from_slice(buffer.samples());
+        spec.get_or_insert(packet_spec);
+    }
+
+    let spec = spec.ok_or_else(|| anyhow!("{:?} has no audio", path))?;
+    let out = to_whisper_format(&interleaved, spec.rate, spec.channels.count() as u16)?;
+    debug!(
+        "Decoded {:?} ({} samples at 16kHz, recorded at {}Hz)",
+        path,
+        out.len(),
+        spec.rate
+    );
+    Ok((out, spec.rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_audio_and_video_files() {
+        assert!(is_audio_file(Path::new("/calls/standup.M4A")));
+        assert!(is_audio_file(Path::new("talk.mp4")));
+        assert!(!is_audio_file(Path::new("notes.txt")));
+        assert!(!is_audio_file(Path::new("recording")));
+    }
+}
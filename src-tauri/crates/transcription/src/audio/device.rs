@@ -8,7 +8,7 @@ pub struct CpalDeviceInfo {
 }
 
 pub fn list_input_devices() -> Result<Vec<CpalDeviceInfo>, Box<dyn std::error::Error>> {
-    let host = crate::audio_toolkit::get_cpal_host();
+    let host = crate::get_cpal_host();
     let default_name = host.default_input_device().and_then(|d| d.name().ok());
 
     let mut out = Vec::<CpalDeviceInfo>::new();
@@ -30,7 +30,7 @@ pub fn list_input_devices() -> Result<Vec<CpalDeviceInfo>, Box<dyn std::error::E
 }
 
 pub fn list_output_devices() -> Result<Vec<CpalDeviceInfo>, Box<dyn std::error::Error>> {
-    let host = crate::audio_toolkit::get_cpal_host();
+    let host = crate::get_cpal_host();
     let default_name = host.default_output_device().and_then(|d| d.name().ok());
 
     let mut out = Vec::<CpalDeviceInfo>::new();
@@ -0,0 +1,191 @@
+//! Clean-up of the live input, frame by frame, before the VAD sees it: noise
+//! suppression with RNNoise and an automatic gain control. Unlike the
+//! preprocessing passes they run while recording, so everything after them,
+//! from the VAD to whichever engine transcribes, gets the cleaned audio.
+//!
+//! RNNoise is also one of the passes of the preprocessing chain, through
+//! `suppress_noise`. Unlike the spectral subtraction in `preprocess` it's a
+//! trained model, so it also takes out noise that changes, like keyboards
+//! and traffic.
+
+use nnnoiseless::DenoiseState;
+
+use crate::audio::utils::rms;
+
+/// RNNoise works on 10ms frames at 48kHz, three times our rate
+const RATIO: usize = 3;
+const CHUNK: usize = DenoiseState::FRAME_SIZE / RATIO;
+/// RNNoise's output lags its input by a frame, the dry signal is held back
+/// as long so the two line up when mixed
+const DELAY: usize = CHUNK;
+/// RNNoise expects samples in the range of 16-bit PCM
+const PCM_SCALE: f32 = 32_768.0;
+
+/// About -20 dBFS, as `normalize_level` aims for
+const TARGET_RMS: f32 = 0.1;
+/// 20 dB up at most, and never more than 12 dB down
+const MAX_GAIN: f32 = 10.0;
+const MIN_GAIN: f32 = 0.25;
+/// Frames quieter than this are taken as silence and hold the gain, so the
+/// room isn't brought up between words
+const GATE_RMS: f32 = 0.005;
+/// Share of the way to the wanted gain moved per frame. Turning down is
+/// fast so a shout doesn't clip for long, turning up slow so it doesn't pump.
+const ATTACK: f32 = 0.5;
+const RELEASE: f32 = 0.05;
+
+/// RNNoise on 16kHz input. Each 10ms is upsampled to 48kHz, denoised and
+/// taken back down; speech has next to nothing above 8kHz, so simple
+/// interpolation and decimation are enough.
+pub struct NoiseSuppressor {
+    state: Box<DenoiseState<'static>>,
+    /// Input not yet a whole chunk
+    pending: Vec<f32>,
+    /// Dry input waiting for the denoised output to catch up
+    dry: Vec<f32>,
+    last: f32,
+    up: Vec<f32>,
+    down: Vec<f32>,
+}
+
+impl Default for NoiseSuppressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoiseSuppressor {
+    pub fn new() -> Self {
+        Self {
+            state: DenoiseState::new(),
+            pending: Vec::with_capacity(CHUNK),
+            dry: vec![0.0; DELAY],
+            last: 0.0,
+            up: vec![0.0; DenoiseState::FRAME_SIZE],
+            down: vec![0.0; DenoiseState::FRAME_SIZE],
+        }
+    }
+
+    /// Denoises `frame` in place, mixing `strength` of the denoised signal
+    /// with the rest of the original, so 0 leaves it as it was (but 10ms
+    /// late) and 1 is RNNoise's output as is. The output runs 10ms behind.
+    pub fn process(&mut self, frame: &mut [f32], strength: f32) {
+        let strength = strength.clamp(0.0, 1.0);
+        self.pending.extend_from_slice(frame);
+        let mut out = Vec::with_capacity(frame.len());
+        let whole = self.pending.len() / CHUNK * CHUNK;
+        for chunk in self.pending[..whole].chunks(CHUNK) {
+            for (i, &sample) in chunk.iter().enumerate() {
+                for step in 0..RATIO {
+                    let t = (step + 1) as f32 / RATIO as f32;
+                    self.up[i * RATIO + step] = (self.last + (sample - self.last) * t) * PCM_SCALE;
+                }
+                self.last = sample;
+            }
+            self.state.process_frame(&mut self.down, &self.up);
+            self.dry.extend_from_slice(chunk);
+            for (i, dry) in self.dry.drain(..CHUNK).enumerate() {
+                let wet = self.down[i * RATIO + RATIO - 1] / PCM_SCALE;
+                out.push(dry + (wet - dry) * strength);
+            }
+        }
+        self.pending.drain(..whole);
+
+        // What's still pending comes out with the next frame, until then
+        // the gap is filled with silence
+        let len = frame.len();
+        let start = len.saturating_sub(out.len());
+        frame[..start].fill(0.0);
+        frame[start..].copy_from_slice(&out[out.len().saturating_sub(len)..]);
+    }
+}
+
+/// Keeps speech near `TARGET_RMS` whether the speaker leans in or sits back.
+/// The gain follows the level of speech frames and holds through silence.
+pub struct AutoGain {
+    gain: f32,
+}
+
+impl Default for AutoGain {
+    fn default() -> Self {
+        Self { gain: 1.0 }
+    }
+}
+
+impl AutoGain {
+    pub fn process(&mut self, frame: &mut [f32]) {
+        let level = rms(frame);
+        let from = self.gain;
+        if level >= GATE_RMS {
+            let wanted = (TARGET_RMS / level).clamp(MIN_GAIN, MAX_GAIN);
+            let rate = if wanted < self.gain { ATTACK } else { RELEASE };
+            self.gain += (wanted - self.gain) * rate;
+        }
+        // Ramp across the frame so the change doesn't click
+        let step = (self.gain - from) / frame.len().max(1) as f32;
+        for (i, sample) in frame.iter_mut().enumerate() {
+            *sample = (*sample * (from + step * (i + 1) as f32)).clamp(-1.0, 1.0);
+        }
+    }
+}
+
+/// Denoises a whole recording with RNNoise, mixing `strength` of the
+/// denoised signal with the rest of the original. The output lines up with
+/// the input, its 10ms delay taken back out.
+pub fn suppress_noise(samples: &[f32], strength: f32) -> Vec<f32> {
+    let mut suppressor = NoiseSuppressor::new();
+    let mut padded = samples.to_vec();
+    padded.resize(samples.len() + DELAY + CHUNK, 0.0);
+    let mut out = Vec::with_capacity(padded.len());
+    for chunk in padded.chunks(CHUNK) {
+        let mut frame = chunk.to_vec();
+        suppressor.process(&mut frame, strength);
+        out.extend(frame);
+    }
+    out.drain(..DELAY);
+    out.truncate(samples.len());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_gain_levels_speech_but_not_silence() {
+        let tone = |amplitude: f32| -> Vec<f32> {
+            (0..480)
+                .map(|i| amplitude * (i as f32 * 0.2).sin())
+                .collect()
+        };
+        let mut agc = AutoGain::default();
+        let mut frame = tone(0.02);
+        for _ in 0..200 {
+            frame = tone(0.02);
+            agc.process(&mut frame);
+        }
+        assert!((rms(&frame) - TARGET_RMS).abs() < 0.01, "{}", rms(&frame));
+
+        // Silence keeps the gain it had instead of being brought up
+        let gain = agc.gain;
+        let mut quiet = tone(0.001);
+        agc.process(&mut quiet);
+        assert_eq!(agc.gain, gain);
+
+        // A loud frame is turned down quickly, without clipping
+        let mut loud = tone(0.9);
+        agc.process(&mut loud);
+        assert!(agc.gain < gain * 0.6);
+        assert!(loud.iter().all(|s| s.abs() <= 1.0));
+    }
+
+    #[test]
+    fn suppression_keeps_length_and_timing() {
+        let audio: Vec<f32> = (0..16_000).map(|i| 0.3 * (i as f32 * 0.05).sin()).collect();
+        // With nothing of the denoised signal mixed in, only the delay is
+        // left to take out
+        assert_eq!(suppress_noise(&audio, 0.0), audio);
+        assert_eq!(suppress_noise(&audio, 1.0).len(), audio.len());
+        assert!(suppress_noise(&[], 1.0).is_empty());
+    }
+}
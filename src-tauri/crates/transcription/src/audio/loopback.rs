@@ -0,0 +1,204 @@
+//! Recording what the computer plays instead of (or along with) a
+//! microphone, to transcribe calls and videos.
+//!
+//! * Windows reads the default output device through WASAPI loopback.
+//! * Linux reads the monitor of the default PulseAudio or PipeWire sink,
+//!   through the `pulse` ALSA device.
+//! * macOS has no loopback without a virtual device, so a BlackHole (or
+//!   Loopback, or Soundflower) input is used. Sound only reaches it when the
+//!   output goes through a Multi-Output Device that includes it.
+
+use std::{
+    collections::VecDeque,
+    io::{Error, ErrorKind},
+    sync::mpsc::{self, TryRecvError},
+    time::Duration,
+};
+
+use cpal::{traits::DeviceTrait, Device, SupportedStreamConfig};
+
+use crate::{audio::FrameResampler, constants};
+
+/// Name system audio goes by among the input devices
+pub const SYSTEM_AUDIO: &str = "System audio";
+
+/// How far one mixed source may run ahead before the other is taken as
+/// silent. WASAPI loopback sends nothing at all while nothing plays.
+const MAX_LAG: Duration = Duration::from_millis(200);
+/// How often the mixer collects what both sources sent
+const MIX_INTERVAL: Duration = Duration::from_millis(10);
+
+#[cfg(target_os = "macos")]
+const VIRTUAL_DEVICES: &[&str] = &["BlackHole", "Loopback Audio", "Soundflower"];
+
+/// The device system audio is read from
+pub(crate) struct SystemAudio {
+    device: Device,
+    pub config: SupportedStreamConfig,
+    /// The PulseAudio source to read, picked when the device opens
+    #[cfg(target_os = "linux")]
+    monitor: String,
+}
+
+fn not_found(message: &str) -> Box<dyn std::error::Error> {
+    Box::new(Error::new(ErrorKind::NotFound, message))
+}
+
+impl SystemAudio {
+    #[cfg(target_os = "windows")]
+    pub fn find() -> Result<Self, Box<dyn std::error::Error>> {
+        use cpal::traits::HostTrait;
+        let device = crate::get_cpal_host()
+            .default_output_device()
+            .ok_or_else(|| not_found("No output device to record from"))?;
+        // An input stream on an output device is a loopback stream
+        let config = device.default_output_config()?;
+        Ok(Self { device, config })
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn find() -> Result<Self, Box<dyn std::error::Error>> {
+        use cpal::traits::HostTrait;
+        let output = std::process::Command::new("pactl")
+            .arg("get-default-sink")
+            .output()
+            .map_err(|_| not_found("Recording system audio needs PulseAudio or PipeWire"))?;
+        let sink = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !output.status.success() || sink.is_empty() {
+            return Err(not_found("No default output to record from"));
+        }
+        let device = crate::get_cpal_host()
+            .input_devices()?
+            .find(|d| d.name().is_ok_and(|name| name == "pulse"))
+            .ok_or_else(|| not_found("The pulse ALSA device isn't installed"))?;
+        let config = device.default_input_config()?;
+        Ok(Self {
+            device,
+            config,
+            monitor: format!("{}.monitor", sink),
+        })
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn find() -> Result<Self, Box<dyn std::error::Error>> {
+        use cpal::traits::HostTrait;
+        let device = crate::get_cpal_host()
+            .input_devices()?
+            .find(|d| {
+                d.name()
+                    .is_ok_and(|name| VIRTUAL_DEVICES.iter().any(|v| name.contains(v)))
+            })
+            .ok_or_else(|| {
+                not_found("Recording system audio needs a virtual device such as BlackHole")
+            })?;
+        let config = device.default_input_config()?;
+        Ok(Self { device, config })
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+    pub fn find() -> Result<Self, Box<dyn std::error::Error>> {
+        Err(not_found("Recording system audio isn't supported here"))
+    }
+
+    pub fn build_stream(
+        &self,
+        sample_tx: mpsc::Sender<Vec<f32>>,
+    ) -> Result<cpal::Stream, cpal::BuildStreamError> {
+        // The pulse plugin connects to the source PULSE_SOURCE names when the
+        // device opens, so it only has to be set meanwhile
+        #[cfg(target_os = "linux")]
+        std::env::set_var("PULSE_SOURCE", &self.monitor);
+        let stream = super::recorder::build_input_stream(&self.device, &self.config, sample_tx);
+        #[cfg(target_os = "linux")]
+        std::env::remove_var("PULSE_SOURCE");
+        stream
+    }
+}
+
+/// Whether system audio can be recorded on this computer as it's set up
+pub fn system_audio_available() -> bool {
+    SystemAudio::find().is_ok()
+}
+
+/// Adds two 16 kHz sources up sample by sample
+struct Mixer {
+    queues: [VecDeque<f32>; 2],
+    max_lag: usize,
+}
+
+impl Mixer {
+    fn new(max_lag: usize) -> Self {
+        Self {
+            queues: [VecDeque::new(), VecDeque::new()],
+            max_lag,
+        }
+    }
+
+    /// Queues `samples` from `source` and returns what can be mixed so far
+    fn push(&mut self, source: usize, samples: &[f32]) -> Vec<f32> {
+        self.queues[source].extend(samples);
+        let [a, b] = &mut self.queues;
+        let both = a.len().min(b.len());
+        let mut out: Vec<f32> = a
+            .drain(..both)
+            .zip(b.drain(..both))
+            .map(|(a, b)| (a + b).clamp(-1.0, 1.0))
+            .collect();
+        // At most one queue is left with samples
+        for queue in &mut self.queues {
+            if queue.len() > self.max_lag {
+                let ahead = queue.len() - self.max_lag;
+                out.extend(queue.drain(..ahead));
+            }
+        }
+        out
+    }
+}
+
+/// Resamples both sources to 16 kHz, mixes them and sends the result on to
+/// `out`, until either source or `out` goes away
+pub(crate) fn mix_sources(
+    sources: [(mpsc::Receiver<Vec<f32>>, u32); 2],
+    out: mpsc::Sender<Vec<f32>>,
+) {
+    let rate = constants::WHISPER_SAMPLE_RATE as usize;
+    let mut mixer = Mixer::new((rate as f64 * MAX_LAG.as_secs_f64()) as usize);
+    let mut resamplers = sources.each_ref().map(|(_, in_rate)| {
+        FrameResampler::new(*in_rate as usize, rate, Duration::from_millis(30))
+    });
+    loop {
+        let mut mixed = Vec::new();
+        for (source, ((rx, _), resampler)) in sources.iter().zip(&mut resamplers).enumerate() {
+            loop {
+                match rx.try_recv() {
+                    Ok(samples) => resampler.push(&samples, |frame| {
+                        mixed.extend(mixer.push(source, frame));
+                    }),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => return,
+                }
+            }
+        }
+        if !mixed.is_empty() && out.send(mixed).is_err() {
+            return;
+        }
+        std::thread::sleep(MIX_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixes_sources_and_fills_in_for_a_silent_one() {
+        let mut mixer = Mixer::new(4);
+        assert!(mixer.push(0, &[0.25, 0.25, 0.25]).is_empty());
+        assert_eq!(mixer.push(1, &[0.5, 0.5]), [0.75, 0.75]);
+        assert_eq!(mixer.push(1, &[1.0]), [1.0]);
+
+        // Nothing from the first source for a while
+        assert!(mixer.push(1, &[0.1; 4]).is_empty());
+        assert_eq!(mixer.push(1, &[0.1; 2]), [0.1, 0.1]);
+    }
+}
@@ -0,0 +1,37 @@
+// Re-export all audio components
+mod decode;
+#[cfg(feature = "capture")]
+mod device;
+mod dsp;
+#[cfg(feature = "capture")]
+mod loopback;
+mod network;
+mod pre_roll;
+mod preprocess;
+#[cfg(feature = "capture")]
+mod recorder;
+mod resampler;
+mod segment;
+mod utils;
+mod visualizer;
+mod watchdog;
+
+pub use decode::{is_audio_file, read_audio_file_with_rate, AUDIO_EXTENSIONS};
+#[cfg(feature = "capture")]
+pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
+pub use dsp::{suppress_noise, AutoGain, NoiseSuppressor};
+#[cfg(feature = "capture")]
+pub use loopback::{system_audio_available, SYSTEM_AUDIO};
+pub use network::NetworkInput;
+pub use pre_roll::PreRoll;
+pub use preprocess::{high_pass, low_pass, normalize_level, reduce_noise, trim_silence};
+#[cfg(feature = "capture")]
+pub use recorder::AudioRecorder;
+pub use resampler::{resample, FrameResampler};
+pub use segment::{compress_silence, overlap_forced_cuts, split_on_silence, SilenceMap};
+pub use utils::{
+    downmix, encode_flac, encode_wav, read_wav_file, read_wav_file_with_rate, rms, save_wav_file,
+    to_pcm16,
+};
+pub use visualizer::AudioVisualiser;
+pub use watchdog::DeadInputDetector;
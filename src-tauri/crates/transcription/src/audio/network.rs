@@ -0,0 +1,510 @@
+//! Audio input streamed over the local network instead of read from a sound
+//! card, so a phone (or anything else with a better microphone) can be used
+//! as the recording device.
+//!
+//! Two kinds of sender are accepted on the same port number:
+//!
+//! * WebSocket over TCP. An optional text message
+//!   `{"sample_rate": 48000, "channels": 1}` describes the stream, then
+//!   binary messages carry interleaved 16-bit little-endian PCM. Without the
+//!   text message 16 kHz mono is assumed.
+//! * RTP over UDP with an L16 payload (16-bit big-endian PCM). Payload types
+//!   10 and 11 are the static 44.1 kHz stereo and mono formats, dynamic ones
+//!   are taken as 16 kHz mono.
+//!
+//! The port is open to the whole network, so a sender pairs first: it opens
+//! a WebSocket with the pairing token, as `?token=` in the URL or as a
+//! bearer token. Only one device is paired at a time, others are turned
+//! away until it disconnects, and RTP is only taken from the paired
+//! device's address while its WebSocket stays open. Whichever of the two
+//! delivers audio first is the one listened to.
+//!
+//! Whatever arrives is mixed down to mono and resampled to 16 kHz. While no
+//! sender is streaming, silence is produced so the recorder keeps running.
+
+use std::{
+    io::{self, ErrorKind},
+    net::{IpAddr, TcpListener, TcpStream, UdpSocket},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use serde::Deserialize;
+use subtle::ConstantTimeEq;
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::{Error as WsError, Message};
+
+use crate::{audio::FrameResampler, constants};
+
+/// After this long without audio from any sender, silence fills the gap
+const SILENCE_AFTER: Duration = Duration::from_millis(250);
+/// How often blocked sockets check whether the input was closed
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+const RTP_HEADER_LEN: usize = 12;
+/// A connection that hasn't finished its handshake by then is dropped, so
+/// it can't hold up the next one
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Which of the paired device's streams is being listened to
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Source {
+    WebSocket,
+    Rtp,
+}
+
+/// The paired device: its address, and the stream it sends audio on once
+/// it has sent any
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Pairing {
+    addr: IpAddr,
+    source: Option<Source>,
+}
+
+type Paired = Arc<Mutex<Option<Pairing>>>;
+
+/// Whether audio from `addr` over `source` is listened to, claiming the
+/// pairing for `source` when nothing was sent over it yet
+fn claim(paired: &Paired, addr: IpAddr, source: Source) -> bool {
+    let mut paired = paired.lock().unwrap();
+    match paired.as_mut() {
+        Some(pairing) if pairing.addr == addr => *pairing.source.get_or_insert(source) == source,
+        _ => false,
+    }
+}
+
+/// Whether the handshake carries `token`, in the URL's query or as a bearer
+/// token. The comparison takes as long wherever the tokens differ.
+fn has_token(query: Option<&str>, authorization: Option<&str>, token: &str) -> bool {
+    let matches = |given: &str| bool::from(given.as_bytes().ct_eq(token.as_bytes()));
+    let in_query = query.is_some_and(|query| {
+        query
+            .split('&')
+            .filter_map(|pair| pair.strip_prefix("token="))
+            .any(matches)
+    });
+    let in_header = authorization
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| matches(given.trim()));
+    !token.is_empty() && (in_query || in_header)
+}
+
+#[derive(Deserialize)]
+struct StreamFormat {
+    sample_rate: u32,
+    #[serde(default = "mono")]
+    channels: u16,
+}
+
+fn mono() -> u16 {
+    1
+}
+
+/// The PCM payload of an RTP packet and its payload type, skipping CSRCs,
+/// header extensions and padding
+fn rtp_payload(packet: &[u8]) -> Option<(u8, &[u8])> {
+    if packet.len() < RTP_HEADER_LEN || packet[0] >> 6 != 2 {
+        return None;
+    }
+    let csrc_count = (packet[0] & 0x0f) as usize;
+    let mut start = RTP_HEADER_LEN + csrc_count * 4;
+    if packet[0] & 0x10 != 0 {
+        let words = u16::from_be_bytes([*packet.get(start + 2)?, *packet.get(start + 3)?]);
+        start += 4 + words as usize * 4;
+    }
+    let mut end = packet.len();
+    if packet[0] & 0x20 != 0 {
+        end = end.checked_sub(*packet.last()? as usize)?;
+    }
+    let payload = packet.get(start..end)?;
+    Some((packet[1] & 0x7f, payload))
+}
+
+/// Sample rate and channel count of an RTP L16 payload type
+fn rtp_format(payload_type: u8) -> (u32, u16) {
+    match payload_type {
+        10 => (44_100, 2),
+        11 => (44_100, 1),
+        _ => (constants::WHISPER_SAMPLE_RATE, 1),
+    }
+}
+
+/// Decodes interleaved 16-bit PCM and mixes it down to mono
+fn decode_pcm16(bytes: &[u8], channels: u16, big_endian: bool) -> Vec<f32> {
+    let samples = bytes.chunks_exact(2).map(|pair| {
+        let pair = [pair[0], pair[1]];
+        let value = if big_endian {
+            i16::from_be_bytes(pair)
+        } else {
+            i16::from_le_bytes(pair)
+        };
+        value as f32 / i16::MAX as f32
+    });
+    let channels = channels.max(1) as usize;
+    if channels == 1 {
+        return samples.collect();
+    }
+    let samples: Vec<f32> = samples.collect();
+    samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Converts one sender's audio to 16 kHz mono, rebuilding the resampler when
+/// the format changes
+struct Converter {
+    format: (u32, u16),
+    resampler: FrameResampler,
+    out: mpsc::Sender<Vec<f32>>,
+}
+
+impl Converter {
+    fn new(out: mpsc::Sender<Vec<f32>>) -> Self {
+        let format = (constants::WHISPER_SAMPLE_RATE, 1);
+        Self {
+            format,
+            resampler: Self::resampler(format.0),
+            out,
+        }
+    }
+
+    fn resampler(sample_rate: u32) -> FrameResampler {
+        FrameResampler::new(
+            sample_rate as usize,
+            constants::WHISPER_SAMPLE_RATE as usize,
+            Duration::from_millis(30),
+        )
+    }
+
+    fn set_format(&mut self, sample_rate: u32, channels: u16) {
+        if sample_rate == 0 || (sample_rate, channels) == self.format {
+            return;
+        }
+        log::info!(
+            "Network microphone format: {} Hz, {} channel(s)",
+            sample_rate,
+            channels
+        );
+        self.format = (sample_rate, channels);
+        self.resampler = Self::resampler(sample_rate);
+    }
+
+    /// Returns false once the input is gone
+    fn push(&mut self, bytes: &[u8], big_endian: bool) -> bool {
+        let samples = decode_pcm16(bytes, self.format.1, big_endian);
+        let mut ok = true;
+        let out = &self.out;
+        self.resampler.push(&samples, |frame| {
+            ok &= out.send(frame.to_vec()).is_ok();
+        });
+        ok
+    }
+}
+
+/// Listens for network audio until dropped.
+pub struct NetworkInput {
+    closed: Arc<AtomicBool>,
+    /// The threads holding the sockets, joined on drop so the port is free
+    /// to bind again straight after
+    sockets: Vec<std::thread::JoinHandle<()>>,
+}
+
+impl NetworkInput {
+    /// Starts listening on `port` for a device paired with `token` and sends
+    /// 16 kHz mono samples to `sample_tx`.
+    pub fn listen(port: u16, token: String, sample_tx: mpsc::Sender<Vec<f32>>) -> io::Result<Self> {
+        if token.is_empty() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "The network microphone needs a pairing token",
+            ));
+        }
+        let tcp = TcpListener::bind(("0.0.0.0", port))?;
+        tcp.set_nonblocking(true)?;
+        let udp = UdpSocket::bind(("0.0.0.0", port))?;
+        udp.set_read_timeout(Some(POLL_INTERVAL))?;
+        log::info!("Listening for a network microphone on port {}", port);
+
+        let closed = Arc::new(AtomicBool::new(false));
+        let paired: Paired = Arc::new(Mutex::new(None));
+        let (net_tx, net_rx) = mpsc::channel::<Vec<f32>>();
+
+        // Forward sender audio, filling gaps with silence
+        std::thread::spawn({
+            let closed = closed.clone();
+            move || {
+                let silence = vec![
+                    0.0;
+                    (constants::WHISPER_SAMPLE_RATE as f64 * SILENCE_AFTER.as_secs_f64())
+                        as usize
+                ];
+                while !closed.load(Ordering::Relaxed) {
+                    let samples = match net_rx.recv_timeout(SILENCE_AFTER) {
+                        Ok(samples) => samples,
+                        Err(_) => silence.clone(),
+                    };
+                    if sample_tx.send(samples).is_err() {
+                        break;
+                    }
+                }
+                closed.store(true, Ordering::Relaxed);
+            }
+        });
+
+        let accept = std::thread::spawn({
+            let closed = closed.clone();
+            let paired = paired.clone();
+            let net_tx = net_tx.clone();
+            move || accept_websockets(tcp, token, paired, net_tx, closed)
+        });
+
+        let rtp = std::thread::spawn({
+            let closed = closed.clone();
+            move || receive_rtp(udp, paired, net_tx, closed)
+        });
+
+        Ok(Self {
+            closed,
+            sockets: vec![accept, rtp],
+        })
+    }
+}
+
+impl Drop for NetworkInput {
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::Relaxed);
+        // Each notices within `POLL_INTERVAL` and closes its socket
+        for handle in self.sockets.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Takes one connection at a time. The handshake runs here, bounded by
+/// `HANDSHAKE_TIMEOUT`, and only the paired device gets a thread of its own.
+fn accept_websockets(
+    listener: TcpListener,
+    token: String,
+    paired: Paired,
+    out: mpsc::Sender<Vec<f32>>,
+    closed: Arc<AtomicBool>,
+) {
+    while !closed.load(Ordering::Relaxed) {
+        let (stream, addr) = match listener.accept() {
+            Ok(accepted) => accepted,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                std::thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+            Err(e) => {
+                log::error!("Network microphone listener failed: {}", e);
+                return;
+            }
+        };
+        if paired.lock().unwrap().is_some() {
+            log::warn!(
+                "Turned away network microphone {}, another device is paired",
+                addr
+            );
+            continue;
+        }
+        let socket = match handshake(stream, &token) {
+            Ok(socket) => socket,
+            Err(e) => {
+                log::warn!("Network microphone {} wasn't paired: {}", addr, e);
+                continue;
+            }
+        };
+        log::info!("Network microphone paired with {}", addr);
+        *paired.lock().unwrap() = Some(Pairing {
+            addr: addr.ip(),
+            source: None,
+        });
+
+        let out = out.clone();
+        let closed = closed.clone();
+        let paired = paired.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = serve_websocket(socket, addr.ip(), &paired, out, closed) {
+                log::warn!("Network microphone connection ended: {}", e);
+            }
+            paired.lock().unwrap().take();
+            log::info!("Network microphone {} disconnected", addr);
+        });
+    }
+}
+
+type Socket = tungstenite::WebSocket<TcpStream>;
+
+/// Accepts the WebSocket if it carries the pairing token
+// The rejection's size is tungstenite's to pick
+#[allow(clippy::result_large_err)]
+fn handshake(stream: TcpStream, token: &str) -> anyhow::Result<Socket> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    stream.set_write_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let check = |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+        let authorization = request
+            .headers()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok());
+        if has_token(request.uri().query(), authorization, token) {
+            return Ok(response);
+        }
+        let mut rejection = ErrorResponse::new(Some("Invalid or missing pairing token".into()));
+        *rejection.status_mut() = StatusCode::UNAUTHORIZED;
+        Err(rejection)
+    };
+    let socket = tungstenite::accept_hdr(stream, check)
+        .map_err(|e| anyhow::anyhow!("WebSocket handshake failed: {}", e))?;
+    socket.get_ref().set_read_timeout(Some(POLL_INTERVAL))?;
+    socket.get_ref().set_write_timeout(None)?;
+    Ok(socket)
+}
+
+fn serve_websocket(
+    mut socket: Socket,
+    addr: IpAddr,
+    paired: &Paired,
+    out: mpsc::Sender<Vec<f32>>,
+    closed: Arc<AtomicBool>,
+) -> anyhow::Result<()> {
+    let mut converter = Converter::new(out);
+    while !closed.load(Ordering::Relaxed) {
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                let format: StreamFormat = serde_json::from_str(&text)?;
+                converter.set_format(format.sample_rate, format.channels);
+            }
+            Ok(Message::Binary(bytes)) => {
+                // The device streams over RTP, and this is only its pairing
+                if !claim(paired, addr, Source::WebSocket) {
+                    continue;
+                }
+                if !converter.push(&bytes, false) {
+                    break;
+                }
+            }
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(WsError::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(WsError::ConnectionClosed) => break,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
+
+fn receive_rtp(
+    socket: UdpSocket,
+    paired: Paired,
+    out: mpsc::Sender<Vec<f32>>,
+    closed: Arc<AtomicBool>,
+) {
+    let mut converter = Converter::new(out);
+    let mut packet = [0u8; 65_536];
+    while !closed.load(Ordering::Relaxed) {
+        let (len, from) = match socket.recv_from(&mut packet) {
+            Ok(received) => received,
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => continue,
+            Err(e) => {
+                log::error!("RTP microphone socket failed: {}", e);
+                return;
+            }
+        };
+        if !claim(&paired, from.ip(), Source::Rtp) {
+            continue;
+        }
+        let Some((payload_type, payload)) = rtp_payload(&packet[..len]) else {
+            continue;
+        };
+        let (sample_rate, channels) = rtp_format(payload_type);
+        converter.set_format(sample_rate, channels);
+        if !converter.push(payload, true) {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rtp_payload_skips_header_fields() {
+        let mut packet = vec![0x80, 96, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0];
+        packet.extend_from_slice(&[1, 2, 3, 4]);
+        assert_eq!(rtp_payload(&packet), Some((96, &[1, 2, 3, 4][..])));
+
+        // One CSRC, a one-word extension and two bytes of padding
+        let mut packet = vec![0xB1, 0x8B, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0];
+        packet.extend_from_slice(&[9, 9, 9, 9]);
+        packet.extend_from_slice(&[0xBE, 0xDE, 0, 1, 7, 7, 7, 7]);
+        packet.extend_from_slice(&[5, 6, 0, 2]);
+        assert_eq!(rtp_payload(&packet), Some((11, &[5, 6][..])));
+
+        assert_eq!(rtp_payload(&[0x40; 16]), None);
+        assert_eq!(rtp_payload(&[0x80, 96]), None);
+    }
+
+    #[test]
+    fn pairing_needs_the_token() {
+        assert!(has_token(Some("token=secret"), None, "secret"));
+        assert!(has_token(Some("rate=48000&token=secret"), None, "secret"));
+        assert!(has_token(None, Some("Bearer secret"), "secret"));
+        assert!(!has_token(Some("token=other"), None, "secret"));
+        assert!(!has_token(Some("token=secre"), None, "secret"));
+        assert!(!has_token(None, Some("secret"), "secret"));
+        assert!(!has_token(None, None, "secret"));
+        // An empty token never pairs anyone
+        assert!(!has_token(Some("token="), None, ""));
+    }
+
+    #[test]
+    fn only_the_paired_device_is_heard_on_one_stream() {
+        let phone: IpAddr = [192, 168, 1, 20].into();
+        let other: IpAddr = [192, 168, 1, 30].into();
+        let paired: Paired = Arc::new(Mutex::new(None));
+        assert!(!claim(&paired, phone, Source::Rtp));
+
+        *paired.lock().unwrap() = Some(Pairing {
+            addr: phone,
+            source: None,
+        });
+        assert!(!claim(&paired, other, Source::Rtp));
+        assert!(claim(&paired, phone, Source::Rtp));
+        assert!(claim(&paired, phone, Source::Rtp));
+        // The WebSocket doesn't interleave with the RTP stream it paired
+        assert!(!claim(&paired, phone, Source::WebSocket));
+    }
+
+    #[test]
+    fn port_is_free_again_once_dropped() {
+        let (tx, _rx) = mpsc::channel();
+        let port = TcpListener::bind(("0.0.0.0", 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let input = NetworkInput::listen(port, "secret".into(), tx.clone()).unwrap();
+        drop(input);
+        assert!(NetworkInput::listen(port, "secret".into(), tx).is_ok());
+    }
+
+    #[test]
+    fn decode_pcm16_mixes_down_to_mono() {
+        let full = i16::MAX.to_le_bytes();
+        assert_eq!(
+            decode_pcm16(&[full[0], full[1], 0, 0], 1, false),
+            vec![1.0, 0.0]
+        );
+        assert_eq!(decode_pcm16(&[full[0], full[1], 0, 0], 2, false), vec![0.5]);
+        let full = i16::MAX.to_be_bytes();
+        assert_eq!(decode_pcm16(&[full[0], full[1], 0], 1, true), vec![1.0]);
+    }
+}
@@ -0,0 +1,69 @@
+//! The last moments of input before a recording starts, so a recording
+//! started by something it heard, like a wake word, doesn't cut off what
+//! was said right after it.
+
+use std::collections::VecDeque;
+
+pub struct PreRoll {
+    samples: VecDeque<f32>,
+    capacity: usize,
+}
+
+impl PreRoll {
+    /// Keeps the last `capacity` samples pushed
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Changes how many samples are kept, dropping the oldest if it shrank
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let overflow = self.samples.len().saturating_sub(capacity);
+        self.samples.drain(..overflow);
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        if self.capacity == 0 {
+            return;
+        }
+        let samples = &samples[samples.len().saturating_sub(self.capacity)..];
+        let overflow = (self.samples.len() + samples.len()).saturating_sub(self.capacity);
+        self.samples.drain(..overflow);
+        self.samples.extend(samples);
+    }
+
+    /// The last `len` samples kept, oldest first, leaving the buffer empty
+    pub fn take(&mut self, len: usize) -> Vec<f32> {
+        let skip = self.samples.len().saturating_sub(len);
+        self.samples.drain(..).skip(skip).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_the_latest_samples() {
+        let mut pre_roll = PreRoll::new(4);
+        pre_roll.push(&[1.0, 2.0, 3.0]);
+        pre_roll.push(&[4.0, 5.0]);
+        assert_eq!(pre_roll.take(4), [2.0, 3.0, 4.0, 5.0]);
+        assert!(pre_roll.take(4).is_empty());
+
+        pre_roll.push(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(pre_roll.take(2), [5.0, 6.0]);
+
+        pre_roll.push(&[1.0, 2.0, 3.0, 4.0]);
+        pre_roll.set_capacity(2);
+        pre_roll.push(&[5.0]);
+        assert_eq!(pre_roll.take(10), [4.0, 5.0]);
+
+        let mut off = PreRoll::new(0);
+        off.push(&[1.0]);
+        assert!(off.take(1).is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+//! Clean-up passes over a finished mono recording, run in whatever order the
+//! preprocessing chain lists them.
+
+use rustfft::{num_complex::Complex32, FftPlanner};
+use std::f32::consts::PI;
+
+use crate::audio::utils::rms;
+
+const FRAME_MS: usize = 30;
+/// RMS level below which a frame counts as silence
+const SILENCE_RMS: f32 = 0.01;
+
+/// Spectral frame length and hop for `reduce_noise`
+const FFT_LEN: usize = 512;
+const HOP: usize = FFT_LEN / 2;
+/// Share of the quietest frames the noise spectrum is estimated from
+const NOISE_FRAMES: f32 = 0.1;
+/// Lowest gain a noisy bin is turned down to, so it doesn't sound hollow
+const NOISE_FLOOR_GAIN: f32 = 0.1;
+
+/// Runs a second-order section with coefficients already divided by `a0`
+fn biquad(samples: &[f32], [b0, b1, b2]: [f32; 3], [a1, a2]: [f32; 2]) -> Vec<f32> {
+    let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+    samples
+        .iter()
+        .map(|&x| {
+            let y = b0 * x + b1 * x1 + b2 * x2 - a1 * y1 - a2 * y2;
+            (x2, x1, y2, y1) = (x1, x, y1, y);
+            y
+        })
+        .collect()
+}
+
+/// Second-order Butterworth high-pass at `cutoff_hz`, to take out rumble and
+/// DC offset below the voice.
+pub fn high_pass(samples: &[f32], sample_rate: usize, cutoff_hz: f32) -> Vec<f32> {
+    let w0 = 2.0 * PI * cutoff_hz / sample_rate as f32;
+    let alpha = w0.sin() / (2.0 * std::f32::consts::FRAC_1_SQRT_2);
+    let cos = w0.cos();
+    let a0 = 1.0 + alpha;
+    let b0 = (1.0 + cos) / 2.0 / a0;
+    biquad(
+        samples,
+        [b0, -(1.0 + cos) / a0, b0],
+        [-2.0 * cos / a0, (1.0 - alpha) / a0],
+    )
+}
+
+/// Second-order Butterworth low-pass at `cutoff_hz`, to take out hiss above
+/// the band a narrowband recording actually carries.
+pub fn low_pass(samples: &[f32], sample_rate: usize, cutoff_hz: f32) -> Vec<f32> {
+    let w0 = 2.0 * PI * cutoff_hz / sample_rate as f32;
+    let alpha = w0.sin() / (2.0 * std::f32::consts::FRAC_1_SQRT_2);
+    let cos = w0.cos();
+    let a0 = 1.0 + alpha;
+    let b0 = (1.0 - cos) / 2.0 / a0;
+    biquad(
+        samples,
+        [b0, (1.0 - cos) / a0, b0],
+        [-2.0 * cos / a0, (1.0 - alpha) / a0],
+    )
+}
+
+/// Spectral subtraction: the noise spectrum is estimated from the quietest
+/// frames of the recording and `strength` times it is taken off every frame.
+pub fn reduce_noise(samples: &[f32], strength: f32) -> Vec<f32> {
+    if samples.len() < FFT_LEN {
+        return samples.to_vec();
+    }
+    let mut planner = FftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(FFT_LEN);
+    let ifft = planner.plan_fft_inverse(FFT_LEN);
+    // Square root of a periodic Hann window on both ends, which adds back up
+    // to one at half overlap
+    let window: Vec<f32> = (0..FFT_LEN)
+        .map(|i| (0.5 - 0.5 * (2.0 * PI * i as f32 / FFT_LEN as f32).cos()).sqrt())
+        .collect();
+
+    // Padding so the first and last samples get full overlap
+    let mut padded = vec![0.0; HOP];
+    padded.extend_from_slice(samples);
+    padded.resize(padded.len().div_ceil(HOP) * HOP + HOP, 0.0);
+    let starts: Vec<usize> = (0..=padded.len() - FFT_LEN).step_by(HOP).collect();
+
+    let spectrum = |start: usize| {
+        let mut buffer: Vec<Complex32> = padded[start..start + FFT_LEN]
+            .iter()
+            .zip(&window)
+            .map(|(s, w)| Complex32::new(s * w, 0.0))
+            .collect();
+        fft.process(&mut buffer);
+        buffer
+    };
+
+    let mut by_level = starts.clone();
+    by_level
+        .sort_by(|a, b| rms(&padded[*a..a + FFT_LEN]).total_cmp(&rms(&padded[*b..b + FFT_LEN])));
+    let quiet = ((starts.len() as f32 * NOISE_FRAMES) as usize).max(1);
+    let mut noise = vec![0.0; FFT_LEN];
+    for &start in &by_level[..quiet] {
+        for (level, bin) in noise.iter_mut().zip(spectrum(start)) {
+            *level += bin.norm() / quiet as f32;
+        }
+    }
+
+    let mut out = vec![0.0; padded.len()];
+    for &start in &starts {
+        let mut buffer = spectrum(start);
+        for (bin, level) in buffer.iter_mut().zip(&noise) {
+            let magnitude = bin.norm();
+            if magnitude > 0.0 {
+                *bin *= (1.0 - strength * level / magnitude).max(NOISE_FLOOR_GAIN);
+            }
+        }
+        ifft.process(&mut buffer);
+        for (i, bin) in buffer.iter().enumerate() {
+            out[start + i] += bin.re * window[i] / FFT_LEN as f32;
+        }
+    }
+    out[HOP..HOP + samples.len()].to_vec()
+}
+
+/// Brings the speech in the recording to `target_rms`, amplifying by at most
+/// `max_gain` and never so much that the loudest sample clips.
+pub fn normalize_level(
+    samples: &[f32],
+    sample_rate: usize,
+    target_rms: f32,
+    max_gain: f32,
+) -> Vec<f32> {
+    let frame_len = (sample_rate * FRAME_MS / 1000).max(1);
+    let voiced: Vec<f32> = samples
+        .chunks(frame_len)
+        .map(rms)
+        .filter(|&level| level >= SILENCE_RMS)
+        .collect();
+    let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    if voiced.is_empty() || peak == 0.0 {
+        return samples.to_vec();
+    }
+    let speech_rms = (voiced.iter().map(|l| l * l).sum::<f32>() / voiced.len() as f32).sqrt();
+    let gain = (target_rms / speech_rms).min(max_gain).min(0.99 / peak);
+    samples.iter().map(|s| s * gain).collect()
+}
+
+/// Cuts silence off both ends of the recording, keeping `keep_ms` of it next
+/// to the speech. A recording without any speech is left alone.
+pub fn trim_silence(samples: &[f32], sample_rate: usize, keep_ms: usize) -> Vec<f32> {
+    let frame_len = (sample_rate * FRAME_MS / 1000).max(1);
+    let loud = |frame: &[f32]| rms(frame) >= SILENCE_RMS;
+    let Some(first) = samples.chunks(frame_len).position(loud) else {
+        return samples.to_vec();
+    };
+    let last = samples.chunks(frame_len).rposition(loud).unwrap_or(first);
+    let keep = sample_rate * keep_ms / 1000;
+    let start = (first * frame_len).saturating_sub(keep);
+    let end = ((last + 1) * frame_len + keep).min(samples.len());
+    samples[start..end].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: usize = 16_000;
+
+    fn tone(ms: usize, amplitude: f32) -> Vec<f32> {
+        tone_at(440.0, ms, amplitude)
+    }
+
+    fn tone_at(hz: f32, ms: usize, amplitude: f32) -> Vec<f32> {
+        (0..RATE * ms / 1000)
+            .map(|i| amplitude * (2.0 * PI * hz * i as f32 / RATE as f32).sin())
+            .collect()
+    }
+
+    /// Deterministic white noise
+    fn noise(ms: usize, amplitude: f32) -> Vec<f32> {
+        let mut state = 1u32;
+        (0..RATE * ms / 1000)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                amplitude * ((state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn high_pass_removes_dc_but_keeps_the_voice() {
+        let filtered = high_pass(&vec![0.5; RATE], RATE, 80.0);
+        assert!(filtered[RATE / 2..].iter().all(|s| s.abs() < 1e-3));
+
+        let voice = tone(1000, 0.5);
+        let filtered = high_pass(&voice, RATE, 80.0);
+        let ratio = rms(&filtered[RATE / 2..]) / rms(&voice[RATE / 2..]);
+        assert!((ratio - 1.0).abs() < 0.05, "ratio {}", ratio);
+    }
+
+    #[test]
+    fn low_pass_removes_hiss_but_keeps_the_voice() {
+        let hiss = tone_at(7000.0, 1000, 0.5);
+        let filtered = low_pass(&hiss, RATE, 3400.0);
+        assert!(rms(&filtered[RATE / 2..]) < rms(&hiss[RATE / 2..]) * 0.25);
+
+        let voice = tone(1000, 0.5);
+        let filtered = low_pass(&voice, RATE, 3400.0);
+        let ratio = rms(&filtered[RATE / 2..]) / rms(&voice[RATE / 2..]);
+        assert!((ratio - 1.0).abs() < 0.05, "ratio {}", ratio);
+    }
+
+    #[test]
+    fn reduce_noise_quiets_noise_and_keeps_length() {
+        let mut samples = noise(2000, 0.05);
+        for (sample, voice) in samples[RATE..].iter_mut().zip(tone(1000, 0.5)) {
+            *sample += voice;
+        }
+        let cleaned = reduce_noise(&samples, 1.5);
+        assert_eq!(cleaned.len(), samples.len());
+        assert!(rms(&cleaned[..RATE]) < rms(&samples[..RATE]) * 0.5);
+        assert!(rms(&cleaned[RATE..]) > rms(&samples[RATE..]) * 0.9);
+    }
+
+    #[test]
+    fn normalize_level_is_capped_by_gain_and_peak() {
+        let quiet = tone(500, 0.02);
+        let louder = normalize_level(&quiet, RATE, 0.1, 10.0);
+        assert!((rms(&louder) - 0.1).abs() < 0.005);
+        assert_eq!(
+            normalize_level(&quiet, RATE, 1.0, 3.0)[400],
+            quiet[400] * 3.0
+        );
+        let peaky = normalize_level(&tone(500, 0.5), RATE, 1.0, 10.0);
+        assert!(peaky.iter().all(|s| s.abs() <= 0.99));
+        assert_eq!(
+            normalize_level(&[0.0; 100], RATE, 0.1, 10.0),
+            vec![0.0; 100]
+        );
+    }
+
+    #[test]
+    fn trim_silence_keeps_some_around_speech() {
+        let mut samples = vec![0.0; RATE * 900 / 1000];
+        samples.extend(tone(600, 0.5));
+        samples.extend(vec![0.0; RATE * 900 / 1000]);
+        let trimmed = trim_silence(&samples, RATE, 120);
+        assert_eq!(trimmed.len(), RATE * (120 + 600 + 120) / 1000);
+        assert_eq!(trim_silence(&[0.0; 800], RATE, 120).len(), 800);
+    }
+}
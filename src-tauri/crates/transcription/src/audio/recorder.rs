@@ -0,0 +1,816 @@
+use std::{
+    io::Error,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use cpal::{
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    Device, Sample, SizedSample,
+};
+
+use crate::{
+    audio::{
+        loopback::{mix_sources, SystemAudio},
+        AudioVisualiser, AutoGain, DeadInputDetector, FrameResampler, NetworkInput,
+        NoiseSuppressor, PreRoll,
+    },
+    constants,
+    vad::{self, NoiseFloor, VadFrame},
+    VoiceActivityDetector,
+};
+
+enum Cmd {
+    /// Starts a recording, with up to this much of the pre-roll in front
+    Start(Duration),
+    Stop(mpsc::Sender<Vec<f32>>),
+    Drain(mpsc::Sender<Vec<f32>>),
+    Peek(usize, mpsc::Sender<Vec<f32>>),
+    Shutdown,
+}
+
+type DeadInputCallback = (Duration, Arc<dyn Fn() + Send + Sync + 'static>);
+type IdleFrameCallback = Arc<dyn Fn(&[f32]) + Send + Sync + 'static>;
+
+/// Length of the frames the consumer works in
+const FRAME_MS: u64 = 30;
+
+/// Frame counts for the current recording, used for a live speech/silence ratio.
+#[derive(Default)]
+struct SpeechStats {
+    speech_frames: AtomicUsize,
+    total_frames: AtomicUsize,
+    /// Count of frames when speech was last heard
+    last_speech_frame: AtomicUsize,
+}
+
+/// Adjustments to the input that can change while the stream is open
+struct InputTuning {
+    /// Bits of the `f32` gain applied before the VAD
+    gain: AtomicU32,
+    /// Keep every frame instead of only speech, to measure the room
+    raw: AtomicBool,
+    /// Bits of the `f32` VAD threshold set for the microphone
+    vad_threshold: AtomicU32,
+    /// Raise the VAD threshold with the noise floor heard between recordings
+    adaptive_vad: AtomicBool,
+    /// Milliseconds of input kept between recordings for the next one
+    pre_roll_ms: AtomicU32,
+    /// Bits of the `f32` noise suppression strength, 0 when it's off
+    noise_suppression: AtomicU32,
+    /// Level the input out before the VAD
+    auto_gain: AtomicBool,
+}
+
+pub struct AudioRecorder {
+    device: Option<Device>,
+    cmd_tx: Option<mpsc::Sender<Cmd>>,
+    worker_handle: Option<std::thread::JoinHandle<()>>,
+    vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
+    level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    speech_stats: Arc<SpeechStats>,
+    input_gate: Option<Arc<AtomicUsize>>,
+    dead_input_cb: Option<DeadInputCallback>,
+    idle_frame_cb: Option<IdleFrameCallback>,
+    tuning: Arc<InputTuning>,
+}
+
+impl AudioRecorder {
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(AudioRecorder {
+            device: None,
+            cmd_tx: None,
+            worker_handle: None,
+            vad: None,
+            level_cb: None,
+            speech_stats: Arc::new(SpeechStats::default()),
+            input_gate: None,
+            dead_input_cb: None,
+            idle_frame_cb: None,
+            tuning: Arc::new(InputTuning {
+                gain: AtomicU32::new(1.0f32.to_bits()),
+                raw: AtomicBool::new(false),
+                vad_threshold: AtomicU32::new(0.3f32.to_bits()),
+                adaptive_vad: AtomicBool::new(false),
+                pre_roll_ms: AtomicU32::new(0),
+                noise_suppression: AtomicU32::new(0.0f32.to_bits()),
+                auto_gain: AtomicBool::new(false),
+            }),
+        })
+    }
+
+    pub fn with_vad(mut self, vad: Box<dyn VoiceActivityDetector>) -> Self {
+        self.vad = Some(Arc::new(Mutex::new(vad)));
+        self
+    }
+
+    pub fn with_level_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(Vec<f32>) + Send + Sync + 'static,
+    {
+        self.level_cb = Some(Arc::new(cb));
+        self
+    }
+
+    /// Input is dropped while `gate` is non-zero, e.g. while a feedback sound
+    /// plays, so our own output never ends up in the recording.
+    pub fn with_input_gate(mut self, gate: Arc<AtomicUsize>) -> Self {
+        self.input_gate = Some(gate);
+        self
+    }
+
+    /// Calls `cb` once per recording when the input has delivered nothing
+    /// but silence or a flatline for `timeout`.
+    pub fn with_dead_input_callback<F>(mut self, timeout: Duration, cb: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.dead_input_cb = Some((timeout, Arc::new(cb)));
+        self
+    }
+
+    /// Calls `cb` with every 16 kHz frame heard between recordings, e.g. to
+    /// listen for a wake word. Frames dropped by the input gate are skipped.
+    pub fn with_idle_frame_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(&[f32]) + Send + Sync + 'static,
+    {
+        self.idle_frame_cb = Some(Arc::new(cb));
+        self
+    }
+
+    /// Scales the input by `gain` before the VAD sees it.
+    pub fn set_input_gain(&self, gain: f32) {
+        self.tuning.gain.store(gain.to_bits(), Ordering::Relaxed);
+    }
+
+    /// With `raw` on, recordings keep silence too instead of only what the
+    /// VAD calls speech.
+    pub fn set_raw_capture(&self, raw: bool) {
+        self.tuning.raw.store(raw, Ordering::Relaxed);
+    }
+
+    pub fn set_vad_threshold(&self, threshold: f32) {
+        self.tuning
+            .vad_threshold
+            .store(threshold.to_bits(), Ordering::Relaxed);
+        if let Some(vad) = &self.vad {
+            vad.lock().unwrap().set_threshold(threshold);
+        }
+    }
+
+    /// With `adaptive` on, each recording starts with the VAD threshold
+    /// raised to suit the noise heard since the last one. That needs the
+    /// stream open between recordings, without it the set threshold is used.
+    pub fn set_adaptive_vad(&self, adaptive: bool) {
+        self.tuning.adaptive_vad.store(adaptive, Ordering::Relaxed);
+    }
+
+    /// Keeps the last `length` of input between recordings, for
+    /// `start_with_pre_roll`. Only works while the stream stays open between
+    /// recordings.
+    pub fn set_pre_roll(&self, length: Duration) {
+        self.tuning
+            .pre_roll_ms
+            .store(length.as_millis() as u32, Ordering::Relaxed);
+    }
+
+    /// Denoises the input before the VAD sees it, mixing `strength` of the
+    /// denoised signal with the original. 0 turns it off.
+    pub fn set_noise_suppression(&self, strength: f32) {
+        self.tuning
+            .noise_suppression
+            .store(strength.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// With `on`, the input is levelled before the VAD sees it, after noise
+    /// suppression.
+    pub fn set_auto_gain(&self, on: bool) {
+        self.tuning.auto_gain.store(on, Ordering::Relaxed);
+    }
+
+    pub fn open(&mut self, device: Option<Device>) -> Result<(), Box<dyn std::error::Error>> {
+        if self.worker_handle.is_some() {
+            return Ok(()); // already open
+        }
+
+        let (sample_tx, sample_rx) = mpsc::channel::<Vec<f32>>();
+        let (cmd_tx, cmd_rx) = mpsc::channel::<Cmd>();
+
+        let host = crate::get_cpal_host();
+        let device = match device {
+            Some(dev) => dev,
+            None => host
+                .default_input_device()
+                .ok_or_else(|| Error::new(std::io::ErrorKind::NotFound, "No input device found"))?,
+        };
+
+        let thread_device = device.clone();
+        let vad = self.vad.clone();
+        // Move the optional level callback into the worker thread
+        let level_cb = self.level_cb.clone();
+        let speech_stats = self.speech_stats.clone();
+        let input_gate = self.input_gate.clone();
+        let dead_input_cb = self.dead_input_cb.clone();
+        let idle_frame_cb = self.idle_frame_cb.clone();
+        let tuning = self.tuning.clone();
+
+        let worker = std::thread::spawn(move || {
+            let config = AudioRecorder::get_preferred_config(&thread_device)
+                .expect("failed to fetch preferred config");
+
+            let sample_rate = config.sample_rate().0;
+            let channels = config.channels() as usize;
+
+            log::info!(
+                "Using device: {:?}\nSample rate: {}\nChannels: {}\nFormat: {:?}",
+                thread_device.name(),
+                sample_rate,
+                channels,
+                config.sample_format()
+            );
+
+            let stream = build_input_stream(&thread_device, &config, sample_tx)
+                .expect("failed to build input stream");
+
+            stream.play().expect("failed to start stream");
+
+            // keep the stream alive while we process samples
+            run_consumer(
+                sample_rate,
+                vad,
+                sample_rx,
+                cmd_rx,
+                level_cb,
+                speech_stats,
+                input_gate,
+                dead_input_cb,
+                idle_frame_cb,
+                tuning,
+            );
+            // stream is dropped here, after run_consumer returns
+        });
+
+        self.device = Some(device);
+        self.cmd_tx = Some(cmd_tx);
+        self.worker_handle = Some(worker);
+
+        Ok(())
+    }
+
+    /// Like `open`, but records audio streamed to `port` over the network
+    /// instead of from a sound card, by the device paired with `token`.
+    pub fn open_network(
+        &mut self,
+        port: u16,
+        token: String,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.worker_handle.is_some() {
+            return Ok(()); // already open
+        }
+
+        let (sample_tx, sample_rx) = mpsc::channel::<Vec<f32>>();
+        let (cmd_tx, cmd_rx) = mpsc::channel::<Cmd>();
+        let input = NetworkInput::listen(port, token, sample_tx)?;
+
+        let vad = self.vad.clone();
+        let level_cb = self.level_cb.clone();
+        let speech_stats = self.speech_stats.clone();
+        let input_gate = self.input_gate.clone();
+        let dead_input_cb = self.dead_input_cb.clone();
+        let idle_frame_cb = self.idle_frame_cb.clone();
+        let tuning = self.tuning.clone();
+
+        let worker = std::thread::spawn(move || {
+            run_consumer(
+                constants::WHISPER_SAMPLE_RATE,
+                vad,
+                sample_rx,
+                cmd_rx,
+                level_cb,
+                speech_stats,
+                input_gate,
+                dead_input_cb,
+                idle_frame_cb,
+                tuning,
+            );
+            // stop listening once the consumer is done
+            drop(input);
+        });
+
+        self.cmd_tx = Some(cmd_tx);
+        self.worker_handle = Some(worker);
+
+        Ok(())
+    }
+
+    /// Like `open`, but records what the computer is playing, mixed with
+    /// `microphone` if one is given, so calls and videos can be transcribed.
+    /// Stretches where nothing plays are silence, so the dead input callback
+    /// isn't used.
+    pub fn open_system_audio(
+        &mut self,
+        microphone: Option<Device>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.worker_handle.is_some() {
+            return Ok(()); // already open
+        }
+
+        let source = SystemAudio::find()?;
+        let (sample_tx, sample_rx) = mpsc::channel::<Vec<f32>>();
+        let (cmd_tx, cmd_rx) = mpsc::channel::<Cmd>();
+
+        let vad = self.vad.clone();
+        let level_cb = self.level_cb.clone();
+        let speech_stats = self.speech_stats.clone();
+        let input_gate = self.input_gate.clone();
+        let idle_frame_cb = self.idle_frame_cb.clone();
+        let tuning = self.tuning.clone();
+
+        let worker = std::thread::spawn(move || {
+            let (system_tx, system_rx) = mpsc::channel::<Vec<f32>>();
+            let system = match source.build_stream(system_tx) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::error!("Failed to capture system audio: {}", e);
+                    return;
+                }
+            };
+            let system_rate = source.config.sample_rate().0;
+
+            let mic = microphone.and_then(|device| {
+                let (mic_tx, mic_rx) = mpsc::channel::<Vec<f32>>();
+                let config = AudioRecorder::get_preferred_config(&device).ok()?;
+                match build_input_stream(&device, &config, mic_tx) {
+                    Ok(stream) => Some((stream, mic_rx, config.sample_rate().0)),
+                    Err(e) => {
+                        log::error!("Failed to open the microphone to mix in: {}", e);
+                        None
+                    }
+                }
+            });
+
+            for stream in std::iter::once(&system).chain(mic.as_ref().map(|(s, _, _)| s)) {
+                if let Err(e) = stream.play() {
+                    log::error!("Failed to start stream: {}", e);
+                    return;
+                }
+            }
+
+            let (consumer_rx, in_rate, mixer) = match mic {
+                Some((_, mic_rx, mic_rate)) => {
+                    let (mixed_tx, mixed_rx) = mpsc::channel::<Vec<f32>>();
+                    let mixer = std::thread::spawn(move || {
+                        mix_sources([(system_rx, system_rate), (mic_rx, mic_rate)], mixed_tx)
+                    });
+                    (mixed_rx, constants::WHISPER_SAMPLE_RATE, Some(mixer))
+                }
+                None => (system_rx, system_rate, None),
+            };
+
+            run_consumer(
+                in_rate,
+                vad,
+                consumer_rx,
+                cmd_rx,
+                level_cb,
+                speech_stats,
+                input_gate,
+                None,
+                idle_frame_cb,
+                tuning,
+            );
+            // streams are dropped here, which ends the mixer
+            drop(system);
+            if let Some(mixer) = mixer {
+                let _ = mixer.join();
+            }
+        });
+
+        self.cmd_tx = Some(cmd_tx);
+        self.worker_handle = Some(worker);
+
+        Ok(())
+    }
+
+    pub fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Start(Duration::ZERO))?;
+        }
+        Ok(())
+    }
+
+    /// Starts a recording that begins with up to `length` of the pre-roll,
+    /// the input heard just before it.
+    pub fn start_with_pre_roll(&self, length: Duration) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Start(length))?;
+        }
+        Ok(())
+    }
+
+    pub fn stop(&self) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
+        let (resp_tx, resp_rx) = mpsc::channel();
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Stop(resp_tx))?;
+        }
+        Ok(resp_rx.recv()?) // wait for the samples
+    }
+
+    /// Takes the samples captured so far without ending the recording.
+    pub fn drain(&self) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
+        let (resp_tx, resp_rx) = mpsc::channel();
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Drain(resp_tx))?;
+        }
+        Ok(resp_rx.recv()?)
+    }
+
+    /// Copies the samples captured from position `from` on, leaving them in
+    /// the recording.
+    pub fn peek(&self, from: usize) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
+        let (resp_tx, resp_rx) = mpsc::channel();
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Peek(from, resp_tx))?;
+        }
+        Ok(resp_rx.recv()?)
+    }
+
+    /// Fraction of frames in the current recording that the VAD classed as
+    /// speech. Without a VAD every frame counts as speech.
+    pub fn speech_ratio(&self) -> f32 {
+        let total = self.speech_stats.total_frames.load(Ordering::Relaxed);
+        if total == 0 {
+            return 0.0;
+        }
+        self.speech_stats.speech_frames.load(Ordering::Relaxed) as f32 / total as f32
+    }
+
+    /// How long the current recording has gone without speech
+    pub fn silence(&self) -> Duration {
+        let total = self.speech_stats.total_frames.load(Ordering::Relaxed);
+        let last = self.speech_stats.last_speech_frame.load(Ordering::Relaxed);
+        Duration::from_millis(total.saturating_sub(last) as u64 * FRAME_MS)
+    }
+
+    pub fn close(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tx) = self.cmd_tx.take() {
+            let _ = tx.send(Cmd::Shutdown);
+        }
+        if let Some(h) = self.worker_handle.take() {
+            let _ = h.join();
+        }
+        self.device = None;
+        Ok(())
+    }
+
+    fn build_stream<T>(
+        device: &cpal::Device,
+        config: &cpal::SupportedStreamConfig,
+        sample_tx: mpsc::Sender<Vec<f32>>,
+        channels: usize,
+    ) -> Result<cpal::Stream, cpal::BuildStreamError>
+    where
+        T: Sample + SizedSample + Send + 'static,
+        f32: cpal::FromSample<T>,
+    {
+        let mut output_buffer = Vec::new();
+
+        let stream_cb = move |data: &[T], _: &cpal::InputCallbackInfo| {
+            output_buffer.clear();
+
+            if channels == 1 {
+                // Direct conversion without intermediate Vec
+                output_buffer.extend(data.iter().map(|&sample| sample.to_sample::<f32>()));
+            } else {
+                // Convert to mono directly
+                let frame_count = data.len() / channels;
+                output_buffer.reserve(frame_count);
+
+                for frame in data.chunks_exact(channels) {
+                    let mono_sample = frame
+                        .iter()
+                        .map(|&sample| sample.to_sample::<f32>())
+                        .sum::<f32>()
+                        / channels as f32;
+                    output_buffer.push(mono_sample);
+                }
+            }
+
+            if sample_tx.send(output_buffer.clone()).is_err() {
+                log::error!("Failed to send samples");
+            }
+        };
+
+        device.build_input_stream(
+            &config.clone().into(),
+            stream_cb,
+            |err| log::error!("Stream error: {}", err),
+            None,
+        )
+    }
+
+    fn get_preferred_config(
+        device: &cpal::Device,
+    ) -> Result<cpal::SupportedStreamConfig, Box<dyn std::error::Error>> {
+        let supported_configs = device.supported_input_configs()?;
+        let mut best_config: Option<cpal::SupportedStreamConfigRange> = None;
+
+        // Try to find a config that supports 16kHz, prioritizing better formats
+        for config_range in supported_configs {
+            if config_range.min_sample_rate().0 <= constants::WHISPER_SAMPLE_RATE
+                && config_range.max_sample_rate().0 >= constants::WHISPER_SAMPLE_RATE
+            {
+                match best_config {
+                    None => best_config = Some(config_range),
+                    Some(ref current) => {
+                        // Prioritize F32 > I16 > I32 > others
+                        let score = |fmt: cpal::SampleFormat| match fmt {
+                            cpal::SampleFormat::F32 => 4,
+                            cpal::SampleFormat::I16 => 3,
+                            cpal::SampleFormat::I32 => 2,
+                            _ => 1,
+                        };
+
+                        if score(config_range.sample_format()) > score(current.sample_format()) {
+                            best_config = Some(config_range);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(config) = best_config {
+            return Ok(config.with_sample_rate(cpal::SampleRate(constants::WHISPER_SAMPLE_RATE)));
+        }
+
+        // If no config supports 16kHz, fall back to default
+        Ok(device.default_input_config()?)
+    }
+}
+
+/// An input stream from `device` that sends mono samples to `sample_tx`
+pub(super) fn build_input_stream(
+    device: &Device,
+    config: &cpal::SupportedStreamConfig,
+    sample_tx: mpsc::Sender<Vec<f32>>,
+) -> Result<cpal::Stream, cpal::BuildStreamError> {
+    let channels = config.channels() as usize;
+    match config.sample_format() {
+        cpal::SampleFormat::U8 => {
+            AudioRecorder::build_stream::<u8>(device, config, sample_tx, channels)
+        }
+        cpal::SampleFormat::I8 => {
+            AudioRecorder::build_stream::<i8>(device, config, sample_tx, channels)
+        }
+        cpal::SampleFormat::I16 => {
+            AudioRecorder::build_stream::<i16>(device, config, sample_tx, channels)
+        }
+        cpal::SampleFormat::I32 => {
+            AudioRecorder::build_stream::<i32>(device, config, sample_tx, channels)
+        }
+        cpal::SampleFormat::F32 => {
+            AudioRecorder::build_stream::<f32>(device, config, sample_tx, channels)
+        }
+        _ => Err(cpal::BuildStreamError::StreamConfigNotSupported),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_consumer(
+    in_sample_rate: u32,
+    vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
+    sample_rx: mpsc::Receiver<Vec<f32>>,
+    cmd_rx: mpsc::Receiver<Cmd>,
+    level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    speech_stats: Arc<SpeechStats>,
+    input_gate: Option<Arc<AtomicUsize>>,
+    dead_input_cb: Option<DeadInputCallback>,
+    idle_frame_cb: Option<IdleFrameCallback>,
+    tuning: Arc<InputTuning>,
+) {
+    let mut frame_resampler = FrameResampler::new(
+        in_sample_rate as usize,
+        constants::WHISPER_SAMPLE_RATE as usize,
+        Duration::from_millis(FRAME_MS),
+    );
+    let samples_in =
+        |length: Duration| (length.as_secs_f32() * constants::WHISPER_SAMPLE_RATE as f32) as usize;
+    let mut pre_roll = PreRoll::new(0);
+
+    let mut processed_samples = Vec::<f32>::new();
+    let mut recording = false;
+
+    // ---------- spectrum visualisation setup ---------------------------- //
+    const BUCKETS: usize = 16;
+    const WINDOW_SIZE: usize = 512;
+    let mut visualizer = AudioVisualiser::new(
+        in_sample_rate,
+        WINDOW_SIZE,
+        BUCKETS,
+        400.0,  // vocal_min_hz
+        4000.0, // vocal_max_hz
+    );
+
+    let mut dead_input =
+        dead_input_cb.map(|(timeout, cb)| (DeadInputDetector::new(in_sample_rate, timeout), cb));
+    let mut noise_floor = NoiseFloor::new();
+    let mut suppressor = NoiseSuppressor::new();
+    let mut agc = AutoGain::default();
+
+    #[allow(clippy::too_many_arguments)]
+    fn handle_frame(
+        samples: &[f32],
+        recording: bool,
+        vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
+        out_buf: &mut Vec<f32>,
+        stats: &SpeechStats,
+        gate: &Option<Arc<AtomicUsize>>,
+        tuning: &InputTuning,
+        suppressor: &mut NoiseSuppressor,
+        agc: &mut AutoGain,
+    ) {
+        if !recording {
+            return;
+        }
+        if gate.as_ref().is_some_and(|g| g.load(Ordering::Relaxed) > 0) {
+            return;
+        }
+
+        let gain = f32::from_bits(tuning.gain.load(Ordering::Relaxed));
+        let amplified: Vec<f32>;
+        let samples = if gain != 1.0 {
+            amplified = samples
+                .iter()
+                .map(|s| (s * gain).clamp(-1.0, 1.0))
+                .collect();
+            &amplified[..]
+        } else {
+            samples
+        };
+
+        let strength = f32::from_bits(tuning.noise_suppression.load(Ordering::Relaxed));
+        let auto_gain = tuning.auto_gain.load(Ordering::Relaxed);
+        let cleaned: Vec<f32>;
+        let samples = if strength > 0.0 || auto_gain {
+            let mut frame = samples.to_vec();
+            if strength > 0.0 {
+                suppressor.process(&mut frame, strength);
+            }
+            if auto_gain {
+                agc.process(&mut frame);
+            }
+            cleaned = frame;
+            &cleaned[..]
+        } else {
+            samples
+        };
+
+        stats.total_frames.fetch_add(1, Ordering::Relaxed);
+        let vad = vad.as_ref().filter(|_| !tuning.raw.load(Ordering::Relaxed));
+        if let Some(vad_arc) = vad {
+            let mut det = vad_arc.lock().unwrap();
+            match det.push_frame(samples).unwrap_or(VadFrame::Speech(samples)) {
+                VadFrame::Speech(buf) => {
+                    stats.speech_frames.fetch_add(1, Ordering::Relaxed);
+                    stats.last_speech_frame.store(
+                        stats.total_frames.load(Ordering::Relaxed),
+                        Ordering::Relaxed,
+                    );
+                    out_buf.extend_from_slice(buf)
+                }
+                VadFrame::Noise => {}
+            }
+        } else {
+            stats.speech_frames.fetch_add(1, Ordering::Relaxed);
+            stats.last_speech_frame.store(
+                stats.total_frames.load(Ordering::Relaxed),
+                Ordering::Relaxed,
+            );
+            out_buf.extend_from_slice(samples);
+        }
+    }
+
+    loop {
+        let raw = match sample_rx.recv() {
+            Ok(s) => s,
+            Err(_) => break, // stream closed
+        };
+
+        // ---------- spectrum processing ---------------------------------- //
+        if let Some(buckets) = visualizer.feed(&raw) {
+            if let Some(cb) = &level_cb {
+                cb(buckets);
+            }
+        }
+
+        if let (true, Some((detector, cb))) = (recording, &mut dead_input) {
+            if detector.push(&raw) {
+                log::warn!("Recording input is delivering only silence or a flatline");
+                cb();
+            }
+        }
+
+        // ---------- existing pipeline ------------------------------------ //
+        frame_resampler.push(&raw, &mut |frame: &[f32]| {
+            if !recording {
+                noise_floor.push(frame);
+                let gated = input_gate
+                    .as_ref()
+                    .is_some_and(|g| g.load(Ordering::Relaxed) > 0);
+                if !gated {
+                    pre_roll.set_capacity(samples_in(Duration::from_millis(
+                        tuning.pre_roll_ms.load(Ordering::Relaxed) as u64,
+                    )));
+                    pre_roll.push(frame);
+                    if let Some(cb) = &idle_frame_cb {
+                        cb(frame);
+                    }
+                }
+            }
+            handle_frame(
+                frame,
+                recording,
+                &vad,
+                &mut processed_samples,
+                &speech_stats,
+                &input_gate,
+                &tuning,
+                &mut suppressor,
+                &mut agc,
+            )
+        });
+
+        // non-blocking check for a command
+        while let Ok(cmd) = cmd_rx.try_recv() {
+            match cmd {
+                Cmd::Start(pre_roll_length) => {
+                    processed_samples.clear();
+                    processed_samples.extend(pre_roll.take(samples_in(pre_roll_length)));
+                    speech_stats.speech_frames.store(0, Ordering::Relaxed);
+                    speech_stats.total_frames.store(0, Ordering::Relaxed);
+                    speech_stats.last_speech_frame.store(0, Ordering::Relaxed);
+                    recording = true;
+                    visualizer.reset(); // Reset visualization buffer
+
+                    // Keeps the last recording's tail from leaking into this
+                    // one. The gain carries over, the speaker likely hasn't moved.
+                    suppressor = NoiseSuppressor::new();
+                    if let Some((detector, _)) = &mut dead_input {
+                        detector.reset();
+                    }
+                    if let Some(v) = &vad {
+                        let mut v = v.lock().unwrap();
+                        v.reset();
+                        let threshold =
+                            f32::from_bits(tuning.vad_threshold.load(Ordering::Relaxed));
+                        if tuning.adaptive_vad.load(Ordering::Relaxed) {
+                            let adapted = noise_floor.adapt_threshold(threshold);
+                            if let Some(level) = noise_floor.level_db() {
+                                log::debug!(
+                                    "Noise floor {:.0} dBFS, VAD threshold {:.2}",
+                                    level,
+                                    adapted
+                                );
+                            }
+                            v.set_threshold(adapted);
+                        } else {
+                            v.set_threshold(threshold);
+                        }
+                    }
+                }
+                Cmd::Stop(reply_tx) => {
+                    recording = false;
+
+                    frame_resampler.finish(&mut |frame: &[f32]| {
+                        // we still want to process the last few frames
+                        handle_frame(
+                            frame,
+                            true,
+                            &vad,
+                            &mut processed_samples,
+                            &speech_stats,
+                            &input_gate,
+                            &tuning,
+                            &mut suppressor,
+                            &mut agc,
+                        )
+                    });
+
+                    let _ = reply_tx.send(std::mem::take(&mut processed_samples));
+                }
+                Cmd::Drain(reply_tx) => {
+                    let _ = reply_tx.send(std::mem::take(&mut processed_samples));
+                }
+                Cmd::Peek(from, reply_tx) => {
+                    let from = from.min(processed_samples.len());
+                    let _ = reply_tx.send(processed_samples[from..].to_vec());
+                }
+                Cmd::Shutdown => return,
+            }
+        }
+    }
+}
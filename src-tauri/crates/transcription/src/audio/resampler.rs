@@ -97,3 +97,37 @@ impl FrameResampler {
         }
     }
 }
+
+/// Resamples a whole recording at once. Unlike `FrameResampler`, the filter
+/// delay is taken off the start and the tail is flushed out, so the output
+/// lines up with the input and is exactly as long as it should be.
+pub fn resample(samples: &[f32], in_hz: usize, out_hz: usize) -> Vec<f32> {
+    if in_hz == out_hz || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let expected = (samples.len() * out_hz + in_hz / 2) / in_hz;
+    let mut resampler = FftFixedIn::<f32>::new(in_hz, out_hz, RESAMPLER_CHUNK_SIZE, 1, 1)
+        .expect("Failed to create resampler");
+    let delay = resampler.output_delay();
+
+    let mut out = Vec::with_capacity(expected + delay + RESAMPLER_CHUNK_SIZE * out_hz / in_hz);
+    let mut chunks = samples.chunks_exact(RESAMPLER_CHUNK_SIZE);
+    for chunk in &mut chunks {
+        if let Ok(resampled) = resampler.process(&[chunk], None) {
+            out.extend_from_slice(&resampled[0]);
+        }
+    }
+    // The last partial chunk, then silence until the delayed samples are out
+    let mut tail = chunks.remainder().to_vec();
+    while out.len() < delay + expected {
+        tail.resize(RESAMPLER_CHUNK_SIZE, 0.0);
+        match resampler.process(&[&tail[..]], None) {
+            Ok(resampled) => out.extend_from_slice(&resampled[0]),
+            Err(_) => break,
+        }
+        tail.clear();
+    }
+    out.drain(..delay.min(out.len()));
+    out.truncate(expected);
+    out
+}
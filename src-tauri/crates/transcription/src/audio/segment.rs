@@ -0,0 +1,238 @@
+use std::ops::Range;
+
+const FRAME_MS: usize = 30;
+/// RMS level below which a frame is treated as a pause between words
+const SILENCE_RMS: f32 = 0.01;
+/// A pause has to last at least this long to be used as a cut point
+const MIN_PAUSE_MS: usize = 150;
+
+/// Splits mono audio at pauses, returning sample ranges that cover the whole
+/// input in order.
+///
+/// Segments shorter than `min_segment_ms` are merged into their neighbour, and
+/// a segment is force-split once it grows past `max_segment_ms` so a single
+/// long run of speech never produces an unbounded chunk.
+pub fn split_on_silence(
+    samples: &[f32],
+    sample_rate: usize,
+    min_segment_ms: usize,
+    max_segment_ms: usize,
+) -> Vec<Range<usize>> {
+    let frame_len = (sample_rate * FRAME_MS / 1000).max(1);
+    let min_pause_frames = MIN_PAUSE_MS.div_ceil(FRAME_MS);
+    let min_segment = sample_rate * min_segment_ms / 1000;
+    let max_segment = (sample_rate * max_segment_ms / 1000).max(frame_len);
+
+    // Candidate cut points at the centre of every sufficiently long pause
+    let mut cuts = Vec::new();
+    let mut quiet_run = 0usize;
+    for (i, frame) in samples.chunks(frame_len).enumerate() {
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+        if rms < SILENCE_RMS {
+            quiet_run += 1;
+            continue;
+        }
+        if quiet_run >= min_pause_frames {
+            let pause_start = (i - quiet_run) * frame_len;
+            cuts.push(pause_start + quiet_run * frame_len / 2);
+        }
+        quiet_run = 0;
+    }
+
+    let mut segments = Vec::new();
+    let mut start = 0usize;
+    for cut in cuts {
+        while cut - start > max_segment {
+            segments.push(start..start + max_segment);
+            start += max_segment;
+        }
+        if cut - start >= min_segment {
+            segments.push(start..cut);
+            start = cut;
+        }
+    }
+    while samples.len() - start > max_segment {
+        segments.push(start..start + max_segment);
+        start += max_segment;
+    }
+    if start < samples.len() {
+        match segments.last_mut() {
+            // Fold a short tail into the previous segment
+            Some(last) if samples.len() - start < min_segment => last.end = samples.len(),
+            _ => segments.push(start..samples.len()),
+        }
+    }
+
+    segments
+}
+
+/// Moves the start of every segment that begins mid-speech, where
+/// `split_on_silence` had to force a cut, back by `overlap_ms`, so a word cut
+/// in half is whole in the later segment. Segments starting at a pause are
+/// left as they are.
+pub fn overlap_forced_cuts(
+    samples: &[f32],
+    sample_rate: usize,
+    segments: &[Range<usize>],
+    overlap_ms: usize,
+) -> Vec<Range<usize>> {
+    let frame_len = (sample_rate * FRAME_MS / 1000).max(1);
+    let overlap = sample_rate * overlap_ms / 1000;
+    segments
+        .iter()
+        .map(|range| {
+            let before = &samples[range.start.saturating_sub(frame_len)..range.start];
+            let rms =
+                (before.iter().map(|s| s * s).sum::<f32>() / before.len().max(1) as f32).sqrt();
+            if rms >= SILENCE_RMS {
+                range.start.saturating_sub(overlap)..range.end
+            } else {
+                range.clone()
+            }
+        })
+        .collect()
+}
+
+/// Where `compress_silence` took audio out, to map positions in the
+/// compressed audio back to the original recording.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SilenceMap {
+    /// Position in the compressed audio and the number of samples removed
+    /// there, in order
+    cuts: Vec<(usize, usize)>,
+}
+
+impl SilenceMap {
+    /// Samples removed in total
+    pub fn removed(&self) -> usize {
+        self.cuts.iter().map(|(_, len)| len).sum()
+    }
+
+    /// Position in the original audio of `position` in the compressed audio
+    pub fn to_original(&self, position: usize) -> usize {
+        position
+            + self
+                .cuts
+                .iter()
+                .take_while(|(at, _)| *at <= position)
+                .map(|(_, len)| len)
+                .sum::<usize>()
+    }
+}
+
+/// Shortens every stretch of silence longer than `min_silence_ms` to
+/// `keep_ms`, half of it on either side, so sparse recordings spend less
+/// time in inference while their pauses still read as pauses.
+pub fn compress_silence(
+    samples: &[f32],
+    sample_rate: usize,
+    min_silence_ms: usize,
+    keep_ms: usize,
+) -> (Vec<f32>, SilenceMap) {
+    let frame_len = (sample_rate * FRAME_MS / 1000).max(1);
+    let min_silence = sample_rate * min_silence_ms / 1000;
+    let keep_half = sample_rate * keep_ms / 2000;
+
+    // Quiet stretches long enough to shorten, as sample ranges
+    let mut stretches = Vec::new();
+    let mut quiet_start = None;
+    for (i, frame) in samples.chunks(frame_len).enumerate() {
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+        if rms < SILENCE_RMS {
+            quiet_start.get_or_insert(i * frame_len);
+        } else if let Some(start) = quiet_start.take() {
+            stretches.push(start..i * frame_len);
+        }
+    }
+    if let Some(start) = quiet_start {
+        stretches.push(start..samples.len());
+    }
+
+    let mut out = Vec::with_capacity(samples.len());
+    let mut map = SilenceMap::default();
+    let mut copied = 0;
+    for stretch in stretches {
+        if stretch.len() < min_silence.max(2 * keep_half + 1) {
+            continue;
+        }
+        out.extend_from_slice(&samples[copied..stretch.start + keep_half]);
+        copied = stretch.end - keep_half;
+        map.cuts
+            .push((out.len(), copied - (stretch.start + keep_half)));
+    }
+    out.extend_from_slice(&samples[copied..]);
+    (out, map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(ms: usize) -> Vec<f32> {
+        (0..16 * ms).map(|i| (i as f32 * 0.1).sin() * 0.5).collect()
+    }
+
+    fn silence(ms: usize) -> Vec<f32> {
+        vec![0.0; 16 * ms]
+    }
+
+    #[test]
+    fn test_split_on_pause() {
+        let audio = [tone(2000), silence(300), tone(2000)].concat();
+        let segments = split_on_silence(&audio, 16000, 1000, 30000);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].start, 0);
+        assert_eq!(segments[1].end, audio.len());
+        assert_eq!(segments[0].end, segments[1].start);
+    }
+
+    #[test]
+    fn test_short_segments_are_merged() {
+        let audio = [tone(2000), silence(300), tone(300)].concat();
+        let segments = split_on_silence(&audio, 16000, 1000, 30000);
+        assert_eq!(segments, vec![0..audio.len()]);
+    }
+
+    #[test]
+    fn test_long_speech_is_force_split() {
+        let audio = tone(5000);
+        let segments = split_on_silence(&audio, 16000, 1000, 2000);
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[2].end, audio.len());
+    }
+
+    #[test]
+    fn test_only_forced_cuts_overlap() {
+        let audio = [tone(2000), silence(300), tone(5000)].concat();
+        let segments = split_on_silence(&audio, 16000, 1000, 3000);
+        assert_eq!(segments.len(), 3);
+        let overlapped = overlap_forced_cuts(&audio, 16000, &segments, 500);
+        assert_eq!(overlapped[0], segments[0]);
+        assert_eq!(overlapped[1], segments[1]);
+        assert_eq!(overlapped[2].start, segments[2].start - 16 * 500);
+        assert_eq!(overlapped[2].end, segments[2].end);
+    }
+
+    #[test]
+    fn test_long_silence_is_shortened() {
+        // Whole 30ms frames, so the silence starts exactly where it's cut
+        let audio = [tone(900), silence(6000), tone(900), silence(300)].concat();
+        let (compressed, map) = compress_silence(&audio, 16000, 3000, 1000);
+        assert_eq!(compressed.len(), 16 * 3100);
+        assert_eq!(map.removed(), 16 * 5000);
+
+        // Speech after the cut maps back to where it was recorded
+        assert_eq!(map.to_original(0), 0);
+        assert_eq!(map.to_original(16 * 1399), 16 * 1399);
+        assert_eq!(map.to_original(16 * 1400), 16 * 6400);
+        assert_eq!(map.to_original(16 * 1900), 16 * 6900);
+    }
+
+    #[test]
+    fn test_short_pauses_are_kept() {
+        let audio = [tone(1000), silence(2000), tone(1000)].concat();
+        let (compressed, map) = compress_silence(&audio, 16000, 3000, 1000);
+        assert_eq!(compressed, audio);
+        assert_eq!(map, SilenceMap::default());
+    }
+}
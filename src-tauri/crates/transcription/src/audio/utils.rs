@@ -0,0 +1,269 @@
+use anyhow::{anyhow, Result};
+use flacenc::component::BitRepr;
+use flacenc::error::Verify;
+use hound::{WavReader, WavSpec, WavWriter};
+use log::debug;
+use std::fmt;
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::{audio::resample, constants};
+
+/// Why a buffer couldn't be converted to the 16kHz mono the engines expect
+#[derive(Debug, PartialEq)]
+pub enum AudioFormatError {
+    NoChannels,
+    NoSampleRate,
+    /// The sample count isn't a whole number of frames
+    PartialFrame {
+        samples: usize,
+        channels: u16,
+    },
+    /// The sample at this index is NaN or infinite
+    NonFinite {
+        index: usize,
+    },
+}
+
+impl fmt::Display for AudioFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoChannels => write!(f, "audio has no channels"),
+            Self::NoSampleRate => write!(f, "audio has a sample rate of 0"),
+            Self::PartialFrame { samples, channels } => write!(
+                f,
+                "{} samples can't be split into frames of {} channels",
+                samples, channels
+            ),
+            Self::NonFinite { index } => write!(f, "sample {} is not a finite number", index),
+        }
+    }
+}
+
+impl std::error::Error for AudioFormatError {}
+
+/// Root mean square level of `frame`, 0 for an empty one
+pub fn rms(frame: &[f32]) -> f32 {
+    (frame.iter().map(|s| s * s).sum::<f32>() / frame.len().max(1) as f32).sqrt()
+}
+
+/// Averages each frame of `channels` interleaved samples down to one. A
+/// partial frame at the end is dropped.
+pub fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    interleaved
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Converts interleaved samples in any layout to 16kHz mono, downmixing and
+/// resampling as needed. Buffers that are already 16kHz mono are returned as is.
+pub fn to_whisper_format(
+    samples: &[f32],
+    sample_rate: u32,
+    channels: u16,
+) -> Result<Vec<f32>, AudioFormatError> {
+    if channels == 0 {
+        return Err(AudioFormatError::NoChannels);
+    }
+    if sample_rate == 0 {
+        return Err(AudioFormatError::NoSampleRate);
+    }
+    if samples.len() % channels as usize != 0 {
+        return Err(AudioFormatError::PartialFrame {
+            samples: samples.len(),
+            channels,
+        });
+    }
+    if let Some(index) = samples.iter().position(|s| !s.is_finite()) {
+        return Err(AudioFormatError::NonFinite { index });
+    }
+
+    let mono = downmix(samples, channels as usize);
+
+    if sample_rate == constants::WHISPER_SAMPLE_RATE {
+        return Ok(mono);
+    }
+
+    Ok(resample(
+        &mono,
+        sample_rate as usize,
+        constants::WHISPER_SAMPLE_RATE as usize,
+    ))
+}
+
+/// Save audio samples as a WAV file
+pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Result<()> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: 16000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = WavWriter::create(file_path.as_ref(), spec)?;
+
+    // Convert f32 samples to i16 for WAV
+    for sample in samples {
+        let sample_i16 = (sample * i16::MAX as f32) as i16;
+        writer.write_sample(sample_i16)?;
+    }
+
+    writer.finalize()?;
+    debug!("Saved WAV file: {:?}", file_path.as_ref());
+    Ok(())
+}
+
+fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+}
+
+/// Converts normalized samples to 16-bit little-endian PCM, clipping anything
+/// out of range. This is what the remote engines are sent.
+pub fn to_pcm16(samples: &[f32]) -> Vec<u8> {
+    samples
+        .iter()
+        .flat_map(|s| to_i16(*s).to_le_bytes())
+        .collect()
+}
+
+/// Encodes 16kHz mono samples as a 16-bit WAV file in memory
+pub fn encode_wav(samples: &[f32]) -> Result<Vec<u8>> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: constants::WHISPER_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut out = Cursor::new(Vec::with_capacity(samples.len() * 2 + 44));
+    let mut writer = WavWriter::new(&mut out, spec)?;
+    for sample in samples {
+        writer.write_sample(to_i16(*sample))?;
+    }
+    writer.finalize()?;
+    Ok(out.into_inner())
+}
+
+/// Encodes 16kHz mono samples as a 16-bit FLAC file in memory, typically
+/// around half the size of the WAV for speech.
+pub fn encode_flac(samples: &[f32]) -> Result<Vec<u8>> {
+    let config = flacenc::config::Encoder::default()
+        .into_verified()
+        .map_err(|(_, e)| anyhow!("Invalid FLAC encoder config: {:?}", e))?;
+    let pcm: Vec<i32> = samples.iter().map(|s| to_i16(*s) as i32).collect();
+    let source = flacenc::source::MemSource::from_samples(
+        &pcm,
+        1,
+        16,
+        constants::WHISPER_SAMPLE_RATE as usize,
+    );
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+        .map_err(|e| anyhow!("FLAC encoding failed: {:?}", e))?;
+    let mut sink = flacenc::bitsink::ByteSink::new();
+    stream
+        .write(&mut sink)
+        .map_err(|e| anyhow!("Failed to write FLAC stream: {:?}", e))?;
+    Ok(sink.as_slice().to_vec())
+}
+
+/// Load a WAV file as 16kHz mono f32 samples, downmixing and resampling as needed
+pub fn read_wav_file<P: AsRef<Path>>(file_path: P) -> Result<Vec<f32>> {
+    read_wav_file_with_rate(file_path).map(|(samples, _)| samples)
+}
+
+/// Like `read_wav_file`, but also returns the sample rate the file was
+/// recorded at, so narrowband recordings can be told apart once upsampled.
+pub fn read_wav_file_with_rate<P: AsRef<Path>>(file_path: P) -> Result<(Vec<f32>, u32)> {
+    let mut reader = WavReader::open(file_path.as_ref())?;
+    let spec = reader.spec();
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|v| v as f32 / scale))
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    let out = to_whisper_format(&interleaved, spec.sample_rate, spec.channels)?;
+
+    debug!(
+        "Loaded WAV file {:?} ({} samples at 16kHz, recorded at {}Hz)",
+        file_path.as_ref(),
+        out.len(),
+        spec.sample_rate
+    );
+    Ok((out, spec.sample_rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downmixes_and_resamples() {
+        let stereo: Vec<f32> = (0..48000).flat_map(|_| [0.5, -0.5]).collect();
+        let out = to_whisper_format(&stereo, 48000, 2).unwrap();
+        assert!((out.len() as i64 - 16000).abs() <= 1024);
+        assert!(out.iter().all(|s| s.abs() < 1e-3));
+
+        // Telephone audio comes out exactly twice as long and in step
+        let tone = |rate: f32, len: usize| -> Vec<f32> {
+            (0..len)
+                .map(|i| (2.0 * std::f32::consts::PI * 440.0 * i as f32 / rate).sin() * 0.5)
+                .collect()
+        };
+        let out = to_whisper_format(&tone(8000.0, 8000), 8000, 1).unwrap();
+        assert_eq!(out.len(), 16000);
+        let expected = tone(16000.0, 16000);
+        assert!(out[1000..15000]
+            .iter()
+            .zip(&expected[1000..15000])
+            .all(|(a, b)| (a - b).abs() < 0.05));
+
+        let mono = vec![0.25; 1600];
+        assert_eq!(to_whisper_format(&mono, 16000, 1).unwrap(), mono);
+    }
+
+    #[test]
+    fn converts_samples_to_pcm16() {
+        assert_eq!(
+            to_pcm16(&[0.0, 1.0, -1.0, 2.0]),
+            vec![0, 0, 0xff, 0x7f, 0x01, 0x80, 0xff, 0x7f]
+        );
+    }
+
+    #[test]
+    fn encodes_wav_and_flac_headers() {
+        let samples: Vec<f32> = (0..16000).map(|i| (i as f32 / 40.0).sin() * 0.5).collect();
+        let wav = encode_wav(&samples).unwrap();
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(wav.len(), 44 + samples.len() * 2);
+
+        let flac = encode_flac(&samples).unwrap();
+        assert_eq!(&flac[..4], b"fLaC");
+        assert!(flac.len() < wav.len());
+    }
+
+    #[test]
+    fn rejects_malformed_buffers() {
+        assert_eq!(
+            to_whisper_format(&[0.0; 3], 16000, 2),
+            Err(AudioFormatError::PartialFrame {
+                samples: 3,
+                channels: 2
+            })
+        );
+        assert_eq!(
+            to_whisper_format(&[0.0], 16000, 0),
+            Err(AudioFormatError::NoChannels)
+        );
+        assert_eq!(
+            to_whisper_format(&[0.0, f32::NAN], 16000, 1),
+            Err(AudioFormatError::NonFinite { index: 1 })
+        );
+    }
+}
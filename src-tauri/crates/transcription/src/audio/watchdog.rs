@@ -0,0 +1,86 @@
+//! Notices when a recording input delivers nothing usable, e.g. a hardware
+//! mute switch, a dead USB interface or a virtual device with no source.
+//! Those produce digital silence or a constant value rather than the low
+//! noise floor of a quiet room.
+
+use std::time::Duration;
+
+/// Blocks whose samples all lie within this range of each other count as
+/// dead. Even a quiet room moves a microphone by more than this.
+const FLATLINE_RANGE: f32 = 1.0e-4;
+
+pub struct DeadInputDetector {
+    sample_rate: u32,
+    timeout: Duration,
+    dead_samples: u64,
+    reported: bool,
+}
+
+impl DeadInputDetector {
+    pub fn new(sample_rate: u32, timeout: Duration) -> Self {
+        Self {
+            sample_rate,
+            timeout,
+            dead_samples: 0,
+            reported: false,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.dead_samples = 0;
+        self.reported = false;
+    }
+
+    /// Feeds a block of raw input. Returns true once per recording, when the
+    /// input has been dead for the whole timeout.
+    pub fn push(&mut self, samples: &[f32]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        let (min, max) = samples.iter().fold((f32::MAX, f32::MIN), |(min, max), &s| {
+            (min.min(s), max.max(s))
+        });
+        if max - min > FLATLINE_RANGE {
+            self.dead_samples = 0;
+            return false;
+        }
+
+        self.dead_samples += samples.len() as u64;
+        let dead_for = Duration::from_secs_f64(self.dead_samples as f64 / self.sample_rate as f64);
+        if self.reported || dead_for < self.timeout {
+            return false;
+        }
+        self.reported = true;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_once_after_the_timeout() {
+        let mut detector = DeadInputDetector::new(1000, Duration::from_secs(2));
+        let silence = vec![0.0; 500];
+        assert!(!detector.push(&silence));
+        assert!(!detector.push(&silence));
+        assert!(!detector.push(&silence));
+        assert!(detector.push(&silence));
+        assert!(!detector.push(&silence));
+
+        detector.reset();
+        assert!(!detector.push(&silence));
+    }
+
+    #[test]
+    fn sound_restarts_the_countdown() {
+        let mut detector = DeadInputDetector::new(1000, Duration::from_secs(1));
+        let flatline = vec![0.25; 600];
+        let noise: Vec<f32> = (0..600).map(|i| (i % 7) as f32 * 0.001).collect();
+        assert!(!detector.push(&flatline));
+        assert!(!detector.push(&noise));
+        assert!(!detector.push(&flatline));
+        assert!(detector.push(&flatline));
+    }
+}
@@ -0,0 +1,509 @@
+//! Client for Deepgram's hosted speech-to-text. Finished recordings go to the
+//! prerecorded endpoint as FLAC or WAV, and `live_request` opens the live
+//! endpoint for streaming raw PCM as it's captured. Both take the same 16 kHz
+//! audio the local engines get, so nothing needs resampling.
+
+use crate::audio::{encode_flac, encode_wav};
+use crate::constants;
+use crate::engine::{self, Engine, Request};
+use crate::timing::{self, TimedWord};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use log::{info, warn};
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::time::Duration;
+use tungstenite::client::IntoClientRequest;
+use tungstenite::handshake::client::Request as LiveRequest;
+use tungstenite::http::HeaderValue;
+
+const LISTEN_URL: &str = "https://api.deepgram.com/v1/listen";
+const LIVE_URL: &str = "wss://api.deepgram.com/v1/listen";
+/// Where both endpoints are served, for checking it can be reached
+pub const HOST: &str = "api.deepgram.com:443";
+pub const DEFAULT_MODEL: &str = "nova-3";
+/// Wait before the first retry of a failed request, doubled for each one
+/// after it
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+/// Longest wait between retries, even if the server asks for more
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// How finished recordings are encoded for upload. Compressed uploads fall
+/// back to WAV if the server rejects them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Codec {
+    Wav,
+    #[default]
+    Flac,
+}
+
+/// Account, model and request options
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub api_key: String,
+    /// A model such as `nova-3`, or empty for `DEFAULT_MODEL`
+    pub model: String,
+    pub codec: Codec,
+    /// How long to wait for a connection to the API
+    pub connect_timeout: Duration,
+    /// How long to wait for the API to send more of its response
+    pub read_timeout: Duration,
+    /// Times a request that hit a rate limit, server error or timeout is sent
+    /// again
+    pub max_retries: u32,
+    /// Label who said what
+    pub diarize: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            model: DEFAULT_MODEL.to_string(),
+            codec: Codec::default(),
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(120),
+            max_retries: 3,
+            diarize: false,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Word {
+    word: String,
+    start: f32,
+    end: f32,
+    /// Only there with `smart_format` or `punctuate`
+    punctuated_word: Option<String>,
+    /// Only there with `diarize`
+    speaker: Option<u32>,
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+struct Alternative {
+    #[serde(default)]
+    transcript: String,
+    #[serde(default)]
+    words: Vec<Word>,
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+pub struct Channel {
+    #[serde(default)]
+    alternatives: Vec<Alternative>,
+}
+
+impl Channel {
+    pub fn transcript(&self) -> &str {
+        self.alternatives
+            .first()
+            .map(|alternative| alternative.transcript.trim())
+            .unwrap_or("")
+    }
+
+    pub fn words(&self) -> Vec<TimedWord> {
+        self.alternatives
+            .first()
+            .map(|alternative| {
+                alternative
+                    .words
+                    .iter()
+                    .map(|word| TimedWord {
+                        start: word.start,
+                        end: word.end,
+                        text: word.punctuated_word.as_ref().unwrap_or(&word.word).clone(),
+                        speaker: word.speaker,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// A prerecorded transcript and the timing of its words
+#[derive(Debug, Default)]
+pub struct Transcript {
+    pub text: String,
+    pub words: Vec<TimedWord>,
+}
+
+#[derive(Deserialize, Debug)]
+struct PrerecordedResults {
+    channels: Vec<Channel>,
+}
+
+#[derive(Deserialize, Debug)]
+struct PrerecordedResponse {
+    results: PrerecordedResults,
+}
+
+/// A message from the live endpoint
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum LiveEvent {
+    Results {
+        #[serde(default)]
+        is_final: bool,
+        channel: Channel,
+    },
+    #[serde(other)]
+    Other,
+}
+
+pub fn model(config: &Config) -> &str {
+    match config.model.trim() {
+        "" => DEFAULT_MODEL,
+        model => model,
+    }
+}
+
+/// Query parameters for either endpoint. `vocabulary` is sent as key terms,
+/// or as keywords for models before Nova 3.
+fn params(
+    config: &Config,
+    language: Option<String>,
+    vocabulary: &[String],
+    live: bool,
+) -> Vec<(&'static str, String)> {
+    let model = model(config);
+    let nova3 = model.starts_with("nova-3");
+    let mut params = vec![
+        ("model", model.to_string()),
+        ("smart_format", "true".to_string()),
+    ];
+    // Uploads are in a container Deepgram reads the format from
+    if live {
+        params.extend([
+            ("encoding", "linear16".to_string()),
+            ("sample_rate", constants::WHISPER_SAMPLE_RATE.to_string()),
+            ("channels", "1".to_string()),
+        ]);
+    }
+    match language {
+        Some(language) => params.push(("language", language)),
+        // The live endpoint can't detect the language, but Nova 3 can
+        // transcribe a mix of them
+        None if live && nova3 => params.push(("language", "multi".to_string())),
+        None if live => {}
+        None => params.push(("detect_language", "true".to_string())),
+    }
+    if live {
+        params.push(("interim_results", "true".to_string()));
+    } else if config.diarize {
+        params.push(("diarize", "true".to_string()));
+    }
+    let vocabulary_key = if nova3 { "keyterm" } else { "keywords" };
+    params.extend(
+        vocabulary
+            .iter()
+            .map(|word| (vocabulary_key, word.trim().to_string()))
+            .filter(|(_, word)| !word.is_empty()),
+    );
+    params
+}
+
+fn api_key(config: &Config) -> Result<&str> {
+    match config.api_key.trim() {
+        "" => Err(anyhow!("No Deepgram API key configured")),
+        key => Ok(key),
+    }
+}
+
+/// Whether a request that got `status` may go through if sent again
+fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Wait before retry number `retry`, counting from 0: what a `Retry-After`
+/// header asks for, in seconds or as a date, or exponential backoff
+fn retry_delay(retry: u32, retry_after: Option<&str>) -> Duration {
+    let asked = retry_after.map(str::trim).and_then(|value| {
+        value
+            .parse::<u64>()
+            .map(Duration::from_secs)
+            .ok()
+            .or_else(|| {
+                DateTime::parse_from_rfc2822(value).ok().map(|at| {
+                    (at.with_timezone(&Utc) - Utc::now())
+                        .to_std()
+                        .unwrap_or_default()
+                })
+            })
+    });
+    asked
+        .unwrap_or_else(|| RETRY_BASE_DELAY.saturating_mul(2u32.saturating_pow(retry)))
+        .min(RETRY_MAX_DELAY)
+}
+
+/// Sends `body` to the prerecorded endpoint, retrying up to
+/// `config.max_retries` times on rate limits, server errors and timeouts.
+/// `on_retry` is told the retry number, the most there will be and the wait.
+async fn upload(
+    config: &Config,
+    body: Vec<u8>,
+    content_type: &str,
+    params: &[(&str, String)],
+    on_retry: &(dyn Fn(u32, u32, Duration) + Sync),
+) -> Result<reqwest::Response> {
+    let client = reqwest::Client::builder()
+        .connect_timeout(config.connect_timeout)
+        .read_timeout(config.read_timeout)
+        .build()?;
+    let authorization = format!("Token {}", api_key(config)?);
+    let mut retry = 0;
+    loop {
+        let result = client
+            .post(LISTEN_URL)
+            .query(params)
+            .header("Authorization", &authorization)
+            .header("Content-Type", content_type)
+            .body(body.clone())
+            .send()
+            .await;
+        let transient = match &result {
+            Ok(response) if is_transient(response.status()) => {
+                let retry_after = response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok());
+                Some((
+                    retry_delay(retry, retry_after),
+                    response.status().to_string(),
+                ))
+            }
+            Err(e) if e.is_timeout() || e.is_connect() => {
+                Some((retry_delay(retry, None), e.to_string()))
+            }
+            _ => None,
+        };
+        let Some((delay, reason)) = transient.filter(|_| retry < config.max_retries) else {
+            // Keeps the reqwest error as the cause, to tell an unreachable
+            // server apart
+            return result.map_err(|e| {
+                let message = format!("Deepgram request failed: {}", e);
+                anyhow::Error::new(e).context(message)
+            });
+        };
+        retry += 1;
+        warn!(
+            "Deepgram request failed ({}), retry {} of {} in {:.1}s",
+            reason,
+            retry,
+            config.max_retries,
+            delay.as_secs_f32()
+        );
+        on_retry(retry, config.max_retries, delay);
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Whether a failed upload looks like the server not taking the format
+fn rejects_format(status: StatusCode) -> bool {
+    status == StatusCode::BAD_REQUEST || status == StatusCode::UNSUPPORTED_MEDIA_TYPE
+}
+
+async fn transcript(response: reqwest::Response) -> Result<Transcript> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Deepgram returned {}: {}", status, body.trim()));
+    }
+    let response: PrerecordedResponse = response.json().await?;
+    Ok(response
+        .results
+        .channels
+        .first()
+        .map(|channel| Transcript {
+            text: channel.transcript().to_string(),
+            words: channel.words(),
+        })
+        .unwrap_or_default())
+}
+
+/// Transcribes 16 kHz mono `samples` with the prerecorded API. A compressed
+/// upload the server rejects is sent again as WAV, and transient failures
+/// are retried as `upload` does.
+pub async fn transcribe(
+    config: &Config,
+    samples: &[f32],
+    language: Option<String>,
+    vocabulary: &[String],
+    on_retry: &(dyn Fn(u32, u32, Duration) + Sync),
+) -> Result<Transcript> {
+    let params = params(config, language, vocabulary, false);
+    let (body, content_type) = match config.codec {
+        Codec::Flac => (encode_flac(samples)?, "audio/flac"),
+        Codec::Wav => (encode_wav(samples)?, "audio/wav"),
+    };
+    info!(
+        "Sending {:.1}s of audio to Deepgram ({}, {} KB as {})",
+        samples.len() as f32 / constants::WHISPER_SAMPLE_RATE as f32,
+        model(config),
+        body.len() / 1024,
+        content_type
+    );
+    let mut response = upload(config, body, content_type, &params, on_retry).await?;
+    if config.codec != Codec::Wav && rejects_format(response.status()) {
+        warn!(
+            "Deepgram rejected the {} upload ({}), sending WAV instead",
+            content_type,
+            response.status()
+        );
+        response = upload(config, encode_wav(samples)?, "audio/wav", &params, on_retry).await?;
+    }
+    transcript(response).await
+}
+
+/// A request for the live endpoint, to open with `tungstenite::connect`.
+/// It takes raw 16-bit PCM and sends `LiveEvent`s back, interim results
+/// included.
+pub fn live_request(
+    config: &Config,
+    language: Option<String>,
+    vocabulary: &[String],
+) -> Result<LiveRequest> {
+    let url =
+        reqwest::Url::parse_with_params(LIVE_URL, params(config, language, vocabulary, true))?;
+    let mut request = url.as_str().into_client_request()?;
+    request.headers_mut().insert(
+        "Authorization",
+        HeaderValue::from_str(&format!("Token {}", api_key(config)?))?,
+    );
+    Ok(request)
+}
+
+/// Deepgram behind the `Engine` interface. With `diarize` on, transcripts
+/// with several speakers come back as "Speaker 1: ..." paragraphs.
+pub struct Client<'r> {
+    pub config: Config,
+    /// Told about each retry of a transient failure, with the retry, the most
+    /// there will be and how long until it's sent
+    pub on_retry: &'r (dyn Fn(u32, u32, Duration) + Sync),
+}
+
+impl Engine for Client<'_> {
+    fn name(&self) -> &str {
+        "Deepgram"
+    }
+
+    fn transcribe<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<engine::Transcript>> {
+        async move {
+            let transcript = transcribe(
+                &self.config,
+                request.samples,
+                request.language.map(str::to_string),
+                request.vocabulary,
+                self.on_retry,
+            )
+            .await?;
+            let segments = timing::segments_from_words(transcript.words);
+            let text = timing::speaker_text(&segments).unwrap_or(transcript.text);
+            Ok(engine::Transcript { text, segments })
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(model: &str) -> Config {
+        Config {
+            model: model.to_string(),
+            ..Config::default()
+        }
+    }
+
+    fn value<'a>(params: &'a [(&str, String)], key: &str) -> Vec<&'a str> {
+        params
+            .iter()
+            .filter(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn maps_language_and_vocabulary_to_params() {
+        let words = vec!["Handy".to_string(), " ".to_string()];
+        let prerecorded = params(&config(""), None, &words, false);
+        assert_eq!(value(&prerecorded, "model"), ["nova-3"]);
+        assert_eq!(value(&prerecorded, "detect_language"), ["true"]);
+        assert_eq!(value(&prerecorded, "keyterm"), ["Handy"]);
+        assert!(value(&prerecorded, "encoding").is_empty());
+        assert!(value(&prerecorded, "diarize").is_empty());
+        let diarized = Config {
+            diarize: true,
+            ..config("")
+        };
+        assert_eq!(
+            value(&params(&diarized, None, &[], false), "diarize"),
+            ["true"]
+        );
+        assert!(value(&params(&diarized, None, &[], true), "diarize").is_empty());
+
+        let live = params(&config("nova-3"), None, &[], true);
+        assert_eq!(value(&live, "language"), ["multi"]);
+        assert_eq!(value(&live, "encoding"), ["linear16"]);
+        let live = params(&config("nova-2"), Some("de".to_string()), &words, true);
+        assert_eq!(value(&live, "language"), ["de"]);
+        assert_eq!(value(&live, "keywords"), ["Handy"]);
+        assert!(value(&params(&config("nova-2"), None, &[], true), "language").is_empty());
+    }
+
+    #[test]
+    fn parses_results() {
+        let prerecorded = r#"{"metadata":{},"results":{"channels":[{"alternatives":[{"transcript":"Hello there.","confidence":0.99,"words":[{"word":"hello","start":0.1,"end":0.4,"punctuated_word":"Hello"},{"word":"there","start":0.5,"end":0.8,"speaker":1}]}]}]}}"#;
+        let response: PrerecordedResponse = serde_json::from_str(prerecorded).unwrap();
+        let channel = &response.results.channels[0];
+        assert_eq!(channel.transcript(), "Hello there.");
+        let words = channel.words();
+        assert_eq!(words[0].text, "Hello");
+        assert_eq!(words[1].text, "there");
+        assert_eq!((words[1].start, words[1].end), (0.5, 0.8));
+        assert_eq!((words[0].speaker, words[1].speaker), (None, Some(1)));
+
+        let live = r#"{"type":"Results","is_final":true,"speech_final":true,"channel":{"alternatives":[{"transcript":"Hi"}]}}"#;
+        match serde_json::from_str(live).unwrap() {
+            LiveEvent::Results { is_final, channel } => {
+                assert!(is_final);
+                assert_eq!(channel.transcript(), "Hi");
+            }
+            LiveEvent::Other => panic!("expected results"),
+        }
+        let other = r#"{"type":"Metadata","request_id":"r"}"#;
+        assert_eq!(
+            serde_json::from_str::<LiveEvent>(other).unwrap(),
+            LiveEvent::Other
+        );
+    }
+
+    #[test]
+    fn retries_back_off_unless_the_server_says_when() {
+        assert_eq!(retry_delay(0, None), Duration::from_secs(1));
+        assert_eq!(retry_delay(2, None), Duration::from_secs(4));
+        assert_eq!(retry_delay(20, None), RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(0, Some(" 7 ")), Duration::from_secs(7));
+        assert_eq!(retry_delay(0, Some("3600")), RETRY_MAX_DELAY);
+        assert_eq!(
+            retry_delay(0, Some("Wed, 21 Oct 2015 07:28:00 GMT")),
+            Duration::ZERO
+        );
+        assert_eq!(retry_delay(1, Some("soon")), Duration::from_secs(2));
+
+        assert!(is_transient(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient(StatusCode::BAD_GATEWAY));
+        assert!(!is_transient(StatusCode::UNAUTHORIZED));
+    }
+}
@@ -0,0 +1,52 @@
+//! One interface over every engine, for embedding the transcription stack
+//! without caring where the speech is recognized.
+
+use crate::timing::TimedSegment;
+use anyhow::Result;
+use futures_util::future::BoxFuture;
+
+/// A recording and what's known about it
+#[derive(Clone, Copy, Debug)]
+pub struct Request<'a> {
+    /// 16 kHz mono audio
+    pub samples: &'a [f32],
+    /// A language tag such as `de` or `zh-Hans`, or `None` to detect it
+    pub language: Option<&'a str>,
+    /// Words to recognize more readily, for engines that take a hint
+    pub vocabulary: &'a [String],
+    /// What the recording is about, for engines that take a prompt
+    pub prompt: Option<&'a str>,
+}
+
+impl<'a> Request<'a> {
+    pub fn new(samples: &'a [f32]) -> Self {
+        Self {
+            samples,
+            language: None,
+            vocabulary: &[],
+            prompt: None,
+        }
+    }
+
+    /// The language without its script or region, `zh` for `zh-Hans`, for
+    /// engines that only take ISO 639-1 codes
+    pub fn base_language(&self) -> Option<&'a str> {
+        self.language
+            .and_then(|language| language.split('-').next())
+            .filter(|language| !language.is_empty())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Transcript {
+    pub text: String,
+    /// When each part was spoken, empty if the engine didn't say
+    pub segments: Vec<TimedSegment>,
+}
+
+pub trait Engine: Send + Sync {
+    /// Shown in logs
+    fn name(&self) -> &str;
+
+    fn transcribe<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<Transcript>>;
+}
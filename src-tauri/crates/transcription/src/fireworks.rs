@@ -0,0 +1,231 @@
+//! Client for Fireworks AI's hosted Whisper models. The request is shaped
+//! like OpenAI's transcription endpoint, but each model is served from a
+//! host of its own, and word timings only come back in `verbose_json` with
+//! `timestamp_granularities` asking for them.
+
+use crate::audio::encode_wav;
+use crate::constants;
+use crate::engine::{self, Engine, Request};
+use crate::text::vocabulary_prompt;
+use crate::timing::{self, TimedWord};
+use anyhow::{anyhow, Context, Result};
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use log::info;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use std::time::Duration;
+
+pub const DEFAULT_MODEL: &str = "whisper-v3";
+/// The faster model, served from its own host
+const TURBO_MODEL: &str = "whisper-v3-turbo";
+const PROD_HOST: &str = "audio-prod.api.fireworks.ai";
+const TURBO_HOST: &str = "audio-turbo.api.fireworks.ai";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const READ_TIMEOUT: Duration = Duration::from_secs(120);
+/// Whisper reads no more of its prompt than this
+const PROMPT_CHARS: usize = 800;
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    pub api_key: String,
+    /// `whisper-v3` or `whisper-v3-turbo`, empty for `DEFAULT_MODEL`
+    pub model: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Word {
+    word: String,
+    start: f32,
+    end: f32,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Response {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    words: Vec<Word>,
+}
+
+pub fn model(config: &Config) -> &str {
+    match config.model.trim() {
+        "" => DEFAULT_MODEL,
+        model => model,
+    }
+}
+
+fn host_name(model: &str) -> &'static str {
+    if model == TURBO_MODEL {
+        TURBO_HOST
+    } else {
+        PROD_HOST
+    }
+}
+
+/// Where `model` is served, as `host:port` for checking it can be reached
+pub fn host(model: &str) -> String {
+    format!("{}:443", host_name(model))
+}
+
+fn url(model: &str) -> String {
+    format!("https://{}/v1/audio/transcriptions", host_name(model))
+}
+
+fn api_key(config: &Config) -> Result<&str> {
+    match config.api_key.trim() {
+        "" => Err(anyhow!("No Fireworks AI API key configured")),
+        key => Ok(key),
+    }
+}
+
+/// The form fields besides the audio. Whisper takes no vocabulary, so the
+/// words go at the end of the prompt, where they sway the spelling most.
+fn fields(config: &Config, request: &Request) -> Vec<(&'static str, String)> {
+    let mut fields = vec![
+        ("model", model(config).to_string()),
+        ("response_format", "verbose_json".to_string()),
+        ("timestamp_granularities", "word".to_string()),
+    ];
+    if let Some(language) = request.base_language() {
+        fields.push(("language", language.to_string()));
+    }
+    let prompt: Vec<String> = request
+        .prompt
+        .map(str::to_string)
+        .into_iter()
+        .chain(vocabulary_prompt(request.vocabulary, PROMPT_CHARS))
+        .collect();
+    if !prompt.is_empty() {
+        fields.push(("prompt", prompt.join(" ")));
+    }
+    fields
+}
+
+/// Transcribes 16 kHz mono `request.samples`
+pub async fn transcribe(config: &Config, request: Request<'_>) -> Result<engine::Transcript> {
+    let client = reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .read_timeout(READ_TIMEOUT)
+        .build()?;
+    let wav = encode_wav(request.samples)?;
+    info!(
+        "Sending {:.1}s of audio to Fireworks AI ({}, {} KB)",
+        request.samples.len() as f32 / constants::WHISPER_SAMPLE_RATE as f32,
+        model(config),
+        wav.len() / 1024
+    );
+
+    let mut form = Form::new().part(
+        "file",
+        Part::bytes(wav)
+            .file_name("recording.wav")
+            .mime_str("audio/wav")?,
+    );
+    for (name, value) in fields(config, &request) {
+        form = form.text(name, value);
+    }
+    let response = client
+        .post(url(model(config)))
+        .bearer_auth(api_key(config)?)
+        .multipart(form)
+        .send()
+        .await
+        .context("Fireworks AI request failed")?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Fireworks AI returned {}: {}", status, body.trim()));
+    }
+    let response: Response = response.json().await?;
+    Ok(engine::Transcript {
+        segments: timing::segments_from_words(response.words()),
+        text: response.text.trim().to_string(),
+    })
+}
+
+impl Response {
+    fn words(&self) -> Vec<TimedWord> {
+        self.words
+            .iter()
+            .map(|word| TimedWord {
+                start: word.start,
+                end: word.end,
+                text: word.word.trim().to_string(),
+                speaker: None,
+            })
+            .collect()
+    }
+}
+
+/// Fireworks AI behind the `Engine` interface
+pub struct Client {
+    pub config: Config,
+}
+
+impl Engine for Client {
+    fn name(&self) -> &str {
+        "Fireworks AI"
+    }
+
+    fn transcribe<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<engine::Transcript>> {
+        transcribe(&self.config, request).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value<'a>(fields: &'a [(&str, String)], key: &str) -> Option<&'a str> {
+        fields
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn sends_each_model_to_its_host() {
+        let turbo = Config {
+            model: "whisper-v3-turbo".to_string(),
+            ..Config::default()
+        };
+        assert_eq!(
+            url(model(&turbo)),
+            "https://audio-turbo.api.fireworks.ai/v1/audio/transcriptions"
+        );
+        assert_eq!(
+            url(model(&Config::default())),
+            "https://audio-prod.api.fireworks.ai/v1/audio/transcriptions"
+        );
+        assert_eq!(host(model(&turbo)), "audio-turbo.api.fireworks.ai:443");
+    }
+
+    #[test]
+    fn puts_vocabulary_after_the_prompt() {
+        let words = vec!["Handy".to_string()];
+        let request = Request {
+            language: Some("pt-BR"),
+            vocabulary: &words,
+            prompt: Some("Notes about the app."),
+            ..Request::new(&[])
+        };
+        let sent = fields(&Config::default(), &request);
+        assert_eq!(value(&sent, "model"), Some("whisper-v3"));
+        assert_eq!(value(&sent, "language"), Some("pt"));
+        assert_eq!(value(&sent, "prompt"), Some("Notes about the app. Handy."));
+        let sent = fields(&Config::default(), &Request::new(&[]));
+        assert_eq!(value(&sent, "language"), None);
+        assert_eq!(value(&sent, "prompt"), None);
+    }
+
+    #[test]
+    fn parses_verbose_json() {
+        let body = r#"{"task":"transcribe","language":"english","duration":1.2,"text":" Hello there.","words":[{"word":"Hello","start":0.1,"end":0.4},{"word":" there.","start":0.5,"end":0.8}],"segments":[]}"#;
+        let response: Response = serde_json::from_str(body).unwrap();
+        assert_eq!(response.text.trim(), "Hello there.");
+        let words = response.words();
+        assert_eq!(words[1].text, "there.");
+        assert_eq!((words[1].start, words[1].end), (0.5, 0.8));
+    }
+}
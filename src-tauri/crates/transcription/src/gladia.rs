@@ -0,0 +1,272 @@
+//! Client for Gladia's hosted speech-to-text. Gladia doesn't take audio in
+//! the transcription request: the recording is uploaded first, a job is
+//! started on the URL Gladia gives it, and the job is polled until its
+//! transcript is ready.
+
+use crate::audio::encode_wav;
+use crate::constants;
+use crate::engine::{self, Engine, Request};
+use crate::timing::{self, TimedWord};
+use anyhow::{anyhow, Context, Result};
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use log::info;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+const UPLOAD_URL: &str = "https://api.gladia.io/v2/upload";
+const PRE_RECORDED_URL: &str = "https://api.gladia.io/v2/pre-recorded";
+/// Where the API is served, for checking it can be reached
+pub const HOST: &str = "api.gladia.io:443";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const READ_TIMEOUT: Duration = Duration::from_secs(120);
+/// Wait between checks on a job
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Longest a job is waited for before giving up on it
+const JOB_TIMEOUT: Duration = Duration::from_secs(600);
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    pub api_key: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Upload {
+    audio_url: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Job {
+    id: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Word {
+    word: String,
+    start: f32,
+    end: f32,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Utterance {
+    #[serde(default)]
+    words: Vec<Word>,
+    /// Only there with diarization on
+    speaker: Option<u32>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Transcription {
+    #[serde(default)]
+    full_transcript: String,
+    #[serde(default)]
+    utterances: Vec<Utterance>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct JobResult {
+    transcription: Transcription,
+}
+
+/// A job as polled: `queued`, `processing`, `done` or `error`, with the
+/// result once it's done
+#[derive(Deserialize, Debug, PartialEq)]
+struct JobStatus {
+    status: String,
+    #[serde(default)]
+    error_code: Option<u16>,
+    #[serde(default)]
+    result: Option<JobResult>,
+}
+
+impl Transcription {
+    fn words(&self) -> Vec<TimedWord> {
+        self.utterances
+            .iter()
+            .flat_map(|utterance| {
+                utterance.words.iter().map(|word| TimedWord {
+                    start: word.start,
+                    end: word.end,
+                    text: word.word.trim().to_string(),
+                    speaker: utterance.speaker,
+                })
+            })
+            .collect()
+    }
+}
+
+fn api_key(config: &Config) -> Result<&str> {
+    match config.api_key.trim() {
+        "" => Err(anyhow!("No Gladia API key configured")),
+        key => Ok(key),
+    }
+}
+
+/// What the job is started with. Gladia detects the language when the list
+/// is empty, and only uses the vocabulary when it's told to.
+fn job_body(audio_url: &str, request: &Request) -> Value {
+    let mut body = json!({
+        "audio_url": audio_url,
+        "language_config": {
+            "languages": request.base_language().into_iter().collect::<Vec<_>>(),
+            "code_switching": false,
+        },
+    });
+    let vocabulary: Vec<&str> = request
+        .vocabulary
+        .iter()
+        .map(|word| word.trim())
+        .filter(|word| !word.is_empty())
+        .collect();
+    if !vocabulary.is_empty() {
+        body["custom_vocabulary"] = json!(true);
+        body["custom_vocabulary_config"] = json!({ "vocabulary": vocabulary });
+    }
+    body
+}
+
+async fn json<T: serde::de::DeserializeOwned>(response: reqwest::Response) -> Result<T> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Gladia returned {}: {}", status, body.trim()));
+    }
+    Ok(response.json().await?)
+}
+
+/// Transcribes 16 kHz mono `request.samples`, waiting for the job to finish
+pub async fn transcribe(config: &Config, request: Request<'_>) -> Result<engine::Transcript> {
+    let client = reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .read_timeout(READ_TIMEOUT)
+        .build()?;
+    let key = api_key(config)?;
+    let wav = encode_wav(request.samples)?;
+    info!(
+        "Sending {:.1}s of audio to Gladia ({} KB)",
+        request.samples.len() as f32 / constants::WHISPER_SAMPLE_RATE as f32,
+        wav.len() / 1024
+    );
+
+    let form = Form::new().part(
+        "audio",
+        Part::bytes(wav)
+            .file_name("recording.wav")
+            .mime_str("audio/wav")?,
+    );
+    let response = client
+        .post(UPLOAD_URL)
+        .header("x-gladia-key", key)
+        .multipart(form)
+        .send()
+        .await
+        .context("Gladia upload failed")?;
+    let upload: Upload = json(response).await?;
+
+    let response = client
+        .post(PRE_RECORDED_URL)
+        .header("x-gladia-key", key)
+        .json(&job_body(&upload.audio_url, &request))
+        .send()
+        .await
+        .context("Gladia request failed")?;
+    let job: Job = json(response).await?;
+
+    let started = Instant::now();
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let response = client
+            .get(format!("{}/{}", PRE_RECORDED_URL, job.id))
+            .header("x-gladia-key", key)
+            .send()
+            .await
+            .context("Gladia request failed")?;
+        let status: JobStatus = json(response).await?;
+        match (status.status.as_str(), status.result) {
+            ("done", Some(result)) => {
+                let transcription = result.transcription;
+                return Ok(engine::Transcript {
+                    segments: timing::segments_from_words(transcription.words()),
+                    text: transcription.full_transcript.trim().to_string(),
+                });
+            }
+            ("error", _) => {
+                return Err(anyhow!(
+                    "Gladia couldn't transcribe the recording (error {})",
+                    status.error_code.unwrap_or_default()
+                ));
+            }
+            _ if started.elapsed() > JOB_TIMEOUT => {
+                return Err(anyhow!(
+                    "Gladia didn't finish within {} minutes",
+                    JOB_TIMEOUT.as_secs() / 60
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Gladia behind the `Engine` interface
+pub struct Client {
+    pub config: Config,
+}
+
+impl Engine for Client {
+    fn name(&self) -> &str {
+        "Gladia"
+    }
+
+    fn transcribe<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<engine::Transcript>> {
+        transcribe(&self.config, request).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_jobs_with_language_and_vocabulary() {
+        let words = vec!["Handy".to_string(), " ".to_string()];
+        let request = Request {
+            language: Some("zh-Hans"),
+            vocabulary: &words,
+            ..Request::new(&[])
+        };
+        let body = job_body("https://api.gladia.io/file/1", &request);
+        assert_eq!(body["audio_url"], "https://api.gladia.io/file/1");
+        assert_eq!(body["language_config"]["languages"], json!(["zh"]));
+        assert_eq!(body["custom_vocabulary"], json!(true));
+        assert_eq!(
+            body["custom_vocabulary_config"]["vocabulary"],
+            json!(["Handy"])
+        );
+
+        let body = job_body("https://api.gladia.io/file/1", &Request::new(&[]));
+        assert_eq!(body["language_config"]["languages"], json!([]));
+        assert!(body.get("custom_vocabulary").is_none());
+    }
+
+    #[test]
+    fn parses_finished_jobs() {
+        let done = r#"{"id":"45463597","status":"done","result":{"metadata":{"audio_duration":1.2},"transcription":{"full_transcript":" Hello there.","languages":["en"],"utterances":[{"start":0.1,"end":0.8,"text":"Hello there.","speaker":0,"words":[{"word":" Hello","start":0.1,"end":0.4,"confidence":0.9},{"word":" there.","start":0.5,"end":0.8,"confidence":0.9}]}]}}}"#;
+        let status: JobStatus = serde_json::from_str(done).unwrap();
+        assert_eq!(status.status, "done");
+        let transcription = status.result.unwrap().transcription;
+        assert_eq!(transcription.full_transcript.trim(), "Hello there.");
+        let words = transcription.words();
+        assert_eq!(words[0].text, "Hello");
+        assert_eq!((words[1].start, words[1].end), (0.5, 0.8));
+        assert_eq!(words[1].speaker, Some(0));
+
+        let queued: JobStatus =
+            serde_json::from_str(r#"{"id":"1","status":"queued","result":null}"#).unwrap();
+        assert_eq!(queued.result, None);
+        let failed: JobStatus =
+            serde_json::from_str(r#"{"id":"1","status":"error","error_code":422}"#).unwrap();
+        assert_eq!(failed.error_code, Some(422));
+    }
+}
@@ -0,0 +1,184 @@
+//! Heuristics against the text Whisper makes up when there's little or no
+//! speech to go on: loops of the same few words, and the video outros and
+//! subtitle credits it picked up from its training data.
+
+/// Sentences Whisper produces from silence or noise. Matched against the
+/// normalized sentence (lowercase, punctuation replaced by spaces).
+const KNOWN_PHRASES: &[&str] = &[
+    "thank you for watching",
+    "thanks for watching",
+    "thank you so much for watching",
+    "thank you very much for watching",
+    "thanks for watching and see you next time",
+    "please subscribe",
+    "please like and subscribe",
+    "like and subscribe",
+    "don t forget to like and subscribe",
+    "please subscribe to my channel",
+    "subtitles by the amara org community",
+    "subtitles by amara org",
+    "transcription by castingwords",
+    "blank audio",
+    "music",
+    "silence",
+    "untertitel der amara org community",
+    "untertitel im auftrag des zdf für funk 2017",
+    "untertitelung des zdf für funk 2017",
+    "sous titres réalisés para la communauté d amara org",
+    "sous titrage société radio canada",
+    "sottotitoli creati dalla comunità amara org",
+    "subtítulos realizados por la comunidad de amara org",
+    "продолжение следует",
+    "редактор субтитров а семкин корректор а егорова",
+    "ご視聴ありがとうございました",
+    "请不吝点赞 订阅 转发 打赏支持明镜与点点栏目",
+];
+
+/// Samples quieter than this throughout count as silence
+const SILENCE_PEAK: f32 = 0.015;
+/// Longest phrase checked for repetition, in words
+const MAX_REPEATED_WORDS: usize = 8;
+
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// True if the recording has no sound loud enough to be speech, which is
+/// when Whisper is most likely to invent something.
+pub fn is_silent(samples: &[f32]) -> bool {
+    samples.iter().all(|s| s.abs() < SILENCE_PEAK)
+}
+
+/// How many times a phrase of `words` words has to repeat back to back before
+/// it's treated as a decoding loop. Single words get more slack so "no, no,
+/// no" survives.
+fn loop_threshold(words: usize) -> usize {
+    if words == 1 {
+        4
+    } else {
+        3
+    }
+}
+
+/// Collapses phrases repeated back to back into a single occurrence.
+fn collapse_repeats(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let keys: Vec<String> = words.iter().map(|w| normalize(w)).collect();
+    let mut kept: Vec<&str> = Vec::with_capacity(words.len());
+
+    let mut i = 0;
+    while i < words.len() {
+        let mut skipped = false;
+        for len in (1..=MAX_REPEATED_WORDS).rev() {
+            if i + len * loop_threshold(len) > words.len() {
+                continue;
+            }
+            let phrase = &keys[i..i + len];
+            let mut repeats = 1;
+            while i + (repeats + 1) * len <= words.len()
+                && keys[i + repeats * len..i + (repeats + 1) * len] == *phrase
+            {
+                repeats += 1;
+            }
+            if repeats >= loop_threshold(len) {
+                kept.extend_from_slice(&words[i..i + len]);
+                i += repeats * len;
+                skipped = true;
+                break;
+            }
+        }
+        if !skipped {
+            kept.push(words[i]);
+            i += 1;
+        }
+    }
+
+    kept.join(" ")
+}
+
+/// Splits after sentence-ending punctuation, keeping the punctuation. A
+/// period only ends a sentence before whitespace, so "amara.org" stays whole.
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let ends_sentence = match c {
+            '。' | '！' | '？' => true,
+            '.' | '!' | '?' => !matches!(chars.peek(), Some((_, next)) if !next.is_whitespace()),
+            _ => false,
+        };
+        if ends_sentence {
+            let end = i + c.len_utf8();
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+    sentences
+}
+
+/// Removes decoding loops and known made-up sentences from a transcript.
+pub fn suppress_hallucinations(text: &str) -> String {
+    let collapsed = collapse_repeats(text);
+    sentences(&collapsed)
+        .into_iter()
+        .filter(|sentence| {
+            let normalized = normalize(sentence);
+            // Bracketed tags like [Music] or (silence) normalize the same way
+            !normalized.is_empty() && !KNOWN_PHRASES.contains(&normalized.as_str())
+        })
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_normal_text_alone() {
+        let text = "Thank you for the update. No, no, no, that's fine!";
+        assert_eq!(suppress_hallucinations(text), text);
+    }
+
+    #[test]
+    fn drops_outros_and_credits() {
+        assert_eq!(suppress_hallucinations("Thanks for watching!"), "");
+        assert_eq!(
+            suppress_hallucinations("See you at three. Subtitles by the Amara.org community"),
+            "See you at three."
+        );
+        assert_eq!(suppress_hallucinations("[BLANK_AUDIO]"), "");
+    }
+
+    #[test]
+    fn collapses_decoding_loops() {
+        assert_eq!(
+            suppress_hallucinations("I think so. I think so. I think so. I think so."),
+            "I think so."
+        );
+        assert_eq!(suppress_hallucinations("okay okay okay okay okay"), "okay");
+        assert_eq!(suppress_hallucinations("okay okay okay"), "okay okay okay");
+    }
+
+    #[test]
+    fn detects_silence() {
+        assert!(is_silent(&[0.0, 0.001, -0.01]));
+        assert!(!is_silent(&[0.0, 0.2, -0.01]));
+    }
+}
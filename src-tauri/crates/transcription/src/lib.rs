@@ -0,0 +1,62 @@
+//! Handy's transcription stack without the app. Every engine is used through
+//! the [`Engine`] trait: give it a [`Request`], get back a [`Transcript`].
+//! The engines are the clients in [`deepgram`], [`fireworks`], [`gladia`],
+//! [`mistral`] and [`wyoming`], and with the `local` feature the models in
+//! `local`, which run on this computer through transcribe-rs. [`timing`]
+//! has the segments transcripts are timed with.
+//!
+//! The `capture` feature adds recording from microphones and system audio
+//! with voice activity detection, which needs cpal and vad-rs. The audio
+//! processing and text clean-up the app runs around the engines are public
+//! for the app's sake but not documented, and may change with it.
+
+pub mod deepgram;
+mod engine;
+pub mod fireworks;
+pub mod gladia;
+#[cfg(feature = "local")]
+pub mod local;
+pub mod mistral;
+pub mod timing;
+pub mod wyoming;
+
+#[doc(hidden)]
+pub mod audio;
+#[doc(hidden)]
+pub mod constants;
+mod hallucination;
+mod text;
+#[cfg(feature = "capture")]
+mod utils;
+#[doc(hidden)]
+pub mod vad;
+#[doc(hidden)]
+pub mod wake;
+
+pub use engine::{Engine, Request, Transcript};
+
+#[doc(hidden)]
+pub use audio::{
+    compress_silence, overlap_forced_cuts, read_wav_file, read_wav_file_with_rate, save_wav_file,
+    split_on_silence, SilenceMap,
+};
+#[cfg(feature = "capture")]
+#[doc(hidden)]
+pub use audio::{list_input_devices, list_output_devices, AudioRecorder, CpalDeviceInfo};
+#[doc(hidden)]
+pub use hallucination::{is_silent, suppress_hallucinations};
+#[doc(hidden)]
+pub use text::{
+    apply_custom_words, apply_replacements, context_tail, find_corrections, skip_overlap,
+    vocabulary_prompt, word_error_rate,
+};
+#[cfg(feature = "capture")]
+#[doc(hidden)]
+pub use utils::get_cpal_host;
+#[cfg(feature = "capture")]
+#[doc(hidden)]
+pub use vad::SileroVad;
+#[doc(hidden)]
+pub use vad::VoiceActivityDetector;
+#[doc(hidden)]
+pub use wake::WakeWordDetector;
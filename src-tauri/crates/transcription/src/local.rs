@@ -0,0 +1,212 @@
+//! The speech models that run on this computer, through transcribe-rs.
+//! Whisper takes a language, a prompt and can translate; Parakeet and
+//! Moonshine transcribe whatever they hear as spoken.
+
+use crate::engine::{Engine, Request, Transcript};
+use crate::timing::TimedSegment;
+use anyhow::{anyhow, Result};
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use transcribe_rs::{
+    engines::{
+        moonshine::{ModelVariant, MoonshineEngine, MoonshineModelParams},
+        parakeet::{
+            ParakeetEngine, ParakeetInferenceParams, ParakeetModelParams, TimestampGranularity,
+        },
+        whisper::{WhisperEngine, WhisperInferenceParams},
+    },
+    TranscriptionEngine, TranscriptionResult, TranscriptionSegment,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModelKind {
+    Whisper,
+    Parakeet,
+    Moonshine,
+}
+
+/// How to decode one recording
+#[derive(Clone, Debug, Default)]
+pub struct DecodeOptions {
+    /// A language tag such as `de` or `zh-Hans`, or `None` to detect it
+    pub language: Option<String>,
+    /// Translate to English instead of transcribing as spoken
+    pub translate: bool,
+    /// Text the recording follows on from, to carry names and style over
+    pub initial_prompt: Option<String>,
+}
+
+/// The platform's system-wide model directory for the app called
+/// `identifier`, where an administrator can install models once for every
+/// account on the computer
+pub fn default_shared_models_dir(identifier: &str) -> Option<PathBuf> {
+    #[cfg(target_os = "macos")]
+    {
+        Some(
+            PathBuf::from("/Library/Application Support")
+                .join(identifier)
+                .join("models"),
+        )
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let _ = identifier;
+        std::env::var_os("ProgramData").map(|dir| PathBuf::from(dir).join("Handy").join("models"))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = identifier;
+        Some(PathBuf::from("/usr/share/handy/models"))
+    }
+}
+
+pub enum LocalEngine {
+    Whisper(WhisperEngine),
+    Parakeet(ParakeetEngine),
+    Moonshine(MoonshineEngine),
+}
+
+/// Segments as the local engines return them
+fn timed_segments(segments: Option<Vec<TranscriptionSegment>>) -> Option<Vec<TimedSegment>> {
+    let segments: Vec<TimedSegment> = segments?
+        .into_iter()
+        .map(|segment| TimedSegment {
+            start: segment.start,
+            end: segment.end,
+            text: segment.text.trim().to_string(),
+            words: Vec::new(),
+            speaker: None,
+        })
+        .collect();
+    Some(segments).filter(|segments| !segments.is_empty())
+}
+
+/// Whisper takes ISO 639-1 codes, so `zh-Hans` and `zh-Hant` are both `zh`
+fn whisper_language(language: &str) -> String {
+    match language {
+        "zh-Hans" | "zh-Hant" => "zh".to_string(),
+        other => other.to_string(),
+    }
+}
+
+impl LocalEngine {
+    /// Loads the `kind` model at `model_path`
+    pub fn open(kind: ModelKind, model_path: &Path) -> Result<Self> {
+        let engine = match kind {
+            ModelKind::Whisper => {
+                let mut engine = WhisperEngine::new();
+                engine
+                    .load_model(model_path)
+                    .map_err(|e| anyhow!("{}", e))?;
+                LocalEngine::Whisper(engine)
+            }
+            ModelKind::Parakeet => {
+                let mut engine = ParakeetEngine::new();
+                engine
+                    .load_model_with_params(model_path, ParakeetModelParams::int8())
+                    .map_err(|e| anyhow!("{}", e))?;
+                LocalEngine::Parakeet(engine)
+            }
+            ModelKind::Moonshine => {
+                let mut engine = MoonshineEngine::new();
+                engine
+                    .load_model_with_params(
+                        model_path,
+                        MoonshineModelParams::variant(ModelVariant::Base),
+                    )
+                    .map_err(|e| anyhow!("{}", e))?;
+                LocalEngine::Moonshine(engine)
+            }
+        };
+        Ok(engine)
+    }
+
+    /// Whether this is a Whisper model, the only kind that takes a language,
+    /// a prompt or translates
+    pub fn is_whisper(&self) -> bool {
+        matches!(self, LocalEngine::Whisper(_))
+    }
+
+    pub fn unload(&mut self) {
+        match self {
+            LocalEngine::Whisper(e) => e.unload_model(),
+            LocalEngine::Parakeet(e) => e.unload_model(),
+            LocalEngine::Moonshine(e) => e.unload_model(),
+        }
+    }
+
+    /// Transcribes 16 kHz mono `samples`
+    pub fn transcribe(
+        &mut self,
+        samples: Vec<f32>,
+        options: &DecodeOptions,
+    ) -> Result<TranscriptionResult> {
+        match self {
+            LocalEngine::Whisper(whisper_engine) => {
+                let params = WhisperInferenceParams {
+                    language: options.language.as_deref().map(whisper_language),
+                    translate: options.translate,
+                    initial_prompt: options.initial_prompt.clone(),
+                    ..Default::default()
+                };
+                whisper_engine
+                    .transcribe_samples(samples, Some(params))
+                    .map_err(|e| anyhow!("Whisper transcription failed: {}", e))
+            }
+            LocalEngine::Parakeet(parakeet_engine) => {
+                let params = ParakeetInferenceParams {
+                    timestamp_granularity: TimestampGranularity::Segment,
+                    ..Default::default()
+                };
+                parakeet_engine
+                    .transcribe_samples(samples, Some(params))
+                    .map_err(|e| anyhow!("Parakeet transcription failed: {}", e))
+            }
+            LocalEngine::Moonshine(moonshine_engine) => moonshine_engine
+                .transcribe_samples(samples, None)
+                .map_err(|e| anyhow!("Moonshine transcription failed: {}", e)),
+        }
+    }
+}
+
+/// A loaded model behind the `Engine` interface. Recordings are decoded one
+/// at a time, on the thread that polls the future.
+pub struct LocalModel {
+    engine: Mutex<LocalEngine>,
+}
+
+impl LocalModel {
+    pub fn new(engine: LocalEngine) -> Self {
+        Self {
+            engine: Mutex::new(engine),
+        }
+    }
+}
+
+impl Engine for LocalModel {
+    fn name(&self) -> &str {
+        "local model"
+    }
+
+    fn transcribe<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<Transcript>> {
+        async move {
+            let mut engine = self
+                .engine
+                .lock()
+                .map_err(|_| anyhow!("The local model panicked"))?;
+            let options = DecodeOptions {
+                language: request.language.map(str::to_string),
+                translate: false,
+                initial_prompt: request.prompt.map(str::to_string),
+            };
+            let result = engine.transcribe(request.samples.to_vec(), &options)?;
+            Ok(Transcript {
+                text: result.text.trim().to_string(),
+                segments: timed_segments(result.segments).unwrap_or_default(),
+            })
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,199 @@
+//! Client for Mistral's Voxtral models, which transcribe through the chat
+//! completions endpoint. The recording goes in the user message as a
+//! base64 `input_audio` part, next to a text part asking for a transcript,
+//! and the reply is the transcript.
+
+use crate::audio::encode_wav;
+use crate::constants;
+use crate::engine::{self, Engine, Request};
+use anyhow::{anyhow, Context, Result};
+use base64::Engine as _;
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use log::info;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+const CHAT_URL: &str = "https://api.mistral.ai/v1/chat/completions";
+/// Where the API is served, for checking it can be reached
+pub const HOST: &str = "api.mistral.ai:443";
+pub const DEFAULT_MODEL: &str = "voxtral-small-latest";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const READ_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    pub api_key: String,
+    /// A Voxtral model such as `voxtral-mini-latest`, empty for
+    /// `DEFAULT_MODEL`
+    pub model: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Message {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Choice {
+    message: Message,
+}
+
+#[derive(Deserialize, Debug)]
+struct Response {
+    #[serde(default)]
+    choices: Vec<Choice>,
+}
+
+pub fn model(config: &Config) -> &str {
+    match config.model.trim() {
+        "" => DEFAULT_MODEL,
+        model => model,
+    }
+}
+
+fn api_key(config: &Config) -> Result<&str> {
+    match config.api_key.trim() {
+        "" => Err(anyhow!("No Mistral API key configured")),
+        key => Ok(key),
+    }
+}
+
+/// What the model is asked to do with the audio. Chat has no language or
+/// vocabulary fields, so they're described here.
+fn instruction(request: &Request) -> String {
+    let mut text = "Transcribe this audio word for word. Reply with the transcript only, \
+                    without comments, translation or quotes."
+        .to_string();
+    if let Some(language) = request.base_language() {
+        text.push_str(&format!(
+            " The speech is in the language with code {}.",
+            language
+        ));
+    }
+    if let Some(prompt) = request.prompt {
+        text.push_str(&format!(" It's about: {}", prompt.trim()));
+    }
+    let words: Vec<&str> = request
+        .vocabulary
+        .iter()
+        .map(|word| word.trim())
+        .filter(|word| !word.is_empty())
+        .collect();
+    if !words.is_empty() {
+        text.push_str(&format!(
+            " These words may come up, spell them this way: {}.",
+            words.join(", ")
+        ));
+    }
+    text
+}
+
+fn body(config: &Config, request: &Request, audio: &str) -> Value {
+    json!({
+        "model": model(config),
+        "temperature": 0,
+        "messages": [{
+            "role": "user",
+            "content": [
+                { "type": "input_audio", "input_audio": audio },
+                { "type": "text", "text": instruction(request) },
+            ],
+        }],
+    })
+}
+
+/// Transcribes 16 kHz mono `request.samples`
+pub async fn transcribe(config: &Config, request: Request<'_>) -> Result<engine::Transcript> {
+    let client = reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .read_timeout(READ_TIMEOUT)
+        .build()?;
+    let wav = encode_wav(request.samples)?;
+    info!(
+        "Sending {:.1}s of audio to Mistral ({}, {} KB)",
+        request.samples.len() as f32 / constants::WHISPER_SAMPLE_RATE as f32,
+        model(config),
+        wav.len() / 1024
+    );
+    let audio = base64::engine::general_purpose::STANDARD.encode(wav);
+    let response = client
+        .post(CHAT_URL)
+        .bearer_auth(api_key(config)?)
+        .json(&body(config, &request, &audio))
+        .send()
+        .await
+        .context("Mistral request failed")?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Mistral returned {}: {}", status, body.trim()));
+    }
+    let response: Response = response.json().await?;
+    let text = response
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|choice| choice.message.content)
+        .ok_or_else(|| anyhow!("Mistral sent no transcript"))?;
+    Ok(engine::Transcript {
+        text: text.trim().to_string(),
+        segments: Vec::new(),
+    })
+}
+
+/// Voxtral behind the `Engine` interface. Chat replies aren't timed, so
+/// transcripts come without segments.
+pub struct Client {
+    pub config: Config,
+}
+
+impl Engine for Client {
+    fn name(&self) -> &str {
+        "Mistral"
+    }
+
+    fn transcribe<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<engine::Transcript>> {
+        transcribe(&self.config, request).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sends_audio_next_to_the_instruction() {
+        let words = vec!["Handy".to_string()];
+        let request = Request {
+            language: Some("fr"),
+            vocabulary: &words,
+            ..Request::new(&[])
+        };
+        let body = body(&Config::default(), &request, "UklGRg==");
+        assert_eq!(body["model"], "voxtral-small-latest");
+        let content = &body["messages"][0]["content"];
+        assert_eq!(
+            content[0],
+            json!({ "type": "input_audio", "input_audio": "UklGRg==" })
+        );
+        let text = content[1]["text"].as_str().unwrap();
+        assert!(text.contains("code fr"));
+        assert!(text.contains("this way: Handy."));
+
+        let plain = instruction(&Request::new(&[]));
+        assert!(!plain.contains("code") && !plain.contains("come up"));
+    }
+
+    #[test]
+    fn parses_the_reply() {
+        let reply = r#"{"id":"1","object":"chat.completion","model":"voxtral-small-latest","choices":[{"index":0,"message":{"role":"assistant","content":" Bonjour à tous."},"finish_reason":"stop"}]}"#;
+        let response: Response = serde_json::from_str(reply).unwrap();
+        assert_eq!(
+            response.choices[0].message.content.as_deref(),
+            Some(" Bonjour à tous.")
+        );
+    }
+}
@@ -0,0 +1,496 @@
+use natural::phonetics::soundex;
+use strsim::levenshtein;
+
+/// Applies custom word corrections to transcribed text using fuzzy matching
+///
+/// This function corrects words in the input text by finding the best matches
+/// from a list of custom words using a combination of:
+/// - Levenshtein distance for string similarity
+/// - Soundex phonetic matching for pronunciation similarity
+///
+/// # Arguments
+/// * `text` - The input text to correct
+/// * `custom_words` - List of custom words to match against
+/// * `threshold` - Maximum similarity score to accept (0.0 = exact match, 1.0 = any match)
+///
+/// # Returns
+/// The corrected text with custom words applied
+pub fn apply_custom_words(text: &str, custom_words: &[String], threshold: f64) -> String {
+    if custom_words.is_empty() {
+        return text.to_string();
+    }
+
+    // Pre-compute lowercase versions to avoid repeated allocations
+    let custom_words_lower: Vec<String> = custom_words.iter().map(|w| w.to_lowercase()).collect();
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut corrected_words = Vec::new();
+
+    for word in words {
+        let cleaned_word = word
+            .trim_matches(|c: char| !c.is_alphabetic())
+            .to_lowercase();
+
+        if cleaned_word.is_empty() {
+            corrected_words.push(word.to_string());
+            continue;
+        }
+
+        // Skip extremely long words to avoid performance issues
+        if cleaned_word.len() > 50 {
+            corrected_words.push(word.to_string());
+            continue;
+        }
+
+        let mut best_match: Option<&String> = None;
+        let mut best_score = f64::MAX;
+
+        for (i, custom_word_lower) in custom_words_lower.iter().enumerate() {
+            // Skip if lengths are too different (optimization)
+            let len_diff = (cleaned_word.len() as i32 - custom_word_lower.len() as i32).abs();
+            if len_diff > 5 {
+                continue;
+            }
+
+            // Calculate Levenshtein distance (normalized by length)
+            let levenshtein_dist = levenshtein(&cleaned_word, custom_word_lower);
+            let max_len = cleaned_word.len().max(custom_word_lower.len()) as f64;
+            let levenshtein_score = if max_len > 0.0 {
+                levenshtein_dist as f64 / max_len
+            } else {
+                1.0
+            };
+
+            // Calculate phonetic similarity using Soundex
+            let phonetic_match = soundex(&cleaned_word, custom_word_lower);
+
+            // Combine scores: favor phonetic matches, but also consider string similarity
+            let combined_score = if phonetic_match {
+                levenshtein_score * 0.3 // Give significant boost to phonetic matches
+            } else {
+                levenshtein_score
+            };
+
+            // Accept if the score is good enough (configurable threshold)
+            if combined_score < threshold && combined_score < best_score {
+                best_match = Some(&custom_words[i]);
+                best_score = combined_score;
+            }
+        }
+
+        if let Some(replacement) = best_match {
+            // Preserve the original case pattern as much as possible
+            let corrected = preserve_case_pattern(word, replacement);
+
+            // Preserve punctuation from original word
+            let (prefix, suffix) = extract_punctuation(word);
+            corrected_words.push(format!("{}{}{}", prefix, corrected, suffix));
+        } else {
+            corrected_words.push(word.to_string());
+        }
+    }
+
+    corrected_words.join(" ")
+}
+
+/// Preserves the case pattern of the original word when applying a replacement
+fn preserve_case_pattern(original: &str, replacement: &str) -> String {
+    if original.chars().all(|c| c.is_uppercase()) {
+        replacement.to_uppercase()
+    } else if original.chars().next().map_or(false, |c| c.is_uppercase()) {
+        let mut chars: Vec<char> = replacement.chars().collect();
+        if let Some(first_char) = chars.get_mut(0) {
+            *first_char = first_char.to_uppercase().next().unwrap_or(*first_char);
+        }
+        chars.into_iter().collect()
+    } else {
+        replacement.to_string()
+    }
+}
+
+/// Extracts punctuation prefix and suffix from a word
+fn extract_punctuation(word: &str) -> (&str, &str) {
+    let prefix_end = word.chars().take_while(|c| !c.is_alphabetic()).count();
+    let suffix_start = word
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| !c.is_alphabetic())
+        .count();
+
+    let prefix = if prefix_end > 0 {
+        &word[..prefix_end]
+    } else {
+        ""
+    };
+
+    let suffix = if suffix_start > 0 {
+        &word[word.len() - suffix_start..]
+    } else {
+        ""
+    };
+
+    (prefix, suffix)
+}
+
+/// Returns the end of `text`, at most `max_chars` long and starting at a word
+/// boundary, for seeding the decoder with what was said before.
+pub fn context_tail(text: &str, max_chars: usize) -> String {
+    let text = text.trim();
+    let char_count = text.chars().count();
+    if char_count <= max_chars {
+        return text.to_string();
+    }
+
+    let start = text
+        .char_indices()
+        .nth(char_count - max_chars)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    let tail = &text[start..];
+    // Drop the partial word at the cut unless the tail is a single word
+    match tail.find(char::is_whitespace) {
+        Some(space) if !text[..start].ends_with(char::is_whitespace) => {
+            tail[space..].trim_start().to_string()
+        }
+        _ => tail.to_string(),
+    }
+}
+
+/// Lists `words` as a decoder prompt, so the model leans towards spelling
+/// them the way they are written. Words that don't fit in `max_chars` are
+/// left out.
+pub fn vocabulary_prompt(words: &[String], max_chars: usize) -> Option<String> {
+    let mut prompt = String::new();
+    for word in words.iter().map(|w| w.trim()).filter(|w| !w.is_empty()) {
+        let separator = if prompt.is_empty() { "" } else { ", " };
+        if prompt.chars().count() + separator.len() + word.chars().count() + 1 > max_chars {
+            break;
+        }
+        prompt.push_str(separator);
+        prompt.push_str(word);
+    }
+    (!prompt.is_empty()).then(|| format!("{}.", prompt))
+}
+
+/// Phrases longer than this aren't learned as corrections, rewriting a whole
+/// sentence isn't a misrecognition
+const MAX_CORRECTION_WORDS: usize = 3;
+/// Transcripts this many words long or longer aren't compared word by word
+const MAX_DIFF_WORDS: usize = 2000;
+
+/// `words` lowercased with punctuation left out, for comparing what was said
+/// regardless of how it was written
+fn normalize_words(words: &[&str]) -> String {
+    words
+        .iter()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn trim_punctuation(text: &str) -> &str {
+    text.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+/// Replaces misrecognized phrases with their corrections. Each rule pairs a
+/// phrase as it was misheard with what it should be. A run of as many words
+/// matches when its normalized edit distance to the misheard form is below
+/// `threshold`; longer rules win over shorter ones.
+pub fn apply_replacements(text: &str, rules: &[(String, String)], threshold: f64) -> String {
+    let rules: Vec<(String, usize, &str)> = rules
+        .iter()
+        .filter_map(|(from, to)| {
+            let words: Vec<&str> = from.split_whitespace().collect();
+            let key = normalize_words(&words);
+            (!key.is_empty()).then_some((key, words.len(), to.as_str()))
+        })
+        .collect();
+    if rules.is_empty() {
+        return text.to_string();
+    }
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut out = Vec::with_capacity(words.len());
+    let mut i = 0;
+    while i < words.len() {
+        let best = rules
+            .iter()
+            .filter(|(_, len, _)| i + len <= words.len())
+            .filter_map(|(key, len, to)| {
+                let span = normalize_words(&words[i..i + len]);
+                let max_len = span.chars().count().max(key.chars().count()).max(1);
+                let score = levenshtein(&span, key) as f64 / max_len as f64;
+                (score == 0.0 || score < threshold).then_some((*len, score, *to))
+            })
+            .max_by(|a, b| a.0.cmp(&b.0).then(b.1.total_cmp(&a.1)));
+
+        match best {
+            Some((len, _, to)) => {
+                let (prefix, _) = extract_punctuation(words[i]);
+                let (_, suffix) = extract_punctuation(words[i + len - 1]);
+                let corrected = preserve_case_pattern(trim_punctuation(words[i]), to);
+                out.push(format!("{}{}{}", prefix, corrected, suffix));
+                i += len;
+            }
+            None => {
+                out.push(words[i].to_string());
+                i += 1;
+            }
+        }
+    }
+    out.join(" ")
+}
+
+/// The phrases changed between a transcript and its edited version, as
+/// (misheard, corrected) pairs. Changes to case or punctuation alone, and
+/// replacements longer than a few words, aren't counted.
+pub fn find_corrections(original: &str, edited: &str) -> Vec<(String, String)> {
+    let before: Vec<&str> = original.split_whitespace().collect();
+    let after: Vec<&str> = edited.split_whitespace().collect();
+    if before.len() >= MAX_DIFF_WORDS || after.len() >= MAX_DIFF_WORDS {
+        return Vec::new();
+    }
+    let key = |word: &&str| normalize_words(&[word]);
+    let before_keys: Vec<String> = before.iter().map(key).collect();
+    let after_keys: Vec<String> = after.iter().map(key).collect();
+
+    // Longest common subsequence of the words, from the end
+    let (n, m) = (before.len(), after.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if before_keys[i] == after_keys[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut corrections: Vec<(String, String)> = Vec::new();
+    let mut push = |from: &[&str], to: &[&str]| {
+        if from.is_empty()
+            || to.is_empty()
+            || from.len() > MAX_CORRECTION_WORDS
+            || to.len() > MAX_CORRECTION_WORDS
+            || normalize_words(from) == normalize_words(to)
+            || normalize_words(from).is_empty()
+        {
+            return;
+        }
+        let pair = (
+            trim_punctuation(&from.join(" ")).to_string(),
+            trim_punctuation(&to.join(" ")).to_string(),
+        );
+        if !corrections.contains(&pair) {
+            corrections.push(pair);
+        }
+    };
+
+    let (mut i, mut j) = (0, 0);
+    let (mut start_i, mut start_j) = (0, 0);
+    loop {
+        if i < n && j < m && before_keys[i] == after_keys[j] {
+            push(&before[start_i..i], &after[start_j..j]);
+            i += 1;
+            j += 1;
+            (start_i, start_j) = (i, j);
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            j += 1;
+        } else if i < n {
+            i += 1;
+        } else {
+            push(&before[start_i..i], &after[start_j..j]);
+            break;
+        }
+    }
+    corrections
+}
+
+/// The part of `next` that doesn't repeat the end of `previous`, for
+/// transcripts of audio that overlaps. Up to `max_words` at the seam are
+/// compared, ignoring case and punctuation, and the longest repeat is
+/// dropped.
+pub fn skip_overlap<'a>(previous: &str, next: &'a str, max_words: usize) -> &'a str {
+    let before: Vec<&str> = previous.split_whitespace().collect();
+    let after: Vec<&str> = next.split_whitespace().collect();
+    let longest = max_words.min(before.len()).min(after.len());
+    let Some(repeated) = (1..=longest)
+        .rev()
+        .find(|&k| normalize_words(&before[before.len() - k..]) == normalize_words(&after[..k]))
+    else {
+        return next.trim();
+    };
+    let last = after[repeated - 1];
+    let end = last.as_ptr() as usize - next.as_ptr() as usize + last.len();
+    next[end..].trim()
+}
+
+/// Word error rate of `hypothesis` against `reference`: the substituted,
+/// inserted and deleted words per reference word, ignoring case and
+/// punctuation.
+pub fn word_error_rate(reference: &str, hypothesis: &str) -> f32 {
+    let normalize = |text: &str| -> Vec<String> {
+        text.split_whitespace()
+            .map(|word| normalize_words(&[word]))
+            .filter(|word| !word.is_empty())
+            .collect()
+    };
+    let reference = normalize(reference);
+    let hypothesis = normalize(hypothesis);
+    if reference.is_empty() {
+        return if hypothesis.is_empty() { 0.0 } else { 1.0 };
+    }
+
+    let mut previous: Vec<usize> = (0..=hypothesis.len()).collect();
+    for (i, expected) in reference.iter().enumerate() {
+        let mut current = vec![i + 1; hypothesis.len() + 1];
+        for (j, heard) in hypothesis.iter().enumerate() {
+            let substitution = previous[j] + usize::from(expected != heard);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[hypothesis.len()] as f32 / reference.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_corrections_pairs_replaced_phrases() {
+        assert_eq!(
+            find_corrections(
+                "I use hand e for dictation, it's grate.",
+                "I use Handy for dictation, it's great!"
+            ),
+            vec![
+                ("hand e".to_string(), "Handy".to_string()),
+                ("grate".to_string(), "great".to_string())
+            ]
+        );
+        // Only case and punctuation changed, or whole sentences rewritten
+        assert!(find_corrections("hello world.", "Hello, world!").is_empty());
+        assert!(find_corrections("one two three four", "five six seven eight").is_empty());
+        // Words added or dropped aren't corrections
+        assert!(find_corrections("send it", "send it now").is_empty());
+    }
+
+    #[test]
+    fn apply_replacements_matches_misheard_forms_fuzzily() {
+        let rules = vec![
+            ("hand e".to_string(), "Handy".to_string()),
+            ("tower e".to_string(), "Tauri".to_string()),
+        ];
+        assert_eq!(
+            apply_replacements("Try hand he, built with Tower E.", &rules, 0.18),
+            "Try Handy, built with Tauri."
+        );
+        assert_eq!(
+            apply_replacements("the hand is here", &rules, 0.18),
+            "the hand is here"
+        );
+        assert_eq!(apply_replacements("hand e", &[], 0.18), "hand e");
+    }
+
+    #[test]
+    fn vocabulary_prompt_lists_words_that_fit() {
+        let words = vec!["Tauri".to_string(), " ".to_string(), "Handy".to_string()];
+        assert_eq!(
+            vocabulary_prompt(&words, 100).as_deref(),
+            Some("Tauri, Handy.")
+        );
+        assert_eq!(vocabulary_prompt(&words, 10).as_deref(), Some("Tauri."));
+        assert_eq!(vocabulary_prompt(&words, 3), None);
+        assert_eq!(vocabulary_prompt(&[], 100), None);
+    }
+
+    #[test]
+    fn test_apply_custom_words_exact_match() {
+        let text = "hello world";
+        let custom_words = vec!["Hello".to_string(), "World".to_string()];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        assert_eq!(result, "Hello World");
+    }
+
+    #[test]
+    fn test_apply_custom_words_fuzzy_match() {
+        let text = "helo wrold";
+        let custom_words = vec!["hello".to_string(), "world".to_string()];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        assert_eq!(result, "hello world");
+    }
+
+    #[test]
+    fn test_preserve_case_pattern() {
+        assert_eq!(preserve_case_pattern("HELLO", "world"), "WORLD");
+        assert_eq!(preserve_case_pattern("Hello", "world"), "World");
+        assert_eq!(preserve_case_pattern("hello", "WORLD"), "WORLD");
+    }
+
+    #[test]
+    fn test_extract_punctuation() {
+        assert_eq!(extract_punctuation("hello"), ("", ""));
+        assert_eq!(extract_punctuation("!hello?"), ("!", "?"));
+        assert_eq!(extract_punctuation("...hello..."), ("...", "..."));
+    }
+
+    #[test]
+    fn test_empty_custom_words() {
+        let text = "hello world";
+        let custom_words = vec![];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        assert_eq!(result, "hello world");
+    }
+
+    #[test]
+    fn test_context_tail_keeps_short_text() {
+        assert_eq!(context_tail("  hello world ", 50), "hello world");
+    }
+
+    #[test]
+    fn test_context_tail_cuts_at_word_boundary() {
+        assert_eq!(context_tail("the quick brown fox", 12), "brown fox");
+        assert_eq!(context_tail("the quick brown fox", 9), "brown fox");
+    }
+
+    #[test]
+    fn test_context_tail_handles_multibyte_text() {
+        assert_eq!(context_tail("grüße aus köln", 8), "aus köln");
+    }
+
+    #[test]
+    fn skip_overlap_drops_repeated_words() {
+        assert_eq!(
+            skip_overlap("we met at the station", "The station, then home.", 8),
+            "then home."
+        );
+        assert_eq!(
+            skip_overlap("hello there", "general Kenobi", 8),
+            "general Kenobi"
+        );
+        assert_eq!(skip_overlap("a b c", "b c", 1), "b c");
+        assert_eq!(skip_overlap("", " first ", 8), "first");
+    }
+
+    #[test]
+    fn word_error_rate_counts_edits_per_reference_word() {
+        assert_eq!(
+            word_error_rate("The quick brown fox.", "the quick brown fox"),
+            0.0
+        );
+        assert_eq!(
+            word_error_rate("the quick brown fox", "the quack brown"),
+            0.5
+        );
+        assert_eq!(word_error_rate("one two", "one two three four"), 1.0);
+        assert_eq!(word_error_rate("", ""), 0.0);
+    }
+}
@@ -0,0 +1,242 @@
+//! When each part of a transcript was spoken, and by whom.
+//!
+//! Engines time their output differently: local models and Wyoming servers
+//! send segments, Deepgram sends words. Words are grouped into cues here, so
+//! either way a transcript ends up as segments short enough to read on screen.
+//! When the engine told speakers apart, each segment has one speaker, and
+//! transcripts with more than one are labeled "Speaker 1" and so on.
+
+use serde::{Deserialize, Serialize};
+
+/// Longest a cue built from words may last
+const MAX_CUE_SECS: f32 = 6.0;
+/// Two lines of 42 characters, the usual subtitle limit
+const MAX_CUE_CHARS: usize = 84;
+/// A pause this long between words starts a new cue
+const CUE_GAP_SECS: f32 = 1.0;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TimedWord {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+    /// Who said it, counting from 0, if the engine told speakers apart
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<u32>,
+}
+
+/// A stretch of the transcript and when it was spoken, in seconds into the
+/// recording
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TimedSegment {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<TimedWord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<u32>,
+}
+
+/// Groups words into cues, ending one at the end of a sentence, at a pause,
+/// when the speaker changes or when it gets too long to read
+pub fn segments_from_words(words: Vec<TimedWord>) -> Vec<TimedSegment> {
+    let mut segments: Vec<TimedSegment> = Vec::new();
+    let mut current: Vec<TimedWord> = Vec::new();
+    for word in words {
+        if let (Some(first), Some(last)) = (current.first(), current.last()) {
+            let chars: usize = current.iter().map(|w| w.text.chars().count() + 1).sum();
+            let full = word.end - first.start > MAX_CUE_SECS
+                || chars + word.text.chars().count() > MAX_CUE_CHARS;
+            let pause = word.start - last.end >= CUE_GAP_SECS;
+            let sentence_end = last.text.ends_with(['.', '?', '!', '。', '？', '！']);
+            let turn = word.speaker != last.speaker;
+            if full || pause || sentence_end || turn {
+                segments.push(segment_of(std::mem::take(&mut current)));
+            }
+        }
+        current.push(word);
+    }
+    if !current.is_empty() {
+        segments.push(segment_of(current));
+    }
+    segments
+}
+
+fn segment_of(words: Vec<TimedWord>) -> TimedSegment {
+    TimedSegment {
+        start: words[0].start,
+        end: words[words.len() - 1].end,
+        text: words
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        speaker: words[0].speaker,
+        words,
+    }
+}
+
+/// Every word of `segments` with when it was spoken. Segments the engine
+/// didn't time word by word share their time out among their words by
+/// length, which is close enough to seek by.
+pub fn words_of(segments: &[TimedSegment]) -> Vec<TimedWord> {
+    let mut words = Vec::new();
+    for segment in segments {
+        if !segment.words.is_empty() {
+            words.extend(segment.words.iter().cloned());
+            continue;
+        }
+        let texts: Vec<&str> = segment.text.split_whitespace().collect();
+        let chars: usize = texts.iter().map(|text| text.chars().count()).sum();
+        let per_char = (segment.end - segment.start).max(0.0) / chars.max(1) as f32;
+        let mut start = segment.start;
+        for text in texts {
+            let end = start + text.chars().count() as f32 * per_char;
+            words.push(TimedWord {
+                start,
+                end,
+                text: text.to_string(),
+                speaker: segment.speaker,
+            });
+            start = end;
+        }
+    }
+    words
+}
+
+/// Whether `segments` have more than one speaker, so are worth labeling
+pub fn has_speakers(segments: &[TimedSegment]) -> bool {
+    let mut speakers = segments.iter().filter_map(|segment| segment.speaker);
+    speakers
+        .next()
+        .is_some_and(|first| speakers.any(|speaker| speaker != first))
+}
+
+/// "Speaker 1" for the first speaker, and so on
+pub fn speaker_label(speaker: u32) -> String {
+    format!("Speaker {}", speaker + 1)
+}
+
+/// The transcript as "Speaker 1: ..." paragraphs, one per turn, when
+/// `segments` have more than one speaker
+pub fn speaker_text(segments: &[TimedSegment]) -> Option<String> {
+    if !has_speakers(segments) {
+        return None;
+    }
+    let mut turns: Vec<(Option<u32>, String)> = Vec::new();
+    for segment in segments.iter().filter(|s| !s.text.trim().is_empty()) {
+        match turns.last_mut() {
+            Some((speaker, text)) if *speaker == segment.speaker => {
+                text.push(' ');
+                text.push_str(segment.text.trim());
+            }
+            _ => turns.push((segment.speaker, segment.text.trim().to_string())),
+        }
+    }
+    let paragraphs: Vec<String> = turns
+        .into_iter()
+        .map(|(speaker, text)| match speaker {
+            Some(speaker) => format!("{}: {}", speaker_label(speaker), text),
+            None => text,
+        })
+        .collect();
+    Some(paragraphs.join("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(start: f32, end: f32, text: &str) -> TimedWord {
+        TimedWord {
+            start,
+            end,
+            text: text.to_string(),
+            speaker: None,
+        }
+    }
+
+    fn spoken_by(speaker: u32, text: &str) -> TimedSegment {
+        TimedSegment {
+            start: 0.0,
+            end: 1.0,
+            text: text.to_string(),
+            words: Vec::new(),
+            speaker: Some(speaker),
+        }
+    }
+
+    #[test]
+    fn groups_words_at_sentences_and_pauses() {
+        let segments = segments_from_words(vec![
+            word(0.0, 0.4, "Hello"),
+            word(0.5, 0.9, "there."),
+            word(1.0, 1.3, "How"),
+            word(1.4, 1.6, "are"),
+            word(3.0, 3.4, "you"),
+        ]);
+        let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["Hello there.", "How are", "you"]);
+        assert_eq!((segments[1].start, segments[1].end), (1.0, 1.6));
+
+        let long: Vec<TimedWord> = (0..20)
+            .map(|i| word(i as f32 * 0.5, i as f32 * 0.5 + 0.4, "word"))
+            .collect();
+        let segments = segments_from_words(long);
+        assert!(segments.len() > 1);
+        assert!(segments.iter().all(|s| s.end - s.start <= MAX_CUE_SECS));
+    }
+
+    #[test]
+    fn splits_cues_at_speaker_turns() {
+        let said = |speaker, start: f32, text| TimedWord {
+            speaker: Some(speaker),
+            ..word(start, start + 0.4, text)
+        };
+        let segments = segments_from_words(vec![
+            said(0, 0.0, "Hi"),
+            said(0, 0.5, "Sam"),
+            said(1, 1.0, "hey"),
+        ]);
+        let turns: Vec<(Option<u32>, &str)> = segments
+            .iter()
+            .map(|s| (s.speaker, s.text.as_str()))
+            .collect();
+        assert_eq!(turns, [(Some(0), "Hi Sam"), (Some(1), "hey")]);
+    }
+
+    #[test]
+    fn spreads_untimed_segments_over_their_words() {
+        let timed = TimedSegment {
+            words: vec![word(0.0, 0.3, "One"), word(0.4, 0.8, "two")],
+            ..spoken_by(0, "One two")
+        };
+        let untimed = TimedSegment {
+            start: 2.0,
+            end: 3.0,
+            ..spoken_by(0, "ab abc")
+        };
+        let words = words_of(&[timed, untimed]);
+        let texts: Vec<&str> = words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(texts, ["One", "two", "ab", "abc"]);
+        assert_eq!((words[1].start, words[1].end), (0.4, 0.8));
+        assert_eq!((words[2].start, words[2].end), (2.0, 2.4));
+        assert_eq!((words[3].start, words[3].end), (2.4, 3.0));
+    }
+
+    #[test]
+    fn labels_speakers_only_when_there_are_several() {
+        let segments = [
+            spoken_by(0, "Shall we start?"),
+            spoken_by(0, "Sure."),
+            spoken_by(1, "Yes."),
+            spoken_by(0, "Good."),
+        ];
+        assert_eq!(
+            speaker_text(&segments).unwrap(),
+            "Speaker 1: Shall we start? Sure.\n\nSpeaker 2: Yes.\n\nSpeaker 1: Good."
+        );
+        assert_eq!(speaker_text(&[spoken_by(0, "Just me.")]), None);
+    }
+}
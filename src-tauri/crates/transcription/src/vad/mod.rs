@@ -23,10 +23,18 @@ pub trait VoiceActivityDetector: Send + Sync {
     }
 
     fn reset(&mut self) {}
+
+    /// Changes how confident the detector has to be to call a frame speech.
+    /// Detectors without a threshold ignore this.
+    fn set_threshold(&mut self, _threshold: f32) {}
 }
 
+mod noise_floor;
+#[cfg(feature = "capture")]
 mod silero;
 mod smoothed;
 
+pub use noise_floor::NoiseFloor;
+#[cfg(feature = "capture")]
 pub use silero::SileroVad;
 pub use smoothed::SmoothedVad;
@@ -0,0 +1,104 @@
+//! Tracks the room's background level from the audio heard between
+//! recordings, so the VAD can ask for more confidence in a noisy room than
+//! in a quiet one.
+
+/// Frames quieter than this are digital silence, not a room
+const SILENCE_DB: f32 = -90.0;
+/// Floor at or below which the configured threshold is used as is
+const QUIET_DB: f32 = -60.0;
+/// Floor at which the threshold is raised all the way to `NOISY_THRESHOLD`
+const NOISY_DB: f32 = -30.0;
+const NOISY_THRESHOLD: f32 = 0.7;
+/// How far one frame moves the estimate down and up. It follows the room
+/// getting quieter quickly but louder slowly, so a word spoken between
+/// recordings barely moves it.
+const FALL_RATE: f32 = 0.1;
+const RISE_RATE: f32 = 0.002;
+
+#[derive(Default)]
+pub struct NoiseFloor {
+    level_db: Option<f32>,
+}
+
+impl NoiseFloor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds one frame of audio heard while not recording
+    pub fn push(&mut self, frame: &[f32]) {
+        if frame.is_empty() {
+            return;
+        }
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+        let db = 20.0 * rms.max(1e-6).log10();
+        if db <= SILENCE_DB {
+            return;
+        }
+        self.level_db = Some(match self.level_db {
+            None => db,
+            Some(level) if db < level => level + (db - level) * FALL_RATE,
+            Some(level) => level + (db - level) * RISE_RATE,
+        });
+    }
+
+    /// The estimated floor in dBFS, once any audio has been heard
+    pub fn level_db(&self) -> Option<f32> {
+        self.level_db
+    }
+
+    /// `base` raised with the noise floor, from unchanged in a quiet room
+    /// up to `NOISY_THRESHOLD` in a loud one
+    pub fn adapt_threshold(&self, base: f32) -> f32 {
+        let Some(level) = self.level_db else {
+            return base;
+        };
+        let noisiness = ((level - QUIET_DB) / (NOISY_DB - QUIET_DB)).clamp(0.0, 1.0);
+        base + (NOISY_THRESHOLD - base).max(0.0) * noisiness
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(amplitude: f32) -> Vec<f32> {
+        (0..480)
+            .map(|i| if i % 2 == 0 { amplitude } else { -amplitude })
+            .collect()
+    }
+
+    #[test]
+    fn follows_quiet_fast_and_loud_slowly() {
+        let mut floor = NoiseFloor::new();
+        assert_eq!(floor.adapt_threshold(0.3), 0.3);
+
+        floor.push(&frame(0.01)); // -40 dB
+        assert!((floor.level_db().unwrap() + 40.0).abs() < 0.1);
+        floor.push(&frame(0.5));
+        assert!(floor.level_db().unwrap() < -39.5);
+        for _ in 0..50 {
+            floor.push(&frame(0.001)); // -60 dB
+        }
+        assert!(floor.level_db().unwrap() < -59.0);
+        floor.push(&[0.0; 480]);
+        assert!(floor.level_db().unwrap() < -59.0);
+    }
+
+    #[test]
+    fn raises_the_threshold_with_the_floor() {
+        let mut floor = NoiseFloor::new();
+        floor.push(&frame(0.0005));
+        assert_eq!(floor.adapt_threshold(0.3), 0.3);
+
+        let mut floor = NoiseFloor::new();
+        floor.push(&frame(0.0316)); // about -30 dB
+        assert!((floor.adapt_threshold(0.3) - NOISY_THRESHOLD).abs() < 0.01);
+
+        let mut floor = NoiseFloor::new();
+        floor.push(&frame(0.00316)); // about -50 dB
+        let adapted = floor.adapt_threshold(0.3);
+        assert!(adapted > 0.4 && adapted < 0.5, "{}", adapted);
+        assert_eq!(floor.adapt_threshold(0.9), 0.9);
+    }
+}
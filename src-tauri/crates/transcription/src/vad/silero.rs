@@ -4,7 +4,7 @@ use std::path::Path;
 use vad_rs::Vad;
 
 use super::{VadFrame, VoiceActivityDetector};
-use crate::audio_toolkit::constants;
+use crate::constants;
 
 const SILERO_FRAME_MS: u32 = 30;
 const SILERO_FRAME_SAMPLES: usize =
@@ -27,10 +27,9 @@ impl SileroVad {
             threshold,
         })
     }
-}
 
-impl VoiceActivityDetector for SileroVad {
-    fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>> {
+    /// The model's probability that `frame` is speech
+    pub fn probability(&mut self, frame: &[f32]) -> Result<f32> {
         if frame.len() != SILERO_FRAME_SAMPLES {
             anyhow::bail!(
                 "expected {SILERO_FRAME_SAMPLES} samples, got {}",
@@ -42,11 +41,20 @@ impl VoiceActivityDetector for SileroVad {
             .engine
             .compute(frame)
             .map_err(|e| anyhow::anyhow!("Silero VAD error: {e}"))?;
+        Ok(result.prob)
+    }
+}
 
-        if result.prob > self.threshold {
+impl VoiceActivityDetector for SileroVad {
+    fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>> {
+        if self.probability(frame)? > self.threshold {
             Ok(VadFrame::Speech(frame))
         } else {
             Ok(VadFrame::Noise)
         }
     }
+
+    fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold.clamp(0.0, 1.0);
+    }
 }
@@ -0,0 +1,421 @@
+//! Spotting spoken wake words, such as "Hey Handy", in the microphone
+//! input while nothing is recording.
+//!
+//! There's no pretrained model for a phrase the user picks, so the detector
+//! learns each one from a few recordings of the user saying it. Each
+//! stretch of speech short enough to be a wake word is compared with those
+//! recordings: both become MFCC frames, and dynamic time warping finds how
+//! far apart they are however fast each was said. Silence costs nothing
+//! beyond a level check per frame, so listening all day stays cheap.
+
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+use std::sync::Arc;
+
+use crate::audio::rms;
+use crate::constants::WHISPER_SAMPLE_RATE;
+
+const SAMPLE_RATE: f32 = WHISPER_SAMPLE_RATE as f32;
+/// Analysis windows of 25 ms, every 10 ms
+const WINDOW: usize = 400;
+const HOP: usize = 160;
+const FFT_SIZE: usize = 512;
+const MEL_BANDS: usize = 26;
+/// Cepstral coefficients kept, after dropping the first, which is loudness
+const CEPSTRA: usize = 12;
+const MIN_HZ: f32 = 100.0;
+const MAX_HZ: f32 = 7000.0;
+
+/// A frame is speech when it's this much louder than the room
+const SPEECH_OVER_NOISE: f32 = 3.0;
+/// ...and at least this loud, so a silent room doesn't make breaths speech
+const MIN_SPEECH_RMS: f32 = 0.004;
+/// How quickly the room level follows quiet frames
+const NOISE_ADAPT: f32 = 0.05;
+/// Silence that ends a stretch of speech
+const END_SILENCE_SECS: f32 = 0.3;
+/// Wake words take longer than this...
+const MIN_WAKE_SECS: f32 = 0.3;
+/// ...and no longer than this, anything longer is someone talking
+const MAX_WAKE_SECS: f32 = 2.0;
+/// Said twice as fast or slow as a recording, it's likely a different phrase
+const MAX_LENGTH_RATIO: f32 = 2.0;
+/// Range of the distance below which speech counts as the wake word, from
+/// the least to the most sensitive
+const STRICT_DISTANCE: f32 = 0.12;
+const LOOSE_DISTANCE: f32 = 0.3;
+
+type Features = Vec<[f32; CEPSTRA]>;
+
+struct Mfcc {
+    fft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    /// Weight of each FFT bin in each mel band
+    filters: Vec<Vec<(usize, f32)>>,
+}
+
+impl Mfcc {
+    fn new() -> Self {
+        let window = (0..WINDOW)
+            .map(|i| {
+                0.54 - 0.46 * (2.0 * std::f32::consts::PI * i as f32 / (WINDOW - 1) as f32).cos()
+            })
+            .collect();
+        let mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+        let hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+        let edges: Vec<f32> = (0..MEL_BANDS + 2)
+            .map(|i| {
+                let m =
+                    mel(MIN_HZ) + (mel(MAX_HZ) - mel(MIN_HZ)) * i as f32 / (MEL_BANDS + 1) as f32;
+                hz(m) * FFT_SIZE as f32 / SAMPLE_RATE
+            })
+            .collect();
+        let filters = (0..MEL_BANDS)
+            .map(|band| {
+                let (low, mid, high) = (edges[band], edges[band + 1], edges[band + 2]);
+                (low.floor() as usize..=high.ceil() as usize)
+                    .filter_map(|bin| {
+                        let b = bin as f32;
+                        let weight = if b <= mid {
+                            (b - low) / (mid - low)
+                        } else {
+                            (high - b) / (high - mid)
+                        };
+                        (weight > 0.0).then_some((bin, weight))
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            fft: FftPlanner::new().plan_fft_forward(FFT_SIZE),
+            window,
+            filters,
+        }
+    }
+
+    /// MFCC frames of `samples`, each with the recording's average taken
+    /// off so the microphone and room matter less
+    fn features(&self, samples: &[f32]) -> Features {
+        let mut frames: Features = Vec::new();
+        let mut buffer = vec![Complex::new(0.0, 0.0); FFT_SIZE];
+        let mut start = 0;
+        while start + WINDOW <= samples.len() {
+            buffer.fill(Complex::new(0.0, 0.0));
+            for (i, sample) in samples[start..start + WINDOW].iter().enumerate() {
+                buffer[i].re = sample * self.window[i];
+            }
+            self.fft.process(&mut buffer);
+            let energies: Vec<f32> = self
+                .filters
+                .iter()
+                .map(|filter| {
+                    let energy: f32 = filter
+                        .iter()
+                        .map(|&(bin, weight)| buffer[bin].norm_sqr() * weight)
+                        .sum();
+                    (energy + 1e-10).ln()
+                })
+                .collect();
+            let mut cepstra = [0.0; CEPSTRA];
+            for (k, c) in cepstra.iter_mut().enumerate() {
+                let k = k + 1;
+                *c = energies
+                    .iter()
+                    .enumerate()
+                    .map(|(n, e)| {
+                        e * (std::f32::consts::PI * k as f32 * (n as f32 + 0.5) / MEL_BANDS as f32)
+                            .cos()
+                    })
+                    .sum();
+            }
+            frames.push(cepstra);
+            start += HOP;
+        }
+
+        if !frames.is_empty() {
+            let mut mean = [0.0; CEPSTRA];
+            for frame in &frames {
+                for (m, c) in mean.iter_mut().zip(frame) {
+                    *m += c / frames.len() as f32;
+                }
+            }
+            for frame in &mut frames {
+                for (c, m) in frame.iter_mut().zip(&mean) {
+                    *c -= m;
+                }
+            }
+        }
+        frames
+    }
+}
+
+fn cosine_distance(a: &[f32; CEPSTRA], b: &[f32; CEPSTRA]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norms =
+        a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|y| y * y).sum::<f32>().sqrt();
+    if norms == 0.0 {
+        return 1.0;
+    }
+    1.0 - dot / norms
+}
+
+/// Average distance between the frames of `a` and `b` along the best
+/// alignment of the two
+fn dtw_distance(a: &Features, b: &Features) -> f32 {
+    if a.is_empty() || b.is_empty() {
+        return f32::INFINITY;
+    }
+    let ratio = a.len().max(b.len()) as f32 / a.len().min(b.len()) as f32;
+    if ratio > MAX_LENGTH_RATIO {
+        return f32::INFINITY;
+    }
+    let mut previous = vec![f32::INFINITY; b.len() + 1];
+    let mut current = vec![f32::INFINITY; b.len() + 1];
+    previous[0] = 0.0;
+    for frame_a in a {
+        current[0] = f32::INFINITY;
+        for (j, frame_b) in b.iter().enumerate() {
+            let best = previous[j].min(previous[j + 1]).min(current[j]);
+            current[j + 1] = cosine_distance(frame_a, frame_b) + best;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()] / (a.len() + b.len()) as f32
+}
+
+fn max_distance(sensitivity: f32) -> f32 {
+    STRICT_DISTANCE + (LOOSE_DISTANCE - STRICT_DISTANCE) * sensitivity.clamp(0.0, 1.0)
+}
+
+/// The part of `samples` between the first and last frame of speech, if
+/// there is any. The room's level is taken from the quietest frames, or
+/// half the loudest when the recording is all speech.
+pub fn trim_to_speech(samples: &[f32]) -> Option<&[f32]> {
+    let frame = HOP * 3;
+    let levels: Vec<f32> = samples.chunks(frame).map(rms).collect();
+    let mut sorted = levels.clone();
+    sorted.sort_by(f32::total_cmp);
+    let noise = *sorted.get(sorted.len() / 5)?;
+    let peak = sorted[sorted.len() - 1];
+    let threshold = (noise * SPEECH_OVER_NOISE)
+        .min(peak / 2.0)
+        .max(MIN_SPEECH_RMS);
+    let first = levels.iter().position(|&level| level > threshold)?;
+    let last = levels.iter().rposition(|&level| level > threshold)?;
+    Some(&samples[first * frame..((last + 1) * frame).min(samples.len())])
+}
+
+/// One wake word the detector listens for
+struct Phrase {
+    templates: Vec<Features>,
+    max_distance: f32,
+}
+
+pub struct WakeWordDetector {
+    mfcc: Mfcc,
+    phrases: Vec<Phrase>,
+    noise: f32,
+    /// The speech heard since it started, with trailing silence
+    burst: Vec<f32>,
+    silent_samples: usize,
+    /// The current speech already ran too long to be the wake word
+    too_long: bool,
+}
+
+impl WakeWordDetector {
+    /// A detector for each phrase in `phrases`, given as its recordings,
+    /// mono at 16 kHz, and its sensitivity. A phrase without speech in any
+    /// of its recordings is never heard, `None` if that's all of them.
+    pub fn new(phrases: &[(&[Vec<f32>], f32)]) -> Option<Self> {
+        let mfcc = Mfcc::new();
+        let phrases: Vec<Phrase> = phrases
+            .iter()
+            .map(|(recordings, sensitivity)| Phrase {
+                templates: recordings
+                    .iter()
+                    .filter_map(|recording| trim_to_speech(recording))
+                    .map(|speech| mfcc.features(speech))
+                    .filter(|features| !features.is_empty())
+                    .collect(),
+                max_distance: max_distance(*sensitivity),
+            })
+            .collect();
+        if phrases.iter().all(|phrase| phrase.templates.is_empty()) {
+            return None;
+        }
+        Some(Self {
+            mfcc,
+            phrases,
+            noise: MIN_SPEECH_RMS / SPEECH_OVER_NOISE,
+            burst: Vec::new(),
+            silent_samples: 0,
+            too_long: false,
+        })
+    }
+
+    /// From 0, only a close match, to 1, anything like phrase `phrase`
+    pub fn set_sensitivity(&mut self, phrase: usize, sensitivity: f32) {
+        if let Some(phrase) = self.phrases.get_mut(phrase) {
+            phrase.max_distance = max_distance(sensitivity);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.burst.clear();
+        self.silent_samples = 0;
+        self.too_long = false;
+    }
+
+    /// The phrase `speech` is closest to, of those it's close enough to
+    fn closest(&self, speech: &[f32]) -> Option<usize> {
+        let features = self.mfcc.features(speech);
+        self.phrases
+            .iter()
+            .enumerate()
+            .filter_map(|(index, phrase)| {
+                let distance = phrase
+                    .templates
+                    .iter()
+                    .map(|template| dtw_distance(&features, template))
+                    .fold(f32::INFINITY, f32::min);
+                (distance <= phrase.max_distance).then_some((index, distance))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+
+    /// Feeds a frame of 16 kHz input. Returns which phrase, by its index in
+    /// the ones given to `new`, a stretch of speech that just ended was.
+    pub fn push(&mut self, frame: &[f32]) -> Option<usize> {
+        let level = rms(frame);
+        let speech = level > (self.noise * SPEECH_OVER_NOISE).max(MIN_SPEECH_RMS);
+        if !speech {
+            self.noise += (level - self.noise) * NOISE_ADAPT;
+        }
+
+        if speech {
+            self.silent_samples = 0;
+            if !self.too_long {
+                self.burst.extend_from_slice(frame);
+                if self.burst.len() as f32 > MAX_WAKE_SECS * SAMPLE_RATE {
+                    self.too_long = true;
+                    self.burst.clear();
+                }
+            }
+            return None;
+        }
+        if self.burst.is_empty() && !self.too_long {
+            return None;
+        }
+
+        self.silent_samples += frame.len();
+        if !self.too_long {
+            self.burst.extend_from_slice(frame);
+        }
+        if (self.silent_samples as f32) < END_SILENCE_SECS * SAMPLE_RATE {
+            return None;
+        }
+
+        let spoken = self.burst.len().saturating_sub(self.silent_samples);
+        let detected = if !self.too_long && spoken as f32 >= MIN_WAKE_SECS * SAMPLE_RATE {
+            self.closest(&self.burst[..spoken])
+        } else {
+            None
+        };
+        if !self.too_long {
+            log::debug!(
+                "Heard {:.1}s of speech, {}",
+                spoken as f32 / SAMPLE_RATE,
+                match detected {
+                    Some(phrase) => format!("wake word {}", phrase),
+                    None => "not a wake word".to_string(),
+                }
+            );
+        }
+        self.reset();
+        detected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A made-up "word": a tone gliding from `from` to `to` Hz
+    fn glide(from: f32, to: f32, secs: f32) -> Vec<f32> {
+        let n = (secs * SAMPLE_RATE) as usize;
+        let mut phase = 0.0f32;
+        (0..n)
+            .map(|i| {
+                let hz = from + (to - from) * i as f32 / n as f32;
+                phase += 2.0 * std::f32::consts::PI * hz / SAMPLE_RATE;
+                0.3 * phase.sin() + 0.1 * (2.7 * phase).sin()
+            })
+            .collect()
+    }
+
+    fn with_silence(speech: Vec<f32>) -> Vec<f32> {
+        let silence = vec![0.0; (0.5 * SAMPLE_RATE) as usize];
+        [silence.clone(), speech, silence].concat()
+    }
+
+    /// The phrase feeding `input` in 30 ms frames detects, if any
+    fn detects(detector: &mut WakeWordDetector, input: &[f32]) -> Option<usize> {
+        input
+            .chunks(480)
+            .fold(None, |found, frame| detector.push(frame).or(found))
+    }
+
+    #[test]
+    fn trims_recordings_to_their_speech() {
+        let speech = glide(300.0, 900.0, 0.6);
+        let recording = with_silence(speech.clone());
+        let trimmed = trim_to_speech(&recording).unwrap();
+        let diff = (trimmed.len() as f32 - speech.len() as f32).abs();
+        assert!(diff <= (HOP * 3) as f32 * 2.0);
+        assert!(trim_to_speech(&vec![0.0; 16000]).is_none());
+    }
+
+    #[test]
+    fn spots_the_enrolled_phrase_at_another_pace() {
+        let enrolled = [with_silence(glide(300.0, 1200.0, 0.7))];
+        let mut detector = WakeWordDetector::new(&[(&enrolled, 0.5)]).unwrap();
+
+        assert_eq!(
+            detects(&mut detector, &with_silence(glide(300.0, 1200.0, 0.8))),
+            Some(0)
+        );
+        assert_eq!(
+            detects(&mut detector, &with_silence(glide(1500.0, 400.0, 0.7))),
+            None
+        );
+        // Too long to be the wake word, whatever it sounds like
+        assert_eq!(
+            detects(&mut detector, &with_silence(glide(300.0, 1200.0, 3.0))),
+            None
+        );
+    }
+
+    #[test]
+    fn tells_the_phrases_apart() {
+        let rising = [with_silence(glide(300.0, 1200.0, 0.7))];
+        let falling = [with_silence(glide(1500.0, 400.0, 0.7))];
+        let mut detector = WakeWordDetector::new(&[(&rising, 0.5), (&falling, 0.5)]).unwrap();
+
+        assert_eq!(
+            detects(&mut detector, &with_silence(glide(1500.0, 400.0, 0.8))),
+            Some(1)
+        );
+        assert_eq!(
+            detects(&mut detector, &with_silence(glide(300.0, 1200.0, 0.8))),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn needs_a_recording_with_speech() {
+        let silent = [vec![0.0; 16000]];
+        assert!(WakeWordDetector::new(&[(&silent, 0.5)]).is_none());
+        assert!(WakeWordDetector::new(&[]).is_none());
+    }
+}
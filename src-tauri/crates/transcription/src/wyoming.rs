@@ -0,0 +1,519 @@
+//! Client for the Wyoming protocol, used by Home Assistant's speech servers
+//! (wyoming-faster-whisper and friends), so transcription can run on another
+//! machine on the LAN.
+//!
+//! Every message is a JSON header line, optionally followed by `data_length`
+//! bytes of JSON data and `payload_length` bytes of binary payload.
+
+use crate::audio::to_pcm16;
+use crate::constants;
+use crate::engine::{self, Engine, Request};
+use crate::timing::TimedSegment;
+use anyhow::{anyhow, Context, Result};
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use log::debug;
+use serde_json::{json, Map, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Long recordings can take a while on a small server.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(120);
+/// 100ms of 16kHz audio per chunk
+const CHUNK_SAMPLES: usize = 1600;
+const SAMPLE_WIDTH: u32 = 2;
+
+#[derive(Debug, PartialEq)]
+struct Event {
+    kind: String,
+    data: Map<String, Value>,
+    payload: Vec<u8>,
+}
+
+impl Event {
+    fn new(kind: &str, data: Value) -> Self {
+        Self {
+            kind: kind.to_string(),
+            data: match data {
+                Value::Object(map) => map,
+                _ => Map::new(),
+            },
+            payload: Vec::new(),
+        }
+    }
+
+    fn write_to(&self, writer: &mut impl Write) -> Result<()> {
+        let data = serde_json::to_vec(&self.data)?;
+        let mut header = json!({
+            "type": self.kind,
+            "version": "1.5.2",
+            "data_length": data.len(),
+        });
+        if !self.payload.is_empty() {
+            header["payload_length"] = json!(self.payload.len());
+        }
+        let mut line = serde_json::to_vec(&header)?;
+        line.push(b'\n');
+        writer.write_all(&line)?;
+        writer.write_all(&data)?;
+        writer.write_all(&self.payload)?;
+        Ok(())
+    }
+
+    fn read_from(reader: &mut impl BufRead) -> Result<Self> {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(anyhow!("Wyoming server closed the connection"));
+        }
+        let header: Value = serde_json::from_str(&line).context("Invalid Wyoming header")?;
+        let kind = header["type"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Wyoming event without a type"))?
+            .to_string();
+
+        // Older servers put the data inline in the header
+        let mut data = header["data"].as_object().cloned().unwrap_or_default();
+        if let Some(len) = header["data_length"].as_u64().filter(|&len| len > 0) {
+            let mut buf = vec![0u8; len as usize];
+            reader.read_exact(&mut buf)?;
+            if let Value::Object(extra) = serde_json::from_slice(&buf)? {
+                data.extend(extra);
+            }
+        }
+        let mut payload = Vec::new();
+        if let Some(len) = header["payload_length"].as_u64().filter(|&len| len > 0) {
+            payload.resize(len as usize, 0);
+            reader.read_exact(&mut payload)?;
+        }
+
+        Ok(Self {
+            kind,
+            data,
+            payload,
+        })
+    }
+}
+
+fn connect(address: &str) -> Result<TcpStream> {
+    let addr = address
+        .to_socket_addrs()
+        .with_context(|| format!("Invalid Wyoming server address: {}", address))?
+        .next()
+        .ok_or_else(|| anyhow!("Couldn't resolve Wyoming server: {}", address))?;
+    let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)
+        .with_context(|| format!("Couldn't connect to Wyoming server at {}", address))?;
+    stream.set_read_timeout(Some(RESPONSE_TIMEOUT))?;
+    Ok(stream)
+}
+
+/// The Wyoming language for a language tag such as `de` or `zh-Hans`
+pub fn language(tag: &str) -> String {
+    match tag {
+        "zh-Hans" | "zh-Hant" => "zh".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// A timed piece of a transcript, for servers that send them
+#[derive(Clone, Debug, PartialEq)]
+pub struct TranscriptSegment {
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    pub text: String,
+}
+
+/// A server's transcript, normalized from whatever shape it came in.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Transcript {
+    pub text: String,
+    pub segments: Vec<TranscriptSegment>,
+    /// The language the server detected or used
+    pub language: Option<String>,
+    /// Length of the transcribed audio in seconds
+    pub duration: Option<f64>,
+}
+
+fn number(value: &Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+}
+
+/// Reads one segment, either a plain string or an object with the text under
+/// `text` and times as `start`/`end` or a `[start, end]` `timestamp`.
+fn segment_from(value: &Value) -> Option<TranscriptSegment> {
+    if let Some(text) = value.as_str() {
+        return Some(TranscriptSegment {
+            start: None,
+            end: None,
+            text: text.to_string(),
+        });
+    }
+    let object = value.as_object()?;
+    let text = ["text", "transcript", "sentence"]
+        .iter()
+        .find_map(|key| object.get(*key)?.as_str())?
+        .to_string();
+    let timestamp = object.get("timestamp").and_then(Value::as_array);
+    let time = |key: &str, index: usize| {
+        object
+            .get(key)
+            .and_then(number)
+            .or_else(|| timestamp.and_then(|t| t.get(index)).and_then(number))
+    };
+    Some(TranscriptSegment {
+        start: time("start", 0),
+        end: time("end", 1),
+        text,
+    })
+}
+
+/// The server's segments as subtitle timing, if it timed all of them
+pub fn timed_segments(segments: &[TranscriptSegment]) -> Option<Vec<TimedSegment>> {
+    let timed = segments
+        .iter()
+        .map(|segment| {
+            Some(TimedSegment {
+                start: segment.start? as f32,
+                end: segment.end? as f32,
+                text: segment.text.trim().to_string(),
+                words: Vec::new(),
+                speaker: None,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(timed).filter(|timed| !timed.is_empty())
+}
+
+/// Joins segment texts the way the server split them: most include their own
+/// leading space, so only add one where neither side has it.
+fn join_segments(segments: &[TranscriptSegment]) -> String {
+    let mut text = String::new();
+    for segment in segments {
+        let needs_space = !text.is_empty()
+            && !text.ends_with(char::is_whitespace)
+            && !segment.text.starts_with(char::is_whitespace);
+        if needs_space {
+            text.push(' ');
+        }
+        text.push_str(&segment.text);
+    }
+    text.trim().to_string()
+}
+
+/// Normalizes the data of a transcript event. Servers differ: the text may be
+/// a string, an array of strings or segments, or only present as `segments`
+/// or `chunks`, and unknown fields are ignored.
+fn transcript_from(data: &Map<String, Value>) -> Transcript {
+    let segments: Vec<TranscriptSegment> = ["segments", "chunks"]
+        .iter()
+        .find_map(|key| data.get(*key)?.as_array())
+        .or_else(|| data.get("text")?.as_array())
+        .map(|items| items.iter().filter_map(segment_from).collect())
+        .unwrap_or_default();
+    let text = ["text", "transcript", "result"]
+        .iter()
+        .find_map(|key| data.get(*key)?.as_str())
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .unwrap_or_else(|| join_segments(&segments));
+    let language = ["language", "lang"]
+        .iter()
+        .find_map(|key| data.get(*key)?.as_str())
+        .map(str::to_string);
+    let duration = data
+        .get("duration")
+        .and_then(number)
+        .or_else(|| segments.iter().filter_map(|s| s.end).reduce(f64::max));
+
+    Transcript {
+        text,
+        segments,
+        language,
+        duration,
+    }
+}
+
+/// Collects the events of a transcription response. Most servers send one
+/// `transcript`; streaming ones (Wyoming 1.6) send `transcript-start`, then
+/// `transcript-chunk`s, and `transcript-stop`, sometimes with a final
+/// `transcript` in between, which wins.
+#[derive(Default)]
+struct TranscriptReader {
+    /// Streamed text so far; chunks are deltas, joined as sent
+    text: String,
+    segments: Vec<TranscriptSegment>,
+    language: Option<String>,
+    streaming: bool,
+}
+
+impl TranscriptReader {
+    /// Takes the next event, returning the transcript once it is complete.
+    fn push(&mut self, event: &Event) -> Result<Option<Transcript>> {
+        match event.kind.as_str() {
+            "transcript" => {
+                let mut transcript = transcript_from(&event.data);
+                if transcript.language.is_none() {
+                    transcript.language = self.language.clone();
+                }
+                Ok(Some(transcript))
+            }
+            "transcript-start" => {
+                self.streaming = true;
+                self.language = transcript_from(&event.data).language;
+                Ok(None)
+            }
+            "transcript-chunk" => {
+                self.streaming = true;
+                match event.data.get("text").and_then(Value::as_str) {
+                    Some(delta) => self.text.push_str(delta),
+                    None => {
+                        let chunk = transcript_from(&event.data);
+                        self.text.push_str(&join_segments(&chunk.segments));
+                        self.segments.extend(chunk.segments);
+                    }
+                }
+                Ok(None)
+            }
+            "transcript-stop" if self.streaming => {
+                let segments = std::mem::take(&mut self.segments);
+                Ok(Some(Transcript {
+                    text: std::mem::take(&mut self.text).trim().to_string(),
+                    duration: segments.iter().filter_map(|s| s.end).reduce(f64::max),
+                    segments,
+                    language: self.language.take(),
+                }))
+            }
+            "error" => Err(anyhow!(
+                "Wyoming server error: {}",
+                event.data["text"].as_str().unwrap_or("unknown error")
+            )),
+            other => {
+                debug!("Ignoring Wyoming event: {}", other);
+                Ok(None)
+            }
+        }
+    }
+}
+
+fn transcribe_event(
+    language: Option<String>,
+    vocabulary: &[String],
+    prompt: Option<&str>,
+) -> Event {
+    let mut data = json!({ "language": language });
+    let mut context = serde_json::Map::new();
+    if !vocabulary.is_empty() {
+        context.insert("vocabulary".to_string(), json!(vocabulary));
+    }
+    if let Some(prompt) = prompt {
+        context.insert("prompt".to_string(), json!(prompt));
+    }
+    if !context.is_empty() {
+        data["context"] = context.into();
+    }
+    Event::new("transcribe", data)
+}
+
+/// Streams the recording to the server and waits for its transcript.
+/// `vocabulary` and `prompt` go along in the transcribe event's context, for
+/// servers that can bias recognition towards them; the others ignore them.
+pub fn transcribe(
+    address: &str,
+    samples: &[f32],
+    language: Option<String>,
+    vocabulary: &[String],
+    prompt: Option<&str>,
+) -> Result<Transcript> {
+    let started = Instant::now();
+    let mut stream = connect(address)?;
+    let audio_format = json!({
+        "rate": constants::WHISPER_SAMPLE_RATE,
+        "width": SAMPLE_WIDTH,
+        "channels": 1,
+    });
+
+    let mut writer = std::io::BufWriter::new(stream.try_clone()?);
+    transcribe_event(language, vocabulary, prompt).write_to(&mut writer)?;
+    Event::new("audio-start", audio_format.clone()).write_to(&mut writer)?;
+    for chunk in samples.chunks(CHUNK_SAMPLES) {
+        let mut event = Event::new("audio-chunk", audio_format.clone());
+        event.payload = to_pcm16(chunk);
+        event.write_to(&mut writer)?;
+    }
+    Event::new("audio-stop", json!({})).write_to(&mut writer)?;
+    writer.flush()?;
+
+    let mut reader = BufReader::new(&mut stream);
+    let mut transcript = TranscriptReader::default();
+    loop {
+        let event = Event::read_from(&mut reader)?;
+        if let Some(transcript) = transcript.push(&event)? {
+            debug!(
+                "Wyoming transcription took {}ms (language {:?}, {:?}s of audio)",
+                started.elapsed().as_millis(),
+                transcript.language,
+                transcript.duration
+            );
+            for segment in &transcript.segments {
+                debug!("  {:?}-{:?}s: {}", segment.start, segment.end, segment.text);
+            }
+            return Ok(transcript);
+        }
+    }
+}
+
+/// Asks the server to describe itself and returns the name of its speech
+/// recognition program, failing if it doesn't offer one.
+pub fn describe(address: &str) -> Result<String> {
+    let mut stream = connect(address)?;
+    Event::new("describe", json!({})).write_to(&mut stream)?;
+
+    let mut reader = BufReader::new(&mut stream);
+    loop {
+        let event = Event::read_from(&mut reader)?;
+        if event.kind != "info" {
+            continue;
+        }
+        return event.data["asr"]
+            .as_array()
+            .and_then(|programs| programs.first())
+            .map(|program| {
+                program["name"]
+                    .as_str()
+                    .unwrap_or("Wyoming ASR")
+                    .to_string()
+            })
+            .ok_or_else(|| anyhow!("{} doesn't offer speech recognition", address));
+    }
+}
+
+/// A Wyoming server behind the `Engine` interface. Recordings are sent on
+/// the thread that polls the future, which blocks until the transcript is in.
+pub struct Client {
+    /// `host:port` to connect to
+    pub address: String,
+}
+
+impl Engine for Client {
+    fn name(&self) -> &str {
+        "Wyoming"
+    }
+
+    fn transcribe<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<engine::Transcript>> {
+        async move {
+            let transcript = transcribe(
+                &self.address,
+                request.samples,
+                request.language.map(language),
+                request.vocabulary,
+                request.prompt,
+            )?;
+            Ok(engine::Transcript {
+                segments: timed_segments(&transcript.segments).unwrap_or_default(),
+                text: transcript.text,
+            })
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn round_trips_events_with_payload() {
+        let mut event = Event::new("audio-chunk", json!({ "rate": 16000 }));
+        event.payload = vec![1, 2, 3, 4];
+
+        let mut buf = Vec::new();
+        event.write_to(&mut buf).unwrap();
+        let parsed = Event::read_from(&mut Cursor::new(buf)).unwrap();
+
+        assert_eq!(parsed, event);
+    }
+
+    #[test]
+    fn reads_inline_data_from_older_servers() {
+        let line = b"{\"type\": \"transcript\", \"data\": {\"text\": \"hello\"}}\n";
+        let event = Event::read_from(&mut Cursor::new(line.to_vec())).unwrap();
+
+        assert_eq!(event.kind, "transcript");
+        assert_eq!(event.data["text"], "hello");
+    }
+
+    fn data(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn normalizes_transcripts_of_different_shapes() {
+        let plain = transcript_from(&data(json!({ "text": " hello ", "extra": 1 })));
+        assert_eq!(plain.text, "hello");
+        assert!(plain.segments.is_empty());
+
+        let segmented = transcript_from(&data(json!({
+            "segments": [
+                { "start": 0.0, "end": 1.5, "text": " Hello" },
+                { "timestamp": [1.5, "3.25"], "text": " world." }
+            ],
+            "language": "en"
+        })));
+        assert_eq!(segmented.text, "Hello world.");
+        assert_eq!(segmented.segments[1].start, Some(1.5));
+        assert_eq!(segmented.language.as_deref(), Some("en"));
+        assert_eq!(segmented.duration, Some(3.25));
+
+        let array = transcript_from(&data(json!({ "text": ["one", "two"] })));
+        assert_eq!(array.text, "one two");
+    }
+
+    #[test]
+    fn assembles_streamed_transcripts() {
+        let mut reader = TranscriptReader::default();
+        let events = [
+            Event::new("transcript-start", json!({ "language": "de" })),
+            Event::new("transcript-chunk", json!({ "text": "Guten" })),
+            Event::new("transcript-chunk", json!({ "text": " Tag" })),
+            Event::new("transcript-chunk", json!({ "text": "!" })),
+        ];
+        for event in &events {
+            assert_eq!(reader.push(event).unwrap(), None);
+        }
+        let done = reader
+            .push(&Event::new("transcript-stop", json!({})))
+            .unwrap()
+            .unwrap();
+        assert_eq!(done.text, "Guten Tag!");
+        assert_eq!(done.language.as_deref(), Some("de"));
+
+        // A stop without a stream before it isn't a transcript
+        let mut reader = TranscriptReader::default();
+        assert_eq!(
+            reader
+                .push(&Event::new("transcript-stop", json!({})))
+                .unwrap(),
+            None
+        );
+        assert!(reader
+            .push(&Event::new("error", json!({ "text": "busy" })))
+            .is_err());
+    }
+
+    #[test]
+    fn sends_vocabulary_only_when_there_is_some() {
+        let event = transcribe_event(Some("en".to_string()), &[], None);
+        assert!(!event.data.contains_key("context"));
+        let event = transcribe_event(None, &["Tauri".to_string()], None);
+        assert_eq!(event.data["context"], json!({ "vocabulary": ["Tauri"] }));
+        let event = transcribe_event(None, &[], Some("A talk about Rust."));
+        assert_eq!(
+            event.data["context"],
+            json!({ "prompt": "A talk about Rust." })
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Spoken state announcements for screen reader users. Handy mostly works
+//! from the background, so without these a blind user has no way of telling
+//! whether a recording started, stopped or produced any text.
+//!
+//! On macOS the announcement goes straight to VoiceOver through
+//! `NSAccessibilityAnnouncementRequestedNotification`, and on Windows it's a
+//! UI Automation notification event raised on the main window, which doesn't
+//! need the window showing. Elsewhere it's sent to one showing webview, the
+//! overlay before the main window, which renders it in an `aria-live` region
+//! that AT-SPI passes on to the screen reader. Nothing is announced from the
+//! background there, so the setting is off by default.
+
+use crate::settings::get_settings;
+use crate::tray_i18n::get_tray_translations;
+use log::debug;
+use tauri::AppHandle;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Announcement {
+    RecordingStarted,
+    Transcribing,
+    /// Pasted a transcript with the given number of words
+    Transcribed(usize),
+    NothingTranscribed,
+    TranscriptionFailed,
+    PasteFailed,
+    Cancelled,
+}
+
+pub fn announce(app: &AppHandle, announcement: Announcement) {
+    let settings = get_settings(app);
+    if !settings.screen_reader_announcements {
+        return;
+    }
+
+    let strings = get_tray_translations(Some(settings.app_language));
+    let message = match announcement {
+        Announcement::RecordingStarted => strings.announce_recording_started,
+        Announcement::Transcribing => strings.announce_transcribing,
+        Announcement::Transcribed(words) => strings
+            .announce_transcribed
+            .replace("{count}", &words.to_string()),
+        Announcement::NothingTranscribed => strings.announce_nothing_transcribed,
+        Announcement::TranscriptionFailed => strings.notification_transcription_failed,
+        Announcement::PasteFailed => strings.notification_paste_failed,
+        Announcement::Cancelled => strings.announce_cancelled,
+    };
+    debug!("Announcing: {}", message);
+    post(app, message);
+}
+
+pub fn word_count(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+#[cfg(target_os = "macos")]
+fn post(app: &AppHandle, message: String) {
+    use objc2::runtime::AnyObject;
+    use objc2::MainThreadMarker;
+    use objc2_app_kit::{
+        NSAccessibilityAnnouncementKey, NSAccessibilityAnnouncementRequestedNotification,
+        NSAccessibilityPostNotificationWithUserInfo, NSAccessibilityPriorityKey,
+        NSAccessibilityPriorityLevel, NSApplication,
+    };
+    use objc2_foundation::{NSDictionary, NSNumber, NSString};
+
+    let result = app.run_on_main_thread(move || {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return;
+        };
+        let ns_app = NSApplication::sharedApplication(mtm);
+        let text = NSString::from_str(&message);
+        let priority = NSNumber::new_isize(NSAccessibilityPriorityLevel::High.0);
+        unsafe {
+            let keys = [NSAccessibilityAnnouncementKey, NSAccessibilityPriorityKey];
+            let values: [&AnyObject; 2] = [text.as_ref(), priority.as_ref()];
+            let user_info = NSDictionary::from_slices(&keys, &values);
+            let element: &AnyObject = ns_app.as_ref();
+            NSAccessibilityPostNotificationWithUserInfo(
+                element,
+                NSAccessibilityAnnouncementRequestedNotification,
+                Some(&user_info),
+            );
+        }
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to post accessibility announcement: {}", e);
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn post(app: &AppHandle, message: String) {
+    use tauri::Manager;
+    use windows::core::BSTR;
+    use windows::Win32::UI::Accessibility::{
+        NotificationKind_Other, NotificationProcessing_ImportantMostRecent,
+        UiaHostProviderFromHwnd, UiaRaiseNotificationEvent,
+    };
+
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let result = window.clone().run_on_main_thread(move || {
+        let Ok(hwnd) = window.hwnd() else {
+            return;
+        };
+        let raised = unsafe {
+            UiaHostProviderFromHwnd(hwnd).and_then(|provider| {
+                UiaRaiseNotificationEvent(
+                    &provider,
+                    NotificationKind_Other,
+                    NotificationProcessing_ImportantMostRecent,
+                    &BSTR::from(message.as_str()),
+                    &BSTR::from("HandyStatus"),
+                )
+            })
+        };
+        if let Err(e) = raised {
+            log::warn!("Failed to raise accessibility notification: {}", e);
+        }
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to post accessibility announcement: {}", e);
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn post(app: &AppHandle, message: String) {
+    use tauri::{Emitter, Manager};
+
+    // Only one live region gets it, or the screen reader reads it twice
+    let Some(label) = ["recording_overlay", "main"].into_iter().find(|label| {
+        app.get_webview_window(label)
+            .is_some_and(|window| window.is_visible().unwrap_or(false))
+    }) else {
+        debug!("No window showing to announce in");
+        return;
+    };
+    if let Err(e) = app.emit_to(label, "accessibility-announcement", message) {
+        log::warn!("Failed to emit accessibility announcement: {}", e);
+    }
+}
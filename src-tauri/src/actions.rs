@@ -1,22 +1,46 @@
+use crate::accessibility::{self, Announcement};
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
 use crate::apple_intelligence;
+use crate::atspi;
 use crate::audio_feedback::{play_feedback_sound, play_feedback_sound_blocking, SoundType};
+use crate::context_rules;
+use crate::countdown;
+use crate::helpers::active_app::{active_app, active_window_title};
 use crate::managers::audio::AudioRecordingManager;
-use crate::managers::history::HistoryManager;
-use crate::managers::transcription::TranscriptionManager;
-use crate::settings::{get_settings, AppSettings, APPLE_INTELLIGENCE_PROVIDER_ID};
+use crate::managers::captions::CaptionsManager;
+use crate::managers::history::{DictationContext, HistoryManager};
+use crate::managers::plugins::PluginManager;
+use crate::managers::transcription::{Transcription, TranscriptionManager};
+use crate::network;
+use crate::notifications;
+use crate::openai_realtime;
+use crate::power;
+use crate::preview;
+use crate::processing;
+use crate::recording_status;
+use crate::settings::{
+    get_settings, AppSettings, BindingOutput, ShortcutBinding, APPLE_INTELLIGENCE_PROVIDER_ID,
+    DICTATION_BINDING_PREFIX,
+};
 use crate::shortcut;
+use crate::templates;
+use crate::transcription_provider;
 use crate::tray::{change_tray_icon, TrayIconState};
+use crate::tts;
 use crate::utils::{self, show_recording_overlay, show_transcribing_overlay};
+use crate::voice_commands;
 use crate::ManagedToggleState;
 use ferrous_opencc::{config::BuiltinConfig, OpenCC};
 use log::{debug, error};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
 use std::sync::Arc;
 use std::time::Instant;
 use tauri::AppHandle;
 use tauri::Manager;
+use tauri_plugin_clipboard_manager::ClipboardExt;
 
 // Shortcut Action Trait
 pub trait ShortcutAction: Send + Sync {
@@ -28,6 +52,7 @@ pub trait ShortcutAction: Send + Sync {
 struct TranscribeAction;
 
 async fn maybe_post_process_transcription(
+    app: &AppHandle,
     settings: &AppSettings,
     transcription: &str,
 ) -> Option<String> {
@@ -90,8 +115,13 @@ async fn maybe_post_process_transcription(
         provider.id, model
     );
 
-    // Replace ${output} variable in the prompt with the actual text
-    let processed_prompt = prompt.replace("${output}", transcription);
+    // A prompt with ${output} has the transcript put in its place. One
+    // without is a system prompt, and the transcript follows as the message.
+    let (system_prompt, processed_prompt) = if prompt.contains("${output}") {
+        (None, prompt.replace("${output}", transcription))
+    } else {
+        (Some(prompt), transcription.to_string())
+    };
     debug!("Processed prompt length: {} chars", processed_prompt.len());
 
     if provider.id == APPLE_INTELLIGENCE_PROVIDER_ID {
@@ -103,6 +133,10 @@ async fn maybe_post_process_transcription(
             }
 
             let token_limit = model.trim().parse::<i32>().unwrap_or(0);
+            let processed_prompt = match &system_prompt {
+                Some(system) => format!("{}\n\n{}", system, processed_prompt),
+                None => processed_prompt,
+            };
             return match apple_intelligence::process_text(&processed_prompt, token_limit) {
                 Ok(result) => {
                     if result.trim().is_empty() {
@@ -130,15 +164,16 @@ async fn maybe_post_process_transcription(
         }
     }
 
-    let api_key = settings
-        .post_process_api_keys
-        .get(&provider.id)
-        .cloned()
-        .unwrap_or_default();
-
     // Send the chat completion request
-    match crate::llm_client::send_chat_completion(&provider, api_key, &model, processed_prompt)
-        .await
+    match crate::llm_client::send_chat_completion(
+        app,
+        &provider,
+        &model,
+        system_prompt,
+        processed_prompt,
+        settings.post_process_temperature,
+    )
+    .await
     {
         Ok(Some(content)) => {
             debug!(
@@ -158,6 +193,7 @@ async fn maybe_post_process_transcription(
                 provider.id,
                 e
             );
+            notifications::notify_post_process_failed(app, &e.to_string());
             None
         }
     }
@@ -207,77 +243,405 @@ async fn maybe_convert_chinese_variant(
     }
 }
 
-impl ShortcutAction for TranscribeAction {
-    fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
-        let start_time = Instant::now();
-        debug!("TranscribeAction::start called for binding: {}", binding_id);
+/// Transcribes recorded samples, post-processes and saves the result, then
+/// pastes it (or reads it back first). Also used to retry a failed recording.
+/// The Cancel shortcut drops it at any point before the paste.
+///
+/// The language, engine, model, translation, custom words, prompt and output
+/// `binding` has set for itself are used over the settings.
+pub async fn transcribe_and_paste(
+    ah: &AppHandle,
+    samples: Vec<f32>,
+    binding: Option<ShortcutBinding>,
+) {
+    if processing::run(ah, transcribe_and_paste_inner(ah, samples, binding))
+        .await
+        .is_none()
+    {
+        debug!("Dictation cancelled while processing");
+    }
+}
 
-        // Load model in the background
-        let tm = app.state::<Arc<TranscriptionManager>>();
-        tm.initiate_model_load();
+/// Puts `text` on the clipboard or appends it to a file, for bindings set up
+/// to do either instead of pasting
+fn deliver(ah: &AppHandle, binding: &ShortcutBinding, text: &str) -> Result<(), String> {
+    match binding.output {
+        BindingOutput::Paste => unreachable!("pasted on the main thread instead"),
+        BindingOutput::Clipboard => ah
+            .clipboard()
+            .write_text(text)
+            .map_err(|e| format!("Failed to copy to clipboard: {}", e)),
+        BindingOutput::File => {
+            let path = binding
+                .output_file
+                .as_deref()
+                .ok_or("No file is set to write dictations to")?;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| format!("Failed to open {}: {}", path, e))?;
+            writeln!(file, "{}", text).map_err(|e| format!("Failed to write to {}: {}", path, e))
+        }
+    }
+}
 
-        let binding_id = binding_id.to_string();
-        change_tray_icon(app, TrayIconState::Recording);
-        show_recording_overlay(app);
+async fn transcribe_and_paste_inner(
+    ah: &AppHandle,
+    samples: Vec<f32>,
+    binding: Option<ShortcutBinding>,
+) {
+    let tm = Arc::clone(&ah.state::<Arc<TranscriptionManager>>());
+    let hm = Arc::clone(&ah.state::<Arc<HistoryManager>>());
+    let mut transcribe_settings = get_settings(ah);
+    let remote_selected = transcribe_settings.remote_enabled();
+    if let Some(binding) = &binding {
+        binding.apply_overrides(&mut transcribe_settings);
+    }
+    let mut model = binding.as_ref().and_then(|binding| binding.model.clone());
+    // No local model is kept loaded while the settings use a remote engine
+    if model.is_none() && remote_selected && !transcribe_settings.remote_enabled() {
+        model = Some(power::effective_model_id(ah, &transcribe_settings));
+    }
+    context_rules::apply_transcription(
+        &mut transcribe_settings,
+        active_app().as_deref(),
+        active_window_title().as_deref(),
+    );
+
+    let transcription_time = Instant::now();
+    let samples_clone = samples.clone(); // Clone for history saving
+    let result = match openai_realtime::finish(ah, &samples).await {
+        Some(text) => Ok(Transcription {
+            text,
+            ..Default::default()
+        }),
+        None => {
+            // Off the async runtime, so a cancel doesn't have to wait for the model
+            let tm_clone = Arc::clone(&tm);
+            let settings = transcribe_settings.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                tm_clone.transcribe_with_model(samples, settings, model)
+            })
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("Transcription task failed: {}", e)))
+        }
+    };
+    match result {
+        Ok(Transcription {
+            text: transcription,
+            usage,
+            engine,
+            segments,
+        }) => {
+            debug!(
+                "Transcription completed in {:?}: '{}'",
+                transcription_time.elapsed(),
+                transcription
+            );
+            if !transcription.is_empty() {
+                let mut settings = transcribe_settings.clone();
+                let target_app = active_app();
+                context_rules::apply(
+                    &mut settings,
+                    target_app.as_deref(),
+                    active_window_title().as_deref(),
+                );
+                // Some shortcuts have their own prompt, or paste what was
+                // said without an LLM pass
+                if let Some(binding) = &binding {
+                    binding.apply_post_process(&mut settings);
+                }
+                // Spoken editing commands are carried out before anything else
+                let spoken = voice_commands::run(ah, &settings, &transcription);
+                if spoken.is_empty() {
+                    utils::hide_recording_overlay(ah);
+                    notifications::notify_transcribed(ah, &transcription);
+                    change_tray_icon(ah, TrayIconState::Idle);
+                    return;
+                }
+                let mut final_text = spoken.clone();
+                let mut post_processed_text = (spoken != transcription).then(|| spoken.clone());
+                let mut post_process_prompt: Option<String> = None;
+                let mut post_process_prompt_id: Option<String> = None;
+
+                // First, check if Chinese variant conversion is needed
+                if let Some(converted_text) =
+                    maybe_convert_chinese_variant(&settings, &spoken).await
+                {
+                    final_text = converted_text.clone();
+                    post_processed_text = Some(converted_text);
+                }
+                // Then apply regular post-processing if enabled
+                else if let Some(processed_text) =
+                    maybe_post_process_transcription(ah, &settings, &spoken).await
+                {
+                    final_text = processed_text.clone();
+                    post_processed_text = Some(processed_text);
+
+                    // Get the prompt that was used
+                    if let Some(prompt_id) = &settings.post_process_selected_prompt_id {
+                        if let Some(prompt) = settings
+                            .post_process_prompts
+                            .iter()
+                            .find(|p| &p.id == prompt_id)
+                        {
+                            post_process_prompt = Some(prompt.prompt.clone());
+                            post_process_prompt_id = Some(prompt.id.clone());
+                        }
+                    }
+                }
 
-        let rm = app.state::<Arc<AudioRecordingManager>>();
+                // A template's trigger or slot types the template's text instead
+                if let Some(text) =
+                    templates::apply(ah, &settings.dictation_templates, &spoken, &final_text)
+                {
+                    final_text = text;
+                    post_processed_text = Some(final_text.clone());
+                }
 
-        // Get the microphone mode to determine audio feedback timing
-        let settings = get_settings(app);
-        let is_always_on = settings.always_on_microphone;
-        debug!("Microphone mode - always_on: {}", is_always_on);
+                // Custom processing steps from plugins run last
+                let pm = Arc::clone(&ah.state::<Arc<PluginManager>>());
+                let plugin_text = pm.transform(final_text.clone());
+                if plugin_text != final_text {
+                    final_text = plugin_text;
+                    post_processed_text = Some(final_text.clone());
+                }
 
-        let mut recording_started = false;
-        if is_always_on {
-            // Always-on mode: Play audio feedback immediately, then apply mute after sound finishes
-            debug!("Always-on mode: Playing audio feedback immediately");
-            let rm_clone = Arc::clone(&rm);
+                // Save to history with post-processed text and prompt
+                let hm_clone = Arc::clone(&hm);
+                let transcription_for_history = transcription.clone();
+                let context = DictationContext {
+                    target_app: target_app.clone(),
+                    language: Some(settings.selected_language.clone()),
+                    post_process_prompt_id,
+                    resource_usage: usage,
+                    engine,
+                    segments,
+                };
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = hm_clone
+                        .save_transcription(
+                            samples_clone,
+                            transcription_for_history,
+                            post_processed_text,
+                            post_process_prompt,
+                            context,
+                        )
+                        .await
+                    {
+                        error!("Failed to save transcription to history: {}", e);
+                    }
+                });
+
+                // An output plugin may deliver the text somewhere else instead
+                if pm.route(&final_text) {
+                    utils::hide_recording_overlay(ah);
+                    notifications::notify_transcribed(ah, &final_text);
+                    change_tray_icon(ah, TrayIconState::Idle);
+                } else if let Some(binding) = binding
+                    .as_ref()
+                    .filter(|binding| binding.output != BindingOutput::Paste)
+                {
+                    // The shortcut sends its dictations somewhere other than
+                    // the focused app
+                    utils::hide_recording_overlay(ah);
+                    match deliver(ah, binding, &final_text) {
+                        Ok(()) => notifications::notify_transcribed(ah, &final_text),
+                        Err(e) => {
+                            error!("Failed to deliver transcription: {}", e);
+                            notifications::notify_paste_failed(ah, final_text);
+                        }
+                    }
+                    change_tray_icon(ah, TrayIconState::Idle);
+                } else if preview::wants_preview(&settings, target_app.as_deref()) {
+                    // Let the user check and edit the text before it goes out
+                    preview::begin_preview(ah, final_text);
+                } else if settings.tts_readback_enabled {
+                    // Read the text back first if confirmation is enabled
+                    tts::begin_readback(ah, final_text);
+                } else {
+                    // Paste the final text (either processed or original)
+                    let ah_clone = ah.clone();
+                    let paste_time = Instant::now();
+                    ah.run_on_main_thread(move || {
+                        let pasted = utils::paste(final_text.clone(), ah_clone.clone());
+                        // Hide the overlay after transcription is complete, so
+                        // it's free for a notice
+                        utils::hide_recording_overlay(&ah_clone);
+                        match pasted {
+                            Ok(()) => {
+                                debug!("Text pasted successfully in {:?}", paste_time.elapsed());
+                                notifications::notify_transcribed(&ah_clone, &final_text);
+                                accessibility::announce(
+                                    &ah_clone,
+                                    Announcement::Transcribed(accessibility::word_count(
+                                        &final_text,
+                                    )),
+                                );
+                            }
+                            Err(e) => {
+                                error!("Failed to paste transcription: {}", e);
+                                accessibility::announce(&ah_clone, Announcement::PasteFailed);
+                                notifications::notify_paste_failed(&ah_clone, final_text);
+                            }
+                        }
+                        change_tray_icon(&ah_clone, TrayIconState::Idle);
+                    })
+                    .unwrap_or_else(|e| {
+                        error!("Failed to run paste on main thread: {:?}", e);
+                        utils::hide_recording_overlay(ah);
+                        change_tray_icon(ah, TrayIconState::Idle);
+                    });
+                }
+            } else {
+                accessibility::announce(ah, Announcement::NothingTranscribed);
+                utils::hide_recording_overlay(ah);
+                change_tray_icon(ah, TrayIconState::Idle);
+            }
+        }
+        Err(err) => {
+            debug!("Global Shortcut Transcription error: {}", err);
+            accessibility::announce(ah, Announcement::TranscriptionFailed);
+            // Without a connection the recording waits for one instead
+            let unreachable = transcription_provider::is_unreachable(&err);
+            if unreachable {
+                network::mark_unreachable();
+            }
+            let queued = unreachable
+                && match tm.queue_pending(&samples_clone, &transcribe_settings, active_app()) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        error!("Failed to keep the recording for later: {}", e);
+                        false
+                    }
+                };
+            utils::hide_recording_overlay(ah);
+            if queued {
+                notifications::notify_queued_offline(ah);
+                change_tray_icon(ah, TrayIconState::Idle);
+            } else {
+                notifications::notify_transcription_failed(ah, samples_clone, &err.to_string());
+                change_tray_icon(ah, TrayIconState::Error);
+            }
+        }
+    }
+}
+
+/// Starts recording for `binding_id`, returning whether the microphone could
+/// be opened.
+fn start_transcribe_recording(app: &AppHandle, binding_id: &str) -> bool {
+    let start_time = Instant::now();
+
+    // Load model in the background
+    let tm = app.state::<Arc<TranscriptionManager>>();
+    tm.initiate_model_load();
+
+    let binding_id = binding_id.to_string();
+    change_tray_icon(app, TrayIconState::Recording);
+    show_recording_overlay(app);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.apply_conferencing_guard();
+
+    // Get the microphone mode to determine audio feedback timing
+    let is_always_on = rm.is_always_on();
+    debug!("Microphone mode - always_on: {}", is_always_on);
+
+    let mut recording_started = false;
+    if is_always_on {
+        // Always-on mode: Play audio feedback immediately, then apply mute after sound finishes
+        debug!("Always-on mode: Playing audio feedback immediately");
+        let rm_clone = Arc::clone(&rm);
+        let app_clone = app.clone();
+        // The blocking helper exits immediately if audio feedback is disabled,
+        // so we can always reuse this thread to ensure mute happens right after playback.
+        std::thread::spawn(move || {
+            play_feedback_sound_blocking(&app_clone, SoundType::Start);
+            rm_clone.apply_mute();
+        });
+
+        recording_started = rm.try_start_recording(&binding_id);
+        debug!("Recording started: {}", recording_started);
+    } else {
+        // On-demand mode: Start recording first, then play audio feedback, then apply mute
+        // This allows the microphone to be activated before playing the sound
+        debug!("On-demand mode: Starting recording first, then audio feedback");
+        let recording_start_time = Instant::now();
+        if rm.try_start_recording(&binding_id) {
+            recording_started = true;
+            debug!("Recording started in {:?}", recording_start_time.elapsed());
+            // Small delay to ensure microphone stream is active
             let app_clone = app.clone();
-            // The blocking helper exits immediately if audio feedback is disabled,
-            // so we can always reuse this thread to ensure mute happens right after playback.
+            let rm_clone = Arc::clone(&rm);
             std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                debug!("Handling delayed audio feedback/mute sequence");
+                // Helper handles disabled audio feedback by returning early, so we reuse it
+                // to keep mute sequencing consistent in every mode.
                 play_feedback_sound_blocking(&app_clone, SoundType::Start);
                 rm_clone.apply_mute();
             });
-
-            recording_started = rm.try_start_recording(&binding_id);
-            debug!("Recording started: {}", recording_started);
         } else {
-            // On-demand mode: Start recording first, then play audio feedback, then apply mute
-            // This allows the microphone to be activated before playing the sound
-            debug!("On-demand mode: Starting recording first, then audio feedback");
-            let recording_start_time = Instant::now();
-            if rm.try_start_recording(&binding_id) {
-                recording_started = true;
-                debug!("Recording started in {:?}", recording_start_time.elapsed());
-                // Small delay to ensure microphone stream is active
-                let app_clone = app.clone();
-                let rm_clone = Arc::clone(&rm);
-                std::thread::spawn(move || {
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                    debug!("Handling delayed audio feedback/mute sequence");
-                    // Helper handles disabled audio feedback by returning early, so we reuse it
-                    // to keep mute sequencing consistent in every mode.
-                    play_feedback_sound_blocking(&app_clone, SoundType::Start);
-                    rm_clone.apply_mute();
-                });
-            } else {
-                debug!("Failed to start recording");
-            }
+            debug!("Failed to start recording");
         }
+    }
+
+    if recording_started {
+        // Dynamically register the cancel shortcut in a separate task to avoid deadlock
+        shortcut::register_cancel_shortcut(app);
+        atspi::prefetch();
+        recording_status::start_ticker(app);
+        openai_realtime::start(app, &binding_id);
+        accessibility::announce(app, Announcement::RecordingStarted);
+    }
+
+    debug!(
+        "Recording start for '{}' took {:?}",
+        binding_id,
+        start_time.elapsed()
+    );
+    recording_started
+}
+
+impl ShortcutAction for TranscribeAction {
+    fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+        debug!("TranscribeAction::start called for binding: {}", binding_id);
 
-        if recording_started {
-            // Dynamically register the cancel shortcut in a separate task to avoid deadlock
-            shortcut::register_cancel_shortcut(app);
+        let secs = countdown::countdown_secs(app, binding_id);
+        if secs == 0 {
+            start_transcribe_recording(app, binding_id);
+            return;
         }
 
-        debug!(
-            "TranscribeAction::start completed in {:?}",
-            start_time.elapsed()
-        );
+        // The model loads while counting down, and Cancel works already
+        app.state::<Arc<TranscriptionManager>>()
+            .initiate_model_load();
+        change_tray_icon(app, TrayIconState::Recording);
+        show_recording_overlay(app);
+        shortcut::register_cancel_shortcut(app);
+        let ah = app.clone();
+        let id = binding_id.to_string();
+        countdown::begin(app, binding_id, secs, move || {
+            if !start_transcribe_recording(&ah, &id) {
+                shortcut::unregister_cancel_shortcut(&ah);
+                // Otherwise the next press would try to stop a recording
+                // that never started
+                shortcut::reset_toggle(&ah, &id);
+            }
+        });
     }
 
     fn stop(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+        // Stopping before the countdown ran out means there's nothing to
+        // transcribe
+        if countdown::cancel() {
+            shortcut::unregister_cancel_shortcut(app);
+            utils::hide_recording_overlay(app);
+            change_tray_icon(app, TrayIconState::Idle);
+            return;
+        }
+
         // Unregister the cancel shortcut when transcription stops
         shortcut::unregister_cancel_shortcut(app);
 
@@ -286,8 +650,6 @@ impl ShortcutAction for TranscribeAction {
 
         let ah = app.clone();
         let rm = Arc::clone(&app.state::<Arc<AudioRecordingManager>>());
-        let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
-        let hm = Arc::clone(&app.state::<Arc<HistoryManager>>());
 
         change_tray_icon(app, TrayIconState::Transcribing);
         show_transcribing_overlay(app);
@@ -297,6 +659,7 @@ impl ShortcutAction for TranscribeAction {
 
         // Play audio feedback for recording stop
         play_feedback_sound(app, SoundType::Stop);
+        accessibility::announce(app, Announcement::Transcribing);
 
         let binding_id = binding_id.to_string(); // Clone binding_id for the async task
 
@@ -315,95 +678,8 @@ impl ShortcutAction for TranscribeAction {
                     samples.len()
                 );
 
-                let transcription_time = Instant::now();
-                let samples_clone = samples.clone(); // Clone for history saving
-                match tm.transcribe(samples) {
-                    Ok(transcription) => {
-                        debug!(
-                            "Transcription completed in {:?}: '{}'",
-                            transcription_time.elapsed(),
-                            transcription
-                        );
-                        if !transcription.is_empty() {
-                            let settings = get_settings(&ah);
-                            let mut final_text = transcription.clone();
-                            let mut post_processed_text: Option<String> = None;
-                            let mut post_process_prompt: Option<String> = None;
-
-                            // First, check if Chinese variant conversion is needed
-                            if let Some(converted_text) =
-                                maybe_convert_chinese_variant(&settings, &transcription).await
-                            {
-                                final_text = converted_text.clone();
-                                post_processed_text = Some(converted_text);
-                            }
-                            // Then apply regular post-processing if enabled
-                            else if let Some(processed_text) =
-                                maybe_post_process_transcription(&settings, &transcription).await
-                            {
-                                final_text = processed_text.clone();
-                                post_processed_text = Some(processed_text);
-
-                                // Get the prompt that was used
-                                if let Some(prompt_id) = &settings.post_process_selected_prompt_id {
-                                    if let Some(prompt) = settings
-                                        .post_process_prompts
-                                        .iter()
-                                        .find(|p| &p.id == prompt_id)
-                                    {
-                                        post_process_prompt = Some(prompt.prompt.clone());
-                                    }
-                                }
-                            }
-
-                            // Save to history with post-processed text and prompt
-                            let hm_clone = Arc::clone(&hm);
-                            let transcription_for_history = transcription.clone();
-                            tauri::async_runtime::spawn(async move {
-                                if let Err(e) = hm_clone
-                                    .save_transcription(
-                                        samples_clone,
-                                        transcription_for_history,
-                                        post_processed_text,
-                                        post_process_prompt,
-                                    )
-                                    .await
-                                {
-                                    error!("Failed to save transcription to history: {}", e);
-                                }
-                            });
-
-                            // Paste the final text (either processed or original)
-                            let ah_clone = ah.clone();
-                            let paste_time = Instant::now();
-                            ah.run_on_main_thread(move || {
-                                match utils::paste(final_text, ah_clone.clone()) {
-                                    Ok(()) => debug!(
-                                        "Text pasted successfully in {:?}",
-                                        paste_time.elapsed()
-                                    ),
-                                    Err(e) => error!("Failed to paste transcription: {}", e),
-                                }
-                                // Hide the overlay after transcription is complete
-                                utils::hide_recording_overlay(&ah_clone);
-                                change_tray_icon(&ah_clone, TrayIconState::Idle);
-                            })
-                            .unwrap_or_else(|e| {
-                                error!("Failed to run paste on main thread: {:?}", e);
-                                utils::hide_recording_overlay(&ah);
-                                change_tray_icon(&ah, TrayIconState::Idle);
-                            });
-                        } else {
-                            utils::hide_recording_overlay(&ah);
-                            change_tray_icon(&ah, TrayIconState::Idle);
-                        }
-                    }
-                    Err(err) => {
-                        debug!("Global Shortcut Transcription error: {}", err);
-                        utils::hide_recording_overlay(&ah);
-                        change_tray_icon(&ah, TrayIconState::Idle);
-                    }
-                }
+                let binding = get_settings(&ah).bindings.get(&binding_id).cloned();
+                transcribe_and_paste(&ah, samples, binding).await;
             } else {
                 debug!("No samples retrieved from recording stop");
                 utils::hide_recording_overlay(&ah);
@@ -436,6 +712,38 @@ impl ShortcutAction for CancelAction {
     }
 }
 
+// Live Captions Action
+struct CaptionsAction;
+
+impl ShortcutAction for CaptionsAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Captions always toggle on press, regardless of push-to-talk
+        let cm = app.state::<Arc<CaptionsManager>>();
+        if cm.is_active() {
+            cm.stop();
+        } else if let Err(e) = cm.start() {
+            error!("Failed to start live captions: {}", e);
+        }
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Nothing to do on stop, captions toggle on press
+    }
+}
+
+// Accept Read-back Action
+struct AcceptReadbackAction;
+
+impl ShortcutAction for AcceptReadbackAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        tts::accept_readback(app);
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Nothing to do on stop for accept
+    }
+}
+
 // Test Action
 struct TestAction;
 
@@ -459,6 +767,17 @@ impl ShortcutAction for TestAction {
     }
 }
 
+/// The action behind `binding_id`. Dictation shortcuts the user added all
+/// transcribe.
+pub fn action_for(binding_id: &str) -> Option<Arc<dyn ShortcutAction>> {
+    let id = if binding_id.starts_with(DICTATION_BINDING_PREFIX) {
+        "transcribe"
+    } else {
+        binding_id
+    };
+    ACTION_MAP.get(id).cloned()
+}
+
 // Static Action Map
 pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::new(|| {
     let mut map = HashMap::new();
@@ -466,10 +785,22 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
         "transcribe".to_string(),
         Arc::new(TranscribeAction) as Arc<dyn ShortcutAction>,
     );
+    map.insert(
+        "translate".to_string(),
+        Arc::new(TranscribeAction) as Arc<dyn ShortcutAction>,
+    );
     map.insert(
         "cancel".to_string(),
         Arc::new(CancelAction) as Arc<dyn ShortcutAction>,
     );
+    map.insert(
+        "accept_readback".to_string(),
+        Arc::new(AcceptReadbackAction) as Arc<dyn ShortcutAction>,
+    );
+    map.insert(
+        "toggle_captions".to_string(),
+        Arc::new(CaptionsAction) as Arc<dyn ShortcutAction>,
+    );
     map.insert(
         "test".to_string(),
         Arc::new(TestAction) as Arc<dyn ShortcutAction>,
@@ -0,0 +1,293 @@
+//! An OpenAI-compatible transcription endpoint on localhost, so other apps on
+//! this computer can use Handy's models.
+//!
+//! `POST /v1/audio/transcriptions` takes the multipart form OpenAI's API
+//! does: the recording as `file`, and optionally `model`, `language` and
+//! `response_format` (`json`, `text` or `verbose_json`). A `model` naming one
+//! of the downloaded models uses that one, anything else (such as
+//! `whisper-1`) uses the selected model. Audio never leaves the computer,
+//! even when dictation uses a remote engine. Requests need the token from
+//! settings as a bearer token.
+
+use crate::managers::model::ModelManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::get_settings;
+use anyhow::Result;
+use axum::extract::{DefaultBodyLimit, Multipart, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use handy_transcription::timing::TimedSegment;
+use log::{error, info, warn};
+use serde_json::json;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use subtle::ConstantTimeEq;
+use tauri::{AppHandle, Manager};
+use tokio::sync::oneshot;
+
+/// Uploads above this are turned away. OpenAI's API stops at 25 MB, this
+/// leaves room for long uncompressed WAV files
+const MAX_UPLOAD_BYTES: usize = 200 * 1024 * 1024;
+/// Times binding the port is tried while a stopped server lets go of it
+const BIND_ATTEMPTS: u32 = 5;
+
+/// Stops the running server when sent to or dropped
+pub type ManagedApiServer = Mutex<Option<oneshot::Sender<()>>>;
+
+#[derive(Clone)]
+struct Server {
+    app: AppHandle,
+    token: String,
+}
+
+/// A new random token for the server's clients to authenticate with, from
+/// the OS's secure random source
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).expect("The OS random number generator failed");
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Stops the server if it runs, and starts it again if it's enabled, so it
+/// picks up changed settings.
+pub fn restart(app: &AppHandle) {
+    let state = app.state::<ManagedApiServer>();
+    if let Some(stop) = state.lock().unwrap().take() {
+        let _ = stop.send(());
+    }
+
+    let settings = get_settings(app);
+    if !settings.api_server_enabled {
+        return;
+    }
+    let (stop, stopped) = oneshot::channel();
+    *state.lock().unwrap() = Some(stop);
+    let server = Server {
+        app: app.clone(),
+        token: settings.api_server_token,
+    };
+    let port = settings.api_server_port;
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = serve(server, port, stopped).await {
+            error!("Transcription API server stopped: {}", e);
+        }
+    });
+}
+
+async fn serve(server: Server, port: u16, stopped: oneshot::Receiver<()>) -> Result<()> {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let mut attempt = 1;
+    let listener = loop {
+        match tokio::net::TcpListener::bind(address).await {
+            Ok(listener) => break listener,
+            Err(e) if attempt < BIND_ATTEMPTS => {
+                warn!("Couldn't listen on {} yet: {}", address, e);
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    };
+    info!("Serving transcriptions on http://{}/v1", address);
+
+    let router = Router::new()
+        .route("/v1/audio/transcriptions", post(transcriptions))
+        .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES))
+        .with_state(server);
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async {
+            let _ = stopped.await;
+        })
+        .await?;
+    info!("Transcription API server on {} stopped", address);
+    Ok(())
+}
+
+/// What a request asked for
+#[derive(Default)]
+struct Upload {
+    file: Vec<u8>,
+    file_name: Option<String>,
+    model: Option<String>,
+    language: Option<String>,
+    response_format: Option<String>,
+}
+
+fn api_error(status: StatusCode, message: &str) -> Response {
+    let kind = if status.is_client_error() {
+        "invalid_request_error"
+    } else {
+        "server_error"
+    };
+    (
+        status,
+        Json(json!({ "error": { "message": message, "type": kind } })),
+    )
+        .into_response()
+}
+
+/// Whether the request carries `token` as its bearer token. The comparison
+/// takes as long wherever the tokens differ, so timing gives nothing away.
+fn authorized(headers: &HeaderMap, token: &str) -> bool {
+    !token.is_empty()
+        && headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|given| bool::from(given.trim().as_bytes().ct_eq(token.as_bytes())))
+}
+
+async fn read_upload(mut multipart: Multipart) -> Result<Upload, String> {
+    let mut upload = Upload::default();
+    while let Some(field) = multipart.next_field().await.map_err(|e| e.to_string())? {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "file" {
+            upload.file_name = field.file_name().map(str::to_string);
+            upload.file = field.bytes().await.map_err(|e| e.to_string())?.to_vec();
+            continue;
+        }
+        let value = field.text().await.map_err(|e| e.to_string())?;
+        let value = Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        match name.as_str() {
+            "model" => upload.model = value,
+            "language" => upload.language = value,
+            "response_format" => upload.response_format = value,
+            // The prompt, temperature and the like have no equivalent here
+            _ => {}
+        }
+    }
+    if upload.file.is_empty() {
+        return Err("No audio file in the request".to_string());
+    }
+    Ok(upload)
+}
+
+async fn transcriptions(
+    State(server): State<Server>,
+    headers: HeaderMap,
+    multipart: Multipart,
+) -> Response {
+    if !authorized(&headers, &server.token) {
+        return api_error(StatusCode::UNAUTHORIZED, "Invalid or missing API token");
+    }
+    let upload = match read_upload(multipart).await {
+        Ok(upload) => upload,
+        Err(e) => return api_error(StatusCode::BAD_REQUEST, &e),
+    };
+    let format = upload
+        .response_format
+        .clone()
+        .unwrap_or_else(|| "json".to_string());
+    if !matches!(format.as_str(), "json" | "text" | "verbose_json") {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            "response_format must be json, text or verbose_json",
+        );
+    }
+
+    let language = upload.language.clone();
+    let app = server.app.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || transcribe(&app, upload)).await;
+    let (text, segments) = match result {
+        Ok(Ok(transcript)) => transcript,
+        Ok(Err(e)) => return api_error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+        Err(e) => return api_error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    };
+
+    match format.as_str() {
+        "text" => text.into_response(),
+        "verbose_json" => {
+            let segments = segments.unwrap_or_default();
+            let duration = segments.last().map_or(0.0, |segment| segment.end);
+            Json(json!({
+                "task": "transcribe",
+                "language": language,
+                "duration": duration,
+                "text": text,
+                "segments": segments
+                    .iter()
+                    .enumerate()
+                    .map(|(id, segment)| json!({
+                        "id": id,
+                        "start": segment.start,
+                        "end": segment.end,
+                        "text": segment.text,
+                    }))
+                    .collect::<Vec<_>>(),
+            }))
+            .into_response()
+        }
+        _ => Json(json!({ "text": text })).into_response(),
+    }
+}
+
+/// The upload's text, and its timing when the engine gives it
+fn transcribe(app: &AppHandle, upload: Upload) -> Result<(String, Option<Vec<TimedSegment>>)> {
+    // The decoder goes by the file's extension, so the upload keeps it
+    let extension = upload
+        .file_name
+        .as_deref()
+        .and_then(|name| Path::new(name).extension())
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("wav")
+        .to_ascii_lowercase();
+    // The system temp dir is shared between OS users on Linux
+    let dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| anyhow::anyhow!("Failed to get app cache dir: {}", e))?;
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("api-upload-{}.{}", generate_token(), extension));
+    std::fs::write(&path, &upload.file)?;
+    let decoded = handy_transcription::audio::read_audio_file_with_rate(&path);
+    let _ = std::fs::remove_file(&path);
+    let (samples, _) = decoded?;
+
+    let mut settings = get_settings(app);
+    if let Some(language) = upload.language {
+        settings.selected_language = language;
+    }
+    let model = upload.model.filter(|id| {
+        app.state::<Arc<ModelManager>>()
+            .get_model_info(id)
+            .is_some_and(|model| model.is_downloaded)
+    });
+    let tm = app.state::<Arc<TranscriptionManager>>();
+    if model.is_none() {
+        tm.initiate_model_load();
+    }
+    let transcription = tm.transcribe_locally(samples, settings, model)?;
+    Ok((transcription.text, transcription.segments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn checks_the_bearer_token() {
+        let mut headers = HeaderMap::new();
+        assert!(!authorized(&headers, "secret"));
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer secret"),
+        );
+        assert!(authorized(&headers, "secret"));
+        assert!(!authorized(&headers, "other"));
+        // An empty token never lets anyone in
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer "));
+        assert!(!authorized(&headers, ""));
+    }
+
+    #[test]
+    fn tokens_are_random() {
+        let token = generate_token();
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, generate_token());
+    }
+}
@@ -0,0 +1,189 @@
+//! Per-application dictation statistics, built from the target app recorded
+//! with each history entry, and suggestions for per-app defaults when one app
+//! keeps getting the same language or post-processing prompt.
+
+use serde::Serialize;
+use specta::Type;
+use std::collections::HashMap;
+
+/// An app needs this many dictations before anything is suggested for it
+const MIN_DICTATIONS: usize = 10;
+/// Share of an app's dictations a value needs to be worth suggesting
+const MIN_SHARE: f32 = 0.8;
+
+/// One dictation, as recorded in history
+#[derive(Clone, Debug)]
+pub struct UsageRecord {
+    pub app: String,
+    pub timestamp: i64,
+    pub words: usize,
+    pub language: Option<String>,
+    pub prompt_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Type, PartialEq)]
+pub struct AppUsageStats {
+    pub app: String,
+    pub dictations: usize,
+    pub words: usize,
+    pub last_used: i64,
+    /// Language used most often, if any was recorded
+    pub top_language: Option<String>,
+    /// Post-processing prompt used most often, if any
+    pub top_prompt_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Type, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestedSetting {
+    Language,
+    PostProcessPrompt,
+}
+
+/// "You almost always use `value` in `app`, make it the default there?"
+#[derive(Clone, Debug, Serialize, Type, PartialEq)]
+pub struct AppDefaultSuggestion {
+    pub app: String,
+    pub setting: SuggestedSetting,
+    pub value: String,
+    /// Fraction of the app's dictations that used `value`
+    pub share: f32,
+    pub dictations: usize,
+}
+
+/// The most common value and how often it occurred
+fn most_common<'a>(values: impl Iterator<Item = Option<&'a str>>) -> Option<(&'a str, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in values.flatten() {
+        *counts.entry(value).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+}
+
+fn by_app(records: &[UsageRecord]) -> Vec<(&str, Vec<&UsageRecord>)> {
+    let mut apps: HashMap<&str, Vec<&UsageRecord>> = HashMap::new();
+    for record in records {
+        apps.entry(record.app.as_str()).or_default().push(record);
+    }
+    let mut apps: Vec<_> = apps.into_iter().collect();
+    apps.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+    apps
+}
+
+/// Statistics per app, most used first
+pub fn app_stats(records: &[UsageRecord]) -> Vec<AppUsageStats> {
+    by_app(records)
+        .into_iter()
+        .map(|(app, records)| AppUsageStats {
+            app: app.to_string(),
+            dictations: records.len(),
+            words: records.iter().map(|r| r.words).sum(),
+            last_used: records.iter().map(|r| r.timestamp).max().unwrap_or(0),
+            top_language: most_common(records.iter().map(|r| r.language.as_deref()))
+                .map(|(value, _)| value.to_string()),
+            top_prompt_id: most_common(records.iter().map(|r| r.prompt_id.as_deref()))
+                .map(|(value, _)| value.to_string()),
+        })
+        .collect()
+}
+
+/// Suggests a per-app language or prompt where an app gets the same one
+/// nearly every time, and it isn't already the global default.
+pub fn suggest_defaults(
+    records: &[UsageRecord],
+    default_language: &str,
+    default_prompt_id: Option<&str>,
+) -> Vec<AppDefaultSuggestion> {
+    let mut suggestions = Vec::new();
+    for (app, records) in by_app(records) {
+        let dictations = records.len();
+        if dictations < MIN_DICTATIONS {
+            continue;
+        }
+
+        let candidates = [
+            (
+                SuggestedSetting::Language,
+                most_common(records.iter().map(|r| r.language.as_deref())),
+                Some(default_language),
+            ),
+            (
+                SuggestedSetting::PostProcessPrompt,
+                most_common(records.iter().map(|r| r.prompt_id.as_deref())),
+                default_prompt_id,
+            ),
+        ];
+        for (setting, top, default) in candidates {
+            let Some((value, count)) = top else {
+                continue;
+            };
+            let share = count as f32 / dictations as f32;
+            if share >= MIN_SHARE && Some(value) != default {
+                suggestions.push(AppDefaultSuggestion {
+                    app: app.to_string(),
+                    setting,
+                    value: value.to_string(),
+                    share,
+                    dictations,
+                });
+            }
+        }
+    }
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(app: &str, timestamp: i64, language: &str, prompt_id: Option<&str>) -> UsageRecord {
+        UsageRecord {
+            app: app.to_string(),
+            timestamp,
+            words: 5,
+            language: Some(language.to_string()),
+            prompt_id: prompt_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn aggregates_per_app() {
+        let records = [
+            record("Slack", 10, "en", None),
+            record("Code", 20, "en", Some("code")),
+            record("Code", 30, "en", Some("code")),
+        ];
+        let stats = app_stats(&records);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].app, "Code");
+        assert_eq!(stats[0].dictations, 2);
+        assert_eq!(stats[0].words, 10);
+        assert_eq!(stats[0].last_used, 30);
+        assert_eq!(stats[0].top_prompt_id.as_deref(), Some("code"));
+        assert_eq!(stats[1].top_prompt_id, None);
+    }
+
+    #[test]
+    fn suggests_consistent_non_default_choices() {
+        let mut records: Vec<UsageRecord> = (0..9)
+            .map(|i| record("Code", i, "en", Some("code")))
+            .collect();
+        records.push(record("Code", 9, "de", None));
+        records.extend((0..12).map(|i| record("Slack", i, "en", None)));
+
+        let suggestions = suggest_defaults(&records, "en", None);
+        assert_eq!(
+            suggestions,
+            vec![AppDefaultSuggestion {
+                app: "Code".to_string(),
+                setting: SuggestedSetting::PostProcessPrompt,
+                value: "code".to_string(),
+                share: 0.9,
+                dictations: 10,
+            }]
+        );
+        assert!(suggest_defaults(&records[..9], "en", None).is_empty());
+    }
+}
@@ -0,0 +1,404 @@
+//! Inserting text through AT-SPI, the Linux accessibility bus, for targets
+//! that mishandle the synthetic events the other paste methods send over
+//! XTEST or a Wayland helper. GTK 4 apps, many of them Flatpaks, drop or
+//! garble a synthetic Ctrl+V and the keys typed directly.
+//!
+//! The focused text field is found by walking the accessibility tree of the
+//! active window, and the text is inserted at its caret through its
+//! `EditableText` interface, the way an on-screen keyboard would. Nothing
+//! is pressed, so the clipboard and keyboard layout don't matter.
+//!
+//! The same field's text is read back by `focused_text`, which the paste
+//! check compares before and after pasting.
+//!
+//! Whether an app wants its text this way is looked up by `prefetch` on its
+//! own thread when a recording starts, and kept per app, so pasting only
+//! reads the answer and never walks the tree itself.
+//!
+//! Other platforms have no AT-SPI; `insert_text` fails there,
+//! `prefers_accessibility` is always false and `focused_text` is `None`.
+
+/// Whether an app built with `toolkit` at `version`, as its AT-SPI
+/// application reports them, is better served by inserting through AT-SPI
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn mishandles_synthetic_paste(toolkit: &str, version: &str) -> bool {
+    let major = version
+        .split('.')
+        .next()
+        .and_then(|major| major.trim().parse::<u32>().ok());
+    toolkit.eq_ignore_ascii_case("gtk") && major.is_some_and(|major| major >= 4)
+}
+
+#[cfg(target_os = "linux")]
+mod bus {
+    use log::debug;
+    use once_cell::sync::Lazy;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+    use zbus::proxy::CacheProperties;
+    use zbus::zvariant::OwnedObjectPath;
+    use zbus::{proxy, Connection};
+
+    /// Gives up on apps that don't answer, so a paste isn't held up
+    const TIMEOUT: Duration = Duration::from_secs(2);
+    /// Stops the walk in huge trees, like a browser with many tabs
+    const MAX_NODES: usize = 5000;
+    /// Smaller limits for working out an app's toolkit, which is only a hint
+    const LOOKUP_TIMEOUT: Duration = Duration::from_millis(500);
+    const LOOKUP_MAX_NODES: usize = 1000;
+    /// A found field is reused by the paste check and the paste just after
+    const REUSE_FOR: Duration = Duration::from_secs(2);
+
+    const REGISTRY: &str = "org.a11y.atspi.Registry";
+    const ROOT_PATH: &str = "/org/a11y/atspi/accessible/root";
+    const EDITABLE_TEXT: &str = "org.a11y.atspi.EditableText";
+
+    // Bits of the AT-SPI state set
+    const STATE_ACTIVE: u32 = 1;
+    const STATE_EDITABLE: u32 = 7;
+    const STATE_FOCUSED: u32 = 12;
+    const STATE_SHOWING: u32 = 25;
+
+    #[proxy(
+        interface = "org.a11y.Bus",
+        default_service = "org.a11y.Bus",
+        default_path = "/org/a11y/bus"
+    )]
+    trait Bus {
+        fn get_address(&self) -> zbus::Result<String>;
+    }
+
+    #[proxy(interface = "org.a11y.atspi.Accessible")]
+    trait Accessible {
+        fn get_children(&self) -> zbus::Result<Vec<(String, OwnedObjectPath)>>;
+        fn get_state(&self) -> zbus::Result<Vec<u32>>;
+        fn get_interfaces(&self) -> zbus::Result<Vec<String>>;
+        fn get_application(&self) -> zbus::Result<(String, OwnedObjectPath)>;
+    }
+
+    #[proxy(interface = "org.a11y.atspi.Application")]
+    trait Application {
+        #[zbus(property)]
+        fn toolkit_name(&self) -> zbus::Result<String>;
+        #[zbus(property)]
+        fn version(&self) -> zbus::Result<String>;
+    }
+
+    #[proxy(interface = "org.a11y.atspi.Text")]
+    trait Text {
+        #[zbus(property)]
+        fn caret_offset(&self) -> zbus::Result<i32>;
+        fn get_text(&self, start_offset: i32, end_offset: i32) -> zbus::Result<String>;
+    }
+
+    #[proxy(interface = "org.a11y.atspi.EditableText")]
+    trait EditableText {
+        fn insert_text(&self, position: i32, text: &str, length: i32) -> zbus::Result<bool>;
+    }
+
+    /// An accessible object: the bus name of its app and its path
+    type Object = (String, OwnedObjectPath);
+
+    /// The field last found, with when it was found
+    static LAST_FOUND: Mutex<Option<(Instant, Object)>> = Mutex::new(None);
+
+    /// Whether each app, by name, should get its text over AT-SPI
+    pub static PREFERS: Lazy<Mutex<HashMap<String, bool>>> = Lazy::new(Default::default);
+
+    fn has_state(states: &[u32], state: u32) -> bool {
+        states
+            .get((state / 32) as usize)
+            .is_some_and(|bits| bits & (1 << (state % 32)) != 0)
+    }
+
+    async fn connect() -> zbus::Result<Connection> {
+        let address = match std::env::var("AT_SPI_BUS_ADDRESS") {
+            Ok(address) => address,
+            Err(_) => {
+                let session = Connection::session().await?;
+                BusProxy::new(&session).await?.get_address().await?
+            }
+        };
+        zbus::connection::Builder::address(address.as_str())?
+            .build()
+            .await
+    }
+
+    async fn accessible<'a>(
+        conn: &'a Connection,
+        (name, path): &'a Object,
+    ) -> zbus::Result<AccessibleProxy<'a>> {
+        AccessibleProxy::builder(conn)
+            .destination(name.as_str())?
+            .path(path)?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await
+    }
+
+    /// The focused editable field of the active window, if it has one
+    async fn focused_field(conn: &Connection, max_nodes: usize) -> zbus::Result<Option<Object>> {
+        let root = (REGISTRY.to_string(), OwnedObjectPath::try_from(ROOT_PATH)?);
+        let mut stack = Vec::new();
+        // Only the active window of each app is searched
+        for app in accessible(conn, &root).await?.get_children().await? {
+            let Ok(app_proxy) = accessible(conn, &app).await else {
+                continue;
+            };
+            for window in app_proxy.get_children().await.unwrap_or_default() {
+                let active = match accessible(conn, &window).await {
+                    Ok(proxy) => proxy
+                        .get_state()
+                        .await
+                        .is_ok_and(|states| has_state(&states, STATE_ACTIVE)),
+                    Err(_) => false,
+                };
+                if active {
+                    stack.push(window);
+                }
+            }
+        }
+
+        let mut visited = 0;
+        while let Some(object) = stack.pop() {
+            visited += 1;
+            if visited > max_nodes {
+                debug!(
+                    "Gave up looking for the focused field after {} nodes",
+                    max_nodes
+                );
+                break;
+            }
+            let Ok(proxy) = accessible(conn, &object).await else {
+                continue;
+            };
+            let Ok(states) = proxy.get_state().await else {
+                continue;
+            };
+            if has_state(&states, STATE_FOCUSED) && has_state(&states, STATE_EDITABLE) {
+                let editable = proxy
+                    .get_interfaces()
+                    .await
+                    .is_ok_and(|interfaces| interfaces.iter().any(|i| i == EDITABLE_TEXT));
+                if editable {
+                    return Ok(Some(object.clone()));
+                }
+            }
+            if has_state(&states, STATE_SHOWING) {
+                stack.extend(proxy.get_children().await.unwrap_or_default());
+            }
+        }
+        Ok(None)
+    }
+
+    /// Whether `object` still has the keyboard focus
+    async fn still_focused(conn: &Connection, object: &Object) -> bool {
+        match accessible(conn, object).await {
+            Ok(proxy) => proxy
+                .get_state()
+                .await
+                .is_ok_and(|states| has_state(&states, STATE_FOCUSED)),
+            Err(_) => false,
+        }
+    }
+
+    /// The focused field, reusing the one just found if it still has focus
+    async fn find(conn: &Connection, max_nodes: usize) -> zbus::Result<Option<Object>> {
+        let last = LAST_FOUND.lock().unwrap().clone();
+        if let Some((found_at, object)) = last {
+            if found_at.elapsed() < REUSE_FOR && still_focused(conn, &object).await {
+                return Ok(Some(object));
+            }
+        }
+        let found = focused_field(conn, max_nodes).await?;
+        *LAST_FOUND.lock().unwrap() = found.clone().map(|object| (Instant::now(), object));
+        Ok(found)
+    }
+
+    async fn toolkit(conn: &Connection) -> zbus::Result<Option<(String, String)>> {
+        let Some(field) = find(conn, LOOKUP_MAX_NODES).await? else {
+            return Ok(None);
+        };
+        let (name, path) = accessible(conn, &field).await?.get_application().await?;
+        let app = ApplicationProxy::builder(conn)
+            .destination(name.as_str())?
+            .path(&path)?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+        Ok(Some((app.toolkit_name().await?, app.version().await?)))
+    }
+
+    /// Inserts `text` at the caret of `field`. Returns whether it took it.
+    async fn insert_at_caret(conn: &Connection, field: &Object, text: &str) -> zbus::Result<bool> {
+        let (name, path) = field;
+        let caret = TextProxy::builder(conn)
+            .destination(name.as_str())?
+            .path(path)?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?
+            .caret_offset()
+            .await
+            // Fields without a caret take the text at the start
+            .unwrap_or(0);
+        EditableTextProxy::builder(conn)
+            .destination(name.as_str())?
+            .path(path)?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?
+            // The length is in bytes
+            .insert_text(caret.max(0), text, text.len() as i32)
+            .await
+    }
+
+    /// The whole text of the focused field
+    async fn read(conn: &Connection) -> zbus::Result<Option<String>> {
+        let Some((name, path)) = find(conn, MAX_NODES).await? else {
+            return Ok(None);
+        };
+        let text = TextProxy::builder(conn)
+            .destination(name.as_str())?
+            .path(&path)?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?
+            // An end of -1 is the end of the text
+            .get_text(0, -1)
+            .await?;
+        Ok(Some(text))
+    }
+
+    async fn insert(conn: &Connection, text: &str) -> Result<(), String> {
+        let field = find(conn, MAX_NODES)
+            .await
+            .map_err(|e| format!("Failed to search the accessibility tree: {}", e))?
+            .ok_or("No focused text field was found on the accessibility bus")?;
+        // The caret moves with the text, the field is looked up again next time
+        LAST_FOUND.lock().unwrap().take();
+        match insert_at_caret(conn, &field, text).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err("The focused field refused the text".to_string()),
+            Err(e) => Err(format!("Failed to insert text over AT-SPI: {}", e)),
+        }
+    }
+
+    fn run<T>(timeout: Duration, fallback: T, task: impl std::future::Future<Output = T>) -> T {
+        tauri::async_runtime::block_on(async {
+            tokio::time::timeout(timeout, task)
+                .await
+                .unwrap_or(fallback)
+        })
+    }
+
+    pub fn toolkit_of_focused() -> Option<(String, String)> {
+        run(LOOKUP_TIMEOUT, None, async {
+            let conn = connect().await.ok()?;
+            toolkit(&conn).await.ok().flatten()
+        })
+    }
+
+    pub fn focused_text() -> Option<String> {
+        run(TIMEOUT, None, async {
+            let conn = connect().await.ok()?;
+            read(&conn).await.ok().flatten()
+        })
+    }
+
+    pub fn insert_text(text: &str) -> Result<(), String> {
+        run(
+            TIMEOUT,
+            Err("The accessibility bus didn't answer in time".to_string()),
+            async {
+                let conn = connect()
+                    .await
+                    .map_err(|e| format!("Failed to connect to the accessibility bus: {}", e))?;
+                insert(&conn, text).await
+            },
+        )
+    }
+}
+
+/// Whether `target_app` should get its text through AT-SPI rather than
+/// synthetic events, as found by `prefetch`. Apps not looked up yet don't.
+pub fn prefers_accessibility(target_app: Option<&str>) -> bool {
+    #[cfg(target_os = "linux")]
+    return target_app.is_some_and(|app| {
+        bus::PREFERS
+            .lock()
+            .unwrap()
+            .get(app)
+            .copied()
+            .unwrap_or(false)
+    });
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = target_app;
+        false
+    }
+}
+
+/// Works out on another thread whether the focused app should get its text
+/// through AT-SPI, for `prefers_accessibility` to answer when pasting. Each
+/// app is only looked up until its toolkit is known.
+pub fn prefetch() {
+    #[cfg(target_os = "linux")]
+    std::thread::spawn(|| {
+        let Some(app) = crate::helpers::active_app::active_app() else {
+            return;
+        };
+        if bus::PREFERS.lock().unwrap().contains_key(&app) {
+            return;
+        }
+        // Not found or too slow to answer, tried again next time
+        let Some((toolkit, version)) = bus::toolkit_of_focused() else {
+            return;
+        };
+        let prefers = mishandles_synthetic_paste(&toolkit, &version);
+        if prefers {
+            log::debug!(
+                "{} is a {} {} app, inserting over AT-SPI",
+                app,
+                toolkit,
+                version
+            );
+        }
+        bus::PREFERS.lock().unwrap().insert(app, prefers);
+    });
+}
+
+/// Inserts `text` at the caret of the focused text field
+pub fn insert_text(text: &str) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    return bus::insert_text(text);
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = text;
+        Err("AT-SPI is only available on Linux".to_string())
+    }
+}
+
+/// The text of the focused field, read over AT-SPI
+#[cfg_attr(any(target_os = "macos", target_os = "windows"), allow(dead_code))]
+pub fn focused_text() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    return bus::focused_text();
+    #[cfg(not(target_os = "linux"))]
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_gtk4_apps() {
+        assert!(mishandles_synthetic_paste("GTK", "4.14.2"));
+        assert!(mishandles_synthetic_paste("gtk", "5.0"));
+        assert!(!mishandles_synthetic_paste("GTK", "3.24.41"));
+        assert!(!mishandles_synthetic_paste("Qt", "6.7.0"));
+        assert!(!mishandles_synthetic_paste("GTK", ""));
+    }
+}
@@ -1,17 +1,68 @@
+use crate::managers::audio::AudioRecordingManager;
 use crate::settings::SoundTheme;
 use crate::settings::{self, AppSettings};
 use cpal::traits::{DeviceTrait, HostTrait};
 use log::{debug, error, warn};
-use rodio::OutputStreamBuilder;
+use rodio::source::{SineWave, Source};
+use rodio::{OutputStream, OutputStreamBuilder, Sink};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 use tauri::{AppHandle, Manager};
 
 pub enum SoundType {
     Start,
     Stop,
+    Error,
+    Done,
+    Countdown,
+}
+
+impl SoundType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SoundType::Start => "start",
+            SoundType::Stop => "stop",
+            SoundType::Error => "error",
+            SoundType::Done => "done",
+            SoundType::Countdown => "countdown",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "start" => Some(SoundType::Start),
+            "stop" => Some(SoundType::Stop),
+            "error" => Some(SoundType::Error),
+            "done" => Some(SoundType::Done),
+            "countdown" => Some(SoundType::Countdown),
+            _ => None,
+        }
+    }
+
+    /// Notes played when there's no sound file for this cue.
+    fn fallback_tones(&self) -> &'static [(f32, u64)] {
+        match self {
+            SoundType::Error => &[(440.0, 120), (330.0, 200)],
+            SoundType::Done => &[(660.0, 80), (880.0, 120)],
+            SoundType::Countdown => &[(880.0, 60)],
+            // Start and stop always have a themed file
+            SoundType::Start | SoundType::Stop => &[],
+        }
+    }
+}
+
+/// How long input stays gated after a cue ends, covering output latency.
+const CUE_TAIL: Duration = Duration::from_millis(150);
+const TONE_GAIN: f32 = 0.25;
+
+/// File name of a user-provided sound in the app data directory.
+pub fn custom_sound_file_name(sound_type: &SoundType) -> String {
+    format!("custom_{}.wav", sound_type.as_str())
 }
 
 fn resolve_sound_path(
@@ -19,17 +70,30 @@ fn resolve_sound_path(
     settings: &AppSettings,
     sound_type: SoundType,
 ) -> Option<PathBuf> {
-    let sound_file = get_sound_path(settings, sound_type);
-    let base_dir = get_sound_base_dir(settings);
-    app.path().resolve(&sound_file, base_dir).ok()
+    match sound_type {
+        SoundType::Start | SoundType::Stop => {
+            let sound_file = get_sound_path(settings, sound_type);
+            let base_dir = get_sound_base_dir(settings);
+            app.path().resolve(&sound_file, base_dir).ok()
+        }
+        // The themes don't ship these, so only a custom file overrides the tones
+        SoundType::Error | SoundType::Done | SoundType::Countdown => app
+            .path()
+            .resolve(
+                custom_sound_file_name(&sound_type),
+                tauri::path::BaseDirectory::AppData,
+            )
+            .ok()
+            .filter(|path| path.exists()),
+    }
 }
 
 fn get_sound_path(settings: &AppSettings, sound_type: SoundType) -> String {
     match (settings.sound_theme, sound_type) {
-        (SoundTheme::Custom, SoundType::Start) => "custom_start.wav".to_string(),
-        (SoundTheme::Custom, SoundType::Stop) => "custom_stop.wav".to_string(),
-        (_, SoundType::Start) => settings.sound_theme.to_start_path(),
+        (SoundTheme::Custom, sound_type) => custom_sound_file_name(&sound_type),
         (_, SoundType::Stop) => settings.sound_theme.to_stop_path(),
+        // Error and done are resolved from custom files only, see resolve_sound_path
+        (_, _) => settings.sound_theme.to_start_path(),
     }
 }
 
@@ -40,14 +104,40 @@ fn get_sound_base_dir(settings: &AppSettings) -> tauri::path::BaseDirectory {
     }
 }
 
+/// Keeps the recorder from capturing while a cue plays.
+struct CueGate(Option<Arc<AtomicUsize>>);
+
+impl CueGate {
+    fn hold(app: &AppHandle) -> Self {
+        let gate = app
+            .try_state::<Arc<AudioRecordingManager>>()
+            .map(|rm| rm.cue_gate());
+        if let Some(gate) = &gate {
+            gate.fetch_add(1, Ordering::Relaxed);
+        }
+        Self(gate)
+    }
+}
+
+impl Drop for CueGate {
+    fn drop(&mut self) {
+        if let Some(gate) = &self.0 {
+            thread::sleep(CUE_TAIL);
+            gate.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
 pub fn play_feedback_sound(app: &AppHandle, sound_type: SoundType) {
     let settings = settings::get_settings(app);
     if !settings.audio_feedback {
         return;
     }
-    if let Some(path) = resolve_sound_path(app, &settings, sound_type) {
-        play_sound_async(app, path);
-    }
+    let app_handle = app.clone();
+    thread::spawn(move || {
+        let _gate = CueGate::hold(&app_handle);
+        play_cue(&app_handle, &settings, sound_type);
+    });
 }
 
 pub fn play_feedback_sound_blocking(app: &AppHandle, sound_type: SoundType) {
@@ -55,25 +145,30 @@ pub fn play_feedback_sound_blocking(app: &AppHandle, sound_type: SoundType) {
     if !settings.audio_feedback {
         return;
     }
-    if let Some(path) = resolve_sound_path(app, &settings, sound_type) {
-        play_sound_blocking(app, &path);
-    }
+    let _gate = CueGate::hold(app);
+    play_cue(app, &settings, sound_type);
 }
 
 pub fn play_test_sound(app: &AppHandle, sound_type: SoundType) {
     let settings = settings::get_settings(app);
-    if let Some(path) = resolve_sound_path(app, &settings, sound_type) {
-        play_sound_blocking(app, &path);
-    }
+    play_cue(app, &settings, sound_type);
 }
 
-fn play_sound_async(app: &AppHandle, path: PathBuf) {
-    let app_handle = app.clone();
-    thread::spawn(move || {
-        if let Err(e) = play_sound_at_path(&app_handle, path.as_path()) {
-            error!("Failed to play sound '{}': {}", path.display(), e);
+fn play_cue(app: &AppHandle, settings: &AppSettings, sound_type: SoundType) {
+    let tones = sound_type.fallback_tones();
+    match resolve_sound_path(app, settings, sound_type) {
+        Some(path) => play_sound_blocking(app, &path),
+        None if !tones.is_empty() => {
+            if let Err(e) = play_tones(
+                tones,
+                settings.selected_output_device.clone(),
+                settings.audio_feedback_volume,
+            ) {
+                error!("Failed to play feedback tones: {}", e);
+            }
         }
-    });
+        None => {}
+    }
 }
 
 fn play_sound_blocking(app: &AppHandle, path: &Path) {
@@ -89,17 +184,22 @@ fn play_sound_at_path(app: &AppHandle, path: &Path) -> Result<(), Box<dyn std::e
     play_audio_file(path, selected_device, volume)
 }
 
-fn play_audio_file(
-    path: &std::path::Path,
+/// Plays an arbitrary audio file on the selected output device at full volume,
+/// blocking until playback finishes.
+pub fn play_file_blocking(app: &AppHandle, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let settings = settings::get_settings(app);
+    play_audio_file(path, settings.selected_output_device.clone(), 1.0)
+}
+
+fn open_output_stream(
     selected_device: Option<String>,
-    volume: f32,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<OutputStream, Box<dyn std::error::Error>> {
     let stream_builder = if let Some(device_name) = selected_device {
         if device_name == "Default" {
             debug!("Using default device");
             OutputStreamBuilder::from_default_device()?
         } else {
-            let host = crate::audio_toolkit::get_cpal_host();
+            let host = handy_transcription::get_cpal_host();
             let devices = host.output_devices()?;
 
             let mut found_device = None;
@@ -123,7 +223,15 @@ fn play_audio_file(
         OutputStreamBuilder::from_default_device()?
     };
 
-    let stream_handle = stream_builder.open_stream()?;
+    Ok(stream_builder.open_stream()?)
+}
+
+fn play_audio_file(
+    path: &std::path::Path,
+    selected_device: Option<String>,
+    volume: f32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let stream_handle = open_output_stream(selected_device)?;
     let mixer = stream_handle.mixer();
 
     let file = File::open(path)?;
@@ -135,3 +243,23 @@ fn play_audio_file(
 
     Ok(())
 }
+
+fn play_tones(
+    tones: &[(f32, u64)],
+    selected_device: Option<String>,
+    volume: f32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let stream_handle = open_output_stream(selected_device)?;
+    let sink = Sink::connect_new(stream_handle.mixer());
+    for &(frequency, duration_ms) in tones {
+        sink.append(
+            SineWave::new(frequency)
+                .take_duration(Duration::from_millis(duration_ms))
+                .amplify(TONE_GAIN),
+        );
+    }
+    sink.set_volume(volume);
+    sink.sleep_until_end();
+
+    Ok(())
+}
@@ -1,12 +0,0 @@
-// Re-export all audio components
-mod device;
-mod recorder;
-mod resampler;
-mod utils;
-mod visualizer;
-
-pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
-pub use recorder::AudioRecorder;
-pub use resampler::FrameResampler;
-pub use utils::save_wav_file;
-pub use visualizer::AudioVisualiser;
@@ -1,12 +0,0 @@
-pub mod audio;
-pub mod constants;
-pub mod text;
-pub mod utils;
-pub mod vad;
-
-pub use audio::{
-    list_input_devices, list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo,
-};
-pub use text::apply_custom_words;
-pub use utils::get_cpal_host;
-pub use vad::{SileroVad, VoiceActivityDetector};
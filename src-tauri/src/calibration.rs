@@ -0,0 +1,222 @@
+//! Tuning the microphone to the user's voice and room.
+//!
+//! The user stays quiet for a few seconds and then reads a known paragraph.
+//! The quiet start gives the noise level and the reading the speech level:
+//! the input gain brings the speech to a steady level and the VAD threshold
+//! is put between the speech probabilities Silero gives the two. The reading
+//! is also transcribed with every downloaded model and scored against the
+//! paragraph. The results are saved as the voice profile of the microphone
+//! that was calibrated, and applied whenever it's opened.
+
+use crate::managers::audio::{AudioRecordingManager, DEFAULT_VAD_THRESHOLD};
+use crate::managers::model::ModelManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::{get_settings, write_settings, ModelScore, VoiceProfile};
+use chrono::Utc;
+use handy_transcription::audio::rms;
+use handy_transcription::{constants, word_error_rate, SileroVad};
+use log::{info, warn};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Recording binding the calibration records under, so no shortcut can
+/// stop it
+const CALIBRATION_BINDING_ID: &str = "calibration";
+
+const FRAME_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize * 30 / 1000;
+/// Share of the quietest frames taken as the room's noise
+const NOISE_SHARE: f32 = 0.2;
+/// Share of the loudest frames taken as the user's speech
+const SPEECH_SHARE: f32 = 0.5;
+/// Speech level the input gain aims for
+const TARGET_SPEECH_RMS: f32 = 0.1;
+const MIN_GAIN: f32 = 0.5;
+const MAX_GAIN: f32 = 4.0;
+const MIN_THRESHOLD: f32 = 0.1;
+const MAX_THRESHOLD: f32 = 0.8;
+/// Shortest recording to calibrate from: the quiet start and a sentence
+const MIN_RECORDING_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize * 5;
+
+/// The value `share` of the way up the sorted `values`
+fn percentile(values: &[f32], share: f32) -> f32 {
+    let index = ((values.len() - 1) as f32 * share).round() as usize;
+    values[index]
+}
+
+/// Indices of the frames in `levels` that are noise and that are speech,
+/// quietest first
+fn split_frames(levels: &[f32]) -> (Vec<usize>, Vec<usize>) {
+    let mut by_level: Vec<usize> = (0..levels.len()).collect();
+    by_level.sort_by(|a, b| levels[*a].total_cmp(&levels[*b]));
+    let noise = ((levels.len() as f32 * NOISE_SHARE) as usize).max(1);
+    let speech = ((levels.len() as f32 * SPEECH_SHARE) as usize).max(1);
+    (
+        by_level[..noise].to_vec(),
+        by_level[levels.len() - speech..].to_vec(),
+    )
+}
+
+/// Input gain that brings speech at `speech_rms` to `TARGET_SPEECH_RMS`,
+/// given the recording was made with `current` gain
+fn pick_gain(current: f32, speech_rms: f32) -> f32 {
+    if speech_rms <= 0.0 {
+        return current;
+    }
+    (current * TARGET_SPEECH_RMS / speech_rms).clamp(MIN_GAIN, MAX_GAIN)
+}
+
+/// VAD threshold halfway between what almost all noise frames and most
+/// speech frames score. If the two overlap the microphone can't tell them
+/// apart well and the default is kept.
+fn pick_threshold(mut noise: Vec<f32>, mut speech: Vec<f32>) -> f32 {
+    if noise.is_empty() || speech.is_empty() {
+        return DEFAULT_VAD_THRESHOLD;
+    }
+    noise.sort_by(f32::total_cmp);
+    speech.sort_by(f32::total_cmp);
+    let noise_high = percentile(&noise, 0.95);
+    let speech_low = percentile(&speech, 0.2);
+    if noise_high >= speech_low {
+        return DEFAULT_VAD_THRESHOLD;
+    }
+    ((noise_high + speech_low) / 2.0).clamp(MIN_THRESHOLD, MAX_THRESHOLD)
+}
+
+/// Starts recording the calibration, silence included
+pub fn start(app: &AppHandle) -> Result<(), String> {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    if !rm.try_start_recording(CALIBRATION_BINDING_ID) {
+        return Err("Couldn't start recording, is another one running?".to_string());
+    }
+    rm.set_raw_capture(true);
+    Ok(())
+}
+
+pub fn cancel(app: &AppHandle) {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.stop_recording(CALIBRATION_BINDING_ID);
+    rm.set_raw_capture(false);
+}
+
+/// Stops the calibration recording, works out the profile for the reading
+/// of `reference` in it and saves it for the current microphone.
+pub fn finish(app: &AppHandle, reference: &str) -> Result<VoiceProfile, String> {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    let samples = rm.stop_recording(CALIBRATION_BINDING_ID);
+    rm.set_raw_capture(false);
+    let samples = samples.ok_or("The calibration wasn't recording")?;
+    if samples.len() < MIN_RECORDING_SAMPLES {
+        return Err("The recording is too short to calibrate from".to_string());
+    }
+
+    let mut settings = get_settings(app);
+    let key = rm.profile_key(&settings);
+    let current_gain = settings
+        .voice_profiles
+        .get(&key)
+        .map_or(1.0, |profile| profile.input_gain);
+
+    let frames: Vec<&[f32]> = samples.chunks_exact(FRAME_SAMPLES).collect();
+    let levels: Vec<f32> = frames.iter().map(|frame| rms(frame)).collect();
+    let (noise_frames, speech_frames) = split_frames(&levels);
+    let speech_rms = rms(&speech_frames
+        .iter()
+        .map(|&i| levels[i])
+        .collect::<Vec<f32>>());
+    let input_gain = pick_gain(current_gain, speech_rms);
+
+    // Score the frames as they'll sound with the new gain
+    let vad_path = app
+        .path()
+        .resolve(
+            "resources/models/silero_vad_v4.onnx",
+            tauri::path::BaseDirectory::Resource,
+        )
+        .map_err(|e| format!("Failed to resolve VAD path: {}", e))?;
+    let mut vad = SileroVad::new(vad_path, DEFAULT_VAD_THRESHOLD).map_err(|e| e.to_string())?;
+    let scale = input_gain / current_gain;
+    let probabilities: Vec<f32> = frames
+        .iter()
+        .map(|frame| {
+            let scaled: Vec<f32> = frame.iter().map(|s| (s * scale).clamp(-1.0, 1.0)).collect();
+            vad.probability(&scaled).unwrap_or(0.0)
+        })
+        .collect();
+    let vad_threshold = pick_threshold(
+        noise_frames.iter().map(|&i| probabilities[i]).collect(),
+        speech_frames.iter().map(|&i| probabilities[i]).collect(),
+    );
+
+    let audio: Vec<f32> = samples
+        .iter()
+        .map(|s| (s * scale).clamp(-1.0, 1.0))
+        .collect();
+    let tm = app.state::<Arc<TranscriptionManager>>();
+    let mut model_scores: Vec<ModelScore> = Vec::new();
+    for model in app
+        .state::<Arc<ModelManager>>()
+        .get_available_models()
+        .into_iter()
+        .filter(|model| model.is_downloaded)
+    {
+        let _ = app.emit("calibration-progress", &model.id);
+        match tm.transcribe_for_comparison(&model.id, &settings, audio.clone()) {
+            Ok(text) => model_scores.push(ModelScore {
+                wer: word_error_rate(reference, &text),
+                model_id: model.id,
+            }),
+            Err(e) => warn!("Couldn't score {} for calibration: {}", model.id, e),
+        }
+    }
+    tm.unload_fallback_model();
+    model_scores.sort_by(|a, b| a.wer.total_cmp(&b.wer));
+
+    let profile = VoiceProfile {
+        vad_threshold,
+        input_gain,
+        model_scores,
+        calibrated_at: Utc::now().timestamp(),
+    };
+    info!(
+        "Calibrated {}: VAD threshold {:.2}, gain {:.2}",
+        key, profile.vad_threshold, profile.input_gain
+    );
+    settings.voice_profiles.insert(key, profile.clone());
+    write_settings(app, settings);
+    rm.update_voice_profile();
+    Ok(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_frames_takes_quietest_and_loudest() {
+        let levels = [0.5, 0.01, 0.4, 0.02, 0.3, 0.6, 0.03, 0.2, 0.7, 0.05];
+        let (noise, speech) = split_frames(&levels);
+        assert_eq!(noise, [1, 3]);
+        assert_eq!(speech, [4, 2, 0, 5, 8]);
+    }
+
+    #[test]
+    fn gain_brings_speech_to_target_within_limits() {
+        assert_eq!(pick_gain(1.0, 0.05), 2.0);
+        assert_eq!(pick_gain(2.0, 0.1), 2.0);
+        assert_eq!(pick_gain(1.0, 0.001), MAX_GAIN);
+        assert_eq!(pick_gain(1.0, 1.0), MIN_GAIN);
+        assert_eq!(pick_gain(1.5, 0.0), 1.5);
+    }
+
+    #[test]
+    fn threshold_sits_between_noise_and_speech() {
+        let noise = vec![0.01, 0.02, 0.05, 0.1, 0.1];
+        let speech = vec![0.3, 0.5, 0.9, 0.95, 0.99];
+        assert!((pick_threshold(noise, speech) - 0.3).abs() < 1e-6);
+
+        let noisy = vec![0.6, 0.7, 0.8];
+        let quiet_speech = vec![0.5, 0.6, 0.7];
+        assert_eq!(pick_threshold(noisy, quiet_speech), DEFAULT_VAD_THRESHOLD);
+        assert_eq!(pick_threshold(vec![], vec![0.9]), DEFAULT_VAD_THRESHOLD);
+    }
+}
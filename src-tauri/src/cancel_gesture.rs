@@ -0,0 +1,78 @@
+//! Cancelling a push-to-talk recording without letting go of the key first:
+//! pressing Cancel while the key is held arms the cancel, and releasing the
+//! key then throws the recording away instead of transcribing it. Pressing
+//! Cancel again disarms. Holding the key for a while without saying anything
+//! arms it too, if `release_to_cancel_silence_secs` is set.
+//!
+//! `cancel-armed` is emitted whenever this changes, so the overlay can show
+//! "release to cancel".
+
+use crate::managers::audio::AudioRecordingManager;
+use crate::settings::get_settings;
+use crate::utils;
+use log::info;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Whether a push-to-talk key is down
+static HELD: AtomicBool = AtomicBool::new(false);
+static ARMED: AtomicBool = AtomicBool::new(false);
+
+fn set_armed(app: &AppHandle, armed: bool) {
+    if ARMED.swap(armed, Ordering::Relaxed) != armed {
+        let _ = app.emit("cancel-armed", armed);
+    }
+}
+
+/// A push-to-talk key went down
+pub fn hold_started(app: &AppHandle) {
+    HELD.store(true, Ordering::Relaxed);
+    set_armed(app, false);
+}
+
+/// A push-to-talk key was let go. Returns true if that cancelled the
+/// recording, in which case it must not be stopped as usual.
+pub fn hold_released(app: &AppHandle) -> bool {
+    HELD.store(false, Ordering::Relaxed);
+    if !ARMED.load(Ordering::Relaxed) {
+        return false;
+    }
+    set_armed(app, false);
+    info!("Push-to-talk key released while armed, cancelling");
+    utils::cancel_current_operation(app);
+    true
+}
+
+/// Handles a press of the Cancel shortcut. Returns true if it armed or
+/// disarmed the gesture rather than cancelling right away.
+pub fn cancel_pressed(app: &AppHandle) -> bool {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    if !HELD.load(Ordering::Relaxed) || !rm.is_recording() {
+        return false;
+    }
+    let settings = get_settings(app);
+    if !settings.release_to_cancel {
+        return false;
+    }
+    set_armed(app, !ARMED.load(Ordering::Relaxed));
+    true
+}
+
+/// Arms the gesture once the key has been held for the configured time with
+/// no speech at all. Called on every recording status tick.
+pub fn check_silence(app: &AppHandle, elapsed_secs: f32, speech_ratio: f32) {
+    if !HELD.load(Ordering::Relaxed) || ARMED.load(Ordering::Relaxed) || speech_ratio > 0.0 {
+        return;
+    }
+    let settings = get_settings(app);
+    if !settings.release_to_cancel {
+        return;
+    }
+    if let Some(secs) = settings.release_to_cancel_silence_secs {
+        if elapsed_secs >= secs as f32 {
+            info!("No speech for {}s, release to cancel", secs);
+            set_armed(app, true);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Command-line actions, so Handy can be driven from automation tools such as
+//! macOS Shortcuts ("Run Shell Script"), launchers or window manager bindings.
+//!
+//! Actions that need the running app (starting dictation, transcribing a file)
+//! are forwarded to it through the single-instance plugin. Files are queued
+//! instead of transcribed right away when batch transcription is deferred.
+//! Reading the last transcript only touches the history database, so it runs
+//! in the calling process and prints to stdout, which lets Shortcuts use it as
+//! output.
+//!
+//! These are not App Intents: Handy has no actions of its own in Shortcuts'
+//! library and can't be picked for the Action button directly. A shortcut
+//! that runs one of these flags can, which is the stand-in until the app
+//! ships intents.
+
+use crate::actions::action_for;
+use crate::managers::history;
+use crate::managers::transcription::TranscriptionManager;
+use crate::recording_guard;
+use crate::settings::get_settings;
+use crate::shortcut;
+use crate::ManagedToggleState;
+use log::{error, info, warn};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliAction {
+    ToggleTranscription,
+    StartDictation,
+    StopDictation,
+    TranscribeFile(PathBuf),
+    LastTranscript,
+}
+
+impl CliAction {
+    /// Finds the first recognised action in `args` (which includes argv[0]).
+    pub fn parse(args: &[String]) -> Option<Self> {
+        let mut iter = args.iter().skip(1);
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--toggle-transcription" => return Some(Self::ToggleTranscription),
+                "--start-dictation" => return Some(Self::StartDictation),
+                "--stop-dictation" => return Some(Self::StopDictation),
+                "--last-transcript" => return Some(Self::LastTranscript),
+                "--transcribe-file" => {
+                    return iter.next().map(|p| Self::TranscribeFile(PathBuf::from(p)));
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// The action with a relative file path taken from `cwd`, the directory
+    /// the command was run in, rather than the running app's.
+    pub fn relative_to(self, cwd: &Path) -> Self {
+        match self {
+            Self::TranscribeFile(path) if path.is_relative() => {
+                Self::TranscribeFile(cwd.join(path))
+            }
+            action => action,
+        }
+    }
+
+    /// Whether this action runs in the calling process instead of the running app.
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::LastTranscript)
+    }
+}
+
+/// Runs an action that doesn't need the GUI and returns the process exit code.
+/// `identifier` is the app's from tauri.conf.json, which names its data dir.
+pub fn run_local(action: &CliAction, identifier: &str) -> i32 {
+    match action {
+        CliAction::LastTranscript => {
+            let Some(db_path) = history_db_path(identifier) else {
+                eprintln!("Could not determine the app data directory");
+                return 1;
+            };
+            match history::read_latest_transcript(&db_path) {
+                Ok(Some(text)) => {
+                    println!("{}", text);
+                    0
+                }
+                Ok(None) => 0,
+                Err(e) => {
+                    eprintln!("Failed to read transcription history: {}", e);
+                    1
+                }
+            }
+        }
+        _ => 1,
+    }
+}
+
+fn history_db_path(identifier: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(identifier).join("history.db"))
+}
+
+/// Runs an action inside the running app, e.g. when forwarded by a second instance.
+pub fn dispatch(app: &AppHandle, action: CliAction) {
+    info!("Handling CLI action: {:?}", action);
+    match action {
+        CliAction::ToggleTranscription => {
+            let is_active = app
+                .state::<ManagedToggleState>()
+                .lock()
+                .map(|states| states.active_toggles.get("transcribe").copied())
+                .ok()
+                .flatten()
+                .unwrap_or(false);
+            set_dictation(app, !is_active, "CLI");
+        }
+        CliAction::StartDictation => set_dictation(app, true, "CLI"),
+        CliAction::StopDictation => set_dictation(app, false, "CLI"),
+        CliAction::TranscribeFile(path) if get_settings(app).batch_deferred => {
+            let tm = app.state::<Arc<TranscriptionManager>>();
+            if let Err(e) = tm.queue_file(path.to_string_lossy().to_string()) {
+                error!("Failed to queue file: {}", e);
+            }
+        }
+        CliAction::TranscribeFile(path) => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = transcribe_file(&app, path).await {
+                    error!("Failed to transcribe file: {}", e);
+                }
+            });
+        }
+        CliAction::LastTranscript => warn!("--last-transcript is handled by the calling process"),
+    }
+}
+
+/// Starts or stops the transcribe binding as its shortcut would, `source`
+/// saying what asked for it in the logs
+fn set_dictation(app: &AppHandle, start: bool, source: &str) {
+    set_recording(app, "transcribe", start, source);
+}
+
+/// Starts or stops recording binding `binding_id` as its shortcut would
+pub(crate) fn set_recording(app: &AppHandle, binding_id: &str, start: bool, source: &str) {
+    if action_for(binding_id).is_none() {
+        return;
+    }
+
+    {
+        let toggle_state_manager = app.state::<ManagedToggleState>();
+        let Ok(mut states) = toggle_state_manager.lock() else {
+            warn!("Failed to lock toggle state manager");
+            return;
+        };
+        let is_active = states
+            .active_toggles
+            .entry(binding_id.to_string())
+            .or_insert(false);
+        if *is_active == start {
+            return;
+        }
+        *is_active = start;
+    }
+
+    if start {
+        if !recording_guard::start(app, binding_id, source) {
+            shortcut::reset_toggle(app, binding_id);
+        }
+    } else {
+        recording_guard::stop(app, binding_id, source);
+    }
+}
+
+/// Transcribes an audio or video file right away, saves it to history and
+/// leaves the text on the clipboard so the calling automation can pick it up.
+async fn transcribe_file(app: &AppHandle, path: PathBuf) -> anyhow::Result<()> {
+    let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
+    let text = tauri::async_runtime::spawn_blocking(move || tm.transcribe_file(&path))
+        .await
+        .map_err(|e| anyhow::anyhow!("Transcription task failed: {}", e))??;
+
+    app.clipboard()
+        .write_text(&text)
+        .map_err(|e| anyhow::anyhow!("Failed to copy transcript: {}", e))?;
+    Ok(())
+}
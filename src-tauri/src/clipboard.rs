@@ -1,7 +1,15 @@
+use crate::atspi;
+use crate::context_rules;
+use crate::helpers::active_app::{active_app, active_window_title};
+use crate::helpers::selection::selected_text;
+use crate::injection_audit::Injection;
 use crate::input::{self, EnigoState};
-use crate::settings::{get_settings, ClipboardHandling, PasteMethod};
-use enigo::Enigo;
-use log::info;
+use crate::paste_check;
+use crate::settings::{get_settings, AppSettings, ClipboardHandling, PasteMethod};
+use crate::virtual_keyboard;
+use crate::voice_commands;
+use enigo::{Enigo, Key};
+use log::{debug, info, warn};
 use tauri::{AppHandle, Manager};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
@@ -361,9 +369,109 @@ fn paste_direct(enigo: &mut Enigo, text: &str) -> Result<(), String> {
     input::paste_text_direct(enigo, text)
 }
 
+/// The paste method for text going into `target_app`, which may have its own
+/// in `app_paste_methods`
+pub fn effective_paste_method(settings: &AppSettings, target_app: Option<&str>) -> PasteMethod {
+    target_app
+        .and_then(|app| {
+            settings
+                .app_paste_methods
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(app))
+        })
+        .map(|(_, method)| *method)
+        .unwrap_or(settings.paste_method)
+}
+
+/// Whether text for `target_app` goes in over AT-SPI instead of `method`,
+/// as for apps known to mishandle synthetic input, unless a method was
+/// picked for the app
+fn wants_accessibility(
+    settings: &AppSettings,
+    target_app: Option<&str>,
+    method: PasteMethod,
+) -> bool {
+    let picked_for_app = target_app.is_some_and(|app| {
+        settings
+            .app_paste_methods
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case(app))
+    });
+    !picked_for_app
+        && !matches!(
+            method,
+            PasteMethod::None | PasteMethod::VirtualKeyboard | PasteMethod::Accessibility
+        )
+        && atspi::prefers_accessibility(target_app)
+}
+
+/// Puts `text` into the focused app with `paste_method`.
+pub fn inject(
+    enigo: &mut Enigo,
+    text: &str,
+    app_handle: &AppHandle,
+    paste_method: PasteMethod,
+) -> Result<(), String> {
+    match paste_method {
+        PasteMethod::None => {
+            info!("PasteMethod::None selected - skipping paste action");
+            Ok(())
+        }
+        PasteMethod::Direct => paste_direct(enigo, text),
+        PasteMethod::VirtualKeyboard => match virtual_keyboard::type_text(text) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                warn!("{}, typing directly instead", e);
+                paste_direct(enigo, text)
+            }
+        },
+        PasteMethod::Accessibility => match atspi::insert_text(text) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                warn!("{}, pasting instead", e);
+                paste_via_clipboard(enigo, text, app_handle, &PasteMethod::CtrlV)
+            }
+        },
+        PasteMethod::CtrlV | PasteMethod::CtrlShiftV | PasteMethod::ShiftInsert => {
+            paste_via_clipboard(enigo, text, app_handle, &paste_method)
+        }
+    }
+}
+
+/// Deletes the focused app's selection, if it has one the accessibility API
+/// can see. Nothing is pressed when it can't tell, so a Backspace never eats
+/// a character that wasn't selected.
+fn clear_selection(enigo: &mut Enigo) -> Result<(), String> {
+    match selected_text() {
+        Some(selected) if !selected.is_empty() => {
+            debug!("Deleting {} selected chars", selected.chars().count());
+            input::click_key(enigo, Key::Backspace)
+        }
+        _ => Ok(()),
+    }
+}
+
 pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
-    let settings = get_settings(&app_handle);
-    let paste_method = settings.paste_method;
+    let mut settings = get_settings(&app_handle);
+    let target_app = active_app();
+    let window_title = active_window_title();
+    let press_enter = context_rules::apply_output(
+        &mut settings,
+        target_app.as_deref(),
+        window_title.as_deref(),
+    );
+    let mut paste_method = effective_paste_method(&settings, target_app.as_deref());
+    if wants_accessibility(&settings, target_app.as_deref(), paste_method) {
+        paste_method = PasteMethod::Accessibility;
+    }
+
+    // Note where the text is going before pasting can move the focus
+    let injection = Injection::start(&settings, paste_method, target_app, window_title);
+    let unmarked = text.clone();
+    let text = match &injection {
+        Some(injection) => injection.mark(text),
+        None => text,
+    };
 
     // Append trailing space if setting is enabled
     let text = if settings.append_trailing_space {
@@ -383,16 +491,45 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
         .lock()
         .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
 
+    if settings.replace_selection && paste_method != PasteMethod::None {
+        clear_selection(&mut enigo)?;
+    }
+
+    let check = (settings.verify_paste && paste_method != PasteMethod::None)
+        .then(paste_check::Snapshot::take);
+
     // Perform the paste operation
-    match paste_method {
-        PasteMethod::None => {
-            info!("PasteMethod::None selected - skipping paste action");
+    let mut result = inject(&mut enigo, &text, &app_handle, paste_method);
+    if let Some(check) = &check {
+        let landed = result.is_ok() && check.landed(&unmarked) != Some(false);
+        if !landed {
+            if let Some(fallback) = paste_check::fallback_method(paste_method) {
+                warn!(
+                    "Text didn't land with {:?}, trying {:?}",
+                    paste_method, fallback
+                );
+                paste_method = fallback;
+                result =
+                    inject(&mut enigo, &text, &app_handle, fallback).and_then(|()| {
+                        match check.landed(&unmarked) {
+                            Some(false) => {
+                                Err(format!("Text didn't land with {:?} either", fallback))
+                            }
+                            _ => Ok(()),
+                        }
+                    });
+            }
         }
-        PasteMethod::Direct => {
-            paste_direct(&mut enigo, &text)?;
+        // Nothing is lost, the text can still be pasted by hand
+        if result.is_err() {
+            let _ = app_handle.clipboard().write_text(&unmarked);
         }
-        PasteMethod::CtrlV | PasteMethod::CtrlShiftV | PasteMethod::ShiftInsert => {
-            paste_via_clipboard(&mut enigo, &text, &app_handle, &paste_method)?
+    }
+    result?;
+    if paste_method != PasteMethod::None {
+        voice_commands::record(&app_handle, &text);
+        if press_enter {
+            input::click_key(&mut enigo, Key::Return)?;
         }
     }
 
@@ -404,5 +541,34 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
             .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
     }
 
+    if let Some(injection) = injection {
+        injection.record(&app_handle, unmarked);
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+
+    #[test]
+    fn app_overrides_the_paste_method() {
+        let mut settings = get_default_settings();
+        settings.paste_method = PasteMethod::CtrlV;
+        settings
+            .app_paste_methods
+            .insert("WindowsTerminal".to_string(), PasteMethod::CtrlShiftV);
+
+        assert_eq!(
+            effective_paste_method(&settings, Some("windowsterminal")),
+            PasteMethod::CtrlShiftV
+        );
+        assert_eq!(
+            effective_paste_method(&settings, Some("Code")),
+            PasteMethod::CtrlV
+        );
+        assert_eq!(effective_paste_method(&settings, None), PasteMethod::CtrlV);
+    }
+}
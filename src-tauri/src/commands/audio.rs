@@ -1,10 +1,18 @@
+use crate::api_server;
 use crate::audio_feedback;
-use crate::audio_toolkit::audio::{list_input_devices, list_output_devices};
-use crate::managers::audio::{AudioRecordingManager, MicrophoneMode};
-use crate::settings::{get_settings, write_settings};
+use crate::managers::audio::AudioRecordingManager;
+use crate::power;
+use crate::settings::{get_settings, write_settings, SoundTheme, WakeWord};
+use crate::wake_word;
+use handy_transcription::audio::{
+    list_input_devices, list_output_devices, system_audio_available, SYSTEM_AUDIO,
+};
 use log::warn;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 
@@ -12,6 +20,9 @@ use tauri::{AppHandle, Manager};
 pub struct CustomSounds {
     start: bool,
     stop: bool,
+    error: bool,
+    done: bool,
+    countdown: bool,
 }
 
 fn custom_sound_exists(app: &AppHandle, sound_type: &str) -> bool {
@@ -29,9 +40,67 @@ pub fn check_custom_sounds(app: AppHandle) -> CustomSounds {
     CustomSounds {
         start: custom_sound_exists(&app, "start"),
         stop: custom_sound_exists(&app, "stop"),
+        error: custom_sound_exists(&app, "error"),
+        done: custom_sound_exists(&app, "done"),
+        countdown: custom_sound_exists(&app, "countdown"),
     }
 }
 
+fn custom_sound_path(app: &AppHandle, sound_type: &str) -> Result<PathBuf, String> {
+    let sound_type = audio_feedback::SoundType::parse(sound_type)
+        .ok_or_else(|| format!("Unknown sound type: {}", sound_type))?;
+    app.path()
+        .resolve(
+            audio_feedback::custom_sound_file_name(&sound_type),
+            tauri::path::BaseDirectory::AppData,
+        )
+        .map_err(|e| format!("Failed to resolve custom sound path: {}", e))
+}
+
+/// Copies a WAV file into the app data directory as the custom sound for `sound_type`.
+#[tauri::command]
+#[specta::specta]
+pub fn import_custom_sound(app: AppHandle, sound_type: String, path: String) -> Result<(), String> {
+    let source = PathBuf::from(path.trim());
+    let is_wav = source
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"));
+    if !is_wav {
+        return Err("Custom sounds must be WAV files".to_string());
+    }
+
+    // Make sure it actually decodes before replacing anything
+    let file = File::open(&source).map_err(|e| format!("Failed to open sound file: {}", e))?;
+    rodio::Decoder::new(BufReader::new(file))
+        .map_err(|e| format!("Failed to decode sound file: {}", e))?;
+
+    let dest = custom_sound_path(&app, &sound_type)?;
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    std::fs::copy(&source, &dest).map_err(|e| format!("Failed to copy sound file: {}", e))?;
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn remove_custom_sound(app: AppHandle, sound_type: String) -> Result<(), String> {
+    let path = custom_sound_path(&app, &sound_type)?;
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to remove sound file: {}", e))?;
+    }
+
+    // The custom theme needs both start and stop sounds
+    let mut settings = get_settings(&app);
+    if settings.sound_theme == SoundTheme::Custom && (sound_type == "start" || sound_type == "stop")
+    {
+        settings.sound_theme = SoundTheme::Marimba;
+        write_settings(&app, settings);
+    }
+    Ok(())
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct AudioDevice {
     pub index: String,
@@ -45,17 +114,11 @@ pub fn update_microphone_mode(app: AppHandle, always_on: bool) -> Result<(), Str
     // Update settings
     let mut settings = get_settings(&app);
     settings.always_on_microphone = always_on;
-    write_settings(&app, settings);
+    write_settings(&app, settings.clone());
 
-    // Update the audio manager mode
+    // Update the audio manager mode, which low power mode may keep on-demand
     let rm = app.state::<Arc<AudioRecordingManager>>();
-    let new_mode = if always_on {
-        MicrophoneMode::AlwaysOn
-    } else {
-        MicrophoneMode::OnDemand
-    };
-
-    rm.update_mode(new_mode)
+    rm.update_mode(power::effective_microphone_mode(&settings))
         .map_err(|e| format!("Failed to update microphone mode: {}", e))
 }
 
@@ -84,6 +147,14 @@ pub fn get_available_microphones() -> Result<Vec<AudioDevice>, String> {
         is_default: false, // The explicit default is handled separately
     }));
 
+    if system_audio_available() {
+        result.push(AudioDevice {
+            index: "system".to_string(),
+            name: SYSTEM_AUDIO.to_string(),
+            is_default: false,
+        });
+    }
+
     Ok(result)
 }
 
@@ -106,6 +177,92 @@ pub fn set_selected_microphone(app: AppHandle, device_name: String) -> Result<()
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn set_microphone_priority(app: AppHandle, devices: Vec<String>) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.microphone_priority = devices;
+    write_settings(&app, settings);
+
+    // Switch right away if the best connected microphone changed
+    app.state::<Arc<AudioRecordingManager>>()
+        .refresh_priority_microphone();
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_remote_microphone(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.remote_microphone = enabled;
+    if enabled && settings.remote_microphone_token.is_empty() {
+        settings.remote_microphone_token = api_server::generate_token();
+    }
+    write_settings(&app, settings);
+
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_remote_microphone()
+        .map_err(|e| format!("Failed to switch microphone: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_remote_microphone_port(app: AppHandle, port: u16) -> Result<(), String> {
+    if port < 1024 {
+        return Err("Choose a port of 1024 or above".to_string());
+    }
+    let mut settings = get_settings(&app);
+    settings.remote_microphone_port = port;
+    write_settings(&app, settings);
+
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_remote_microphone()
+        .map_err(|e| format!("Failed to switch microphone: {}", e))
+}
+
+/// Replaces the network microphone's pairing token, so a device paired with
+/// the old one is disconnected and can't pair again
+#[tauri::command]
+#[specta::specta]
+pub fn regenerate_remote_microphone_token(app: AppHandle) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.remote_microphone_token = api_server::generate_token();
+    let enabled = settings.remote_microphone;
+    write_settings(&app, settings);
+
+    if !enabled {
+        return Ok(());
+    }
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_remote_microphone()
+        .map_err(|e| format!("Failed to switch microphone: {}", e))
+}
+
+/// The addresses a phone on the same network can stream to, the WebSocket
+/// one with the pairing token
+#[tauri::command]
+#[specta::specta]
+pub fn get_remote_microphone_addresses(app: AppHandle) -> Vec<String> {
+    let settings = get_settings(&app);
+    let port = settings.remote_microphone_port;
+    // Connecting a UDP socket sends nothing, it only picks the outgoing interface
+    let ip = std::net::UdpSocket::bind("0.0.0.0:0")
+        .and_then(|socket| {
+            socket.connect("192.0.2.1:9")?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|_| "localhost".to_string());
+    vec![
+        format!(
+            "ws://{}:{}/?token={}",
+            ip, port, settings.remote_microphone_token
+        ),
+        format!("rtp://{}:{}", ip, port),
+    ]
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_selected_microphone(app: AppHandle) -> Result<String, String> {
@@ -161,13 +318,9 @@ pub fn get_selected_output_device(app: AppHandle) -> Result<String, String> {
 #[tauri::command]
 #[specta::specta]
 pub async fn play_test_sound(app: AppHandle, sound_type: String) {
-    let sound = match sound_type.as_str() {
-        "start" => audio_feedback::SoundType::Start,
-        "stop" => audio_feedback::SoundType::Stop,
-        _ => {
-            warn!("Unknown sound type: {}", sound_type);
-            return;
-        }
+    let Some(sound) = audio_feedback::SoundType::parse(&sound_type) else {
+        warn!("Unknown sound type: {}", sound_type);
+        return;
     };
     audio_feedback::play_test_sound(&app, sound);
 }
@@ -194,9 +347,128 @@ pub fn get_clamshell_microphone(app: AppHandle) -> Result<String, String> {
         .unwrap_or_else(|| "default".to_string()))
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn set_conferencing_microphone(app: AppHandle, device_name: String) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.conferencing_microphone = if device_name == "default" {
+        None
+    } else {
+        Some(device_name)
+    };
+    write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_conferencing_microphone(app: AppHandle) -> Result<String, String> {
+    let settings = get_settings(&app);
+    Ok(settings
+        .conferencing_microphone
+        .unwrap_or_else(|| "default".to_string()))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn is_recording(app: AppHandle) -> bool {
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     audio_manager.is_recording()
 }
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_wake_word_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.wake_word_enabled = enabled;
+    write_settings(&app, settings.clone());
+
+    // Listening for the wake word keeps the microphone open
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_mode(power::effective_microphone_mode(&settings))
+        .map_err(|e| format!("Failed to update microphone mode: {}", e))?;
+    rm.update_voice_profile();
+    wake_word::reload(&app);
+    Ok(())
+}
+
+/// Adds a wake word with no recordings yet, starting the transcribe
+/// binding until another is picked
+#[tauri::command]
+#[specta::specta]
+pub fn add_wake_word(app: AppHandle) -> Result<WakeWord, String> {
+    let mut settings = get_settings(&app);
+    let entry = WakeWord {
+        id: format!("wake_word_{}", chrono::Utc::now().timestamp_millis()),
+        name: "Wake Word".to_string(),
+        binding_id: "transcribe".to_string(),
+        sensitivity: 0.5,
+    };
+    settings.wake_words.push(entry.clone());
+    write_settings(&app, settings);
+    Ok(entry)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_wake_word(
+    app: AppHandle,
+    id: String,
+    name: String,
+    binding_id: String,
+    sensitivity: f32,
+) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    if !wake_word::can_start(&binding_id) || !settings.bindings.contains_key(&binding_id) {
+        return Err(format!("A wake word can't start '{}'", binding_id));
+    }
+    let entry = settings
+        .wake_words
+        .iter_mut()
+        .find(|entry| entry.id == id)
+        .ok_or_else(|| format!("Wake word with id '{}' not found", id))?;
+    entry.name = name;
+    entry.binding_id = binding_id;
+    entry.sensitivity = sensitivity.clamp(0.0, 1.0);
+    write_settings(&app, settings);
+    wake_word::reload(&app);
+    Ok(())
+}
+
+/// Removes wake word `id` along with its recordings
+#[tauri::command]
+#[specta::specta]
+pub fn delete_wake_word(app: AppHandle, id: String) -> Result<(), String> {
+    wake_word::remove_samples(&app, &id)?;
+    let mut settings = get_settings(&app);
+    settings.wake_words.retain(|entry| entry.id != id);
+    write_settings(&app, settings);
+    wake_word::reload(&app);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_wake_word_sample_count(app: AppHandle, id: String) -> usize {
+    wake_word::sample_count(&app, &id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn start_wake_word_sample(app: AppHandle, id: String) -> Result<(), String> {
+    wake_word::start_sample(&app, &id)
+}
+
+/// Stops recording wake word `id` and returns how many recordings of it
+/// there are now
+#[tauri::command]
+#[specta::specta]
+pub async fn finish_wake_word_sample(app: AppHandle, id: String) -> Result<usize, String> {
+    wake_word::finish_sample(&app, &id).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn clear_wake_word_samples(app: AppHandle, id: String) -> Result<(), String> {
+    wake_word::clear_samples(&app, &id)
+}
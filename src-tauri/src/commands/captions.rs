@@ -0,0 +1,93 @@
+use crate::managers::captions::{self, CaptionLine, CaptionsManager};
+use crate::settings::get_settings;
+use chrono::{DateTime, Local};
+use std::fs;
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_opener::OpenerExt;
+
+#[tauri::command]
+#[specta::specta]
+pub fn start_live_captions(
+    captions_manager: State<'_, Arc<CaptionsManager>>,
+) -> Result<(), String> {
+    captions_manager.start().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn stop_live_captions(captions_manager: State<'_, Arc<CaptionsManager>>) {
+    captions_manager.stop();
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn is_live_captions_active(captions_manager: State<'_, Arc<CaptionsManager>>) -> bool {
+    captions_manager.is_active()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_caption_lines(captions_manager: State<'_, Arc<CaptionsManager>>) -> Vec<CaptionLine> {
+    captions_manager.get_lines()
+}
+
+/// The last captions session as Markdown, along with when it started
+fn session_markdown(
+    app: &AppHandle,
+    captions_manager: &CaptionsManager,
+) -> Result<(String, DateTime<Local>), String> {
+    let (started, lines) = captions_manager
+        .get_session()
+        .filter(|(_, lines)| !lines.is_empty())
+        .ok_or_else(|| "There is no captions session to export yet".to_string())?;
+    let started_at = DateTime::from_timestamp_millis(started)
+        .unwrap_or_default()
+        .with_timezone(&Local);
+
+    let settings = get_settings(app);
+    let title = format!("Live captions, {}", started_at.format("%Y-%m-%d %H:%M"));
+    let markdown = captions::session_markdown(
+        &title,
+        started,
+        &lines,
+        settings.caption_export_timestamps,
+        &settings.caption_speaker_label,
+    );
+    Ok((markdown, started_at))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn copy_caption_session(
+    app: AppHandle,
+    captions_manager: State<'_, Arc<CaptionsManager>>,
+) -> Result<(), String> {
+    let (markdown, _) = session_markdown(&app, &captions_manager)?;
+    app.clipboard()
+        .write_text(markdown)
+        .map_err(|e| format!("Failed to copy captions: {}", e))
+}
+
+/// Saves the last captions session to the Documents folder and returns the
+/// path of the file.
+#[tauri::command]
+#[specta::specta]
+pub fn save_caption_session(
+    app: AppHandle,
+    captions_manager: State<'_, Arc<CaptionsManager>>,
+) -> Result<String, String> {
+    let (markdown, started_at) = session_markdown(&app, &captions_manager)?;
+    let dir = dirs::document_dir()
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| "Could not find the Documents folder".to_string())?;
+    let path = dir.join(format!(
+        "Handy Captions {}.md",
+        started_at.format("%Y-%m-%d %H%M")
+    ));
+
+    fs::write(&path, markdown).map_err(|e| format!("Failed to save captions: {}", e))?;
+    let _ = app.opener().reveal_item_in_dir(&path);
+    Ok(path.to_string_lossy().to_string())
+}
@@ -1,6 +1,23 @@
-use crate::managers::history::{HistoryEntry, HistoryManager};
+use crate::app_usage::{self, AppDefaultSuggestion, AppUsageStats};
+use crate::injection_audit;
+use crate::managers::history::{HistoryEntry, HistoryManager, HistoryPage, InjectionRecord};
+use crate::share;
+use crate::subtitles::{self, SubtitleFormat};
+use crate::transcription_quality::{self, QualityStats};
+use crate::utils;
+use chrono::{DateTime, Local};
+use handy_transcription::timing::{self, TimedSegment};
+use handy_transcription::{constants, read_wav_file};
+use serde::Serialize;
+use specta::Type;
+use std::fs;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_opener::OpenerExt;
+
+/// Largest page the history picker can ask for
+const MAX_PAGE_SIZE: usize = 100;
 
 #[tauri::command]
 #[specta::specta]
@@ -14,6 +31,252 @@ pub async fn get_history_entries(
         .map_err(|e| e.to_string())
 }
 
+/// A page of entries matching `query`, for the keyboard-driven history
+/// picker. The picker moves through pages by `offset` and acts on the
+/// selected entry by id with the commands below.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_page(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    query: String,
+    saved_only: bool,
+    offset: usize,
+    limit: usize,
+) -> Result<HistoryPage, String> {
+    history_manager
+        .query_entries(&query, saved_only, offset, limit.clamp(1, MAX_PAGE_SIZE))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// A page of entries containing every word of `query`, best matches first,
+/// through the full-text index. See `HistoryManager::search_entries`.
+#[tauri::command]
+#[specta::specta]
+pub async fn search_history(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    query: String,
+    offset: usize,
+    limit: usize,
+) -> Result<HistoryPage, String> {
+    history_manager
+        .search_entries(&query, offset, limit.clamp(1, MAX_PAGE_SIZE))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn copy_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<(), String> {
+    let entry = find_entry(&history_manager, id).await?;
+    app.clipboard()
+        .write_text(entry.text())
+        .map_err(|e| format!("Failed to copy transcript: {}", e))
+}
+
+/// Saves an edited transcript for an entry.
+#[tauri::command]
+#[specta::specta]
+pub async fn update_history_entry_text(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    text: String,
+) -> Result<(), String> {
+    history_manager
+        .update_transcription_text(id, text.trim())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Rates the transcript of an entry thumbs up (`true`) or down, or clears
+/// the rating.
+#[tauri::command]
+#[specta::specta]
+pub async fn rate_history_entry(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    rating: Option<bool>,
+) -> Result<(), String> {
+    history_manager
+        .set_rating(id, rating)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// How the rated entries came out for each engine and language, best first.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_quality_report(
+    history_manager: State<'_, Arc<HistoryManager>>,
+) -> Result<Vec<QualityStats>, String> {
+    let records = history_manager
+        .rating_records()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(transcription_quality::quality_report(&records))
+}
+
+/// Pastes an entry into the app that had focus before the picker opened.
+#[tauri::command]
+#[specta::specta]
+pub async fn paste_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<(), String> {
+    let entry = find_entry(&history_manager, id).await?;
+    utils::paste_after_refocus(&app, entry.text().to_string());
+    Ok(())
+}
+
+/// Sends an entry to one of the configured share destinations.
+#[tauri::command]
+#[specta::specta]
+pub async fn share_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    destination_id: String,
+) -> Result<(), String> {
+    let destination = crate::settings::get_settings(&app)
+        .share_destinations
+        .into_iter()
+        .find(|destination| destination.id == destination_id)
+        .ok_or_else(|| format!("Share destination '{}' not found", destination_id))?;
+    let entry = find_entry(&history_manager, id).await?;
+    share::share(&app, &destination, &entry).await
+}
+
+/// Saves an entry as subtitles to the Documents folder and returns the path
+/// of the file. Entries without timing become one cue over the recording.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_history_subtitles(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    format: SubtitleFormat,
+) -> Result<String, String> {
+    let entry = find_entry(&history_manager, id).await?;
+    let segments = history_manager
+        .get_segments(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .filter(|segments| !segments.is_empty());
+    let segments = match segments {
+        Some(segments) => segments,
+        None => {
+            let audio = read_wav_file(history_manager.get_audio_file_path(&entry.file_name))
+                .map_err(|e| format!("Failed to read the recording: {}", e))?;
+            vec![TimedSegment {
+                start: 0.0,
+                end: audio.len() as f32 / constants::WHISPER_SAMPLE_RATE as f32,
+                text: entry
+                    .post_processed_text
+                    .unwrap_or(entry.transcription_text),
+                words: Vec::new(),
+                speaker: None,
+            }]
+        }
+    };
+
+    let dir = dirs::document_dir()
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| "Could not find the Documents folder".to_string())?;
+    let recorded_at = DateTime::from_timestamp(entry.timestamp, 0)
+        .unwrap_or_default()
+        .with_timezone(&Local);
+    let path = dir.join(format!(
+        "Handy {}.{}",
+        recorded_at.format("%Y-%m-%d %H%M%S"),
+        format.extension()
+    ));
+    fs::write(&path, subtitles::render(format, &segments))
+        .map_err(|e| format!("Failed to save subtitles: {}", e))?;
+    let _ = app.opener().reveal_item_in_dir(&path);
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// A word of a transcript and when it was said, in seconds into the recording
+#[derive(Serialize, Type)]
+pub struct SpokenWord {
+    start: f32,
+    end: f32,
+    text: String,
+}
+
+/// The words of an entry's transcript with their times, for following the
+/// recording and seeking by word. Empty when the entry wasn't timed.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_entry_words(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<Vec<SpokenWord>, String> {
+    let segments = history_manager
+        .get_segments(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    Ok(timing::words_of(&segments)
+        .into_iter()
+        .map(|word| SpokenWord {
+            start: word.start,
+            end: word.end,
+            text: word.text,
+        })
+        .collect())
+}
+
+/// Dictation counts and habits for each app dictated into, most used first.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_app_usage_stats(
+    history_manager: State<'_, Arc<HistoryManager>>,
+) -> Result<Vec<AppUsageStats>, String> {
+    let records = history_manager
+        .usage_records()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(app_usage::app_stats(&records))
+}
+
+/// Apps that nearly always get the same language or post-processing prompt,
+/// and what could be made their default.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_app_default_suggestions(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+) -> Result<Vec<AppDefaultSuggestion>, String> {
+    let settings = crate::settings::get_settings(&app);
+    let records = history_manager
+        .usage_records()
+        .await
+        .map_err(|e| e.to_string())?;
+    let default_prompt_id = settings
+        .post_process_enabled
+        .then_some(settings.post_process_selected_prompt_id.as_deref())
+        .flatten();
+    Ok(app_usage::suggest_defaults(
+        &records,
+        &settings.selected_language,
+        default_prompt_id,
+    ))
+}
+
+async fn find_entry(history_manager: &HistoryManager, id: i64) -> Result<HistoryEntry, String> {
+    history_manager
+        .get_entry_by_id(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("History entry {} no longer exists", id))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn toggle_history_entry_saved(
@@ -99,3 +362,35 @@ pub async fn update_recording_retention_period(
 
     Ok(())
 }
+
+/// Logged text injections matching `query`, newest first, optionally within
+/// a time range in Unix milliseconds.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_text_injections(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    query: String,
+    since_ms: Option<i64>,
+    until_ms: Option<i64>,
+    limit: usize,
+) -> Result<Vec<InjectionRecord>, String> {
+    history_manager
+        .query_injections(&query, since_ms, until_ms, limit.clamp(1, MAX_PAGE_SIZE))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Finds where `text`, copied back out of some app, was injected: by its
+/// invisible marker when it has one, otherwise by an exact match.
+#[tauri::command]
+#[specta::specta]
+pub async fn identify_injected_text(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    text: String,
+) -> Result<Option<InjectionRecord>, String> {
+    let record = match injection_audit::find_marker(&text) {
+        Some(timestamp_ms) => history_manager.injection_at(timestamp_ms).await,
+        None => history_manager.latest_injection_of(text.trim()).await,
+    };
+    record.map_err(|e| e.to_string())
+}
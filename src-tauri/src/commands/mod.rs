@@ -1,9 +1,16 @@
 pub mod audio;
+pub mod captions;
 pub mod history;
 pub mod models;
+pub mod notifications;
+pub mod oauth;
+pub mod plugins;
+pub mod preview;
 pub mod transcription;
 
-use crate::settings::{get_settings, write_settings, AppSettings, LogLevel};
+use crate::calibration;
+use crate::injection_test::{self, InjectionTestReport};
+use crate::settings::{get_settings, write_settings, AppSettings, LogLevel, VoiceProfile};
 use crate::utils::cancel_current_operation;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_opener::OpenerExt;
@@ -14,6 +21,12 @@ pub fn cancel_operation(app: AppHandle) {
     cancel_current_operation(&app);
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn is_on_battery() -> bool {
+    crate::power::is_on_battery()
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_app_dir_path(app: AppHandle) -> Result<String, String> {
@@ -131,6 +144,47 @@ pub fn check_apple_intelligence_available() -> bool {
     }
 }
 
+/// Waits `delay_secs` for the user to focus a text field elsewhere, then
+/// tries each paste method there and remembers the first one that worked.
+#[specta::specta]
+#[tauri::command]
+pub async fn test_paste_methods(
+    app: AppHandle,
+    delay_secs: u64,
+) -> Result<InjectionTestReport, String> {
+    tokio::time::sleep(std::time::Duration::from_secs(delay_secs)).await;
+    tauri::async_runtime::spawn_blocking(move || injection_test::run(&app))
+        .await
+        .map_err(|e| format!("Paste test failed: {}", e))?
+}
+
+/// Starts recording a voice calibration. The user stays quiet for a few
+/// seconds, then reads the calibration paragraph.
+#[specta::specta]
+#[tauri::command]
+pub fn start_voice_calibration(app: AppHandle) -> Result<(), String> {
+    calibration::start(&app)
+}
+
+/// Stops the calibration recording and saves the voice profile it gives for
+/// the current microphone.
+#[specta::specta]
+#[tauri::command]
+pub async fn finish_voice_calibration(
+    app: AppHandle,
+    reference: String,
+) -> Result<VoiceProfile, String> {
+    tauri::async_runtime::spawn_blocking(move || calibration::finish(&app, &reference))
+        .await
+        .map_err(|e| format!("Calibration failed: {}", e))?
+}
+
+#[specta::specta]
+#[tauri::command]
+pub fn cancel_voice_calibration(app: AppHandle) {
+    calibration::cancel(&app);
+}
+
 /// Try to initialize Enigo (keyboard/mouse simulation).
 /// On macOS, this will return an error if accessibility permissions are not granted.
 #[specta::specta]
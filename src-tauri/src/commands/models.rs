@@ -1,6 +1,8 @@
-use crate::managers::model::{ModelInfo, ModelManager};
+use crate::managers::model::{LanguagePack, ModelInfo, ModelManager, PUNCTUATION_MODEL_ID};
 use crate::managers::transcription::TranscriptionManager;
+use crate::memory_budget::{self, MemoryUsage};
 use crate::settings::{get_settings, write_settings};
+use std::path::Path;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -21,6 +23,48 @@ pub async fn get_model_info(
     Ok(model_manager.get_model_info(&model_id))
 }
 
+/// The punctuation restoration model, which isn't part of the model list
+#[tauri::command]
+#[specta::specta]
+pub async fn get_punctuation_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<Option<ModelInfo>, String> {
+    Ok(model_manager.get_model_info(PUNCTUATION_MODEL_ID))
+}
+
+/// Memory Handy and its model use, and the budget they're kept to
+#[tauri::command]
+#[specta::specta]
+pub fn get_memory_usage(app_handle: AppHandle) -> MemoryUsage {
+    memory_budget::usage(&app_handle)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_language_packs(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<Vec<LanguagePack>, String> {
+    Ok(model_manager.get_language_packs())
+}
+
+/// Downloads a language pack's models and switches to it, loading its
+/// speech model.
+#[tauri::command]
+#[specta::specta]
+pub async fn install_language_pack(
+    model_manager: State<'_, Arc<ModelManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    pack_id: String,
+) -> Result<(), String> {
+    let model_id = model_manager
+        .install_language_pack(&pack_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    transcription_manager
+        .load_model(&model_id)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn download_model(
@@ -33,6 +77,34 @@ pub async fn download_model(
         .map_err(|e| e.to_string())
 }
 
+/// Imports a fine-tuned Whisper model file as a model based on
+/// `base_model_id`.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_fine_tune(
+    model_manager: State<'_, Arc<ModelManager>>,
+    path: String,
+    name: String,
+    base_model_id: String,
+) -> Result<ModelInfo, String> {
+    model_manager
+        .import_fine_tune(Path::new(path.trim()), &name, &base_model_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Imports a punctuation model from a folder holding `model.onnx`,
+/// `vocab.txt` and `config.json`.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_punctuation_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    path: String,
+) -> Result<ModelInfo, String> {
+    model_manager
+        .import_punctuation_model(Path::new(path.trim()))
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_model(
@@ -135,3 +207,14 @@ pub async fn get_recommended_first_model() -> Result<String, String> {
     // Recommend Parakeet V3 model for first-time users - fastest and most accurate
     Ok("parakeet-tdt-0.6b-v3".to_string())
 }
+
+/// The shared model directory in use, or `None` when there isn't one.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_shared_models_dir(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<Option<String>, String> {
+    Ok(model_manager
+        .shared_models_dir()
+        .map(|dir| dir.to_string_lossy().to_string()))
+}
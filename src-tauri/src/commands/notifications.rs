@@ -0,0 +1,79 @@
+use crate::actions::transcribe_and_paste;
+use crate::notifications::ManagedNotificationContext;
+use crate::settings;
+use crate::utils::{
+    self, change_tray_icon, hide_main_window, show_transcribing_overlay, TrayIconState,
+    REFOCUS_DELAY,
+};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+#[tauri::command]
+#[specta::specta]
+pub fn retry_last_transcription(app: AppHandle) -> Result<(), String> {
+    let samples = app
+        .state::<ManagedNotificationContext>()
+        .lock()
+        .map_err(|e| e.to_string())?
+        .failed_samples
+        .take()
+        .ok_or_else(|| "No failed recording to retry".to_string())?;
+
+    hide_main_window(&app);
+    change_tray_icon(&app, TrayIconState::Transcribing);
+    show_transcribing_overlay(&app);
+
+    std::thread::spawn(move || {
+        std::thread::sleep(REFOCUS_DELAY);
+        tauri::async_runtime::block_on(transcribe_and_paste(&app, samples, None));
+    });
+    Ok(())
+}
+
+/// Turns off every remote engine, so transcription stays on this computer,
+/// and retries the failed recording with the local model
+#[tauri::command]
+#[specta::specta]
+pub fn switch_to_local_transcription(app: AppHandle) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.wyoming_enabled = false;
+    settings.deepgram_enabled = false;
+    settings.cloud_transcription.provider = None;
+    settings::write_settings(&app, settings);
+    retry_last_transcription(app)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn repaste_transcript(app: AppHandle) -> Result<(), String> {
+    let text = take_unpasted_text(&app)?;
+
+    // The button that triggered this lives in our window, so hand focus back
+    // to the target app before pasting.
+    utils::paste_after_refocus(&app, text);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn copy_unpasted_transcript(app: AppHandle) -> Result<(), String> {
+    let text = app
+        .state::<ManagedNotificationContext>()
+        .lock()
+        .map_err(|e| e.to_string())?
+        .unpasted_text
+        .clone()
+        .ok_or_else(|| "No transcript waiting to be pasted".to_string())?;
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| format!("Failed to copy transcript: {}", e))
+}
+
+fn take_unpasted_text(app: &AppHandle) -> Result<String, String> {
+    app.state::<ManagedNotificationContext>()
+        .lock()
+        .map_err(|e| e.to_string())?
+        .unpasted_text
+        .take()
+        .ok_or_else(|| "No transcript waiting to be pasted".to_string())
+}
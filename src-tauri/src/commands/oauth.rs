@@ -0,0 +1,80 @@
+use crate::oauth::{self, DeviceAuthorization};
+use crate::settings::{get_settings, write_settings};
+use log::info;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// Device code of the sign-in currently being polled, so a new sign-in or a
+/// cancel stops the old poll loop.
+static PENDING_SIGN_IN: Mutex<Option<String>> = Mutex::new(None);
+
+fn is_pending(device_code: &str) -> bool {
+    PENDING_SIGN_IN.lock().unwrap().as_deref() == Some(device_code)
+}
+
+/// Starts signing in to a provider and returns the code to show the user.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_post_process_sign_in(
+    app: AppHandle,
+    provider_id: String,
+) -> Result<DeviceAuthorization, String> {
+    let config = get_settings(&app)
+        .post_process_oauth
+        .get(&provider_id)
+        .cloned()
+        .ok_or_else(|| format!("Provider '{}' doesn't use OAuth sign-in", provider_id))?;
+
+    let authorization = oauth::request_device_code(&config).await?;
+    *PENDING_SIGN_IN.lock().unwrap() = Some(authorization.device_code.clone());
+    Ok(authorization)
+}
+
+/// Waits for the user to finish a sign-in started with
+/// `start_post_process_sign_in` and stores the token.
+#[tauri::command]
+#[specta::specta]
+pub async fn finish_post_process_sign_in(
+    app: AppHandle,
+    provider_id: String,
+    authorization: DeviceAuthorization,
+) -> Result<(), String> {
+    let config = get_settings(&app)
+        .post_process_oauth
+        .get(&provider_id)
+        .cloned()
+        .ok_or_else(|| format!("Provider '{}' doesn't use OAuth sign-in", provider_id))?;
+
+    let device_code = authorization.device_code.clone();
+    let result = oauth::wait_for_token(&config, &authorization, || !is_pending(&device_code)).await;
+    {
+        let mut pending = PENDING_SIGN_IN.lock().unwrap();
+        if pending.as_deref() == Some(device_code.as_str()) {
+            *pending = None;
+        }
+    }
+    let token = result?;
+
+    let mut settings = get_settings(&app);
+    settings
+        .post_process_oauth_tokens
+        .insert(provider_id.clone(), token);
+    write_settings(&app, settings);
+    info!("Signed in to post-processing provider '{}'", provider_id);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_post_process_sign_in() {
+    *PENDING_SIGN_IN.lock().unwrap() = None;
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn sign_out_post_process(app: AppHandle, provider_id: String) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.post_process_oauth_tokens.remove(&provider_id);
+    write_settings(&app, settings);
+    Ok(())
+}
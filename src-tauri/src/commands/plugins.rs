@@ -0,0 +1,42 @@
+use crate::managers::plugins::{PluginInfo, PluginManager};
+use crate::settings::{get_settings, write_settings};
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+use tauri_plugin_opener::OpenerExt;
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_plugins(plugin_manager: State<'_, Arc<PluginManager>>) -> Vec<PluginInfo> {
+    plugin_manager.list()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn reload_plugins(plugin_manager: State<'_, Arc<PluginManager>>) -> Vec<PluginInfo> {
+    plugin_manager.reload();
+    plugin_manager.list()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_plugin_enabled(app: AppHandle, plugin_id: String, enabled: bool) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.enabled_plugins.retain(|id| id != &plugin_id);
+    if enabled {
+        settings.enabled_plugins.push(plugin_id);
+    }
+    write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn open_plugins_dir(
+    app: AppHandle,
+    plugin_manager: State<'_, Arc<PluginManager>>,
+) -> Result<(), String> {
+    let path = plugin_manager.plugins_dir().to_string_lossy().to_string();
+    app.opener()
+        .open_path(path, None::<String>)
+        .map_err(|e| format!("Failed to open plugins directory: {}", e))
+}
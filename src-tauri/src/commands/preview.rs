@@ -0,0 +1,25 @@
+use crate::preview;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_preview_text(app: AppHandle) -> Option<String> {
+    preview::pending_text(&app)
+}
+
+/// Pastes the transcript with whatever edits were made in the popup.
+#[tauri::command]
+#[specta::specta]
+pub fn confirm_preview(app: AppHandle, text: String) -> Result<(), String> {
+    if !preview::has_pending_preview(&app) {
+        return Err("No transcript is waiting for confirmation".to_string());
+    }
+    preview::confirm_preview(&app, text);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_preview(app: AppHandle) {
+    preview::discard_preview(&app);
+}
@@ -1,8 +1,20 @@
+use crate::api_server;
+use crate::managers::batch::BatchJob;
+use crate::managers::pending::PendingTranscription;
 use crate::managers::transcription::TranscriptionManager;
-use crate::settings::{get_settings, write_settings, ModelUnloadTimeout};
+use crate::network;
+use crate::settings::{get_settings, write_settings, ModelUnloadTimeout, WordReplacement};
+use crate::wyoming::{self, WyomingServer};
+use handy_transcription::audio::is_audio_file;
+use handy_transcription::find_corrections;
 use serde::Serialize;
 use specta::Type;
-use tauri::{AppHandle, State};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(3);
 
 #[derive(Serialize, Type)]
 pub struct ModelLoadStatus {
@@ -38,3 +50,201 @@ pub fn unload_model_manually(
         .unload_model()
         .map_err(|e| format!("Failed to unload model: {}", e))
 }
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_batch_jobs(app: AppHandle) -> Vec<BatchJob> {
+    app.state::<Arc<TranscriptionManager>>().batch_jobs()
+}
+
+/// Transcribes an audio or video file now and saves it to history, where
+/// it can be exported like any dictation. Returns the transcript.
+#[tauri::command]
+#[specta::specta]
+pub async fn transcribe_audio_file(app: AppHandle, path: String) -> Result<String, String> {
+    let path = PathBuf::from(path.trim());
+    if !path.is_file() {
+        return Err(format!("File not found: {}", path.display()));
+    }
+    if !is_audio_file(&path) {
+        return Err(format!(
+            "Can't transcribe {}, it isn't an audio or video file",
+            path.display()
+        ));
+    }
+    let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
+    tauri::async_runtime::spawn_blocking(move || tm.transcribe_file(&path))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Adds an audio or video file to the batch queue, to be transcribed when the schedule
+/// allows it.
+#[tauri::command]
+#[specta::specta]
+pub fn queue_file_transcription(app: AppHandle, path: String) -> Result<(), String> {
+    app.state::<Arc<TranscriptionManager>>()
+        .queue_file(path)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn remove_batch_job(app: AppHandle, path: String) {
+    app.state::<Arc<TranscriptionManager>>()
+        .remove_batch_job(&path);
+}
+
+/// How many file transcripts are cached.
+#[tauri::command]
+#[specta::specta]
+pub fn get_file_cache_count(app: AppHandle) -> usize {
+    app.state::<Arc<TranscriptionManager>>().file_cache_count()
+}
+
+/// Forgets every cached file transcript, so files are transcribed again.
+#[tauri::command]
+#[specta::specta]
+pub fn clear_file_transcription_cache(app: AppHandle) {
+    app.state::<Arc<TranscriptionManager>>().clear_file_cache();
+}
+
+/// Transcribes every queued file now, regardless of the schedule. Returns how
+/// many were processed.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_batch_jobs_now(app: AppHandle) -> Result<usize, String> {
+    let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
+    tauri::async_runtime::spawn_blocking(move || tm.run_batch_jobs(true))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_pending_transcriptions(app: AppHandle) -> Vec<PendingTranscription> {
+    app.state::<Arc<TranscriptionManager>>()
+        .pending_transcriptions()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn remove_pending_transcription(app: AppHandle, id: String) {
+    app.state::<Arc<TranscriptionManager>>().remove_pending(&id);
+}
+
+/// Sends the recordings kept while offline again now instead of waiting for
+/// the next check. Returns how many were transcribed.
+#[tauri::command]
+#[specta::specta]
+pub async fn retry_pending_transcriptions(app: AppHandle) -> Result<usize, String> {
+    let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
+    tauri::async_runtime::spawn_blocking(move || {
+        network::recheck(&app);
+        tm.run_pending(true)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Looks for Wyoming speech servers advertised on the local network.
+#[tauri::command]
+#[specta::specta]
+pub async fn discover_wyoming_servers() -> Result<Vec<WyomingServer>, String> {
+    tauri::async_runtime::spawn_blocking(|| wyoming::discover(DISCOVERY_TIMEOUT))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("Failed to discover Wyoming servers: {}", e))
+}
+
+/// Connects to a Wyoming server and returns the name of its speech
+/// recognition program.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_wyoming_server(address: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || wyoming::describe(address.trim()))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("{:#}", e))
+}
+
+/// The corrections made from `original` to `edited` that aren't in the
+/// replacement dictionary yet, to offer learning them.
+#[tauri::command]
+#[specta::specta]
+pub fn suggest_word_replacements(
+    app: AppHandle,
+    original: String,
+    edited: String,
+) -> Vec<WordReplacement> {
+    let known = get_settings(&app).word_replacements;
+    find_corrections(&original, &edited)
+        .into_iter()
+        .map(|(from, to)| WordReplacement { from, to })
+        .filter(|suggestion| {
+            !known
+                .iter()
+                .any(|rule| rule.from.eq_ignore_ascii_case(&suggestion.from))
+        })
+        .collect()
+}
+
+/// Adds a correction to the replacement dictionary, replacing any rule for
+/// the same misheard form.
+#[tauri::command]
+#[specta::specta]
+pub fn learn_word_replacement(app: AppHandle, replacement: WordReplacement) -> Result<(), String> {
+    let from = replacement.from.trim();
+    let to = replacement.to.trim();
+    if from.is_empty() || to.is_empty() {
+        return Err("A replacement needs both a misheard and a corrected form".to_string());
+    }
+    let mut settings = get_settings(&app);
+    settings
+        .word_replacements
+        .retain(|rule| !rule.from.eq_ignore_ascii_case(from));
+    settings.word_replacements.push(WordReplacement {
+        from: from.to_string(),
+        to: to.to_string(),
+    });
+    write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_api_server_enabled(app: AppHandle, enabled: bool) {
+    let mut settings = get_settings(&app);
+    settings.api_server_enabled = enabled;
+    if enabled && settings.api_server_token.is_empty() {
+        settings.api_server_token = api_server::generate_token();
+    }
+    write_settings(&app, settings);
+    api_server::restart(&app);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_api_server_port(app: AppHandle, port: u16) -> Result<(), String> {
+    if port < 1024 {
+        return Err("Choose a port of 1024 or above".to_string());
+    }
+    let mut settings = get_settings(&app);
+    settings.api_server_port = port;
+    write_settings(&app, settings);
+    api_server::restart(&app);
+    Ok(())
+}
+
+/// Replaces the API server's token, so clients given the old one lose access
+#[tauri::command]
+#[specta::specta]
+pub fn regenerate_api_server_token(app: AppHandle) -> String {
+    let mut settings = get_settings(&app);
+    settings.api_server_token = api_server::generate_token();
+    let token = settings.api_server_token.clone();
+    write_settings(&app, settings);
+    api_server::restart(&app);
+    token
+}
@@ -0,0 +1,180 @@
+//! Profiles per window rather than per app. The first rule whose app and
+//! window title pattern both match the focused window decides which prompt
+//! runs, so a GitHub tab and a Gmail tab in the same browser can be
+//! formatted differently. A rule can also bring its own transcription
+//! prompt, for windows where different names and jargon come up, switch
+//! punctuation restoration and the trailing space, and press Enter after
+//! pasting, e.g. to send a chat message.
+//!
+//! Window titles aren't available on macOS (see `active_app`), so rules with
+//! a title pattern never match there.
+
+use crate::settings::{AppSettings, ContextRule};
+use log::{debug, warn};
+use regex::RegexBuilder;
+
+/// Checks that a title pattern compiles, for validating rules when they're
+/// saved.
+pub fn validate_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.is_empty() {
+        return Ok(());
+    }
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map(|_| ())
+        .map_err(|e| format!("Invalid window title pattern '{}': {}", pattern, e))
+}
+
+fn matches(rule: &ContextRule, app: Option<&str>, title: Option<&str>) -> bool {
+    let app_matches = rule.app.trim().is_empty()
+        || app.is_some_and(|app| app.eq_ignore_ascii_case(rule.app.trim()));
+    if !app_matches {
+        return false;
+    }
+    if rule.title_pattern.is_empty() {
+        return true;
+    }
+    match RegexBuilder::new(&rule.title_pattern)
+        .case_insensitive(true)
+        .build()
+    {
+        Ok(pattern) => title.is_some_and(|title| pattern.is_match(title)),
+        Err(e) => {
+            warn!("Skipping context rule '{}': {}", rule.name, e);
+            false
+        }
+    }
+}
+
+/// The first rule that matches the window, if any
+pub fn matching_rule<'a>(
+    rules: &'a [ContextRule],
+    app: Option<&str>,
+    title: Option<&str>,
+) -> Option<&'a ContextRule> {
+    rules.iter().find(|rule| matches(rule, app, title))
+}
+
+/// Switches `settings` to the prompt of the rule matching the window. A rule
+/// without a prompt turns post-processing off for that window.
+pub fn apply(settings: &mut AppSettings, app: Option<&str>, title: Option<&str>) {
+    let Some(rule) = matching_rule(&settings.context_rules, app, title) else {
+        return;
+    };
+    debug!(
+        "Context rule '{}' matches {:?} / {:?}",
+        rule.name, app, title
+    );
+    let prompt_id = rule.prompt_id.clone();
+    settings.post_process_enabled = prompt_id.is_some();
+    settings.post_process_selected_prompt_id = prompt_id;
+}
+
+/// Switches `settings` to the transcription prompt and punctuation of the
+/// rule matching the window, where it has its own.
+pub fn apply_transcription(settings: &mut AppSettings, app: Option<&str>, title: Option<&str>) {
+    let Some(rule) = matching_rule(&settings.context_rules, app, title).cloned() else {
+        return;
+    };
+    if let Some(prompt) = rule.transcription_prompt {
+        settings.transcription_prompt = prompt;
+    }
+    if let Some(punctuation) = rule.punctuation_restoration {
+        settings.punctuation_restoration = punctuation;
+    }
+}
+
+/// Switches `settings` to the trailing space of the rule matching the
+/// window, and returns whether Enter should be pressed after pasting.
+pub fn apply_output(settings: &mut AppSettings, app: Option<&str>, title: Option<&str>) -> bool {
+    let Some(rule) = matching_rule(&settings.context_rules, app, title) else {
+        return false;
+    };
+    let press_enter = rule.press_enter;
+    if let Some(space) = rule.append_trailing_space {
+        settings.append_trailing_space = space;
+    }
+    press_enter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(app: &str, title_pattern: &str) -> ContextRule {
+        ContextRule {
+            id: format!("{}-{}", app, title_pattern),
+            name: String::new(),
+            app: app.to_string(),
+            title_pattern: title_pattern.to_string(),
+            prompt_id: None,
+            transcription_prompt: None,
+            punctuation_restoration: None,
+            append_trailing_space: None,
+            press_enter: false,
+        }
+    }
+
+    #[test]
+    fn first_rule_matching_app_and_title_wins() {
+        let rules = vec![
+            rule("firefox", r"pull request #\d+"),
+            rule("Firefox", "gmail"),
+            rule("", "gmail"),
+            rule("slack", ""),
+        ];
+        let find = |app, title| matching_rule(&rules, app, title).map(|r| r.id.as_str());
+
+        assert_eq!(
+            find(
+                Some("firefox"),
+                Some("Fix paste · Pull Request #12 · GitHub")
+            ),
+            Some(r"firefox-pull request #\d+")
+        );
+        assert_eq!(
+            find(Some("firefox"), Some("Inbox - Gmail")),
+            Some("Firefox-gmail")
+        );
+        assert_eq!(find(Some("chrome"), Some("Inbox - Gmail")), Some("-gmail"));
+        assert_eq!(find(Some("Slack"), None), Some("slack-"));
+        assert_eq!(find(Some("firefox"), None), None);
+        assert_eq!(find(None, Some("GitHub")), None);
+    }
+
+    #[test]
+    fn rules_override_transcription_and_output() {
+        let mut settings = crate::settings::get_default_settings();
+        settings.punctuation_restoration = true;
+        settings.append_trailing_space = true;
+        settings.context_rules = vec![ContextRule {
+            transcription_prompt: Some("Rust, cargo, clippy".to_string()),
+            punctuation_restoration: Some(false),
+            append_trailing_space: Some(false),
+            press_enter: true,
+            ..rule("slack", "")
+        }];
+
+        let mut other = settings.clone();
+        apply_transcription(&mut other, Some("Code"), None);
+        assert!(!apply_output(&mut other, Some("Code"), None));
+        assert!(other.punctuation_restoration && other.append_trailing_space);
+
+        apply_transcription(&mut settings, Some("Slack"), None);
+        assert!(apply_output(&mut settings, Some("Slack"), None));
+        assert_eq!(settings.transcription_prompt, "Rust, cargo, clippy");
+        assert!(!settings.punctuation_restoration && !settings.append_trailing_space);
+    }
+
+    #[test]
+    fn invalid_patterns_never_match() {
+        let rules = vec![rule("", "(unclosed")];
+        assert_eq!(
+            matching_rule(&rules, Some("app"), Some("(unclosed")).map(|r| &r.id),
+            None
+        );
+        assert!(validate_pattern("(unclosed").is_err());
+        assert!(validate_pattern("").is_ok());
+    }
+}
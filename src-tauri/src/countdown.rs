@@ -0,0 +1,84 @@
+//! Optional countdown before a binding starts recording, so the key click
+//! isn't captured and there's a moment to get ready. Each second emits
+//! `recording-countdown` with the seconds left and plays the countdown cue.
+//! Cancel, or pressing the binding again, drops it before anything is
+//! recorded.
+
+use crate::audio_feedback::{play_feedback_sound, SoundType};
+use crate::settings::get_settings;
+use log::{debug, info};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Countdowns can't run for longer than this
+pub const MAX_COUNTDOWN_SECS: u32 = 10;
+
+/// Bumped for every countdown so one that was cancelled exits
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+static PENDING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Debug, Serialize)]
+struct CountdownTick {
+    binding_id: String,
+    remaining_secs: u32,
+}
+
+/// The countdown `binding_id` is set up with, 0 if none
+pub fn countdown_secs(app: &AppHandle, binding_id: &str) -> u32 {
+    get_settings(app)
+        .bindings
+        .get(binding_id)
+        .map(|binding| binding.countdown_secs.min(MAX_COUNTDOWN_SECS))
+        .unwrap_or(0)
+}
+
+pub fn is_counting_down() -> bool {
+    PENDING.load(Ordering::Relaxed)
+}
+
+/// Counts down `secs` seconds, then calls `start` unless the countdown was
+/// cancelled in the meantime.
+pub fn begin<F>(app: &AppHandle, binding_id: &str, secs: u32, start: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    let generation = GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    PENDING.store(true, Ordering::Relaxed);
+    info!("Counting down {}s before recording '{}'", secs, binding_id);
+
+    let app = app.clone();
+    let binding_id = binding_id.to_string();
+    thread::spawn(move || {
+        let current = || GENERATION.load(Ordering::Relaxed) == generation;
+        for remaining_secs in (1..=secs).rev() {
+            if !current() {
+                return;
+            }
+            let _ = app.emit(
+                "recording-countdown",
+                CountdownTick {
+                    binding_id: binding_id.clone(),
+                    remaining_secs,
+                },
+            );
+            play_feedback_sound(&app, SoundType::Countdown);
+            thread::sleep(Duration::from_secs(1));
+        }
+        if current() && PENDING.swap(false, Ordering::Relaxed) {
+            start();
+        }
+    });
+}
+
+/// Drops a countdown that hasn't finished yet. Returns true if there was one.
+pub fn cancel() -> bool {
+    if !PENDING.swap(false, Ordering::Relaxed) {
+        return false;
+    }
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+    debug!("Recording countdown cancelled");
+    true
+}
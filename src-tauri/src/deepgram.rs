@@ -0,0 +1,159 @@
+//! Transcription with Deepgram's hosted API, through the client in
+//! `handy_transcription::deepgram`. With streaming on, dictations are also
+//! sent to the live endpoint while they record, through the session in
+//! `openai_realtime`, so the overlay shows partial text and the transcript is
+//! ready when recording stops.
+
+use crate::openai_realtime::{self, AudioFeed, Chunk};
+use crate::settings::{AppSettings, DeepgramConfig, UploadCodec};
+use crate::transcription_provider;
+use anyhow::{anyhow, Result};
+use handy_transcription::audio::to_pcm16;
+use handy_transcription::deepgram::{self as api, Codec};
+use log::info;
+use serde_json::json;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tungstenite::{Error as WsError, Message};
+
+/// The selected language as Deepgram takes it, or `None` to detect it.
+/// Deepgram understands the same BCP-47 tags, including `zh-Hans`/`zh-Hant`.
+fn language(settings: &AppSettings) -> Option<String> {
+    match settings.selected_language.as_str() {
+        "auto" => None,
+        other => Some(other.to_string()),
+    }
+}
+
+fn client_config(config: &DeepgramConfig) -> api::Config {
+    api::Config {
+        api_key: config.api_key.clone(),
+        model: config.model.clone(),
+        codec: match config.upload_codec {
+            UploadCodec::Wav => Codec::Wav,
+            UploadCodec::Flac => Codec::Flac,
+        },
+        connect_timeout: Duration::from_secs(config.connect_timeout_secs.max(1)),
+        read_timeout: Duration::from_secs(config.read_timeout_secs.max(1)),
+        max_retries: config.max_retries,
+        diarize: config.diarize,
+    }
+}
+
+/// A prerecorded API client for `config`, telling `on_retry` about each
+/// retry of a transient failure
+pub fn client<'r>(
+    config: &DeepgramConfig,
+    on_retry: &'r (dyn Fn(u32, u32, Duration) + Sync),
+) -> api::Client<'r> {
+    api::Client {
+        config: client_config(config),
+        on_retry,
+    }
+}
+
+/// Final results so far, plus the latest interim result after them
+#[derive(Default)]
+struct LiveTranscript {
+    finals: Vec<String>,
+    interim: String,
+}
+
+impl LiveTranscript {
+    fn update(&mut self, is_final: bool, text: &str) {
+        if is_final {
+            if !text.is_empty() {
+                self.finals.push(text.to_string());
+            }
+            self.interim.clear();
+        } else {
+            self.interim = text.to_string();
+        }
+    }
+
+    fn text(&self) -> String {
+        self.finals
+            .iter()
+            .chain(std::iter::once(&self.interim))
+            .filter(|text| !text.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Streams `feed` to the live API, emitting `transcription-partial` as
+/// results come in, and returns the final transcript.
+pub fn stream(app: &AppHandle, settings: &AppSettings, feed: &mut AudioFeed) -> Result<String> {
+    let config = client_config(&settings.deepgram);
+    let request = api::live_request(
+        &config,
+        language(settings),
+        transcription_provider::vocabulary(settings),
+    )?;
+    let (mut socket, _) = tungstenite::connect(request)
+        .map_err(|e| anyhow!("Couldn't connect to Deepgram: {}", e))?;
+    openai_realtime::set_poll_timeout(&socket)?;
+    info!("Streaming dictation to Deepgram ({})", api::model(&config));
+
+    let mut transcript = LiveTranscript::default();
+    let mut finished_at: Option<Instant> = None;
+
+    loop {
+        match feed.next()? {
+            Some(Chunk::Audio(samples)) if !samples.is_empty() => {
+                socket.send(Message::Binary(to_pcm16(&samples)))?;
+            }
+            Some(Chunk::Audio(_)) | None => {}
+            Some(Chunk::Last(samples)) => {
+                if !samples.is_empty() {
+                    socket.send(Message::Binary(to_pcm16(&samples)))?;
+                }
+                // Deepgram sends the remaining results, then closes
+                socket.send(Message::Text(json!({ "type": "CloseStream" }).to_string()))?;
+                finished_at = Some(Instant::now());
+            }
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                if let Ok(api::LiveEvent::Results { is_final, channel }) =
+                    serde_json::from_str(&text)
+                {
+                    transcript.update(is_final, channel.transcript());
+                    let _ = app.emit("transcription-partial", transcript.text());
+                }
+            }
+            Ok(Message::Close(_)) | Err(WsError::ConnectionClosed | WsError::AlreadyClosed)
+                if finished_at.is_some() =>
+            {
+                return Ok(transcript.text());
+            }
+            Ok(Message::Close(_)) => return Err(anyhow!("Deepgram closed the connection")),
+            Ok(_) => {}
+            Err(e) if openai_realtime::is_timeout(&e) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        if finished_at.is_some_and(|at| at.elapsed() > openai_realtime::FINISH_TIMEOUT) {
+            return Err(anyhow!("Deepgram didn't finish the transcript in time"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn live_transcript_replaces_interim_results() {
+        let mut transcript = LiveTranscript::default();
+        transcript.update(false, "Hel");
+        assert_eq!(transcript.text(), "Hel");
+        transcript.update(true, "Hello.");
+        transcript.update(false, "How");
+        assert_eq!(transcript.text(), "Hello. How");
+        transcript.update(true, "");
+        assert_eq!(transcript.text(), "Hello.");
+    }
+}
@@ -0,0 +1,162 @@
+//! The application that has keyboard focus, which is where a dictation is
+//! about to be pasted.
+//!
+//! - macOS asks NSWorkspace for the frontmost application.
+//! - Windows reads the executable of the foreground window's process.
+//! - Linux uses `xdotool`, so it only works on X11 (and XWayland windows).
+//!
+//! Window titles and positions are only read on Windows and Linux. macOS
+//! needs the screen recording permission to see other apps' window titles.
+
+/// Name of the focused application, e.g. "Code" or "Slack"
+#[cfg(target_os = "macos")]
+pub fn active_app() -> Option<String> {
+    use objc2_app_kit::NSWorkspace;
+
+    let name = NSWorkspace::sharedWorkspace()
+        .frontmostApplication()?
+        .localizedName()?;
+    Some(name.to_string())
+}
+
+/// Name of the focused application, e.g. "Code" or "Slack"
+#[cfg(target_os = "windows")]
+pub fn active_app() -> Option<String> {
+    use std::path::Path;
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_invalid() {
+            return None;
+        }
+
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut path = [0u16; 1024];
+        let mut path_len = path.len() as u32;
+        let result = QueryFullProcessImageNameW(
+            process,
+            PROCESS_NAME_WIN32,
+            PWSTR(path.as_mut_ptr()),
+            &mut path_len,
+        );
+        let _ = CloseHandle(process);
+        result.ok()?;
+
+        let path = String::from_utf16_lossy(&path[..path_len as usize]);
+        Some(Path::new(&path).file_stem()?.to_string_lossy().to_string())
+    }
+}
+
+/// Name of the focused application, e.g. "code" or "slack"
+#[cfg(target_os = "linux")]
+pub fn active_app() -> Option<String> {
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowpid"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let pid = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let name = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn active_app() -> Option<String> {
+    None
+}
+
+/// Title of the focused window, e.g. "Re: Invoice - Outlook"
+#[cfg(target_os = "windows")]
+pub fn active_window_title() -> Option<String> {
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowTextW};
+
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_invalid() {
+            return None;
+        }
+        let mut title = [0u16; 512];
+        let len = GetWindowTextW(hwnd, &mut title);
+        Some(String::from_utf16_lossy(&title[..len.max(0) as usize])).filter(|t| !t.is_empty())
+    }
+}
+
+/// Title of the focused window, e.g. "Re: Invoice - Thunderbird"
+#[cfg(target_os = "linux")]
+pub fn active_window_title() -> Option<String> {
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowname"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let title = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Some(title).filter(|title| !title.is_empty())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn active_window_title() -> Option<String> {
+    None
+}
+
+/// Centre of the focused window in physical screen coordinates, to find the
+/// monitor it's on
+#[cfg(target_os = "windows")]
+pub fn active_window_center() -> Option<(i32, i32)> {
+    use windows::Win32::Foundation::RECT;
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowRect};
+
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_invalid() {
+            return None;
+        }
+        let mut rect = RECT::default();
+        GetWindowRect(hwnd, &mut rect).ok()?;
+        Some(((rect.left + rect.right) / 2, (rect.top + rect.bottom) / 2))
+    }
+}
+
+/// Centre of the focused window in physical screen coordinates, to find the
+/// monitor it's on
+#[cfg(target_os = "linux")]
+pub fn active_window_center() -> Option<(i32, i32)> {
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowgeometry", "--shell"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // Lines like "X=120", "WIDTH=1280"
+    let geometry = String::from_utf8_lossy(&output.stdout);
+    let value = |key: &str| -> Option<i32> {
+        geometry
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))?
+            .trim()
+            .parse()
+            .ok()
+    };
+    Some((
+        value("X")? + value("WIDTH")? / 2,
+        value("Y")? + value("HEIGHT")? / 2,
+    ))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn active_window_center() -> Option<(i32, i32)> {
+    None
+}
@@ -0,0 +1,239 @@
+//! Detects a video call capturing the microphone, so push-to-talk dictation
+//! doesn't end up broadcast into the meeting.
+//!
+//! - Linux asks PulseAudio (or PipeWire's pulse server) which applications
+//!   are recording.
+//! - Windows reads the microphone privacy usage records, which mark the apps
+//!   currently using it.
+//! - macOS doesn't expose per-app microphone use. A call is assumed when the
+//!   default input is in use by some process and a conferencing app is
+//!   running, which can't be told apart from Handy's own stream while it has
+//!   the microphone open.
+
+use std::process::Command;
+
+/// Substrings of process names, binaries or package IDs, and the name to
+/// show for them. Browsers are left out: they record for much more than
+/// calls, so a browser tab would trip the guard for any voice input.
+const CONFERENCING_APPS: &[(&str, &str)] = &[
+    ("zoom", "Zoom"),
+    ("teams", "Microsoft Teams"),
+    ("webex", "Webex"),
+    ("slack", "Slack"),
+    ("discord", "Discord"),
+    ("skype", "Skype"),
+];
+
+fn conferencing_app_name(identifier: &str) -> Option<&'static str> {
+    let identifier = identifier.to_lowercase();
+    CONFERENCING_APPS
+        .iter()
+        .find(|(needle, _)| identifier.contains(needle))
+        .map(|(_, name)| *name)
+}
+
+/// Application names and binaries of the recording streams listed by
+/// `pactl list source-outputs`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_pactl_source_outputs(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once(" = ")?;
+            matches!(key, "application.name" | "application.process.binary")
+                .then(|| value.trim_matches('"').to_string())
+        })
+        .collect()
+}
+
+/// Apps with an open microphone session in the output of
+/// `reg query ...\ConsentStore\microphone /s`. Those have a start time but
+/// no stop time yet.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn parse_consent_store(output: &str) -> Vec<String> {
+    let mut in_use = Vec::new();
+    let mut key: Option<&str> = None;
+    let mut started = false;
+
+    for line in output.lines() {
+        if line.starts_with("HKEY_") {
+            key = line.trim().rsplit('\\').next();
+            started = false;
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["LastUsedTimeStart", _, value] => started = *value != "0x0",
+            ["LastUsedTimeStop", _, "0x0"] if started => {
+                if let Some(key) = key {
+                    in_use.push(key.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    in_use
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn capturing_apps(_own_stream_open: bool) -> Vec<String> {
+    command_output("pactl", &["list", "source-outputs"])
+        .map(|output| parse_pactl_source_outputs(&output))
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "windows")]
+fn capturing_apps(_own_stream_open: bool) -> Vec<String> {
+    command_output(
+        "reg",
+        &[
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone",
+            "/s",
+        ],
+    )
+    .map(|output| parse_consent_store(&output))
+    .unwrap_or_default()
+}
+
+/// Whether any process is using the default input device, from CoreAudio's
+/// `kAudioDevicePropertyDeviceIsRunningSomewhere`
+#[cfg(target_os = "macos")]
+fn default_input_in_use() -> bool {
+    use std::ffi::c_void;
+    use std::ptr;
+
+    #[repr(C)]
+    struct AudioObjectPropertyAddress {
+        selector: u32,
+        scope: u32,
+        element: u32,
+    }
+
+    #[link(name = "CoreAudio", kind = "framework")]
+    extern "C" {
+        fn AudioObjectGetPropertyData(
+            object: u32,
+            address: *const AudioObjectPropertyAddress,
+            qualifier_size: u32,
+            qualifier: *const c_void,
+            size: *mut u32,
+            data: *mut c_void,
+        ) -> i32;
+    }
+
+    const SYSTEM_OBJECT: u32 = 1;
+    const SCOPE_GLOBAL: u32 = u32::from_be_bytes(*b"glob");
+    const DEFAULT_INPUT_DEVICE: u32 = u32::from_be_bytes(*b"dIn ");
+    const IS_RUNNING_SOMEWHERE: u32 = u32::from_be_bytes(*b"gone");
+
+    /// A `u32` property of `object`, such as a device ID or a flag
+    fn read_u32(object: u32, selector: u32) -> Option<u32> {
+        let address = AudioObjectPropertyAddress {
+            selector,
+            scope: SCOPE_GLOBAL,
+            element: 0,
+        };
+        let mut value = 0u32;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        // SAFETY: `value` and `size` describe a u32 buffer, which is what
+        // both properties read here hold
+        let status = unsafe {
+            AudioObjectGetPropertyData(
+                object,
+                &address,
+                0,
+                ptr::null(),
+                &mut size,
+                &mut value as *mut u32 as *mut c_void,
+            )
+        };
+        (status == 0).then_some(value)
+    }
+
+    read_u32(SYSTEM_OBJECT, DEFAULT_INPUT_DEVICE)
+        .filter(|&device| device != 0)
+        .and_then(|device| read_u32(device, IS_RUNNING_SOMEWHERE))
+        .is_some_and(|running| running != 0)
+}
+
+#[cfg(target_os = "macos")]
+fn capturing_apps(own_stream_open: bool) -> Vec<String> {
+    if own_stream_open || !default_input_in_use() {
+        return Vec::new();
+    }
+    command_output("ps", &["-axo", "comm="])
+        .map(|output| {
+            output
+                .lines()
+                .filter(|process| process.contains(".app/"))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn capturing_apps(_own_stream_open: bool) -> Vec<String> {
+    Vec::new()
+}
+
+/// The name of a conferencing app that's using the microphone right now.
+/// `own_stream_open` is whether Handy has the microphone open itself.
+pub fn active_conferencing_app(own_stream_open: bool) -> Option<&'static str> {
+    capturing_apps(own_stream_open)
+        .iter()
+        .find_map(|app| conferencing_app_name(app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_recording_apps_in_pactl_output() {
+        let output = r#"Source Output #42
+	Driver: protocol-native.c
+	Properties:
+		media.name = "RecordStream"
+		application.name = "ZOOM VoiceEngine"
+		application.process.binary = "zoom"
+
+Source Output #43
+	Properties:
+		application.name = "handy"
+"#;
+        let apps = parse_pactl_source_outputs(output);
+        assert_eq!(apps, vec!["ZOOM VoiceEngine", "zoom", "handy"]);
+        assert_eq!(
+            apps.iter().find_map(|app| conferencing_app_name(app)),
+            Some("Zoom")
+        );
+        assert_eq!(conferencing_app_name("handy"), None);
+    }
+
+    #[test]
+    fn finds_open_microphone_sessions_in_the_registry() {
+        let output = r"
+HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone\MSTeams_8wekyb3d8bbwe
+    LastUsedTimeStart    REG_QWORD    0x1da1c2b3c4d5e6f
+    LastUsedTimeStop    REG_QWORD    0x0
+
+HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone\NonPackaged\C:#Program Files#Slack#slack.exe
+    LastUsedTimeStart    REG_QWORD    0x1da1c2b3c4d5e6f
+    LastUsedTimeStop    REG_QWORD    0x1da1c2b3c4d5e70
+";
+        let apps = parse_consent_store(output);
+        assert_eq!(apps, vec!["MSTeams_8wekyb3d8bbwe"]);
+        assert_eq!(conferencing_app_name(&apps[0]), Some("Microsoft Teams"));
+    }
+}
@@ -0,0 +1,78 @@
+//! How long the user has been away from the keyboard and mouse.
+//!
+//! - Windows uses GetLastInputInfo.
+//! - macOS reads the HID system's idle time from `ioreg`.
+//! - Linux runs `xprintidle`, which only knows about X11 input.
+
+use std::time::Duration;
+
+/// `HIDIdleTime` from `ioreg -c IOHIDSystem`, in nanoseconds
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_hid_idle_time(output: &str) -> Option<Duration> {
+    output.lines().find_map(|line| {
+        let (_, value) = line.split_once("\"HIDIdleTime\" = ")?;
+        value.trim().parse().ok().map(Duration::from_nanos)
+    })
+}
+
+#[cfg(target_os = "macos")]
+pub fn system_idle_time() -> Option<Duration> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-c", "IOHIDSystem", "-d", "4"])
+        .output()
+        .ok()?;
+    parse_hid_idle_time(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "windows")]
+pub fn system_idle_time() -> Option<Duration> {
+    use windows::Win32::System::SystemInformation::GetTickCount;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    unsafe {
+        if !GetLastInputInfo(&mut info).as_bool() {
+            return None;
+        }
+        let idle_ms = GetTickCount().wrapping_sub(info.dwTime);
+        Some(Duration::from_millis(idle_ms as u64))
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn system_idle_time() -> Option<Duration> {
+    let output = std::process::Command::new("xprintidle").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_millis)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn system_idle_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_idle_time_from_ioreg() {
+        let output = r#"    | |   "HIDParameters" = {"HIDClickTime"=500000000}
+    | |   "HIDIdleTime" = 61500000000
+    | |   "HIDKeyboardModifierMappingPairs" = ()"#;
+        assert_eq!(
+            parse_hid_idle_time(output),
+            Some(Duration::from_secs_f64(61.5))
+        );
+        assert_eq!(parse_hid_idle_time("nothing here"), None);
+    }
+}
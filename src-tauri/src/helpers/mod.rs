@@ -1 +1,5 @@
+pub mod active_app;
 pub mod clamshell;
+pub mod conferencing;
+pub mod idle;
+pub mod selection;
@@ -0,0 +1,139 @@
+//! The text selected in the focused app, and all of the focused field's
+//! text, read through the platform accessibility API so the selection can be
+//! replaced reliably before pasting and a paste can be checked afterwards.
+//!
+//! - macOS reads `AXSelectedText` and `AXValue` of the focused element, which
+//!   needs the accessibility permission Handy already asks for to paste.
+//! - Windows asks UI Automation for the focused element's text pattern, or
+//!   its value for plain edit controls.
+//! - Elsewhere neither can be read and `None` is returned.
+//!
+//! `Some("")` means the focused element was read and nothing is selected;
+//! `None` means it couldn't be told, e.g. the app doesn't expose its text.
+
+/// String `attribute` of the focused element
+#[cfg(target_os = "macos")]
+fn focused_attribute(attribute: &str) -> Option<String> {
+    use objc2_foundation::NSString;
+    use std::ffi::c_void;
+    use std::ptr;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateSystemWide() -> *const c_void;
+        fn AXUIElementCopyAttributeValue(
+            element: *const c_void,
+            attribute: *const c_void,
+            value: *mut *const c_void,
+        ) -> i32;
+    }
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFRelease(cf: *const c_void);
+        fn CFGetTypeID(cf: *const c_void) -> usize;
+        fn CFStringGetTypeID() -> usize;
+    }
+
+    /// Copies `attribute` of `element`; the caller releases it
+    unsafe fn copy_attribute(element: *const c_void, attribute: &str) -> Option<*const c_void> {
+        // NSString is toll-free bridged with CFString
+        let name = NSString::from_str(attribute);
+        let mut value = ptr::null();
+        let error = AXUIElementCopyAttributeValue(
+            element,
+            &*name as *const NSString as *const c_void,
+            &mut value,
+        );
+        (error == 0 && !value.is_null()).then_some(value)
+    }
+
+    unsafe {
+        let system = AXUIElementCreateSystemWide();
+        if system.is_null() {
+            return None;
+        }
+        let focused = copy_attribute(system, "AXFocusedUIElement");
+        CFRelease(system);
+        let focused = focused?;
+        let value = copy_attribute(focused, attribute);
+        CFRelease(focused);
+        let value = value?;
+        // The value of e.g. a slider is a number
+        let text = (CFGetTypeID(value) == CFStringGetTypeID())
+            .then(|| (*(value as *const NSString)).to_string());
+        CFRelease(value);
+        text
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn selected_text() -> Option<String> {
+    focused_attribute("AXSelectedText")
+}
+
+#[cfg(target_os = "macos")]
+pub fn focused_text() -> Option<String> {
+    focused_attribute("AXValue")
+}
+
+#[cfg(target_os = "windows")]
+fn focused_element() -> Option<windows::Win32::UI::Accessibility::IUIAutomationElement> {
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED,
+    };
+    use windows::Win32::UI::Accessibility::{CUIAutomation, IUIAutomation};
+
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let automation: IUIAutomation =
+            CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER).ok()?;
+        automation.GetFocusedElement().ok()
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn selected_text() -> Option<String> {
+    use windows::Win32::UI::Accessibility::{IUIAutomationTextPattern, UIA_TextPatternId};
+
+    unsafe {
+        let pattern: IUIAutomationTextPattern = focused_element()?
+            .GetCurrentPatternAs(UIA_TextPatternId)
+            .ok()?;
+        let ranges = pattern.GetSelection().ok()?;
+        let mut text = String::new();
+        for i in 0..ranges.Length().ok()? {
+            text.push_str(&ranges.GetElement(i).ok()?.GetText(-1).ok()?.to_string());
+        }
+        Some(text)
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn focused_text() -> Option<String> {
+    use windows::Win32::UI::Accessibility::{
+        IUIAutomationTextPattern, IUIAutomationValuePattern, UIA_TextPatternId, UIA_ValuePatternId,
+    };
+
+    unsafe {
+        let focused = focused_element()?;
+        if let Ok(pattern) =
+            focused.GetCurrentPatternAs::<IUIAutomationTextPattern>(UIA_TextPatternId)
+        {
+            return Some(pattern.DocumentRange().ok()?.GetText(-1).ok()?.to_string());
+        }
+        // Plain edit controls only have a value
+        let pattern: IUIAutomationValuePattern =
+            focused.GetCurrentPatternAs(UIA_ValuePatternId).ok()?;
+        Some(pattern.CurrentValue().ok()?.to_string())
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn selected_text() -> Option<String> {
+    None
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn focused_text() -> Option<String> {
+    crate::atspi::focused_text()
+}
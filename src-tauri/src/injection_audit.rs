@@ -0,0 +1,153 @@
+//! Optional audit trail of the text Handy types or pastes into other apps:
+//! when, into which app and window, and how, so you can later check what was
+//! dictated where.
+//!
+//! Each injection can also carry an invisible marker, its timestamp written
+//! in zero-width characters at the end of the text. Copying the text back out
+//! of the target app and looking it up finds the exact record, even after it
+//! was edited.
+
+use crate::managers::history::HistoryManager;
+use crate::settings::{AppSettings, PasteMethod};
+use chrono::Utc;
+use log::error;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+/// Brackets the marker so it can be found inside surrounding text
+const MARKER_FENCE: char = '\u{2063}';
+/// Zero-width characters for the base-4 digits of the timestamp
+const MARKER_DIGITS: [char; 4] = ['\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}'];
+
+fn method_name(method: PasteMethod) -> &'static str {
+    match method {
+        PasteMethod::CtrlV => "ctrl_v",
+        PasteMethod::Direct => "direct",
+        PasteMethod::None => "none",
+        PasteMethod::ShiftInsert => "shift_insert",
+        PasteMethod::CtrlShiftV => "ctrl_shift_v",
+        PasteMethod::VirtualKeyboard => "virtual_keyboard",
+        PasteMethod::Accessibility => "accessibility",
+    }
+}
+
+/// Invisible marker encoding `timestamp_ms`
+pub fn marker(timestamp_ms: i64) -> String {
+    let mut digits = Vec::new();
+    let mut value = timestamp_ms.max(0) as u64;
+    loop {
+        digits.push(MARKER_DIGITS[(value % 4) as usize]);
+        value /= 4;
+        if value == 0 {
+            break;
+        }
+    }
+    std::iter::once(MARKER_FENCE)
+        .chain(digits.into_iter().rev())
+        .chain(std::iter::once(MARKER_FENCE))
+        .collect()
+}
+
+/// Timestamp of the last well-formed marker in `text`, if it has one
+pub fn find_marker(text: &str) -> Option<i64> {
+    let parts: Vec<&str> = text.split(MARKER_FENCE).collect();
+    // A marker is a run of digits with a fence on both sides
+    parts
+        .get(1..parts.len().saturating_sub(1))?
+        .iter()
+        .filter_map(|part| {
+            if part.is_empty() {
+                return None;
+            }
+            part.chars().try_fold(0i64, |value, c| {
+                let digit = MARKER_DIGITS.iter().position(|&d| d == c)?;
+                value.checked_mul(4)?.checked_add(digit as i64)
+            })
+        })
+        .last()
+}
+
+/// An injection about to happen, captured before the paste so the window is
+/// the one the text goes into.
+pub struct Injection {
+    timestamp_ms: i64,
+    target_app: Option<String>,
+    window_title: Option<String>,
+    method: &'static str,
+    marked: bool,
+}
+
+impl Injection {
+    /// `None` unless auditing is on and `method` actually injects text. The
+    /// app and window are the ones the caller already looked up.
+    pub fn start(
+        settings: &AppSettings,
+        method: PasteMethod,
+        target_app: Option<String>,
+        window_title: Option<String>,
+    ) -> Option<Self> {
+        if !settings.injection_audit || method == PasteMethod::None {
+            return None;
+        }
+        Some(Self {
+            timestamp_ms: Utc::now().timestamp_millis(),
+            target_app,
+            window_title,
+            method: method_name(method),
+            marked: settings.injection_marker,
+        })
+    }
+
+    /// `text` with the invisible marker appended, if markers are on.
+    pub fn mark(&self, text: String) -> String {
+        if self.marked {
+            text + &marker(self.timestamp_ms)
+        } else {
+            text
+        }
+    }
+
+    /// Logs the injection of `text`, as it was before marking. The database
+    /// is written in the background so the caller isn't held up.
+    pub fn record(self, app: &AppHandle, text: String) {
+        let Some(hm) = app.try_state::<Arc<HistoryManager>>() else {
+            return;
+        };
+        let hm = Arc::clone(&hm);
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = hm.record_injection(
+                self.timestamp_ms,
+                self.target_app.as_deref(),
+                self.window_title.as_deref(),
+                self.method,
+                &text,
+                self.marked,
+            ) {
+                error!("Failed to record text injection: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marker_round_trips_through_surrounding_text() {
+        let timestamp = 1_760_000_000_123;
+        let marked = format!("Dear customer,{} thanks!", marker(timestamp));
+        assert_eq!(find_marker(&marked), Some(timestamp));
+        assert!(marked.starts_with("Dear customer,"));
+        assert_eq!(find_marker(&marker(0)), Some(0));
+
+        // The last of several markers wins, stray fences are ignored
+        let both = format!("{}a{}", marker(5), marker(9));
+        assert_eq!(find_marker(&both), Some(9));
+        let stray = format!("\u{2063}a{}", marker(7));
+        assert_eq!(find_marker(&stray), Some(7));
+        assert_eq!(find_marker("plain text"), None);
+        assert_eq!(find_marker("\u{2063}not a marker\u{2063}"), None);
+        assert_eq!(find_marker("\u{2063}\u{200B}"), None);
+    }
+}
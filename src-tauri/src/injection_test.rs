@@ -0,0 +1,151 @@
+//! Dry run of the paste methods against whatever app has focus, to find one
+//! that works there without dictating anything.
+//!
+//! Each method puts a short marker into the focused text field, which is
+//! then read back through the accessibility API the way the paste check
+//! does. Nothing is selected or copied, so a terminal isn't sent a Ctrl+C.
+//! Whatever the method added is deleted again, whether or not it was the
+//! marker. Apps whose field can't be read aren't tested.
+
+use crate::clipboard::inject;
+use crate::helpers::active_app::active_app;
+use crate::helpers::selection::focused_text;
+use crate::input::{self, EnigoState};
+use crate::paste_check;
+use crate::settings::{get_settings, write_settings, PasteMethod};
+use chrono::Utc;
+use enigo::Key;
+use log::{debug, info};
+use serde::Serialize;
+use specta::Type;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// Methods to try, most preferred first
+#[cfg(target_os = "macos")]
+const CANDIDATES: &[PasteMethod] = &[PasteMethod::CtrlV, PasteMethod::Direct];
+#[cfg(target_os = "windows")]
+const CANDIDATES: &[PasteMethod] = &[
+    PasteMethod::CtrlV,
+    PasteMethod::Direct,
+    PasteMethod::CtrlShiftV,
+    PasteMethod::ShiftInsert,
+];
+#[cfg(target_os = "linux")]
+const CANDIDATES: &[PasteMethod] = &[
+    PasteMethod::CtrlV,
+    PasteMethod::Direct,
+    PasteMethod::CtrlShiftV,
+    PasteMethod::ShiftInsert,
+    PasteMethod::Accessibility,
+];
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+const CANDIDATES: &[PasteMethod] = &[PasteMethod::CtrlV, PasteMethod::Direct];
+
+/// Time for the app to take in the marker before it is read back
+const SETTLE: Duration = Duration::from_millis(300);
+
+const UNREADABLE: &str =
+    "The focused field can't be read back, so paste methods can't be tested in it";
+
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct MethodResult {
+    pub method: PasteMethod,
+    pub worked: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct InjectionTestReport {
+    /// The app that was tested, if it could be identified
+    pub app: Option<String>,
+    pub results: Vec<MethodResult>,
+    /// The method saved as the app's override, if one worked
+    pub applied: Option<PasteMethod>,
+}
+
+/// Harmless marker of letters and digits only, which the read-back compares
+/// exactly
+fn marker(attempt: usize) -> String {
+    format!("handy{}{}", attempt, Utc::now().timestamp_millis() % 10_000)
+}
+
+/// How many characters the field grew by
+fn added_chars(before: &str, after: &str) -> usize {
+    after.chars().count().saturating_sub(before.chars().count())
+}
+
+fn try_method(app: &AppHandle, method: PasteMethod, marker: &str) -> Result<bool, String> {
+    let before = focused_text().ok_or(UNREADABLE)?;
+    let enigo_state = app
+        .try_state::<EnigoState>()
+        .ok_or("Enigo state not initialized")?;
+    let mut enigo = enigo_state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+
+    // Part of the marker may be in even if the method failed halfway
+    let injected = inject(&mut enigo, marker, app, method);
+    std::thread::sleep(SETTLE);
+    let after = focused_text().ok_or(UNREADABLE)?;
+    debug!("Read back {:?} after {:?}", after, method);
+
+    for _ in 0..added_chars(&before, &after) {
+        input::click_key(&mut enigo, Key::Backspace)?;
+    }
+    injected?;
+    Ok(paste_check::appeared(&before, &after, marker) == Some(true))
+}
+
+/// Tries every paste method on the focused app and saves the first that
+/// worked as its override in `app_paste_methods`.
+pub fn run(app: &AppHandle) -> Result<InjectionTestReport, String> {
+    let target_app = active_app();
+    if target_app
+        .as_deref()
+        .is_some_and(|name| name.eq_ignore_ascii_case(&app.package_info().name))
+    {
+        return Err("Click into a text field in another app first".to_string());
+    }
+    if focused_text().is_none() {
+        return Err(UNREADABLE.to_string());
+    }
+    info!("Testing paste methods in {:?}", target_app);
+
+    let clipboard = app.clipboard();
+    let saved_clipboard = clipboard.read_text().unwrap_or_default();
+
+    let results: Vec<MethodResult> = CANDIDATES
+        .iter()
+        .enumerate()
+        .map(|(attempt, &method)| {
+            let (worked, error) = match try_method(app, method, &marker(attempt)) {
+                Ok(worked) => (worked, None),
+                Err(e) => (false, Some(e)),
+            };
+            MethodResult {
+                method,
+                worked,
+                error,
+            }
+        })
+        .collect();
+
+    let _ = clipboard.write_text(&saved_clipboard);
+
+    let applied = results.iter().find(|r| r.worked).map(|r| r.method);
+    if let (Some(name), Some(method)) = (&target_app, applied) {
+        info!("Using {:?} for {}", method, name);
+        let mut settings = get_settings(app);
+        settings.app_paste_methods.insert(name.clone(), method);
+        write_settings(app, settings);
+    }
+
+    Ok(InjectionTestReport {
+        app: target_app,
+        results,
+        applied,
+    })
+}
@@ -121,3 +121,10 @@ pub fn paste_text_direct(enigo: &mut Enigo, text: &str) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Presses and releases a single key.
+pub fn click_key(enigo: &mut Enigo, key: Key) -> Result<(), String> {
+    enigo
+        .key(key, enigo::Direction::Click)
+        .map_err(|e| format!("Failed to click {:?} key: {}", key, e))
+}
@@ -0,0 +1,202 @@
+//! Remembers the language auto-detection settled on for the current dictation
+//! session. Re-detecting on every utterance adds latency and occasionally
+//! flips to the wrong language on short phrases, so once a transcript is
+//! confidently in one language we pass it explicitly until the session goes
+//! idle.
+
+use log::{debug, info};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use whatlang::Lang;
+
+struct Locked {
+    language: String,
+    last_used: Instant,
+}
+
+#[derive(Default)]
+pub struct LanguageLock {
+    locked: Mutex<Option<Locked>>,
+}
+
+impl LanguageLock {
+    /// The locked language, unless the session has been idle for `timeout`
+    pub fn current(&self, timeout: Duration) -> Option<String> {
+        self.current_at(Instant::now(), timeout)
+    }
+
+    /// Locks to the language of `text` if nothing is locked yet, and keeps an
+    /// existing lock alive.
+    pub fn observe(&self, text: &str, timeout: Duration) {
+        self.observe_at(Instant::now(), text, timeout);
+    }
+
+    pub fn clear(&self) {
+        if self.locked.lock().unwrap().take().is_some() {
+            debug!("Cleared language lock");
+        }
+    }
+
+    fn current_at(&self, now: Instant, timeout: Duration) -> Option<String> {
+        let mut locked = self.locked.lock().unwrap();
+        if locked
+            .as_ref()
+            .is_some_and(|l| now.duration_since(l.last_used) > timeout)
+        {
+            debug!("Language lock expired after inactivity");
+            *locked = None;
+        }
+        locked.as_ref().map(|l| l.language.clone())
+    }
+
+    fn observe_at(&self, now: Instant, text: &str, timeout: Duration) {
+        if self.current_at(now, timeout).is_some() {
+            if let Some(locked) = self.locked.lock().unwrap().as_mut() {
+                locked.last_used = now;
+            }
+            return;
+        }
+
+        let Some(language) = detect(text) else {
+            return;
+        };
+        info!("Locking transcription language to '{}'", language);
+        *self.locked.lock().unwrap() = Some(Locked {
+            language: language.to_string(),
+            last_used: now,
+        });
+    }
+}
+
+/// The Whisper language code of `text`, if it can be told reliably
+fn detect(text: &str) -> Option<&'static str> {
+    let info = whatlang::detect(text)?;
+    if !info.is_reliable() {
+        return None;
+    }
+    whisper_code(info.lang())
+}
+
+fn whisper_code(lang: Lang) -> Option<&'static str> {
+    let code = match lang {
+        Lang::Afr => "af",
+        Lang::Amh => "am",
+        Lang::Ara => "ar",
+        Lang::Aze => "az",
+        Lang::Bel => "be",
+        Lang::Ben => "bn",
+        Lang::Bul => "bg",
+        Lang::Cat => "ca",
+        Lang::Ces => "cs",
+        Lang::Cmn => "zh",
+        Lang::Dan => "da",
+        Lang::Deu => "de",
+        Lang::Ell => "el",
+        Lang::Eng => "en",
+        Lang::Est => "et",
+        Lang::Fin => "fi",
+        Lang::Fra => "fr",
+        Lang::Guj => "gu",
+        Lang::Heb => "he",
+        Lang::Hin => "hi",
+        Lang::Hrv => "hr",
+        Lang::Hun => "hu",
+        Lang::Hye => "hy",
+        Lang::Ind => "id",
+        Lang::Ita => "it",
+        Lang::Jav => "jw",
+        Lang::Jpn => "ja",
+        Lang::Kan => "kn",
+        Lang::Kat => "ka",
+        Lang::Khm => "km",
+        Lang::Kor => "ko",
+        Lang::Lat => "la",
+        Lang::Lav => "lv",
+        Lang::Lit => "lt",
+        Lang::Mal => "ml",
+        Lang::Mar => "mr",
+        Lang::Mkd => "mk",
+        Lang::Mya => "my",
+        Lang::Nep => "ne",
+        Lang::Nld => "nl",
+        Lang::Nob => "no",
+        Lang::Pan => "pa",
+        Lang::Pes => "fa",
+        Lang::Pol => "pl",
+        Lang::Por => "pt",
+        Lang::Ron => "ro",
+        Lang::Rus => "ru",
+        Lang::Sin => "si",
+        Lang::Slk => "sk",
+        Lang::Slv => "sl",
+        Lang::Sna => "sn",
+        Lang::Spa => "es",
+        Lang::Srp => "sr",
+        Lang::Swe => "sv",
+        Lang::Tam => "ta",
+        Lang::Tel => "te",
+        Lang::Tgl => "tl",
+        Lang::Tha => "th",
+        Lang::Tuk => "tk",
+        Lang::Tur => "tr",
+        Lang::Ukr => "uk",
+        Lang::Urd => "ur",
+        Lang::Uzb => "uz",
+        Lang::Vie => "vi",
+        Lang::Yid => "yi",
+        // Whisper doesn't know Esperanto, Odia, Akan or Zulu
+        _ => return None,
+    };
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(600);
+    const GERMAN: &str =
+        "Das ist ein ziemlich langer deutscher Satz, damit die Erkennung sicher ist.";
+
+    #[test]
+    fn locks_to_the_detected_language() {
+        let lock = LanguageLock::default();
+        let start = Instant::now();
+        lock.observe_at(start, GERMAN, TIMEOUT);
+
+        assert_eq!(lock.current_at(start, TIMEOUT).as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn keeps_the_lock_while_dictating() {
+        let lock = LanguageLock::default();
+        let start = Instant::now();
+        lock.observe_at(start, GERMAN, TIMEOUT);
+        // A later English utterance doesn't flip the lock, only refreshes it
+        lock.observe_at(
+            start + Duration::from_secs(500),
+            "This is a fairly long English sentence for the detector.",
+            TIMEOUT,
+        );
+
+        let later = start + Duration::from_secs(1000);
+        assert_eq!(lock.current_at(later, TIMEOUT).as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn expires_after_inactivity() {
+        let lock = LanguageLock::default();
+        let start = Instant::now();
+        lock.observe_at(start, GERMAN, TIMEOUT);
+
+        let later = start + TIMEOUT + Duration::from_secs(1);
+        assert_eq!(lock.current_at(later, TIMEOUT), None);
+    }
+
+    #[test]
+    fn ignores_text_too_short_to_tell() {
+        let lock = LanguageLock::default();
+        lock.observe("ok", TIMEOUT);
+        assert_eq!(lock.current(TIMEOUT), None);
+    }
+}
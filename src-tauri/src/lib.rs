@@ -1,34 +1,78 @@
+mod accessibility;
 mod actions;
+mod api_server;
+mod app_usage;
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
 mod apple_intelligence;
+mod atspi;
 mod audio_feedback;
-pub mod audio_toolkit;
+mod calibration;
+mod cancel_gesture;
+mod cli;
 mod clipboard;
 mod commands;
+mod context_rules;
+mod countdown;
+mod deepgram;
 mod helpers;
+mod injection_audit;
+mod injection_test;
 mod input;
+mod language_lock;
 mod llm_client;
 mod managers;
+mod memory_budget;
+mod network;
+mod notifications;
+mod oauth;
+mod openai_realtime;
 mod overlay;
+mod paste_check;
+#[cfg(target_os = "linux")]
+mod portal_shortcuts;
+mod power;
+mod preprocessing;
+mod preview;
+mod processing;
+mod punctuation;
+mod punctuation_style;
+mod recording_guard;
+mod recording_status;
+mod resource_usage;
 mod settings;
+mod share;
 mod shortcut;
 mod signal_handle;
+mod spelling;
+mod subtitles;
+mod templates;
+mod text_rules;
+mod transcription_provider;
+mod transcription_quality;
 mod tray;
 mod tray_i18n;
+mod tts;
 mod utils;
+mod virtual_keyboard;
+mod voice_commands;
+mod wake_word;
+mod wyoming;
 use specta_typescript::{BigIntExportBehavior, Typescript};
 use tauri_specta::{collect_commands, Builder};
 
 use env_filter::Builder as EnvFilterBuilder;
 use managers::audio::AudioRecordingManager;
+use managers::captions::CaptionsManager;
 use managers::history::HistoryManager;
 use managers::model::ModelManager;
+use managers::plugins::PluginManager;
 use managers::transcription::TranscriptionManager;
 #[cfg(unix)]
 use signal_hook::consts::SIGUSR2;
 #[cfg(unix)]
 use signal_hook::iterator::Signals;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::{Arc, Mutex};
 use tauri::image::Image;
@@ -127,16 +171,33 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     );
     let history_manager =
         Arc::new(HistoryManager::new(app_handle).expect("Failed to initialize history manager"));
+    let captions_manager = Arc::new(CaptionsManager::new(app_handle));
+    let plugin_manager =
+        Arc::new(PluginManager::new(app_handle).expect("Failed to initialize plugin manager"));
 
     // Add managers to Tauri's managed state
     app_handle.manage(recording_manager.clone());
     app_handle.manage(model_manager.clone());
     app_handle.manage(transcription_manager.clone());
     app_handle.manage(history_manager.clone());
+    app_handle.manage(captions_manager.clone());
+    app_handle.manage(plugin_manager.clone());
 
     // Initialize the shortcuts
     shortcut::init_shortcuts(app_handle);
 
+    // Watch the power source for low power mode
+    power::start_monitor(app_handle);
+
+    // Notice the remote engine coming and going with the network
+    network::start_monitor(app_handle);
+
+    // Serve transcriptions to other apps if the user turned that on
+    api_server::restart(app_handle);
+
+    // Listen for the wake word if the user recorded it
+    wake_word::reload(app_handle);
+
     #[cfg(unix)]
     let signals = Signals::new(&[SIGUSR2]).unwrap();
     // Set up SIGUSR2 signal handler for toggling transcription
@@ -195,8 +256,8 @@ fn initialize_core_logic(app_handle: &AppHandle) {
         .unwrap();
     app_handle.manage(tray);
 
-    // Initialize tray menu with idle state
-    utils::update_tray_menu(app_handle, &utils::TrayIconState::Idle, None);
+    // Initialize tray menu with idle state, using the user's icon set if any
+    utils::change_tray_icon(app_handle, utils::TrayIconState::Idle);
 
     // Get the autostart manager and configure based on user setting
     let autostart_manager = app_handle.autolaunch();
@@ -212,6 +273,9 @@ fn initialize_core_logic(app_handle: &AppHandle) {
 
     // Create the recording overlay window (hidden by default)
     utils::create_recording_overlay(app_handle);
+
+    // Create the live captions overlay window (hidden by default)
+    utils::create_captions_overlay(app_handle);
 }
 
 #[tauri::command]
@@ -228,6 +292,14 @@ fn trigger_update_check(app: AppHandle) -> Result<(), String> {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Some CLI actions only read local data and never need the GUI
+    let args: Vec<String> = std::env::args().collect();
+    let cli_action = cli::CliAction::parse(&args);
+    let context = tauri::generate_context!();
+    if let Some(action) = cli_action.as_ref().filter(|a| a.is_local()) {
+        std::process::exit(cli::run_local(action, &context.config().identifier));
+    }
+
     // Parse console logging directives from RUST_LOG, falling back to info-level logging
     // when the variable is unset
     let console_filter = build_console_filter();
@@ -235,6 +307,16 @@ pub fn run() {
     let specta_builder = Builder::<tauri::Wry>::new().commands(collect_commands![
         shortcut::change_binding,
         shortcut::reset_binding,
+        shortcut::change_binding_when_busy,
+        shortcut::change_binding_countdown,
+        shortcut::change_binding_translate,
+        shortcut::change_binding_dictation,
+        shortcut::change_binding_skip_post_process,
+        shortcut::change_binding_pipeline,
+        shortcut::change_binding_recording_mode,
+        shortcut::change_hands_free_silence_secs_setting,
+        shortcut::add_dictation_binding,
+        shortcut::remove_dictation_binding,
         shortcut::change_ptt_setting,
         shortcut::change_audio_feedback_setting,
         shortcut::change_audio_feedback_volume_setting,
@@ -243,15 +325,94 @@ pub fn run() {
         shortcut::change_autostart_setting,
         shortcut::change_translate_to_english_setting,
         shortcut::change_selected_language_setting,
+        shortcut::change_language_lock_setting,
+        shortcut::change_code_switching_setting,
+        shortcut::change_punctuation_restoration_setting,
+        shortcut::change_caption_segment_at_pauses_setting,
+        shortcut::change_caption_max_segment_secs_setting,
+        shortcut::change_conferencing_guard_setting,
+        shortcut::change_oxford_comma_setting,
+        shortcut::change_dash_style_setting,
+        shortcut::change_quote_style_setting,
+        shortcut::change_ellipsis_style_setting,
+        shortcut::change_preview_before_paste_setting,
+        shortcut::change_preview_apps_setting,
+        shortcut::change_injection_audit_setting,
+        shortcut::change_injection_marker_setting,
+        shortcut::change_adaptive_engine_setting,
+        shortcut::change_adaptive_threshold_setting,
+        shortcut::change_adaptive_long_model_setting,
+        shortcut::change_app_paste_methods_setting,
+        shortcut::change_boost_custom_words_setting,
+        shortcut::change_context_rules_setting,
+        shortcut::change_release_to_cancel_setting,
+        shortcut::change_release_to_cancel_silence_secs_setting,
+        shortcut::change_max_concurrent_downloads_setting,
+        shortcut::change_download_limit_kbps_setting,
+        shortcut::change_share_destinations_setting,
+        shortcut::change_compress_silence_setting,
+        shortcut::change_segment_long_recordings_setting,
+        shortcut::change_realtime_transcription_setting,
+        shortcut::change_realtime_endpoint_setting,
+        shortcut::change_deepgram_enabled_setting,
+        shortcut::change_deepgram_setting,
+        shortcut::change_cloud_transcription_setting,
+        shortcut::change_preprocessing_setting,
+        shortcut::change_word_replacements_setting,
+        shortcut::change_dictation_templates_setting,
+        shortcut::change_text_rules_setting,
+        shortcut::change_file_cache_limit_setting,
+        shortcut::change_batch_deferred_setting,
+        shortcut::change_batch_window_start_setting,
+        shortcut::change_batch_window_end_setting,
+        shortcut::change_batch_require_ac_power_setting,
+        shortcut::change_batch_idle_minutes_setting,
+        shortcut::change_model_memory_budget_setting,
+        shortcut::change_input_watchdog_setting,
+        shortcut::change_adaptive_vad_setting,
+        shortcut::change_noise_suppression_setting,
+        shortcut::change_noise_suppression_strength_setting,
+        shortcut::change_auto_gain_control_setting,
+        shortcut::change_pre_roll_setting,
+        shortcut::change_system_audio_mix_microphone_setting,
+        shortcut::change_tray_icon_dir_setting,
+        shortcut::change_tray_indicator_setting,
+        shortcut::change_caption_export_timestamps_setting,
+        shortcut::change_caption_speaker_label_setting,
+        shortcut::change_spoken_spelling_setting,
+        shortcut::change_voice_commands_setting,
+        shortcut::change_spelling_trigger_setting,
+        shortcut::change_hallucination_filter_setting,
+        shortcut::change_retranscribe_model_setting,
+        shortcut::change_retranscribe_max_latency_setting,
+        shortcut::change_shared_models_dir_setting,
+        shortcut::change_wyoming_enabled_setting,
+        shortcut::change_wyoming_server_setting,
+        shortcut::change_notification_preferences_setting,
+        shortcut::change_screen_reader_announcements_setting,
+        shortcut::change_low_power_enabled_setting,
+        shortcut::change_low_power_model_setting,
+        shortcut::change_low_power_pause_always_on_microphone_setting,
+        shortcut::change_low_power_model_unload_timeout_setting,
+        shortcut::change_tts_readback_setting,
+        shortcut::change_tts_piper_path_setting,
+        shortcut::change_tts_voice_path_setting,
         shortcut::change_overlay_position_setting,
+        shortcut::change_overlay_placement_setting,
+        shortcut::change_overlay_scale_setting,
+        shortcut::reset_overlay_positions,
         shortcut::change_debug_mode_setting,
         shortcut::change_word_correction_threshold_setting,
         shortcut::change_paste_method_setting,
         shortcut::change_clipboard_handling_setting,
+        shortcut::change_transcription_context_setting,
+        shortcut::change_transcription_prompt_setting,
         shortcut::change_post_process_enabled_setting,
         shortcut::change_post_process_base_url_setting,
         shortcut::change_post_process_api_key_setting,
+        shortcut::change_post_process_oauth_setting,
         shortcut::change_post_process_model_setting,
+        shortcut::change_post_process_temperature_setting,
         shortcut::set_post_process_provider,
         shortcut::fetch_post_process_models,
         shortcut::add_post_process_prompt,
@@ -263,11 +424,14 @@ pub fn run() {
         shortcut::resume_binding,
         shortcut::change_mute_while_recording_setting,
         shortcut::change_append_trailing_space_setting,
+        shortcut::change_replace_selection_setting,
+        shortcut::change_verify_paste_setting,
         shortcut::change_app_language_setting,
         shortcut::change_update_checks_setting,
         trigger_update_check,
         commands::cancel_operation,
         commands::get_app_dir_path,
+        commands::is_on_battery,
         commands::get_app_settings,
         commands::get_default_settings,
         commands::get_log_dir_path,
@@ -277,9 +441,19 @@ pub fn run() {
         commands::open_app_data_dir,
         commands::check_apple_intelligence_available,
         commands::initialize_enigo,
+        commands::test_paste_methods,
+        commands::start_voice_calibration,
+        commands::finish_voice_calibration,
+        commands::cancel_voice_calibration,
         commands::models::get_available_models,
         commands::models::get_model_info,
+        commands::models::get_punctuation_model,
+        commands::models::get_memory_usage,
+        commands::models::get_language_packs,
+        commands::models::install_language_pack,
         commands::models::download_model,
+        commands::models::import_fine_tune,
+        commands::models::import_punctuation_model,
         commands::models::delete_model,
         commands::models::cancel_download,
         commands::models::set_active_model,
@@ -289,28 +463,98 @@ pub fn run() {
         commands::models::has_any_models_available,
         commands::models::has_any_models_or_downloads,
         commands::models::get_recommended_first_model,
+        commands::models::get_shared_models_dir,
+        commands::plugins::get_plugins,
+        commands::plugins::reload_plugins,
+        commands::plugins::set_plugin_enabled,
+        commands::plugins::open_plugins_dir,
+        commands::oauth::start_post_process_sign_in,
+        commands::oauth::finish_post_process_sign_in,
+        commands::oauth::cancel_post_process_sign_in,
+        commands::oauth::sign_out_post_process,
         commands::audio::update_microphone_mode,
         commands::audio::get_microphone_mode,
         commands::audio::get_available_microphones,
         commands::audio::set_selected_microphone,
+        commands::audio::set_microphone_priority,
+        commands::audio::set_remote_microphone,
+        commands::audio::set_remote_microphone_port,
+        commands::audio::regenerate_remote_microphone_token,
+        commands::audio::get_remote_microphone_addresses,
         commands::audio::get_selected_microphone,
         commands::audio::get_available_output_devices,
         commands::audio::set_selected_output_device,
         commands::audio::get_selected_output_device,
         commands::audio::play_test_sound,
         commands::audio::check_custom_sounds,
+        commands::audio::import_custom_sound,
+        commands::audio::remove_custom_sound,
         commands::audio::set_clamshell_microphone,
         commands::audio::get_clamshell_microphone,
+        commands::audio::set_conferencing_microphone,
+        commands::audio::get_conferencing_microphone,
         commands::audio::is_recording,
+        commands::audio::set_wake_word_enabled,
+        commands::audio::add_wake_word,
+        commands::audio::update_wake_word,
+        commands::audio::delete_wake_word,
+        commands::audio::get_wake_word_sample_count,
+        commands::audio::start_wake_word_sample,
+        commands::audio::finish_wake_word_sample,
+        commands::audio::clear_wake_word_samples,
         commands::transcription::set_model_unload_timeout,
         commands::transcription::get_model_load_status,
         commands::transcription::unload_model_manually,
+        commands::transcription::discover_wyoming_servers,
+        commands::transcription::check_wyoming_server,
+        commands::transcription::get_batch_jobs,
+        commands::transcription::transcribe_audio_file,
+        commands::transcription::queue_file_transcription,
+        commands::transcription::get_file_cache_count,
+        commands::transcription::clear_file_transcription_cache,
+        commands::transcription::remove_batch_job,
+        commands::transcription::run_batch_jobs_now,
+        commands::transcription::get_pending_transcriptions,
+        commands::transcription::remove_pending_transcription,
+        commands::transcription::retry_pending_transcriptions,
+        commands::transcription::suggest_word_replacements,
+        commands::transcription::learn_word_replacement,
+        commands::transcription::set_api_server_enabled,
+        commands::transcription::set_api_server_port,
+        commands::transcription::regenerate_api_server_token,
+        commands::captions::start_live_captions,
+        commands::captions::stop_live_captions,
+        commands::captions::is_live_captions_active,
+        commands::captions::get_caption_lines,
+        commands::captions::copy_caption_session,
+        commands::captions::save_caption_session,
+        commands::notifications::retry_last_transcription,
+        commands::notifications::repaste_transcript,
+        commands::notifications::copy_unpasted_transcript,
+        commands::notifications::switch_to_local_transcription,
+        commands::preview::get_preview_text,
+        commands::preview::confirm_preview,
+        commands::preview::cancel_preview,
         commands::history::get_history_entries,
+        commands::history::get_history_page,
+        commands::history::search_history,
+        commands::history::copy_history_entry,
+        commands::history::paste_history_entry,
+        commands::history::share_history_entry,
+        commands::history::export_history_subtitles,
+        commands::history::get_history_entry_words,
+        commands::history::update_history_entry_text,
+        commands::history::rate_history_entry,
+        commands::history::get_quality_report,
+        commands::history::get_app_usage_stats,
+        commands::history::get_app_default_suggestions,
         commands::history::toggle_history_entry_saved,
         commands::history::get_audio_file_path,
         commands::history::delete_history_entry,
         commands::history::update_history_limit,
         commands::history::update_recording_retention_period,
+        commands::history::get_text_injections,
+        commands::history::identify_injected_text,
         helpers::clamshell::is_laptop,
     ]);
 
@@ -352,14 +596,19 @@ pub fn run() {
     }
 
     builder
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            show_main_window(app);
-        }))
+        .plugin(tauri_plugin_single_instance::init(
+            |app, args, cwd| match cli::CliAction::parse(&args) {
+                // Paths are relative to where the second instance was started
+                Some(action) => cli::dispatch(app, action.relative_to(Path::new(&cwd))),
+                None => show_main_window(app),
+            },
+        ))
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_macos_permissions::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_store::Builder::default().build())
@@ -369,6 +618,16 @@ pub fn run() {
             Some(vec![]),
         ))
         .manage(Mutex::new(ShortcutToggleStates::default()))
+        .manage(tts::ManagedPendingReadback::default())
+        .manage(preview::ManagedPendingPreview::default())
+        .manage(templates::ManagedTemplateFill::default())
+        .manage(voice_commands::ManagedVoiceCommands::default())
+        .manage(recording_guard::ManagedQueuedBinding::default())
+        .manage(processing::ManagedProcessing::default())
+        .manage(openai_realtime::ManagedRealtime::default())
+        .manage(notifications::ManagedNotificationContext::default())
+        .manage(api_server::ManagedApiServer::default())
+        .manage(wake_word::ManagedWakeWord::default())
         .setup(move |app| {
             let settings = get_settings(&app.handle());
             let tauri_log_level: tauri_plugin_log::LogLevel = settings.log_level.into();
@@ -379,8 +638,11 @@ pub fn run() {
 
             initialize_core_logic(&app_handle);
 
-            // Show main window only if not starting hidden
-            if !settings.start_hidden {
+            // Launched by an automation: run the action rather than opening the window
+            if let Some(action) = cli_action {
+                cli::dispatch(&app_handle, action);
+            } else if !settings.start_hidden {
+                // Show main window only if not starting hidden
                 if let Some(main_window) = app_handle.get_webview_window("main") {
                     main_window.show().unwrap();
                     main_window.set_focus().unwrap();
@@ -403,14 +665,17 @@ pub fn run() {
                     }
                 }
             }
+            tauri::WindowEvent::Moved(position) if window.label() == "recording_overlay" => {
+                overlay::overlay_moved(window.app_handle(), *position);
+            }
             tauri::WindowEvent::ThemeChanged(theme) => {
                 log::info!("Theme changed to: {:?}", theme);
-                // Update tray icon to match new theme, maintaining idle state
-                utils::change_tray_icon(&window.app_handle(), utils::TrayIconState::Idle);
+                // Update tray icon to match new theme, keeping its state
+                utils::refresh_tray_icon(&window.app_handle());
             }
             _ => {}
         })
         .invoke_handler(specta_builder.invoke_handler())
-        .run(tauri::generate_context!())
+        .run(context)
         .expect("error while running tauri application");
 }
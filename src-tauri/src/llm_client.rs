@@ -1,7 +1,9 @@
-use crate::settings::PostProcessProvider;
+use crate::settings::{self, PostProcessProvider};
 use log::debug;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, REFERER, USER_AGENT};
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
 
 #[derive(Debug, Serialize)]
 struct ChatMessage {
@@ -13,6 +15,8 @@ struct ChatMessage {
 struct ChatCompletionRequest {
     model: String,
     messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -76,36 +80,92 @@ fn create_client(provider: &PostProcessProvider, api_key: &str) -> Result<reqwes
         .map_err(|e| format!("Failed to build HTTP client: {}", e))
 }
 
-/// Send a chat completion request to an OpenAI-compatible API
+/// The API key or OAuth access token to send to the provider
+async fn credential(
+    app: &AppHandle,
+    provider: &PostProcessProvider,
+    force_refresh: bool,
+) -> Result<String, String> {
+    if let Some(token) = crate::oauth::access_token(app, &provider.id, force_refresh).await? {
+        return Ok(token);
+    }
+    Ok(settings::get_settings(app)
+        .post_process_api_keys
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Sends the request built by `build`, refreshing the OAuth token and trying
+/// once more if the provider rejects it.
+async fn send_authorized(
+    app: &AppHandle,
+    provider: &PostProcessProvider,
+    build: impl Fn(reqwest::Client) -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, String> {
+    let api_key = credential(app, provider, false).await?;
+    let response = build(create_client(provider, &api_key)?)
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let uses_oauth = settings::get_settings(app)
+        .post_process_oauth
+        .contains_key(&provider.id);
+    if response.status() != StatusCode::UNAUTHORIZED || !uses_oauth {
+        return Ok(response);
+    }
+
+    debug!(
+        "Provider '{}' rejected the access token, refreshing",
+        provider.id
+    );
+    let api_key = credential(app, provider, true).await?;
+    build(create_client(provider, &api_key)?)
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))
+}
+
+/// Send a chat completion request to an OpenAI-compatible API, with `system`
+/// as the system prompt if given. `temperature` is left to the provider if
+/// not set.
 /// Returns Ok(Some(content)) on success, Ok(None) if response has no content,
 /// or Err on actual errors (HTTP, parsing, etc.)
 pub async fn send_chat_completion(
+    app: &AppHandle,
     provider: &PostProcessProvider,
-    api_key: String,
     model: &str,
+    system: Option<String>,
     prompt: String,
+    temperature: Option<f32>,
 ) -> Result<Option<String>, String> {
     let base_url = provider.base_url.trim_end_matches('/');
     let url = format!("{}/chat/completions", base_url);
 
     debug!("Sending chat completion request to: {}", url);
 
-    let client = create_client(provider, &api_key)?;
-
+    let mut messages = Vec::new();
+    if let Some(system) = system {
+        messages.push(ChatMessage {
+            role: "system".to_string(),
+            content: system,
+        });
+    }
+    messages.push(ChatMessage {
+        role: "user".to_string(),
+        content: prompt,
+    });
     let request_body = ChatCompletionRequest {
         model: model.to_string(),
-        messages: vec![ChatMessage {
-            role: "user".to_string(),
-            content: prompt,
-        }],
+        messages,
+        temperature,
     };
 
-    let response = client
-        .post(&url)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("HTTP request failed: {}", e))?;
+    let response = send_authorized(app, provider, |client| {
+        client.post(&url).json(&request_body)
+    })
+    .await?;
 
     let status = response.status();
     if !status.is_success() {
@@ -133,19 +193,15 @@ pub async fn send_chat_completion(
 /// Fetch available models from an OpenAI-compatible API
 /// Returns a list of model IDs
 pub async fn fetch_models(
+    app: &AppHandle,
     provider: &PostProcessProvider,
-    api_key: String,
 ) -> Result<Vec<String>, String> {
     let base_url = provider.base_url.trim_end_matches('/');
     let url = format!("{}/models", base_url);
 
     debug!("Fetching models from: {}", url);
 
-    let client = create_client(provider, &api_key)?;
-
-    let response = client
-        .get(&url)
-        .send()
+    let response = send_authorized(app, provider, |client| client.get(&url))
         .await
         .map_err(|e| format!("Failed to fetch models: {}", e))?;
 
@@ -1,10 +1,17 @@
-use crate::audio_toolkit::{list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad};
-use crate::helpers::clamshell;
-use crate::settings::{get_settings, AppSettings};
+use crate::helpers::{clamshell, conferencing};
+use crate::notifications;
+use crate::settings::{get_settings, AppSettings, ConferencingGuard};
 use crate::utils;
+use crate::wake_word;
+use cpal::traits::HostTrait;
+use handy_transcription::audio::SYSTEM_AUDIO;
+use handy_transcription::{
+    get_cpal_host, list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad,
+};
 use log::{debug, error, info};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tauri::Manager;
 
 fn set_mute(mute: bool) {
@@ -97,6 +104,20 @@ fn set_mute(mute: bool) {
 }
 
 const WHISPER_SAMPLE_RATE: usize = 16000;
+/// How long an input can deliver nothing at all before we warn about it
+const DEAD_INPUT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Silero speech probability above which a frame counts as speech, until
+/// the microphone is calibrated
+pub const DEFAULT_VAD_THRESHOLD: f32 = 0.3;
+/// Key of the voice profile for audio streamed over the network
+const NETWORK_PROFILE_KEY: &str = "network";
+/// Key of the voice profile for the system default microphone
+const DEFAULT_PROFILE_KEY: &str = "default";
+/// How often connected microphones are checked against `microphone_priority`
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How often apps using the microphone are checked for a video call while
+/// `conferencing_guard` is on
+const CONFERENCING_POLL_INTERVAL: Duration = Duration::from_secs(3);
 
 /* ──────────────────────────────────────────────────────────────── */
 
@@ -114,11 +135,30 @@ pub enum MicrophoneMode {
 
 /* ──────────────────────────────────────────────────────────────── */
 
+/// The highest ranked microphone in `priority` that is connected
+fn preferred_microphone(priority: &[String], connected: &[String]) -> Option<String> {
+    priority
+        .iter()
+        .find(|name| connected.contains(name))
+        .cloned()
+}
+
+fn connected_microphones() -> Vec<String> {
+    match list_input_devices() {
+        Ok(devices) => devices.into_iter().map(|d| d.name).collect(),
+        Err(e) => {
+            debug!("Failed to list input devices: {}", e);
+            Vec::new()
+        }
+    }
+}
+
 fn create_audio_recorder(
     vad_path: &str,
     app_handle: &tauri::AppHandle,
+    cue_gate: Arc<AtomicUsize>,
 ) -> Result<AudioRecorder, anyhow::Error> {
-    let silero = SileroVad::new(vad_path, 0.3)
+    let silero = SileroVad::new(vad_path, DEFAULT_VAD_THRESHOLD)
         .map_err(|e| anyhow::anyhow!("Failed to create SileroVad: {}", e))?;
     let smoothed_vad = SmoothedVad::new(Box::new(silero), 15, 15, 2);
 
@@ -127,11 +167,24 @@ fn create_audio_recorder(
     let recorder = AudioRecorder::new()
         .map_err(|e| anyhow::anyhow!("Failed to create AudioRecorder: {}", e))?
         .with_vad(Box::new(smoothed_vad))
+        .with_input_gate(cue_gate)
         .with_level_callback({
             let app_handle = app_handle.clone();
             move |levels| {
                 utils::emit_levels(&app_handle, &levels);
             }
+        })
+        .with_dead_input_callback(DEAD_INPUT_TIMEOUT, {
+            let app_handle = app_handle.clone();
+            move || {
+                if get_settings(&app_handle).input_watchdog {
+                    notifications::notify_dead_input(&app_handle);
+                }
+            }
+        })
+        .with_idle_frame_callback({
+            let app_handle = app_handle.clone();
+            move |frame| wake_word::listen(&app_handle, frame)
         });
 
     Ok(recorder)
@@ -149,6 +202,18 @@ pub struct AudioRecordingManager {
     is_open: Arc<Mutex<bool>>,
     is_recording: Arc<Mutex<bool>>,
     did_mute: Arc<Mutex<bool>>,
+    /// Number of feedback sounds playing; input is dropped while non-zero
+    cue_gate: Arc<AtomicUsize>,
+    /// Recording from `conferencing_microphone` because a call has the usual one
+    use_conferencing_mic: Arc<AtomicBool>,
+    /// The call found by the last conferencing check, if any
+    conferencing_app: Arc<Mutex<Option<&'static str>>>,
+    /// The connected microphone `microphone_priority` currently picks
+    priority_mic: Arc<Mutex<Option<String>>>,
+    /// The next recording starts with the pre-roll, for the wake word
+    pre_roll_armed: Arc<AtomicBool>,
+    /// The current recording was started by the wake word
+    started_by_wake: Arc<AtomicBool>,
 }
 
 impl AudioRecordingManager {
@@ -156,7 +221,7 @@ impl AudioRecordingManager {
 
     pub fn new(app: &tauri::AppHandle) -> Result<Self, anyhow::Error> {
         let settings = get_settings(app);
-        let mode = if settings.always_on_microphone {
+        let mode = if settings.always_on_microphone || settings.wake_word_enabled {
             MicrophoneMode::AlwaysOn
         } else {
             MicrophoneMode::OnDemand
@@ -171,10 +236,21 @@ impl AudioRecordingManager {
             is_open: Arc::new(Mutex::new(false)),
             is_recording: Arc::new(Mutex::new(false)),
             did_mute: Arc::new(Mutex::new(false)),
+            cue_gate: Arc::new(AtomicUsize::new(0)),
+            use_conferencing_mic: Arc::new(AtomicBool::new(false)),
+            conferencing_app: Arc::new(Mutex::new(None)),
+            priority_mic: Arc::new(Mutex::new(preferred_microphone(
+                &settings.microphone_priority,
+                &connected_microphones(),
+            ))),
+            pre_roll_armed: Arc::new(AtomicBool::new(false)),
+            started_by_wake: Arc::new(AtomicBool::new(false)),
         };
+        manager.start_device_monitor();
+        manager.start_conferencing_monitor();
 
         // Always-on?  Open immediately.
-        if matches!(mode, MicrophoneMode::AlwaysOn) {
+        if matches!(mode, MicrophoneMode::AlwaysOn) || settings.remote_microphone {
             manager.start_microphone_stream()?;
         }
 
@@ -183,7 +259,9 @@ impl AudioRecordingManager {
 
     /* ---------- helper methods --------------------------------------------- */
 
-    fn get_effective_microphone_device(&self, settings: &AppSettings) -> Option<cpal::Device> {
+    /// Name of the microphone recordings come from, `None` for the system
+    /// default.
+    fn effective_microphone_name(&self, settings: &AppSettings) -> Option<String> {
         // Check if we're in clamshell mode and have a clamshell microphone configured
         let use_clamshell_mic = if let Ok(is_clamshell) = clamshell::is_clamshell() {
             is_clamshell && settings.clamshell_microphone.is_some()
@@ -191,17 +269,42 @@ impl AudioRecordingManager {
             false
         };
 
-        let device_name = if use_clamshell_mic {
-            settings.clamshell_microphone.as_ref().unwrap()
+        let priority_mic = self.priority_microphone();
+        if self.use_conferencing_mic.load(Ordering::Relaxed) {
+            settings.conferencing_microphone.clone()
+        } else if use_clamshell_mic {
+            settings.clamshell_microphone.clone()
+        } else if priority_mic.is_some() {
+            priority_mic
         } else {
-            settings.selected_microphone.as_ref()?
+            settings.selected_microphone.clone()
+        }
+    }
+
+    /// Whether recordings come from what the computer plays
+    fn records_system_audio(settings: &AppSettings) -> bool {
+        settings.selected_microphone.as_deref() == Some(SYSTEM_AUDIO)
+    }
+
+    /// The microphone mixed in with system audio, the one recordings would
+    /// otherwise come from
+    fn mixed_microphone(&self, settings: &AppSettings) -> Option<cpal::Device> {
+        let settings = AppSettings {
+            selected_microphone: None,
+            ..settings.clone()
         };
+        self.get_effective_microphone_device(&settings)
+            .or_else(|| get_cpal_host().default_input_device())
+    }
+
+    fn get_effective_microphone_device(&self, settings: &AppSettings) -> Option<cpal::Device> {
+        let device_name = self.effective_microphone_name(settings)?;
 
         // Find the device by name
         match list_input_devices() {
             Ok(devices) => devices
                 .into_iter()
-                .find(|d| d.name == *device_name)
+                .find(|d| d.name == device_name)
                 .map(|d| d.device),
             Err(e) => {
                 debug!("Failed to list devices, using default: {}", e);
@@ -210,6 +313,103 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Checks for a video call using the microphone before a dictation starts,
+    /// and warns about it or moves recording to the conferencing microphone
+    /// depending on `conferencing_guard`. Uses the last background check, so
+    /// starting isn't held up by scanning the apps.
+    pub fn apply_conferencing_guard(&self) {
+        let settings = get_settings(&self.app_handle);
+        let app = match settings.conferencing_guard {
+            ConferencingGuard::Off => None,
+            _ => *self.conferencing_app.lock().unwrap(),
+        };
+
+        let switch = app.is_some()
+            && settings.conferencing_guard == ConferencingGuard::SwitchMicrophone
+            && settings.conferencing_microphone.is_some();
+        if self.use_conferencing_mic.swap(switch, Ordering::Relaxed) != switch {
+            info!(
+                "{} conferencing microphone",
+                if switch {
+                    "Switching to"
+                } else {
+                    "Switching back from"
+                }
+            );
+            if let Err(e) = self.update_selected_device() {
+                error!("Failed to switch microphone: {}", e);
+            }
+        }
+
+        if let (Some(app), false) = (app, switch) {
+            notifications::notify_conferencing_mic(&self.app_handle, app);
+        }
+    }
+
+    /// The microphone picked from `microphone_priority`, if any is connected
+    pub fn priority_microphone(&self) -> Option<String> {
+        self.priority_mic.lock().unwrap().clone()
+    }
+
+    /// Checks `microphone_priority` against the connected microphones and
+    /// switches to the best one if that changed. Returns whether it did.
+    pub fn refresh_priority_microphone(&self) -> bool {
+        let settings = get_settings(&self.app_handle);
+        let preferred =
+            preferred_microphone(&settings.microphone_priority, &connected_microphones());
+        let previous =
+            std::mem::replace(&mut *self.priority_mic.lock().unwrap(), preferred.clone());
+        if previous == preferred {
+            return false;
+        }
+        info!(
+            "Preferred microphone changed from {:?} to {:?}",
+            previous, preferred
+        );
+        if let Err(e) = self.update_selected_device() {
+            error!("Failed to switch microphone: {}", e);
+        }
+        true
+    }
+
+    /// Follows microphones being plugged in and removed while a priority list
+    /// is set. Switching waits until no recording is running.
+    fn start_device_monitor(&self) {
+        let manager = self.clone();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(DEVICE_POLL_INTERVAL);
+            let settings = get_settings(&manager.app_handle);
+            if settings.microphone_priority.is_empty()
+                || settings.remote_microphone
+                || manager.is_recording()
+            {
+                continue;
+            }
+            if manager.refresh_priority_microphone() {
+                let name = manager
+                    .priority_microphone()
+                    .or(settings.selected_microphone)
+                    .unwrap_or_else(|| "Default".to_string());
+                notifications::notify_microphone_switched(&manager.app_handle, &name);
+            }
+        });
+    }
+
+    /// Looks for a video call using the microphone every few seconds while
+    /// `conferencing_guard` is on.
+    fn start_conferencing_monitor(&self) {
+        let manager = self.clone();
+        std::thread::spawn(move || loop {
+            let settings = get_settings(&manager.app_handle);
+            let app = match settings.conferencing_guard {
+                ConferencingGuard::Off => None,
+                _ => conferencing::active_conferencing_app(*manager.is_open.lock().unwrap()),
+            };
+            *manager.conferencing_app.lock().unwrap() = app;
+            std::thread::sleep(CONFERENCING_POLL_INTERVAL);
+        });
+    }
+
     /* ---------- microphone life-cycle -------------------------------------- */
 
     /// Applies mute if mute_while_recording is enabled and stream is open
@@ -217,7 +417,11 @@ impl AudioRecordingManager {
         let settings = get_settings(&self.app_handle);
         let mut did_mute_guard = self.did_mute.lock().unwrap();
 
-        if settings.mute_while_recording && *self.is_open.lock().unwrap() {
+        // Muting would silence system audio as it's recorded
+        if settings.mute_while_recording
+            && !Self::records_system_audio(&settings)
+            && *self.is_open.lock().unwrap()
+        {
             set_mute(true);
             *did_mute_guard = true;
             debug!("Mute applied");
@@ -261,6 +465,7 @@ impl AudioRecordingManager {
             *recorder_opt = Some(create_audio_recorder(
                 vad_path.to_str().unwrap(),
                 &self.app_handle,
+                self.cue_gate.clone(),
             )?);
         }
 
@@ -269,8 +474,26 @@ impl AudioRecordingManager {
         let selected_device = self.get_effective_microphone_device(&settings);
 
         if let Some(rec) = recorder_opt.as_mut() {
-            rec.open(selected_device)
-                .map_err(|e| anyhow::anyhow!("Failed to open recorder: {}", e))?;
+            if settings.remote_microphone {
+                rec.open_network(
+                    settings.remote_microphone_port,
+                    settings.remote_microphone_token.clone(),
+                )
+            } else if Self::records_system_audio(&settings) {
+                rec.open_system_audio(
+                    settings
+                        .system_audio_mix_microphone
+                        .then(|| self.mixed_microphone(&settings))
+                        .flatten(),
+                )
+            } else {
+                rec.open(selected_device)
+            }
+            .map_err(|e| anyhow::anyhow!("Failed to open recorder: {}", e))?;
+        }
+
+        if let Some(rec) = recorder_opt.as_ref() {
+            self.apply_profile(rec, &settings);
         }
 
         *open_flag = true;
@@ -314,7 +537,9 @@ impl AudioRecordingManager {
 
         match (cur_mode, &new_mode) {
             (MicrophoneMode::AlwaysOn, MicrophoneMode::OnDemand) => {
-                if matches!(*self.state.lock().unwrap(), RecordingState::Idle) {
+                if matches!(*self.state.lock().unwrap(), RecordingState::Idle)
+                    && !get_settings(&self.app_handle).remote_microphone
+                {
                     drop(mode_guard);
                     self.stop_microphone_stream();
                 }
@@ -344,8 +569,20 @@ impl AudioRecordingManager {
                 }
             }
 
+            let wake = self.pre_roll_armed.swap(false, Ordering::SeqCst);
+            let pre_roll = if wake {
+                wake_word::PRE_ROLL
+            } else {
+                Duration::from_millis(get_settings(&self.app_handle).pre_roll_ms as u64)
+            };
             if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
-                if rec.start().is_ok() {
+                let started = if pre_roll.is_zero() {
+                    rec.start()
+                } else {
+                    rec.start_with_pre_roll(pre_roll)
+                };
+                if started.is_ok() {
+                    self.started_by_wake.store(wake, Ordering::SeqCst);
                     *self.is_recording.lock().unwrap() = true;
                     *state = RecordingState::Recording {
                         binding_id: binding_id.to_string(),
@@ -361,6 +598,26 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Whether the stream closes between recordings. A remote microphone
+    /// stays open so the phone can stay connected.
+    fn closes_when_idle(&self) -> bool {
+        matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand)
+            && !get_settings(&self.app_handle).remote_microphone
+    }
+
+    /// Switches between a remote and a local microphone, opening the stream
+    /// for a remote one right away.
+    pub fn update_remote_microphone(&self) -> Result<(), anyhow::Error> {
+        if self.is_recording() {
+            return Err(anyhow::anyhow!("Can't switch microphones while recording"));
+        }
+        self.stop_microphone_stream();
+        if !self.closes_when_idle() {
+            self.start_microphone_stream()?;
+        }
+        Ok(())
+    }
+
     pub fn update_selected_device(&self) -> Result<(), anyhow::Error> {
         // If currently open, restart the microphone stream to use the new device
         if *self.is_open.lock().unwrap() {
@@ -379,6 +636,7 @@ impl AudioRecordingManager {
             } if active == binding_id => {
                 *state = RecordingState::Idle;
                 drop(state);
+                self.started_by_wake.store(false, Ordering::SeqCst);
 
                 let samples = if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
                     match rec.stop() {
@@ -396,7 +654,7 @@ impl AudioRecordingManager {
                 *self.is_recording.lock().unwrap() = false;
 
                 // In on-demand mode turn the mic off again
-                if matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand) {
+                if self.closes_when_idle() {
                     self.stop_microphone_stream();
                 }
 
@@ -414,6 +672,163 @@ impl AudioRecordingManager {
             _ => None,
         }
     }
+
+    /// Takes the audio captured so far for `binding_id` while leaving the
+    /// recording running. Returns `None` if that binding is not recording.
+    pub fn drain_recording(&self, binding_id: &str) -> Option<Vec<f32>> {
+        let state = self.state.lock().unwrap();
+
+        match *state {
+            RecordingState::Recording {
+                binding_id: ref active,
+            } if active == binding_id => {
+                drop(state);
+                match self.recorder.lock().unwrap().as_ref() {
+                    Some(rec) => match rec.drain() {
+                        Ok(buf) => Some(buf),
+                        Err(e) => {
+                            error!("drain() failed: {e}");
+                            None
+                        }
+                    },
+                    None => {
+                        error!("Recorder not available");
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Copies the audio captured for `binding_id` from sample `from` on,
+    /// leaving the recording as it is. Returns `None` if that binding is not
+    /// recording.
+    pub fn peek_recording(&self, binding_id: &str, from: usize) -> Option<Vec<f32>> {
+        let state = self.state.lock().unwrap();
+
+        match *state {
+            RecordingState::Recording {
+                binding_id: ref active,
+            } if active == binding_id => {
+                drop(state);
+                match self.recorder.lock().unwrap().as_ref()?.peek(from) {
+                    Ok(buf) => Some(buf),
+                    Err(e) => {
+                        error!("peek() failed: {e}");
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// The `voice_profiles` key of the input recordings currently come from
+    pub fn profile_key(&self, settings: &AppSettings) -> String {
+        if settings.remote_microphone {
+            return NETWORK_PROFILE_KEY.to_string();
+        }
+        self.effective_microphone_name(settings)
+            .unwrap_or_else(|| DEFAULT_PROFILE_KEY.to_string())
+    }
+
+    /// Applies the microphone's calibrated VAD threshold and gain, or the
+    /// defaults if it hasn't been calibrated
+    fn apply_profile(&self, rec: &AudioRecorder, settings: &AppSettings) {
+        rec.set_adaptive_vad(settings.adaptive_vad);
+        // Enough for both the pre-roll setting and the wake word
+        let mut pre_roll = Duration::from_millis(settings.pre_roll_ms as u64);
+        if settings.wake_word_enabled {
+            pre_roll = pre_roll.max(wake_word::PRE_ROLL);
+        }
+        rec.set_pre_roll(pre_roll);
+        rec.set_noise_suppression(if settings.noise_suppression {
+            settings.noise_suppression_strength
+        } else {
+            0.0
+        });
+        rec.set_auto_gain(settings.auto_gain_control);
+        let key = self.profile_key(settings);
+        match settings.voice_profiles.get(&key) {
+            Some(profile) => {
+                debug!("Using the calibrated voice profile for {}", key);
+                rec.set_vad_threshold(profile.vad_threshold);
+                rec.set_input_gain(profile.input_gain);
+            }
+            None => {
+                rec.set_vad_threshold(DEFAULT_VAD_THRESHOLD);
+                rec.set_input_gain(1.0);
+            }
+        }
+    }
+
+    /// Applies a changed voice profile, VAD, pre-roll or clean-up setting to an open
+    /// stream
+    pub fn update_voice_profile(&self) {
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            self.apply_profile(rec, &get_settings(&self.app_handle));
+        }
+    }
+
+    /// Keeps silence in recordings too, for calibration. See
+    /// `AudioRecorder::set_raw_capture`.
+    pub fn set_raw_capture(&self, raw: bool) {
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            rec.set_raw_capture(raw);
+        }
+    }
+
+    pub fn cue_gate(&self) -> Arc<AtomicUsize> {
+        self.cue_gate.clone()
+    }
+
+    pub fn is_always_on(&self) -> bool {
+        matches!(*self.mode.lock().unwrap(), MicrophoneMode::AlwaysOn)
+    }
+
+    /// Speech/silence ratio of the current recording, if there is one.
+    pub fn speech_ratio(&self) -> Option<f32> {
+        if !self.is_recording() {
+            return None;
+        }
+        self.recorder
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|rec| rec.speech_ratio())
+    }
+
+    /// How long the current recording has been silent, if there is one.
+    pub fn silence(&self) -> Option<Duration> {
+        if !self.is_recording() {
+            return None;
+        }
+        self.recorder
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|rec| rec.silence())
+    }
+
+    /// Makes the next recording start with what was heard just before it
+    pub fn arm_pre_roll(&self) {
+        self.pre_roll_armed.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the current recording was started by the wake word
+    pub fn started_by_wake_word(&self) -> bool {
+        self.started_by_wake.load(Ordering::SeqCst)
+    }
+
+    /// The binding the current recording was started for
+    pub fn active_binding(&self) -> Option<String> {
+        match &*self.state.lock().unwrap() {
+            RecordingState::Recording { binding_id } => Some(binding_id.clone()),
+            RecordingState::Idle => None,
+        }
+    }
+
     pub fn is_recording(&self) -> bool {
         matches!(
             *self.state.lock().unwrap(),
@@ -428,6 +843,7 @@ impl AudioRecordingManager {
         if let RecordingState::Recording { .. } = *state {
             *state = RecordingState::Idle;
             drop(state);
+            self.started_by_wake.store(false, Ordering::SeqCst);
 
             if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
                 let _ = rec.stop(); // Discard the result
@@ -436,9 +852,31 @@ impl AudioRecordingManager {
             *self.is_recording.lock().unwrap() = false;
 
             // In on-demand mode turn the mic off again
-            if matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand) {
+            if self.closes_when_idle() {
                 self.stop_microphone_stream();
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preferred_microphone_is_highest_ranked_connected() {
+        let priority = vec!["Headset".to_string(), "USB Mic".to_string()];
+        let connected = vec!["Built-in".to_string(), "USB Mic".to_string()];
+        assert_eq!(
+            preferred_microphone(&priority, &connected).as_deref(),
+            Some("USB Mic")
+        );
+        let both = vec!["USB Mic".to_string(), "Headset".to_string()];
+        assert_eq!(
+            preferred_microphone(&priority, &both).as_deref(),
+            Some("Headset")
+        );
+        assert_eq!(preferred_microphone(&priority, &["Built-in".into()]), None);
+        assert_eq!(preferred_microphone(&[], &connected), None);
+    }
+}
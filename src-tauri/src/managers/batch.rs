@@ -0,0 +1,173 @@
+//! Deferred file transcription. Jobs wait in a queue persisted next to the
+//! history database until the configured schedule allows them to run: inside
+//! a time window such as overnight, on AC power, and/or once the machine has
+//! been idle for a while. The scheduler itself runs in the transcription
+//! manager.
+
+use crate::settings::AppSettings;
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+pub const MINUTES_PER_DAY: u32 = 24 * 60;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type, PartialEq)]
+pub struct BatchJob {
+    pub path: String,
+    pub queued_at: i64,
+}
+
+/// Emitted as "batch-job-completed" once a queued file was processed
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct BatchJobResult {
+    pub path: String,
+    pub text: Option<String>,
+    pub error: Option<String>,
+}
+
+pub struct BatchQueue {
+    file: PathBuf,
+    jobs: Mutex<Vec<BatchJob>>,
+}
+
+impl BatchQueue {
+    /// Loads the queue from `file`, starting empty if it's missing or unreadable.
+    pub fn load(file: PathBuf) -> Self {
+        let jobs = match fs::read_to_string(&file) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                warn!("Ignoring invalid batch queue {:?}: {}", file, e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self {
+            file,
+            jobs: Mutex::new(jobs),
+        }
+    }
+
+    pub fn jobs(&self) -> Vec<BatchJob> {
+        self.jobs.lock().unwrap().clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.lock().unwrap().is_empty()
+    }
+
+    /// Adds a file to the end of the queue. Returns false if it's already queued.
+    pub fn push(&self, path: String) -> bool {
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.iter().any(|job| job.path == path) {
+            return false;
+        }
+        jobs.push(BatchJob {
+            path,
+            queued_at: chrono::Utc::now().timestamp(),
+        });
+        self.save(&jobs);
+        true
+    }
+
+    pub fn remove(&self, path: &str) -> bool {
+        let mut jobs = self.jobs.lock().unwrap();
+        let before = jobs.len();
+        jobs.retain(|job| job.path != path);
+        let removed = jobs.len() != before;
+        if removed {
+            self.save(&jobs);
+        }
+        removed
+    }
+
+    pub fn front(&self) -> Option<BatchJob> {
+        self.jobs.lock().unwrap().first().cloned()
+    }
+
+    fn save(&self, jobs: &[BatchJob]) {
+        let result = serde_json::to_string_pretty(jobs)
+            .map_err(|e| e.to_string())
+            .and_then(|json| fs::write(&self.file, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            error!("Failed to save batch queue to {:?}: {}", self.file, e);
+        }
+    }
+}
+
+/// Whether `minute` (since midnight) falls in the window from `start` up to
+/// `end`. Windows where `end` comes before `start` wrap around midnight.
+fn in_window(minute: u32, start: u32, end: u32) -> bool {
+    if start <= end {
+        (start..end).contains(&minute)
+    } else {
+        minute >= start || minute < end
+    }
+}
+
+/// Whether queued jobs may run now. Every condition that is configured has to
+/// hold; with deferral off they always may. An unknown idle time counts as
+/// idle, so platforms that can't report it still get their jobs done.
+pub fn schedule_allows(
+    settings: &AppSettings,
+    minute_of_day: u32,
+    on_battery: bool,
+    idle: Option<Duration>,
+) -> bool {
+    if !settings.batch_deferred {
+        return true;
+    }
+    if let (Some(start), Some(end)) = (settings.batch_window_start, settings.batch_window_end) {
+        if start != end && !in_window(minute_of_day, start, end) {
+            return false;
+        }
+    }
+    if settings.batch_require_ac_power && on_battery {
+        return false;
+    }
+    match (settings.batch_idle_minutes, idle) {
+        (Some(minutes), Some(idle)) => idle >= Duration::from_secs(minutes as u64 * 60),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+
+    #[test]
+    fn windows_wrap_around_midnight() {
+        assert!(in_window(60, 0, 120));
+        assert!(!in_window(120, 0, 120));
+        // 22:00 to 06:00
+        assert!(in_window(23 * 60, 22 * 60, 6 * 60));
+        assert!(in_window(3 * 60, 22 * 60, 6 * 60));
+        assert!(!in_window(12 * 60, 22 * 60, 6 * 60));
+    }
+
+    #[test]
+    fn every_configured_condition_must_hold() {
+        let mut settings = get_default_settings();
+        assert!(schedule_allows(&settings, 12 * 60, true, None));
+
+        settings.batch_deferred = true;
+        settings.batch_window_start = Some(22 * 60);
+        settings.batch_window_end = Some(6 * 60);
+        settings.batch_require_ac_power = true;
+        settings.batch_idle_minutes = Some(10);
+        let idle = Some(Duration::from_secs(15 * 60));
+        assert!(schedule_allows(&settings, 60, false, idle));
+        assert!(!schedule_allows(&settings, 12 * 60, false, idle));
+        assert!(!schedule_allows(&settings, 60, true, idle));
+        assert!(!schedule_allows(
+            &settings,
+            60,
+            false,
+            Some(Duration::from_secs(60))
+        ));
+        assert!(schedule_allows(&settings, 60, false, None));
+    }
+}
@@ -0,0 +1,395 @@
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::{get_settings, CaptionTimestamps};
+use crate::utils;
+use anyhow::Result;
+use handy_transcription::{constants, split_on_silence};
+use log::{debug, error, info};
+use serde::Serialize;
+use specta::Type;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Binding id the captions feed records under, so it never collides with a
+/// dictation shortcut.
+pub const CAPTIONS_BINDING_ID: &str = "live_captions";
+
+/// How often captured audio is cut off and turned into a caption line.
+const CAPTION_INTERVAL: Duration = Duration::from_millis(2500);
+/// How often captured audio is checked for finished segments when captions
+/// are cut at pauses instead.
+const SEGMENT_INTERVAL: Duration = Duration::from_millis(500);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Chunks shorter than this (0.4s at 16kHz) are carried over to the next tick.
+const MIN_CAPTION_SAMPLES: usize = 6400;
+/// Pauses closer together than this are skipped when cutting at pauses
+const MIN_SEGMENT_MS: usize = 1500;
+const MAX_CAPTION_LINES: usize = 50;
+
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct CaptionLine {
+    pub id: u64,
+    pub text: String,
+    pub timestamp: i64,
+}
+
+pub struct CaptionsManager {
+    app_handle: AppHandle,
+    active: Arc<AtomicBool>,
+    next_id: Arc<AtomicU64>,
+    lines: Arc<Mutex<VecDeque<CaptionLine>>>,
+    /// Every line since captions were last started, kept after they stop so
+    /// the session can be exported
+    session: Arc<Mutex<Vec<CaptionLine>>>,
+    session_started: Mutex<Option<i64>>,
+    worker_handle: Mutex<Option<thread::JoinHandle<()>>>,
+}
+
+impl CaptionsManager {
+    pub fn new(app_handle: &AppHandle) -> Self {
+        Self {
+            app_handle: app_handle.clone(),
+            active: Arc::new(AtomicBool::new(false)),
+            next_id: Arc::new(AtomicU64::new(0)),
+            lines: Arc::new(Mutex::new(VecDeque::new())),
+            session: Arc::new(Mutex::new(Vec::new())),
+            session_started: Mutex::new(None),
+            worker_handle: Mutex::new(None),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    pub fn get_lines(&self) -> Vec<CaptionLine> {
+        self.lines.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// When the last session started, in milliseconds, and all of its lines
+    pub fn get_session(&self) -> Option<(i64, Vec<CaptionLine>)> {
+        let started = (*self.session_started.lock().unwrap())?;
+        Some((started, self.session.lock().unwrap().clone()))
+    }
+
+    pub fn start(&self) -> Result<()> {
+        if self.is_active() {
+            return Ok(());
+        }
+
+        let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
+        if !rm.try_start_recording(CAPTIONS_BINDING_ID) {
+            return Err(anyhow::anyhow!(
+                "Microphone is busy, finish the current recording first"
+            ));
+        }
+
+        let tm = self.app_handle.state::<Arc<TranscriptionManager>>();
+        tm.initiate_model_load();
+
+        self.lines.lock().unwrap().clear();
+        self.session.lock().unwrap().clear();
+        *self.session_started.lock().unwrap() = Some(chrono::Utc::now().timestamp_millis());
+        self.active.store(true, Ordering::Relaxed);
+        utils::show_captions_overlay(&self.app_handle);
+        let _ = self.app_handle.emit("captions-state-changed", true);
+        info!("Live captions started");
+
+        let app = self.app_handle.clone();
+        let active = self.active.clone();
+        let next_id = self.next_id.clone();
+        let lines = self.lines.clone();
+        let session = self.session.clone();
+        let handle = thread::spawn(move || {
+            run_captions_loop(&app, &active, &next_id, &lines, &session);
+
+            // The loop also exits when the recording is cancelled from elsewhere,
+            // so make sure the UI reflects that.
+            active.store(false, Ordering::Relaxed);
+            utils::hide_captions_overlay(&app);
+            let _ = app.emit("captions-state-changed", false);
+            debug!("Captions worker thread exited");
+        });
+        *self.worker_handle.lock().unwrap() = Some(handle);
+
+        Ok(())
+    }
+
+    pub fn stop(&self) {
+        if !self.active.swap(false, Ordering::Relaxed) {
+            return;
+        }
+
+        if let Some(handle) = self.worker_handle.lock().unwrap().take() {
+            let _ = handle.join();
+        }
+
+        let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
+        let _ = rm.stop_recording(CAPTIONS_BINDING_ID);
+        info!("Live captions stopped");
+    }
+}
+
+fn run_captions_loop(
+    app: &AppHandle,
+    active: &AtomicBool,
+    next_id: &AtomicU64,
+    lines: &Mutex<VecDeque<CaptionLine>>,
+    session: &Mutex<Vec<CaptionLine>>,
+) {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    let tm = app.state::<Arc<TranscriptionManager>>();
+    let mut pending = Vec::<f32>::new();
+
+    while active.load(Ordering::Relaxed) {
+        let settings = get_settings(app);
+        let interval = if settings.caption_segment_at_pauses {
+            SEGMENT_INTERVAL
+        } else {
+            CAPTION_INTERVAL
+        };
+
+        let mut waited = Duration::ZERO;
+        while waited < interval && active.load(Ordering::Relaxed) {
+            thread::sleep(POLL_INTERVAL);
+            waited += POLL_INTERVAL;
+        }
+        if !active.load(Ordering::Relaxed) {
+            break;
+        }
+
+        // The recorder only passes on speech, so nothing new means a pause
+        let speaker_paused = match rm.drain_recording(CAPTIONS_BINDING_ID) {
+            Some(samples) => {
+                let paused = samples.is_empty();
+                pending.extend(samples);
+                paused
+            }
+            None => {
+                debug!("Captions recording is no longer active");
+                break;
+            }
+        };
+
+        let chunks = if settings.caption_segment_at_pauses {
+            let max_segment_ms = settings.caption_max_segment_secs.max(1) as usize * 1000;
+            let segments = finished_segments(&pending, max_segment_ms, speaker_paused);
+            let Some(end) = segments.last().map(|segment| segment.end) else {
+                continue;
+            };
+            let chunks: Vec<Vec<f32>> = segments
+                .into_iter()
+                .map(|segment| pending[segment].to_vec())
+                .collect();
+            pending.drain(..end);
+            chunks
+        } else if pending.len() >= MIN_CAPTION_SAMPLES {
+            vec![std::mem::take(&mut pending)]
+        } else {
+            continue;
+        };
+
+        for chunk in chunks {
+            let text = match tm.transcribe(chunk) {
+                Ok(text) => text,
+                Err(e) => {
+                    error!("Caption transcription failed: {}", e);
+                    continue;
+                }
+            };
+            if !text.is_empty() {
+                push_line(app, next_id, lines, session, text);
+            }
+        }
+    }
+}
+
+fn push_line(
+    app: &AppHandle,
+    next_id: &AtomicU64,
+    lines: &Mutex<VecDeque<CaptionLine>>,
+    session: &Mutex<Vec<CaptionLine>>,
+    text: String,
+) {
+    let line = CaptionLine {
+        id: next_id.fetch_add(1, Ordering::Relaxed),
+        text,
+        timestamp: chrono::Utc::now().timestamp_millis(),
+    };
+    session.lock().unwrap().push(line.clone());
+    {
+        let mut lines = lines.lock().unwrap();
+        lines.push_back(line.clone());
+        while lines.len() > MAX_CAPTION_LINES {
+            lines.pop_front();
+        }
+    }
+    let _ = app.emit("caption-line", &line);
+}
+
+/// The segments of `samples` that are ready to be captioned: everything up to
+/// the last pause, plus the speech after it once the speaker has stopped or
+/// it reaches `max_segment_ms`.
+fn finished_segments(
+    samples: &[f32],
+    max_segment_ms: usize,
+    speaker_paused: bool,
+) -> Vec<Range<usize>> {
+    if samples.len() < MIN_CAPTION_SAMPLES {
+        return Vec::new();
+    }
+    let sample_rate = constants::WHISPER_SAMPLE_RATE as usize;
+    let mut segments = split_on_silence(samples, sample_rate, MIN_SEGMENT_MS, max_segment_ms);
+
+    let max_segment = sample_rate * max_segment_ms / 1000;
+    let last_is_open = segments.last().is_some_and(|last| last.len() < max_segment);
+    if last_is_open && !speaker_paused {
+        segments.pop();
+    }
+    segments
+}
+
+fn format_offset(ms: i64, with_hours: bool) -> String {
+    let seconds = ms.max(0) / 1000;
+    if with_hours {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    } else {
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+/// Renders a captions session as Markdown, with each paragraph labelled with
+/// the speaker and, depending on `timestamps`, its time into the session.
+pub fn session_markdown(
+    title: &str,
+    started: i64,
+    lines: &[CaptionLine],
+    timestamps: CaptionTimestamps,
+    speaker: &str,
+) -> String {
+    let bucket_ms = match timestamps {
+        CaptionTimestamps::Off => None,
+        CaptionTimestamps::EveryLine => Some(0),
+        CaptionTimestamps::EveryMinute => Some(60_000),
+        CaptionTimestamps::EveryFiveMinutes => Some(300_000),
+    };
+    let with_hours = lines
+        .last()
+        .is_some_and(|line| line.timestamp - started >= 3_600_000);
+
+    let mut paragraphs: Vec<(i64, Vec<&str>)> = Vec::new();
+    for line in lines {
+        let offset = line.timestamp - started;
+        let starts_paragraph = match (bucket_ms, paragraphs.last()) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(0), Some(_)) => true,
+            (Some(bucket), Some((first, _))) => offset / bucket != first / bucket,
+        };
+        if starts_paragraph {
+            paragraphs.push((offset, vec![line.text.as_str()]));
+        } else if let Some((_, texts)) = paragraphs.last_mut() {
+            texts.push(line.text.as_str());
+        }
+    }
+
+    let mut markdown = format!("# {}\n", title);
+    for (offset, texts) in paragraphs {
+        let label = match bucket_ms {
+            Some(_) => format!("[{}] {}", format_offset(offset, with_hours), speaker),
+            None => speaker.to_string(),
+        };
+        markdown.push_str(&format!("\n**{}:** {}\n", label, texts.join(" ")));
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(seconds: i64, text: &str) -> CaptionLine {
+        CaptionLine {
+            id: seconds as u64,
+            text: text.to_string(),
+            timestamp: 1_000_000 + seconds * 1000,
+        }
+    }
+
+    #[test]
+    fn timestamps_every_line() {
+        let lines = [line(3, "Hello everyone."), line(65, "Let's start.")];
+        let markdown = session_markdown(
+            "Standup",
+            1_000_000,
+            &lines,
+            CaptionTimestamps::EveryLine,
+            "Speaker 1",
+        );
+        assert_eq!(
+            markdown,
+            "# Standup\n\n**[00:03] Speaker 1:** Hello everyone.\n\n**[01:05] Speaker 1:** Let's start.\n"
+        );
+    }
+
+    #[test]
+    fn merges_lines_within_a_bucket() {
+        let lines = [
+            line(10, "One."),
+            line(50, "Two."),
+            line(61, "Three."),
+            line(3700, "Four."),
+        ];
+        let markdown = session_markdown(
+            "Notes",
+            1_000_000,
+            &lines,
+            CaptionTimestamps::EveryMinute,
+            "Me",
+        );
+        assert_eq!(
+            markdown,
+            "# Notes\n\n**[0:00:10] Me:** One. Two.\n\n**[0:01:01] Me:** Three.\n\n**[1:01:40] Me:** Four.\n"
+        );
+    }
+
+    fn speech(ms: usize) -> Vec<f32> {
+        (0..16 * ms).map(|i| (i as f32 * 0.1).sin() * 0.5).collect()
+    }
+
+    #[test]
+    fn keeps_the_open_segment_until_the_speaker_pauses() {
+        let audio = [speech(2000), vec![0.0; 16 * 600], speech(2000)].concat();
+        let segments = finished_segments(&audio, 30000, false);
+        assert_eq!(segments.len(), 1);
+        assert!(segments[0].end < audio.len());
+
+        let segments = finished_segments(&audio, 30000, true);
+        assert_eq!(segments.last().map(|s| s.end), Some(audio.len()));
+        assert!(finished_segments(&speech(200), 30000, true).is_empty());
+    }
+
+    #[test]
+    fn cuts_long_speech_at_the_maximum_length() {
+        let segments = finished_segments(&speech(5000), 2000, false);
+        // The 1s tail is too short for a segment of its own
+        assert_eq!(segments, vec![0..32000, 32000..80000]);
+    }
+
+    #[test]
+    fn merges_everything_without_timestamps() {
+        let lines = [line(1, "One."), line(400, "Two.")];
+        let markdown = session_markdown("Notes", 1_000_000, &lines, CaptionTimestamps::Off, "Me");
+        assert_eq!(markdown, "# Notes\n\n**Me:** One. Two.\n");
+    }
+}
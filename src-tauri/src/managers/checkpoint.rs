@@ -0,0 +1,143 @@
+//! Per-segment results of long remote transcriptions, kept on disk so a job
+//! that fails partway (usually the network to the Wyoming server dropping)
+//! picks up after the last finished segment when the same audio is
+//! transcribed again, from a retry or the next batch run.
+//!
+//! Checkpoints are keyed by a fingerprint of the audio and the server, and
+//! deleted once the job completes. Leftovers from jobs that were never retried
+//! are pruned after a week.
+
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CheckpointState {
+    segments: usize,
+    /// Transcripts of the finished segments, in order, including empty ones
+    texts: Vec<String>,
+}
+
+pub struct Checkpoint {
+    file: PathBuf,
+    state: CheckpointState,
+}
+
+/// FNV-1a over the samples and the server address. Only needs to tell jobs
+/// apart, but has to stay the same across runs, which std's hasher doesn't
+/// promise.
+fn fingerprint(samples: &[f32], server: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let bytes = samples
+        .iter()
+        .flat_map(|s| s.to_bits().to_le_bytes())
+        .chain(server.bytes());
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}-{}", hash, samples.len())
+}
+
+fn prune(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age > MAX_AGE);
+        if expired {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+impl Checkpoint {
+    /// Opens the checkpoint for this audio, starting fresh unless an earlier
+    /// attempt split it into the same `segments` and got partway.
+    pub fn open(dir: &Path, samples: &[f32], server: &str, segments: usize) -> Self {
+        prune(dir);
+        if let Err(e) = fs::create_dir_all(dir) {
+            warn!("Failed to create checkpoint directory {:?}: {}", dir, e);
+        }
+
+        let file = dir.join(format!("{}.json", fingerprint(samples, server)));
+        let state = fs::read_to_string(&file)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<CheckpointState>(&contents).ok())
+            .filter(|state| state.segments == segments && state.texts.len() <= segments)
+            .unwrap_or(CheckpointState {
+                segments,
+                texts: Vec::new(),
+            });
+        if !state.texts.is_empty() {
+            debug!(
+                "Resuming transcription at segment {} of {}",
+                state.texts.len() + 1,
+                segments
+            );
+        }
+        Self { file, state }
+    }
+
+    /// Transcripts of the segments finished by earlier attempts
+    pub fn completed(&self) -> &[String] {
+        &self.state.texts
+    }
+
+    pub fn record(&mut self, text: &str) {
+        self.state.texts.push(text.to_string());
+        let result = serde_json::to_string(&self.state)
+            .map_err(|e| e.to_string())
+            .and_then(|json| fs::write(&self.file, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            warn!("Failed to save checkpoint {:?}: {}", self.file, e);
+        }
+    }
+
+    /// Deletes the checkpoint once the whole job has been transcribed.
+    pub fn finish(self) {
+        let _ = fs::remove_file(&self.file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resumes_where_the_last_attempt_stopped() {
+        let dir = std::env::temp_dir().join(format!("handy-checkpoint-{}", std::process::id()));
+        let samples = vec![0.1, -0.2, 0.3];
+
+        let mut checkpoint = Checkpoint::open(&dir, &samples, "host:10300", 3);
+        assert!(checkpoint.completed().is_empty());
+        checkpoint.record("first");
+        checkpoint.record("");
+
+        let checkpoint = Checkpoint::open(&dir, &samples, "host:10300", 3);
+        assert_eq!(checkpoint.completed(), ["first", ""]);
+        // Split differently, e.g. after a settings change
+        assert!(Checkpoint::open(&dir, &samples, "host:10300", 4)
+            .completed()
+            .is_empty());
+        // Different server
+        assert!(Checkpoint::open(&dir, &samples, "other:10300", 3)
+            .completed()
+            .is_empty());
+
+        checkpoint.finish();
+        assert!(Checkpoint::open(&dir, &samples, "host:10300", 3)
+            .completed()
+            .is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
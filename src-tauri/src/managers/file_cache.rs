@@ -0,0 +1,153 @@
+//! Transcripts of files, so transcribing the same file again with the same
+//! engine and settings returns right away. Entries are keyed by a fingerprint
+//! of the file's contents, the engine and the settings that shape the
+//! transcript, and kept in a JSON file next to the batch queue. Past the
+//! size limit the least recently used entries go first.
+
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+struct CacheEntry {
+    key: String,
+    text: String,
+    last_used: i64,
+}
+
+/// FNV-1a over the file, the engine and the settings, for the same reason
+/// as checkpoints: it has to stay the same across runs.
+pub fn key(contents: &[u8], engine: &str, settings: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let bytes = contents
+        .iter()
+        .copied()
+        .chain(engine.bytes())
+        .chain([0])
+        .chain(settings.bytes());
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}-{}", hash, contents.len())
+}
+
+pub struct FileCache {
+    file: PathBuf,
+    entries: Mutex<Vec<CacheEntry>>,
+}
+
+impl FileCache {
+    /// Loads the cache from `file`, starting empty if it's missing or unreadable.
+    pub fn load(file: PathBuf) -> Self {
+        let entries = match fs::read_to_string(&file) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                warn!(
+                    "Ignoring invalid file transcription cache {:?}: {}",
+                    file, e
+                );
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self {
+            file,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// The transcript cached under `key`, marking it as just used.
+    pub fn get(&self, key: &str) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.iter_mut().find(|entry| entry.key == key)?;
+        entry.last_used = chrono::Utc::now().timestamp_millis();
+        let text = entry.text.clone();
+        self.save(&entries);
+        Some(text)
+    }
+
+    /// Caches `text` under `key`, dropping the least recently used entries to
+    /// keep at most `limit`. A limit of zero turns the cache off.
+    pub fn insert(&self, key: String, text: String, limit: usize) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|entry| entry.key != key);
+        entries.push(CacheEntry {
+            key,
+            text,
+            last_used: chrono::Utc::now().timestamp_millis(),
+        });
+        evict(&mut entries, limit);
+        self.save(&entries);
+    }
+
+    /// Drops entries past `limit`, after the limit was lowered.
+    pub fn trim(&self, limit: usize) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() > limit {
+            evict(&mut entries, limit);
+            self.save(&entries);
+        }
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.clear();
+        self.save(&entries);
+    }
+
+    fn save(&self, entries: &[CacheEntry]) {
+        let result = serde_json::to_string(entries)
+            .map_err(|e| e.to_string())
+            .and_then(|json| fs::write(&self.file, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            error!(
+                "Failed to save file transcription cache to {:?}: {}",
+                self.file, e
+            );
+        }
+    }
+}
+
+fn evict(entries: &mut Vec<CacheEntry>, limit: usize) {
+    if entries.len() > limit {
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.last_used));
+        entries.truncate(limit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, last_used: i64) -> CacheEntry {
+        CacheEntry {
+            key: key.to_string(),
+            text: String::new(),
+            last_used,
+        }
+    }
+
+    #[test]
+    fn keys_change_with_engine_and_settings() {
+        let wav = [1, 2, 3];
+        assert_eq!(key(&wav, "local", "{}"), key(&wav, "local", "{}"));
+        assert_ne!(key(&wav, "local", "{}"), key(&wav, "Wyoming", "{}"));
+        assert_ne!(key(&wav, "local", "{}"), key(&wav, "local", "{\"a\":1}"));
+        assert_ne!(key(&wav, "local", "{}"), key(&[1, 2], "local", "{}"));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut entries = vec![entry("a", 3), entry("b", 1), entry("c", 2)];
+        evict(&mut entries, 2);
+        assert_eq!(entries, vec![entry("a", 3), entry("c", 2)]);
+        evict(&mut entries, 0);
+        assert!(entries.is_empty());
+    }
+}
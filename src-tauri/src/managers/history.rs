@@ -1,15 +1,20 @@
 use anyhow::Result;
 use chrono::{DateTime, Local, Utc};
 use log::{debug, error, info};
-use rusqlite::{params, Connection, OptionalExtension};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
 use rusqlite_migration::{Migrations, M};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Emitter, Manager};
 
-use crate::audio_toolkit::save_wav_file;
+use crate::app_usage::UsageRecord;
+use crate::resource_usage::{InferenceDevice, ResourceUsage};
+use crate::transcription_quality::RatingRecord;
+use handy_transcription::constants::WHISPER_SAMPLE_RATE;
+use handy_transcription::save_wav_file;
+use handy_transcription::timing::TimedSegment;
 
 /// Database migrations for transcription history.
 /// Each migration is applied in order. The library tracks which migrations
@@ -31,6 +36,95 @@ static MIGRATIONS: &[M] = &[
     ),
     M::up("ALTER TABLE transcription_history ADD COLUMN post_processed_text TEXT;"),
     M::up("ALTER TABLE transcription_history ADD COLUMN post_process_prompt TEXT;"),
+    M::up(
+        "ALTER TABLE transcription_history ADD COLUMN target_app TEXT;
+        ALTER TABLE transcription_history ADD COLUMN language TEXT;
+        ALTER TABLE transcription_history ADD COLUMN post_process_prompt_id TEXT;",
+    ),
+    M::up(
+        "ALTER TABLE transcription_history ADD COLUMN inference_device TEXT;
+        ALTER TABLE transcription_history ADD COLUMN inference_ms INTEGER;
+        ALTER TABLE transcription_history ADD COLUMN peak_ram_mb INTEGER;
+        ALTER TABLE transcription_history ADD COLUMN vram_mb INTEGER;
+        ALTER TABLE transcription_history ADD COLUMN cpu_percent REAL;",
+    ),
+    M::up(
+        "CREATE TABLE IF NOT EXISTS text_injections (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp_ms INTEGER NOT NULL,
+            target_app TEXT,
+            window_title TEXT,
+            method TEXT NOT NULL,
+            text TEXT NOT NULL,
+            marked BOOLEAN NOT NULL DEFAULT 0
+        );
+        CREATE INDEX IF NOT EXISTS text_injections_timestamp ON text_injections (timestamp_ms);",
+    ),
+    M::up(
+        "ALTER TABLE transcription_history ADD COLUMN engine TEXT;
+        ALTER TABLE transcription_history ADD COLUMN rating BOOLEAN;",
+    ),
+    M::up("ALTER TABLE transcription_history ADD COLUMN segments TEXT;"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN duration_ms INTEGER;"),
+    // Full-text index over the transcripts, kept in step by triggers and
+    // filled from the entries already there
+    M::up(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS transcription_search USING fts5(
+            title, transcription_text, post_processed_text,
+            content='transcription_history', content_rowid='id',
+            tokenize='unicode61 remove_diacritics 2'
+        );
+        INSERT INTO transcription_search(transcription_search) VALUES ('rebuild');
+        CREATE TRIGGER IF NOT EXISTS transcription_search_insert
+        AFTER INSERT ON transcription_history BEGIN
+            INSERT INTO transcription_search (rowid, title, transcription_text, post_processed_text)
+            VALUES (new.id, new.title, new.transcription_text, new.post_processed_text);
+        END;
+        CREATE TRIGGER IF NOT EXISTS transcription_search_delete
+        AFTER DELETE ON transcription_history BEGIN
+            INSERT INTO transcription_search (transcription_search, rowid, title, transcription_text, post_processed_text)
+            VALUES ('delete', old.id, old.title, old.transcription_text, old.post_processed_text);
+        END;
+        CREATE TRIGGER IF NOT EXISTS transcription_search_update
+        AFTER UPDATE OF title, transcription_text, post_processed_text ON transcription_history BEGIN
+            INSERT INTO transcription_search (transcription_search, rowid, title, transcription_text, post_processed_text)
+            VALUES ('delete', old.id, old.title, old.transcription_text, old.post_processed_text);
+            INSERT INTO transcription_search (rowid, title, transcription_text, post_processed_text)
+            VALUES (new.id, new.title, new.transcription_text, new.post_processed_text);
+        END;",
+    ),
+    // The same index split into trigrams, since unicode61 only splits at
+    // spaces and punctuation and so can't find a word inside Chinese or
+    // Japanese text
+    M::up(
+        "DROP TRIGGER IF EXISTS transcription_search_insert;
+        DROP TRIGGER IF EXISTS transcription_search_delete;
+        DROP TRIGGER IF EXISTS transcription_search_update;
+        DROP TABLE IF EXISTS transcription_search;
+        CREATE VIRTUAL TABLE transcription_search USING fts5(
+            title, transcription_text, post_processed_text,
+            content='transcription_history', content_rowid='id',
+            tokenize='trigram remove_diacritics 1'
+        );
+        INSERT INTO transcription_search(transcription_search) VALUES ('rebuild');
+        CREATE TRIGGER transcription_search_insert
+        AFTER INSERT ON transcription_history BEGIN
+            INSERT INTO transcription_search (rowid, title, transcription_text, post_processed_text)
+            VALUES (new.id, new.title, new.transcription_text, new.post_processed_text);
+        END;
+        CREATE TRIGGER transcription_search_delete
+        AFTER DELETE ON transcription_history BEGIN
+            INSERT INTO transcription_search (transcription_search, rowid, title, transcription_text, post_processed_text)
+            VALUES ('delete', old.id, old.title, old.transcription_text, old.post_processed_text);
+        END;
+        CREATE TRIGGER transcription_search_update
+        AFTER UPDATE OF title, transcription_text, post_processed_text ON transcription_history BEGIN
+            INSERT INTO transcription_search (transcription_search, rowid, title, transcription_text, post_processed_text)
+            VALUES ('delete', old.id, old.title, old.transcription_text, old.post_processed_text);
+            INSERT INTO transcription_search (rowid, title, transcription_text, post_processed_text)
+            VALUES (new.id, new.title, new.transcription_text, new.post_processed_text);
+        END;",
+    ),
 ];
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -43,6 +137,164 @@ pub struct HistoryEntry {
     pub transcription_text: String,
     pub post_processed_text: Option<String>,
     pub post_process_prompt: Option<String>,
+    /// The application the transcript was pasted into, when it could be detected
+    pub target_app: Option<String>,
+    /// Resources the transcription took, for entries recorded since this was added
+    pub resource_usage: Option<ResourceUsage>,
+    /// Model ID or remote provider that transcribed the entry
+    pub engine: Option<String>,
+    /// Thumbs up (`true`) or down the user gave the transcript
+    pub rating: Option<bool>,
+    /// Length of the recording, for entries recorded since this was added
+    pub duration_ms: Option<i64>,
+}
+
+/// Where a dictation went, what it was set up with and what it cost, stored
+/// alongside it for the per-app statistics in `app_usage` and the history UI.
+#[derive(Clone, Debug, Default)]
+pub struct DictationContext {
+    pub target_app: Option<String>,
+    pub language: Option<String>,
+    pub post_process_prompt_id: Option<String>,
+    pub resource_usage: Option<ResourceUsage>,
+    pub engine: Option<String>,
+    /// When each part of the transcript was spoken, if the engine said
+    pub segments: Option<Vec<TimedSegment>>,
+}
+
+const ENTRY_COLUMNS: &str = "id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, target_app, inference_device, inference_ms, peak_ram_mb, vram_mb, cpu_percent, engine, rating, duration_ms";
+
+fn entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
+    Ok(HistoryEntry {
+        id: row.get("id")?,
+        file_name: row.get("file_name")?,
+        timestamp: row.get("timestamp")?,
+        saved: row.get("saved")?,
+        title: row.get("title")?,
+        transcription_text: row.get("transcription_text")?,
+        post_processed_text: row.get("post_processed_text")?,
+        post_process_prompt: row.get("post_process_prompt")?,
+        target_app: row.get("target_app")?,
+        resource_usage: match row.get::<_, Option<i64>>("inference_ms")? {
+            Some(inference_ms) => Some(ResourceUsage {
+                device: InferenceDevice::parse(
+                    row.get::<_, Option<String>>("inference_device")?
+                        .as_deref()
+                        .unwrap_or_default(),
+                ),
+                inference_ms: inference_ms as u64,
+                peak_ram_mb: row.get::<_, Option<i64>>("peak_ram_mb")?.unwrap_or(0) as u64,
+                vram_mb: row.get::<_, Option<i64>>("vram_mb")?.map(|mb| mb as u64),
+                cpu_percent: row.get::<_, Option<f64>>("cpu_percent")?.unwrap_or(0.0) as f32,
+            }),
+            None => None,
+        },
+        engine: row.get("engine")?,
+        rating: row.get("rating")?,
+        duration_ms: row.get("duration_ms")?,
+    })
+}
+
+impl HistoryEntry {
+    /// The text to copy or paste: the post-processed version when there is one
+    pub fn text(&self) -> &str {
+        self.post_processed_text
+            .as_deref()
+            .unwrap_or(&self.transcription_text)
+    }
+}
+
+/// A transcript Handy pasted or typed into another app, logged when injection
+/// auditing is on. See `injection_audit`.
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct InjectionRecord {
+    pub id: i64,
+    /// Milliseconds since the Unix epoch, also the key in the invisible marker
+    pub timestamp_ms: i64,
+    pub target_app: Option<String>,
+    pub window_title: Option<String>,
+    /// The paste method used, e.g. "ctrl_v" or "direct"
+    pub method: String,
+    pub text: String,
+    /// Whether the text carried an invisible marker
+    pub marked: bool,
+}
+
+const INJECTION_COLUMNS: &str = "id, timestamp_ms, target_app, window_title, method, text, marked";
+
+fn injection_from_row(row: &rusqlite::Row) -> rusqlite::Result<InjectionRecord> {
+    Ok(InjectionRecord {
+        id: row.get("id")?,
+        timestamp_ms: row.get("timestamp_ms")?,
+        target_app: row.get("target_app")?,
+        window_title: row.get("window_title")?,
+        method: row.get("method")?,
+        text: row.get("text")?,
+        marked: row.get("marked")?,
+    })
+}
+
+/// One page of history entries, newest first, with the number of entries
+/// matching the filter so a picker can show its position.
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct HistoryPage {
+    pub entries: Vec<HistoryEntry>,
+    pub offset: usize,
+    pub total: usize,
+}
+
+/// A LIKE pattern matching `query` anywhere, with its wildcards escaped
+fn like_pattern(query: &str) -> String {
+    let mut pattern = String::from("%");
+    for c in query.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// Words shorter than this have no trigram in the full-text index
+const MIN_INDEXED_CHARS: usize = 3;
+
+/// The words of a search query, split at spaces and punctuation
+fn query_words(query: &str) -> impl Iterator<Item = &str> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+}
+
+/// An FTS5 query matching entries containing every word of `query`
+/// anywhere, so a word still being typed matches too. `None` when there are
+/// no words to look for or one is too short for the index.
+fn match_expression(query: &str) -> Option<String> {
+    let words: Vec<&str> = query_words(query).collect();
+    if words.is_empty()
+        || words
+            .iter()
+            .any(|word| word.chars().count() < MIN_INDEXED_CHARS)
+    {
+        return None;
+    }
+    // Quoted, so words like AND or NEAR aren't taken as operators
+    let words: Vec<String> = words.iter().map(|word| format!("\"{}\"", word)).collect();
+    Some(words.join(" "))
+}
+
+/// Reads the newest transcript straight from the database, preferring the
+/// post-processed text. Used by the CLI, which runs without the app.
+pub fn read_latest_transcript(db_path: &Path) -> Result<Option<String>> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let text = conn
+        .query_row(
+            "SELECT COALESCE(post_processed_text, transcription_text) FROM transcription_history ORDER BY timestamp DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(text)
 }
 
 pub struct HistoryManager {
@@ -183,10 +435,12 @@ impl HistoryManager {
         transcription_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
+        context: DictationContext,
     ) -> Result<()> {
         let timestamp = Utc::now().timestamp();
         let file_name = format!("handy-{}.wav", timestamp);
         let title = self.format_timestamp_title(timestamp);
+        let duration_ms = audio_samples.len() as i64 * 1000 / WHISPER_SAMPLE_RATE as i64;
 
         // Save WAV file
         let file_path = self.recordings_dir.join(&file_name);
@@ -200,6 +454,8 @@ impl HistoryManager {
             transcription_text,
             post_processed_text,
             post_process_prompt,
+            duration_ms,
+            context,
         )?;
 
         // Clean up old entries
@@ -221,11 +477,38 @@ impl HistoryManager {
         transcription_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
+        duration_ms: i64,
+        context: DictationContext,
     ) -> Result<()> {
         let conn = self.get_connection()?;
+        let usage = context.resource_usage.as_ref();
+        let segments = context
+            .segments
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
         conn.execute(
-            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt],
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, target_app, language, post_process_prompt_id, inference_device, inference_ms, peak_ram_mb, vram_mb, cpu_percent, engine, segments, duration_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+            params![
+                file_name,
+                timestamp,
+                false,
+                title,
+                transcription_text,
+                post_processed_text,
+                post_process_prompt,
+                context.target_app,
+                context.language,
+                context.post_process_prompt_id,
+                usage.map(|u| u.device.as_str()),
+                usage.map(|u| u.inference_ms as i64),
+                usage.map(|u| u.peak_ram_mb as i64),
+                usage.and_then(|u| u.vram_mb).map(|mb| mb as i64),
+                usage.map(|u| u.cpu_percent as f64),
+                context.engine,
+                segments,
+                duration_ms
+            ],
         )?;
 
         debug!("Saved transcription to database");
@@ -354,29 +637,182 @@ impl HistoryManager {
 
     pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt FROM transcription_history ORDER BY timestamp DESC"
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history ORDER BY timestamp DESC",
+            ENTRY_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map([], entry_from_row)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        Ok(entries)
+    }
+
+    /// Entries whose text or title contains `query`, newest first. Used by
+    /// the history picker, which pages through them from the keyboard.
+    pub async fn query_entries(
+        &self,
+        query: &str,
+        saved_only: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<HistoryPage> {
+        let conn = self.get_connection()?;
+        let pattern = like_pattern(query.trim());
+        let filter = "(transcription_text LIKE ?1 ESCAPE '\\'
+                OR post_processed_text LIKE ?1 ESCAPE '\\'
+                OR title LIKE ?1 ESCAPE '\\')
+             AND (saved = 1 OR NOT ?2)";
+
+        let total: i64 = conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM transcription_history WHERE {}",
+                filter
+            ),
+            params![pattern, saved_only],
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history WHERE {} ORDER BY timestamp DESC LIMIT ?3 OFFSET ?4",
+            ENTRY_COLUMNS, filter
+        ))?;
+        let rows = stmt.query_map(
+            params![pattern, saved_only, limit as i64, offset as i64],
+            entry_from_row,
+        )?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        Ok(HistoryPage {
+            entries,
+            offset,
+            total: total as usize,
+        })
+    }
+
+    /// Entries containing every word of `query`, best matches first. Unlike
+    /// `query_entries` this goes through the full-text index, so it matches
+    /// regardless of accents and stays fast on a long history. Queries with a
+    /// word too short for the index, as most Chinese and Japanese words are,
+    /// go through `query_entries` instead.
+    pub async fn search_entries(
+        &self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<HistoryPage> {
+        let Some(expression) = match_expression(query) else {
+            if query_words(query).next().is_none() {
+                return Ok(HistoryPage {
+                    entries: Vec::new(),
+                    offset,
+                    total: 0,
+                });
+            }
+            return self.query_entries(query, false, offset, limit).await;
+        };
+        let conn = self.get_connection()?;
+
+        let total: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM transcription_search WHERE transcription_search MATCH ?1",
+            params![expression],
+            |row| row.get(0),
+        )?;
+
+        // Only the matches' ids and ranks come from the index, so the entry
+        // columns aren't ambiguous
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history
+             JOIN (SELECT rowid AS match_id, rank FROM transcription_search
+                   WHERE transcription_search MATCH ?1) ON match_id = id
+             ORDER BY rank, timestamp DESC LIMIT ?2 OFFSET ?3",
+            ENTRY_COLUMNS
+        ))?;
+        let rows = stmt.query_map(
+            params![expression, limit as i64, offset as i64],
+            entry_from_row,
         )?;
 
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        Ok(HistoryPage {
+            entries,
+            offset,
+            total: total as usize,
+        })
+    }
+
+    /// Every dictation with a known target app, for `app_usage`
+    pub async fn usage_records(&self) -> Result<Vec<UsageRecord>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT target_app, timestamp, transcription_text, language, post_process_prompt_id
+             FROM transcription_history WHERE target_app IS NOT NULL",
+        )?;
         let rows = stmt.query_map([], |row| {
-            Ok(HistoryEntry {
-                id: row.get("id")?,
-                file_name: row.get("file_name")?,
+            let text: String = row.get("transcription_text")?;
+            Ok(UsageRecord {
+                app: row.get("target_app")?,
                 timestamp: row.get("timestamp")?,
-                saved: row.get("saved")?,
-                title: row.get("title")?,
-                transcription_text: row.get("transcription_text")?,
-                post_processed_text: row.get("post_processed_text")?,
-                post_process_prompt: row.get("post_process_prompt")?,
+                words: text.split_whitespace().count(),
+                language: row.get("language")?,
+                prompt_id: row.get("post_process_prompt_id")?,
             })
         })?;
 
-        let mut entries = Vec::new();
+        let mut records = Vec::new();
         for row in rows {
-            entries.push(row?);
+            records.push(row?);
         }
+        Ok(records)
+    }
 
-        Ok(entries)
+    /// Every rated dictation with a known engine, for `transcription_quality`
+    pub async fn rating_records(&self) -> Result<Vec<RatingRecord>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT engine, language, rating FROM transcription_history
+             WHERE engine IS NOT NULL AND rating IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(RatingRecord {
+                engine: row.get("engine")?,
+                language: row.get("language")?,
+                good: row.get("rating")?,
+            })
+        })?;
+
+        let mut records = Vec::new();
+        for row in rows {
+            records.push(row?);
+        }
+        Ok(records)
+    }
+
+    /// Gives an entry a thumbs up or down, or clears its rating with `None`.
+    pub async fn set_rating(&self, id: i64, rating: Option<bool>) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE transcription_history SET rating = ?1 WHERE id = ?2",
+            params![rating, id],
+        )?;
+        debug!("Rated entry {}: {:?}", id, rating);
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+        Ok(())
     }
 
     pub async fn toggle_saved_status(&self, id: i64) -> Result<()> {
@@ -406,31 +842,50 @@ impl HistoryManager {
         Ok(())
     }
 
+    /// Replaces the transcript of an entry with an edited version.
+    pub async fn update_transcription_text(&self, id: i64, text: &str) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE transcription_history SET transcription_text = ?1 WHERE id = ?2",
+            params![text, id],
+        )?;
+        debug!("Updated transcript of entry {}", id);
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+        Ok(())
+    }
+
+    /// The timing saved with an entry, for entries transcribed by an engine
+    /// that gave one
+    pub async fn get_segments(&self, id: i64) -> Result<Option<Vec<TimedSegment>>> {
+        let conn = self.get_connection()?;
+        let segments: Option<String> = conn
+            .query_row(
+                "SELECT segments FROM transcription_history WHERE id = ?1",
+                params![id],
+                |row| row.get("segments"),
+            )
+            .optional()?
+            .flatten();
+        Ok(segments
+            .map(|json| serde_json::from_str(&json))
+            .transpose()?)
+    }
+
     pub fn get_audio_file_path(&self, file_name: &str) -> PathBuf {
         self.recordings_dir.join(file_name)
     }
 
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
-             FROM transcription_history WHERE id = ?1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history WHERE id = ?1",
+            ENTRY_COLUMNS
+        ))?;
 
-        let entry = stmt
-            .query_row([id], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                })
-            })
-            .optional()?;
+        let entry = stmt.query_row([id], entry_from_row).optional()?;
 
         Ok(entry)
     }
@@ -466,6 +921,89 @@ impl HistoryManager {
         Ok(())
     }
 
+    pub fn record_injection(
+        &self,
+        timestamp_ms: i64,
+        target_app: Option<&str>,
+        window_title: Option<&str>,
+        method: &str,
+        text: &str,
+        marked: bool,
+    ) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "INSERT INTO text_injections (timestamp_ms, target_app, window_title, method, text, marked) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![timestamp_ms, target_app, window_title, method, text, marked],
+        )?;
+        debug!("Recorded text injection into {:?}", target_app);
+        Ok(())
+    }
+
+    /// Logged injections whose text, app or window title contains `query`,
+    /// newest first, limited to those between `since_ms` and `until_ms` when
+    /// given.
+    pub async fn query_injections(
+        &self,
+        query: &str,
+        since_ms: Option<i64>,
+        until_ms: Option<i64>,
+        limit: usize,
+    ) -> Result<Vec<InjectionRecord>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM text_injections
+             WHERE (text LIKE ?1 ESCAPE '\\'
+                 OR target_app LIKE ?1 ESCAPE '\\'
+                 OR window_title LIKE ?1 ESCAPE '\\')
+               AND (?2 IS NULL OR timestamp_ms >= ?2)
+               AND (?3 IS NULL OR timestamp_ms <= ?3)
+             ORDER BY timestamp_ms DESC LIMIT ?4",
+            INJECTION_COLUMNS
+        ))?;
+        let rows = stmt.query_map(
+            params![like_pattern(query.trim()), since_ms, until_ms, limit as i64],
+            injection_from_row,
+        )?;
+
+        let mut records = Vec::new();
+        for row in rows {
+            records.push(row?);
+        }
+        Ok(records)
+    }
+
+    /// The injection carrying the marker for `timestamp_ms`
+    pub async fn injection_at(&self, timestamp_ms: i64) -> Result<Option<InjectionRecord>> {
+        let conn = self.get_connection()?;
+        let record = conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM text_injections WHERE timestamp_ms = ?1 AND marked = 1",
+                    INJECTION_COLUMNS
+                ),
+                [timestamp_ms],
+                injection_from_row,
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    /// The latest injection of exactly `text`
+    pub async fn latest_injection_of(&self, text: &str) -> Result<Option<InjectionRecord>> {
+        let conn = self.get_connection()?;
+        let record = conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM text_injections WHERE text = ?1 ORDER BY timestamp_ms DESC LIMIT 1",
+                    INJECTION_COLUMNS
+                ),
+                [text],
+                injection_from_row,
+            )
+            .optional()?;
+        Ok(record)
+    }
+
     fn format_timestamp_title(&self, timestamp: i64) -> String {
         if let Some(utc_datetime) = DateTime::from_timestamp(timestamp, 0) {
             // Convert UTC to local timezone
@@ -476,3 +1014,34 @@ impl HistoryManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_like_wildcards() {
+        assert_eq!(like_pattern("meeting"), "%meeting%");
+        assert_eq!(like_pattern("50%_off\\"), "%50\\%\\_off\\\\%");
+    }
+
+    #[test]
+    fn builds_full_text_queries() {
+        assert_eq!(match_expression("budget"), Some("\"budget\"".to_string()));
+        assert_eq!(
+            match_expression(" Q3A budget, NEAR \"final"),
+            Some("\"Q3A\" \"budget\" \"NEAR\" \"final\"".to_string())
+        );
+        assert_eq!(
+            match_expression("预算会议"),
+            Some("\"预算会议\"".to_string())
+        );
+        assert_eq!(match_expression(" -- "), None);
+    }
+
+    #[test]
+    fn leaves_short_words_to_text_search() {
+        assert_eq!(match_expression("Q3 budget"), None);
+        assert_eq!(match_expression("预算"), None);
+    }
+}
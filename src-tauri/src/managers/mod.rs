@@ -1,4 +1,10 @@
 pub mod audio;
+pub mod batch;
+pub mod captions;
+pub mod checkpoint;
+pub mod file_cache;
 pub mod history;
 pub mod model;
+pub mod pending;
+pub mod plugins;
 pub mod transcription;
@@ -2,15 +2,18 @@ use crate::settings::{get_settings, write_settings};
 use anyhow::Result;
 use flate2::read::GzDecoder;
 use futures_util::StreamExt;
+use handy_transcription::local::default_shared_models_dir;
 use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::collections::HashMap;
 use std::fs;
 use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tar::Archive;
 use tauri::{AppHandle, Emitter, Manager};
 
@@ -19,6 +22,92 @@ pub enum EngineType {
     Whisper,
     Parakeet,
     Moonshine,
+    /// Restores punctuation and casing in transcripts, not a speech model
+    Punctuation,
+}
+
+/// ID of the punctuation restoration model, which is kept out of the model list
+pub const PUNCTUATION_MODEL_ID: &str = "punctuation-bert-base";
+
+/// Imported fine-tunes, kept in the models directory next to their weights
+const FINE_TUNES_FILE: &str = "fine_tunes.json";
+/// Identifies a whisper.cpp model file
+const GGML_MAGIC: u32 = 0x6767_6d6c;
+
+/// A fine-tuned Whisper model the user imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FineTune {
+    id: String,
+    name: String,
+    base_model: String,
+    filename: String,
+}
+
+/// The shape of a whisper.cpp model, from its file header. Fine-tunes keep
+/// the shape of the model they were trained from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WhisperShape {
+    n_vocab: i32,
+    n_audio_ctx: i32,
+    n_audio_state: i32,
+    n_audio_head: i32,
+    n_audio_layer: i32,
+    n_text_ctx: i32,
+    n_text_state: i32,
+    n_text_head: i32,
+    n_text_layer: i32,
+    n_mels: i32,
+}
+
+/// Reads the header of a whisper.cpp model. Adapter weights, which
+/// whisper.cpp can't apply on top of a model, are named in the error.
+fn read_whisper_shape(mut reader: impl Read) -> Result<WhisperShape> {
+    let mut header = [0u8; 48];
+    reader
+        .read_exact(&mut header)
+        .map_err(|_| anyhow::anyhow!("The file is too short to be a Whisper model"))?;
+    let field = |i: usize| i32::from_le_bytes(header[4 * i..4 * i + 4].try_into().unwrap());
+    if field(0) as u32 != GGML_MAGIC {
+        // Hugging Face exports start with their JSON header's length
+        let adapter = header.starts_with(b"GGUF") || header[8] == b'{';
+        return Err(if adapter {
+            anyhow::anyhow!(
+                "This looks like LoRA or Hugging Face weights, which Whisper can't load on top of a model. Merge them into the base model and convert the result to ggml first."
+            )
+        } else {
+            anyhow::anyhow!("Not a whisper.cpp (ggml) model file")
+        });
+    }
+    Ok(WhisperShape {
+        n_vocab: field(1),
+        n_audio_ctx: field(2),
+        n_audio_state: field(3),
+        n_audio_head: field(4),
+        n_audio_layer: field(5),
+        n_text_ctx: field(6),
+        n_text_state: field(7),
+        n_text_head: field(8),
+        n_text_layer: field(9),
+        n_mels: field(10),
+    })
+}
+
+/// A model ID for a fine-tune called `name` that isn't taken yet
+fn fine_tune_id(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let base = format!("finetune-{}", if slug.is_empty() { "model" } else { &slug });
+    let mut id = base.clone();
+    let mut n = 2;
+    while taken(&id) {
+        id = format!("{}-{}", base, n);
+        n += 1;
+    }
+    id
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -33,9 +122,14 @@ pub struct ModelInfo {
     pub is_downloading: bool,
     pub partial_size: u64,
     pub is_directory: bool,
+    /// Found only in the shared model directory, so it can't be deleted
+    pub is_shared: bool,
     pub engine_type: EngineType,
     pub accuracy_score: f32, // 0.0 to 1.0, higher is more accurate
     pub speed_score: f32,    // 0.0 to 1.0, higher is faster
+    /// The model this one is a fine-tune of, for imported fine-tunes
+    #[serde(default)]
+    pub base_model: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -46,10 +140,135 @@ pub struct DownloadProgress {
     pub percentage: f64,
 }
 
+/// Everything needed to dictate in one language, installed together
+struct LanguagePackDef {
+    id: &'static str,
+    /// Value for `selected_language`
+    language: &'static str,
+    name: &'static str,
+    /// The speech model first, then any helper models it needs
+    model_ids: &'static [&'static str],
+}
+
+const LANGUAGE_PACKS: &[LanguagePackDef] = &[
+    LanguagePackDef {
+        id: "english",
+        language: "en",
+        name: "English",
+        model_ids: &["parakeet-tdt-0.6b-v2"],
+    },
+    LanguagePackDef {
+        id: "german",
+        language: "de",
+        name: "Deutsch",
+        model_ids: &["parakeet-tdt-0.6b-v3"],
+    },
+    LanguagePackDef {
+        id: "french",
+        language: "fr",
+        name: "Français",
+        model_ids: &["parakeet-tdt-0.6b-v3"],
+    },
+    LanguagePackDef {
+        id: "spanish",
+        language: "es",
+        name: "Español",
+        model_ids: &["parakeet-tdt-0.6b-v3"],
+    },
+    LanguagePackDef {
+        id: "japanese",
+        language: "ja",
+        name: "日本語",
+        model_ids: &["turbo"],
+    },
+    LanguagePackDef {
+        id: "chinese",
+        language: "zh-Hans",
+        name: "简体中文",
+        model_ids: &["turbo"],
+    },
+];
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct LanguagePack {
+    pub id: String,
+    pub language: String,
+    pub name: String,
+    pub model_ids: Vec<String>,
+    pub size_mb: u64,
+    /// All of the pack's models are downloaded
+    pub is_installed: bool,
+}
+
+/// How often a queued download checks for a free slot
+const DOWNLOAD_QUEUE_POLL: Duration = Duration::from_millis(250);
+/// Bandwidth is measured over windows this long, so a pause doesn't build
+/// up credit for a burst afterwards
+const THROTTLE_WINDOW: Duration = Duration::from_secs(1);
+
+/// How long to pause so that `bytes` received over `elapsed` stay within
+/// `limit` bytes per second
+fn throttle_delay(bytes: u64, elapsed: Duration, limit: u64) -> Duration {
+    if limit == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_secs_f64(bytes as f64 / limit as f64).saturating_sub(elapsed)
+}
+
+/// Bandwidth cap shared by all running downloads.
+struct Throttle {
+    /// Bytes per second, 0 for no limit
+    limit: AtomicU64,
+    window: Mutex<(Instant, u64)>,
+}
+
+impl Throttle {
+    fn new(limit: u64) -> Self {
+        Self {
+            limit: AtomicU64::new(limit),
+            window: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    /// Counts `bytes` more received and returns how long to wait before
+    /// reading on
+    fn consume(&self, bytes: u64) -> Duration {
+        let limit = self.limit.load(Ordering::Relaxed);
+        if limit == 0 {
+            return Duration::ZERO;
+        }
+        let mut window = self.window.lock().unwrap();
+        let now = Instant::now();
+        if now.duration_since(window.0) > THROTTLE_WINDOW {
+            *window = (now, 0);
+        }
+        window.1 += bytes;
+        throttle_delay(window.1, now.duration_since(window.0), limit)
+    }
+}
+
+/// Frees a download slot when the download ends, however it ends
+struct DownloadSlot<'a>(&'a AtomicUsize);
+
+impl Drop for DownloadSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn download_limit_bytes(app_handle: &AppHandle) -> u64 {
+    get_settings(app_handle)
+        .download_limit_kbps
+        .map_or(0, |kbps| kbps as u64 * 1024)
+}
+
 pub struct ModelManager {
     app_handle: AppHandle,
     models_dir: PathBuf,
+    shared_models_dir: Mutex<Option<PathBuf>>,
     available_models: Mutex<HashMap<String, ModelInfo>>,
+    active_downloads: AtomicUsize,
+    throttle: Throttle,
 }
 
 impl ModelManager {
@@ -81,9 +300,11 @@ impl ModelManager {
                 is_downloading: false,
                 partial_size: 0,
                 is_directory: false,
+                is_shared: false,
                 engine_type: EngineType::Whisper,
                 accuracy_score: 0.60,
                 speed_score: 0.85,
+                base_model: None,
             },
         );
 
@@ -101,9 +322,11 @@ impl ModelManager {
                 is_downloading: false,
                 partial_size: 0,
                 is_directory: false,
+                is_shared: false,
                 engine_type: EngineType::Whisper,
                 accuracy_score: 0.75,
                 speed_score: 0.60,
+                base_model: None,
             },
         );
 
@@ -120,9 +343,11 @@ impl ModelManager {
                 is_downloading: false,
                 partial_size: 0,
                 is_directory: false,
+                is_shared: false,
                 engine_type: EngineType::Whisper,
                 accuracy_score: 0.80,
                 speed_score: 0.40,
+                base_model: None,
             },
         );
 
@@ -139,9 +364,11 @@ impl ModelManager {
                 is_downloading: false,
                 partial_size: 0,
                 is_directory: false,
+                is_shared: false,
                 engine_type: EngineType::Whisper,
                 accuracy_score: 0.85,
                 speed_score: 0.30,
+                base_model: None,
             },
         );
 
@@ -159,9 +386,11 @@ impl ModelManager {
                 is_downloading: false,
                 partial_size: 0,
                 is_directory: true,
+                is_shared: false,
                 engine_type: EngineType::Parakeet,
                 accuracy_score: 0.85,
                 speed_score: 0.85,
+                base_model: None,
             },
         );
 
@@ -178,9 +407,11 @@ impl ModelManager {
                 is_downloading: false,
                 partial_size: 0,
                 is_directory: true,
+                is_shared: false,
                 engine_type: EngineType::Parakeet,
                 accuracy_score: 0.80,
                 speed_score: 0.85,
+                base_model: None,
             },
         );
 
@@ -197,18 +428,48 @@ impl ModelManager {
                 is_downloading: false,
                 partial_size: 0,
                 is_directory: true,
+                is_shared: false,
                 engine_type: EngineType::Moonshine,
                 accuracy_score: 0.70,
                 speed_score: 0.90,
+                base_model: None,
+            },
+        );
+
+        available_models.insert(
+            PUNCTUATION_MODEL_ID.to_string(),
+            ModelInfo {
+                id: PUNCTUATION_MODEL_ID.to_string(),
+                name: "Punctuation Restoration".to_string(),
+                description: "Adds punctuation and capitalization to English transcripts."
+                    .to_string(),
+                filename: "punctuation-bert-base".to_string(),
+                // Imported from a folder rather than downloaded
+                url: None,
+                size_mb: 0,
+                is_downloaded: false,
+                is_downloading: false,
+                partial_size: 0,
+                is_directory: true,
+                is_shared: false,
+                engine_type: EngineType::Punctuation,
+                accuracy_score: 0.0,
+                speed_score: 0.0,
+                base_model: None,
             },
         );
 
         let manager = Self {
             app_handle: app_handle.clone(),
             models_dir,
+            shared_models_dir: Mutex::new(resolve_shared_models_dir(app_handle)),
             available_models: Mutex::new(available_models),
+            active_downloads: AtomicUsize::new(0),
+            throttle: Throttle::new(download_limit_bytes(app_handle)),
         };
 
+        manager.load_fine_tunes();
+
         // Migrate any bundled models to user directory
         manager.migrate_bundled_models()?;
 
@@ -221,9 +482,14 @@ impl ModelManager {
         Ok(manager)
     }
 
+    /// The speech models the user can transcribe with
     pub fn get_available_models(&self) -> Vec<ModelInfo> {
         let models = self.available_models.lock().unwrap();
-        models.values().cloned().collect()
+        models
+            .values()
+            .filter(|model| !matches!(model.engine_type, EngineType::Punctuation))
+            .cloned()
+            .collect()
     }
 
     pub fn get_model_info(&self, model_id: &str) -> Option<ModelInfo> {
@@ -231,6 +497,56 @@ impl ModelManager {
         models.get(model_id).cloned()
     }
 
+    pub fn get_language_packs(&self) -> Vec<LanguagePack> {
+        let models = self.available_models.lock().unwrap();
+        LANGUAGE_PACKS
+            .iter()
+            .map(|pack| {
+                let pack_models: Vec<&ModelInfo> = pack
+                    .model_ids
+                    .iter()
+                    .filter_map(|id| models.get(*id))
+                    .collect();
+                LanguagePack {
+                    id: pack.id.to_string(),
+                    language: pack.language.to_string(),
+                    name: pack.name.to_string(),
+                    model_ids: pack.model_ids.iter().map(|id| id.to_string()).collect(),
+                    size_mb: pack_models.iter().map(|m| m.size_mb).sum(),
+                    is_installed: pack_models.iter().all(|m| m.is_downloaded),
+                }
+            })
+            .collect()
+    }
+
+    /// Downloads whatever the pack is missing, then switches to it by
+    /// selecting its speech model and language. Returns the speech model,
+    /// which still has to be loaded.
+    pub async fn install_language_pack(&self, pack_id: &str) -> Result<String> {
+        let pack = LANGUAGE_PACKS
+            .iter()
+            .find(|pack| pack.id == pack_id)
+            .ok_or_else(|| anyhow::anyhow!("Language pack not found: {}", pack_id))?;
+
+        for model_id in pack.model_ids {
+            let downloaded = self
+                .get_model_info(model_id)
+                .is_some_and(|info| info.is_downloaded);
+            if !downloaded {
+                self.download_model(model_id).await?;
+            }
+        }
+
+        let speech_model = pack.model_ids[0].to_string();
+        let mut settings = get_settings(&self.app_handle);
+        settings.selected_model = speech_model.clone();
+        settings.selected_language = pack.language.to_string();
+        write_settings(&self.app_handle, settings);
+
+        info!("Installed language pack {}", pack.id);
+        Ok(speech_model)
+    }
+
     fn migrate_bundled_models(&self) -> Result<()> {
         // Check for bundled models and copy them to user directory
         let bundled_models = ["ggml-small.bin"]; // Add other bundled models here if any
@@ -258,6 +574,193 @@ impl ModelManager {
         Ok(())
     }
 
+    fn read_fine_tunes(&self) -> Vec<FineTune> {
+        fs::read_to_string(self.models_dir.join(FINE_TUNES_FILE))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    fn write_fine_tunes(&self, fine_tunes: &[FineTune]) -> Result<()> {
+        fs::write(
+            self.models_dir.join(FINE_TUNES_FILE),
+            serde_json::to_string_pretty(fine_tunes)?,
+        )?;
+        Ok(())
+    }
+
+    /// Lists the imported fine-tunes with the models they're based on
+    fn load_fine_tunes(&self) {
+        let mut models = self.available_models.lock().unwrap();
+        for fine_tune in self.read_fine_tunes() {
+            let Some(base) = models.get(&fine_tune.base_model).cloned() else {
+                warn!(
+                    "Skipping fine-tune {} of unknown model {}",
+                    fine_tune.id, fine_tune.base_model
+                );
+                continue;
+            };
+            models.insert(fine_tune.id.clone(), fine_tune_info(&fine_tune, &base));
+        }
+    }
+
+    /// Copies a fine-tuned Whisper model into the models directory, after
+    /// checking it has the shape of `base_model_id`, which must be installed
+    /// to compare against.
+    pub fn import_fine_tune(
+        &self,
+        path: &Path,
+        name: &str,
+        base_model_id: &str,
+    ) -> Result<ModelInfo> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("Give the fine-tune a name"));
+        }
+        let base = self
+            .get_model_info(base_model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", base_model_id))?;
+        if !matches!(base.engine_type, EngineType::Whisper) || base.base_model.is_some() {
+            return Err(anyhow::anyhow!(
+                "Only the built-in Whisper models can be fine-tuned"
+            ));
+        }
+        let shape = read_whisper_shape(File::open(path)?)?;
+        let base_shape = read_whisper_shape(File::open(self.get_model_path(base_model_id)?)?)?;
+        if shape != base_shape {
+            return Err(anyhow::anyhow!(
+                "This isn't a fine-tune of {}: its layers don't match",
+                base.name
+            ));
+        }
+
+        let id = fine_tune_id(name, |id| self.get_model_info(id).is_some());
+        let fine_tune = FineTune {
+            id: id.clone(),
+            name: name.to_string(),
+            base_model: base_model_id.to_string(),
+            filename: format!("{}.bin", id),
+        };
+        let partial_path = self
+            .models_dir
+            .join(format!("{}.partial", &fine_tune.filename));
+        fs::copy(path, &partial_path)?;
+        fs::rename(&partial_path, self.models_dir.join(&fine_tune.filename))?;
+
+        let mut fine_tunes = self.read_fine_tunes();
+        fine_tunes.push(fine_tune.clone());
+        self.write_fine_tunes(&fine_tunes)?;
+        self.available_models
+            .lock()
+            .unwrap()
+            .insert(id.clone(), fine_tune_info(&fine_tune, &base));
+        self.update_download_status()?;
+        info!(
+            "Imported {:?} as fine-tune {} of {}",
+            path, id, base_model_id
+        );
+
+        self.get_model_info(&id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", id))
+    }
+
+    /// Copies a punctuation model folder into the models directory, replacing
+    /// any imported before.
+    pub fn import_punctuation_model(&self, dir: &Path) -> Result<ModelInfo> {
+        crate::punctuation::check_dir(dir)?;
+        let target = self.models_dir.join(PUNCTUATION_MODEL_ID);
+        let partial = self
+            .models_dir
+            .join(format!("{}.partial", PUNCTUATION_MODEL_ID));
+        if partial.exists() {
+            fs::remove_dir_all(&partial)?;
+        }
+        fs::create_dir_all(&partial)?;
+        for file in ["model.onnx", "vocab.txt", "config.json"] {
+            fs::copy(dir.join(file), partial.join(file))?;
+        }
+        if target.exists() {
+            fs::remove_dir_all(&target)?;
+        }
+        fs::rename(&partial, &target)?;
+        self.update_download_status()?;
+        info!("Imported punctuation model from {:?}", dir);
+
+        self.get_model_info(PUNCTUATION_MODEL_ID)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", PUNCTUATION_MODEL_ID))
+    }
+
+    /// The shared model directory currently in use, if any.
+    pub fn shared_models_dir(&self) -> Option<PathBuf> {
+        self.shared_models_dir.lock().unwrap().clone()
+    }
+
+    /// Re-reads the shared model directory from settings and rescans models.
+    pub fn refresh_shared_models_dir(&self) -> Result<()> {
+        *self.shared_models_dir.lock().unwrap() = resolve_shared_models_dir(&self.app_handle);
+        self.update_download_status()
+    }
+
+    /// Re-reads the download bandwidth limit from settings. Running
+    /// downloads pick it up right away.
+    pub fn refresh_download_limit(&self) {
+        self.throttle
+            .limit
+            .store(download_limit_bytes(&self.app_handle), Ordering::Relaxed);
+    }
+
+    /// Waits until fewer than `max_concurrent_downloads` downloads run and
+    /// takes a slot. Returns `None` if the download was cancelled while it
+    /// waited.
+    async fn claim_download_slot(&self, model_id: &str) -> Option<DownloadSlot<'_>> {
+        let mut logged = false;
+        loop {
+            let still_wanted = self
+                .available_models
+                .lock()
+                .unwrap()
+                .get(model_id)
+                .is_some_and(|model| model.is_downloading);
+            if !still_wanted {
+                info!("Queued download of {} was cancelled", model_id);
+                return None;
+            }
+
+            let max = get_settings(&self.app_handle)
+                .max_concurrent_downloads
+                .max(1);
+            let active = self.active_downloads.load(Ordering::Relaxed);
+            if active < max
+                && self
+                    .active_downloads
+                    .compare_exchange(active, active + 1, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return Some(DownloadSlot(&self.active_downloads));
+            }
+            if !logged {
+                info!(
+                    "Download of {} queued, {} already running",
+                    model_id, active
+                );
+                logged = true;
+            }
+            tokio::time::sleep(DOWNLOAD_QUEUE_POLL).await;
+        }
+    }
+
+    /// A complete copy of the model in the shared directory. Partial and
+    /// extracting leftovers there are ignored, we never write to it.
+    fn shared_model_path(&self, model: &ModelInfo) -> Option<PathBuf> {
+        let path = self.shared_models_dir()?.join(&model.filename);
+        let complete = if model.is_directory {
+            path.is_dir()
+        } else {
+            path.is_file()
+        };
+        complete.then_some(path)
+    }
+
     fn update_download_status(&self) -> Result<()> {
         let mut models = self.available_models.lock().unwrap();
 
@@ -276,7 +779,9 @@ impl ModelManager {
                     let _ = fs::remove_dir_all(&extracting_path);
                 }
 
-                model.is_downloaded = model_path.exists() && model_path.is_dir();
+                let in_user_dir = model_path.exists() && model_path.is_dir();
+                model.is_shared = !in_user_dir && self.shared_model_path(model).is_some();
+                model.is_downloaded = in_user_dir || model.is_shared;
                 model.is_downloading = false;
 
                 // Get partial file size if it exists (for the .tar.gz being downloaded)
@@ -290,7 +795,9 @@ impl ModelManager {
                 let model_path = self.models_dir.join(&model.filename);
                 let partial_path = self.models_dir.join(format!("{}.partial", &model.filename));
 
-                model.is_downloaded = model_path.exists();
+                let in_user_dir = model_path.exists();
+                model.is_shared = !in_user_dir && self.shared_model_path(model).is_some();
+                model.is_downloaded = in_user_dir || model.is_shared;
                 model.is_downloading = false;
 
                 // Get partial file size if it exists
@@ -313,7 +820,9 @@ impl ModelManager {
         if settings.selected_model.is_empty() {
             // Find the first available (downloaded) model
             let models = self.available_models.lock().unwrap();
-            if let Some(available_model) = models.values().find(|model| model.is_downloaded) {
+            if let Some(available_model) = models.values().find(|model| {
+                model.is_downloaded && !matches!(model.engine_type, EngineType::Punctuation)
+            }) {
                 info!(
                     "Auto-selecting model: {} ({})",
                     available_model.id, available_model.name
@@ -376,6 +885,11 @@ impl ModelManager {
             }
         }
 
+        // Wait for a free slot, a cancel while queued leaves the queue
+        let Some(_slot) = self.claim_download_slot(model_id).await else {
+            return Ok(());
+        };
+
         // Create HTTP client with range request for resuming
         let client = reqwest::Client::new();
         let mut request = client.get(&url);
@@ -472,6 +986,11 @@ impl ModelManager {
             file.write_all(&chunk)?;
             downloaded += chunk.len() as u64;
 
+            let delay = self.throttle.consume(chunk.len() as u64);
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+
             let percentage = if total_size > 0 {
                 (downloaded as f64 / total_size as f64) * 100.0
             } else {
@@ -620,6 +1139,13 @@ impl ModelManager {
 
         debug!("ModelManager: Found model info: {:?}", model_info);
 
+        if model_info.is_shared {
+            return Err(anyhow::anyhow!(
+                "Model {} is provided by the shared model directory and can't be deleted",
+                model_id
+            ));
+        }
+
         let model_path = self.models_dir.join(&model_info.filename);
         let partial_path = self
             .models_dir
@@ -659,6 +1185,14 @@ impl ModelManager {
             return Err(anyhow::anyhow!("No model files found to delete"));
         }
 
+        // Imported fine-tunes can't be downloaded again, so they're forgotten
+        if model_info.base_model.is_some() {
+            let mut fine_tunes = self.read_fine_tunes();
+            fine_tunes.retain(|fine_tune| fine_tune.id != model_id);
+            self.write_fine_tunes(&fine_tunes)?;
+            self.available_models.lock().unwrap().remove(model_id);
+        }
+
         // Update download status
         self.update_download_status()?;
         debug!("ModelManager: download status updated");
@@ -683,6 +1217,12 @@ impl ModelManager {
             ));
         }
 
+        if model_info.is_shared {
+            return self
+                .shared_model_path(&model_info)
+                .ok_or_else(|| anyhow::anyhow!("Shared model no longer available: {}", model_id));
+        }
+
         let model_path = self.models_dir.join(&model_info.filename);
         let partial_path = self
             .models_dir
@@ -741,3 +1281,93 @@ impl ModelManager {
         Ok(())
     }
 }
+
+/// The read-only model directory shared by all OS users on this machine,
+/// either configured in settings or the platform's system-wide location.
+/// Administrators install models there once instead of every account
+/// downloading its own copy.
+fn resolve_shared_models_dir(app_handle: &AppHandle) -> Option<PathBuf> {
+    let dir = match get_settings(app_handle).shared_models_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => default_shared_models_dir(&app_handle.config().identifier)?,
+    };
+    if fs::read_dir(&dir).is_ok() {
+        info!("Using shared model directory: {:?}", dir);
+        Some(dir)
+    } else {
+        None
+    }
+}
+
+fn fine_tune_info(fine_tune: &FineTune, base: &ModelInfo) -> ModelInfo {
+    ModelInfo {
+        id: fine_tune.id.clone(),
+        name: fine_tune.name.clone(),
+        description: format!("Fine-tune of {}", base.name),
+        filename: fine_tune.filename.clone(),
+        url: None,
+        size_mb: base.size_mb,
+        is_downloaded: false,
+        is_downloading: false,
+        partial_size: 0,
+        is_directory: false,
+        is_shared: false,
+        engine_type: EngineType::Whisper,
+        accuracy_score: base.accuracy_score,
+        speed_score: base.speed_score,
+        base_model: Some(fine_tune.base_model.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttle_waits_until_the_limit_is_kept() {
+        let second = Duration::from_secs(1);
+        assert_eq!(throttle_delay(2048, second, 1024), second);
+        assert_eq!(throttle_delay(512, second, 1024), Duration::ZERO);
+        assert_eq!(throttle_delay(1 << 30, second, 0), Duration::ZERO);
+    }
+
+    #[test]
+    fn reads_whisper_headers_and_spots_adapters() {
+        let mut header = GGML_MAGIC.to_le_bytes().to_vec();
+        for value in [51865, 1500, 768, 12, 12, 448, 768, 12, 12, 80, 1] {
+            header.extend_from_slice(&i32::to_le_bytes(value));
+        }
+        let shape = read_whisper_shape(&header[..]).unwrap();
+        assert_eq!(
+            (shape.n_vocab, shape.n_audio_layer, shape.n_mels),
+            (51865, 12, 80)
+        );
+
+        let mut safetensors = 120u64.to_le_bytes().to_vec();
+        safetensors.extend_from_slice(&[b'{'; 40]);
+        let error = read_whisper_shape(&safetensors[..]).unwrap_err();
+        assert!(error.to_string().contains("LoRA"));
+        assert!(read_whisper_shape(&[0u8; 10][..]).is_err());
+    }
+
+    #[test]
+    fn fine_tune_ids_are_unique_slugs() {
+        assert_eq!(
+            fine_tune_id("Medical (EN)", |_| false),
+            "finetune-medical-en"
+        );
+        assert_eq!(
+            fine_tune_id("Medical", |id| id == "finetune-medical"),
+            "finetune-medical-2"
+        );
+        assert_eq!(fine_tune_id("ß", |_| false), "finetune-model");
+    }
+
+    #[test]
+    fn language_packs_start_with_a_speech_model() {
+        for pack in LANGUAGE_PACKS {
+            assert!(!pack.model_ids.is_empty(), "{} has no models", pack.id);
+            assert_ne!(pack.model_ids[0], PUNCTUATION_MODEL_ID);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Dictations that couldn't be transcribed because the remote engine was
+//! unreachable. Each one is kept as a WAV file next to a snapshot of the
+//! settings it was recorded with, and the transcription manager's scheduler
+//! sends them again until the engine answers.
+
+use crate::settings::AppSettings;
+use anyhow::Result;
+use handy_transcription::audio::{encode_wav, read_wav_file};
+use handy_transcription::constants;
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type, PartialEq)]
+pub struct PendingTranscription {
+    pub id: String,
+    pub queued_at: i64,
+    pub duration_secs: f32,
+    /// App that had focus when the dictation was recorded
+    pub target_app: Option<String>,
+    /// Why the last attempt failed, if it got through to the engine
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// How many attempts got through to the engine and failed
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+pub struct PendingQueue {
+    dir: PathBuf,
+    jobs: Mutex<Vec<PendingTranscription>>,
+}
+
+impl PendingQueue {
+    /// Loads the queue kept in `dir`, starting empty if there is none.
+    pub fn load(dir: PathBuf) -> Self {
+        let jobs = match fs::read_to_string(dir.join("queue.json")) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                warn!("Ignoring invalid pending queue in {:?}: {}", dir, e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self {
+            dir,
+            jobs: Mutex::new(jobs),
+        }
+    }
+
+    pub fn jobs(&self) -> Vec<PendingTranscription> {
+        self.jobs.lock().unwrap().clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.lock().unwrap().is_empty()
+    }
+
+    /// Saves `samples` and `settings` to disk and adds them to the queue
+    pub fn push(
+        &self,
+        samples: &[f32],
+        settings: &AppSettings,
+        target_app: Option<String>,
+    ) -> Result<PendingTranscription> {
+        fs::create_dir_all(&self.dir)?;
+        let now = chrono::Utc::now();
+        let job = PendingTranscription {
+            id: format!("pending-{}", now.timestamp_millis()),
+            queued_at: now.timestamp(),
+            duration_secs: samples.len() as f32 / constants::WHISPER_SAMPLE_RATE as f32,
+            target_app,
+            last_error: None,
+            attempts: 0,
+        };
+        fs::write(self.wav_path(&job.id), encode_wav(samples)?)?;
+        fs::write(
+            self.settings_path(&job.id),
+            serde_json::to_string(settings)?,
+        )?;
+
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.push(job.clone());
+        self.save(&jobs);
+        Ok(job)
+    }
+
+    /// The audio and settings of a queued dictation
+    pub fn read(&self, id: &str) -> Result<(Vec<f32>, AppSettings)> {
+        let samples = read_wav_file(self.wav_path(id))?;
+        let settings = serde_json::from_str(&fs::read_to_string(self.settings_path(id))?)?;
+        Ok((samples, settings))
+    }
+
+    pub fn set_error(&self, id: &str, error: String) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.iter_mut().find(|job| job.id == id) {
+            job.last_error = Some(error);
+            job.attempts += 1;
+            self.save(&jobs);
+        }
+    }
+
+    /// Drops a dictation from the queue and deletes its files
+    pub fn remove(&self, id: &str) -> bool {
+        let mut jobs = self.jobs.lock().unwrap();
+        let before = jobs.len();
+        jobs.retain(|job| job.id != id);
+        let removed = jobs.len() != before;
+        if removed {
+            let _ = fs::remove_file(self.wav_path(id));
+            let _ = fs::remove_file(self.settings_path(id));
+            self.save(&jobs);
+        }
+        removed
+    }
+
+    fn wav_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.wav", id))
+    }
+
+    fn settings_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    fn save(&self, jobs: &[PendingTranscription]) {
+        let result = serde_json::to_string_pretty(jobs)
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                fs::write(self.dir.join("queue.json"), json).map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            error!("Failed to save pending queue to {:?}: {}", self.dir, e);
+        }
+    }
+}
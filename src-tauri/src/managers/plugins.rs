@@ -0,0 +1,559 @@
+//! Sandboxed WASM plugins for custom processing steps.
+//!
+//! Each plugin lives in its own folder under `<app data>/plugins` with a
+//! `plugin.json` manifest next to the compiled module:
+//!
+//! ```json
+//! {
+//!   "id": "shout",
+//!   "name": "Shout",
+//!   "version": "1.0.0",
+//!   "description": "Upper-cases every transcript",
+//!   "kind": "transform",
+//!   "capabilities": ["log"]
+//! }
+//! ```
+//!
+//! The module exports `memory` and `alloc(len: i32) -> i32`, which Handy
+//! uses to pass the transcript in as UTF-8, plus one hook depending on its
+//! kind:
+//!
+//! - `transform`: `process(ptr: i32, len: i32) -> i64` returns the new text,
+//!   packed as `(ptr << 32) | len`.
+//! - `output`: `route(ptr: i32, len: i32) -> i32` returns 1 when the plugin
+//!   delivered the transcript itself and Handy shouldn't paste it.
+//!
+//! Plugins get no WASI and can only import the `handy` host functions their
+//! manifest declares a capability for. Every call runs in a fresh instance
+//! with capped memory and fuel, so a plugin can't keep state between
+//! transcripts or hang the pipeline.
+
+use crate::settings::get_settings;
+use anyhow::{anyhow, Context, Result};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use wasmi::{Caller, Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+const MANIFEST_FILE: &str = "plugin.json";
+const HOST_MODULE: &str = "handy";
+/// Roughly a few hundred milliseconds of work per call.
+const FUEL_PER_CALL: u64 = 500_000_000;
+const MAX_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginKind {
+    /// Rewrites the transcript before it's pasted
+    Transform,
+    /// Decides where the transcript goes instead of pasting it
+    Output,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginCapability {
+    /// `log(ptr, len)`: write a line to Handy's log
+    Log,
+    /// `write_clipboard(ptr, len) -> i32`: replace the clipboard text
+    Clipboard,
+    /// `append_file(name_ptr, name_len, ptr, len) -> i32`: append to a file
+    /// in the plugin's own data folder
+    Files,
+}
+
+impl PluginCapability {
+    const ALL: [Self; 3] = [Self::Log, Self::Clipboard, Self::Files];
+
+    /// The `handy` host function the capability lets a plugin import
+    fn host_function(self) -> &'static str {
+        match self {
+            Self::Log => "log",
+            Self::Clipboard => "write_clipboard",
+            Self::Files => "append_file",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct PluginManifest {
+    id: String,
+    name: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    description: String,
+    kind: PluginKind,
+    #[serde(default = "default_entry")]
+    entry: String,
+    #[serde(default)]
+    capabilities: Vec<PluginCapability>,
+}
+
+fn default_entry() -> String {
+    "plugin.wasm".to_string()
+}
+
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct PluginInfo {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub kind: PluginKind,
+    pub capabilities: Vec<PluginCapability>,
+    pub enabled: bool,
+    /// Why the plugin couldn't be loaded, if it couldn't
+    pub error: Option<String>,
+}
+
+struct LoadedPlugin {
+    manifest: PluginManifest,
+    data_dir: PathBuf,
+    module: Result<Module, String>,
+}
+
+struct HostState {
+    app_handle: AppHandle,
+    plugin_id: String,
+    data_dir: PathBuf,
+    limits: StoreLimits,
+}
+
+pub struct PluginManager {
+    app_handle: AppHandle,
+    plugins_dir: PathBuf,
+    engine: Engine,
+    plugins: Mutex<Vec<LoadedPlugin>>,
+}
+
+impl PluginManager {
+    pub fn new(app_handle: &AppHandle) -> Result<Self> {
+        let plugins_dir = app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| anyhow!("Failed to get app data dir: {}", e))?
+            .join("plugins");
+        fs::create_dir_all(&plugins_dir)?;
+
+        let mut config = Config::default();
+        config.consume_fuel(true);
+
+        let manager = Self {
+            app_handle: app_handle.clone(),
+            plugins_dir,
+            engine: Engine::new(&config),
+            plugins: Mutex::new(Vec::new()),
+        };
+        manager.reload();
+        Ok(manager)
+    }
+
+    pub fn plugins_dir(&self) -> &Path {
+        &self.plugins_dir
+    }
+
+    /// Rescans the plugins folder and recompiles every module. Folders are
+    /// read in name order, and a plugin whose id an earlier one already has
+    /// is skipped.
+    pub fn reload(&self) {
+        let mut plugins: Vec<LoadedPlugin> = Vec::new();
+        let entries = match fs::read_dir(&self.plugins_dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to read plugins directory: {}", e);
+                return;
+            }
+        };
+        let mut dirs: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect();
+        dirs.sort();
+
+        for dir in dirs {
+            let manifest = match read_manifest(&dir) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    warn!("Skipping plugin in {:?}: {:#}", dir, e);
+                    continue;
+                }
+            };
+            if let Some(other) = plugins.iter().find(|p| p.manifest.id == manifest.id) {
+                warn!(
+                    "Skipping plugin in {:?}: the one in {:?} already has the id '{}'",
+                    dir,
+                    other.data_dir.parent().unwrap_or(&other.data_dir),
+                    manifest.id
+                );
+                continue;
+            }
+            let module = fs::read(dir.join(&manifest.entry))
+                .map_err(|e| format!("Failed to read {}: {}", manifest.entry, e))
+                .and_then(|bytes| Module::new(&self.engine, &bytes[..]).map_err(|e| e.to_string()))
+                .and_then(|module| {
+                    check_imports(&module, &manifest.capabilities).map_err(|e| e.to_string())?;
+                    Ok(module)
+                });
+            match &module {
+                Ok(_) => info!("Loaded plugin '{}' ({:?})", manifest.id, manifest.kind),
+                Err(e) => warn!("Failed to load plugin '{}': {}", manifest.id, e),
+            }
+            plugins.push(LoadedPlugin {
+                data_dir: dir.join("data"),
+                manifest,
+                module,
+            });
+        }
+
+        plugins.sort_by(|a, b| a.manifest.id.cmp(&b.manifest.id));
+        *self.plugins.lock().unwrap() = plugins;
+    }
+
+    pub fn list(&self) -> Vec<PluginInfo> {
+        let enabled = get_settings(&self.app_handle).enabled_plugins;
+        self.plugins
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|plugin| PluginInfo {
+                id: plugin.manifest.id.clone(),
+                name: plugin.manifest.name.clone(),
+                version: plugin.manifest.version.clone(),
+                description: plugin.manifest.description.clone(),
+                kind: plugin.manifest.kind,
+                capabilities: plugin.manifest.capabilities.clone(),
+                enabled: enabled.contains(&plugin.manifest.id),
+                error: plugin.module.as_ref().err().cloned(),
+            })
+            .collect()
+    }
+
+    /// Runs the transcript through every enabled transform plugin in turn.
+    /// A failing plugin is skipped and the text it was given is kept.
+    pub fn transform(&self, text: String) -> String {
+        self.enabled_of_kind(PluginKind::Transform)
+            .into_iter()
+            .fold(text, |text, plugin| {
+                match self.call(&plugin, "process", &text) {
+                    Ok(output) => output,
+                    Err(e) => {
+                        warn!("Plugin '{}' failed: {:#}", plugin.id, e);
+                        text
+                    }
+                }
+            })
+    }
+
+    /// Offers the transcript to each enabled output plugin. Returns true once
+    /// one of them has handled it, in which case it shouldn't be pasted.
+    pub fn route(&self, text: &str) -> bool {
+        self.enabled_of_kind(PluginKind::Output)
+            .into_iter()
+            .any(|plugin| match self.call(&plugin, "route", text) {
+                Ok(handled) => {
+                    debug!("Plugin '{}' handled the transcript: {}", plugin.id, handled);
+                    handled
+                }
+                Err(e) => {
+                    warn!("Plugin '{}' failed: {:#}", plugin.id, e);
+                    false
+                }
+            })
+    }
+
+    fn enabled_of_kind(&self, kind: PluginKind) -> Vec<PluginHandle> {
+        let enabled = get_settings(&self.app_handle).enabled_plugins;
+        if enabled.is_empty() {
+            return Vec::new();
+        }
+        self.plugins
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|p| p.manifest.kind == kind && enabled.contains(&p.manifest.id))
+            .filter_map(|p| {
+                Some(PluginHandle {
+                    id: p.manifest.id.clone(),
+                    capabilities: p.manifest.capabilities.clone(),
+                    data_dir: p.data_dir.clone(),
+                    module: p.module.as_ref().ok()?.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn call<R: HookResult>(&self, plugin: &PluginHandle, hook: &str, input: &str) -> Result<R> {
+        let state = HostState {
+            app_handle: self.app_handle.clone(),
+            plugin_id: plugin.id.clone(),
+            data_dir: plugin.data_dir.clone(),
+            limits: StoreLimitsBuilder::new()
+                .memory_size(MAX_MEMORY_BYTES)
+                .build(),
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(FUEL_PER_CALL)?;
+
+        let linker = host_linker(&self.engine, &plugin.capabilities)?;
+        let instance = linker
+            .instantiate(&mut store, &plugin.module)?
+            .start(&mut store)?;
+
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| anyhow!("Plugin doesn't export its memory"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "alloc")?;
+
+        let len = i32::try_from(input.len())?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory
+            .write(&mut store, ptr as usize, input.as_bytes())
+            .map_err(|e| anyhow!("Failed to pass input to plugin: {}", e))?;
+
+        let func = instance.get_typed_func::<(i32, i32), R::Raw>(&store, hook)?;
+        let raw = func.call(&mut store, (ptr, len))?;
+        R::read(raw, &memory, &store)
+    }
+}
+
+/// The parts of a loaded plugin needed to run it, copied out so no lock is
+/// held while plugin code runs.
+struct PluginHandle {
+    id: String,
+    capabilities: Vec<PluginCapability>,
+    data_dir: PathBuf,
+    module: Module,
+}
+
+/// Turns a hook's raw return value into its result.
+trait HookResult: Sized {
+    type Raw: wasmi::WasmResults;
+    fn read(raw: Self::Raw, memory: &wasmi::Memory, store: &Store<HostState>) -> Result<Self>;
+}
+
+impl HookResult for String {
+    type Raw = i64;
+
+    fn read(raw: i64, memory: &wasmi::Memory, store: &Store<HostState>) -> Result<Self> {
+        let (ptr, len) = unpack(raw);
+        let mut buf = vec![0u8; len];
+        memory
+            .read(store, ptr, &mut buf)
+            .map_err(|e| anyhow!("Plugin returned an invalid output range: {}", e))?;
+        String::from_utf8(buf).context("Plugin returned invalid UTF-8")
+    }
+}
+
+impl HookResult for bool {
+    type Raw = i32;
+
+    fn read(raw: i32, _memory: &wasmi::Memory, _store: &Store<HostState>) -> Result<Self> {
+        Ok(raw == 1)
+    }
+}
+
+/// Splits a hook's packed `(ptr << 32) | len` return value.
+fn unpack(packed: i64) -> (usize, usize) {
+    let packed = packed as u64;
+    ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize)
+}
+
+fn read_manifest(dir: &Path) -> Result<PluginManifest> {
+    let contents = fs::read_to_string(dir.join(MANIFEST_FILE))
+        .with_context(|| format!("Missing {}", MANIFEST_FILE))?;
+    let manifest: PluginManifest =
+        serde_json::from_str(&contents).with_context(|| format!("Invalid {}", MANIFEST_FILE))?;
+    if manifest.id.is_empty() {
+        return Err(anyhow!("Manifest has an empty id"));
+    }
+    Ok(manifest)
+}
+
+/// Fails unless everything `module` imports is a `handy` host function that
+/// one of `capabilities` provides, so an undeclared import is reported when
+/// the plugin loads rather than on every call.
+fn check_imports(module: &Module, capabilities: &[PluginCapability]) -> Result<()> {
+    for import in module.imports() {
+        let needed = PluginCapability::ALL
+            .into_iter()
+            .find(|c| import.module() == HOST_MODULE && c.host_function() == import.name())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Plugin imports {}::{}, which Handy doesn't provide",
+                    import.module(),
+                    import.name()
+                )
+            })?;
+        if !capabilities.contains(&needed) {
+            return Err(anyhow!(
+                "Plugin imports {}::{} without declaring the {} capability",
+                import.module(),
+                import.name(),
+                serde_json::to_string(&needed)?
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Links only the host functions covered by the plugin's capabilities.
+fn host_linker(engine: &Engine, capabilities: &[PluginCapability]) -> Result<Linker<HostState>> {
+    let mut linker = Linker::<HostState>::new(engine);
+
+    for capability in capabilities {
+        match capability {
+            PluginCapability::Log => {
+                linker.func_wrap(
+                    HOST_MODULE,
+                    capability.host_function(),
+                    |caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+                        if let Some(message) = read_guest_str(&caller, ptr, len) {
+                            info!("[plugin {}] {}", caller.data().plugin_id, message);
+                        }
+                    },
+                )?;
+            }
+            PluginCapability::Clipboard => {
+                linker.func_wrap(
+                    HOST_MODULE,
+                    capability.host_function(),
+                    |caller: Caller<'_, HostState>, ptr: i32, len: i32| -> i32 {
+                        let Some(text) = read_guest_str(&caller, ptr, len) else {
+                            return -1;
+                        };
+                        match caller.data().app_handle.clipboard().write_text(text) {
+                            Ok(()) => 0,
+                            Err(_) => -1,
+                        }
+                    },
+                )?;
+            }
+            PluginCapability::Files => {
+                linker.func_wrap(
+                    HOST_MODULE,
+                    capability.host_function(),
+                    |caller: Caller<'_, HostState>,
+                     name_ptr: i32,
+                     name_len: i32,
+                     ptr: i32,
+                     len: i32|
+                     -> i32 {
+                        let (Some(name), Some(text)) = (
+                            read_guest_str(&caller, name_ptr, name_len),
+                            read_guest_str(&caller, ptr, len),
+                        ) else {
+                            return -1;
+                        };
+                        match append_plugin_file(&caller.data().data_dir, &name, &text) {
+                            Ok(()) => 0,
+                            Err(e) => {
+                                warn!(
+                                    "[plugin {}] append_file failed: {}",
+                                    caller.data().plugin_id,
+                                    e
+                                );
+                                -1
+                            }
+                        }
+                    },
+                )?;
+            }
+        }
+    }
+
+    Ok(linker)
+}
+
+fn read_guest_str(caller: &Caller<'_, HostState>, ptr: i32, len: i32) -> Option<String> {
+    let memory = caller.get_export("memory")?.into_memory()?;
+    let mut buf = vec![0u8; usize::try_from(len).ok()?];
+    memory
+        .read(caller, usize::try_from(ptr).ok()?, &mut buf)
+        .ok()?;
+    String::from_utf8(buf).ok()
+}
+
+/// Appends to a file in the plugin's data folder. Only plain file names are
+/// accepted so a plugin can't reach outside its folder.
+fn append_plugin_file(data_dir: &Path, name: &str, text: &str) -> Result<()> {
+    if !is_plain_file_name(name) {
+        return Err(anyhow!("Invalid file name: {}", name));
+    }
+    fs::create_dir_all(data_dir)?;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(data_dir.join(name))?;
+    file.write_all(text.as_bytes())?;
+    Ok(())
+}
+
+fn is_plain_file_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\', ':', '\0'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpacks_pointer_and_length() {
+        assert_eq!(unpack((16 << 32) | 5), (16, 5));
+        assert_eq!(
+            unpack(((0xffff_fff0u64 << 32) | 3) as i64),
+            (0xffff_fff0, 3)
+        );
+    }
+
+    /// A module importing one `(i32, i32)` function as `module::name`
+    fn importing(module: &str, name: &str) -> Module {
+        let mut import = vec![1, module.len() as u8];
+        import.extend(module.as_bytes());
+        import.push(name.len() as u8);
+        import.extend(name.as_bytes());
+        import.extend([0, 0]);
+        let mut bytes = vec![
+            0, b'a', b's', b'm', 1, 0, 0, 0, 1, 6, 1, 0x60, 2, 0x7f, 0x7f, 0,
+        ];
+        bytes.extend([2, import.len() as u8]);
+        bytes.extend(import);
+        Module::new(&Engine::default(), &bytes[..]).unwrap()
+    }
+
+    #[test]
+    fn checks_imports_against_capabilities() {
+        let log = importing("handy", "log");
+        assert!(check_imports(&log, &[PluginCapability::Log]).is_ok());
+        assert!(check_imports(&log, &[PluginCapability::Files]).is_err());
+        assert!(check_imports(&importing("handy", "exec"), &PluginCapability::ALL).is_err());
+        assert!(check_imports(&importing("env", "log"), &PluginCapability::ALL).is_err());
+    }
+
+    #[test]
+    fn rejects_paths_outside_the_data_folder() {
+        assert!(is_plain_file_name("notes.md"));
+        assert!(!is_plain_file_name(""));
+        assert!(!is_plain_file_name(".."));
+        assert!(!is_plain_file_name("../settings.json"));
+        assert!(!is_plain_file_name("sub\\file.txt"));
+        assert!(!is_plain_file_name("C:file.txt"));
+    }
+
+    #[test]
+    fn parses_manifest_defaults() {
+        let manifest: PluginManifest =
+            serde_json::from_str(r#"{"id": "shout", "name": "Shout", "kind": "transform"}"#)
+                .unwrap();
+        assert_eq!(manifest.entry, "plugin.wasm");
+        assert!(manifest.capabilities.is_empty());
+    }
+}
@@ -1,24 +1,73 @@
-use crate::audio_toolkit::apply_custom_words;
-use crate::managers::model::{EngineType, ModelManager};
-use crate::settings::{get_settings, ModelUnloadTimeout};
+use crate::helpers::idle;
+use crate::language_lock::LanguageLock;
+use crate::managers::batch::{self, BatchJob, BatchJobResult, BatchQueue};
+use crate::managers::checkpoint::Checkpoint;
+use crate::managers::file_cache::{self, FileCache};
+use crate::managers::history::{DictationContext, HistoryManager};
+use crate::managers::model::{EngineType, ModelInfo, ModelManager, PUNCTUATION_MODEL_ID};
+use crate::managers::pending::{PendingQueue, PendingTranscription};
+use crate::memory_budget;
+use crate::network;
+use crate::notifications;
+use crate::power;
+use crate::preprocessing;
+use crate::punctuation::{needs_restoration, PunctuationModel};
+use crate::punctuation_style;
+use crate::resource_usage::{ResourceUsage, UsageMonitor};
+use crate::settings::{get_settings, AppSettings, ModelUnloadTimeout, TranscriptionContext};
+use crate::spelling;
+use crate::text_rules;
+use crate::transcription_provider::{self, Job, TimedTranscript};
 use anyhow::Result;
+use chrono::Timelike;
+use handy_transcription::local::{DecodeOptions, LocalEngine, ModelKind};
+use handy_transcription::timing::{self, TimedSegment};
+use handy_transcription::{
+    apply_custom_words, apply_replacements, compress_silence, constants, context_tail, is_silent,
+    split_on_silence, suppress_hallucinations, vocabulary_prompt, Engine, Request, SilenceMap,
+};
 use log::{debug, error, info, warn};
 use serde::Serialize;
+use std::future::Future;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Condvar, Mutex};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
 use std::thread;
-use std::time::{Duration, SystemTime};
-use tauri::{AppHandle, Emitter};
-use transcribe_rs::{
-    engines::{
-        moonshine::{ModelVariant, MoonshineEngine, MoonshineModelParams},
-        parakeet::{
-            ParakeetEngine, ParakeetInferenceParams, ParakeetModelParams, TimestampGranularity,
-        },
-        whisper::{WhisperEngine, WhisperInferenceParams},
-    },
-    TranscriptionEngine,
-};
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use transcribe_rs::{TranscriptionResult, TranscriptionSegment};
+
+// Whisper needs a couple of seconds of audio for reliable language detection
+const CODE_SWITCH_MIN_SEGMENT_MS: usize = 2000;
+const CODE_SWITCH_MAX_SEGMENT_MS: usize = 30000;
+/// Recordings longer than this (two minutes at 16kHz) are long jobs. Remote
+/// ones go in segments that can be checkpointed, local ones only with
+/// `segment_long_recordings`, as splitting costs accuracy at the cuts and
+/// times each segment as a whole.
+const LONG_JOB_SAMPLES: usize = 16000 * 120;
+const LONG_JOB_MIN_SEGMENT_MS: usize = 10000;
+const LONG_JOB_MAX_SEGMENT_MS: usize = 30000;
+/// With `compress_silence`, pauses in long jobs longer than this are cut
+/// down to `SILENCE_KEEP_MS` before inference
+const SILENCE_SKIP_MIN_MS: usize = 3000;
+const SILENCE_KEEP_MS: usize = 1000;
+/// Attempts per segment of a long remote job before it's left for a retry
+const REMOTE_SEGMENT_ATTEMPTS: u32 = 3;
+const REMOTE_RETRY_DELAY: Duration = Duration::from_secs(2);
+/// How often the batch scheduler checks whether queued files may run
+const BATCH_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Failed attempts after which a kept dictation is only sent again by hand
+const PENDING_MAX_ATTEMPTS: u32 = 3;
+/// Whisper only looks at the last 224 prompt tokens, these two together stay
+/// under that
+const CONTEXT_PROMPT_CHARS: usize = 400;
+const VOCABULARY_PROMPT_CHARS: usize = 300;
+/// Recordings shorter than this are too short to judge by their transcript length
+const RETRANSCRIBE_MIN_SAMPLES: usize = 16000 * 4;
+/// Normal speech produces well over ten characters a second in alphabetic
+/// scripts and around four in Chinese or Japanese
+const RETRANSCRIBE_MIN_CHARS_PER_SECOND: f32 = 2.0;
 
 #[derive(Clone, Debug, Serialize)]
 pub struct ModelStateEvent {
@@ -28,28 +77,112 @@ pub struct ModelStateEvent {
     pub error: Option<String>,
 }
 
-enum LoadedEngine {
-    Whisper(WhisperEngine),
-    Parakeet(ParakeetEngine),
-    Moonshine(MoonshineEngine),
+#[derive(Clone, Debug, Serialize)]
+pub struct TranscriptionProgress {
+    pub completed: usize,
+    pub total: usize,
+    pub percent: f32,
+    /// Chunk being worked on, counting from 1
+    pub current_chunk: usize,
+    /// Estimated seconds left, once a chunk has been timed
+    pub eta_secs: Option<f32>,
+    /// File being transcribed, for file jobs
+    pub task: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TranscriptionRetry {
+    pub retry: u32,
+    pub max_retries: u32,
+    pub delay_secs: f32,
+}
+
+/// A transcript and what it took to make it. The rest is `None` if no
+/// engine ran, for empty or silent audio.
+#[derive(Clone, Debug, Default)]
+pub struct Transcription {
+    pub text: String,
+    pub usage: Option<ResourceUsage>,
+    /// Model ID or provider name that did the transcription
+    pub engine: Option<String>,
+    /// Timing of the transcript, when the engine gives it
+    pub segments: Option<Vec<TimedSegment>>,
+}
+
+/// The idle watcher and batch scheduler threads. Only the manager returned
+/// by `new` holds their handles: clones get an empty set, so the clones handed
+/// to load threads, captions or the API server can be dropped without
+/// stopping them.
+struct Workers {
+    shutdown_signal: Arc<AtomicBool>,
+    handles: Vec<(&'static str, thread::JoinHandle<()>)>,
+}
+
+impl Clone for Workers {
+    fn clone(&self) -> Self {
+        Self {
+            shutdown_signal: self.shutdown_signal.clone(),
+            handles: Vec::new(),
+        }
+    }
+}
+
+impl Drop for Workers {
+    fn drop(&mut self) {
+        if self.handles.is_empty() {
+            return;
+        }
+        debug!("Shutting down TranscriptionManager");
+
+        // Signal the threads to shutdown and wait for them to finish
+        self.shutdown_signal.store(true, Ordering::Relaxed);
+        for (name, handle) in self.handles.drain(..) {
+            if let Err(e) = handle.join() {
+                warn!("Failed to join {} thread: {:?}", name, e);
+            } else {
+                debug!("{} thread joined successfully", name);
+            }
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct TranscriptionManager {
-    engine: Arc<Mutex<Option<LoadedEngine>>>,
+    engine: Arc<Mutex<Option<LocalEngine>>>,
     model_manager: Arc<ModelManager>,
     app_handle: AppHandle,
     current_model_id: Arc<Mutex<Option<String>>>,
     last_activity: Arc<AtomicU64>,
     shutdown_signal: Arc<AtomicBool>,
-    watcher_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
     is_loading: Arc<Mutex<bool>>,
     loading_condvar: Arc<Condvar>,
+    language_lock: Arc<LanguageLock>,
+    last_transcript: Arc<Mutex<Option<String>>>,
+    /// Model for `retranscribe_model`, keyed by its ID
+    fallback_engine: Arc<Mutex<Option<(String, LocalEngine)>>>,
+    punctuation_model: Arc<Mutex<Option<PunctuationModel>>>,
+    batch_queue: Arc<BatchQueue>,
+    file_cache: Arc<FileCache>,
+    batch_running: Arc<AtomicBool>,
+    pending_queue: Arc<PendingQueue>,
+    pending_running: Arc<AtomicBool>,
+    workers: Workers,
+    /// When the running job in parts started and how many parts it had
+    /// done by then, for the ETA
+    progress_start: Arc<Mutex<Option<(Instant, usize)>>>,
+    progress_task: Arc<Mutex<Option<String>>>,
 }
 
 impl TranscriptionManager {
     pub fn new(app_handle: &AppHandle, model_manager: Arc<ModelManager>) -> Result<Self> {
-        let manager = Self {
+        let app_data_dir = app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?;
+        let batch_file = app_data_dir.join("batch_jobs.json");
+
+        let shutdown_signal = Arc::new(AtomicBool::new(false));
+        let mut manager = Self {
             engine: Arc::new(Mutex::new(None)),
             model_manager,
             app_handle: app_handle.clone(),
@@ -60,10 +193,24 @@ impl TranscriptionManager {
                     .unwrap()
                     .as_millis() as u64,
             )),
-            shutdown_signal: Arc::new(AtomicBool::new(false)),
-            watcher_handle: Arc::new(Mutex::new(None)),
+            shutdown_signal: shutdown_signal.clone(),
             is_loading: Arc::new(Mutex::new(false)),
             loading_condvar: Arc::new(Condvar::new()),
+            language_lock: Arc::new(LanguageLock::default()),
+            last_transcript: Arc::new(Mutex::new(None)),
+            fallback_engine: Arc::new(Mutex::new(None)),
+            punctuation_model: Arc::new(Mutex::new(None)),
+            batch_queue: Arc::new(BatchQueue::load(batch_file)),
+            file_cache: Arc::new(FileCache::load(app_data_dir.join("file_cache.json"))),
+            batch_running: Arc::new(AtomicBool::new(false)),
+            pending_queue: Arc::new(PendingQueue::load(app_data_dir.join("pending"))),
+            pending_running: Arc::new(AtomicBool::new(false)),
+            workers: Workers {
+                shutdown_signal,
+                handles: Vec::new(),
+            },
+            progress_start: Arc::new(Mutex::new(None)),
+            progress_task: Arc::new(Mutex::new(None)),
         };
 
         // Start the idle watcher
@@ -81,11 +228,12 @@ impl TranscriptionManager {
                     }
 
                     let settings = get_settings(&app_handle_cloned);
-                    let timeout_seconds = settings.model_unload_timeout.to_seconds();
+                    let unload_timeout = power::effective_unload_timeout(&settings);
+                    let timeout_seconds = unload_timeout.to_seconds();
 
                     if let Some(limit_seconds) = timeout_seconds {
                         // Skip polling-based unloading for immediate timeout since it's handled directly in transcribe()
-                        if settings.model_unload_timeout == ModelUnloadTimeout::Immediately {
+                        if unload_timeout == ModelUnloadTimeout::Immediately {
                             continue;
                         }
 
@@ -116,6 +264,7 @@ impl TranscriptionManager {
                                         "Model unloaded due to inactivity (took {}ms)",
                                         unload_duration.as_millis()
                                     );
+                                    notifications::notify_model_unloaded(&app_handle_cloned);
                                 }
                             }
                         }
@@ -123,7 +272,39 @@ impl TranscriptionManager {
                 }
                 debug!("Idle watcher thread shutting down gracefully");
             });
-            *manager.watcher_handle.lock().unwrap() = Some(handle);
+            manager.workers.handles.push(("Idle watcher", handle));
+        }
+
+        // Start the batch scheduler
+        {
+            let manager_cloned = manager.clone();
+            let shutdown_signal = manager.shutdown_signal.clone();
+            let handle = thread::spawn(move || {
+                let mut waited = Duration::ZERO;
+                while !shutdown_signal.load(Ordering::Relaxed) {
+                    // Sleep in short steps so shutdown isn't held up
+                    thread::sleep(Duration::from_secs(1));
+                    waited += Duration::from_secs(1);
+                    if waited < BATCH_CHECK_INTERVAL {
+                        continue;
+                    }
+                    waited = Duration::ZERO;
+
+                    if !manager_cloned.batch_queue.is_empty() && manager_cloned.batch_may_run() {
+                        manager_cloned.run_batch_jobs(false);
+                    }
+                    // Only worth trying if the engine is back, the network
+                    // monitor may have missed it coming back
+                    if !manager_cloned.pending_queue.is_empty()
+                        && (network::remote_reachable()
+                            || network::recheck(&manager_cloned.app_handle))
+                    {
+                        manager_cloned.run_pending(false);
+                    }
+                }
+                debug!("Batch scheduler thread shutting down gracefully");
+            });
+            manager.workers.handles.push(("Batch scheduler", handle));
         }
 
         Ok(manager)
@@ -141,14 +322,12 @@ impl TranscriptionManager {
         {
             let mut engine = self.engine.lock().unwrap();
             if let Some(ref mut loaded_engine) = *engine {
-                match loaded_engine {
-                    LoadedEngine::Whisper(ref mut e) => e.unload_model(),
-                    LoadedEngine::Parakeet(ref mut e) => e.unload_model(),
-                    LoadedEngine::Moonshine(ref mut e) => e.unload_model(),
-                }
+                loaded_engine.unload();
             }
             *engine = None; // Drop the engine to free memory
         }
+        self.unload_fallback_model();
+        *self.punctuation_model.lock().unwrap() = None;
         {
             let mut current_model = self.current_model_id.lock().unwrap();
             *current_model = None;
@@ -176,7 +355,7 @@ impl TranscriptionManager {
     /// Unloads the model immediately if the setting is enabled and the model is loaded
     pub fn maybe_unload_immediately(&self, context: &str) {
         let settings = get_settings(&self.app_handle);
-        if settings.model_unload_timeout == ModelUnloadTimeout::Immediately
+        if power::effective_unload_timeout(&settings) == ModelUnloadTimeout::Immediately
             && self.is_model_loaded()
         {
             info!("Immediately unloading model after {}", context);
@@ -220,71 +399,30 @@ impl TranscriptionManager {
             return Err(anyhow::anyhow!(error_msg));
         }
 
+        // Something smaller may have to do in the memory budget
+        let model_info = match get_settings(&self.app_handle).model_memory_budget_mb {
+            Some(budget_mb) => self.within_budget(model_info, budget_mb)?,
+            None => model_info,
+        };
+        let model_id = model_info.id.as_str();
+
         let model_path = self.model_manager.get_model_path(model_id)?;
 
         // Create appropriate engine based on model type
-        let loaded_engine = match model_info.engine_type {
-            EngineType::Whisper => {
-                let mut engine = WhisperEngine::new();
-                engine.load_model(&model_path).map_err(|e| {
-                    let error_msg = format!("Failed to load whisper model {}: {}", model_id, e);
-                    let _ = self.app_handle.emit(
-                        "model-state-changed",
-                        ModelStateEvent {
-                            event_type: "loading_failed".to_string(),
-                            model_id: Some(model_id.to_string()),
-                            model_name: Some(model_info.name.clone()),
-                            error: Some(error_msg.clone()),
-                        },
-                    );
-                    anyhow::anyhow!(error_msg)
-                })?;
-                LoadedEngine::Whisper(engine)
-            }
-            EngineType::Parakeet => {
-                let mut engine = ParakeetEngine::new();
-                engine
-                    .load_model_with_params(&model_path, ParakeetModelParams::int8())
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load parakeet model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::Parakeet(engine)
-            }
-            EngineType::Moonshine => {
-                let mut engine = MoonshineEngine::new();
-                engine
-                    .load_model_with_params(
-                        &model_path,
-                        MoonshineModelParams::variant(ModelVariant::Base),
-                    )
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load moonshine model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::Moonshine(engine)
-            }
-        };
+        let loaded_engine = open_engine(&model_info.engine_type, &model_path).map_err(|e| {
+            let engine_name = format!("{:?}", model_info.engine_type).to_lowercase();
+            let error_msg = format!("Failed to load {} model {}: {}", engine_name, model_id, e);
+            let _ = self.app_handle.emit(
+                "model-state-changed",
+                ModelStateEvent {
+                    event_type: "loading_failed".to_string(),
+                    model_id: Some(model_id.to_string()),
+                    model_name: Some(model_info.name.clone()),
+                    error: Some(error_msg.clone()),
+                },
+            );
+            anyhow::anyhow!(error_msg)
+        })?;
 
         // Update the current engine and model ID
         {
@@ -316,8 +454,52 @@ impl TranscriptionManager {
         Ok(())
     }
 
+    /// `model_info`, or the model to load instead to stay within `budget_mb`.
+    /// Fails, telling the frontend, when no downloaded model fits.
+    fn within_budget(&self, model_info: ModelInfo, budget_mb: u64) -> Result<ModelInfo> {
+        let models = self.model_manager.get_available_models();
+        match memory_budget::pick(&model_info, &models, budget_mb).cloned() {
+            Some(picked) if picked.id == model_info.id => Ok(picked),
+            Some(picked) => {
+                info!(
+                    "{} needs about {} MB, more than the {} MB budget, loading {} instead",
+                    model_info.name,
+                    memory_budget::estimated_mb(&model_info),
+                    budget_mb,
+                    picked.name
+                );
+                Ok(picked)
+            }
+            None => {
+                let error_msg = format!(
+                    "{} needs about {} MB and no downloaded model fits in the {} MB memory budget",
+                    model_info.name,
+                    memory_budget::estimated_mb(&model_info),
+                    budget_mb
+                );
+                let _ = self.app_handle.emit(
+                    "model-state-changed",
+                    ModelStateEvent {
+                        event_type: "loading_failed".to_string(),
+                        model_id: Some(model_info.id.clone()),
+                        model_name: Some(model_info.name.clone()),
+                        error: Some(error_msg.clone()),
+                    },
+                );
+                Err(anyhow::anyhow!(error_msg))
+            }
+        }
+    }
+
     /// Kicks off the model loading in a background thread if it's not already loaded
     pub fn initiate_model_load(&self) {
+        // Transcription happens remotely, no local model needed, unless
+        // adaptive mode keeps short recordings local
+        let settings = get_settings(&self.app_handle);
+        if settings.remote_enabled() && !settings.adaptive_engine {
+            return;
+        }
+
         let mut is_loading = self.is_loading.lock().unwrap();
         if *is_loading || self.is_model_loaded() {
             return;
@@ -327,7 +509,8 @@ impl TranscriptionManager {
         let self_clone = self.clone();
         thread::spawn(move || {
             let settings = get_settings(&self_clone.app_handle);
-            if let Err(e) = self_clone.load_model(&settings.selected_model) {
+            let model_id = power::effective_model_id(&self_clone.app_handle, &settings);
+            if let Err(e) = self_clone.load_model(&model_id) {
                 error!("Failed to load model: {}", e);
             }
             let mut is_loading = self_clone.is_loading.lock().unwrap();
@@ -336,12 +519,361 @@ impl TranscriptionManager {
         });
     }
 
+    /// Frees the model used for re-transcription. A re-transcription that ran
+    /// past its latency cap may still be using it, then it's freed on the next
+    /// unload instead.
+    pub fn unload_fallback_model(&self) {
+        if let Ok(mut fallback) = self.fallback_engine.try_lock() {
+            *fallback = None;
+        }
+    }
+
+    /// Forgets the language locked in by auto-detection so the next
+    /// utterance is detected again.
+    pub fn clear_language_lock(&self) {
+        self.language_lock.clear();
+    }
+
     pub fn get_current_model(&self) -> Option<String> {
         let current_model = self.current_model_id.lock().unwrap();
         current_model.clone()
     }
 
+    /// Transcribes an audio or video file and saves the result to history.
+    /// Telephone recordings are cleaned up for their narrow band first. A file
+    /// that was transcribed before with the same engine and settings is
+    /// answered from the cache, without another history entry.
+    pub fn transcribe_file(&self, path: &Path) -> Result<String> {
+        let settings = get_settings(&self.app_handle);
+        let cache_key = (settings.file_cache_limit > 0)
+            .then(|| std::fs::read(path))
+            .transpose()?
+            .map(|contents| {
+                file_cache::key(
+                    &contents,
+                    &cache_engine(&settings),
+                    &cache_settings(&settings),
+                )
+            });
+        if let Some(text) = cache_key
+            .as_deref()
+            .and_then(|key| self.file_cache.get(key))
+        {
+            info!(
+                "Using cached transcript of {:?} ({} chars)",
+                path,
+                text.len()
+            );
+            return Ok(text);
+        }
+
+        let (samples, source_rate) = handy_transcription::audio::read_audio_file_with_rate(path)?;
+        let audio = if source_rate <= preprocessing::NARROWBAND_MAX_HZ {
+            info!(
+                "{:?} is narrowband ({}Hz), using the telephone profile",
+                path, source_rate
+            );
+            preprocessing::narrowband(samples.clone())
+        } else {
+            samples.clone()
+        };
+        self.initiate_model_load();
+        *self.progress_task.lock().unwrap() = Some(path.to_string_lossy().into_owned());
+        let result = self.transcribe_with_settings(audio, settings.clone());
+        *self.progress_task.lock().unwrap() = None;
+        let Transcription {
+            text,
+            usage,
+            engine,
+            segments,
+        } = result?;
+
+        let hm = Arc::clone(&self.app_handle.state::<Arc<HistoryManager>>());
+        tauri::async_runtime::block_on(hm.save_transcription(
+            samples,
+            text.clone(),
+            None,
+            None,
+            DictationContext {
+                resource_usage: usage,
+                engine,
+                segments,
+                ..Default::default()
+            },
+        ))?;
+        if let Some(key) = cache_key {
+            self.file_cache
+                .insert(key, text.clone(), settings.file_cache_limit);
+        }
+        info!("Transcribed {:?} ({} chars)", path, text.len());
+        Ok(text)
+    }
+
+    pub fn file_cache_count(&self) -> usize {
+        self.file_cache.count()
+    }
+
+    pub fn clear_file_cache(&self) {
+        self.file_cache.clear();
+        info!("Cleared the file transcription cache");
+    }
+
+    pub fn trim_file_cache(&self, limit: usize) {
+        self.file_cache.trim(limit);
+    }
+
+    pub fn batch_jobs(&self) -> Vec<BatchJob> {
+        self.batch_queue.jobs()
+    }
+
+    /// Queues a file to be transcribed once the batch schedule allows it.
+    pub fn queue_file(&self, path: String) -> Result<()> {
+        if !Path::new(&path).is_file() {
+            return Err(anyhow::anyhow!("File not found: {}", path));
+        }
+        if self.batch_queue.push(path.clone()) {
+            info!("Queued {} for batch transcription", path);
+            let _ = self
+                .app_handle
+                .emit("batch-jobs-changed", self.batch_jobs());
+        }
+        Ok(())
+    }
+
+    pub fn remove_batch_job(&self, path: &str) {
+        if self.batch_queue.remove(path) {
+            let _ = self
+                .app_handle
+                .emit("batch-jobs-changed", self.batch_jobs());
+        }
+    }
+
+    fn batch_may_run(&self) -> bool {
+        let settings = get_settings(&self.app_handle);
+        let now = chrono::Local::now();
+        batch::schedule_allows(
+            &settings,
+            now.hour() * 60 + now.minute(),
+            power::is_on_battery(),
+            idle::system_idle_time(),
+        )
+    }
+
+    /// Works through the batch queue, rechecking the schedule before every
+    /// file unless `force` is set. Returns how many files were processed;
+    /// does nothing if a run is already in progress.
+    pub fn run_batch_jobs(&self, force: bool) -> usize {
+        if self.batch_running.swap(true, Ordering::SeqCst) {
+            return 0;
+        }
+
+        let mut processed = 0;
+        while let Some(job) = self.batch_queue.front() {
+            if self.shutdown_signal.load(Ordering::Relaxed) || !(force || self.batch_may_run()) {
+                break;
+            }
+
+            let result = match self.transcribe_file(Path::new(&job.path)) {
+                Ok(text) => BatchJobResult {
+                    path: job.path.clone(),
+                    text: Some(text),
+                    error: None,
+                },
+                Err(e) if transcription_provider::is_unreachable(&e) => {
+                    // The job stays queued and resumes from its checkpoint
+                    // on the next run
+                    warn!("Engine unreachable for batch job {}: {}", job.path, e);
+                    network::mark_unreachable();
+                    break;
+                }
+                Err(e) => {
+                    error!("Batch transcription of {} failed: {}", job.path, e);
+                    BatchJobResult {
+                        path: job.path.clone(),
+                        text: None,
+                        error: Some(e.to_string()),
+                    }
+                }
+            };
+            // Other failures, like an unreadable file, are reported and dropped,
+            // retrying them wouldn't go any better
+            self.batch_queue.remove(&job.path);
+            processed += 1;
+            let _ = self.app_handle.emit("batch-job-completed", result);
+            let _ = self
+                .app_handle
+                .emit("batch-jobs-changed", self.batch_jobs());
+        }
+
+        self.batch_running.store(false, Ordering::SeqCst);
+        processed
+    }
+
+    pub fn pending_transcriptions(&self) -> Vec<PendingTranscription> {
+        self.pending_queue.jobs()
+    }
+
+    /// Keeps a dictation the remote engine couldn't be reached for, to be
+    /// transcribed with `settings` once it can.
+    pub fn queue_pending(
+        &self,
+        samples: &[f32],
+        settings: &AppSettings,
+        target_app: Option<String>,
+    ) -> Result<()> {
+        let job = self.pending_queue.push(samples, settings, target_app)?;
+        info!(
+            "Kept {:.1}s of audio as {} until the engine is reachable",
+            job.duration_secs, job.id
+        );
+        self.emit_pending_changed();
+        Ok(())
+    }
+
+    pub fn remove_pending(&self, id: &str) {
+        if self.pending_queue.remove(id) {
+            self.emit_pending_changed();
+        }
+    }
+
+    fn emit_pending_changed(&self) {
+        let _ = self.app_handle.emit(
+            "pending-transcriptions-changed",
+            self.pending_transcriptions(),
+        );
+    }
+
+    /// Sends the pending dictations again, oldest first, stopping if the
+    /// engine turns out to be unreachable. One that fails for another reason
+    /// keeps its error and is skipped, and after `PENDING_MAX_ATTEMPTS` such
+    /// failures only `force` sends it again. Finished ones are saved to
+    /// history and offered for pasting. Returns how many were transcribed.
+    /// Nothing is sent while the engine is known to be unreachable, the
+    /// local model would take them instead.
+    pub fn run_pending(&self, force: bool) -> usize {
+        if !network::remote_reachable() {
+            return 0;
+        }
+        if self.pending_running.swap(true, Ordering::SeqCst) {
+            return 0;
+        }
+
+        let mut transcribed = 0;
+        for job in self.pending_queue.jobs() {
+            if self.shutdown_signal.load(Ordering::Relaxed) {
+                break;
+            }
+            if !force && job.attempts >= PENDING_MAX_ATTEMPTS {
+                continue;
+            }
+            let result = self
+                .pending_queue
+                .read(&job.id)
+                .and_then(|(samples, settings)| {
+                    let transcription =
+                        self.transcribe_with_settings(samples.clone(), settings.clone())?;
+                    Ok((samples, settings, transcription))
+                });
+            let (
+                samples,
+                settings,
+                Transcription {
+                    text,
+                    usage,
+                    engine,
+                    segments,
+                },
+            ) = match result {
+                Ok(done) => done,
+                Err(e) if transcription_provider::is_unreachable(&e) => {
+                    debug!("Engine still unreachable for {}: {}", job.id, e);
+                    network::mark_unreachable();
+                    break;
+                }
+                Err(e) => {
+                    warn!("Pending transcription {} failed: {}", job.id, e);
+                    self.pending_queue.set_error(&job.id, e.to_string());
+                    self.emit_pending_changed();
+                    continue;
+                }
+            };
+
+            let hm = Arc::clone(&self.app_handle.state::<Arc<HistoryManager>>());
+            let saved = tauri::async_runtime::block_on(hm.save_transcription(
+                samples,
+                text.clone(),
+                None,
+                None,
+                DictationContext {
+                    target_app: job.target_app.clone(),
+                    language: Some(settings.selected_language.clone()),
+                    resource_usage: usage,
+                    engine,
+                    segments,
+                    ..Default::default()
+                },
+            ));
+            if let Err(e) = saved {
+                error!("Failed to save pending transcription to history: {}", e);
+            }
+            info!("Transcribed pending {} ({} chars)", job.id, text.len());
+            self.remove_pending(&job.id);
+            if !text.is_empty() {
+                notifications::notify_pending_transcribed(&self.app_handle, text);
+            }
+            transcribed += 1;
+        }
+
+        self.pending_running.store(false, Ordering::SeqCst);
+        transcribed
+    }
+
     pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
+        self.transcribe_with_settings(audio, get_settings(&self.app_handle))
+            .map(|transcription| transcription.text)
+    }
+
+    /// Transcribes with `settings` rather than the current ones, for a
+    /// dictation recorded under earlier settings.
+    pub fn transcribe_with_settings(
+        &self,
+        audio: Vec<f32>,
+        settings: AppSettings,
+    ) -> Result<Transcription> {
+        self.transcribe_with_model(audio, settings, None)
+    }
+
+    /// Transcribes with `model_id` if given, for shortcuts set up with a
+    /// model of their own, and the engine the settings route to otherwise.
+    pub fn transcribe_with_model(
+        &self,
+        audio: Vec<f32>,
+        settings: AppSettings,
+        model_id: Option<String>,
+    ) -> Result<Transcription> {
+        self.transcribe_routed(audio, settings, model_id.map(EngineRoute::LocalModel))
+    }
+
+    /// Transcribes on this machine whatever the settings route to, with
+    /// `model_id` if given and the selected model otherwise.
+    pub fn transcribe_locally(
+        &self,
+        audio: Vec<f32>,
+        settings: AppSettings,
+        model_id: Option<String>,
+    ) -> Result<Transcription> {
+        let route = model_id.map_or(EngineRoute::Local, EngineRoute::LocalModel);
+        self.transcribe_routed(audio, settings, Some(route))
+    }
+
+    /// Transcribes with the engine `route` picks, or the one the settings
+    /// route to if it's `None`.
+    fn transcribe_routed(
+        &self,
+        audio: Vec<f32>,
+        mut settings: AppSettings,
+        route: Option<EngineRoute>,
+    ) -> Result<Transcription> {
         // Update last activity timestamp
         self.last_activity.store(
             SystemTime::now()
@@ -358,86 +890,191 @@ impl TranscriptionManager {
         if audio.is_empty() {
             debug!("Empty audio vector");
             self.maybe_unload_immediately("empty audio");
-            return Ok(String::new());
+            return Ok(Transcription::default());
         }
 
-        // Check if model is loaded, if not try to load it
-        {
-            // If the model is loading, wait for it to complete.
-            let mut is_loading = self.is_loading.lock().unwrap();
-            while *is_loading {
-                is_loading = self.loading_condvar.wait(is_loading).unwrap();
+        let audio = preprocessing::run(
+            &settings.preprocessing,
+            settings.noise_suppression_strength,
+            audio,
+        );
+
+        // Translated or code-switched output doesn't tell us the spoken language
+        let lock_timeout = Duration::from_secs(settings.language_lock_minutes as u64 * 60);
+        let lock_language = settings.selected_language == "auto"
+            && settings.language_lock_minutes > 0
+            && !settings.translate_to_english
+            && !settings.code_switching;
+        if lock_language {
+            if let Some(language) = self.language_lock.current(lock_timeout) {
+                debug!("Using locked language '{}'", language);
+                settings.selected_language = language;
             }
+        }
 
-            let engine_guard = self.engine.lock().unwrap();
-            if engine_guard.is_none() {
-                return Err(anyhow::anyhow!("Model is not loaded for transcription."));
+        let route = match route.unwrap_or_else(|| route_engine(&settings, audio.len())) {
+            EngineRoute::Remote if !network::remote_reachable() => self.offline_route(&settings)?,
+            route => route,
+        };
+        let route = match route {
+            EngineRoute::LocalModel(id) if self.get_current_model().as_deref() == Some(&id) => {
+                EngineRoute::Local
             }
+            route => route,
+        };
+        if settings.adaptive_engine {
+            debug!(
+                "Routing {:.1}s of audio to {:?}",
+                audio.len() as f32 / constants::WHISPER_SAMPLE_RATE as f32,
+                route
+            );
         }
 
-        // Get current settings for configuration
-        let settings = get_settings(&self.app_handle);
+        // Whisper tends to invent text for silence, so don't give it the chance
+        let routed_to_whisper = match &route {
+            EngineRoute::LocalModel(model_id) => self.is_whisper_model(model_id),
+            EngineRoute::Local => self.is_whisper_model(
+                &self
+                    .get_current_model()
+                    .unwrap_or_else(|| power::effective_model_id(&self.app_handle, &settings)),
+            ),
+            EngineRoute::Remote => false,
+        };
+        if settings.hallucination_filter && routed_to_whisper && is_silent(&audio) {
+            debug!("Skipping transcription of silent audio");
+            self.maybe_unload_immediately("silent audio");
+            return Ok(Transcription::default());
+        }
 
-        // Perform transcription with the appropriate engine
-        let result = {
-            let mut engine_guard = self.engine.lock().unwrap();
-            let engine = engine_guard.as_mut().ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Model failed to load after auto-load attempt. Please check your model settings."
-                )
-            })?;
-
-            match engine {
-                LoadedEngine::Whisper(whisper_engine) => {
-                    // Normalize language code for Whisper
-                    // Convert zh-Hans and zh-Hant to zh since Whisper uses ISO 639-1 codes
-                    let whisper_language = if settings.selected_language == "auto" {
-                        None
-                    } else {
-                        let normalized = if settings.selected_language == "zh-Hans"
-                            || settings.selected_language == "zh-Hant"
-                        {
-                            "zh".to_string()
-                        } else {
-                            settings.selected_language.clone()
-                        };
-                        Some(normalized)
-                    };
-
-                    let params = WhisperInferenceParams {
-                        language: whisper_language,
-                        translate: settings.translate_to_english,
-                        ..Default::default()
-                    };
-
-                    whisper_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?
+        let monitor = UsageMonitor::start();
+        let retry_audio = settings.retranscribe_model.is_some().then(|| audio.clone());
+        let mut engine;
+        let mut transcript = match &route {
+            EngineRoute::LocalModel(model_id) => {
+                engine = Some(model_id.clone());
+                let result = self.transcribe_with_fallback(model_id, &settings, audio)?;
+                TimedTranscript {
+                    text: result.text,
+                    segments: timing::from_engine(result.segments),
                 }
-                LoadedEngine::Parakeet(parakeet_engine) => {
-                    let params = ParakeetInferenceParams {
-                        timestamp_granularity: TimestampGranularity::Segment,
-                        ..Default::default()
-                    };
-                    parakeet_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?
+            }
+            EngineRoute::Remote | EngineRoute::Local => {
+                let provider = if route == EngineRoute::Remote {
+                    transcription_provider::remote(&settings)
+                        .ok_or_else(|| anyhow::anyhow!("No remote engine is enabled"))?
+                } else {
+                    transcription_provider::local()
+                };
+                engine = if route == EngineRoute::Remote {
+                    Some(provider.name().to_string())
+                } else {
+                    self.get_current_model()
+                };
+                let capabilities = provider.capabilities(&settings);
+                debug!(
+                    "Transcribing with {} (streaming: {}, diarization: {}, translation: {})",
+                    provider.name(),
+                    capabilities.streaming,
+                    capabilities.diarization,
+                    capabilities.translation
+                );
+                if settings.translate_to_english && !capabilities.translation {
+                    warn!(
+                        "{} can't translate, transcribing as spoken",
+                        provider.name()
+                    );
                 }
-                LoadedEngine::Moonshine(moonshine_engine) => moonshine_engine
-                    .transcribe_samples(audio, None)
-                    .map_err(|e| anyhow::anyhow!("Moonshine transcription failed: {}", e))?,
+                tauri::async_runtime::block_on(provider.transcribe(Job {
+                    manager: self,
+                    settings: &settings,
+                    audio,
+                }))?
             }
         };
+        if let Some(audio) = retry_audio {
+            if let Some((model_id, retried)) =
+                self.maybe_retranscribe(&settings, audio, &transcript.text)
+            {
+                engine = Some(model_id);
+                transcript = retried;
+            }
+        }
+        let TimedTranscript { mut text, segments } = transcript;
+        let whisper = match &route {
+            EngineRoute::LocalModel(_) => self
+                .fallback_engine
+                .lock()
+                .unwrap()
+                .as_ref()
+                .is_some_and(|(_, engine)| engine.is_whisper()),
+            _ => self
+                .engine
+                .lock()
+                .unwrap()
+                .as_ref()
+                .is_some_and(LocalEngine::is_whisper),
+        };
+        let usage = monitor.finish(route == EngineRoute::Remote, whisper);
+        debug!("Transcription resource usage: {:?}", usage);
+
+        // Only Whisper makes these up, other engines' text is left as it is
+        if settings.hallucination_filter && whisper {
+            let filtered = suppress_hallucinations(&text);
+            if filtered != text.trim() {
+                info!(
+                    "Suppressed likely hallucination: {:?} -> {:?}",
+                    text, filtered
+                );
+            }
+            text = filtered;
+        }
+
+        if lock_language {
+            self.language_lock.observe(&text, lock_timeout);
+        }
+
+        // Before word correction, which would "correct" the spelled letters
+        if settings.spoken_spelling {
+            text = spelling::apply(&text, &settings.spelling_trigger);
+        }
 
         // Apply word correction if custom words are configured
         let corrected_result = if !settings.custom_words.is_empty() {
             apply_custom_words(
-                &result.text,
+                &text,
                 &settings.custom_words,
                 settings.word_correction_threshold,
             )
         } else {
-            result.text
+            text
+        };
+        let corrected_result = if !settings.word_replacements.is_empty() {
+            apply_replacements(
+                &corrected_result,
+                &settings.replacement_rules(),
+                settings.word_correction_threshold,
+            )
+        } else {
+            corrected_result
+        };
+
+        let punctuated_result =
+            if settings.punctuation_restoration && needs_restoration(&corrected_result) {
+                self.restore_punctuation(&corrected_result)
+            } else {
+                corrected_result
+            };
+
+        let styled_result = if punctuation_style::is_enabled(&settings) {
+            punctuation_style::apply(&punctuated_result, &settings)
+        } else {
+            punctuated_result
+        };
+
+        let ruled_result = if text_rules::is_enabled(&settings.text_rules) {
+            text_rules::apply(&styled_result, &settings.text_rules)
+        } else {
+            styled_result
         };
 
         let et = std::time::Instant::now();
@@ -452,34 +1089,705 @@ impl TranscriptionManager {
             translation_note
         );
 
-        let final_result = corrected_result.trim().to_string();
+        let final_result = ruled_result.trim().to_string();
 
         if final_result.is_empty() {
             info!("Transcription result is empty");
         } else {
             info!("Transcription result: {}", final_result);
+            *self.last_transcript.lock().unwrap() = Some(final_result.clone());
         }
 
         self.maybe_unload_immediately("transcription");
 
-        Ok(final_result)
+        Ok(Transcription {
+            text: final_result,
+            usage: Some(usage),
+            engine,
+            segments,
+        })
     }
-}
 
-impl Drop for TranscriptionManager {
-    fn drop(&mut self) {
-        debug!("Shutting down TranscriptionManager");
+    /// Re-runs `audio` through the `retranscribe_model` when `text` looks like
+    /// the engine struggled. Returns the model and its transcript, or `None`
+    /// to keep `text`, also when the retry doesn't finish within the latency
+    /// cap.
+    fn maybe_retranscribe(
+        &self,
+        settings: &AppSettings,
+        audio: Vec<f32>,
+        text: &str,
+    ) -> Option<(String, TimedTranscript)> {
+        let model_id = settings.retranscribe_model.clone()?;
+        if !looks_unreliable(text, audio.len()) {
+            return None;
+        }
+        if !settings.remote_enabled() && self.get_current_model().as_deref() == Some(&model_id) {
+            return None;
+        }
 
-        // Signal the watcher thread to shutdown
-        self.shutdown_signal.store(true, Ordering::Relaxed);
+        info!(
+            "Transcript looks unreliable ({} characters for {:.1}s of audio), retrying with {}",
+            text.chars().count(),
+            audio.len() as f32 / constants::WHISPER_SAMPLE_RATE as f32,
+            model_id
+        );
+        let started = std::time::Instant::now();
+        let (tx, rx) = mpsc::channel();
+        let manager = self.clone();
+        let retry_settings = settings.clone();
+        let retry_model = model_id.clone();
+        thread::spawn(move || {
+            let _ = tx.send(manager.transcribe_with_fallback(&retry_model, &retry_settings, audio));
+        });
 
-        // Wait for the thread to finish gracefully
-        if let Some(handle) = self.watcher_handle.lock().unwrap().take() {
-            if let Err(e) = handle.join() {
-                warn!("Failed to join idle watcher thread: {:?}", e);
+        let cap = Duration::from_millis(settings.retranscribe_max_latency_ms as u64);
+        match rx.recv_timeout(cap) {
+            Ok(Ok(result)) if !result.text.trim().is_empty() => {
+                info!(
+                    "Re-transcription finished in {}ms",
+                    started.elapsed().as_millis()
+                );
+                Some((
+                    model_id,
+                    TimedTranscript {
+                        text: result.text,
+                        segments: timing::from_engine(result.segments),
+                    },
+                ))
+            }
+            Ok(Ok(_)) => None,
+            Ok(Err(e)) => {
+                warn!("Re-transcription failed, keeping the original: {}", e);
+                None
+            }
+            Err(_) => {
+                warn!(
+                    "Re-transcription took longer than {}ms, keeping the original",
+                    cap.as_millis()
+                );
+                None
+            }
+        }
+    }
+
+    /// Transcribes with `model_id`, whether or not it's the loaded model, so
+    /// calibration can compare the downloaded models on one recording.
+    pub(crate) fn transcribe_for_comparison(
+        &self,
+        model_id: &str,
+        settings: &AppSettings,
+        audio: Vec<f32>,
+    ) -> Result<String> {
+        if self.get_current_model().as_deref() == Some(model_id) && self.is_model_loaded() {
+            return Ok(self.transcribe_local(settings, audio)?.text);
+        }
+        Ok(self
+            .transcribe_with_fallback(model_id, settings, audio)?
+            .text)
+    }
+
+    /// Transcribes with the fallback model, loading it on first use. It stays
+    /// loaded next to the main model until that's unloaded.
+    /// Where a dictation meant for the remote engine goes while it can't be
+    /// reached: the downloaded local model, or nowhere, failing as
+    /// unreachable so the dictation is kept until it can be.
+    fn offline_route(&self, settings: &AppSettings) -> Result<EngineRoute> {
+        let model_id = power::effective_model_id(&self.app_handle, settings);
+        let downloaded = self
+            .model_manager
+            .get_model_info(&model_id)
+            .is_some_and(|info| info.is_downloaded);
+        if !downloaded {
+            return Err(anyhow::Error::new(std::io::Error::new(
+                std::io::ErrorKind::NetworkUnreachable,
+                "The remote engine can't be reached right now",
+            )));
+        }
+        info!(
+            "Remote engine unreachable, transcribing with {} instead",
+            model_id
+        );
+        Ok(EngineRoute::LocalModel(model_id))
+    }
+
+    /// Whether `model_id` is a Whisper model, including fine-tunes of one
+    fn is_whisper_model(&self, model_id: &str) -> bool {
+        self.model_manager
+            .get_model_info(model_id)
+            .is_some_and(|info| matches!(info.engine_type, EngineType::Whisper))
+    }
+
+    fn transcribe_with_fallback(
+        &self,
+        model_id: &str,
+        settings: &AppSettings,
+        audio: Vec<f32>,
+    ) -> Result<TranscriptionResult> {
+        let mut fallback = self.fallback_engine.lock().unwrap();
+        if !matches!(fallback.as_ref(), Some((id, _)) if id == model_id) {
+            let model_info = self
+                .model_manager
+                .get_model_info(model_id)
+                .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+            if !model_info.is_downloaded {
+                return Err(anyhow::anyhow!("Model not downloaded: {}", model_id));
+            }
+            let model_path = self.model_manager.get_model_path(model_id)?;
+            let engine = open_engine(&model_info.engine_type, &model_path)?;
+            debug!("Loaded fallback model {}", model_id);
+            *fallback = Some((model_id.to_string(), engine));
+        }
+
+        let (_, engine) = fallback.as_mut().expect("fallback engine loaded above");
+        let initial_prompt = if engine.is_whisper() {
+            self.context_prompt(settings)
+        } else {
+            None
+        };
+        run_engine(engine, settings, false, initial_prompt, audio)
+    }
+
+    /// Runs `text` through the punctuation model, loading it on first use.
+    /// Returns `text` unchanged if the model isn't available.
+    fn restore_punctuation(&self, text: &str) -> String {
+        let started = std::time::Instant::now();
+        let mut model = self.punctuation_model.lock().unwrap();
+        if model.is_none() {
+            let loaded = self
+                .model_manager
+                .get_model_path(PUNCTUATION_MODEL_ID)
+                .and_then(|path| PunctuationModel::load(&path));
+            match loaded {
+                Ok(loaded) => *model = Some(loaded),
+                Err(e) => {
+                    warn!("Punctuation model unavailable: {}", e);
+                    return text.to_string();
+                }
+            }
+        }
+
+        match model
+            .as_mut()
+            .expect("punctuation model loaded above")
+            .restore(text)
+        {
+            Ok(restored) => {
+                debug!(
+                    "Restored punctuation in {}ms",
+                    started.elapsed().as_millis()
+                );
+                restored
+            }
+            Err(e) => {
+                warn!("Punctuation restoration failed: {}", e);
+                text.to_string()
+            }
+        }
+    }
+
+    /// The end of the previous transcript or the clipboard, depending on the
+    /// `transcription_context` setting, then the user's transcription prompt,
+    /// then the custom words if they are boosted. They go last because
+    /// Whisper weighs the end of the prompt the most.
+    fn context_prompt(&self, settings: &AppSettings) -> Option<String> {
+        // The user's prompt takes its room from the context
+        let prompt = transcription_provider::prompt(settings)
+            .map(|prompt| context_tail(prompt, CONTEXT_PROMPT_CHARS));
+        let context_chars = CONTEXT_PROMPT_CHARS.saturating_sub(
+            prompt
+                .as_ref()
+                .map_or(0, |prompt| prompt.chars().count() + 1),
+        );
+        let context = match settings.transcription_context {
+            TranscriptionContext::None => None,
+            TranscriptionContext::PreviousTranscript => {
+                self.last_transcript.lock().unwrap().clone()
+            }
+            TranscriptionContext::Clipboard => self.app_handle.clipboard().read_text().ok(),
+        }
+        .filter(|_| context_chars > 0)
+        .map(|context| context_tail(&context, context_chars))
+        .filter(|tail| !tail.is_empty());
+        let vocabulary = if settings.boost_custom_words {
+            vocabulary_prompt(&settings.custom_words, VOCABULARY_PROMPT_CHARS)
+        } else {
+            None
+        };
+        let parts: Vec<String> = [context, prompt, vocabulary]
+            .into_iter()
+            .flatten()
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+
+    /// Transcribes with the locally loaded model, waiting for it to finish
+    /// loading first.
+    pub(crate) fn transcribe_local(
+        &self,
+        settings: &AppSettings,
+        audio: Vec<f32>,
+    ) -> Result<TranscriptionResult> {
+        // Check if model is loaded, if not try to load it
+        {
+            // If the model is loading, wait for it to complete.
+            let mut is_loading = self.is_loading.lock().unwrap();
+            while *is_loading {
+                is_loading = self.loading_condvar.wait(is_loading).unwrap();
+            }
+
+            let engine_guard = self.engine.lock().unwrap();
+            if engine_guard.is_none() {
+                return Err(anyhow::anyhow!("Model is not loaded for transcription."));
+            }
+        }
+
+        // Perform transcription with the appropriate engine
+        let mut engine_guard = self.engine.lock().unwrap();
+        let engine = engine_guard.as_mut().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Model failed to load after auto-load attempt. Please check your model settings."
+            )
+        })?;
+
+        // Code-switching decodes every segment with language detection
+        let code_switching = settings.code_switching && engine.is_whisper();
+        let initial_prompt = if engine.is_whisper() {
+            self.context_prompt(settings)
+        } else {
+            None
+        };
+
+        let mut decode = |samples: Vec<f32>| {
+            run_engine(
+                &mut *engine,
+                settings,
+                code_switching,
+                initial_prompt.clone(),
+                samples,
+            )
+        };
+
+        let result = if code_switching {
+            self.transcribe_in_segments(
+                &audio,
+                CODE_SWITCH_MIN_SEGMENT_MS,
+                CODE_SWITCH_MAX_SEGMENT_MS,
+                None,
+                &mut decode,
+            )?
+        } else if audio.len() > LONG_JOB_SAMPLES && settings.segment_long_recordings {
+            self.transcribe_in_segments(
+                &audio,
+                LONG_JOB_MIN_SEGMENT_MS,
+                LONG_JOB_MAX_SEGMENT_MS,
+                None,
+                &mut decode,
+            )?
+        } else if audio.len() > LONG_JOB_SAMPLES && settings.compress_silence {
+            decode_compressed(&audio, &mut decode)?
+        } else {
+            decode(audio)?
+        };
+        Ok(result)
+    }
+
+    /// Emits `transcription-retry` when a remote provider waits to send a
+    /// failed request again.
+    pub(crate) fn report_retry(&self, retry: u32, max_retries: u32, delay: Duration) {
+        let _ = self.app_handle.emit(
+            "transcription-retry",
+            TranscriptionRetry {
+                retry,
+                max_retries,
+                delay_secs: delay.as_secs_f32(),
+            },
+        );
+    }
+
+    /// Starts timing a job done in parts, `completed` of which are already
+    /// done (from a checkpoint), and reports it.
+    pub(crate) fn start_progress(&self, completed: usize, total: usize) {
+        *self.progress_start.lock().unwrap() = Some((Instant::now(), completed));
+        self.report_progress(completed, total);
+    }
+
+    /// Emits `transcription-progress` for a job done in parts.
+    pub(crate) fn report_progress(&self, completed: usize, total: usize) {
+        let eta_secs = self
+            .progress_start
+            .lock()
+            .unwrap()
+            .and_then(|(started, skipped)| {
+                estimate_remaining(
+                    started.elapsed(),
+                    completed.saturating_sub(skipped),
+                    total.saturating_sub(completed),
+                )
+            });
+        let _ = self.app_handle.emit(
+            "transcription-progress",
+            TranscriptionProgress {
+                completed,
+                total,
+                percent: completed as f32 / total.max(1) as f32 * 100.0,
+                current_chunk: (completed + 1).min(total),
+                eta_secs,
+                task: self.progress_task.lock().unwrap().clone(),
+            },
+        );
+    }
+
+    /// Sends a recording to a remote `engine`. Long ones go in segments,
+    /// each retried a few times. Finished segments are checkpointed under
+    /// `key`, so if the engine stays unreachable, transcribing the same audio
+    /// again resumes there.
+    pub(crate) fn transcribe_remote(
+        &self,
+        engine: &dyn Engine,
+        key: &str,
+        request: Request,
+    ) -> Result<TimedTranscript> {
+        if request.samples.len() <= LONG_JOB_SAMPLES {
+            let transcript = wait(engine.transcribe(request))?;
+            return Ok(TimedTranscript {
+                text: transcript.text,
+                segments: Some(transcript.segments).filter(|s| !s.is_empty()),
+            });
+        }
+        let checkpoint_dir = self
+            .app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?
+            .join("checkpoints");
+
+        let mut decode = |samples: Vec<f32>| {
+            let mut attempt = 1;
+            loop {
+                let segment = Request {
+                    samples: &samples,
+                    ..request
+                };
+                match wait(engine.transcribe(segment)) {
+                    Ok(transcript) => {
+                        return Ok(TranscriptionResult {
+                            text: transcript.text,
+                            segments: None,
+                        })
+                    }
+                    Err(e) if attempt < REMOTE_SEGMENT_ATTEMPTS => {
+                        warn!("Segment transcription failed (attempt {}): {}", attempt, e);
+                        thread::sleep(REMOTE_RETRY_DELAY * attempt);
+                        attempt += 1;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        };
+
+        let result = self.transcribe_in_segments(
+            request.samples,
+            LONG_JOB_MIN_SEGMENT_MS,
+            LONG_JOB_MAX_SEGMENT_MS,
+            Some((&checkpoint_dir, key)),
+            &mut decode,
+        )?;
+        Ok(TimedTranscript {
+            text: result.text,
+            segments: timing::from_engine(result.segments),
+        })
+    }
+
+    /// Decodes `audio` one pause-delimited segment at a time, emitting
+    /// `transcription-progress` after each so long jobs can show progress.
+    /// With a checkpoint directory and key, finished segments are saved there
+    /// and skipped when an earlier attempt already got them. Long silences in
+    /// long jobs are skipped if `compress_silence` is on; the returned
+    /// segments are timed against the original audio either way.
+    fn transcribe_in_segments(
+        &self,
+        audio: &[f32],
+        min_segment_ms: usize,
+        max_segment_ms: usize,
+        checkpoint: Option<(&Path, &str)>,
+        decode: &mut dyn FnMut(Vec<f32>) -> Result<TranscriptionResult>,
+    ) -> Result<TranscriptionResult> {
+        let sample_rate = constants::WHISPER_SAMPLE_RATE as usize;
+        let compressed;
+        let (audio, silence) =
+            if get_settings(&self.app_handle).compress_silence && audio.len() > LONG_JOB_SAMPLES {
+                let (samples, silence) =
+                    compress_silence(audio, sample_rate, SILENCE_SKIP_MIN_MS, SILENCE_KEEP_MS);
+                info!(
+                    "Skipping {:.1}s of silence",
+                    silence.removed() as f32 / sample_rate as f32
+                );
+                compressed = samples;
+                (&compressed[..], silence)
             } else {
-                debug!("Idle watcher thread joined successfully");
+                (audio, SilenceMap::default())
+            };
+
+        let segments = split_on_silence(audio, sample_rate, min_segment_ms, max_segment_ms);
+        let total = segments.len();
+        debug!("Decoding {} segments", total);
+
+        let mut checkpoint = checkpoint.map(|(dir, key)| Checkpoint::open(dir, audio, key, total));
+        let mut texts: Vec<String> = checkpoint
+            .as_ref()
+            .map(|c| c.completed().to_vec())
+            .unwrap_or_default();
+        self.start_progress(texts.len(), total);
+
+        for (i, range) in segments.iter().cloned().enumerate().skip(texts.len()) {
+            let result = decode(audio[range].to_vec())?;
+            let text = result.text.trim();
+            if let Some(checkpoint) = checkpoint.as_mut() {
+                checkpoint.record(text);
             }
+            texts.push(text.to_string());
+            self.report_progress(i + 1, total);
         }
+
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.finish();
+        }
+
+        // Timestamps within a chunk are relative to it, so each chunk becomes
+        // one segment spanning the audio it came from
+        let seconds = |position: usize| position as f32 / sample_rate as f32;
+        let segments: Vec<TranscriptionSegment> = segments
+            .into_iter()
+            .zip(texts)
+            .filter(|(_, text)| !text.is_empty())
+            .map(|(range, text)| TranscriptionSegment {
+                start: seconds(silence.to_original(range.start)),
+                end: seconds(silence.to_original(range.end - 1) + 1),
+                text,
+            })
+            .collect();
+        let text = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(TranscriptionResult {
+            text,
+            segments: Some(segments),
+        })
+    }
+}
+
+/// Where a recording is transcribed
+#[derive(Debug, PartialEq)]
+enum EngineRoute {
+    /// The selected local model
+    Local,
+    /// Another local model, loaded next to the selected one
+    LocalModel(String),
+    /// The Wyoming server, or Deepgram
+    Remote,
+}
+
+/// Picks the engine for `sample_count` samples of audio. In adaptive mode,
+/// recordings shorter than the threshold stay on the fast selected model and
+/// longer ones go to the accurate model, or the remote engine when no long
+/// model is set.
+fn route_engine(settings: &AppSettings, sample_count: usize) -> EngineRoute {
+    if !settings.adaptive_engine {
+        return if settings.remote_enabled() {
+            EngineRoute::Remote
+        } else {
+            EngineRoute::Local
+        };
+    }
+    let threshold =
+        settings.adaptive_threshold_secs as usize * constants::WHISPER_SAMPLE_RATE as usize;
+    if sample_count < threshold {
+        return EngineRoute::Local;
+    }
+    match &settings.adaptive_long_model {
+        Some(model_id) => EngineRoute::LocalModel(model_id.clone()),
+        None if settings.remote_enabled() => EngineRoute::Remote,
+        None => EngineRoute::Local,
+    }
+}
+
+/// The engine a file would be transcribed with, for the file cache. The
+/// remote server's address and options are part of `cache_settings`.
+fn cache_engine(settings: &AppSettings) -> String {
+    match transcription_provider::remote(settings) {
+        Some(provider) => format!("{}+{}", settings.selected_model, provider.name()),
+        None => settings.selected_model.clone(),
+    }
+}
+
+/// The settings that shape a file's transcript, for the file cache. Changing
+/// any of them transcribes the file again.
+fn cache_settings(settings: &AppSettings) -> String {
+    serde_json::json!({
+        "language": settings.selected_language,
+        "translate": settings.translate_to_english,
+        "code_switching": settings.code_switching,
+        "custom_words": settings.custom_words,
+        "boost_custom_words": settings.boost_custom_words,
+        "word_correction_threshold": settings.word_correction_threshold,
+        "word_replacements": settings.word_replacements,
+        "prompt": settings.transcription_prompt,
+        "preprocessing": settings.preprocessing,
+        "compress_silence": settings.compress_silence,
+        "segment_long_recordings": settings.segment_long_recordings,
+        "hallucination_filter": settings.hallucination_filter,
+        "punctuation_restoration": settings.punctuation_restoration,
+        "oxford_comma": settings.oxford_comma,
+        "dash_style": settings.dash_style,
+        "quote_style": settings.quote_style,
+        "ellipsis_style": settings.ellipsis_style,
+        "spoken_spelling": settings.spoken_spelling,
+        "spelling_trigger": settings.spelling_trigger,
+        "text_rules": settings.text_rules,
+        "retranscribe_model": settings.retranscribe_model,
+        "adaptive_engine": settings.adaptive_engine,
+        "adaptive_threshold_secs": settings.adaptive_threshold_secs,
+        "adaptive_long_model": settings.adaptive_long_model,
+        "wyoming_server": settings.wyoming_server,
+        "deepgram": settings.deepgram,
+    })
+    .to_string()
+}
+
+/// The engines don't report confidence, so a transcript that's very short for
+/// the amount of audio is taken as a sign the model struggled.
+fn looks_unreliable(text: &str, sample_count: usize) -> bool {
+    if sample_count < RETRANSCRIBE_MIN_SAMPLES {
+        return false;
+    }
+    let seconds = sample_count as f32 / constants::WHISPER_SAMPLE_RATE as f32;
+    let chars = text.chars().filter(|c| !c.is_whitespace()).count();
+    (chars as f32 / seconds) < RETRANSCRIBE_MIN_CHARS_PER_SECOND
+}
+
+fn open_engine(engine_type: &EngineType, model_path: &Path) -> Result<LocalEngine> {
+    let kind = match engine_type {
+        EngineType::Whisper => ModelKind::Whisper,
+        EngineType::Parakeet => ModelKind::Parakeet,
+        EngineType::Moonshine => ModelKind::Moonshine,
+        EngineType::Punctuation => {
+            return Err(anyhow::anyhow!("Not a speech recognition model"));
+        }
+    };
+    LocalEngine::open(kind, model_path)
+}
+
+fn run_engine(
+    engine: &mut LocalEngine,
+    settings: &AppSettings,
+    code_switching: bool,
+    initial_prompt: Option<String>,
+    samples: Vec<f32>,
+) -> Result<TranscriptionResult> {
+    let options = DecodeOptions {
+        language: Some(settings.selected_language.clone())
+            .filter(|language| !code_switching && language != "auto"),
+        translate: settings.translate_to_english,
+        initial_prompt,
+    };
+    engine.transcribe(samples, &options)
+}
+
+/// Decodes `audio` in one pass with its long silences cut down, timing the
+/// segments against the original audio
+fn decode_compressed(
+    audio: &[f32],
+    decode: &mut dyn FnMut(Vec<f32>) -> Result<TranscriptionResult>,
+) -> Result<TranscriptionResult> {
+    let sample_rate = constants::WHISPER_SAMPLE_RATE as usize;
+    let (samples, silence) =
+        compress_silence(audio, sample_rate, SILENCE_SKIP_MIN_MS, SILENCE_KEEP_MS);
+    info!(
+        "Skipping {:.1}s of silence",
+        silence.removed() as f32 / sample_rate as f32
+    );
+    let mut result = decode(samples)?;
+    let position = |secs: f32| (secs.max(0.0) * sample_rate as f32) as usize;
+    let seconds = |position: usize| position as f32 / sample_rate as f32;
+    for segment in result.segments.iter_mut().flatten() {
+        segment.start = seconds(silence.to_original(position(segment.start)));
+        // The end is just past the last sample, which is the one to map
+        segment.end = seconds(silence.to_original(position(segment.end).saturating_sub(1)) + 1);
+    }
+    Ok(result)
+}
+
+/// Waits for an engine's `future` from the synchronous segment loop, which
+/// runs inside the provider future the transcription thread is blocked on
+fn wait<T>(future: impl Future<Output = T>) -> T {
+    tokio::task::block_in_place(|| tauri::async_runtime::block_on(future))
+}
+
+/// Seconds left for `remaining` parts at the pace of the `done` that took
+/// `elapsed`
+fn estimate_remaining(elapsed: Duration, done: usize, remaining: usize) -> Option<f32> {
+    if done == 0 {
+        return None;
+    }
+    Some(elapsed.as_secs_f32() / done as f32 * remaining as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_time_left_from_the_pace_so_far() {
+        assert_eq!(estimate_remaining(Duration::from_secs(10), 0, 5), None);
+        assert_eq!(
+            estimate_remaining(Duration::from_secs(10), 2, 6),
+            Some(30.0)
+        );
+        assert_eq!(estimate_remaining(Duration::from_secs(10), 4, 0), Some(0.0));
+    }
+
+    #[test]
+    fn routes_by_recording_length_in_adaptive_mode() {
+        let mut settings = crate::settings::get_default_settings();
+        let short = 16000 * 3;
+        let long = 16000 * 8;
+        assert_eq!(route_engine(&settings, long), EngineRoute::Local);
+        settings.wyoming_enabled = true;
+        assert_eq!(route_engine(&settings, short), EngineRoute::Remote);
+
+        settings.adaptive_engine = true;
+        assert_eq!(route_engine(&settings, short), EngineRoute::Local);
+        assert_eq!(route_engine(&settings, long), EngineRoute::Remote);
+
+        settings.adaptive_long_model = Some("large".to_string());
+        assert_eq!(
+            route_engine(&settings, long),
+            EngineRoute::LocalModel("large".to_string())
+        );
+        settings.adaptive_threshold_secs = 10;
+        assert_eq!(route_engine(&settings, long), EngineRoute::Local);
+    }
+
+    #[test]
+    fn short_recordings_are_never_retried() {
+        assert!(!looks_unreliable("", RETRANSCRIBE_MIN_SAMPLES - 1));
+    }
+
+    #[test]
+    fn flags_transcripts_too_short_for_the_audio() {
+        let ten_seconds = 16000 * 10;
+        assert!(looks_unreliable("", ten_seconds));
+        assert!(looks_unreliable("Thank you.", ten_seconds));
+        assert!(!looks_unreliable(
+            "Let's move the standup to Thursday so the whole team can join.",
+            ten_seconds
+        ));
+        assert!(!looks_unreliable(
+            "明天下午三点在会议室开会讨论预算问题",
+            16000 * 5
+        ));
     }
 }
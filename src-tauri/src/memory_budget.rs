@@ -0,0 +1,149 @@
+//! Keeps local speech models within the memory the user set aside for them,
+//! so loading a large one doesn't push the system into swap.
+//!
+//! What a model takes once loaded is estimated from the size of its files
+//! plus the runtime's buffers. The budget covers wherever the weights end
+//! up, RAM or VRAM. When the model asked for doesn't fit, the most accurate
+//! downloaded model that does is loaded instead, preferring one of the same
+//! engine so e.g. a large Whisper model falls back to a smaller quantized
+//! Whisper model. When none fits, loading is refused.
+
+use crate::managers::model::{EngineType, ModelInfo, ModelManager};
+use crate::managers::transcription::TranscriptionManager;
+use crate::resource_usage;
+use crate::settings::get_settings;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{AppHandle, Manager};
+
+const MB: u64 = 1024 * 1024;
+/// Buffers a loaded model needs on top of its weights, as a share of them
+const RUNTIME_FACTOR_PERCENT: u64 = 120;
+/// And the fixed part of them, e.g. Whisper's compute buffers
+const RUNTIME_OVERHEAD_MB: u64 = 150;
+
+/// Memory `model` takes once loaded, roughly
+pub fn estimated_mb(model: &ModelInfo) -> u64 {
+    model.size_mb * RUNTIME_FACTOR_PERCENT / 100 + RUNTIME_OVERHEAD_MB
+}
+
+/// The model to load instead of `wanted` so it fits in `budget_mb`: `wanted`
+/// itself if it fits, else the most accurate downloaded speech model that
+/// does, of the same engine if there is one. `None` when nothing fits.
+pub fn pick<'a>(
+    wanted: &'a ModelInfo,
+    models: &'a [ModelInfo],
+    budget_mb: u64,
+) -> Option<&'a ModelInfo> {
+    if estimated_mb(wanted) <= budget_mb {
+        return Some(wanted);
+    }
+    models
+        .iter()
+        .filter(|model| {
+            model.is_downloaded
+                && !matches!(model.engine_type, EngineType::Punctuation)
+                && estimated_mb(model) <= budget_mb
+        })
+        .max_by(|a, b| {
+            let same_engine = |model: &ModelInfo| {
+                std::mem::discriminant(&model.engine_type)
+                    == std::mem::discriminant(&wanted.engine_type)
+            };
+            same_engine(a)
+                .cmp(&same_engine(b))
+                .then(a.accuracy_score.total_cmp(&b.accuracy_score))
+        })
+}
+
+/// Memory in use, for the settings page
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct MemoryUsage {
+    pub budget_mb: Option<u64>,
+    /// The loaded speech model and what it's estimated to take
+    pub model_id: Option<String>,
+    pub model_estimate_mb: Option<u64>,
+    /// All of Handy, not only the model
+    pub process_ram_mb: u64,
+    /// `None` where it can't be read, see `resource_usage`
+    pub vram_mb: Option<u64>,
+    pub system_available_mb: u64,
+}
+
+pub fn usage(app: &AppHandle) -> MemoryUsage {
+    let settings = get_settings(app);
+    let tm = app.state::<Arc<TranscriptionManager>>();
+    let model_id = tm.get_current_model();
+    let model_estimate_mb = model_id
+        .as_deref()
+        .and_then(|id| app.state::<Arc<ModelManager>>().get_model_info(id))
+        .map(|model| estimated_mb(&model));
+
+    let mut system = System::new();
+    system.refresh_memory();
+    let process_ram_mb = sysinfo::get_current_pid()
+        .ok()
+        .and_then(|pid| {
+            system.refresh_processes_specifics(
+                ProcessesToUpdate::Some(&[pid]),
+                false,
+                ProcessRefreshKind::nothing().with_memory(),
+            );
+            system.process(pid).map(|process| process.memory() / MB)
+        })
+        .unwrap_or(0);
+
+    MemoryUsage {
+        budget_mb: settings.model_memory_budget_mb,
+        model_id,
+        model_estimate_mb,
+        process_ram_mb,
+        vram_mb: resource_usage::process_vram_bytes().map(|bytes| bytes / MB),
+        system_available_mb: system.available_memory() / MB,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str, engine_type: EngineType, size_mb: u64, accuracy: f32) -> ModelInfo {
+        ModelInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            filename: id.to_string(),
+            url: None,
+            size_mb,
+            is_downloaded: true,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory: false,
+            is_shared: false,
+            engine_type,
+            accuracy_score: accuracy,
+            speed_score: 0.5,
+            base_model: None,
+        }
+    }
+
+    #[test]
+    fn falls_back_to_the_best_model_that_fits() {
+        let models = vec![
+            model("turbo", EngineType::Whisper, 1600, 0.8),
+            model("small", EngineType::Whisper, 487, 0.6),
+            model("parakeet", EngineType::Parakeet, 478, 0.85),
+            model("moonshine", EngineType::Moonshine, 58, 0.5),
+        ];
+        let chosen =
+            |wanted: usize, budget| pick(&models[wanted], &models, budget).map(|m| m.id.as_str());
+
+        assert_eq!(chosen(0, 4096), Some("turbo"));
+        // Another Whisper model before a more accurate Parakeet one
+        assert_eq!(chosen(0, 1024), Some("small"));
+        assert_eq!(chosen(2, 300), Some("moonshine"));
+        assert_eq!(chosen(0, 100), None);
+    }
+}
@@ -0,0 +1,113 @@
+//! Whether the remote engine can be reached. The OS reports network changes,
+//! like Wi-Fi dropping or a VPN coming up, and each one is followed by a
+//! quick connection to the engine. While it can't be reached dictations go
+//! to the local model, or are kept for later if there is none, instead of
+//! waiting for a request to time out. Dictations kept while offline are sent
+//! as soon as it can be reached again.
+
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::get_settings;
+use crate::transcription_provider;
+use futures_util::{FutureExt, StreamExt};
+use log::{debug, info, warn};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+/// Changes come in bursts as interfaces and addresses come and go, the
+/// network is checked once they settle
+const SETTLE_DELAY: Duration = Duration::from_secs(2);
+
+static REACHABLE: AtomicBool = AtomicBool::new(true);
+
+/// Whether the remote engine could be reached when last checked. Also true
+/// when no remote engine is in use.
+pub fn remote_reachable() -> bool {
+    REACHABLE.load(Ordering::Relaxed)
+}
+
+/// Notes that a request just failed to reach the remote engine, so the next
+/// dictations don't try it again until the next check finds it.
+pub fn mark_unreachable() {
+    set_reachable(false);
+}
+
+fn set_reachable(reachable: bool) {
+    if REACHABLE.swap(reachable, Ordering::Relaxed) != reachable {
+        info!(
+            "Remote engine is {}",
+            if reachable {
+                "reachable again"
+            } else {
+                "unreachable"
+            }
+        );
+    }
+}
+
+/// Whether a connection to `address`, a `host:port`, can be opened
+fn probe(address: &str) -> bool {
+    let Ok(addrs) = address.to_socket_addrs() else {
+        debug!("Couldn't resolve {}", address);
+        return false;
+    };
+    addrs
+        .into_iter()
+        .any(|addr| TcpStream::connect_timeout(&addr, PROBE_TIMEOUT).is_ok())
+}
+
+/// Checks whether the remote engine can be reached. Blocks for up to a few
+/// seconds.
+pub fn recheck(app: &AppHandle) -> bool {
+    let settings = get_settings(app);
+    let reachable = transcription_provider::remote(&settings)
+        .and_then(|provider| provider.address(&settings))
+        .is_none_or(|address| probe(&address));
+    set_reachable(reachable);
+    reachable
+}
+
+/// Rechecks on another thread, after the remote engine settings changed
+pub fn recheck_in_background(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || recheck(&app));
+}
+
+/// Rechecks the remote engine whenever the OS reports a network change, and
+/// sends the dictations kept while it couldn't be reached once it can.
+pub fn start_monitor(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut watcher = match if_watch::tokio::IfWatcher::new() {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                warn!("Can't watch for network changes: {}", e);
+                return;
+            }
+        };
+        while let Some(event) = watcher.next().await {
+            match event {
+                Ok(event) => debug!("Network change: {:?}", event),
+                Err(e) => {
+                    warn!("Network change watcher failed: {}", e);
+                    continue;
+                }
+            }
+            // Let the burst of changes settle, then look once
+            tokio::time::sleep(SETTLE_DELAY).await;
+            while let Some(Some(_)) = watcher.next().now_or_never() {}
+
+            let app = app.clone();
+            let _ = tauri::async_runtime::spawn_blocking(move || {
+                let was_reachable = remote_reachable();
+                if recheck(&app) && !was_reachable {
+                    app.state::<Arc<TranscriptionManager>>().run_pending(false);
+                }
+            })
+            .await;
+        }
+    });
+}
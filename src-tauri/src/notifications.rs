@@ -0,0 +1,433 @@
+//! Notifications that offer a follow-up action, such as retrying a failed
+//! transcription or pasting a transcript again.
+//!
+//! The main window shows these as toasts with action buttons, which call back
+//! into the commands in `commands::notifications`. When the window is hidden
+//! a system notification is shown as well, so the failure isn't silent, with
+//! the same buttons where the desktop shows them.
+//!
+//! Each kind belongs to one of the events in `NotificationPreferences`, which
+//! decide whether it plays a cue, shows a toast or appears in the overlay.
+
+use crate::audio_feedback::{play_feedback_sound, SoundType};
+use crate::commands::notifications as commands;
+use crate::overlay;
+use crate::settings::{get_settings, NotificationChannels, NotificationPreferences};
+use crate::tray_i18n::{get_tray_translations, TrayStrings};
+use log::{debug, warn};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    TranscriptionFailed,
+    PasteFailed,
+    /// A video call was using the microphone when dictation started; the
+    /// detail is the app's name
+    ConferencingMic,
+    /// The microphone delivered only silence for several seconds of a recording
+    DeadInput,
+    /// The microphone changed because a higher ranked one was connected, or
+    /// the one in use went away; the detail is the new microphone
+    MicrophoneSwitched,
+    /// The remote engine couldn't be reached, so the recording was kept to
+    /// be transcribed later
+    QueuedOffline,
+    /// A recording kept for later has been transcribed; the detail is the
+    /// transcript
+    PendingTranscribed,
+    /// A dictation was transcribed and delivered; the detail is the text
+    Transcribed,
+    /// Post-processing failed, so the transcript was used as spoken; the
+    /// detail is the error
+    PostProcessFailed,
+    /// The model was unloaded after sitting idle
+    ModelUnloaded,
+}
+
+impl NotificationKind {
+    fn channels(&self, preferences: &NotificationPreferences) -> NotificationChannels {
+        match self {
+            NotificationKind::TranscriptionFailed
+            | NotificationKind::PasteFailed
+            | NotificationKind::ConferencingMic
+            | NotificationKind::DeadInput => preferences.errors,
+            NotificationKind::MicrophoneSwitched
+            | NotificationKind::QueuedOffline
+            | NotificationKind::PendingTranscribed
+            | NotificationKind::PostProcessFailed => preferences.fallback,
+            NotificationKind::Transcribed => preferences.transcribed,
+            NotificationKind::ModelUnloaded => preferences.model_unloaded,
+        }
+    }
+
+    fn sound(&self) -> SoundType {
+        match self {
+            NotificationKind::Transcribed
+            | NotificationKind::PendingTranscribed
+            | NotificationKind::ModelUnloaded => SoundType::Done,
+            _ => SoundType::Error,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationAction {
+    /// Run the last failed recording through transcription again
+    RetryTranscription,
+    /// Paste the transcript that couldn't be pasted
+    Repaste,
+    /// Copy the transcript that couldn't be pasted to the clipboard
+    CopyTranscript,
+    /// Turn off the remote engine and retry the failed recording locally
+    SwitchToLocal,
+}
+
+impl NotificationAction {
+    /// Identifies the button in a system notification
+    fn id(&self) -> &'static str {
+        match self {
+            NotificationAction::RetryTranscription => "retry_transcription",
+            NotificationAction::Repaste => "repaste",
+            NotificationAction::CopyTranscript => "copy_transcript",
+            NotificationAction::SwitchToLocal => "switch_to_local",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        [
+            NotificationAction::RetryTranscription,
+            NotificationAction::Repaste,
+            NotificationAction::CopyTranscript,
+            NotificationAction::SwitchToLocal,
+        ]
+        .into_iter()
+        .find(|action| action.id() == id)
+    }
+
+    fn label(&self, strings: &TrayStrings) -> String {
+        match self {
+            NotificationAction::RetryTranscription => strings.notification_action_retry.clone(),
+            NotificationAction::Repaste => strings.notification_action_repaste.clone(),
+            NotificationAction::CopyTranscript => strings.notification_action_copy.clone(),
+            NotificationAction::SwitchToLocal => {
+                strings.notification_action_switch_to_local.clone()
+            }
+        }
+    }
+
+    fn run(&self, app: &AppHandle) -> Result<(), String> {
+        let app = app.clone();
+        match self {
+            NotificationAction::RetryTranscription => commands::retry_last_transcription(app),
+            NotificationAction::Repaste => commands::repaste_transcript(app),
+            NotificationAction::CopyTranscript => commands::copy_unpasted_transcript(app),
+            NotificationAction::SwitchToLocal => commands::switch_to_local_transcription(app),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AppNotification {
+    pub kind: NotificationKind,
+    /// Error detail, shown below the title
+    pub detail: Option<String>,
+    pub actions: Vec<NotificationAction>,
+}
+
+/// What the notification actions operate on. Only the most recent failure is
+/// kept, older notifications just stop doing anything.
+#[derive(Default)]
+pub struct NotificationContext {
+    pub failed_samples: Option<Vec<f32>>,
+    pub unpasted_text: Option<String>,
+}
+
+pub type ManagedNotificationContext = Mutex<NotificationContext>;
+
+pub fn notify_transcription_failed(app: &AppHandle, samples: Vec<f32>, error: &str) {
+    if samples.is_empty() {
+        return;
+    }
+    app.state::<ManagedNotificationContext>()
+        .lock()
+        .unwrap()
+        .failed_samples = Some(samples);
+
+    let mut actions = vec![NotificationAction::RetryTranscription];
+    if get_settings(app).remote_enabled() {
+        actions.push(NotificationAction::SwitchToLocal);
+    }
+    emit(
+        app,
+        AppNotification {
+            kind: NotificationKind::TranscriptionFailed,
+            detail: Some(error.to_string()),
+            actions,
+        },
+    );
+}
+
+pub fn notify_paste_failed(app: &AppHandle, text: String) {
+    app.state::<ManagedNotificationContext>()
+        .lock()
+        .unwrap()
+        .unpasted_text = Some(text);
+
+    emit(
+        app,
+        AppNotification {
+            kind: NotificationKind::PasteFailed,
+            detail: None,
+            actions: vec![
+                NotificationAction::Repaste,
+                NotificationAction::CopyTranscript,
+            ],
+        },
+    );
+}
+
+pub fn notify_queued_offline(app: &AppHandle) {
+    emit(
+        app,
+        AppNotification {
+            kind: NotificationKind::QueuedOffline,
+            detail: None,
+            actions: vec![],
+        },
+    );
+}
+
+pub fn notify_pending_transcribed(app: &AppHandle, text: String) {
+    app.state::<ManagedNotificationContext>()
+        .lock()
+        .unwrap()
+        .unpasted_text = Some(text.clone());
+
+    emit(
+        app,
+        AppNotification {
+            kind: NotificationKind::PendingTranscribed,
+            detail: Some(text),
+            actions: vec![
+                NotificationAction::Repaste,
+                NotificationAction::CopyTranscript,
+            ],
+        },
+    );
+}
+
+pub fn notify_conferencing_mic(app: &AppHandle, conferencing_app: &str) {
+    emit(
+        app,
+        AppNotification {
+            kind: NotificationKind::ConferencingMic,
+            detail: Some(conferencing_app.to_string()),
+            actions: vec![],
+        },
+    );
+}
+
+pub fn notify_dead_input(app: &AppHandle) {
+    emit(
+        app,
+        AppNotification {
+            kind: NotificationKind::DeadInput,
+            detail: None,
+            actions: vec![],
+        },
+    );
+}
+
+pub fn notify_microphone_switched(app: &AppHandle, microphone: &str) {
+    emit(
+        app,
+        AppNotification {
+            kind: NotificationKind::MicrophoneSwitched,
+            detail: Some(microphone.to_string()),
+            actions: vec![],
+        },
+    );
+}
+
+pub fn notify_transcribed(app: &AppHandle, text: &str) {
+    emit(
+        app,
+        AppNotification {
+            kind: NotificationKind::Transcribed,
+            detail: Some(text.to_string()),
+            actions: vec![],
+        },
+    );
+}
+
+pub fn notify_post_process_failed(app: &AppHandle, error: &str) {
+    emit(
+        app,
+        AppNotification {
+            kind: NotificationKind::PostProcessFailed,
+            detail: Some(error.to_string()),
+            actions: vec![],
+        },
+    );
+}
+
+pub fn notify_model_unloaded(app: &AppHandle) {
+    emit(
+        app,
+        AppNotification {
+            kind: NotificationKind::ModelUnloaded,
+            detail: None,
+            actions: vec![],
+        },
+    );
+}
+
+fn emit(app: &AppHandle, notification: AppNotification) {
+    debug!("Notification: {:?}", notification);
+    let channels = notification
+        .kind
+        .channels(&get_settings(app).notification_preferences);
+    if channels.sound {
+        play_feedback_sound(app, notification.kind.sound());
+    }
+    if channels.overlay {
+        overlay::show_notice_overlay(app, &notification);
+    }
+    if !channels.toast {
+        return;
+    }
+
+    if let Err(e) = app.emit("app-notification", &notification) {
+        warn!("Failed to emit notification: {}", e);
+    }
+
+    let main_visible = app
+        .get_webview_window("main")
+        .and_then(|w| w.is_visible().ok())
+        .unwrap_or(false);
+    if !main_visible {
+        show_system_notification(app, &notification);
+    }
+}
+
+/// Shows `notification` on the desktop. Without buttons it points the user at
+/// the app instead.
+fn show_system_notification(app: &AppHandle, notification: &AppNotification) {
+    let settings = get_settings(app);
+    let strings = get_tray_translations(Some(settings.app_language));
+    let (title, body) = match notification.kind {
+        NotificationKind::TranscriptionFailed => (
+            strings.notification_transcription_failed,
+            strings.notification_open_app,
+        ),
+        NotificationKind::PasteFailed => (
+            strings.notification_paste_failed,
+            strings.notification_open_app,
+        ),
+        NotificationKind::ConferencingMic => (
+            strings
+                .notification_conferencing_mic
+                .replace("{app}", notification.detail.as_deref().unwrap_or_default()),
+            strings.notification_conferencing_hint,
+        ),
+        NotificationKind::DeadInput => (
+            strings.notification_dead_input,
+            strings.notification_dead_input_hint,
+        ),
+        NotificationKind::MicrophoneSwitched => (
+            strings
+                .notification_microphone_switched
+                .replace("{mic}", notification.detail.as_deref().unwrap_or_default()),
+            strings.notification_microphone_switched_hint,
+        ),
+        NotificationKind::QueuedOffline => (
+            strings.notification_queued_offline,
+            strings.notification_queued_offline_hint,
+        ),
+        NotificationKind::PendingTranscribed => (
+            strings.notification_pending_transcribed,
+            strings.notification_open_app_to_paste,
+        ),
+        NotificationKind::Transcribed => (
+            strings.notification_transcribed,
+            notification.detail.clone().unwrap_or_default(),
+        ),
+        NotificationKind::PostProcessFailed => (
+            strings.notification_post_process_failed,
+            strings.notification_post_process_failed_hint,
+        ),
+        NotificationKind::ModelUnloaded => (
+            strings.notification_model_unloaded,
+            strings.notification_model_unloaded_hint,
+        ),
+    };
+
+    if !notification.actions.is_empty() {
+        let body = notification.detail.clone().unwrap_or_default();
+        match show_with_actions(app, &title, &body, &notification.actions, &strings) {
+            Ok(()) => return,
+            Err(e) => warn!("Failed to show notification with actions: {}", e),
+        }
+    }
+
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        warn!("Failed to show system notification: {}", e);
+    }
+}
+
+/// The notification plugin can't add buttons on desktop, so these go through
+/// notify-rust directly. macOS puts all but the first button in a drop-down.
+fn show_with_actions(
+    app: &AppHandle,
+    title: &str,
+    body: &str,
+    actions: &[NotificationAction],
+    strings: &TrayStrings,
+) -> Result<(), String> {
+    let mut native = notify_rust::Notification::new();
+    native.summary(title).body(body).auto_icon();
+    for action in actions {
+        native.action(action.id(), &action.label(strings));
+    }
+
+    // Like the plugin, only name the app where the system knows it
+    #[cfg(target_os = "macos")]
+    let _ = notify_rust::set_application(if tauri::is_dev() {
+        "com.apple.Terminal"
+    } else {
+        &app.config().identifier
+    });
+    #[cfg(windows)]
+    {
+        let installed = tauri::utils::platform::current_exe()
+            .ok()
+            .and_then(|exe| {
+                exe.parent()
+                    .map(|dir| !dir.ends_with("target/debug") && !dir.ends_with("target/release"))
+            })
+            .unwrap_or(false);
+        if installed {
+            native.app_id(&app.config().identifier);
+        }
+    }
+
+    let handle = native.show().map_err(|e| e.to_string())?;
+    let app = app.clone();
+    // Blocks until the notification is clicked or dismissed
+    std::thread::spawn(move || {
+        handle.wait_for_action(|id| {
+            let Some(action) = NotificationAction::from_id(id) else {
+                return;
+            };
+            if let Err(e) = action.run(&app) {
+                warn!("Notification action {:?} failed: {}", action, e);
+            }
+        });
+    });
+    Ok(())
+}
@@ -0,0 +1,306 @@
+//! OAuth 2.0 device authorization grant (RFC 8628) for post-processing
+//! providers that sit behind an identity provider, such as Azure AD protected
+//! gateways or corporate SSO proxies, instead of taking a static API key.
+//!
+//! The user signs in on a second screen with a short code while we poll the
+//! token endpoint. Tokens are kept in the settings next to the API keys and
+//! refreshed shortly before they expire.
+
+use crate::settings::{self, OAuthConfig, OAuthToken};
+use log::{debug, info};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// Refresh this long before the token expires so a request doesn't race it
+const EXPIRY_MARGIN_SECS: i64 = 60;
+/// RFC 8628 §3.5: back off by five seconds when asked to slow down
+const SLOW_DOWN_SECS: u64 = 5;
+
+fn default_interval() -> u64 {
+    5
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    /// Some older servers (Azure AD v1, Google) call this `verification_url`
+    #[serde(alias = "verification_url")]
+    pub verification_uri: String,
+    #[serde(default)]
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: u64,
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+#[derive(Debug)]
+enum Poll {
+    Pending,
+    SlowDown,
+    Token(OAuthToken),
+}
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+fn describe_error(status: StatusCode, body: &str) -> String {
+    match serde_json::from_str::<ErrorResponse>(body) {
+        Ok(error) => match error.error_description {
+            Some(description) => format!("{}: {}", error.error, description),
+            None => error.error,
+        },
+        Err(_) => format!("status {}: {}", status, body),
+    }
+}
+
+/// Interprets a token endpoint response. `previous_refresh` is kept when a
+/// refresh doesn't hand out a new refresh token.
+fn parse_token_response(
+    status: StatusCode,
+    body: &str,
+    previous_refresh: Option<&str>,
+    issued_at: i64,
+) -> Result<Poll, String> {
+    if status.is_success() {
+        let token: TokenResponse = serde_json::from_str(body)
+            .map_err(|e| format!("Failed to parse token response: {}", e))?;
+        return Ok(Poll::Token(OAuthToken {
+            access_token: token.access_token,
+            refresh_token: token
+                .refresh_token
+                .or_else(|| previous_refresh.map(str::to_string)),
+            expires_at: token.expires_in.map(|secs| issued_at + secs),
+        }));
+    }
+
+    match serde_json::from_str::<ErrorResponse>(body) {
+        Ok(error) if error.error == "authorization_pending" => Ok(Poll::Pending),
+        Ok(error) if error.error == "slow_down" => Ok(Poll::SlowDown),
+        _ => Err(describe_error(status, body)),
+    }
+}
+
+fn is_expiring(token: &OAuthToken, now: i64) -> bool {
+    token
+        .expires_at
+        .is_some_and(|expires_at| expires_at - EXPIRY_MARGIN_SECS <= now)
+}
+
+async fn post_token_request(
+    config: &OAuthConfig,
+    form: &[(&str, &str)],
+    previous_refresh: Option<&str>,
+) -> Result<Poll, String> {
+    let issued_at = now();
+    let response = reqwest::Client::new()
+        .post(&config.token_url)
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| format!("Token request failed: {}", e))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read token response: {}", e))?;
+    parse_token_response(status, &body, previous_refresh, issued_at)
+}
+
+/// Starts a sign-in and returns the code the user has to enter.
+pub async fn request_device_code(config: &OAuthConfig) -> Result<DeviceAuthorization, String> {
+    let mut form = vec![("client_id", config.client_id.as_str())];
+    if !config.scope.is_empty() {
+        form.push(("scope", config.scope.as_str()));
+    }
+
+    let response = reqwest::Client::new()
+        .post(&config.device_authorization_url)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| format!("Device authorization request failed: {}", e))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read device authorization response: {}", e))?;
+    if !status.is_success() {
+        return Err(format!(
+            "Device authorization failed: {}",
+            describe_error(status, &body)
+        ));
+    }
+
+    serde_json::from_str(&body)
+        .map_err(|e| format!("Failed to parse device authorization response: {}", e))
+}
+
+/// Polls the token endpoint until the user finishes signing in, the code
+/// expires, or `is_cancelled` returns true.
+pub async fn wait_for_token(
+    config: &OAuthConfig,
+    authorization: &DeviceAuthorization,
+    is_cancelled: impl Fn() -> bool,
+) -> Result<OAuthToken, String> {
+    let deadline = Instant::now() + Duration::from_secs(authorization.expires_in);
+    let mut interval = authorization.interval.max(1);
+
+    loop {
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+        if is_cancelled() {
+            return Err("Sign-in was cancelled".to_string());
+        }
+        if Instant::now() >= deadline {
+            return Err("The sign-in code expired. Please try again.".to_string());
+        }
+
+        let form = [
+            ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+            ("device_code", authorization.device_code.as_str()),
+            ("client_id", config.client_id.as_str()),
+        ];
+        match post_token_request(config, &form, None).await? {
+            Poll::Pending => {}
+            Poll::SlowDown => interval += SLOW_DOWN_SECS,
+            Poll::Token(token) => return Ok(token),
+        }
+    }
+}
+
+async fn refresh(config: &OAuthConfig, refresh_token: &str) -> Result<OAuthToken, String> {
+    let mut form = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token),
+        ("client_id", config.client_id.as_str()),
+    ];
+    if !config.scope.is_empty() {
+        form.push(("scope", config.scope.as_str()));
+    }
+
+    match post_token_request(config, &form, Some(refresh_token)).await {
+        Ok(Poll::Token(token)) => Ok(token),
+        Ok(_) => Err("Unexpected response while refreshing the token".to_string()),
+        Err(e) => Err(format!("Failed to refresh the sign-in: {}", e)),
+    }
+}
+
+/// Returns the access token for a provider that uses OAuth, refreshing it if
+/// it's about to expire or `force_refresh` is set (e.g. after a 401). Returns
+/// `None` for providers that use an API key.
+pub async fn access_token(
+    app: &AppHandle,
+    provider_id: &str,
+    force_refresh: bool,
+) -> Result<Option<String>, String> {
+    let settings = settings::get_settings(app);
+    let Some(config) = settings.post_process_oauth.get(provider_id).cloned() else {
+        return Ok(None);
+    };
+    let label = settings
+        .post_process_provider(provider_id)
+        .map(|provider| provider.label.clone())
+        .unwrap_or_else(|| provider_id.to_string());
+    let token = settings
+        .post_process_oauth_tokens
+        .get(provider_id)
+        .cloned()
+        .ok_or_else(|| format!("Not signed in to {}", label))?;
+
+    if !force_refresh && !is_expiring(&token, now()) {
+        return Ok(Some(token.access_token));
+    }
+
+    let Some(refresh_token) = token.refresh_token.as_deref() else {
+        return Err(format!(
+            "The sign-in for {} has expired. Please sign in again.",
+            label
+        ));
+    };
+    debug!("Refreshing OAuth token for provider '{}'", provider_id);
+    let refreshed = refresh(&config, refresh_token).await?;
+    let access_token = refreshed.access_token.clone();
+
+    let mut settings = settings::get_settings(app);
+    settings
+        .post_process_oauth_tokens
+        .insert(provider_id.to_string(), refreshed);
+    settings::write_settings(app, settings);
+    info!("Refreshed OAuth token for provider '{}'", provider_id);
+
+    Ok(Some(access_token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_polling_while_authorization_is_pending() {
+        let body = r#"{"error":"authorization_pending"}"#;
+        let poll = parse_token_response(StatusCode::BAD_REQUEST, body, None, 0).unwrap();
+        assert!(matches!(poll, Poll::Pending));
+
+        let body = r#"{"error":"slow_down"}"#;
+        let poll = parse_token_response(StatusCode::BAD_REQUEST, body, None, 0).unwrap();
+        assert!(matches!(poll, Poll::SlowDown));
+    }
+
+    #[test]
+    fn reports_terminal_errors() {
+        let body = r#"{"error":"access_denied","error_description":"User declined"}"#;
+        let err = parse_token_response(StatusCode::BAD_REQUEST, body, None, 0).unwrap_err();
+        assert_eq!(err, "access_denied: User declined");
+    }
+
+    #[test]
+    fn keeps_refresh_token_and_computes_expiry() {
+        let body = r#"{"access_token":"new","expires_in":3600}"#;
+        let Poll::Token(token) =
+            parse_token_response(StatusCode::OK, body, Some("old"), 100).unwrap()
+        else {
+            panic!("expected a token");
+        };
+        assert_eq!(token.access_token, "new");
+        assert_eq!(token.refresh_token.as_deref(), Some("old"));
+        assert_eq!(token.expires_at, Some(3700));
+    }
+
+    #[test]
+    fn refreshes_shortly_before_expiry() {
+        let token = OAuthToken {
+            access_token: "token".to_string(),
+            refresh_token: None,
+            expires_at: Some(1000),
+        };
+        assert!(!is_expiring(&token, 900));
+        assert!(is_expiring(&token, 950));
+        assert!(!is_expiring(
+            &OAuthToken {
+                expires_at: None,
+                ..token
+            },
+            5000
+        ));
+    }
+}
@@ -0,0 +1,472 @@
+//! Streaming transcription over WebSocket to OpenAI's realtime transcription
+//! API, or any server speaking the same protocol.
+//!
+//! While a dictation records, the captured audio is sent as it comes in and
+//! the server's partial transcripts are emitted as `transcription-partial`
+//! so the overlay can show text before the recording ends. When recording
+//! stops, the rest of the audio is committed and the finished transcript is
+//! used for the dictation. If anything goes wrong the recording is
+//! transcribed the usual way instead.
+//!
+//! The session itself is shared with remote providers that stream, like
+//! Deepgram's live API, which `start` uses instead when the selected one has
+//! streaming on.
+
+use crate::managers::audio::AudioRecordingManager;
+use crate::settings::{get_settings, RealtimeEndpoint};
+use crate::transcription_provider;
+use crate::wyoming;
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use handy_transcription::audio::{to_pcm16, FrameResampler};
+use handy_transcription::constants;
+use log::{debug, info, warn};
+use serde::Deserialize;
+use serde_json::json;
+use std::io::ErrorKind;
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tungstenite::client::IntoClientRequest;
+use tungstenite::http::HeaderValue;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Error as WsError, Message, WebSocket};
+
+/// The realtime API takes 24 kHz mono PCM
+const API_SAMPLE_RATE: usize = 24_000;
+/// How long a socket read waits before more audio is sent
+pub const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// How long the server gets to finish the transcript after recording stops
+pub const FINISH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The session streaming the current dictation, if there is one.
+pub type ManagedRealtime = Mutex<Option<Session>>;
+
+pub struct Session {
+    finish_tx: mpsc::Sender<Vec<f32>>,
+    cancelled: Arc<AtomicBool>,
+    handle: JoinHandle<Result<String>>,
+}
+
+/// A piece of the recording being streamed
+pub enum Chunk {
+    /// Audio recorded since the last read
+    Audio(Vec<f32>),
+    /// The rest of the audio, once recording stopped
+    Last(Vec<f32>),
+}
+
+/// The recording of a dictation, read as it comes in
+pub struct AudioFeed<'a> {
+    app: &'a AppHandle,
+    binding_id: &'a str,
+    finish_rx: mpsc::Receiver<Vec<f32>>,
+    cancelled: &'a AtomicBool,
+    sent: usize,
+    finished: bool,
+}
+
+impl AudioFeed<'_> {
+    /// Reads what was recorded since the last call, or `None` once the last
+    /// chunk was read. Fails if the dictation was cancelled.
+    pub fn next(&mut self) -> Result<Option<Chunk>> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(anyhow!("Streaming transcription cancelled"));
+        }
+        if self.finished {
+            return Ok(None);
+        }
+        match self.finish_rx.try_recv() {
+            Ok(samples) => {
+                self.finished = true;
+                let rest = samples.get(self.sent..).unwrap_or_default();
+                Ok(Some(Chunk::Last(rest.to_vec())))
+            }
+            Err(TryRecvError::Empty) => {
+                // Nothing new once recording stops, until `finish` sends the rest
+                let samples = self
+                    .app
+                    .state::<Arc<AudioRecordingManager>>()
+                    .peek_recording(self.binding_id, self.sent)
+                    .unwrap_or_default();
+                self.sent += samples.len();
+                Ok(Some(Chunk::Audio(samples)))
+            }
+            Err(TryRecvError::Disconnected) => Err(anyhow!("Streaming session dropped")),
+        }
+    }
+}
+
+/// Sets a read timeout on the socket, so reading doesn't hold up sending
+pub fn set_poll_timeout(socket: &WebSocket<MaybeTlsStream<TcpStream>>) -> Result<()> {
+    match socket.get_ref() {
+        MaybeTlsStream::Plain(stream) => stream.set_read_timeout(Some(POLL_INTERVAL))?,
+        MaybeTlsStream::NativeTls(stream) => {
+            stream.get_ref().set_read_timeout(Some(POLL_INTERVAL))?
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Whether a failed socket read only timed out
+pub fn is_timeout(error: &WsError) -> bool {
+    matches!(error, WsError::Io(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut))
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct ServerError {
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "type")]
+enum ServerEvent {
+    #[serde(rename = "input_audio_buffer.committed")]
+    Committed { item_id: String },
+    #[serde(rename = "conversation.item.input_audio_transcription.delta")]
+    Delta { item_id: String, delta: String },
+    #[serde(rename = "conversation.item.input_audio_transcription.completed")]
+    Completed { item_id: String, transcript: String },
+    #[serde(rename = "conversation.item.input_audio_transcription.failed")]
+    Failed { item_id: String, error: ServerError },
+    #[serde(rename = "error")]
+    Error { error: ServerError },
+    #[serde(other)]
+    Other,
+}
+
+struct Item {
+    id: String,
+    text: String,
+    done: bool,
+}
+
+/// The transcript so far, one item per utterance the server committed
+#[derive(Default)]
+struct Transcript {
+    items: Vec<Item>,
+}
+
+impl Transcript {
+    fn item(&mut self, id: &str) -> &mut Item {
+        match self.items.iter().position(|item| item.id == id) {
+            Some(index) => &mut self.items[index],
+            None => {
+                self.items.push(Item {
+                    id: id.to_string(),
+                    text: String::new(),
+                    done: false,
+                });
+                self.items.last_mut().unwrap()
+            }
+        }
+    }
+
+    fn delta(&mut self, id: &str, delta: &str) {
+        let item = self.item(id);
+        if !item.done {
+            item.text.push_str(delta);
+        }
+    }
+
+    fn complete(&mut self, id: &str, text: &str) {
+        let item = self.item(id);
+        item.text = text.to_string();
+        item.done = true;
+    }
+
+    fn is_done(&self) -> bool {
+        self.items.iter().all(|item| item.done)
+    }
+
+    fn text(&self) -> String {
+        self.items
+            .iter()
+            .map(|item| item.text.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn connect(endpoint: &RealtimeEndpoint) -> Result<WebSocket<MaybeTlsStream<TcpStream>>> {
+    let mut request = endpoint.url.as_str().into_client_request()?;
+    if !endpoint.api_key.is_empty() {
+        request.headers_mut().insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Bearer {}", endpoint.api_key))?,
+        );
+    }
+    request
+        .headers_mut()
+        .insert("OpenAI-Beta", HeaderValue::from_static("realtime=v1"));
+
+    let (socket, _) = tungstenite::connect(request)
+        .map_err(|e| anyhow!("Couldn't connect to {}: {}", endpoint.url, e))?;
+    set_poll_timeout(&socket)?;
+    Ok(socket)
+}
+
+fn send_json(
+    socket: &mut WebSocket<MaybeTlsStream<TcpStream>>,
+    event: serde_json::Value,
+) -> Result<()> {
+    socket.send(Message::Text(event.to_string()))?;
+    Ok(())
+}
+
+fn send_audio(socket: &mut WebSocket<MaybeTlsStream<TcpStream>>, samples: &[f32]) -> Result<()> {
+    if samples.is_empty() {
+        return Ok(());
+    }
+    send_json(
+        socket,
+        json!({
+            "type": "input_audio_buffer.append",
+            "audio": STANDARD.encode(to_pcm16(samples)),
+        }),
+    )
+}
+
+fn stream(
+    app: &AppHandle,
+    endpoint: &RealtimeEndpoint,
+    language: Option<String>,
+    feed: &mut AudioFeed,
+) -> Result<String> {
+    let mut socket = connect(endpoint)?;
+    let mut transcription = json!({ "model": endpoint.model });
+    if let Some(language) = language {
+        transcription["language"] = json!(language);
+    }
+    send_json(
+        &mut socket,
+        json!({
+            "type": "transcription_session.update",
+            "session": {
+                "input_audio_format": "pcm16",
+                "input_audio_transcription": transcription,
+                "turn_detection": { "type": "server_vad" },
+            },
+        }),
+    )?;
+    info!("Streaming dictation to {}", endpoint.url);
+
+    let mut resampler = FrameResampler::new(
+        constants::WHISPER_SAMPLE_RATE as usize,
+        API_SAMPLE_RATE,
+        Duration::from_millis(30),
+    );
+    let mut transcript = Transcript::default();
+    let mut finished_at: Option<Instant> = None;
+    let mut committed = false;
+
+    loop {
+        let mut audio = Vec::new();
+        match feed.next()? {
+            Some(Chunk::Audio(samples)) => {
+                resampler.push(&samples, |frame| audio.extend_from_slice(frame));
+                send_audio(&mut socket, &audio)?;
+            }
+            Some(Chunk::Last(samples)) => {
+                resampler.push(&samples, |frame| audio.extend_from_slice(frame));
+                resampler.finish(|frame| audio.extend_from_slice(frame));
+                send_audio(&mut socket, &audio)?;
+                send_json(&mut socket, json!({ "type": "input_audio_buffer.commit" }))?;
+                finished_at = Some(Instant::now());
+            }
+            None => {}
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                let event = serde_json::from_str(&text).unwrap_or(ServerEvent::Other);
+                match event {
+                    ServerEvent::Committed { item_id } => {
+                        transcript.item(&item_id);
+                        committed |= finished_at.is_some();
+                    }
+                    ServerEvent::Delta { item_id, delta } => {
+                        transcript.delta(&item_id, &delta);
+                        let _ = app.emit("transcription-partial", transcript.text());
+                    }
+                    ServerEvent::Completed {
+                        item_id,
+                        transcript: text,
+                    } => {
+                        transcript.complete(&item_id, &text);
+                        let _ = app.emit("transcription-partial", transcript.text());
+                    }
+                    ServerEvent::Failed { item_id, error } => {
+                        warn!(
+                            "Realtime transcription of an utterance failed: {}",
+                            error.message
+                        );
+                        transcript.complete(&item_id, "");
+                    }
+                    ServerEvent::Error { error } => {
+                        // Committing after the server already took all the
+                        // audio fails with an empty buffer, which is fine
+                        if finished_at.is_some()
+                            && error.code.as_deref() == Some("input_audio_buffer_commit_empty")
+                        {
+                            committed = true;
+                        } else {
+                            return Err(anyhow!("Realtime server error: {}", error.message));
+                        }
+                    }
+                    ServerEvent::Other => {}
+                }
+            }
+            Ok(Message::Close(_)) => return Err(anyhow!("Realtime server closed the connection")),
+            Ok(_) => {}
+            Err(e) if is_timeout(&e) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        if let Some(finished_at) = finished_at {
+            if committed && transcript.is_done() {
+                let _ = socket.close(None);
+                return Ok(transcript.text());
+            }
+            if finished_at.elapsed() > FINISH_TIMEOUT {
+                return Err(anyhow!(
+                    "Realtime server didn't finish the transcript in time"
+                ));
+            }
+        }
+    }
+}
+
+/// Starts streaming the recording of `binding_id`, if realtime transcription
+/// or a streaming remote provider is set up.
+pub fn start(app: &AppHandle, binding_id: &str) {
+    let settings = get_settings(app);
+    // A remote provider that streams takes precedence over the realtime
+    // endpoint
+    let provider = transcription_provider::remote(&settings)
+        .filter(|provider| provider.capabilities(&settings).streaming);
+    if provider.is_none()
+        && (!settings.realtime_transcription || settings.realtime_endpoint.url.is_empty())
+    {
+        return;
+    }
+
+    let (finish_tx, finish_rx) = mpsc::channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let handle = thread::spawn({
+        let app = app.clone();
+        let binding_id = binding_id.to_string();
+        let cancelled = cancelled.clone();
+        move || {
+            let mut feed = AudioFeed {
+                app: &app,
+                binding_id: &binding_id,
+                finish_rx,
+                cancelled: &cancelled,
+                sent: 0,
+                finished: false,
+            };
+            match provider {
+                Some(provider) => provider.stream(&app, &settings, &mut feed),
+                None => {
+                    let language = wyoming::language(&settings);
+                    stream(&app, &settings.realtime_endpoint, language, &mut feed)
+                }
+            }
+        }
+    });
+
+    let previous = app
+        .state::<ManagedRealtime>()
+        .lock()
+        .unwrap()
+        .replace(Session {
+            finish_tx,
+            cancelled,
+            handle,
+        });
+    if let Some(previous) = previous {
+        previous.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Hands the complete recording to the streaming session and waits for its
+/// transcript. Returns `None` without a session, or if it failed, so the
+/// recording is transcribed the usual way.
+pub async fn finish(app: &AppHandle, samples: &[f32]) -> Option<String> {
+    let session = app.state::<ManagedRealtime>().lock().unwrap().take()?;
+    if session.finish_tx.send(samples.to_vec()).is_err() {
+        debug!("Realtime session ended before recording stopped");
+    }
+    let result = tauri::async_runtime::spawn_blocking(move || session.handle.join())
+        .await
+        .ok()?;
+    match result {
+        Ok(Ok(text)) => Some(text),
+        Ok(Err(e)) => {
+            warn!(
+                "Streaming transcription failed, transcribing the recording instead: {}",
+                e
+            );
+            None
+        }
+        Err(_) => {
+            warn!("Realtime transcription thread panicked");
+            None
+        }
+    }
+}
+
+/// Drops the streaming session of a cancelled dictation.
+pub fn cancel(app: &AppHandle) {
+    if let Some(session) = app.state::<ManagedRealtime>().lock().unwrap().take() {
+        session.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transcript_keeps_utterances_in_commit_order() {
+        let mut transcript = Transcript::default();
+        transcript.item("a");
+        transcript.delta("a", "Hello ");
+        transcript.delta("b", "wor");
+        assert_eq!(transcript.text(), "Hello wor");
+        assert!(!transcript.is_done());
+
+        transcript.complete("b", "world.");
+        transcript.delta("b", " late");
+        transcript.complete("a", "Hello,");
+        assert_eq!(transcript.text(), "Hello, world.");
+        assert!(transcript.is_done());
+    }
+
+    #[test]
+    fn parses_server_events() {
+        let delta = r#"{"type":"conversation.item.input_audio_transcription.delta","event_id":"e1","item_id":"i1","content_index":0,"delta":"Hi"}"#;
+        assert_eq!(
+            serde_json::from_str::<ServerEvent>(delta).unwrap(),
+            ServerEvent::Delta {
+                item_id: "i1".to_string(),
+                delta: "Hi".to_string()
+            }
+        );
+        let other = r#"{"type":"session.created","session":{}}"#;
+        assert_eq!(
+            serde_json::from_str::<ServerEvent>(other).unwrap(),
+            ServerEvent::Other
+        );
+    }
+}
@@ -1,10 +1,13 @@
+use crate::helpers::active_app;
 use crate::input;
+use crate::notifications::AppNotification;
 use crate::settings;
-use crate::settings::OverlayPosition;
-use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize};
-
-#[cfg(not(target_os = "macos"))]
+use crate::settings::{AppSettings, OverlayOffset, OverlayPlacement, OverlayPosition};
 use log::debug;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Monitor, PhysicalPosition, PhysicalSize};
 
 #[cfg(not(target_os = "macos"))]
 use tauri::WebviewWindowBuilder;
@@ -25,8 +28,44 @@ tauri_panel! {
     })
 }
 
+#[cfg(target_os = "macos")]
+tauri_panel! {
+    panel!(CaptionsOverlayPanel {
+        config: {
+            can_become_key_window: false,
+            is_floating_panel: true
+        }
+    })
+}
+
 const OVERLAY_WIDTH: f64 = 172.0;
 const OVERLAY_HEIGHT: f64 = 36.0;
+/// Range of `overlay_scale`
+pub const MIN_OVERLAY_SCALE: f32 = 0.75;
+pub const MAX_OVERLAY_SCALE: f32 = 2.0;
+
+/// How long a notice stays in the recording overlay
+const NOTICE_DURATION: Duration = Duration::from_secs(3);
+/// Time the fade-out animation gets before the window is hidden
+const FADE_OUT: Duration = Duration::from_millis(300);
+
+/// Bumped whenever the recording overlay is shown, so a hide that was asked
+/// for earlier doesn't take down what's been shown since
+static OVERLAY_SHOWN: AtomicU64 = AtomicU64::new(0);
+/// Whether the overlay is showing a recording or transcription, which
+/// notices don't interrupt
+static OVERLAY_BUSY: AtomicBool = AtomicBool::new(false);
+/// Physical position the overlay was last put at, to tell our own moves from
+/// the user dragging it
+static PLACED_AT: Mutex<Option<(i32, i32)>> = Mutex::new(None);
+/// Bumped on every move, a drag is saved once the moves stop
+static OVERLAY_MOVES: AtomicU64 = AtomicU64::new(0);
+/// Moves this far apart belong to the same drag
+const DRAG_SETTLE: Duration = Duration::from_millis(500);
+
+const CAPTIONS_OVERLAY_WIDTH: f64 = 720.0;
+const CAPTIONS_OVERLAY_HEIGHT: f64 = 110.0;
+const CAPTIONS_OVERLAY_BOTTOM_OFFSET: f64 = 96.0;
 
 #[cfg(target_os = "macos")]
 const OVERLAY_TOP_OFFSET: f64 = 46.0;
@@ -70,19 +109,51 @@ fn force_overlay_topmost(overlay_window: &tauri::webview::WebviewWindow) {
 }
 
 fn get_monitor_with_cursor(app_handle: &AppHandle) -> Option<tauri::Monitor> {
-    if let Some(mouse_location) = input::get_cursor_position(app_handle) {
-        if let Ok(monitors) = app_handle.available_monitors() {
-            for monitor in monitors {
-                let is_within =
-                    is_mouse_within_monitor(mouse_location, monitor.position(), monitor.size());
-                if is_within {
-                    return Some(monitor);
-                }
-            }
+    input::get_cursor_position(app_handle)
+        .and_then(|mouse_location| monitor_at(app_handle, mouse_location))
+        .or_else(|| app_handle.primary_monitor().ok().flatten())
+}
+
+/// The monitor a physical screen position is on
+fn monitor_at(app_handle: &AppHandle, point: (i32, i32)) -> Option<Monitor> {
+    app_handle
+        .available_monitors()
+        .ok()?
+        .into_iter()
+        .find(|monitor| is_mouse_within_monitor(point, monitor.position(), monitor.size()))
+}
+
+/// Key of a monitor in `overlay_positions`. Its name where the OS gives one,
+/// otherwise its size and place in the desktop.
+fn monitor_key(monitor: &Monitor) -> String {
+    monitor.name().cloned().unwrap_or_else(|| {
+        let (position, size) = (monitor.position(), monitor.size());
+        format!(
+            "{}x{}@{},{}",
+            size.width, size.height, position.x, position.y
+        )
+    })
+}
+
+/// The monitor the recording overlay goes on, per `overlay_placement`
+fn overlay_monitor(app_handle: &AppHandle, settings: &AppSettings) -> Option<Monitor> {
+    if settings.overlay_placement == OverlayPlacement::FollowFocusedWindow {
+        // Where the focused window can't be read this falls back to the cursor
+        if let Some(monitor) =
+            active_app::active_window_center().and_then(|center| monitor_at(app_handle, center))
+        {
+            return Some(monitor);
         }
     }
+    get_monitor_with_cursor(app_handle)
+}
 
-    app_handle.primary_monitor().ok().flatten()
+/// Logical size of the recording overlay at the chosen scale
+fn overlay_size(settings: &AppSettings) -> (f64, f64) {
+    let scale = settings
+        .overlay_scale
+        .clamp(MIN_OVERLAY_SCALE, MAX_OVERLAY_SCALE) as f64;
+    (OVERLAY_WIDTH * scale, OVERLAY_HEIGHT * scale)
 }
 
 fn is_mouse_within_monitor(
@@ -107,28 +178,95 @@ fn is_mouse_within_monitor(
 }
 
 fn calculate_overlay_position(app_handle: &AppHandle) -> Option<(f64, f64)> {
-    if let Some(monitor) = get_monitor_with_cursor(app_handle) {
+    let settings = settings::get_settings(app_handle);
+    let monitor = overlay_monitor(app_handle, &settings)?;
+    let work_area = monitor.work_area();
+    let scale = monitor.scale_factor();
+    let work_area_width = work_area.size.width as f64 / scale;
+    let work_area_height = work_area.size.height as f64 / scale;
+    let work_area_x = work_area.position.x as f64 / scale;
+    let work_area_y = work_area.position.y as f64 / scale;
+    let (width, height) = overlay_size(&settings);
+
+    if settings.overlay_placement == OverlayPlacement::Remembered {
+        if let Some(offset) = settings.overlay_positions.get(&monitor_key(&monitor)) {
+            // Kept on screen in case the work area shrank since
+            let x = offset.x.clamp(0.0, (work_area_width - width).max(0.0));
+            let y = offset.y.clamp(0.0, (work_area_height - height).max(0.0));
+            return Some((work_area_x + x, work_area_y + y));
+        }
+    }
+
+    let x = work_area_x + (work_area_width - width) / 2.0;
+    let y = match settings.overlay_position {
+        OverlayPosition::Top => work_area_y + OVERLAY_TOP_OFFSET,
+        OverlayPosition::Bottom | OverlayPosition::None => {
+            // don't subtract the overlay height it puts it too far up, only
+            // what scaling it up added
+            work_area_y + work_area_height - OVERLAY_BOTTOM_OFFSET - (height - OVERLAY_HEIGHT)
+        }
+    };
+
+    Some((x, y))
+}
+
+/// Sizes and moves the recording overlay for the current settings and
+/// monitors
+fn place_overlay(app_handle: &AppHandle, overlay_window: &tauri::WebviewWindow) {
+    let settings = settings::get_settings(app_handle);
+    let (width, height) = overlay_size(&settings);
+    let _ = overlay_window.set_size(tauri::Size::Logical(tauri::LogicalSize { width, height }));
+    let _ = overlay_window.set_zoom(width / OVERLAY_WIDTH);
+    if let Some((x, y)) = calculate_overlay_position(app_handle) {
+        let _ =
+            overlay_window.set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }));
+    }
+    if let Ok(position) = overlay_window.outer_position() {
+        *PLACED_AT.lock().unwrap() = Some((position.x, position.y));
+    }
+}
+
+/// Remembers where the user dragged the recording overlay to, on the monitor
+/// it was dropped on. Called for every move of the window, the position is
+/// saved once they stop.
+pub fn overlay_moved(app_handle: &AppHandle, position: PhysicalPosition<i32>) {
+    let position = (position.x, position.y);
+    if *PLACED_AT.lock().unwrap() == Some(position) {
+        return;
+    }
+    if settings::get_settings(app_handle).overlay_placement != OverlayPlacement::Remembered {
+        return;
+    }
+
+    let moves = OVERLAY_MOVES.fetch_add(1, Ordering::SeqCst) + 1;
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(DRAG_SETTLE);
+        if OVERLAY_MOVES.load(Ordering::SeqCst) != moves {
+            return;
+        }
+        let Some(monitor) = monitor_at(&app_handle, position) else {
+            return;
+        };
         let work_area = monitor.work_area();
         let scale = monitor.scale_factor();
-        let work_area_width = work_area.size.width as f64 / scale;
-        let work_area_height = work_area.size.height as f64 / scale;
-        let work_area_x = work_area.position.x as f64 / scale;
-        let work_area_y = work_area.position.y as f64 / scale;
-
-        let settings = settings::get_settings(app_handle);
-
-        let x = work_area_x + (work_area_width - OVERLAY_WIDTH) / 2.0;
-        let y = match settings.overlay_position {
-            OverlayPosition::Top => work_area_y + OVERLAY_TOP_OFFSET,
-            OverlayPosition::Bottom | OverlayPosition::None => {
-                // don't subtract the overlay height it puts it too far up
-                work_area_y + work_area_height - OVERLAY_BOTTOM_OFFSET
-            }
+        let offset = OverlayOffset {
+            x: (position.0 - work_area.position.x) as f64 / scale,
+            y: (position.1 - work_area.position.y) as f64 / scale,
         };
-
-        return Some((x, y));
-    }
-    None
+        debug!(
+            "Overlay dragged to {:.0},{:.0} on {}",
+            offset.x,
+            offset.y,
+            monitor_key(&monitor)
+        );
+        let mut settings = settings::get_settings(&app_handle);
+        settings
+            .overlay_positions
+            .insert(monitor_key(&monitor), offset);
+        settings::write_settings(&app_handle, settings);
+        *PLACED_AT.lock().unwrap() = Some(position);
+    });
 }
 
 /// Creates the recording overlay window and keeps it hidden by default
@@ -214,10 +352,7 @@ pub fn show_recording_overlay(app_handle: &AppHandle) {
 
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
         // Update position before showing to prevent flicker from position changes
-        if let Some((x, y)) = calculate_overlay_position(app_handle) {
-            let _ = overlay_window
-                .set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }));
-        }
+        place_overlay(app_handle, &overlay_window);
 
         let _ = overlay_window.show();
 
@@ -225,6 +360,8 @@ pub fn show_recording_overlay(app_handle: &AppHandle) {
         #[cfg(target_os = "windows")]
         force_overlay_topmost(&overlay_window);
 
+        OVERLAY_SHOWN.fetch_add(1, Ordering::SeqCst);
+        OVERLAY_BUSY.store(true, Ordering::SeqCst);
         // Emit event to trigger fade-in animation with recording state
         let _ = overlay_window.emit("show-overlay", "recording");
     }
@@ -247,18 +384,45 @@ pub fn show_transcribing_overlay(app_handle: &AppHandle) {
         #[cfg(target_os = "windows")]
         force_overlay_topmost(&overlay_window);
 
+        OVERLAY_SHOWN.fetch_add(1, Ordering::SeqCst);
+        OVERLAY_BUSY.store(true, Ordering::SeqCst);
         // Emit event to switch to transcribing state
         let _ = overlay_window.emit("show-overlay", "transcribing");
     }
 }
 
-/// Updates the overlay window position based on current settings
+/// Shows a notification in the recording overlay for a few seconds, unless
+/// the overlay is busy with a recording
+pub fn show_notice_overlay(app_handle: &AppHandle, notification: &AppNotification) {
+    let settings = settings::get_settings(app_handle);
+    if settings.overlay_position == OverlayPosition::None || OVERLAY_BUSY.load(Ordering::SeqCst) {
+        return;
+    }
+
+    update_overlay_position(app_handle);
+
+    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        let _ = overlay_window.show();
+
+        #[cfg(target_os = "windows")]
+        force_overlay_topmost(&overlay_window);
+
+        let shown = OVERLAY_SHOWN.fetch_add(1, Ordering::SeqCst) + 1;
+        let _ = overlay_window.emit("show-notice", notification);
+        std::thread::spawn(move || {
+            std::thread::sleep(NOTICE_DURATION);
+            if OVERLAY_SHOWN.load(Ordering::SeqCst) == shown {
+                let _ = overlay_window.emit("hide-overlay", ());
+                hide_after_fade(overlay_window, shown);
+            }
+        });
+    }
+}
+
+/// Updates the overlay window size and position based on current settings
 pub fn update_overlay_position(app_handle: &AppHandle) {
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
-        if let Some((x, y)) = calculate_overlay_position(app_handle) {
-            let _ = overlay_window
-                .set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }));
-        }
+        place_overlay(app_handle, &overlay_window);
     }
 }
 
@@ -266,11 +430,134 @@ pub fn update_overlay_position(app_handle: &AppHandle) {
 pub fn hide_recording_overlay(app_handle: &AppHandle) {
     // Always hide the overlay regardless of settings - if setting was changed while recording,
     // we still want to hide it properly
+    OVERLAY_BUSY.store(false, Ordering::SeqCst);
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
         // Emit event to trigger fade-out animation
         let _ = overlay_window.emit("hide-overlay", ());
         // Hide the window after a short delay to allow animation to complete
-        let window_clone = overlay_window.clone();
+        hide_after_fade(overlay_window, OVERLAY_SHOWN.load(Ordering::SeqCst));
+    }
+}
+
+/// Hides the window once the fade-out is over, if nothing was shown in it
+/// after `shown`
+fn hide_after_fade(overlay_window: tauri::WebviewWindow, shown: u64) {
+    std::thread::spawn(move || {
+        std::thread::sleep(FADE_OUT);
+        if OVERLAY_SHOWN.load(Ordering::SeqCst) == shown {
+            let _ = overlay_window.hide();
+        }
+    });
+}
+
+fn calculate_captions_overlay_position(app_handle: &AppHandle) -> Option<(f64, f64)> {
+    let monitor = get_monitor_with_cursor(app_handle)?;
+    let work_area = monitor.work_area();
+    let scale = monitor.scale_factor();
+    let work_area_width = work_area.size.width as f64 / scale;
+    let work_area_height = work_area.size.height as f64 / scale;
+    let work_area_x = work_area.position.x as f64 / scale;
+    let work_area_y = work_area.position.y as f64 / scale;
+
+    // Sit above the recording overlay so the two never overlap
+    let x = work_area_x + (work_area_width - CAPTIONS_OVERLAY_WIDTH) / 2.0;
+    let y =
+        work_area_y + work_area_height - CAPTIONS_OVERLAY_HEIGHT - CAPTIONS_OVERLAY_BOTTOM_OFFSET;
+    Some((x, y))
+}
+
+/// Creates the live captions overlay window and keeps it hidden by default
+#[cfg(not(target_os = "macos"))]
+pub fn create_captions_overlay(app_handle: &AppHandle) {
+    if let Some((x, y)) = calculate_captions_overlay_position(app_handle) {
+        match WebviewWindowBuilder::new(
+            app_handle,
+            "captions_overlay",
+            tauri::WebviewUrl::App("src/captions/index.html".into()),
+        )
+        .title("Live Captions")
+        .position(x, y)
+        .resizable(false)
+        .inner_size(CAPTIONS_OVERLAY_WIDTH, CAPTIONS_OVERLAY_HEIGHT)
+        .shadow(false)
+        .maximizable(false)
+        .minimizable(false)
+        .closable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .transparent(true)
+        .focused(false)
+        .visible(false)
+        .build()
+        {
+            Ok(_window) => {
+                debug!("Captions overlay window created successfully (hidden)");
+            }
+            Err(e) => {
+                debug!("Failed to create captions overlay window: {}", e);
+            }
+        }
+    }
+}
+
+/// Creates the live captions overlay panel and keeps it hidden by default (macOS)
+#[cfg(target_os = "macos")]
+pub fn create_captions_overlay(app_handle: &AppHandle) {
+    if let Some((x, y)) = calculate_captions_overlay_position(app_handle) {
+        match PanelBuilder::<_, CaptionsOverlayPanel>::new(app_handle, "captions_overlay")
+            .url(WebviewUrl::App("src/captions/index.html".into()))
+            .title("Live Captions")
+            .position(tauri::Position::Logical(tauri::LogicalPosition { x, y }))
+            .level(PanelLevel::Status)
+            .size(tauri::Size::Logical(tauri::LogicalSize {
+                width: CAPTIONS_OVERLAY_WIDTH,
+                height: CAPTIONS_OVERLAY_HEIGHT,
+            }))
+            .has_shadow(false)
+            .transparent(true)
+            .no_activate(true)
+            .corner_radius(0.0)
+            .with_window(|w| w.decorations(false).transparent(true))
+            .collection_behavior(
+                CollectionBehavior::new()
+                    .can_join_all_spaces()
+                    .full_screen_auxiliary(),
+            )
+            .build()
+        {
+            Ok(panel) => {
+                let _ = panel.hide();
+            }
+            Err(e) => {
+                log::error!("Failed to create captions overlay panel: {}", e);
+            }
+        }
+    }
+}
+
+/// Shows the live captions overlay on the monitor that currently has the cursor
+pub fn show_captions_overlay(app_handle: &AppHandle) {
+    if let Some(captions_window) = app_handle.get_webview_window("captions_overlay") {
+        if let Some((x, y)) = calculate_captions_overlay_position(app_handle) {
+            let _ = captions_window
+                .set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }));
+        }
+
+        let _ = captions_window.show();
+
+        #[cfg(target_os = "windows")]
+        force_overlay_topmost(&captions_window);
+
+        let _ = captions_window.emit("show-captions", ());
+    }
+}
+
+/// Hides the live captions overlay with fade-out animation
+pub fn hide_captions_overlay(app_handle: &AppHandle) {
+    if let Some(captions_window) = app_handle.get_webview_window("captions_overlay") {
+        let _ = captions_window.emit("hide-captions", ());
+        let window_clone = captions_window.clone();
         std::thread::spawn(move || {
             std::thread::sleep(std::time::Duration::from_millis(300));
             let _ = window_clone.hide();
@@ -0,0 +1,114 @@
+//! Checking that a paste landed in the target app. Some apps drop a paste
+//! that arrives while they're busy or ignore synthetic key presses, and the
+//! dictation was lost without a word.
+//!
+//! The focused field is read through the accessibility API (AX on macOS,
+//! UI Automation on Windows, AT-SPI on Linux) before and after the paste and
+//! the two are compared. Nothing is pressed to find out, so a terminal or an
+//! app with its own key bindings isn't sent stray keys. The text is pasted
+//! another way only when the field is readable and stayed as it was; when
+//! it can't be read, or changed in a way that can't be matched up, the
+//! paste is taken to have worked rather than risk pasting twice.
+//!
+//! Text is compared by its letters and digits only, so an app turning quotes
+//! into smart quotes or line breaks into its own doesn't count as a miss.
+
+use crate::helpers::selection::focused_text;
+use crate::settings::PasteMethod;
+use std::time::Duration;
+
+/// Time for the app to take in the paste before it is read back, and how
+/// often it is read before giving up
+const SETTLE: Duration = Duration::from_millis(150);
+const READS: usize = 4;
+
+fn normalize(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphanumeric()).collect()
+}
+
+/// Whether `text` is in `after` once more than it was in `before`. `None`
+/// when the field changed but not by the text, as when the app reformatted
+/// it: that's no proof it was missed.
+pub fn appeared(before: &str, after: &str, text: &str) -> Option<bool> {
+    let text = normalize(text);
+    if text.is_empty() {
+        return None;
+    }
+    let (before, after) = (normalize(before), normalize(after));
+    if after.matches(text.as_str()).count() > before.matches(text.as_str()).count() {
+        Some(true)
+    } else if after == before {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// The method tried next when `method` didn't get the text in: typing when
+/// pasting failed and the other way around
+pub fn fallback_method(method: PasteMethod) -> Option<PasteMethod> {
+    match method {
+        PasteMethod::CtrlV | PasteMethod::CtrlShiftV | PasteMethod::ShiftInsert => {
+            Some(PasteMethod::Direct)
+        }
+        PasteMethod::Direct | PasteMethod::VirtualKeyboard | PasteMethod::Accessibility => {
+            Some(PasteMethod::CtrlV)
+        }
+        PasteMethod::None => None,
+    }
+}
+
+/// The focused field as it was before pasting
+pub struct Snapshot {
+    before: Option<String>,
+}
+
+impl Snapshot {
+    pub fn take() -> Self {
+        Self {
+            before: focused_text(),
+        }
+    }
+
+    /// Whether `text` is in the target app now. `Some(false)` only when the
+    /// field was read every time and never changed, `None` when it can't be
+    /// told.
+    pub fn landed(&self, text: &str) -> Option<bool> {
+        let before = self.before.as_ref()?;
+        for _ in 0..READS {
+            std::thread::sleep(SETTLE);
+            // The focus moved, or the field stopped exposing its text
+            let after = focused_text()?;
+            match appeared(before, &after, text) {
+                Some(false) => continue,
+                landed => return landed,
+            }
+        }
+        Some(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tells_whether_the_text_arrived() {
+        let text = "Let's meet at \"noon\".";
+        assert_eq!(
+            appeared("Hi ", "Hi Let’s meet at “noon”.", text),
+            Some(true)
+        );
+        assert_eq!(appeared("Hi ", "Hi ", text), Some(false));
+        // A second dictation of the same text counts only once it's there twice
+        let once = "Let's meet at noon.";
+        assert_eq!(appeared(once, once, text), Some(false));
+        assert_eq!(
+            appeared(once, &format!("{} {}", once, once), text),
+            Some(true)
+        );
+        assert_eq!(appeared("", "?", "?"), None);
+        // The field changed without the text showing up, which proves nothing
+        assert_eq!(appeared("Hi ", "Hello ", text), None);
+    }
+}
@@ -0,0 +1,220 @@
+//! Global shortcuts through the XDG desktop portal.
+//!
+//! On Wayland the global-shortcut plugin can only grab keys while an XWayland
+//! window has focus, so hotkeys appear to do nothing. GNOME and KDE expose the
+//! `org.freedesktop.portal.GlobalShortcuts` interface instead: we describe our
+//! bindings, the compositor asks the user to confirm the triggers, and it then
+//! sends activation signals over D-Bus.
+//!
+//! While the session runs it owns the bindings: `register_shortcut` and
+//! `unregister_shortcut` hand changes to `bind` and `unbind`, which have the
+//! session bind the new set again. If the portal is missing or the session
+//! ends, the bindings it had are registered natively instead.
+
+use crate::settings::ShortcutBinding;
+use crate::shortcut;
+use ashpd::desktop::global_shortcuts::{GlobalShortcuts, NewShortcut};
+use ashpd::desktop::Session;
+use futures_util::{stream, StreamExt};
+use log::{debug, info, warn};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::ShortcutState;
+use tokio::sync::Notify;
+
+/// The bindings of the running session
+struct Portal {
+    bindings: BTreeMap<String, ShortcutBinding>,
+    /// Bindings whose activations are ignored while they're being edited
+    muted: HashSet<String>,
+    /// Has the session bind `bindings` again. Changes made before it gets to
+    /// it are bound together.
+    rebind: Arc<Notify>,
+}
+
+static PORTAL: Mutex<Option<Portal>> = Mutex::new(None);
+
+enum Event {
+    Shortcut(String, ShortcutState),
+    Rebind,
+    Closed,
+}
+
+/// Converts our binding syntax (`ctrl+shift+space`) into the trigger syntax
+/// from the XDG shortcuts spec (`CTRL+SHIFT+space`).
+fn to_portal_trigger(binding: &str) -> String {
+    binding
+        .split('+')
+        .map(|part| match part.trim().to_lowercase().as_str() {
+            "ctrl" | "control" => "CTRL".to_string(),
+            "shift" => "SHIFT".to_string(),
+            "alt" | "option" => "ALT".to_string(),
+            "super" | "meta" | "cmd" | "command" | "win" | "windows" => "LOGO".to_string(),
+            key => key.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Hands `bindings` to a portal session, falling back to native
+/// registration when there's no portal or once the session ends.
+pub fn start(app: &AppHandle, bindings: Vec<ShortcutBinding>) {
+    let rebind = Arc::new(Notify::new());
+    *PORTAL.lock().unwrap() = Some(Portal {
+        bindings: bindings.into_iter().map(|b| (b.id.clone(), b)).collect(),
+        muted: HashSet::new(),
+        rebind: rebind.clone(),
+    });
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match run(&app, rebind).await {
+            Ok(()) => warn!("Portal shortcut session ended, falling back to native shortcuts"),
+            Err(e) => warn!(
+                "GlobalShortcuts portal unavailable ({}), falling back to native shortcuts. \
+                 On compositors without the portal (e.g. sway, Hyprland), bind a key to \
+                 `pkill -USR2 -n handy` to toggle transcription.",
+                e
+            ),
+        }
+        // What the session had, with any changes since
+        let bindings = PORTAL
+            .lock()
+            .unwrap()
+            .take()
+            .map(|portal| portal.bindings.into_values().collect())
+            .unwrap_or_default();
+        shortcut::register_all(&app, bindings);
+    });
+}
+
+/// Adds or updates `binding` in the portal session. Returns false when no
+/// session runs, and the binding is to be registered natively.
+pub fn bind(binding: &ShortcutBinding) -> bool {
+    let mut portal = PORTAL.lock().unwrap();
+    let Some(portal) = portal.as_mut() else {
+        return false;
+    };
+    portal.bindings.insert(binding.id.clone(), binding.clone());
+    portal.muted.remove(&binding.id);
+    portal.rebind.notify_one();
+    true
+}
+
+/// Removes binding `id` from the portal session. Returns false when no
+/// session runs.
+pub fn unbind(id: &str) -> bool {
+    let mut portal = PORTAL.lock().unwrap();
+    let Some(portal) = portal.as_mut() else {
+        return false;
+    };
+    if portal.bindings.remove(id).is_some() {
+        portal.rebind.notify_one();
+    }
+    true
+}
+
+/// Ignores activations of binding `id` while `muted`, without binding again,
+/// which would have the compositor ask the user to confirm the triggers
+/// twice for every edit. Returns false when no session runs.
+pub fn mute(id: &str, muted: bool) -> bool {
+    let mut portal = PORTAL.lock().unwrap();
+    let Some(portal) = portal.as_mut() else {
+        return false;
+    };
+    if muted {
+        portal.muted.insert(id.to_string());
+    } else {
+        portal.muted.remove(id);
+    }
+    true
+}
+
+fn is_muted(id: &str) -> bool {
+    PORTAL
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|portal| portal.muted.contains(id))
+}
+
+/// Binds the session's current bindings
+async fn bind_current(
+    proxy: &GlobalShortcuts<'_>,
+    session: &Session<'_, GlobalShortcuts<'_>>,
+) -> ashpd::Result<()> {
+    let bindings: Vec<ShortcutBinding> = PORTAL
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|portal| portal.bindings.values().cloned().collect())
+        .unwrap_or_default();
+    let shortcuts: Vec<NewShortcut> = bindings
+        .iter()
+        .map(|b| {
+            NewShortcut::new(b.id.as_str(), b.description.as_str())
+                .preferred_trigger(Some(to_portal_trigger(&b.current_binding).as_str()))
+        })
+        .collect();
+
+    let response = proxy
+        .bind_shortcuts(session, &shortcuts, None)
+        .await?
+        .response()?;
+    for bound in response.shortcuts() {
+        info!(
+            "Portal shortcut '{}' bound to '{}'",
+            bound.id(),
+            bound.trigger_description()
+        );
+    }
+    Ok(())
+}
+
+/// Binds the session's bindings, then keeps forwarding activations to the
+/// shortcut dispatcher and binding again on changes until the session ends.
+///
+/// Returns an error if the portal isn't available or the first bind request
+/// fails.
+async fn run(app: &AppHandle, rebind: Arc<Notify>) -> ashpd::Result<()> {
+    let proxy = GlobalShortcuts::new().await?;
+    let session = proxy.create_session().await?;
+    bind_current(&proxy, &session).await?;
+
+    let activated = proxy
+        .receive_activated()
+        .await?
+        .map(|event| Event::Shortcut(event.shortcut_id().to_string(), ShortcutState::Pressed));
+    let deactivated = proxy
+        .receive_deactivated()
+        .await?
+        .map(|event| Event::Shortcut(event.shortcut_id().to_string(), ShortcutState::Released));
+    // The signals also stop when the connection to the portal goes
+    let shortcuts = stream::select(activated, deactivated)
+        .chain(stream::once(std::future::ready(Event::Closed)));
+    let closed = session.receive_closed().await?.map(|()| Event::Closed);
+    let rebinds = stream::unfold(rebind, |rebind| async move {
+        rebind.notified().await;
+        Some((Event::Rebind, rebind))
+    });
+    let mut events = std::pin::pin!(stream::select(stream::select(shortcuts, closed), rebinds));
+
+    while let Some(event) = events.next().await {
+        match event {
+            Event::Shortcut(id, state) => {
+                debug!("Portal shortcut '{}': {:?}", id, state);
+                if !is_muted(&id) {
+                    shortcut::handle_shortcut_event(app, &id, &id, state);
+                }
+            }
+            Event::Rebind => {
+                if let Err(e) = bind_current(&proxy, &session).await {
+                    warn!("Failed to bind the changed portal shortcuts: {}", e);
+                }
+            }
+            Event::Closed => break,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+//! Low-power mode. While the machine runs on battery Handy can switch to a
+//! smaller model, keep the microphone closed between recordings and use a
+//! different model unload timeout. Each rule is configured in settings and
+//! only applies while low power mode is enabled.
+
+use crate::managers::audio::{AudioRecordingManager, MicrophoneMode};
+use crate::managers::model::ModelManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::{get_settings, AppSettings, ModelUnloadTimeout};
+use log::{debug, info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+static ON_BATTERY: AtomicBool = AtomicBool::new(false);
+
+/// Whether any battery is currently discharging. `None` if the power state
+/// can't be read, e.g. on desktops without a battery.
+fn read_on_battery() -> Option<bool> {
+    let manager = starship_battery::Manager::new().ok()?;
+    let mut batteries = manager.batteries().ok()?.flatten().peekable();
+    batteries.peek()?;
+    Some(batteries.any(|b| b.state() == starship_battery::State::Discharging))
+}
+
+pub fn is_on_battery() -> bool {
+    ON_BATTERY.load(Ordering::Relaxed)
+}
+
+fn is_low_power(settings: &AppSettings) -> bool {
+    settings.low_power_enabled && is_on_battery()
+}
+
+/// The model to load, taking the low-power model into account when it has
+/// been downloaded.
+pub fn effective_model_id(app: &AppHandle, settings: &AppSettings) -> String {
+    if is_low_power(settings) {
+        if let Some(model_id) = &settings.low_power_model {
+            let mm = app.state::<Arc<ModelManager>>();
+            if mm
+                .get_model_info(model_id)
+                .is_some_and(|info| info.is_downloaded)
+            {
+                return model_id.clone();
+            }
+        }
+    }
+    settings.selected_model.clone()
+}
+
+pub fn effective_unload_timeout(settings: &AppSettings) -> ModelUnloadTimeout {
+    if is_low_power(settings) {
+        if let Some(timeout) = settings.low_power_model_unload_timeout {
+            return timeout;
+        }
+    }
+    settings.model_unload_timeout
+}
+
+pub fn effective_microphone_mode(settings: &AppSettings) -> MicrophoneMode {
+    if (settings.always_on_microphone || settings.wake_word_enabled)
+        && !(is_low_power(settings) && settings.low_power_pause_always_on_microphone)
+    {
+        MicrophoneMode::AlwaysOn
+    } else {
+        MicrophoneMode::OnDemand
+    }
+}
+
+/// Polls the power source and re-applies the rules whenever it changes.
+pub fn start_monitor(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || loop {
+        if let Some(on_battery) = read_on_battery() {
+            if ON_BATTERY.swap(on_battery, Ordering::Relaxed) != on_battery {
+                info!(
+                    "Power source changed: {}",
+                    if on_battery { "battery" } else { "AC" }
+                );
+                let _ = app.emit("power-state-changed", on_battery);
+                apply(&app);
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    });
+}
+
+/// Brings the microphone mode and loaded model in line with the current
+/// power state and settings.
+pub fn apply(app: &AppHandle) {
+    let settings = get_settings(app);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    if let Err(e) = rm.update_mode(effective_microphone_mode(&settings)) {
+        warn!("Failed to update microphone mode for power state: {}", e);
+    }
+
+    // Swap the model now rather than on the next recording, unless it's in use
+    let tm = app.state::<Arc<TranscriptionManager>>();
+    let model_id = effective_model_id(app, &settings);
+    let loaded_other_model = matches!(tm.get_current_model(), Some(id) if id != model_id);
+    if rm.is_recording() || !loaded_other_model {
+        return;
+    }
+    debug!("Switching to model '{}' for power state", model_id);
+    if let Err(e) = tm.unload_model() {
+        warn!("Failed to unload model for power state: {}", e);
+        return;
+    }
+    tm.initiate_model_load();
+}
@@ -0,0 +1,106 @@
+//! The preprocessing chain a recording goes through before it's transcribed.
+//! Recordings are saved to history as they were captured, so transcribing
+//! one again after changing the chain compares the two.
+
+use crate::settings::{PreprocessNode, PreprocessStep};
+use handy_transcription::audio::{
+    high_pass, low_pass, normalize_level, reduce_noise, suppress_noise, trim_silence,
+};
+use handy_transcription::constants;
+use log::debug;
+
+/// Below the lowest voices, so only rumble and DC offset go
+const HIGH_PASS_HZ: f32 = 80.0;
+/// How many times the estimated noise is taken off each frame
+const DENOISE_STRENGTH: f32 = 1.5;
+/// About -20 dBFS
+const TARGET_RMS: f32 = 0.1;
+/// 20 dB, so a whispered recording doesn't bring up the room with it
+const MAX_GAIN: f32 = 10.0;
+/// Silence kept around the speech when trimming
+const TRIM_KEEP_MS: usize = 200;
+
+/// Files recorded at this rate or below are treated as telephone audio
+pub const NARROWBAND_MAX_HZ: u32 = 8_000;
+/// The telephone band. Below it is line hum, above it the hiss and images
+/// of upsampling, neither of which the models have seen much of.
+const NARROWBAND_LOW_HZ: f32 = 300.0;
+const NARROWBAND_HIGH_HZ: f32 = 3_400.0;
+/// Phone recordings are often quiet, so more gain is allowed than for a mic
+const NARROWBAND_MAX_GAIN: f32 = 20.0;
+
+/// Runs `audio` through the nodes of `chain` that aren't bypassed, in order.
+/// `rnnoise_strength` is how much of RNNoise's output the `Rnnoise` node
+/// keeps.
+pub fn run(chain: &[PreprocessStep], rnnoise_strength: f32, audio: Vec<f32>) -> Vec<f32> {
+    let rate = constants::WHISPER_SAMPLE_RATE as usize;
+    chain
+        .iter()
+        .filter(|step| !step.bypass)
+        .fold(audio, |audio, step| {
+            debug!("Preprocessing {} samples: {:?}", audio.len(), step.node);
+            match step.node {
+                PreprocessNode::HighPass => high_pass(&audio, rate, HIGH_PASS_HZ),
+                PreprocessNode::Denoise => reduce_noise(&audio, DENOISE_STRENGTH),
+                PreprocessNode::Rnnoise => suppress_noise(&audio, rnnoise_strength),
+                PreprocessNode::AutoGain => normalize_level(&audio, rate, TARGET_RMS, MAX_GAIN),
+                PreprocessNode::Trim => trim_silence(&audio, rate, TRIM_KEEP_MS),
+            }
+        })
+}
+
+/// Cleans up a file recorded at `NARROWBAND_MAX_HZ` or below, already
+/// upsampled to 16kHz, before it goes through the usual chain: it's cut to
+/// the telephone band and brought up to the level the models expect.
+pub fn narrowband(audio: Vec<f32>) -> Vec<f32> {
+    let rate = constants::WHISPER_SAMPLE_RATE as usize;
+    let audio = high_pass(&audio, rate, NARROWBAND_LOW_HZ);
+    let audio = low_pass(&audio, rate, NARROWBAND_HIGH_HZ);
+    normalize_level(&audio, rate, TARGET_RMS, NARROWBAND_MAX_GAIN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_bypassed_nodes() {
+        let mut audio = vec![0.0; 9_600];
+        audio.extend(vec![0.5; 4_800]);
+        let trim = |bypass| PreprocessStep {
+            node: PreprocessNode::Trim,
+            bypass,
+        };
+        assert_eq!(run(&[trim(true)], 1.0, audio.clone()).len(), audio.len());
+        assert_eq!(run(&[trim(false)], 1.0, audio.clone()).len(), 4_800 + 3_200);
+        assert_eq!(run(&[], 1.0, audio.clone()), audio);
+    }
+
+    #[test]
+    fn narrowband_drops_hum_and_raises_quiet_speech() {
+        let tone = |hz: f32, amplitude: f32| -> Vec<f32> {
+            (0..16_000)
+                .map(|i| amplitude * (2.0 * std::f32::consts::PI * hz * i as f32 / 16_000.0).sin())
+                .collect()
+        };
+        let rms =
+            |audio: &[f32]| (audio[8_000..].iter().map(|s| s * s).sum::<f32>() / 8_000.0).sqrt();
+        // Mains hum under a quiet voice
+        let audio: Vec<f32> = tone(50.0, 0.05)
+            .iter()
+            .zip(tone(1_000.0, 0.02))
+            .map(|(hum, voice)| hum + voice)
+            .collect();
+        let cleaned = narrowband(audio);
+        let voice = tone(1_000.0, 1.0);
+        let correlation = cleaned[8_000..]
+            .iter()
+            .zip(&voice[8_000..])
+            .map(|(a, b)| a * b)
+            .sum::<f32>()
+            / 8_000.0;
+        // What's left is nearly all voice, turned up
+        assert!(rms(&cleaned) > 0.05, "rms {}", rms(&cleaned));
+        assert!(correlation / (rms(&cleaned) * rms(&voice)) > 0.95);
+    }
+}
@@ -0,0 +1,168 @@
+//! Optional preview of a transcript before it is pasted. The text opens in a
+//! small editable popup and is only pasted once confirmed, for targets where a
+//! misheard word is costly, like an email to a customer.
+
+use crate::accessibility::{self, Announcement};
+use crate::notifications;
+use crate::settings::AppSettings;
+use crate::shortcut;
+use crate::tray::{change_tray_icon, TrayIconState};
+use crate::utils;
+use log::{debug, error, info};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, WebviewWindowBuilder};
+
+/// Transcript shown in the preview popup, waiting to be confirmed or cancelled.
+pub type ManagedPendingPreview = Mutex<Option<String>>;
+
+const PREVIEW_LABEL: &str = "preview";
+const PREVIEW_WIDTH: f64 = 480.0;
+const PREVIEW_HEIGHT: f64 = 280.0;
+
+/// Whether a transcript dictated into `target_app` should be previewed. With
+/// no apps listed, every transcript is.
+pub fn wants_preview(settings: &AppSettings, target_app: Option<&str>) -> bool {
+    if !settings.preview_before_paste {
+        return false;
+    }
+    if settings.preview_apps.is_empty() {
+        return true;
+    }
+    target_app.is_some_and(|app| {
+        settings
+            .preview_apps
+            .iter()
+            .any(|listed| listed.trim().eq_ignore_ascii_case(app))
+    })
+}
+
+pub fn has_pending_preview(app: &AppHandle) -> bool {
+    app.state::<ManagedPendingPreview>()
+        .lock()
+        .map(|pending| pending.is_some())
+        .unwrap_or(false)
+}
+
+pub fn pending_text(app: &AppHandle) -> Option<String> {
+    app.state::<ManagedPendingPreview>().lock().unwrap().clone()
+}
+
+/// Holds `text` back from pasting and opens it in the preview popup.
+pub fn begin_preview(app: &AppHandle, text: String) {
+    *app.state::<ManagedPendingPreview>().lock().unwrap() = Some(text.clone());
+    shortcut::register_cancel_shortcut(app);
+    utils::hide_recording_overlay(app);
+
+    if let Some(window) = app.get_webview_window(PREVIEW_LABEL) {
+        let _ = window.center();
+        let _ = window.show();
+        let _ = window.set_focus();
+        // The popup also asks for the text when it first loads
+        let _ = window.emit("show-preview", text);
+        return;
+    }
+
+    let result = WebviewWindowBuilder::new(
+        app,
+        PREVIEW_LABEL,
+        tauri::WebviewUrl::App("src/preview/index.html".into()),
+    )
+    .title("Preview")
+    .inner_size(PREVIEW_WIDTH, PREVIEW_HEIGHT)
+    .center()
+    .resizable(true)
+    .maximizable(false)
+    .minimizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .focused(true)
+    .build();
+    if let Err(e) = result {
+        error!("Failed to open preview window, pasting without it: {}", e);
+        confirm_preview(app, text);
+    }
+}
+
+fn take_pending(app: &AppHandle) -> Option<String> {
+    let pending = app.state::<ManagedPendingPreview>().lock().unwrap().take();
+    if pending.is_some() {
+        shortcut::unregister_cancel_shortcut(app);
+        if let Some(window) = app.get_webview_window(PREVIEW_LABEL) {
+            let _ = window.hide();
+        }
+    }
+    pending
+}
+
+/// Pastes `text`, the transcript as edited in the popup, into the app that had
+/// focus before the popup opened.
+pub fn confirm_preview(app: &AppHandle, text: String) {
+    if take_pending(app).is_none() {
+        return;
+    }
+    info!("Preview confirmed");
+    #[cfg(target_os = "macos")]
+    {
+        let _ = app.hide();
+    }
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(utils::REFOCUS_DELAY);
+        let ah = app.clone();
+        app.run_on_main_thread(move || {
+            match utils::paste(text.clone(), ah.clone()) {
+                Ok(()) => {
+                    notifications::notify_transcribed(&ah, &text);
+                    accessibility::announce(
+                        &ah,
+                        Announcement::Transcribed(accessibility::word_count(&text)),
+                    );
+                }
+                Err(e) => {
+                    error!("Failed to paste transcription: {}", e);
+                    accessibility::announce(&ah, Announcement::PasteFailed);
+                    notifications::notify_paste_failed(&ah, text);
+                }
+            }
+            change_tray_icon(&ah, TrayIconState::Idle);
+        })
+        .unwrap_or_else(|e| {
+            error!("Failed to run paste on main thread: {:?}", e);
+            change_tray_icon(&app, TrayIconState::Idle);
+        });
+    });
+}
+
+/// Closes the popup without pasting. Returns whether anything was pending.
+pub fn discard_preview(app: &AppHandle) -> bool {
+    let discarded = take_pending(app).is_some();
+    if discarded {
+        debug!("Preview discarded");
+        change_tray_icon(app, TrayIconState::Idle);
+    }
+    discarded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+
+    #[test]
+    fn previews_only_the_listed_apps() {
+        let mut settings = get_default_settings();
+        assert!(!wants_preview(&settings, Some("Mail")));
+
+        settings.preview_before_paste = true;
+        assert!(wants_preview(&settings, Some("Mail")));
+        assert!(wants_preview(&settings, None));
+
+        settings.preview_apps = vec!["Mail".to_string(), " outlook ".to_string()];
+        assert!(wants_preview(&settings, Some("mail")));
+        assert!(wants_preview(&settings, Some("Outlook")));
+        assert!(!wants_preview(&settings, Some("Code")));
+        assert!(!wants_preview(&settings, None));
+    }
+}
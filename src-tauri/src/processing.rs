@@ -0,0 +1,70 @@
+//! Cancelling a dictation after recording stopped, while it is still being
+//! transcribed or post-processed. The Cancel shortcut stays registered for
+//! that time; cancelling drops the work, including any request in flight,
+//! so nothing gets pasted.
+
+use crate::managers::audio::AudioRecordingManager;
+use crate::preview;
+use crate::shortcut;
+use crate::tts;
+use futures_util::future::{AbortHandle, Abortable};
+use log::info;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+/// Dictations being processed, by an id handed out in `run`.
+pub type ManagedProcessing = Mutex<HashMap<u64, AbortHandle>>;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+pub fn is_processing(app: &AppHandle) -> bool {
+    app.state::<ManagedProcessing>()
+        .lock()
+        .map(|processing| !processing.is_empty())
+        .unwrap_or(false)
+}
+
+/// Runs `work` until it finishes or is cancelled, returning `None` if it
+/// was cancelled.
+pub async fn run<F: Future>(app: &AppHandle, work: F) -> Option<F::Output> {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let (handle, registration) = AbortHandle::new_pair();
+    app.state::<ManagedProcessing>()
+        .lock()
+        .unwrap()
+        .insert(id, handle);
+    shortcut::register_cancel_shortcut(app);
+
+    let result = Abortable::new(work, registration).await.ok();
+
+    let others = {
+        let mut processing = app.state::<ManagedProcessing>().lock().unwrap();
+        processing.remove(&id);
+        !processing.is_empty()
+    };
+    // A new recording, or a preview or read-back started by `work`, still
+    // needs the shortcut
+    let recording = app.state::<Arc<AudioRecordingManager>>().is_recording();
+    if !others
+        && !recording
+        && !preview::has_pending_preview(app)
+        && !tts::has_pending_readback(app)
+    {
+        shortcut::unregister_cancel_shortcut(app);
+    }
+    result
+}
+
+/// Aborts every dictation still being processed.
+pub fn cancel(app: &AppHandle) {
+    let processing = std::mem::take(&mut *app.state::<ManagedProcessing>().lock().unwrap());
+    if !processing.is_empty() {
+        info!("Cancelling {} dictation(s) in processing", processing.len());
+    }
+    for handle in processing.values() {
+        handle.abort();
+    }
+}
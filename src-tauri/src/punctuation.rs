@@ -0,0 +1,339 @@
+//! Punctuation and casing restoration for engines that emit plain lowercase
+//! words, such as Moonshine or a Vosk server behind Wyoming.
+//!
+//! No model is downloaded for this: the user imports one, an uncased BERT
+//! token classifier exported to ONNX. Its directory holds `model.onnx`
+//! (inputs `input_ids` and `attention_mask`, output `logits`), its WordPiece
+//! `vocab.txt` and the Hugging Face `config.json`, whose `id2label` names the
+//! output classes. Every word is labelled with the punctuation that follows
+//! it and whether it starts with a capital.
+
+use anyhow::Result;
+use log::debug;
+use ort::session::Session;
+use ort::value::Tensor;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// BERT's limit of 512 positions, minus [CLS] and [SEP]
+const MAX_PIECES: usize = 510;
+/// Longer words are unlikely to be real and become [UNK], as in BERT
+const MAX_WORD_CHARS: usize = 100;
+
+/// What a class says about a word: the punctuation after it, if any, and
+/// whether it's capitalized
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Label {
+    punctuation: Option<char>,
+    capitalize: bool,
+}
+
+impl Label {
+    /// Parses a class name whose first character is the punctuation after
+    /// the word (`O` for none) and whose second is `U` if the word is
+    /// capitalized, or `O`
+    fn parse(name: &str) -> Option<Self> {
+        let mut chars = name.chars();
+        let punctuation = match chars.next()? {
+            'O' => None,
+            c if c.is_ascii_punctuation() => Some(c),
+            _ => return None,
+        };
+        let capitalize = match chars.next()? {
+            'U' => true,
+            'O' => false,
+            _ => return None,
+        };
+        chars.next().is_none().then_some(Self {
+            punctuation,
+            capitalize,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct ModelConfig {
+    id2label: HashMap<String, String>,
+}
+
+/// The model's classes in output order, from `id2label` in its `config.json`
+fn read_labels(config: &str) -> Result<Vec<Label>> {
+    let config: ModelConfig = serde_json::from_str(config)?;
+    (0..config.id2label.len())
+        .map(|id| {
+            let name = config
+                .id2label
+                .get(&id.to_string())
+                .ok_or_else(|| anyhow::anyhow!("config.json has no label for class {}", id))?;
+            Label::parse(name)
+                .ok_or_else(|| anyhow::anyhow!("Unsupported punctuation label {:?}", name))
+        })
+        .collect()
+}
+
+/// Checks that `dir` holds a model this module can run, without loading it
+pub fn check_dir(dir: &Path) -> Result<()> {
+    for file in ["model.onnx", "vocab.txt"] {
+        if !dir.join(file).is_file() {
+            return Err(anyhow::anyhow!("The folder has no {}", file));
+        }
+    }
+    read_labels(&fs::read_to_string(dir.join("config.json"))?)?;
+    Ok(())
+}
+
+pub struct PunctuationModel {
+    session: Session,
+    vocab: HashMap<String, i64>,
+    labels: Vec<Label>,
+}
+
+impl PunctuationModel {
+    pub fn load(dir: &Path) -> Result<Self> {
+        let labels = read_labels(&fs::read_to_string(dir.join("config.json"))?)?;
+        let vocab = fs::read_to_string(dir.join("vocab.txt"))?
+            .lines()
+            .enumerate()
+            .map(|(id, token)| (token.to_string(), id as i64))
+            .collect::<HashMap<_, _>>();
+        for special in ["[CLS]", "[SEP]", "[UNK]"] {
+            if !vocab.contains_key(special) {
+                return Err(anyhow::anyhow!("Vocabulary has no {} token", special));
+            }
+        }
+
+        let session = Session::builder()?.commit_from_file(dir.join("model.onnx"))?;
+        Ok(Self {
+            session,
+            vocab,
+            labels,
+        })
+    }
+
+    /// Punctuates and capitalizes `text`. Words themselves are kept as they
+    /// are, so casing from custom words or the engine survives.
+    pub fn restore(&mut self, text: &str) -> Result<String> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let pieces: Vec<Vec<i64>> = words
+            .iter()
+            .map(|word| word_pieces(word, &self.vocab))
+            .collect();
+
+        let mut labels = Vec::with_capacity(words.len());
+        let mut start = 0;
+        while start < words.len() {
+            let mut end = start;
+            let mut count = 0;
+            while end < words.len() && (end == start || count + pieces[end].len() <= MAX_PIECES) {
+                count += pieces[end].len();
+                end += 1;
+            }
+            labels.extend(
+                self.classify(&pieces[start..end])?
+                    .into_iter()
+                    .map(|class| self.labels.get(class).copied()),
+            );
+            start = end;
+        }
+
+        Ok(apply_labels(&words, &labels))
+    }
+
+    /// Labels each word by the prediction for its first piece.
+    fn classify(&mut self, words: &[Vec<i64>]) -> Result<Vec<usize>> {
+        let mut ids = vec![self.vocab["[CLS]"]];
+        let mut first_piece = Vec::with_capacity(words.len());
+        for pieces in words {
+            first_piece.push(ids.len());
+            ids.extend(pieces.iter().take(MAX_PIECES));
+        }
+        ids.truncate(MAX_PIECES + 1);
+        ids.push(self.vocab["[SEP]"]);
+
+        let len = ids.len();
+        let outputs = self.session.run(ort::inputs![
+            "input_ids" => Tensor::from_array(([1, len], ids))?,
+            "attention_mask" => Tensor::from_array(([1, len], vec![1i64; len]))?,
+        ])?;
+        let (_, logits) = outputs["logits"].try_extract_tensor::<f32>()?;
+
+        let labels = first_piece
+            .into_iter()
+            .map(|position| {
+                let classes = self.labels.len();
+                logits
+                    .get(position * classes..(position + 1) * classes)
+                    .unwrap_or_default()
+                    .iter()
+                    .enumerate()
+                    .max_by(|a, b| a.1.total_cmp(b.1))
+                    .map_or(usize::MAX, |(class, _)| class)
+            })
+            .collect();
+        debug!("Classified {} words for punctuation", words.len());
+        Ok(labels)
+    }
+}
+
+/// True if `text` has no punctuation of its own, which is when the model is
+/// worth running. Text that already has some is left alone.
+pub fn needs_restoration(text: &str) -> bool {
+    text.chars().any(char::is_alphabetic) && !text.chars().any(|c| ".,?!。，？！".contains(c))
+}
+
+/// WordPiece tokenization of a single word for an uncased vocabulary:
+/// punctuation becomes its own piece, and the rest is split greedily into the
+/// longest pieces in the vocabulary.
+fn word_pieces(word: &str, vocab: &HashMap<String, i64>) -> Vec<i64> {
+    let unknown = vocab["[UNK]"];
+    let lowercase = word.to_lowercase();
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    for c in lowercase.chars() {
+        if c.is_ascii_punctuation() {
+            if !current.is_empty() {
+                parts.push(std::mem::take(&mut current));
+            }
+            parts.push(c.to_string());
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+
+    let mut ids = Vec::new();
+    for part in parts {
+        let chars: Vec<char> = part.chars().collect();
+        if chars.len() > MAX_WORD_CHARS {
+            ids.push(unknown);
+            continue;
+        }
+
+        let mut part_ids = Vec::new();
+        let mut start = 0;
+        while start < chars.len() {
+            let found = (start + 1..=chars.len()).rev().find_map(|end| {
+                let piece: String = chars[start..end].iter().collect();
+                let piece = if start > 0 {
+                    format!("##{}", piece)
+                } else {
+                    piece
+                };
+                vocab.get(&piece).map(|&id| (id, end))
+            });
+            match found {
+                Some((id, end)) => {
+                    part_ids.push(id);
+                    start = end;
+                }
+                None => {
+                    part_ids = vec![unknown];
+                    break;
+                }
+            }
+        }
+        ids.extend(part_ids);
+    }
+
+    if ids.is_empty() {
+        ids.push(unknown);
+    }
+    ids
+}
+
+/// Rebuilds the text from the words and their labels, `None` leaving a word
+/// as it is. The first word and anything after a sentence end are
+/// capitalized regardless of the model.
+fn apply_labels(words: &[&str], labels: &[Option<Label>]) -> String {
+    let mut output = String::new();
+    let mut sentence_start = true;
+    for (word, label) in words.iter().zip(labels) {
+        let punctuation = label.and_then(|label| label.punctuation);
+        let capitalize = label.is_some_and(|label| label.capitalize) || sentence_start;
+
+        if !output.is_empty() {
+            output.push(' ');
+        }
+        let mut word_chars = word.chars();
+        if let (true, Some(first)) = (capitalize, word_chars.next()) {
+            output.extend(first.to_uppercase());
+            output.push_str(word_chars.as_str());
+        } else {
+            output.push_str(word);
+        }
+
+        // A trailing apostrophe or dash on its own reads as a mistake
+        if let Some(punctuation) = punctuation.filter(|c| !matches!(c, '\'' | '-')) {
+            output.push(punctuation);
+        }
+        sentence_start = matches!(punctuation, Some('.' | '!' | '?'));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocab() -> HashMap<String, i64> {
+        [
+            "[UNK]", "[CLS]", "[SEP]", "play", "##ing", "don", "'", "t", "i",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect()
+    }
+
+    #[test]
+    fn splits_words_into_pieces() {
+        let vocab = vocab();
+        assert_eq!(word_pieces("Playing", &vocab), vec![3, 4]);
+        assert_eq!(word_pieces("don't", &vocab), vec![5, 6, 7]);
+        assert_eq!(word_pieces("xyz", &vocab), vec![0]);
+    }
+
+    #[test]
+    fn applies_punctuation_and_casing() {
+        let words = ["so", "i", "bought", "an", "iPhone", "did", "you"];
+        let labels = ["OO", "OU", "OO", "OO", ".O", "OO", "?O"].map(Label::parse);
+        assert_eq!(
+            apply_labels(&words, &labels),
+            "So I bought an iPhone. Did you?"
+        );
+        // A class the config didn't name leaves the word alone
+        assert_eq!(apply_labels(&["so", "yes"], &[None, None]), "So yes");
+    }
+
+    #[test]
+    fn reads_the_label_map_from_the_config() {
+        let config = r#"{"architectures": ["BertForTokenClassification"],
+            "id2label": {"1": ",O", "0": "OO", "2": "?U"}}"#;
+        assert_eq!(
+            read_labels(config).unwrap(),
+            vec![
+                Label::parse("OO").unwrap(),
+                Label::parse(",O").unwrap(),
+                Label {
+                    punctuation: Some('?'),
+                    capitalize: true
+                },
+            ]
+        );
+        // Classes must be numbered from 0 without gaps, in the known format
+        assert!(read_labels(r#"{"id2label": {"0": "OO", "2": ".O"}}"#).is_err());
+        assert!(read_labels(r#"{"id2label": {"0": "B-PERIOD"}}"#).is_err());
+        assert!(read_labels(r#"{"labels": []}"#).is_err());
+    }
+
+    #[test]
+    fn only_restores_unpunctuated_text() {
+        assert!(needs_restoration("hello there how are you"));
+        assert!(!needs_restoration("Hello there, how are you?"));
+        assert!(!needs_restoration("   "));
+    }
+}
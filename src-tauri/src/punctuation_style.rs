@@ -0,0 +1,237 @@
+//! Punctuation style preferences applied to every transcript: the Oxford
+//! comma, how dashes, quotes and ellipses are written. Each is a fixed text
+//! rewrite, so the same transcript always comes out the same way, whichever
+//! engine or punctuation model produced it.
+
+use crate::settings::{AppSettings, DashStyle, EllipsisStyle, OxfordComma, QuoteStyle};
+
+/// Words that open a clause rather than a list item, as in "If so, call and
+/// ask", where adding a serial comma would be wrong
+const CLAUSE_OPENERS: &[&str] = &[
+    "yes", "no", "well", "so", "okay", "ok", "oh", "now", "then", "also", "however", "hi", "hey",
+    "but", "if", "when", "because", "while", "after", "before", "since", "although", "though",
+    "as", "once", "unless", "until", "first", "finally",
+];
+/// List items longer than this are more likely clauses, which are left alone
+const MAX_ITEM_WORDS: usize = 3;
+/// The first item also carries whatever leads into the list ("I bought apples")
+const MAX_FIRST_ITEM_WORDS: usize = 5;
+
+pub fn is_enabled(settings: &AppSettings) -> bool {
+    settings.oxford_comma != OxfordComma::Keep
+        || settings.dash_style != DashStyle::Keep
+        || settings.quote_style != QuoteStyle::Keep
+        || settings.ellipsis_style != EllipsisStyle::Keep
+}
+
+pub fn apply(text: &str, settings: &AppSettings) -> String {
+    let mut text = match settings.ellipsis_style {
+        EllipsisStyle::Keep => text.to_string(),
+        EllipsisStyle::Character => text.replace(". . .", "…").replace("...", "…"),
+        EllipsisStyle::Dots => text.replace('…', "..."),
+    };
+
+    text = match settings.dash_style {
+        DashStyle::Keep => text,
+        DashStyle::SpacedEnDash => replace_dashes(&text, &[" -- ", " — ", " - ", "--", "—"], " – "),
+        DashStyle::EmDash => replace_dashes(&text, &[" -- ", " — ", " – ", " - ", "--"], "—"),
+    };
+
+    text = match settings.quote_style {
+        QuoteStyle::Keep => text,
+        QuoteStyle::Straight => text
+            .chars()
+            .map(|c| match c {
+                '“' | '”' | '„' => '"',
+                '‘' | '’' | '‚' => '\'',
+                c => c,
+            })
+            .collect(),
+        QuoteStyle::Curly => curly_quotes(&text),
+    };
+
+    match settings.oxford_comma {
+        OxfordComma::Keep => text,
+        OxfordComma::Add => sentences(&text).map(|s| oxford_comma(s, true)).collect(),
+        OxfordComma::Remove => sentences(&text).map(|s| oxford_comma(s, false)).collect(),
+    }
+}
+
+/// Replaces each pattern in order, so spaced variants go before bare ones.
+/// Hyphens inside words and unspaced en-dashes in ranges ("1–5") are kept.
+fn replace_dashes(text: &str, patterns: &[&str], dash: &str) -> String {
+    let mut text = text.to_string();
+    for pattern in patterns {
+        text = text.replace(pattern, dash);
+    }
+    text
+}
+
+/// Turns straight quotes into opening or closing ones depending on what comes
+/// before them. A single quote after a letter is an apostrophe.
+fn curly_quotes(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut output = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let opens = prev.is_none_or(|p| p.is_whitespace() || "([{-—–".contains(p));
+        match c {
+            '"' => output.push(if opens { '“' } else { '”' }),
+            '\'' => {
+                let next_is_letter = chars.get(i + 1).is_some_and(|n| n.is_alphabetic());
+                output.push(if opens && next_is_letter {
+                    '‘'
+                } else {
+                    '’'
+                });
+            }
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+/// Splits after sentence-ending punctuation, keeping every character.
+fn sentences(text: &str) -> impl Iterator<Item = &str> {
+    let mut start = 0;
+    let mut ends: Vec<usize> = text
+        .char_indices()
+        .filter(|(_, c)| matches!(c, '.' | '!' | '?'))
+        .map(|(i, c)| i + c.len_utf8())
+        .collect();
+    ends.push(text.len());
+    ends.dedup();
+    ends.into_iter().filter_map(move |end| {
+        let sentence = &text[start..end];
+        start = end;
+        (!sentence.is_empty()).then_some(sentence)
+    })
+}
+
+fn word_count(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+/// Whether comma-separated `items` followed by "and/or `last`" read as a list
+/// of short items rather than clauses.
+fn is_list(items: &[&str], last: &str) -> bool {
+    let Some((first, rest)) = items.split_first() else {
+        return false;
+    };
+    let opener = first.split_whitespace().next().map(|word| {
+        word.trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase()
+    });
+    if opener.is_some_and(|word| CLAUSE_OPENERS.contains(&word.as_str())) {
+        return false;
+    }
+    !rest.is_empty()
+        && (1..=MAX_FIRST_ITEM_WORDS).contains(&word_count(first))
+        && rest
+            .iter()
+            .all(|item| (1..=MAX_ITEM_WORDS).contains(&word_count(item)))
+        && (1..=MAX_ITEM_WORDS).contains(&word_count(last))
+}
+
+/// Adds or removes the comma before the conjunction ending a list.
+fn oxford_comma(sentence: &str, add: bool) -> String {
+    let parts: Vec<&str> = sentence.split(',').collect();
+    let Some((&tail, items)) = parts.split_last() else {
+        return sentence.to_string();
+    };
+    if items.is_empty() {
+        return sentence.to_string();
+    }
+
+    for conjunction in [" and ", " or "] {
+        if add {
+            // "A, B and C": the conjunction sits inside the last part
+            let Some(pos) = tail.find(conjunction).filter(|&pos| pos > 0) else {
+                continue;
+            };
+            let (item, last) = tail.split_at(pos);
+            let mut list = items.to_vec();
+            list.push(item);
+            if is_list(&list, &last[conjunction.len()..]) {
+                return format!("{},{},{}", items.join(","), item, last);
+            }
+        } else if let Some(last) = tail.strip_prefix(conjunction) {
+            // "A, B, and C": the conjunction starts the last part
+            if is_list(items, last) {
+                return format!("{}{}", items.join(","), tail);
+            }
+        }
+    }
+    sentence.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+
+    #[test]
+    fn adds_and_removes_the_oxford_comma_in_lists_only() {
+        let mut settings = get_default_settings();
+        settings.oxford_comma = OxfordComma::Add;
+        assert_eq!(
+            apply(
+                "I bought apples, pears and plums. Tea or coffee?",
+                &settings
+            ),
+            "I bought apples, pears, and plums. Tea or coffee?"
+        );
+        assert_eq!(
+            apply("Red, green, blue or yellow", &settings),
+            "Red, green, blue, or yellow"
+        );
+        assert_eq!(
+            apply("If so, call and ask.", &settings),
+            "If so, call and ask."
+        );
+        assert_eq!(
+            apply("When I got home after work, I ate and slept.", &settings),
+            "When I got home after work, I ate and slept."
+        );
+
+        settings.oxford_comma = OxfordComma::Remove;
+        assert_eq!(
+            apply("Apples, pears, and plums.", &settings),
+            "Apples, pears and plums."
+        );
+        assert_eq!(
+            apply("I came home, and then I slept.", &settings),
+            "I came home, and then I slept."
+        );
+    }
+
+    #[test]
+    fn rewrites_dashes_quotes_and_ellipses() {
+        let mut settings = get_default_settings();
+        assert!(!is_enabled(&settings));
+
+        settings.dash_style = DashStyle::EmDash;
+        assert_eq!(
+            apply(
+                "It was late -- too late - for a well-known fix, pages 1–5",
+                &settings
+            ),
+            "It was late—too late—for a well-known fix, pages 1–5"
+        );
+        settings.dash_style = DashStyle::SpacedEnDash;
+        assert_eq!(apply("late—too late", &settings), "late – too late");
+
+        settings.quote_style = QuoteStyle::Curly;
+        assert_eq!(
+            apply(r#"She said "don't go" ('not yet')"#, &settings),
+            "She said “don’t go” (‘not yet’)"
+        );
+        settings.quote_style = QuoteStyle::Straight;
+        assert_eq!(apply("“don’t”", &settings), r#""don't""#);
+
+        settings.ellipsis_style = EllipsisStyle::Character;
+        assert_eq!(apply("Well... maybe", &settings), "Well… maybe");
+        settings.ellipsis_style = EllipsisStyle::Dots;
+        assert_eq!(apply("Well… maybe", &settings), "Well... maybe");
+    }
+}
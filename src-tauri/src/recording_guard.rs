@@ -0,0 +1,148 @@
+//! Decides what happens when a recording shortcut fires while a different
+//! one is already recording, following the pressed binding's `when_busy`:
+//! ignore the press, finish the other recording first, or wait for it to end.
+
+use crate::actions::action_for;
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::captions::{CaptionsManager, CAPTIONS_BINDING_ID};
+use crate::settings::{get_settings, ConcurrentRecording, DICTATION_BINDING_PREFIX};
+use log::{debug, info, warn};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// Binding waiting for the current recording to end.
+pub type ManagedQueuedBinding = Mutex<Option<String>>;
+
+/// Bindings whose actions record from the microphone
+const RECORDING_BINDINGS: &[&str] = &["transcribe", "translate", "toggle_captions"];
+const CAPTIONS_SHORTCUT_ID: &str = "toggle_captions";
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long a stopped recording gets to hand over the microphone
+const HANDOVER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The shortcut binding behind the recording the audio manager is running,
+/// if it's one of ours
+fn recording_binding(app: &AppHandle) -> Option<String> {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    let id = rm.active_binding()?;
+    Some(if id == CAPTIONS_BINDING_ID {
+        CAPTIONS_SHORTCUT_ID.to_string()
+    } else {
+        id
+    })
+}
+
+/// The other binding that has the microphone, if `binding_id` records
+fn conflicting_binding(app: &AppHandle, binding_id: &str) -> Option<String> {
+    if !RECORDING_BINDINGS.contains(&binding_id)
+        && !binding_id.starts_with(DICTATION_BINDING_PREFIX)
+    {
+        return None;
+    }
+    recording_binding(app).filter(|active| active != binding_id)
+}
+
+fn run_start(app: &AppHandle, binding_id: &str, shortcut_str: &str) {
+    if let Some(action) = action_for(binding_id) {
+        action.start(app, binding_id, shortcut_str);
+    }
+}
+
+fn queued(app: &AppHandle) -> Option<String> {
+    app.state::<ManagedQueuedBinding>().lock().unwrap().clone()
+}
+
+/// Ends the recording of `binding_id` the way its own shortcut would, so a
+/// dictation is still transcribed and pasted.
+fn stop_recording_of(app: &AppHandle, binding_id: &str) {
+    if binding_id == CAPTIONS_SHORTCUT_ID {
+        app.state::<Arc<CaptionsManager>>().stop();
+    } else if let Some(action) = action_for(binding_id) {
+        action.stop(app, binding_id, "busy");
+    }
+}
+
+/// Starts the action of `binding_id`, unless another binding is recording.
+/// Returns false if the press was ignored, so toggle state can be undone.
+pub fn start(app: &AppHandle, binding_id: &str, shortcut_str: &str) -> bool {
+    // Pressing a queued binding again takes it back out of the queue
+    if queued(app).as_deref() == Some(binding_id) {
+        clear_queue(app);
+        return false;
+    }
+
+    let Some(active) = conflicting_binding(app, binding_id) else {
+        run_start(app, binding_id, shortcut_str);
+        return true;
+    };
+
+    let when_busy = get_settings(app)
+        .bindings
+        .get(binding_id)
+        .map(|binding| binding.when_busy)
+        .unwrap_or_default();
+    info!(
+        "'{}' pressed while '{}' is recording: {:?}",
+        binding_id, active, when_busy
+    );
+
+    match when_busy {
+        ConcurrentRecording::Ignore => return false,
+        ConcurrentRecording::StopAndStart => stop_recording_of(app, &active),
+        ConcurrentRecording::Queue => {}
+    }
+
+    // Either way the binding waits for the microphone, and can still be
+    // released or pressed again to drop out before it gets it
+    let deadline =
+        (when_busy == ConcurrentRecording::StopAndStart).then(|| Instant::now() + HANDOVER_TIMEOUT);
+    *app.state::<ManagedQueuedBinding>().lock().unwrap() = Some(binding_id.to_string());
+    let app = app.clone();
+    let binding_id = binding_id.to_string();
+    let shortcut_str = shortcut_str.to_string();
+    std::thread::spawn(move || {
+        let rm = app.state::<Arc<AudioRecordingManager>>();
+        loop {
+            if queued(&app).as_deref() != Some(binding_id.as_str()) {
+                debug!("'{}' left the recording queue", binding_id);
+                return;
+            }
+            if !rm.is_recording() {
+                app.state::<ManagedQueuedBinding>().lock().unwrap().take();
+                run_start(&app, &binding_id, &shortcut_str);
+                return;
+            }
+            if deadline.is_some_and(|deadline| Instant::now() > deadline) {
+                warn!("'{}' did not release the microphone in time", active);
+                clear_queue(&app);
+                return;
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    });
+    true
+}
+
+/// Stops the action of `binding_id`. A binding that is only queued leaves the
+/// queue, and a stop for a binding that lost the microphone to another one
+/// is dropped so it can't tear down that recording's overlay.
+pub fn stop(app: &AppHandle, binding_id: &str, shortcut_str: &str) {
+    if queued(app).as_deref() == Some(binding_id) {
+        clear_queue(app);
+        return;
+    }
+    if conflicting_binding(app, binding_id).is_some() {
+        debug!("Ignoring stop for '{}', it isn't recording", binding_id);
+        return;
+    }
+    if let Some(action) = action_for(binding_id) {
+        action.stop(app, binding_id, shortcut_str);
+    }
+}
+
+pub fn clear_queue(app: &AppHandle) {
+    if let Some(binding_id) = app.state::<ManagedQueuedBinding>().lock().unwrap().take() {
+        info!("Dropped queued recording for '{}'", binding_id);
+    }
+}
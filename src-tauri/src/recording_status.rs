@@ -0,0 +1,87 @@
+//! Live status while recording and transcribing, so the overlay and tray can
+//! show more than a static icon.
+
+use crate::cancel_gesture;
+use crate::managers::audio::AudioRecordingManager;
+use crate::settings::{get_settings, RecordingMode};
+use crate::shortcut;
+use crate::wake_word;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::tray::TrayIcon;
+use tauri::{AppHandle, Emitter, Manager};
+
+const TICK_INTERVAL: Duration = Duration::from_millis(500);
+/// Typical dictation pace, used to turn speech time into a rough word count.
+const WORDS_PER_SPEECH_SECOND: f32 = 2.5;
+
+/// Bumped for every recording so a ticker left over from the previous one exits.
+static TICKER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RecordingStatus {
+    pub elapsed_secs: f32,
+    /// Fraction of the recording the VAD classed as speech, 0.0 to 1.0
+    pub speech_ratio: f32,
+    /// Estimate from speech time, the real count is only known after transcription
+    pub estimated_words: u32,
+    /// How the recording stops, for the overlay to show
+    pub mode: Option<RecordingMode>,
+}
+
+/// Emits `recording-status` to every window until the recording ends, and
+/// mirrors the elapsed time in the tray tooltip.
+pub fn start_ticker(app: &AppHandle) {
+    let generation = TICKER_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    let app = app.clone();
+    thread::spawn(move || {
+        let rm = Arc::clone(&app.state::<Arc<AudioRecordingManager>>());
+        let started = Instant::now();
+
+        loop {
+            thread::sleep(TICK_INTERVAL);
+            if TICKER_GENERATION.load(Ordering::Relaxed) != generation {
+                return;
+            }
+            let Some(speech_ratio) = rm.speech_ratio() else {
+                break;
+            };
+
+            let elapsed_secs = started.elapsed().as_secs_f32();
+            // A wake word recording stops on silence like a hands-free one
+            let mode = if rm.started_by_wake_word() {
+                Some(RecordingMode::HandsFree)
+            } else {
+                rm.active_binding()
+                    .map(|binding_id| get_settings(&app).recording_mode(&binding_id))
+            };
+            let status = RecordingStatus {
+                elapsed_secs,
+                speech_ratio,
+                estimated_words: (elapsed_secs * speech_ratio * WORDS_PER_SPEECH_SECOND) as u32,
+                mode,
+            };
+            let _ = app.emit("recording-status", &status);
+            cancel_gesture::check_silence(&app, elapsed_secs, speech_ratio);
+            wake_word::check_silence(&app);
+            shortcut::check_hands_free(&app);
+            set_tray_tooltip(&app, Some(&format_elapsed(elapsed_secs)));
+        }
+
+        set_tray_tooltip(&app, None);
+    });
+}
+
+fn set_tray_tooltip(app: &AppHandle, elapsed: Option<&str>) {
+    let tooltip = elapsed.map(|elapsed| format!("Handy · {}", elapsed));
+    let _ = app.state::<TrayIcon>().set_tooltip(tooltip);
+}
+
+/// Formats seconds as `m:ss`.
+fn format_elapsed(secs: f32) -> String {
+    let secs = secs as u64;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
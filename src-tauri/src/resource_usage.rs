@@ -0,0 +1,264 @@
+//! Resources used by a transcription, recorded with its history entry so you
+//! can check the GPU is really in use and compare models or settings.
+//!
+//! RAM and CPU time come from the process itself. VRAM is per-process GPU
+//! memory, read once in the background when a transcription starts:
+//! - DXGI on Windows, which covers any vendor.
+//! - `nvidia-smi` on Linux, so only CUDA use on NVIDIA cards is seen.
+//! - Not available on macOS, where GPU memory is shared with the system.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+const MB: u64 = 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InferenceDevice {
+    Cpu,
+    Gpu,
+    /// Transcribed by a Wyoming server
+    Remote,
+    /// Could be either, GPU memory couldn't be read
+    Unknown,
+}
+
+impl InferenceDevice {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Gpu => "gpu",
+            Self::Remote => "remote",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "cpu" => Self::Cpu,
+            "gpu" => Self::Gpu,
+            "remote" => Self::Remote,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type, PartialEq)]
+pub struct ResourceUsage {
+    pub device: InferenceDevice,
+    pub inference_ms: u64,
+    pub peak_ram_mb: u64,
+    pub vram_mb: Option<u64>,
+    /// Average over the transcription, as a share of all cores
+    pub cpu_percent: f32,
+}
+
+/// Samples the process while a transcription runs.
+pub struct UsageMonitor {
+    pid: Option<Pid>,
+    started: Instant,
+    cpu_ms_at_start: u64,
+    peak_ram: Arc<AtomicU64>,
+    /// GPU memory in bytes, once the sampler has read it
+    vram: Arc<Mutex<Option<u64>>>,
+    stop: Arc<AtomicBool>,
+}
+
+fn refresh(system: &mut System, pid: Pid) -> Option<(u64, u64)> {
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        false,
+        ProcessRefreshKind::nothing().with_memory().with_cpu(),
+    );
+    system
+        .process(pid)
+        .map(|process| (process.memory(), process.accumulated_cpu_time()))
+}
+
+fn cpu_percent(cpu_ms: u64, wall_ms: u64, cores: usize) -> f32 {
+    if wall_ms == 0 || cores == 0 {
+        return 0.0;
+    }
+    (cpu_ms as f32 / (wall_ms as f32 * cores as f32) * 100.0).min(100.0)
+}
+
+/// Whether the work ran on the GPU, judging by the GPU memory the process
+/// holds. Local engines other than Whisper always run on the CPU.
+fn device_for(remote: bool, whisper: bool, vram_mb: Option<u64>) -> InferenceDevice {
+    match (remote, whisper, vram_mb) {
+        (true, _, _) => InferenceDevice::Remote,
+        (false, false, _) => InferenceDevice::Cpu,
+        (false, true, Some(0)) => InferenceDevice::Cpu,
+        (false, true, Some(_)) => InferenceDevice::Gpu,
+        (false, true, None) => InferenceDevice::Unknown,
+    }
+}
+
+impl UsageMonitor {
+    pub fn start() -> Self {
+        let pid = sysinfo::get_current_pid().ok();
+        let mut system = System::new();
+        let (ram, cpu_ms) = pid
+            .and_then(|pid| refresh(&mut system, pid))
+            .unwrap_or_default();
+        let peak_ram = Arc::new(AtomicU64::new(ram));
+        let vram = Arc::new(Mutex::new(None));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        // Not joined, so finishing never waits on a sample in progress
+        {
+            let peak_ram = peak_ram.clone();
+            let vram = vram.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                *vram.lock().unwrap() = process_vram_bytes();
+                let Some(pid) = pid else {
+                    return;
+                };
+                while !stop.load(Ordering::Relaxed) {
+                    thread::sleep(SAMPLE_INTERVAL);
+                    if let Some((ram, _)) = refresh(&mut system, pid) {
+                        peak_ram.fetch_max(ram, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+
+        Self {
+            pid,
+            started: Instant::now(),
+            cpu_ms_at_start: cpu_ms,
+            peak_ram,
+            vram,
+            stop,
+        }
+    }
+
+    /// Stops sampling. `remote` is set for Wyoming transcriptions, `whisper`
+    /// when the local engine is Whisper, the only one that can use the GPU.
+    /// VRAM is unknown if it hadn't been read by then.
+    pub fn finish(self, remote: bool, whisper: bool) -> ResourceUsage {
+        let wall_ms = self.started.elapsed().as_millis() as u64;
+        self.stop.store(true, Ordering::Relaxed);
+
+        let mut system = System::new();
+        let (ram, cpu_ms) = self
+            .pid
+            .and_then(|pid| refresh(&mut system, pid))
+            .unwrap_or_default();
+        let peak_ram = self.peak_ram.load(Ordering::Relaxed).max(ram);
+        let cores = thread::available_parallelism().map_or(1, |n| n.get());
+        let vram_mb = if remote {
+            None
+        } else {
+            *self.vram.lock().unwrap()
+        }
+        .map(|b| b / MB);
+
+        ResourceUsage {
+            device: device_for(remote, whisper, vram_mb),
+            inference_ms: wall_ms,
+            peak_ram_mb: peak_ram / MB,
+            vram_mb,
+            cpu_percent: cpu_percent(cpu_ms.saturating_sub(self.cpu_ms_at_start), wall_ms, cores),
+        }
+    }
+}
+
+/// GPU memory used by `pid` in `nvidia-smi --query-compute-apps=pid,used_memory
+/// --format=csv,noheader,nounits` output, in MiB. `None` if the process isn't
+/// listed, which doesn't rule out the GPU: Vulkan use isn't reported there.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_nvidia_smi(output: &str, pid: u32) -> Option<u64> {
+    let used: Vec<u64> = output
+        .lines()
+        .filter_map(|line| {
+            let (line_pid, used) = line.split_once(',')?;
+            (line_pid.trim().parse::<u32>().ok()? == pid)
+                .then(|| used.trim().parse::<u64>().ok())
+                .flatten()
+        })
+        .collect();
+    (!used.is_empty()).then(|| used.iter().sum())
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn process_vram_bytes() -> Option<u64> {
+    let output = std::process::Command::new("nvidia-smi")
+        .args([
+            "--query-compute-apps=pid,used_memory",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_nvidia_smi(&String::from_utf8_lossy(&output.stdout), std::process::id())
+        .map(|mib| mib * MB)
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn process_vram_bytes() -> Option<u64> {
+    use windows::core::Interface;
+    use windows::Win32::Graphics::Dxgi::{
+        CreateDXGIFactory1, IDXGIAdapter3, IDXGIFactory1, DXGI_MEMORY_SEGMENT_GROUP_LOCAL,
+        DXGI_QUERY_VIDEO_MEMORY_INFO,
+    };
+
+    unsafe {
+        let factory: IDXGIFactory1 = CreateDXGIFactory1().ok()?;
+        let mut total = 0;
+        let mut index = 0;
+        while let Ok(adapter) = factory.EnumAdapters1(index) {
+            index += 1;
+            let Ok(adapter) = adapter.cast::<IDXGIAdapter3>() else {
+                continue;
+            };
+            let mut info = DXGI_QUERY_VIDEO_MEMORY_INFO::default();
+            if adapter
+                .QueryVideoMemoryInfo(0, DXGI_MEMORY_SEGMENT_GROUP_LOCAL, &mut info)
+                .is_ok()
+            {
+                total += info.CurrentUsage;
+            }
+        }
+        Some(total)
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub(crate) fn process_vram_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_this_process_from_nvidia_smi() {
+        let output = "1201, 310\n4242, 1536\n4242, 12\n";
+        assert_eq!(parse_nvidia_smi(output, 4242), Some(1548));
+        assert_eq!(parse_nvidia_smi(output, 7), None);
+    }
+
+    #[test]
+    fn infers_the_device_and_cpu_share() {
+        assert_eq!(device_for(false, true, Some(900)), InferenceDevice::Gpu);
+        assert_eq!(device_for(false, true, Some(0)), InferenceDevice::Cpu);
+        assert_eq!(device_for(false, true, None), InferenceDevice::Unknown);
+        assert_eq!(device_for(false, false, None), InferenceDevice::Cpu);
+        assert_eq!(device_for(true, true, None), InferenceDevice::Remote);
+
+        // Two of eight cores busy for the whole second
+        assert_eq!(cpu_percent(2000, 1000, 8), 25.0);
+        assert_eq!(cpu_percent(10, 0, 8), 0.0);
+    }
+}
@@ -8,6 +8,8 @@ use tauri_plugin_store::StoreExt;
 
 pub const APPLE_INTELLIGENCE_PROVIDER_ID: &str = "apple_intelligence";
 pub const APPLE_INTELLIGENCE_DEFAULT_MODEL_ID: &str = "Apple Intelligence";
+/// Ids of the dictation shortcuts users add themselves start with this
+pub const DICTATION_BINDING_PREFIX: &str = "dictate_";
 
 #[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
 #[serde(rename_all = "lowercase")]
@@ -76,6 +78,64 @@ impl From<LogLevel> for tauri_plugin_log::LogLevel {
     }
 }
 
+/// What a recording shortcut does when another one is already recording
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcurrentRecording {
+    Ignore,
+    /// Finish the other recording, then start this one
+    StopAndStart,
+    /// Start this one as soon as the other recording ends
+    Queue,
+}
+
+impl Default for ConcurrentRecording {
+    fn default() -> Self {
+        ConcurrentRecording::Ignore
+    }
+}
+
+/// Engine a binding's dictations are transcribed with, whichever the
+/// settings select otherwise
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingEngine {
+    Local,
+    Wyoming,
+    Deepgram,
+}
+
+/// Where a binding's dictations go
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingOutput {
+    /// Typed into the focused app, as set in the settings
+    Paste,
+    /// Put on the clipboard only
+    Clipboard,
+    /// Appended to `output_file`
+    File,
+}
+
+impl Default for BindingOutput {
+    fn default() -> Self {
+        BindingOutput::Paste
+    }
+}
+
+/// How a recording shortcut starts and stops recording
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingMode {
+    /// Record while the keys are held
+    PushToTalk,
+    /// Press to start, press again to stop
+    Toggle,
+    /// Press to start, stops by itself once speech is followed by
+    /// `hands_free_silence_secs` of silence. Pressing again stops it too.
+    HandsFree,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct ShortcutBinding {
     pub id: String,
@@ -83,6 +143,92 @@ pub struct ShortcutBinding {
     pub description: String,
     pub default_binding: String,
     pub current_binding: String,
+    #[serde(default)]
+    pub when_busy: ConcurrentRecording,
+    /// Seconds counted down before recording starts, 0 for none
+    #[serde(default)]
+    pub countdown_secs: u32,
+    /// Whether dictations from this binding are translated to English,
+    /// `None` to follow `translate_to_english`
+    #[serde(default)]
+    pub translate: Option<bool>,
+    /// Language dictations from this binding are in, `None` to follow
+    /// `selected_language`
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Model dictations from this binding are transcribed with, `None` for
+    /// the selected one
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Custom words used instead of `custom_words`
+    #[serde(default)]
+    pub custom_words: Option<Vec<String>>,
+    /// Paste dictations from this binding without post-processing them
+    #[serde(default)]
+    pub skip_post_process: bool,
+    /// Engine dictations from this binding use, `None` for the one the
+    /// settings select
+    #[serde(default)]
+    pub engine: Option<BindingEngine>,
+    /// Prompt dictations from this binding are post-processed with, over
+    /// the selected one and context rules
+    #[serde(default)]
+    pub prompt_id: Option<String>,
+    #[serde(default)]
+    pub output: BindingOutput,
+    /// File `BindingOutput::File` appends to
+    #[serde(default)]
+    pub output_file: Option<String>,
+    /// `None` to follow `push_to_talk`
+    #[serde(default)]
+    pub recording_mode: Option<RecordingMode>,
+}
+
+impl ShortcutBinding {
+    /// Switches `settings` to this binding's own language, translation,
+    /// engine and custom words. The model is picked by the transcription
+    /// manager.
+    pub fn apply_overrides(&self, settings: &mut AppSettings) {
+        if let Some(engine) = self.engine {
+            settings.wyoming_enabled = engine == BindingEngine::Wyoming;
+            settings.deepgram_enabled = engine == BindingEngine::Deepgram;
+            settings.cloud_transcription.provider = None;
+            // The binding's engine is used whatever the length
+            settings.adaptive_engine = false;
+        }
+        if let Some(translate) = self.translate {
+            settings.translate_to_english = translate;
+        }
+        if let Some(language) = &self.language {
+            settings.selected_language = language.clone();
+        }
+        if let Some(words) = &self.custom_words {
+            settings.custom_words = words.clone();
+        }
+    }
+
+    /// Switches `settings` to this binding's post-processing, once context
+    /// rules have had their say
+    pub fn apply_post_process(&self, settings: &mut AppSettings) {
+        if self.skip_post_process {
+            settings.post_process_enabled = false;
+        } else if let Some(prompt_id) = &self.prompt_id {
+            settings.post_process_enabled = true;
+            settings.post_process_selected_prompt_id = Some(prompt_id.clone());
+        }
+    }
+}
+
+/// A phrase that starts a recording when it's said, recorded by the user
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct WakeWord {
+    pub id: String,
+    pub name: String,
+    /// Recording binding the phrase starts, so its language, model and
+    /// other options apply
+    pub binding_id: String,
+    /// From 0, only a close match, to 1, anything like the phrase
+    pub sensitivity: f32,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
@@ -92,6 +238,336 @@ pub struct LLMPrompt {
     pub prompt: String,
 }
 
+/// Settings for dictations into a matching window: which post-processing
+/// prompt runs, and how the text is transcribed and typed.
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct ContextRule {
+    pub id: String,
+    pub name: String,
+    /// App name, compared case-insensitively. Empty matches any app.
+    pub app: String,
+    /// Case-insensitive regex searched for in the window title. Empty
+    /// matches any title.
+    pub title_pattern: String,
+    /// Prompt to post-process with, or `None` to paste the transcript as is
+    pub prompt_id: Option<String>,
+    /// Replaces `transcription_prompt` for dictations into a matching window
+    #[serde(default)]
+    pub transcription_prompt: Option<String>,
+    /// Replaces `punctuation_restoration`, e.g. off in a code editor
+    #[serde(default)]
+    pub punctuation_restoration: Option<bool>,
+    /// Replaces `append_trailing_space`
+    #[serde(default)]
+    pub append_trailing_space: Option<bool>,
+    /// Press Enter after pasting, to send a chat message
+    #[serde(default)]
+    pub press_enter: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareKind {
+    Email,
+    Slack,
+    Notion,
+    Command,
+}
+
+/// Somewhere a history entry can be sent from the history page.
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct ShareDestination {
+    pub id: String,
+    pub name: String,
+    pub kind: ShareKind,
+    /// The email address, Slack webhook URL, Notion parent page id, or shell
+    /// command that gets the message on stdin
+    pub target: String,
+    /// Notion integration token, unused by the other kinds
+    pub token: String,
+    /// The message, with `{text}`, `{title}`, `{app}` and `{date}` filled in
+    /// from the entry
+    pub template: String,
+}
+
+/// A WebSocket endpoint speaking OpenAI's realtime transcription protocol.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct RealtimeEndpoint {
+    pub url: String,
+    pub model: String,
+    pub api_key: String,
+}
+
+impl Default for RealtimeEndpoint {
+    fn default() -> Self {
+        Self {
+            url: "wss://api.openai.com/v1/realtime?intent=transcription".to_string(),
+            model: "gpt-4o-mini-transcribe".to_string(),
+            api_key: String::new(),
+        }
+    }
+}
+
+/// Deepgram account and model for remote transcription.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct DeepgramConfig {
+    pub api_key: String,
+    pub model: String,
+    /// Also stream dictations to the live API while they record
+    pub streaming: bool,
+    #[serde(default)]
+    pub upload_codec: UploadCodec,
+    /// Seconds to wait for a connection to the API
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// Seconds to wait for the API to send more of its response
+    #[serde(default = "default_read_timeout_secs")]
+    pub read_timeout_secs: u64,
+    /// Times a request that hit a rate limit, server error or timeout is sent
+    /// again
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Label who said what in recordings with several speakers
+    #[serde(default)]
+    pub diarize: bool,
+}
+
+impl Default for DeepgramConfig {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            model: "nova-3".to_string(),
+            streaming: true,
+            upload_codec: UploadCodec::default(),
+            connect_timeout_secs: default_connect_timeout_secs(),
+            read_timeout_secs: default_read_timeout_secs(),
+            max_retries: default_max_retries(),
+            diarize: false,
+        }
+    }
+}
+
+/// Hosted speech-to-text APIs that only need a key and a model, each with
+/// its own request format
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CloudProvider {
+    Gladia,
+    Fireworks,
+    Mistral,
+}
+
+impl CloudProvider {
+    /// How it's named in the settings, as a key of `CloudTranscription`'s
+    /// maps
+    pub fn id(self) -> &'static str {
+        match self {
+            CloudProvider::Gladia => "gladia",
+            CloudProvider::Fireworks => "fireworks",
+            CloudProvider::Mistral => "mistral",
+        }
+    }
+}
+
+/// Which `CloudProvider` transcribes, and the account of each
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Type)]
+pub struct CloudTranscription {
+    /// `None` when none of them does
+    #[serde(default)]
+    pub provider: Option<CloudProvider>,
+    /// API key of each provider, by its id
+    #[serde(default)]
+    pub api_keys: HashMap<String, String>,
+    /// Model of each provider, by its id. Missing or empty for the
+    /// provider's default.
+    #[serde(default)]
+    pub models: HashMap<String, String>,
+}
+
+impl CloudTranscription {
+    pub fn api_key(&self, provider: CloudProvider) -> String {
+        self.api_keys
+            .get(provider.id())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn model(&self, provider: CloudProvider) -> String {
+        self.models.get(provider.id()).cloned().unwrap_or_default()
+    }
+}
+
+fn default_connect_timeout_secs() -> u64 {
+    10
+}
+
+fn default_read_timeout_secs() -> u64 {
+    120
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+/// Where one kind of notification shows up
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+pub struct NotificationChannels {
+    /// Play the error or done cue, if audio feedback is on
+    pub sound: bool,
+    /// A toast in the main window, or a system notification while it's hidden
+    pub toast: bool,
+    /// A short message in the recording overlay
+    pub overlay: bool,
+}
+
+impl NotificationChannels {
+    const fn new(sound: bool, toast: bool, overlay: bool) -> Self {
+        Self {
+            sound,
+            toast,
+            overlay,
+        }
+    }
+}
+
+/// Which notifications are shown, and how, for each kind of event.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+pub struct NotificationPreferences {
+    /// Failed transcriptions and pastes, and warnings about the microphone
+    pub errors: NotificationChannels,
+    /// Every dictation that was transcribed and delivered
+    pub transcribed: NotificationChannels,
+    /// Something stood in for what was asked for: the recording was kept for
+    /// later, post-processing failed, or another microphone was picked
+    pub fallback: NotificationChannels,
+    /// The model was unloaded after sitting idle
+    pub model_unloaded: NotificationChannels,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            errors: NotificationChannels::new(true, true, false),
+            transcribed: NotificationChannels::new(false, false, false),
+            fallback: NotificationChannels::new(false, true, false),
+            model_unloaded: NotificationChannels::new(false, false, false),
+        }
+    }
+}
+
+/// How finished recordings are encoded for upload. Compressed uploads fall
+/// back to WAV if the server rejects them.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadCodec {
+    Wav,
+    #[default]
+    Flac,
+}
+
+/// A clean-up pass recordings can go through before transcription.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PreprocessNode {
+    HighPass,
+    Denoise,
+    /// RNNoise, at `noise_suppression_strength`
+    Rnnoise,
+    AutoGain,
+    Trim,
+}
+
+/// A node of the preprocessing chain. A bypassed node keeps its place in the
+/// chain but leaves the audio alone, to compare transcripts with and without
+/// it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct PreprocessStep {
+    pub node: PreprocessNode,
+    #[serde(default)]
+    pub bypass: bool,
+}
+
+/// A rule of the replacement dictionary: `from` as it was misrecognized, and
+/// what it should be.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct WordReplacement {
+    pub from: String,
+    pub to: String,
+}
+
+/// Casing a `Casing` text rule gives the transcript.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TextCasing {
+    Lower,
+    Upper,
+    /// The first letter of every sentence is capitalized
+    Sentence,
+    /// The first letter of every word is capitalized
+    Title,
+}
+
+/// What a text rule does to the transcript.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TextRuleAction {
+    /// Replaces `find` wherever it appears, ignoring case
+    Literal {
+        find: String,
+        replace: String,
+    },
+    /// Replaces matches of the regex `pattern`. `$1` and so on in `replace`
+    /// are its capture groups.
+    Regex {
+        pattern: String,
+        replace: String,
+    },
+    /// Turns spoken punctuation ("comma", "new line") into the marks
+    SpokenPunctuation,
+    Casing {
+        casing: TextCasing,
+    },
+    /// Writes spelled-out numbers as digits
+    Numbers,
+}
+
+/// A rule of the text rules engine. A disabled rule keeps its place in the
+/// list but leaves the transcript alone.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct TextRule {
+    pub action: TextRuleAction,
+    pub enabled: bool,
+}
+
+/// Text typed when its trigger phrase is dictated. `{slot}`s in the body are
+/// filled by the dictations that follow, in order.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct DictationTemplate {
+    pub trigger: String,
+    pub body: String,
+}
+
+/// Word error rate of one model on a calibration recording
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct ModelScore {
+    pub model_id: String,
+    pub wer: f32,
+}
+
+/// What calibrating an input device found for the user's voice and room.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct VoiceProfile {
+    /// Silero VAD speech probability above which a frame counts as speech
+    pub vad_threshold: f32,
+    /// Applied to the input before the VAD
+    pub input_gain: f32,
+    /// Installed models, best first
+    #[serde(default)]
+    pub model_scores: Vec<ModelScore>,
+    /// Unix timestamp of the calibration
+    pub calibrated_at: i64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct PostProcessProvider {
     pub id: String,
@@ -103,6 +579,27 @@ pub struct PostProcessProvider {
     pub models_endpoint: Option<String>,
 }
 
+/// OAuth 2.0 device authorization (RFC 8628) for providers that sit behind
+/// an identity provider instead of accepting a static API key.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct OAuthConfig {
+    pub device_authorization_url: String,
+    pub token_url: String,
+    pub client_id: String,
+    #[serde(default)]
+    pub scope: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct OAuthToken {
+    pub access_token: String,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// Unix timestamp in seconds, if the server said when the token expires
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
 #[serde(rename_all = "lowercase")]
 pub enum OverlayPosition {
@@ -111,6 +608,33 @@ pub enum OverlayPosition {
     Bottom,
 }
 
+/// Which monitor the recording overlay appears on, and where on it
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayPlacement {
+    /// The monitor with the cursor, at `overlay_position`
+    FollowCursor,
+    /// The monitor with the focused window, at `overlay_position`
+    FollowFocusedWindow,
+    /// Wherever it was last dragged to on the monitor with the cursor, or
+    /// at `overlay_position` on monitors it hasn't been dragged on
+    Remembered,
+}
+
+impl Default for OverlayPlacement {
+    fn default() -> Self {
+        OverlayPlacement::FollowCursor
+    }
+}
+
+/// Where the overlay was dragged to on a monitor, in logical pixels from the
+/// top left of its work area
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
+pub struct OverlayOffset {
+    pub x: f64,
+    pub y: f64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
 #[serde(rename_all = "snake_case")]
 pub enum ModelUnloadTimeout {
@@ -132,6 +656,10 @@ pub enum PasteMethod {
     None,
     ShiftInsert,
     CtrlShiftV,
+    /// Typed on a virtual keyboard, for remote desktops, VMs and games
+    VirtualKeyboard,
+    /// Inserted through AT-SPI on Linux, for apps that mishandle the others
+    Accessibility,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
@@ -141,6 +669,134 @@ pub enum ClipboardHandling {
     CopyToClipboard,
 }
 
+/// Text to seed Whisper's prompt with, so names and terminology carry over
+/// between consecutive dictations.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionContext {
+    None,
+    PreviousTranscript,
+    Clipboard,
+}
+
+impl Default for TranscriptionContext {
+    fn default() -> Self {
+        TranscriptionContext::None
+    }
+}
+
+/// How often an exported caption transcript gets a timestamp. Lines between
+/// timestamps are merged into one paragraph.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionTimestamps {
+    Off,
+    EveryLine,
+    EveryMinute,
+    EveryFiveMinutes,
+}
+
+impl Default for CaptionTimestamps {
+    fn default() -> Self {
+        CaptionTimestamps::EveryLine
+    }
+}
+
+/// Extra marker drawn on the tray icon so the state shows up regardless of
+/// the icon set or the menu bar's colors.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayIndicator {
+    Off,
+    Dot,
+    /// A dot that blinks while recording
+    Flash,
+}
+
+impl Default for TrayIndicator {
+    fn default() -> Self {
+        TrayIndicator::Off
+    }
+}
+
+/// What to do when dictation starts while a video call is using the microphone
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ConferencingGuard {
+    Off,
+    Warn,
+    /// Record from `conferencing_microphone` instead
+    SwitchMicrophone,
+}
+
+impl Default for ConferencingGuard {
+    fn default() -> Self {
+        ConferencingGuard::Off
+    }
+}
+
+/// Serial comma before the "and" or "or" ending a list
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum OxfordComma {
+    Keep,
+    Add,
+    Remove,
+}
+
+impl Default for OxfordComma {
+    fn default() -> Self {
+        OxfordComma::Keep
+    }
+}
+
+/// How dashes between clauses are written
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DashStyle {
+    Keep,
+    /// "this – that"
+    SpacedEnDash,
+    /// "this—that"
+    EmDash,
+}
+
+impl Default for DashStyle {
+    fn default() -> Self {
+        DashStyle::Keep
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteStyle {
+    Keep,
+    Straight,
+    Curly,
+}
+
+impl Default for QuoteStyle {
+    fn default() -> Self {
+        QuoteStyle::Keep
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum EllipsisStyle {
+    Keep,
+    /// The single "…" character
+    Character,
+    /// Three periods
+    Dots,
+}
+
+impl Default for EllipsisStyle {
+    fn default() -> Self {
+        EllipsisStyle::Keep
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
 #[serde(rename_all = "snake_case")]
 pub enum RecordingRetentionPeriod {
@@ -293,6 +949,211 @@ pub struct AppSettings {
     pub append_trailing_space: bool,
     #[serde(default = "default_app_language")]
     pub app_language: String,
+    #[serde(default)]
+    pub code_switching: bool,
+    #[serde(default)]
+    pub tts_readback_enabled: bool,
+    #[serde(default)]
+    pub tts_piper_path: Option<String>,
+    #[serde(default)]
+    pub tts_voice_path: Option<String>,
+    #[serde(default)]
+    pub low_power_enabled: bool,
+    #[serde(default)]
+    pub low_power_model: Option<String>,
+    #[serde(default = "default_low_power_pause_always_on_microphone")]
+    pub low_power_pause_always_on_microphone: bool,
+    #[serde(default)]
+    pub low_power_model_unload_timeout: Option<ModelUnloadTimeout>,
+    #[serde(default = "default_screen_reader_announcements")]
+    pub screen_reader_announcements: bool,
+    #[serde(default)]
+    pub shared_models_dir: Option<String>,
+    #[serde(default)]
+    pub enabled_plugins: Vec<String>,
+    #[serde(default)]
+    pub wyoming_enabled: bool,
+    #[serde(default)]
+    pub wyoming_server: Option<String>,
+    #[serde(default)]
+    pub post_process_oauth: HashMap<String, OAuthConfig>,
+    #[serde(default)]
+    pub post_process_oauth_tokens: HashMap<String, OAuthToken>,
+    #[serde(default)]
+    pub language_lock_minutes: u32,
+    #[serde(default)]
+    pub transcription_context: TranscriptionContext,
+    #[serde(default)]
+    pub retranscribe_model: Option<String>,
+    #[serde(default = "default_retranscribe_max_latency_ms")]
+    pub retranscribe_max_latency_ms: u32,
+    /// Skips silent audio and drops made-up text, only for Whisper models
+    #[serde(default = "default_hallucination_filter")]
+    pub hallucination_filter: bool,
+    #[serde(default)]
+    pub punctuation_restoration: bool,
+    #[serde(default)]
+    pub caption_export_timestamps: CaptionTimestamps,
+    #[serde(default = "default_caption_speaker_label")]
+    pub caption_speaker_label: String,
+    #[serde(default)]
+    pub tray_icon_dir: Option<String>,
+    #[serde(default)]
+    pub tray_indicator: TrayIndicator,
+    #[serde(default)]
+    pub conferencing_guard: ConferencingGuard,
+    #[serde(default)]
+    pub conferencing_microphone: Option<String>,
+    #[serde(default = "default_input_watchdog")]
+    pub input_watchdog: bool,
+    #[serde(default)]
+    pub caption_segment_at_pauses: bool,
+    #[serde(default = "default_caption_max_segment_secs")]
+    pub caption_max_segment_secs: u32,
+    #[serde(default)]
+    pub batch_deferred: bool,
+    #[serde(default)]
+    pub batch_window_start: Option<u32>,
+    #[serde(default)]
+    pub batch_window_end: Option<u32>,
+    #[serde(default)]
+    pub batch_require_ac_power: bool,
+    #[serde(default)]
+    pub batch_idle_minutes: Option<u32>,
+    #[serde(default)]
+    pub oxford_comma: OxfordComma,
+    #[serde(default)]
+    pub dash_style: DashStyle,
+    #[serde(default)]
+    pub quote_style: QuoteStyle,
+    #[serde(default)]
+    pub ellipsis_style: EllipsisStyle,
+    #[serde(default)]
+    pub preview_before_paste: bool,
+    #[serde(default)]
+    pub preview_apps: Vec<String>,
+    #[serde(default)]
+    pub injection_audit: bool,
+    #[serde(default)]
+    pub injection_marker: bool,
+    #[serde(default)]
+    pub adaptive_engine: bool,
+    #[serde(default = "default_adaptive_threshold_secs")]
+    pub adaptive_threshold_secs: u32,
+    #[serde(default)]
+    pub adaptive_long_model: Option<String>,
+    #[serde(default)]
+    pub microphone_priority: Vec<String>,
+    #[serde(default)]
+    pub remote_microphone: bool,
+    #[serde(default = "default_remote_microphone_port")]
+    pub remote_microphone_port: u16,
+    /// What a device sends to pair with the network microphone
+    #[serde(default)]
+    pub remote_microphone_token: String,
+    #[serde(default)]
+    pub app_paste_methods: HashMap<String, PasteMethod>,
+    #[serde(default)]
+    pub boost_custom_words: bool,
+    #[serde(default)]
+    pub context_rules: Vec<ContextRule>,
+    #[serde(default)]
+    pub release_to_cancel: bool,
+    #[serde(default)]
+    pub release_to_cancel_silence_secs: Option<u32>,
+    #[serde(default = "default_max_concurrent_downloads")]
+    pub max_concurrent_downloads: usize,
+    #[serde(default)]
+    pub download_limit_kbps: Option<u32>,
+    #[serde(default)]
+    pub share_destinations: Vec<ShareDestination>,
+    #[serde(default)]
+    pub compress_silence: bool,
+    /// Decode local recordings over two minutes in segments, to show
+    /// progress while they transcribe
+    #[serde(default)]
+    pub segment_long_recordings: bool,
+    #[serde(default)]
+    pub realtime_transcription: bool,
+    #[serde(default)]
+    pub realtime_endpoint: RealtimeEndpoint,
+    #[serde(default)]
+    pub deepgram_enabled: bool,
+    #[serde(default)]
+    pub deepgram: DeepgramConfig,
+    #[serde(default)]
+    pub cloud_transcription: CloudTranscription,
+    #[serde(default)]
+    pub preprocessing: Vec<PreprocessStep>,
+    #[serde(default)]
+    pub word_replacements: Vec<WordReplacement>,
+    /// Calibration results, by `AudioRecordingManager::profile_key`
+    #[serde(default)]
+    pub voice_profiles: HashMap<String, VoiceProfile>,
+    #[serde(default = "default_adaptive_vad")]
+    pub adaptive_vad: bool,
+    /// Delete text selected in the target app before pasting over it,
+    /// for apps that would otherwise insert next to the selection
+    #[serde(default)]
+    pub replace_selection: bool,
+    /// Join letters spelled after `spelling_trigger` into one word
+    #[serde(default)]
+    pub spoken_spelling: bool,
+    #[serde(default = "default_spelling_trigger")]
+    pub spelling_trigger: String,
+    #[serde(default)]
+    pub dictation_templates: Vec<DictationTemplate>,
+    #[serde(default)]
+    pub transcription_prompt: String,
+    #[serde(default)]
+    pub post_process_temperature: Option<f32>,
+    #[serde(default)]
+    pub text_rules: Vec<TextRule>,
+    #[serde(default = "default_file_cache_limit")]
+    pub file_cache_limit: usize,
+    #[serde(default)]
+    pub voice_commands: bool,
+    #[serde(default)]
+    pub system_audio_mix_microphone: bool,
+    #[serde(default)]
+    pub notification_preferences: NotificationPreferences,
+    #[serde(default)]
+    pub api_server_enabled: bool,
+    #[serde(default = "default_api_server_port")]
+    pub api_server_port: u16,
+    #[serde(default)]
+    pub api_server_token: String,
+    #[serde(default)]
+    pub wake_word_enabled: bool,
+    #[serde(default = "default_wake_words")]
+    pub wake_words: Vec<WakeWord>,
+    #[serde(default)]
+    pub pre_roll_ms: u32,
+    /// Denoise the live input with RNNoise before the VAD
+    #[serde(default)]
+    pub noise_suppression: bool,
+    /// How much of RNNoise's output is mixed with the original, live and in
+    /// the `Rnnoise` preprocessing node
+    #[serde(default = "default_noise_suppression_strength")]
+    pub noise_suppression_strength: f32,
+    /// Level the live input before the VAD, after noise suppression
+    #[serde(default)]
+    pub auto_gain_control: bool,
+    #[serde(default)]
+    pub overlay_placement: OverlayPlacement,
+    #[serde(default = "default_overlay_scale")]
+    pub overlay_scale: f32,
+    #[serde(default)]
+    pub overlay_positions: HashMap<String, OverlayOffset>,
+    #[serde(default)]
+    pub model_memory_budget_mb: Option<u64>,
+    /// Check that pasted text landed in the target app, and paste it another
+    /// way when it didn't
+    #[serde(default = "default_verify_paste")]
+    pub verify_paste: bool,
+    /// Silence after speech that ends a hands-free recording
+    #[serde(default = "default_hands_free_silence_secs")]
+    pub hands_free_silence_secs: u32,
 }
 
 fn default_model() -> String {
@@ -303,6 +1164,39 @@ fn default_always_on_microphone() -> bool {
     false
 }
 
+fn default_low_power_pause_always_on_microphone() -> bool {
+    true
+}
+
+fn default_screen_reader_announcements() -> bool {
+    // Elsewhere announcements only reach a showing window's live region
+    cfg!(any(target_os = "macos", target_os = "windows"))
+}
+
+fn default_hallucination_filter() -> bool {
+    true
+}
+
+fn default_input_watchdog() -> bool {
+    true
+}
+
+fn default_adaptive_vad() -> bool {
+    true
+}
+
+fn default_verify_paste() -> bool {
+    false
+}
+
+fn default_hands_free_silence_secs() -> u32 {
+    2
+}
+
+fn default_spelling_trigger() -> String {
+    "spell".to_string()
+}
+
 fn default_translate_to_english() -> bool {
     false
 }
@@ -323,6 +1217,10 @@ fn default_selected_language() -> String {
     "auto".to_string()
 }
 
+fn default_overlay_scale() -> f32 {
+    1.0
+}
+
 fn default_overlay_position() -> OverlayPosition {
     #[cfg(target_os = "linux")]
     return OverlayPosition::None;
@@ -346,6 +1244,51 @@ fn default_history_limit() -> usize {
     5
 }
 
+fn default_file_cache_limit() -> usize {
+    500
+}
+
+fn default_retranscribe_max_latency_ms() -> u32 {
+    4000
+}
+
+fn default_caption_speaker_label() -> String {
+    "Speaker 1".to_string()
+}
+
+fn default_caption_max_segment_secs() -> u32 {
+    20
+}
+
+fn default_adaptive_threshold_secs() -> u32 {
+    5
+}
+
+fn default_remote_microphone_port() -> u16 {
+    7010
+}
+
+fn default_api_server_port() -> u16 {
+    8178
+}
+
+fn default_wake_words() -> Vec<WakeWord> {
+    vec![WakeWord {
+        id: "default".to_string(),
+        name: "Hey Handy".to_string(),
+        binding_id: "transcribe".to_string(),
+        sensitivity: 0.5,
+    }]
+}
+
+fn default_noise_suppression_strength() -> f32 {
+    1.0
+}
+
+fn default_max_concurrent_downloads() -> usize {
+    2
+}
+
 fn default_recording_retention_period() -> RecordingRetentionPeriod {
     RecordingRetentionPeriod::PreserveLimit
 }
@@ -532,6 +1475,42 @@ pub fn get_default_settings() -> AppSettings {
             description: "Converts your speech into text.".to_string(),
             default_binding: default_shortcut.to_string(),
             current_binding: default_shortcut.to_string(),
+            when_busy: ConcurrentRecording::default(),
+            countdown_secs: 0,
+            translate: None,
+            language: None,
+            model: None,
+            custom_words: None,
+            skip_post_process: false,
+            engine: None,
+            prompt_id: None,
+            output: BindingOutput::default(),
+            output_file: None,
+            recording_mode: None,
+        },
+    );
+    bindings.insert(
+        "translate".to_string(),
+        ShortcutBinding {
+            id: "translate".to_string(),
+            name: "Translate".to_string(),
+            description: "Converts your speech into English text.".to_string(),
+            // Unbound like Live Captions, Ctrl+Alt+Space is also AltGr+Space
+            // on many European layouts
+            default_binding: String::new(),
+            current_binding: String::new(),
+            when_busy: ConcurrentRecording::default(),
+            countdown_secs: 0,
+            translate: Some(true),
+            language: None,
+            model: None,
+            custom_words: None,
+            skip_post_process: false,
+            engine: None,
+            prompt_id: None,
+            output: BindingOutput::default(),
+            output_file: None,
+            recording_mode: None,
         },
     );
     bindings.insert(
@@ -542,6 +1521,65 @@ pub fn get_default_settings() -> AppSettings {
             description: "Cancels the current recording.".to_string(),
             default_binding: "escape".to_string(),
             current_binding: "escape".to_string(),
+            when_busy: ConcurrentRecording::default(),
+            countdown_secs: 0,
+            translate: None,
+            language: None,
+            model: None,
+            custom_words: None,
+            skip_post_process: false,
+            engine: None,
+            prompt_id: None,
+            output: BindingOutput::default(),
+            output_file: None,
+            recording_mode: None,
+        },
+    );
+    bindings.insert(
+        "accept_readback".to_string(),
+        ShortcutBinding {
+            id: "accept_readback".to_string(),
+            name: "Accept Read-back".to_string(),
+            description: "Pastes the transcript after it has been read back.".to_string(),
+            default_binding: "enter".to_string(),
+            current_binding: "enter".to_string(),
+            when_busy: ConcurrentRecording::default(),
+            countdown_secs: 0,
+            translate: None,
+            language: None,
+            model: None,
+            custom_words: None,
+            skip_post_process: false,
+            engine: None,
+            prompt_id: None,
+            output: BindingOutput::default(),
+            output_file: None,
+            recording_mode: None,
+        },
+    );
+    bindings.insert(
+        "toggle_captions".to_string(),
+        ShortcutBinding {
+            id: "toggle_captions".to_string(),
+            name: "Live Captions".to_string(),
+            description: "Shows rolling captions of what the microphone hears.".to_string(),
+            // No keys until the user picks some. The merge below adds new
+            // bindings to existing settings, and a default global hotkey
+            // would take over a combination their other apps use.
+            default_binding: String::new(),
+            current_binding: String::new(),
+            when_busy: ConcurrentRecording::default(),
+            countdown_secs: 0,
+            translate: None,
+            language: None,
+            model: None,
+            custom_words: None,
+            skip_post_process: false,
+            engine: None,
+            prompt_id: None,
+            output: BindingOutput::default(),
+            output_file: None,
+            recording_mode: None,
         },
     );
 
@@ -581,10 +1619,131 @@ pub fn get_default_settings() -> AppSettings {
         mute_while_recording: false,
         append_trailing_space: false,
         app_language: default_app_language(),
+        code_switching: false,
+        tts_readback_enabled: false,
+        tts_piper_path: None,
+        tts_voice_path: None,
+        low_power_enabled: false,
+        low_power_model: None,
+        low_power_pause_always_on_microphone: true,
+        low_power_model_unload_timeout: None,
+        screen_reader_announcements: default_screen_reader_announcements(),
+        shared_models_dir: None,
+        enabled_plugins: Vec::new(),
+        wyoming_enabled: false,
+        wyoming_server: None,
+        post_process_oauth: HashMap::new(),
+        post_process_oauth_tokens: HashMap::new(),
+        language_lock_minutes: 0,
+        transcription_context: TranscriptionContext::default(),
+        retranscribe_model: None,
+        retranscribe_max_latency_ms: default_retranscribe_max_latency_ms(),
+        hallucination_filter: default_hallucination_filter(),
+        punctuation_restoration: false,
+        caption_export_timestamps: CaptionTimestamps::default(),
+        caption_speaker_label: default_caption_speaker_label(),
+        tray_icon_dir: None,
+        tray_indicator: TrayIndicator::default(),
+        conferencing_guard: ConferencingGuard::default(),
+        conferencing_microphone: None,
+        input_watchdog: default_input_watchdog(),
+        caption_segment_at_pauses: false,
+        caption_max_segment_secs: default_caption_max_segment_secs(),
+        batch_deferred: false,
+        batch_window_start: None,
+        batch_window_end: None,
+        batch_require_ac_power: false,
+        batch_idle_minutes: None,
+        oxford_comma: OxfordComma::Keep,
+        dash_style: DashStyle::Keep,
+        quote_style: QuoteStyle::Keep,
+        ellipsis_style: EllipsisStyle::Keep,
+        preview_before_paste: false,
+        preview_apps: Vec::new(),
+        injection_audit: false,
+        injection_marker: false,
+        adaptive_engine: false,
+        adaptive_threshold_secs: default_adaptive_threshold_secs(),
+        adaptive_long_model: None,
+        microphone_priority: Vec::new(),
+        remote_microphone: false,
+        remote_microphone_port: default_remote_microphone_port(),
+        remote_microphone_token: String::new(),
+        app_paste_methods: HashMap::new(),
+        boost_custom_words: false,
+        context_rules: Vec::new(),
+        release_to_cancel: false,
+        release_to_cancel_silence_secs: None,
+        max_concurrent_downloads: default_max_concurrent_downloads(),
+        download_limit_kbps: None,
+        share_destinations: Vec::new(),
+        compress_silence: false,
+        segment_long_recordings: false,
+        realtime_transcription: false,
+        realtime_endpoint: RealtimeEndpoint::default(),
+        deepgram_enabled: false,
+        deepgram: DeepgramConfig::default(),
+        cloud_transcription: CloudTranscription::default(),
+        preprocessing: Vec::new(),
+        word_replacements: Vec::new(),
+        voice_profiles: HashMap::new(),
+        adaptive_vad: default_adaptive_vad(),
+        replace_selection: false,
+        spoken_spelling: false,
+        spelling_trigger: default_spelling_trigger(),
+        dictation_templates: Vec::new(),
+        transcription_prompt: String::new(),
+        post_process_temperature: None,
+        text_rules: Vec::new(),
+        file_cache_limit: default_file_cache_limit(),
+        voice_commands: false,
+        system_audio_mix_microphone: false,
+        notification_preferences: NotificationPreferences::default(),
+        api_server_enabled: false,
+        api_server_port: default_api_server_port(),
+        api_server_token: String::new(),
+        wake_word_enabled: false,
+        wake_words: default_wake_words(),
+        pre_roll_ms: 0,
+        noise_suppression: false,
+        noise_suppression_strength: default_noise_suppression_strength(),
+        auto_gain_control: false,
+        overlay_placement: OverlayPlacement::default(),
+        overlay_scale: default_overlay_scale(),
+        overlay_positions: HashMap::new(),
+        model_memory_budget_mb: None,
+        verify_paste: default_verify_paste(),
+        hands_free_silence_secs: default_hands_free_silence_secs(),
     }
 }
 
 impl AppSettings {
+    /// Whether transcription goes to a remote service: Wyoming, Deepgram or
+    /// a cloud provider
+    pub fn remote_enabled(&self) -> bool {
+        self.wyoming_enabled || self.deepgram_enabled || self.cloud_transcription.provider.is_some()
+    }
+
+    /// How binding `binding_id` records, its own mode or `push_to_talk`
+    pub fn recording_mode(&self, binding_id: &str) -> RecordingMode {
+        self.bindings
+            .get(binding_id)
+            .and_then(|binding| binding.recording_mode)
+            .unwrap_or(if self.push_to_talk {
+                RecordingMode::PushToTalk
+            } else {
+                RecordingMode::Toggle
+            })
+    }
+
+    /// The replacement dictionary as (misheard, corrected) pairs
+    pub fn replacement_rules(&self) -> Vec<(String, String)> {
+        self.word_replacements
+            .iter()
+            .map(|rule| (rule.from.clone(), rule.to.clone()))
+            .collect()
+    }
+
     pub fn active_post_process_provider(&self) -> Option<&PostProcessProvider> {
         self.post_process_providers
             .iter()
@@ -614,6 +1773,22 @@ pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
         .expect("Failed to initialize store");
 
     let mut settings = if let Some(settings_value) = store.get("settings") {
+        // The done cue had a setting of its own before notifications could be
+        // picked per event
+        let done_sound = settings_value.get("notification_preferences").is_none()
+            && settings_value
+                .get("audio_feedback_done")
+                .and_then(|value| value.as_bool())
+                == Some(true);
+        // There was one wake word before each could start its own binding
+        let wake_word_sensitivity = if settings_value.get("wake_words").is_none() {
+            settings_value
+                .get("wake_word_sensitivity")
+                .and_then(|value| value.as_f64())
+        } else {
+            None
+        };
+
         // Parse the entire settings object
         match serde_json::from_value::<AppSettings>(settings_value) {
             Ok(mut settings) => {
@@ -621,6 +1796,17 @@ pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
                 let default_settings = get_default_settings();
                 let mut updated = false;
 
+                if done_sound {
+                    settings.notification_preferences.transcribed.sound = true;
+                    updated = true;
+                }
+                if let Some(sensitivity) = wake_word_sensitivity {
+                    for wake_word in &mut settings.wake_words {
+                        wake_word.sensitivity = sensitivity as f32;
+                    }
+                    updated = true;
+                }
+
                 // Merge default bindings into existing settings
                 for (key, value) in default_settings.bindings {
                     if !settings.bindings.contains_key(&key) {
@@ -631,7 +1817,7 @@ pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
                 }
 
                 if updated {
-                    debug!("Settings updated with new bindings");
+                    debug!("Settings updated with new bindings or migrated values");
                     store.set("settings", serde_json::to_value(&settings).unwrap());
                 }
 
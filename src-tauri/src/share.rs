@@ -0,0 +1,250 @@
+//! Sending a history entry somewhere else: a draft in the mail client, a
+//! Slack webhook, a new Notion page, or the stdin of a shell command. Each
+//! destination formats the entry with its own template.
+
+use crate::managers::history::HistoryEntry;
+use crate::settings::{ShareDestination, ShareKind};
+use chrono::{DateTime, Local};
+use log::info;
+use serde_json::json;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+const NOTION_PAGES_URL: &str = "https://api.notion.com/v1/pages";
+const NOTION_VERSION: &str = "2022-06-28";
+/// Longest text Notion accepts in one rich text object
+const NOTION_TEXT_LIMIT: usize = 2000;
+
+/// Replaces the `{name}` placeholders in `template` with their values.
+/// Unknown placeholders are left as they are.
+fn fill_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            values
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (*value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                out.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The entry formatted with the destination's template, `{text}` alone if
+/// the template is empty
+fn render(template: &str, entry: &HistoryEntry) -> String {
+    let template = if template.trim().is_empty() {
+        "{text}"
+    } else {
+        template
+    };
+    let date = DateTime::from_timestamp(entry.timestamp, 0)
+        .map(|utc| {
+            utc.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    fill_template(
+        template,
+        &[
+            ("text", entry.text()),
+            ("title", &entry.title),
+            ("app", entry.target_app.as_deref().unwrap_or("")),
+            ("date", &date),
+        ],
+    )
+}
+
+/// Percent-encodes everything but unreserved characters, for mailto links
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Splits `text` into chunks of at most `limit` characters
+fn chunks(text: &str, limit: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    chars
+        .chunks(limit)
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+fn send_email(app: &AppHandle, address: &str, subject: &str, body: &str) -> Result<(), String> {
+    let url = format!(
+        "mailto:{}?subject={}&body={}",
+        address.trim(),
+        percent_encode(subject),
+        percent_encode(body)
+    );
+    app.opener()
+        .open_url(url, None::<String>)
+        .map_err(|e| format!("Failed to open the mail client: {}", e))
+}
+
+async fn check_response(response: reqwest::Response, service: &str) -> Result<(), String> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(format!("{} returned {}: {}", service, status, body.trim()))
+}
+
+async fn send_slack(webhook_url: &str, body: &str) -> Result<(), String> {
+    let response = reqwest::Client::new()
+        .post(webhook_url.trim())
+        .json(&json!({ "text": body }))
+        .send()
+        .await
+        .map_err(|e| format!("Slack request failed: {}", e))?;
+    check_response(response, "Slack").await
+}
+
+async fn send_notion(
+    token: &str,
+    parent_page_id: &str,
+    title: &str,
+    body: &str,
+) -> Result<(), String> {
+    let paragraphs: Vec<_> = body
+        .split('\n')
+        .filter(|line| !line.trim().is_empty())
+        .flat_map(|line| chunks(line, NOTION_TEXT_LIMIT))
+        .map(|text| {
+            json!({
+                "object": "block",
+                "type": "paragraph",
+                "paragraph": { "rich_text": [{ "type": "text", "text": { "content": text } }] }
+            })
+        })
+        .collect();
+    let page = json!({
+        "parent": { "page_id": parent_page_id.trim() },
+        "properties": {
+            "title": { "title": [{ "type": "text", "text": { "content": title } }] }
+        },
+        "children": paragraphs,
+    });
+
+    let response = reqwest::Client::new()
+        .post(NOTION_PAGES_URL)
+        .bearer_auth(token.trim())
+        .header("Notion-Version", NOTION_VERSION)
+        .json(&page)
+        .send()
+        .await
+        .map_err(|e| format!("Notion request failed: {}", e))?;
+    check_response(response, "Notion").await
+}
+
+fn run_command(command: &str, body: &str) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    let (shell, flag) = ("cmd", "/C");
+    #[cfg(not(target_os = "windows"))]
+    let (shell, flag) = ("sh", "-c");
+
+    let mut child = Command::new(shell)
+        .args([flag, command])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run '{}': {}", command, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(body.as_bytes())
+            .map_err(|e| format!("Failed to write to '{}': {}", command, e))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to wait for '{}': {}", command, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "'{}' exited with {}: {}",
+            command,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Sends `entry` to `destination`.
+pub async fn share(
+    app: &AppHandle,
+    destination: &ShareDestination,
+    entry: &HistoryEntry,
+) -> Result<(), String> {
+    info!(
+        "Sharing history entry {} to '{}'",
+        entry.id, destination.name
+    );
+    let body = render(&destination.template, entry);
+    match destination.kind {
+        ShareKind::Email => send_email(app, &destination.target, &entry.title, &body),
+        ShareKind::Slack => send_slack(&destination.target, &body).await,
+        ShareKind::Notion => {
+            send_notion(&destination.token, &destination.target, &entry.title, &body).await
+        }
+        ShareKind::Command => {
+            let command = destination.target.clone();
+            tauri::async_runtime::spawn_blocking(move || run_command(&command, &body))
+                .await
+                .map_err(|e| format!("Command task failed: {}", e))?
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_template_replaces_known_placeholders() {
+        let values = [("text", "hello {title}"), ("app", "Slack")];
+        assert_eq!(
+            fill_template("{app}: {text}", &values),
+            "Slack: hello {title}"
+        );
+        assert_eq!(fill_template("{other} {text", &values), "{other} {text");
+        assert_eq!(fill_template("{{text}}", &values), "{hello {title}}");
+    }
+
+    #[test]
+    fn percent_encode_keeps_unreserved_characters() {
+        assert_eq!(percent_encode("a-b_c.d~e"), "a-b_c.d~e");
+        assert_eq!(percent_encode("a b&c\né"), "a%20b%26c%0A%C3%A9");
+    }
+
+    #[test]
+    fn chunks_split_on_characters() {
+        assert_eq!(chunks("ééé", 2), vec!["éé", "é"]);
+        assert!(chunks("", 2).is_empty());
+    }
+}
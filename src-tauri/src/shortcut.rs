@@ -1,36 +1,89 @@
-use log::{error, warn};
+use log::{error, info, warn};
 use serde::Serialize;
 use specta::Type;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_autostart::ManagerExt;
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 
-use crate::actions::ACTION_MAP;
+use crate::actions::action_for;
+use crate::cancel_gesture;
+use crate::context_rules;
+use crate::countdown;
 use crate::managers::audio::AudioRecordingManager;
+use crate::managers::batch::MINUTES_PER_DAY;
+use crate::managers::model::ModelManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::network;
+use crate::power;
+use crate::preview;
+use crate::processing;
+use crate::recording_guard;
 use crate::settings::ShortcutBinding;
 use crate::settings::{
-    self, get_settings, ClipboardHandling, LLMPrompt, OverlayPosition, PasteMethod, SoundTheme,
-    APPLE_INTELLIGENCE_DEFAULT_MODEL_ID, APPLE_INTELLIGENCE_PROVIDER_ID,
+    self, get_settings, BindingEngine, BindingOutput, CaptionTimestamps, ClipboardHandling,
+    CloudTranscription, ConcurrentRecording, ConferencingGuard, ContextRule, DashStyle,
+    DeepgramConfig, DictationTemplate, EllipsisStyle, LLMPrompt, ModelUnloadTimeout,
+    NotificationPreferences, OAuthConfig, OverlayPlacement, OverlayPosition, OxfordComma,
+    PasteMethod, PreprocessStep, QuoteStyle, RealtimeEndpoint, RecordingMode, ShareDestination,
+    SoundTheme, TextRule, TranscriptionContext, TrayIndicator, WordReplacement,
+    APPLE_INTELLIGENCE_DEFAULT_MODEL_ID, APPLE_INTELLIGENCE_PROVIDER_ID, DICTATION_BINDING_PREFIX,
 };
+use crate::text_rules;
 use crate::tray;
+use crate::tts;
 use crate::ManagedToggleState;
 
+/// Bindings that are only registered while they can do something, so they
+/// don't swallow common keys like Escape or Enter the rest of the time.
+const DYNAMIC_BINDINGS: &[&str] = &["cancel", "accept_readback"];
+
 pub fn init_shortcuts(app: &AppHandle) {
     let default_bindings = settings::get_default_settings().bindings;
     let user_settings = settings::load_or_create_app_settings(app);
 
-    // Register all default shortcuts, applying user customizations
-    for (id, default_binding) in default_bindings {
-        if id == "cancel" {
-            continue; // Skip cancel shortcut, it will be registered dynamically
-        }
-        let binding = user_settings
+    // Collect all default shortcuts, applying user customizations
+    let mut bindings: Vec<ShortcutBinding> = default_bindings
+        .into_iter()
+        // Skip dynamic shortcuts, they are registered only while needed
+        .filter(|(id, _)| !DYNAMIC_BINDINGS.contains(&id.as_str()))
+        .map(|(id, default_binding)| {
+            user_settings
+                .bindings
+                .get(&id)
+                .cloned()
+                .unwrap_or(default_binding)
+        })
+        // and bindings without keys
+        .filter(|binding| !binding.current_binding.is_empty())
+        .collect();
+    // Then the dictation shortcuts the user added and gave keys
+    bindings.extend(
+        user_settings
             .bindings
-            .get(&id)
-            .cloned()
-            .unwrap_or(default_binding);
+            .values()
+            .filter(|binding| {
+                binding.id.starts_with(DICTATION_BINDING_PREFIX)
+                    && !binding.current_binding.is_empty()
+            })
+            .cloned(),
+    );
+
+    // Native key grabs don't work on Wayland, go through the desktop portal instead
+    #[cfg(target_os = "linux")]
+    if crate::utils::is_wayland() {
+        crate::portal_shortcuts::start(app, bindings);
+        return;
+    }
 
+    register_all(app, bindings);
+}
+
+pub fn register_all(app: &AppHandle, bindings: Vec<ShortcutBinding>) {
+    for binding in bindings {
+        let id = binding.id.clone();
         if let Err(e) = register_shortcut(app, binding) {
             error!("Failed to register shortcut {} during init: {}", id, e);
         }
@@ -81,110 +134,1292 @@ pub fn change_binding(
         }
     }
 
-    // Unregister the existing binding
-    if let Err(e) = unregister_shortcut(&app, binding_to_modify.clone()) {
-        let error_msg = format!("Failed to unregister shortcut: {}", e);
+    // Unregister the existing binding, if it had keys yet
+    if !binding_to_modify.current_binding.is_empty() {
+        if let Err(e) = unregister_shortcut(&app, binding_to_modify.clone()) {
+            let error_msg = format!("Failed to unregister shortcut: {}", e);
+            error!("change_binding error: {}", error_msg);
+        }
+    }
+
+    // No keys leaves the binding unbound, as a reset does for bindings that
+    // ship without keys
+    if binding.is_empty() {
+        let mut updated_binding = binding_to_modify;
+        updated_binding.current_binding = binding;
+        settings.bindings.insert(id, updated_binding.clone());
+        settings::write_settings(&app, settings);
+        return Ok(BindingResponse {
+            success: true,
+            binding: Some(updated_binding),
+            error: None,
+        });
+    }
+
+    // Validate the new shortcut before we touch the current registration
+    if let Err(e) = validate_shortcut_string(&binding) {
+        warn!("change_binding validation error: {}", e);
+        return Err(e);
+    }
+
+    // Create an updated binding
+    let mut updated_binding = binding_to_modify;
+    updated_binding.current_binding = binding;
+
+    // Register the new binding
+    if let Err(e) = register_shortcut(&app, updated_binding.clone()) {
+        let error_msg = format!("Failed to register shortcut: {}", e);
         error!("change_binding error: {}", error_msg);
+        return Ok(BindingResponse {
+            success: false,
+            binding: None,
+            error: Some(error_msg),
+        });
+    }
+
+    // Update the binding in the settings
+    settings.bindings.insert(id, updated_binding.clone());
+
+    // Save the settings
+    settings::write_settings(&app, settings);
+
+    // Return the updated binding
+    Ok(BindingResponse {
+        success: true,
+        binding: Some(updated_binding),
+        error: None,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn reset_binding(app: AppHandle, id: String) -> Result<BindingResponse, String> {
+    let binding = settings::get_stored_binding(&app, &id);
+
+    return change_binding(app, id, binding.default_binding);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_binding_when_busy(
+    app: AppHandle,
+    id: String,
+    when_busy: ConcurrentRecording,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let binding = settings
+        .bindings
+        .get_mut(&id)
+        .ok_or_else(|| format!("Binding with id '{}' not found", id))?;
+    binding.when_busy = when_busy;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_binding_countdown(app: AppHandle, id: String, secs: u32) -> Result<(), String> {
+    if secs > countdown::MAX_COUNTDOWN_SECS {
+        return Err(format!(
+            "The countdown can be at most {} seconds",
+            countdown::MAX_COUNTDOWN_SECS
+        ));
+    }
+    let mut settings = settings::get_settings(&app);
+    let binding = settings
+        .bindings
+        .get_mut(&id)
+        .ok_or_else(|| format!("Binding with id '{}' not found", id))?;
+    binding.countdown_secs = secs;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Sets whether dictations from binding `id` are translated, `None` to follow
+/// the Translate to English setting
+#[tauri::command]
+#[specta::specta]
+pub fn change_binding_translate(
+    app: AppHandle,
+    id: String,
+    translate: Option<bool>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let binding = settings
+        .bindings
+        .get_mut(&id)
+        .ok_or_else(|| format!("Binding with id '{}' not found", id))?;
+    binding.translate = translate;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Adds a dictation shortcut with no keys yet, to be set up with its own
+/// language, model and custom words
+#[tauri::command]
+#[specta::specta]
+pub fn add_dictation_binding(app: AppHandle) -> Result<ShortcutBinding, String> {
+    let mut settings = settings::get_settings(&app);
+    let binding = ShortcutBinding {
+        id: format!(
+            "{}{}",
+            DICTATION_BINDING_PREFIX,
+            chrono::Utc::now().timestamp_millis()
+        ),
+        name: "Dictation".to_string(),
+        description: "Dictates with its own language, model and custom words.".to_string(),
+        default_binding: String::new(),
+        current_binding: String::new(),
+        when_busy: ConcurrentRecording::default(),
+        countdown_secs: 0,
+        translate: None,
+        language: None,
+        model: None,
+        custom_words: None,
+        skip_post_process: false,
+        engine: None,
+        prompt_id: None,
+        output: BindingOutput::default(),
+        output_file: None,
+        recording_mode: None,
+    };
+    settings
+        .bindings
+        .insert(binding.id.clone(), binding.clone());
+    settings::write_settings(&app, settings);
+    Ok(binding)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn remove_dictation_binding(app: AppHandle, id: String) -> Result<(), String> {
+    if !id.starts_with(DICTATION_BINDING_PREFIX) {
+        return Err(format!("'{}' isn't a dictation shortcut", id));
+    }
+    let mut settings = settings::get_settings(&app);
+    let binding = settings
+        .bindings
+        .remove(&id)
+        .ok_or_else(|| format!("Binding with id '{}' not found", id))?;
+    if !binding.current_binding.is_empty() {
+        if let Err(e) = unregister_shortcut(&app, binding) {
+            warn!("Failed to unregister removed shortcut '{}': {}", id, e);
+        }
+    }
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Sets the language, model and custom words of dictations from binding
+/// `id`, `None` for each to follow the settings
+#[tauri::command]
+#[specta::specta]
+pub fn change_binding_dictation(
+    app: AppHandle,
+    id: String,
+    language: Option<String>,
+    model: Option<String>,
+    custom_words: Option<Vec<String>>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let binding = settings
+        .bindings
+        .get_mut(&id)
+        .ok_or_else(|| format!("Binding with id '{}' not found", id))?;
+    binding.language = language;
+    binding.model = model;
+    binding.custom_words = custom_words.map(|words| {
+        words
+            .into_iter()
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty())
+            .collect()
+    });
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_binding_skip_post_process(
+    app: AppHandle,
+    id: String,
+    skip: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let binding = settings
+        .bindings
+        .get_mut(&id)
+        .ok_or_else(|| format!("Binding with id '{}' not found", id))?;
+    binding.skip_post_process = skip;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Sets the engine, post-processing prompt and output of dictations from
+/// binding `id`. `None` for the engine or prompt follows the settings.
+#[tauri::command]
+#[specta::specta]
+pub fn change_binding_pipeline(
+    app: AppHandle,
+    id: String,
+    engine: Option<BindingEngine>,
+    prompt_id: Option<String>,
+    output: BindingOutput,
+    output_file: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    if let Some(prompt_id) = &prompt_id {
+        if !settings
+            .post_process_prompts
+            .iter()
+            .any(|p| &p.id == prompt_id)
+        {
+            return Err(format!("Prompt with id '{}' not found", prompt_id));
+        }
+    }
+    let output_file = output_file
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+    if output == BindingOutput::File && output_file.is_none() {
+        return Err("Choose a file to write dictations to".to_string());
+    }
+    let binding = settings
+        .bindings
+        .get_mut(&id)
+        .ok_or_else(|| format!("Binding with id '{}' not found", id))?;
+    binding.engine = engine;
+    binding.prompt_id = prompt_id;
+    binding.output = output;
+    binding.output_file = output_file;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Sets how binding `id` records, `None` to follow the push-to-talk setting
+#[tauri::command]
+#[specta::specta]
+pub fn change_binding_recording_mode(
+    app: AppHandle,
+    id: String,
+    mode: Option<RecordingMode>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let binding = settings
+        .bindings
+        .get_mut(&id)
+        .ok_or_else(|| format!("Binding with id '{}' not found", id))?;
+    binding.recording_mode = mode;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_hands_free_silence_secs_setting(app: AppHandle, secs: u32) -> Result<(), String> {
+    if !(1..=30).contains(&secs) {
+        return Err("Hands-free recordings stop after 1 to 30 seconds of silence".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.hands_free_silence_secs = secs;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_ptt_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    // TODO if the setting is currently false, we probably want to
+    // cancel any ongoing recordings or actions
+    settings.push_to_talk = enabled;
+
+    settings::write_settings(&app, settings);
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_audio_feedback_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.audio_feedback = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_audio_feedback_volume_setting(app: AppHandle, volume: f32) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.audio_feedback_volume = volume;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_sound_theme_setting(app: AppHandle, theme: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match theme.as_str() {
+        "marimba" => SoundTheme::Marimba,
+        "pop" => SoundTheme::Pop,
+        "custom" => SoundTheme::Custom,
+        other => {
+            warn!("Invalid sound theme '{}', defaulting to marimba", other);
+            SoundTheme::Marimba
+        }
+    };
+    settings.sound_theme = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_translate_to_english_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.translate_to_english = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_tts_readback_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.tts_readback_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_tts_piper_path_setting(app: AppHandle, path: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let path = path.trim();
+    settings.tts_piper_path = (!path.is_empty()).then(|| path.to_string());
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_tts_voice_path_setting(app: AppHandle, path: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let path = path.trim();
+    settings.tts_voice_path = (!path.is_empty()).then(|| path.to_string());
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_low_power_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.low_power_enabled = enabled;
+    settings::write_settings(&app, settings);
+    power::apply(&app);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_low_power_model_setting(
+    app: AppHandle,
+    model_id: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.low_power_model = model_id;
+    settings::write_settings(&app, settings);
+    power::apply(&app);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_low_power_pause_always_on_microphone_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.low_power_pause_always_on_microphone = enabled;
+    settings::write_settings(&app, settings);
+    power::apply(&app);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_low_power_model_unload_timeout_setting(
+    app: AppHandle,
+    timeout: Option<ModelUnloadTimeout>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.low_power_model_unload_timeout = timeout;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_notification_preferences_setting(
+    app: AppHandle,
+    preferences: NotificationPreferences,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.notification_preferences = preferences;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_screen_reader_announcements_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.screen_reader_announcements = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_shared_models_dir_setting(app: AppHandle, path: String) -> Result<(), String> {
+    let path = path.trim();
+    if !path.is_empty() && !std::path::Path::new(path).is_dir() {
+        return Err(format!("Not a directory: {}", path));
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.shared_models_dir = (!path.is_empty()).then(|| path.to_string());
+    settings::write_settings(&app, settings);
+
+    app.state::<Arc<ModelManager>>()
+        .refresh_shared_models_dir()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_wyoming_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.wyoming_enabled = enabled;
+    // Only one remote engine is used at a time
+    settings.deepgram_enabled &= !enabled;
+    if enabled {
+        settings.cloud_transcription.provider = None;
+    }
+    settings::write_settings(&app, settings);
+    network::recheck_in_background(&app);
+
+    // The local model isn't used while a Wyoming server does the work
+    if enabled {
+        app.state::<Arc<TranscriptionManager>>()
+            .unload_model()
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_wyoming_server_setting(app: AppHandle, address: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let address = address.trim();
+    settings.wyoming_server = (!address.is_empty()).then(|| address.to_string());
+    settings::write_settings(&app, settings);
+    network::recheck_in_background(&app);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_retranscribe_model_setting(
+    app: AppHandle,
+    model_id: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.retranscribe_model = model_id;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<TranscriptionManager>>()
+        .unload_fallback_model();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_retranscribe_max_latency_setting(app: AppHandle, ms: u32) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.retranscribe_max_latency_ms = ms;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_punctuation_restoration_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.punctuation_restoration = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_caption_export_timestamps_setting(
+    app: AppHandle,
+    timestamps: String,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match timestamps.as_str() {
+        "off" => CaptionTimestamps::Off,
+        "every_line" => CaptionTimestamps::EveryLine,
+        "every_minute" => CaptionTimestamps::EveryMinute,
+        "every_five_minutes" => CaptionTimestamps::EveryFiveMinutes,
+        other => {
+            warn!(
+                "Invalid caption timestamps '{}', defaulting to every_line",
+                other
+            );
+            CaptionTimestamps::EveryLine
+        }
+    };
+    settings.caption_export_timestamps = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_caption_speaker_label_setting(app: AppHandle, label: String) -> Result<(), String> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("Speaker label cannot be empty".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.caption_speaker_label = label.to_string();
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_spoken_spelling_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.spoken_spelling = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_voice_commands_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.voice_commands = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_spelling_trigger_setting(app: AppHandle, trigger: String) -> Result<(), String> {
+    let trigger = trigger.trim();
+    if trigger.is_empty() || trigger.contains(char::is_whitespace) {
+        return Err("The spelling trigger must be a single word".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.spelling_trigger = trigger.to_string();
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_tray_icon_dir_setting(app: AppHandle, path: String) -> Result<(), String> {
+    let path = path.trim();
+    if !path.is_empty() && !std::path::Path::new(path).is_dir() {
+        return Err(format!("Not a directory: {}", path));
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.tray_icon_dir = (!path.is_empty()).then(|| path.to_string());
+    settings::write_settings(&app, settings);
+    tray::refresh_tray_icon(&app);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_input_watchdog_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.input_watchdog = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_adaptive_vad_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.adaptive_vad = enabled;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_voice_profile();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_noise_suppression_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.noise_suppression = enabled;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_voice_profile();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_noise_suppression_strength_setting(
+    app: AppHandle,
+    strength: f32,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.noise_suppression_strength = strength.clamp(0.0, 1.0);
+    settings::write_settings(&app, settings);
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_voice_profile();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_auto_gain_control_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.auto_gain_control = enabled;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_voice_profile();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_pre_roll_setting(app: AppHandle, ms: u32) -> Result<(), String> {
+    if ms > 2000 {
+        return Err("The pre-roll can be at most 2 seconds".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.pre_roll_ms = ms;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_voice_profile();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_system_audio_mix_microphone_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.system_audio_mix_microphone = enabled;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_selected_device()
+        .map_err(|e| format!("Failed to reopen the input: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_caption_segment_at_pauses_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.caption_segment_at_pauses = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_caption_max_segment_secs_setting(app: AppHandle, secs: u32) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.caption_max_segment_secs = secs;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_batch_deferred_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.batch_deferred = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_batch_window_start_setting(
+    app: AppHandle,
+    minute: Option<u32>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.batch_window_start = minute.map(|m| m % MINUTES_PER_DAY);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_batch_window_end_setting(app: AppHandle, minute: Option<u32>) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.batch_window_end = minute.map(|m| m % MINUTES_PER_DAY);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_batch_require_ac_power_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.batch_require_ac_power = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Sets the memory the speech model may take, reloading it now if the loaded
+/// one doesn't fit
+#[tauri::command]
+#[specta::specta]
+pub fn change_model_memory_budget_setting(
+    app: AppHandle,
+    budget_mb: Option<u64>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.model_memory_budget_mb = budget_mb.filter(|&mb| mb > 0);
+    settings::write_settings(&app, settings);
+
+    let usage = crate::memory_budget::usage(&app);
+    let over_budget = usage
+        .budget_mb
+        .zip(usage.model_estimate_mb)
+        .is_some_and(|(budget, estimate)| estimate > budget);
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    if over_budget && !rm.is_recording() {
+        let tm = app.state::<Arc<TranscriptionManager>>();
+        tm.unload_model().map_err(|e| e.to_string())?;
+        tm.initiate_model_load();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_batch_idle_minutes_setting(
+    app: AppHandle,
+    minutes: Option<u32>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.batch_idle_minutes = minutes.filter(|&m| m > 0);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_conferencing_guard_setting(app: AppHandle, guard: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match guard.as_str() {
+        "off" => ConferencingGuard::Off,
+        "warn" => ConferencingGuard::Warn,
+        "switch_microphone" => ConferencingGuard::SwitchMicrophone,
+        other => {
+            warn!("Invalid conferencing guard '{}', defaulting to off", other);
+            ConferencingGuard::Off
+        }
+    };
+    settings.conferencing_guard = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_oxford_comma_setting(app: AppHandle, comma: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match comma.as_str() {
+        "keep" => OxfordComma::Keep,
+        "add" => OxfordComma::Add,
+        "remove" => OxfordComma::Remove,
+        other => {
+            warn!("Invalid oxford comma '{}', defaulting to keep", other);
+            OxfordComma::Keep
+        }
+    };
+    settings.oxford_comma = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_dash_style_setting(app: AppHandle, style: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match style.as_str() {
+        "keep" => DashStyle::Keep,
+        "spaced_en_dash" => DashStyle::SpacedEnDash,
+        "em_dash" => DashStyle::EmDash,
+        other => {
+            warn!("Invalid dash style '{}', defaulting to keep", other);
+            DashStyle::Keep
+        }
+    };
+    settings.dash_style = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_quote_style_setting(app: AppHandle, style: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match style.as_str() {
+        "keep" => QuoteStyle::Keep,
+        "straight" => QuoteStyle::Straight,
+        "curly" => QuoteStyle::Curly,
+        other => {
+            warn!("Invalid quote style '{}', defaulting to keep", other);
+            QuoteStyle::Keep
+        }
+    };
+    settings.quote_style = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_ellipsis_style_setting(app: AppHandle, style: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match style.as_str() {
+        "keep" => EllipsisStyle::Keep,
+        "character" => EllipsisStyle::Character,
+        "dots" => EllipsisStyle::Dots,
+        other => {
+            warn!("Invalid ellipsis style '{}', defaulting to keep", other);
+            EllipsisStyle::Keep
+        }
+    };
+    settings.ellipsis_style = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_preview_before_paste_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.preview_before_paste = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_preview_apps_setting(app: AppHandle, apps: Vec<String>) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.preview_apps = apps
+        .into_iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_injection_audit_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.injection_audit = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_injection_marker_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.injection_marker = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_adaptive_engine_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.adaptive_engine = enabled;
+    settings::write_settings(&app, settings);
+    // Short recordings now need the local model even with Wyoming enabled
+    if enabled {
+        app.state::<Arc<TranscriptionManager>>()
+            .initiate_model_load();
+    } else {
+        app.state::<Arc<TranscriptionManager>>()
+            .unload_fallback_model();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_adaptive_threshold_setting(app: AppHandle, secs: u32) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.adaptive_threshold_secs = secs.max(1);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_adaptive_long_model_setting(
+    app: AppHandle,
+    model_id: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.adaptive_long_model = model_id;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<TranscriptionManager>>()
+        .unload_fallback_model();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_app_paste_methods_setting(
+    app: AppHandle,
+    methods: HashMap<String, PasteMethod>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.app_paste_methods = methods;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_boost_custom_words_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.boost_custom_words = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_transcription_prompt_setting(app: AppHandle, prompt: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.transcription_prompt = prompt;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_context_rules_setting(app: AppHandle, rules: Vec<ContextRule>) -> Result<(), String> {
+    for rule in &rules {
+        context_rules::validate_pattern(&rule.title_pattern)?;
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.context_rules = rules;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_release_to_cancel_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.release_to_cancel = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_release_to_cancel_silence_secs_setting(
+    app: AppHandle,
+    secs: Option<u32>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.release_to_cancel_silence_secs = secs.filter(|&s| s > 0);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_max_concurrent_downloads_setting(app: AppHandle, count: usize) -> Result<(), String> {
+    if !(1..=8).contains(&count) {
+        return Err("Between 1 and 8 downloads can run at once".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.max_concurrent_downloads = count;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_download_limit_kbps_setting(app: AppHandle, kbps: Option<u32>) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.download_limit_kbps = kbps.filter(|&k| k > 0);
+    settings::write_settings(&app, settings);
+
+    app.state::<Arc<ModelManager>>().refresh_download_limit();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_share_destinations_setting(
+    app: AppHandle,
+    destinations: Vec<ShareDestination>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.share_destinations = destinations;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_compress_silence_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.compress_silence = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_segment_long_recordings_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.segment_long_recordings = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_realtime_transcription_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.realtime_transcription = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_realtime_endpoint_setting(
+    app: AppHandle,
+    endpoint: RealtimeEndpoint,
+) -> Result<(), String> {
+    let url = endpoint.url.trim();
+    if !url.is_empty() && !url.starts_with("ws://") && !url.starts_with("wss://") {
+        return Err("The realtime endpoint needs a ws:// or wss:// URL".to_string());
     }
+    let mut settings = settings::get_settings(&app);
+    settings.realtime_endpoint = RealtimeEndpoint {
+        url: url.to_string(),
+        ..endpoint
+    };
+    settings::write_settings(&app, settings);
+    Ok(())
+}
 
-    // Validate the new shortcut before we touch the current registration
-    if let Err(e) = validate_shortcut_string(&binding) {
-        warn!("change_binding validation error: {}", e);
-        return Err(e);
+#[tauri::command]
+#[specta::specta]
+pub fn change_deepgram_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.deepgram_enabled = enabled;
+    settings.wyoming_enabled &= !enabled;
+    if enabled {
+        settings.cloud_transcription.provider = None;
     }
+    settings::write_settings(&app, settings);
+    network::recheck_in_background(&app);
 
-    // Create an updated binding
-    let mut updated_binding = binding_to_modify;
-    updated_binding.current_binding = binding;
-
-    // Register the new binding
-    if let Err(e) = register_shortcut(&app, updated_binding.clone()) {
-        let error_msg = format!("Failed to register shortcut: {}", e);
-        error!("change_binding error: {}", error_msg);
-        return Ok(BindingResponse {
-            success: false,
-            binding: None,
-            error: Some(error_msg),
-        });
+    // The local model isn't used while Deepgram does the work
+    if enabled {
+        app.state::<Arc<TranscriptionManager>>()
+            .unload_model()
+            .map_err(|e| e.to_string())?;
     }
+    Ok(())
+}
 
-    // Update the binding in the settings
-    settings.bindings.insert(id, updated_binding.clone());
-
-    // Save the settings
+#[tauri::command]
+#[specta::specta]
+pub fn change_preprocessing_setting(
+    app: AppHandle,
+    mut chain: Vec<PreprocessStep>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    // A node runs at most once, where it first appears
+    let mut seen = Vec::new();
+    chain.retain(|step| {
+        let first = !seen.contains(&step.node);
+        seen.push(step.node);
+        first
+    });
+    settings.preprocessing = chain;
     settings::write_settings(&app, settings);
-
-    // Return the updated binding
-    Ok(BindingResponse {
-        success: true,
-        binding: Some(updated_binding),
-        error: None,
-    })
+    Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn reset_binding(app: AppHandle, id: String) -> Result<BindingResponse, String> {
-    let binding = settings::get_stored_binding(&app, &id);
-
-    return change_binding(app, id, binding.default_binding);
+pub fn change_word_replacements_setting(
+    app: AppHandle,
+    replacements: Vec<WordReplacement>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.word_replacements = replacements
+        .into_iter()
+        .filter(|rule| !rule.from.trim().is_empty() && !rule.to.trim().is_empty())
+        .collect();
+    settings::write_settings(&app, settings);
+    Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_ptt_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+pub fn change_dictation_templates_setting(
+    app: AppHandle,
+    templates: Vec<DictationTemplate>,
+) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
+    settings.dictation_templates = templates
+        .into_iter()
+        .filter(|template| !template.trigger.trim().is_empty() && !template.body.is_empty())
+        .collect();
+    settings::write_settings(&app, settings);
+    Ok(())
+}
 
-    // TODO if the setting is currently false, we probably want to
-    // cancel any ongoing recordings or actions
-    settings.push_to_talk = enabled;
-
+#[tauri::command]
+#[specta::specta]
+pub fn change_file_cache_limit_setting(app: AppHandle, limit: usize) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.file_cache_limit = limit;
     settings::write_settings(&app, settings);
+    app.state::<Arc<TranscriptionManager>>()
+        .trim_file_cache(limit);
+    Ok(())
+}
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_text_rules_setting(app: AppHandle, rules: Vec<TextRule>) -> Result<(), String> {
+    text_rules::validate(&rules)?;
+    let mut settings = settings::get_settings(&app);
+    settings.text_rules = rules;
+    settings::write_settings(&app, settings);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_audio_feedback_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+pub fn change_deepgram_setting(app: AppHandle, config: DeepgramConfig) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
-    settings.audio_feedback = enabled;
+    settings.deepgram = DeepgramConfig {
+        api_key: config.api_key.trim().to_string(),
+        model: config.model.trim().to_string(),
+        ..config
+    };
     settings::write_settings(&app, settings);
     Ok(())
 }
 
+/// Picks the cloud provider that transcribes, `None` in `config.provider`
+/// for none, and saves the key and model of each
 #[tauri::command]
 #[specta::specta]
-pub fn change_audio_feedback_volume_setting(app: AppHandle, volume: f32) -> Result<(), String> {
+pub fn change_cloud_transcription_setting(
+    app: AppHandle,
+    config: CloudTranscription,
+) -> Result<(), String> {
+    let trim = |values: HashMap<String, String>| -> HashMap<String, String> {
+        values
+            .into_iter()
+            .map(|(id, value)| (id, value.trim().to_string()))
+            .collect()
+    };
     let mut settings = settings::get_settings(&app);
-    settings.audio_feedback_volume = volume;
+    let enabled = config.provider.is_some();
+    settings.cloud_transcription = CloudTranscription {
+        provider: config.provider,
+        api_keys: trim(config.api_keys),
+        models: trim(config.models),
+    };
+    if enabled {
+        settings.wyoming_enabled = false;
+        settings.deepgram_enabled = false;
+    }
     settings::write_settings(&app, settings);
+    network::recheck_in_background(&app);
+
+    // The local model isn't used while a cloud provider does the work
+    if enabled {
+        app.state::<Arc<TranscriptionManager>>()
+            .unload_model()
+            .map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_sound_theme_setting(app: AppHandle, theme: String) -> Result<(), String> {
+pub fn change_tray_indicator_setting(app: AppHandle, indicator: String) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
-    let parsed = match theme.as_str() {
-        "marimba" => SoundTheme::Marimba,
-        "pop" => SoundTheme::Pop,
-        "custom" => SoundTheme::Custom,
+    let parsed = match indicator.as_str() {
+        "off" => TrayIndicator::Off,
+        "dot" => TrayIndicator::Dot,
+        "flash" => TrayIndicator::Flash,
         other => {
-            warn!("Invalid sound theme '{}', defaulting to marimba", other);
-            SoundTheme::Marimba
+            warn!("Invalid tray indicator '{}', defaulting to off", other);
+            TrayIndicator::Off
         }
     };
-    settings.sound_theme = parsed;
+    settings.tray_indicator = parsed;
     settings::write_settings(&app, settings);
+    tray::refresh_tray_icon(&app);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_translate_to_english_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+pub fn change_hallucination_filter_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
-    settings.translate_to_english = enabled;
+    settings.hallucination_filter = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_code_switching_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.code_switching = enabled;
     settings::write_settings(&app, settings);
     Ok(())
 }
@@ -195,6 +1430,19 @@ pub fn change_selected_language_setting(app: AppHandle, language: String) -> Res
     let mut settings = settings::get_settings(&app);
     settings.selected_language = language;
     settings::write_settings(&app, settings);
+    app.state::<Arc<TranscriptionManager>>()
+        .clear_language_lock();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_language_lock_setting(app: AppHandle, minutes: u32) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.language_lock_minutes = minutes;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<TranscriptionManager>>()
+        .clear_language_lock();
     Ok(())
 }
 
@@ -220,6 +1468,52 @@ pub fn change_overlay_position_setting(app: AppHandle, position: String) -> Resu
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_overlay_placement_setting(app: AppHandle, placement: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match placement.as_str() {
+        "follow_cursor" => OverlayPlacement::FollowCursor,
+        "follow_focused_window" => OverlayPlacement::FollowFocusedWindow,
+        "remembered" => OverlayPlacement::Remembered,
+        other => {
+            warn!(
+                "Invalid overlay placement '{}', defaulting to follow_cursor",
+                other
+            );
+            OverlayPlacement::FollowCursor
+        }
+    };
+    settings.overlay_placement = parsed;
+    settings::write_settings(&app, settings);
+    crate::utils::update_overlay_position(&app);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_overlay_scale_setting(app: AppHandle, scale: f32) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.overlay_scale = scale.clamp(
+        crate::overlay::MIN_OVERLAY_SCALE,
+        crate::overlay::MAX_OVERLAY_SCALE,
+    );
+    settings::write_settings(&app, settings);
+    crate::utils::update_overlay_position(&app);
+    Ok(())
+}
+
+/// Forgets where the overlay was dragged to on every monitor
+#[tauri::command]
+#[specta::specta]
+pub fn reset_overlay_positions(app: AppHandle) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.overlay_positions.clear();
+    settings::write_settings(&app, settings);
+    crate::utils::update_overlay_position(&app);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_debug_mode_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
@@ -334,6 +1628,8 @@ pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(),
         "none" => PasteMethod::None,
         "shift_insert" => PasteMethod::ShiftInsert,
         "ctrl_shift_v" => PasteMethod::CtrlShiftV,
+        "virtual_keyboard" => PasteMethod::VirtualKeyboard,
+        "accessibility" => PasteMethod::Accessibility,
         other => {
             warn!("Invalid paste method '{}', defaulting to ctrl_v", other);
             PasteMethod::CtrlV
@@ -364,6 +1660,27 @@ pub fn change_clipboard_handling_setting(app: AppHandle, handling: String) -> Re
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_transcription_context_setting(app: AppHandle, context: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match context.as_str() {
+        "none" => TranscriptionContext::None,
+        "previous_transcript" => TranscriptionContext::PreviousTranscript,
+        "clipboard" => TranscriptionContext::Clipboard,
+        other => {
+            warn!(
+                "Invalid transcription context '{}', defaulting to none",
+                other
+            );
+            TranscriptionContext::None
+        }
+    };
+    settings.transcription_context = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_post_process_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
@@ -431,6 +1748,42 @@ pub fn change_post_process_api_key_setting(
     Ok(())
 }
 
+/// Sets or clears the OAuth sign-in used instead of an API key. Any existing
+/// sign-in is dropped when the configuration changes.
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_oauth_setting(
+    app: AppHandle,
+    provider_id: String,
+    oauth: Option<OAuthConfig>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    validate_provider_exists(&settings, &provider_id)?;
+    if settings.post_process_oauth.get(&provider_id) == oauth.as_ref() {
+        return Ok(());
+    }
+
+    settings.post_process_oauth_tokens.remove(&provider_id);
+    match oauth {
+        Some(oauth) => {
+            if oauth.device_authorization_url.trim().is_empty()
+                || oauth.token_url.trim().is_empty()
+                || oauth.client_id.trim().is_empty()
+            {
+                return Err(
+                    "Device authorization URL, token URL and client ID are required".to_string(),
+                );
+            }
+            settings.post_process_oauth.insert(provider_id, oauth);
+        }
+        None => {
+            settings.post_process_oauth.remove(&provider_id);
+        }
+    }
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_post_process_model_setting(
@@ -445,6 +1798,22 @@ pub fn change_post_process_model_setting(
     Ok(())
 }
 
+/// Sampling temperature for post-processing, `None` for the provider default
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_temperature_setting(
+    app: AppHandle,
+    temperature: Option<f32>,
+) -> Result<(), String> {
+    if temperature.is_some_and(|t| !(0.0..=2.0).contains(&t)) {
+        return Err("The temperature must be between 0 and 2".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.post_process_temperature = temperature;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn set_post_process_provider(app: AppHandle, provider_id: String) -> Result<(), String> {
@@ -564,16 +1933,17 @@ pub async fn fetch_post_process_models(
         .get(&provider_id)
         .cloned()
         .unwrap_or_default();
+    let uses_oauth = settings.post_process_oauth.contains_key(&provider_id);
 
     // Skip fetching if no API key for providers that typically need one
-    if api_key.trim().is_empty() && provider.id != "custom" {
+    if api_key.trim().is_empty() && !uses_oauth && provider.id != "custom" {
         return Err(format!(
             "API key is required for {}. Please add an API key to list available models.",
             provider.label
         ));
     }
 
-    crate::llm_client::fetch_models(provider, api_key).await
+    crate::llm_client::fetch_models(&app, provider).await
 }
 
 #[tauri::command]
@@ -601,6 +1971,26 @@ pub fn change_mute_while_recording_setting(app: AppHandle, enabled: bool) -> Res
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_verify_paste_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.verify_paste = enabled;
+    settings::write_settings(&app, settings);
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_replace_selection_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.replace_selection = enabled;
+    settings::write_settings(&app, settings);
+
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_append_trailing_space_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
@@ -651,7 +2041,12 @@ fn validate_shortcut_string(raw: &str) -> Result<(), String> {
 #[tauri::command]
 #[specta::specta]
 pub fn suspend_binding(app: AppHandle, id: String) -> Result<(), String> {
-    if let Some(b) = settings::get_bindings(&app).get(&id).cloned() {
+    #[cfg(target_os = "linux")]
+    if crate::portal_shortcuts::mute(&id, true) {
+        return Ok(());
+    }
+    let binding = settings::get_bindings(&app).get(&id).cloned();
+    if let Some(b) = binding.filter(|b| !b.current_binding.is_empty()) {
         if let Err(e) = unregister_shortcut(&app, b) {
             error!("suspend_binding error for id '{}': {}", id, e);
             return Err(e);
@@ -664,7 +2059,12 @@ pub fn suspend_binding(app: AppHandle, id: String) -> Result<(), String> {
 #[tauri::command]
 #[specta::specta]
 pub fn resume_binding(app: AppHandle, id: String) -> Result<(), String> {
-    if let Some(b) = settings::get_bindings(&app).get(&id).cloned() {
+    #[cfg(target_os = "linux")]
+    if crate::portal_shortcuts::mute(&id, false) {
+        return Ok(());
+    }
+    let binding = settings::get_bindings(&app).get(&id).cloned();
+    if let Some(b) = binding.filter(|b| !b.current_binding.is_empty()) {
         if let Err(e) = register_shortcut(&app, b) {
             error!("resume_binding error for id '{}': {}", id, e);
             return Err(e);
@@ -674,10 +2074,29 @@ pub fn resume_binding(app: AppHandle, id: String) -> Result<(), String> {
 }
 
 pub fn register_cancel_shortcut(app: &AppHandle) {
-    // Cancel shortcut is disabled on Linux due to instability with dynamic shortcut registration
+    register_dynamic_shortcut(app, "cancel");
+}
+
+pub fn unregister_cancel_shortcut(app: &AppHandle) {
+    unregister_dynamic_shortcut(app, "cancel");
+}
+
+/// Registers the accept and cancel shortcuts while a read-back is pending.
+pub fn register_readback_shortcuts(app: &AppHandle) {
+    register_dynamic_shortcut(app, "accept_readback");
+    register_dynamic_shortcut(app, "cancel");
+}
+
+pub fn unregister_readback_shortcuts(app: &AppHandle) {
+    unregister_dynamic_shortcut(app, "accept_readback");
+    unregister_dynamic_shortcut(app, "cancel");
+}
+
+fn register_dynamic_shortcut(app: &AppHandle, id: &'static str) {
+    // Dynamic shortcuts are disabled on Linux due to instability with dynamic shortcut registration
     #[cfg(target_os = "linux")]
     {
-        let _ = app;
+        let _ = (app, id);
         return;
     }
 
@@ -685,20 +2104,20 @@ pub fn register_cancel_shortcut(app: &AppHandle) {
     {
         let app_clone = app.clone();
         tauri::async_runtime::spawn(async move {
-            if let Some(cancel_binding) = get_settings(&app_clone).bindings.get("cancel").cloned() {
-                if let Err(e) = register_shortcut(&app_clone, cancel_binding) {
-                    eprintln!("Failed to register cancel shortcut: {}", e);
+            if let Some(binding) = get_settings(&app_clone).bindings.get(id).cloned() {
+                if let Err(e) = register_shortcut(&app_clone, binding) {
+                    eprintln!("Failed to register {} shortcut: {}", id, e);
                 }
             }
         });
     }
 }
 
-pub fn unregister_cancel_shortcut(app: &AppHandle) {
-    // Cancel shortcut is disabled on Linux due to instability with dynamic shortcut registration
+fn unregister_dynamic_shortcut(app: &AppHandle, id: &'static str) {
+    // Dynamic shortcuts are disabled on Linux due to instability with dynamic shortcut registration
     #[cfg(target_os = "linux")]
     {
-        let _ = app;
+        let _ = (app, id);
         return;
     }
 
@@ -706,14 +2125,126 @@ pub fn unregister_cancel_shortcut(app: &AppHandle) {
     {
         let app_clone = app.clone();
         tauri::async_runtime::spawn(async move {
-            if let Some(cancel_binding) = get_settings(&app_clone).bindings.get("cancel").cloned() {
+            if let Some(binding) = get_settings(&app_clone).bindings.get(id).cloned() {
                 // We ignore errors here as it might already be unregistered
-                let _ = unregister_shortcut(&app_clone, cancel_binding);
+                let _ = unregister_shortcut(&app_clone, binding);
             }
         });
     }
 }
 
+/// Dispatches a press or release of `binding_id` to its action, applying the
+/// rules of its recording mode. Shared by every shortcut backend.
+pub fn handle_shortcut_event(
+    ah: &AppHandle,
+    binding_id: &str,
+    shortcut_string: &str,
+    state: ShortcutState,
+) {
+    let settings = get_settings(ah);
+
+    let Some(action) = action_for(binding_id) else {
+        warn!(
+            "No action defined for shortcut ID '{}'. Shortcut: '{}', State: {:?}",
+            binding_id, shortcut_string, state
+        );
+        return;
+    };
+
+    if binding_id == "cancel" {
+        let audio_manager = ah.state::<Arc<AudioRecordingManager>>();
+        let is_active = audio_manager.is_recording()
+            || tts::has_pending_readback(ah)
+            || preview::has_pending_preview(ah)
+            || processing::is_processing(ah)
+            || countdown::is_counting_down();
+        if is_active && state == ShortcutState::Pressed && !cancel_gesture::cancel_pressed(ah) {
+            action.start(ah, binding_id, shortcut_string);
+        }
+    } else if binding_id == "toggle_captions" || binding_id == "accept_readback" {
+        if state == ShortcutState::Pressed {
+            recording_guard::start(ah, binding_id, shortcut_string);
+        }
+    } else if settings.recording_mode(binding_id) == RecordingMode::PushToTalk {
+        if state == ShortcutState::Pressed {
+            cancel_gesture::hold_started(ah);
+            recording_guard::start(ah, binding_id, shortcut_string);
+        } else if state == ShortcutState::Released && !cancel_gesture::hold_released(ah) {
+            recording_guard::stop(ah, binding_id, shortcut_string);
+        }
+    } else {
+        // Toggle and hands-free modes: toggle on press only
+        if state == ShortcutState::Pressed {
+            // Determine action and update state while holding the lock,
+            // but RELEASE the lock before calling the action to avoid deadlocks.
+            // (Actions may need to acquire the lock themselves, e.g., cancel_current_operation)
+            let should_start: bool;
+            {
+                let toggle_state_manager = ah.state::<ManagedToggleState>();
+                let mut states = toggle_state_manager
+                    .lock()
+                    .expect("Failed to lock toggle state manager");
+
+                let is_currently_active = states
+                    .active_toggles
+                    .entry(binding_id.to_string())
+                    .or_insert(false);
+
+                should_start = !*is_currently_active;
+                *is_currently_active = should_start;
+            } // Lock released here
+
+            // Now call the action without holding the lock
+            if should_start {
+                if !recording_guard::start(ah, binding_id, shortcut_string) {
+                    reset_toggle(ah, binding_id);
+                }
+            } else {
+                recording_guard::stop(ah, binding_id, shortcut_string);
+            }
+        }
+    }
+}
+
+/// Stops a hands-free recording once what was said has been followed by
+/// `hands_free_silence_secs` of silence. Called on every recording status
+/// tick.
+pub fn check_hands_free(app: &AppHandle) {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    let Some(binding_id) = rm.active_binding() else {
+        return;
+    };
+    let settings = get_settings(app);
+    // The wake word has its own silence to stop on
+    if settings.recording_mode(&binding_id) != RecordingMode::HandsFree
+        || rm.started_by_wake_word()
+        || rm.speech_ratio().is_none_or(|ratio| ratio == 0.0)
+    {
+        return;
+    }
+    let stop_after = Duration::from_secs(settings.hands_free_silence_secs as u64);
+    if rm.silence().is_some_and(|silence| silence >= stop_after) {
+        info!(
+            "No speech for {}s, stopping the hands-free recording",
+            settings.hands_free_silence_secs
+        );
+        reset_toggle(app, &binding_id);
+        let shortcut = settings
+            .bindings
+            .get(&binding_id)
+            .map(|binding| binding.current_binding.clone())
+            .unwrap_or_default();
+        recording_guard::stop(app, &binding_id, &shortcut);
+    }
+}
+
+/// Marks a toggle binding as off again after its start was turned down.
+pub fn reset_toggle(app: &AppHandle, binding_id: &str) {
+    if let Ok(mut states) = app.state::<ManagedToggleState>().lock() {
+        states.active_toggles.insert(binding_id.to_string(), false);
+    }
+}
+
 pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), String> {
     // Validate human-level rules first
     if let Err(e) = validate_shortcut_string(&binding.current_binding) {
@@ -737,6 +2268,12 @@ pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<()
         }
     };
 
+    // A portal session on Wayland takes the binding instead
+    #[cfg(target_os = "linux")]
+    if crate::portal_shortcuts::bind(&binding) {
+        return Ok(());
+    }
+
     // Prevent duplicate registrations that would silently shadow one another
     if app.global_shortcut().is_registered(shortcut) {
         let error_msg = format!("Shortcut '{}' is already in use", binding.current_binding);
@@ -750,62 +2287,19 @@ pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<()
     app.global_shortcut()
         .on_shortcut(shortcut, move |ah, scut, event| {
             if scut == &shortcut {
-                let shortcut_string = scut.into_string();
-                let settings = get_settings(ah);
-
-                if let Some(action) = ACTION_MAP.get(&binding_id_for_closure) {
-                    if binding_id_for_closure == "cancel" {
-                        let audio_manager = ah.state::<Arc<AudioRecordingManager>>();
-                        if audio_manager.is_recording() && event.state == ShortcutState::Pressed {
-                            action.start(ah, &binding_id_for_closure, &shortcut_string);
-                        }
-                        return;
-                    } else if settings.push_to_talk {
-                        if event.state == ShortcutState::Pressed {
-                            action.start(ah, &binding_id_for_closure, &shortcut_string);
-                        } else if event.state == ShortcutState::Released {
-                            action.stop(ah, &binding_id_for_closure, &shortcut_string);
-                        }
-                    } else {
-                        // Toggle mode: toggle on press only
-                        if event.state == ShortcutState::Pressed {
-                            // Determine action and update state while holding the lock,
-                            // but RELEASE the lock before calling the action to avoid deadlocks.
-                            // (Actions may need to acquire the lock themselves, e.g., cancel_current_operation)
-                            let should_start: bool;
-                            {
-                                let toggle_state_manager = ah.state::<ManagedToggleState>();
-                                let mut states = toggle_state_manager
-                                    .lock()
-                                    .expect("Failed to lock toggle state manager");
-
-                                let is_currently_active = states
-                                    .active_toggles
-                                    .entry(binding_id_for_closure.clone())
-                                    .or_insert(false);
-
-                                should_start = !*is_currently_active;
-                                *is_currently_active = should_start;
-                            } // Lock released here
-
-                            // Now call the action without holding the lock
-                            if should_start {
-                                action.start(ah, &binding_id_for_closure, &shortcut_string);
-                            } else {
-                                action.stop(ah, &binding_id_for_closure, &shortcut_string);
-                            }
-                        }
-                    }
-                } else {
-                    warn!(
-                        "No action defined in ACTION_MAP for shortcut ID '{}'. Shortcut: '{}', State: {:?}",
-                        binding_id_for_closure, shortcut_string, event.state
-                    );
-                }
+                handle_shortcut_event(
+                    ah,
+                    &binding_id_for_closure,
+                    &scut.into_string(),
+                    event.state,
+                );
             }
         })
         .map_err(|e| {
-            let error_msg = format!("Couldn't register shortcut '{}': {}", binding.current_binding, e);
+            let error_msg = format!(
+                "Couldn't register shortcut '{}': {}",
+                binding.current_binding, e
+            );
             error!("_register_shortcut registration error: {}", error_msg);
             error_msg
         })?;
@@ -814,6 +2308,11 @@ pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<()
 }
 
 pub fn unregister_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    if crate::portal_shortcuts::unbind(&binding.id) {
+        return Ok(());
+    }
+
     let shortcut = match binding.current_binding.parse::<Shortcut>() {
         Ok(s) => s,
         Err(e) => {
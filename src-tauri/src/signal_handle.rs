@@ -1,4 +1,6 @@
 use crate::actions::ACTION_MAP;
+use crate::recording_guard;
+use crate::shortcut;
 use crate::ManagedToggleState;
 use log::{debug, info, warn};
 use std::thread;
@@ -24,7 +26,7 @@ pub fn setup_signal_handler(app_handle: AppHandle, mut signals: Signals) {
                     let binding_id = "transcribe";
                     let shortcut_string = "SIGUSR2";
 
-                    if let Some(action) = ACTION_MAP.get(binding_id) {
+                    if ACTION_MAP.contains_key(binding_id) {
                         // Determine action and update state while holding the lock,
                         // but RELEASE the lock before calling the action to avoid deadlocks.
                         // (Actions may need to acquire the lock themselves, e.g., cancel_current_operation)
@@ -55,11 +57,22 @@ pub fn setup_signal_handler(app_handle: AppHandle, mut signals: Signals) {
                         // Now call the action without holding the lock
                         if should_start {
                             debug!("SIGUSR2: Starting transcription (was inactive)");
-                            action.start(&app_handle_for_signal, binding_id, shortcut_string);
-                            info!("SIGUSR2: Transcription started");
+                            if recording_guard::start(
+                                &app_handle_for_signal,
+                                binding_id,
+                                shortcut_string,
+                            ) {
+                                info!("SIGUSR2: Transcription started");
+                            } else {
+                                shortcut::reset_toggle(&app_handle_for_signal, binding_id);
+                            }
                         } else {
                             debug!("SIGUSR2: Stopping transcription (was active)");
-                            action.stop(&app_handle_for_signal, binding_id, shortcut_string);
+                            recording_guard::stop(
+                                &app_handle_for_signal,
+                                binding_id,
+                                shortcut_string,
+                            );
                             debug!("SIGUSR2: Transcription stopped");
                         }
                     } else {
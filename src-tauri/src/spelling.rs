@@ -0,0 +1,185 @@
+//! Spoken spelling: after the trigger word ("spell" by default), letters,
+//! NATO alphabet words and digits are joined into one word, so identifiers,
+//! codes and unusual names come out the way they were spelled. "spell kilo
+//! eight capital x-ray" becomes "k8X".
+//!
+//! Spelling stops at the first word that isn't part of it. It takes at least
+//! two spelled characters, so "I can't spell a word" is left alone.
+
+/// Fewest characters that count as spelling something
+const MIN_SPELLED: usize = 2;
+
+const NATO: &[(&str, char)] = &[
+    ("alfa", 'a'),
+    ("alpha", 'a'),
+    ("bravo", 'b'),
+    ("charlie", 'c'),
+    ("delta", 'd'),
+    ("echo", 'e'),
+    ("foxtrot", 'f'),
+    ("golf", 'g'),
+    ("hotel", 'h'),
+    ("india", 'i'),
+    ("juliet", 'j'),
+    ("juliett", 'j'),
+    ("kilo", 'k'),
+    ("lima", 'l'),
+    ("mike", 'm'),
+    ("november", 'n'),
+    ("oscar", 'o'),
+    ("papa", 'p'),
+    ("quebec", 'q'),
+    ("romeo", 'r'),
+    ("sierra", 's'),
+    ("tango", 't'),
+    ("uniform", 'u'),
+    ("victor", 'v'),
+    ("whiskey", 'w'),
+    ("whisky", 'w'),
+    ("x-ray", 'x'),
+    ("xray", 'x'),
+    ("yankee", 'y'),
+    ("zulu", 'z'),
+];
+
+const DIGITS: &[&str] = &[
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+#[derive(Debug, PartialEq)]
+enum Spelled {
+    Char(char),
+    /// The next letter is upper case
+    Capital,
+}
+
+/// What a word stands for when spelling, if anything
+fn spelled(word: &str) -> Option<Vec<Spelled>> {
+    let mut chars = word.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return c.is_ascii_alphanumeric().then(|| vec![Spelled::Char(c)]);
+    }
+    if let Some((_, letter)) = NATO.iter().find(|(name, _)| *name == word) {
+        return Some(vec![Spelled::Char(*letter)]);
+    }
+    if let Some(digit) = DIGITS.iter().position(|name| *name == word) {
+        return Some(vec![Spelled::Char((b'0' + digit as u8) as char)]);
+    }
+    match word {
+        "capital" | "uppercase" | "cap" => return Some(vec![Spelled::Capital]),
+        "dash" | "hyphen" => return Some(vec![Spelled::Char('-')]),
+        "underscore" => return Some(vec![Spelled::Char('_')]),
+        "dot" => return Some(vec![Spelled::Char('.')]),
+        _ => {}
+    }
+    // Engines often write spelled letters as "S-M-I-T-H"
+    if word.contains('-') {
+        let parts: Option<Vec<Vec<Spelled>>> = word.split('-').map(spelled).collect();
+        return parts.map(|parts| parts.into_iter().flatten().collect());
+    }
+    None
+}
+
+/// `token` lower-cased without the punctuation around it
+fn bare(token: &str) -> String {
+    token
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Punctuation after the last letter or digit of `token`
+fn trailing_punctuation(token: &str) -> &str {
+    let end = token
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_alphanumeric())
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    &token[end..]
+}
+
+/// Replaces every spelled-out sequence after `trigger` in `text` with the
+/// word it spells
+pub fn apply(text: &str, trigger: &str) -> String {
+    let trigger = trigger.trim().to_lowercase();
+    if trigger.is_empty() {
+        return text.to_string();
+    }
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let mut out: Vec<String> = Vec::with_capacity(tokens.len());
+    let mut changed = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        if bare(tokens[i]) != trigger {
+            out.push(tokens[i].to_string());
+            i += 1;
+            continue;
+        }
+
+        let mut word = String::new();
+        let mut capital = false;
+        let mut end = i + 1;
+        while let Some(pieces) = tokens.get(end).and_then(|token| spelled(&bare(token))) {
+            for piece in pieces {
+                match piece {
+                    Spelled::Capital => capital = true,
+                    Spelled::Char(c) if capital => {
+                        word.extend(c.to_uppercase());
+                        capital = false;
+                    }
+                    Spelled::Char(c) => word.extend(c.to_lowercase()),
+                }
+            }
+            end += 1;
+        }
+
+        if word.chars().count() < MIN_SPELLED {
+            out.push(tokens[i].to_string());
+            i += 1;
+            continue;
+        }
+        // Keep what followed the spelling, like the full stop ending the
+        // sentence. The commas engines put between letters go.
+        word.push_str(trailing_punctuation(tokens[end - 1]));
+        out.push(word);
+        changed = true;
+        i = end;
+    }
+
+    if changed {
+        out.join(" ")
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_letters_nato_words_and_digits() {
+        assert_eq!(
+            apply("My name is spell S, M, I, T, H.", "spell"),
+            "My name is smith."
+        );
+        assert_eq!(
+            apply(
+                "The code is Spell kilo eight capital x-ray dash four",
+                "spell"
+            ),
+            "The code is k8X-4"
+        );
+        assert_eq!(
+            apply("spell one two three and spell S-M-I-T-H, thanks", "spell"),
+            "123 and smith, thanks"
+        );
+    }
+
+    #[test]
+    fn leaves_ordinary_uses_of_the_trigger_alone() {
+        let text = "I can't spell a word, but spell is fine.";
+        assert_eq!(apply(text, "spell"), text);
+        assert_eq!(apply("spell a b", ""), "spell a b");
+        assert_eq!(apply("buchstabieren alpha bravo", "Buchstabieren"), "ab");
+    }
+}
@@ -0,0 +1,128 @@
+//! Export of a transcript's timing as SubRip (SRT) or WebVTT subtitles. The
+//! timing itself comes from the engines, see `handy_transcription::timing`.
+//! Cues are labeled with their speaker when there is more than one.
+
+use handy_transcription::timing::{has_speakers, speaker_label, TimedSegment};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+impl SubtitleFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::Vtt => "vtt",
+        }
+    }
+}
+
+/// `seconds` as `HH:MM:SS` followed by `separator` and milliseconds
+fn timestamp(seconds: f32, separator: char) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        separator,
+        millis % 1000
+    )
+}
+
+/// Renders `segments` as a subtitle file, leaving out empty ones. Cues are
+/// labeled with their speaker, as a WebVTT voice span or a prefix in SRT.
+pub fn render(format: SubtitleFormat, segments: &[TimedSegment]) -> String {
+    let (mut out, separator) = match format {
+        SubtitleFormat::Srt => (String::new(), ','),
+        SubtitleFormat::Vtt => ("WEBVTT\n\n".to_string(), '.'),
+    };
+    let labeled = has_speakers(segments);
+    let cues = segments.iter().filter(|s| !s.text.trim().is_empty());
+    for (i, segment) in cues.enumerate() {
+        if format == SubtitleFormat::Srt {
+            out.push_str(&format!("{}\n", i + 1));
+        }
+        let text = match segment.speaker.filter(|_| labeled) {
+            Some(speaker) if format == SubtitleFormat::Vtt => {
+                format!("<v {}>{}", speaker_label(speaker), segment.text.trim())
+            }
+            Some(speaker) => format!("{}: {}", speaker_label(speaker), segment.text.trim()),
+            None => segment.text.trim().to_string(),
+        };
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            timestamp(segment.start, separator),
+            timestamp(segment.end.max(segment.start), separator),
+            text
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spoken_by(speaker: u32, text: &str) -> TimedSegment {
+        TimedSegment {
+            start: 0.0,
+            end: 1.0,
+            text: text.to_string(),
+            words: Vec::new(),
+            speaker: Some(speaker),
+        }
+    }
+
+    #[test]
+    fn renders_srt_and_vtt() {
+        let segments = [
+            TimedSegment {
+                start: 0.0,
+                end: 1.5,
+                text: "Hello there.".to_string(),
+                words: Vec::new(),
+                speaker: None,
+            },
+            TimedSegment {
+                start: 1.5,
+                end: 2.0,
+                text: " ".to_string(),
+                words: Vec::new(),
+                speaker: None,
+            },
+            TimedSegment {
+                start: 3661.25,
+                end: 3662.0,
+                text: "Later".to_string(),
+                words: Vec::new(),
+                speaker: None,
+            },
+        ];
+        assert_eq!(
+            render(SubtitleFormat::Srt, &segments),
+            "1\n00:00:00,000 --> 00:00:01,500\nHello there.\n\n\
+             2\n01:01:01,250 --> 01:01:02,000\nLater\n\n"
+        );
+        assert_eq!(
+            render(SubtitleFormat::Vtt, &segments),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:01.500\nHello there.\n\n\
+             01:01:01.250 --> 01:01:02.000\nLater\n\n"
+        );
+    }
+
+    #[test]
+    fn labels_cues_only_when_there_are_several_speakers() {
+        let segments = [spoken_by(0, "Shall we start?"), spoken_by(1, "Yes.")];
+        assert!(render(SubtitleFormat::Vtt, &segments).contains("\n<v Speaker 2>Yes.\n"));
+        assert!(render(SubtitleFormat::Srt, &segments).contains("\nSpeaker 2: Yes.\n"));
+
+        let alone = [spoken_by(0, "Just me.")];
+        assert!(render(SubtitleFormat::Srt, &alone).contains("\nJust me.\n"));
+    }
+}
@@ -0,0 +1,174 @@
+//! Dictation templates. Saying a template's trigger phrase types its body up
+//! to the first `{slot}`, and each dictation after that fills the next slot
+//! and types the text up to the one after it, until the template is done.
+//! "Meeting note: {title} — attendees {people}" takes two more dictations.
+//!
+//! The slot waiting to be filled is sent to the overlay as `template-slot`,
+//! so it can show what to say next.
+
+use crate::settings::DictationTemplate;
+use log::debug;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// The template being filled, if any.
+pub type ManagedTemplateFill = Mutex<Option<TemplateFill>>;
+
+/// A template left half filled for this long is dropped, so an unrelated
+/// dictation later on is pasted as is
+const FILL_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug)]
+pub struct TemplateFill {
+    /// Slot names, in the order they are filled
+    slots: Vec<String>,
+    /// The text after each slot
+    after: Vec<String>,
+    next: usize,
+    last_step: Instant,
+}
+
+impl TemplateFill {
+    fn next_slot(&self) -> Option<&str> {
+        self.slots.get(self.next).map(String::as_str)
+    }
+
+    /// Fills the next slot with `value`, returning what to type
+    fn fill(&mut self, value: &str) -> String {
+        let text = format!("{}{}", slot_value(value), self.after[self.next]);
+        self.next += 1;
+        self.last_step = Instant::now();
+        text
+    }
+
+    fn is_done(&self) -> bool {
+        self.next >= self.slots.len()
+    }
+}
+
+/// Splits `body` into the text before the first slot and a fill for the
+/// slots, if it has any. A `{` without a closing `}` is kept as text.
+fn parse(body: &str) -> (String, Option<TemplateFill>) {
+    let mut texts = vec![String::new()];
+    let mut slots = Vec::new();
+    let mut rest = body;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}').map(|i| open + i) else {
+            break;
+        };
+        texts.last_mut().unwrap().push_str(&rest[..open]);
+        slots.push(rest[open + 1..close].trim().to_string());
+        texts.push(String::new());
+        rest = &rest[close + 1..];
+    }
+    texts.last_mut().unwrap().push_str(rest);
+
+    let before = texts.remove(0);
+    let fill = (!slots.is_empty()).then(|| TemplateFill {
+        slots,
+        after: texts,
+        next: 0,
+        last_step: Instant::now(),
+    });
+    (before, fill)
+}
+
+/// `text` lower-cased with punctuation dropped and spaces collapsed, so a
+/// trigger matches however the engine punctuated it
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn find_trigger<'a>(
+    templates: &'a [DictationTemplate],
+    spoken: &str,
+) -> Option<&'a DictationTemplate> {
+    let spoken = normalize(spoken);
+    if spoken.is_empty() {
+        return None;
+    }
+    templates
+        .iter()
+        .find(|template| normalize(&template.trigger) == spoken)
+}
+
+/// A dictation as a slot value, without the full stop engines end it with
+fn slot_value(text: &str) -> &str {
+    text.trim().trim_end_matches(['.', '。'])
+}
+
+fn emit_slot(app: &AppHandle, fill: Option<&TemplateFill>) {
+    let _ = app.emit("template-slot", fill.and_then(TemplateFill::next_slot));
+}
+
+/// What to type for a dictation that triggers a template or fills one of its
+/// slots, or `None` to paste `text` as usual. `spoken` is the transcript
+/// before post-processing, which the trigger is matched against.
+pub fn apply(
+    app: &AppHandle,
+    templates: &[DictationTemplate],
+    spoken: &str,
+    text: &str,
+) -> Option<String> {
+    let state = app.state::<ManagedTemplateFill>();
+    let mut active = state.lock().unwrap();
+
+    if let Some(template) = find_trigger(templates, spoken) {
+        debug!("Starting template '{}'", template.trigger);
+        let (before, fill) = parse(&template.body);
+        *active = fill;
+        emit_slot(app, active.as_ref());
+        return Some(before);
+    }
+
+    let fill = active
+        .as_mut()
+        .filter(|fill| fill.last_step.elapsed() < FILL_TIMEOUT)?;
+    let typed = fill.fill(text);
+    if fill.is_done() {
+        *active = None;
+    }
+    emit_slot(app, active.as_ref());
+    Some(typed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_slots_in_order() {
+        let (before, fill) = parse("Meeting note: {title} — attendees {people}");
+        assert_eq!(before, "Meeting note: ");
+        let mut fill = fill.unwrap();
+        assert_eq!(fill.next_slot(), Some("title"));
+        assert_eq!(fill.fill(" Weekly sync. "), "Weekly sync — attendees ");
+        assert_eq!(fill.next_slot(), Some("people"));
+        assert!(!fill.is_done());
+        assert_eq!(fill.fill("Ana and Bo."), "Ana and Bo");
+        assert!(fill.is_done());
+
+        let (before, fill) = parse("No slots {here");
+        assert_eq!(before, "No slots {here");
+        assert!(fill.is_none());
+    }
+
+    #[test]
+    fn matches_triggers_however_punctuated() {
+        let templates = [DictationTemplate {
+            trigger: "Meeting note".to_string(),
+            body: "{title}".to_string(),
+        }];
+        assert!(find_trigger(&templates, "Meeting note.").is_some());
+        assert!(find_trigger(&templates, " meeting,  NOTE ").is_some());
+        assert!(find_trigger(&templates, "Meeting notes").is_none());
+        assert!(find_trigger(&templates, "").is_none());
+    }
+}
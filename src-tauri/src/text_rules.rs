@@ -0,0 +1,430 @@
+//! User-defined rules run over every transcript, in the order they're listed:
+//! find and replace, spoken punctuation, casing and number formatting. They
+//! run after the engine's output has been corrected and styled, so a
+//! transcript comes out the same way whichever engine produced it.
+
+use crate::settings::{TextCasing, TextRule, TextRuleAction};
+use log::warn;
+use regex::{NoExpand, Regex, RegexBuilder};
+
+/// Punctuation a word can carry from the engine, taken off when comparing it
+const TRIM: &[char] = &[',', '.', '!', '?', ';', ':', '"', '\''];
+/// Punctuation the engine put where a spoken mark now goes
+const MARKS: &[char] = &[',', '.', '!', '?', ';', ':'];
+
+/// How a spoken mark joins the words around it
+#[derive(Clone, Copy)]
+enum Attach {
+    /// Onto the end of the word before, like a comma
+    Before,
+    /// Onto the start of the word after, like an opening parenthesis
+    After,
+    /// Takes the place of the space, like a line break
+    Replace,
+}
+
+/// Spoken forms of punctuation, longest first so "question mark" isn't
+/// taken for something ending in "mark"
+const SPOKEN: &[(&[&str], &str, Attach)] = &[
+    (&["exclamation", "point"], "!", Attach::Before),
+    (&["exclamation", "mark"], "!", Attach::Before),
+    (&["question", "mark"], "?", Attach::Before),
+    (&["full", "stop"], ".", Attach::Before),
+    (&["new", "paragraph"], "\n\n", Attach::Replace),
+    (&["new", "line"], "\n", Attach::Replace),
+    (&["open", "paren"], "(", Attach::After),
+    (&["close", "paren"], ")", Attach::Before),
+    (&["open", "quote"], "\"", Attach::After),
+    (&["close", "quote"], "\"", Attach::Before),
+    (&["comma"], ",", Attach::Before),
+    (&["period"], ".", Attach::Before),
+    (&["colon"], ":", Attach::Before),
+    (&["semicolon"], ";", Attach::Before),
+];
+
+const UNITS: &[&str] = &[
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: &[&str] = &[
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const SCALES: &[(&str, u64)] = &[
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+];
+/// Numbers below this on their own stay words, as style guides have it, so
+/// "one of them" isn't turned into "1 of them"
+const MIN_LONE_NUMBER: u64 = 10;
+
+pub fn is_enabled(rules: &[TextRule]) -> bool {
+    rules.iter().any(|rule| rule.enabled)
+}
+
+/// Checks that every regex rule compiles, so a broken pattern is caught when
+/// it's saved rather than skipped on every dictation.
+pub fn validate(rules: &[TextRule]) -> Result<(), String> {
+    for rule in rules {
+        if let TextRuleAction::Regex { pattern, .. } = &rule.action {
+            Regex::new(pattern).map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs `text` through the enabled `rules`, in order.
+pub fn apply(text: &str, rules: &[TextRule]) -> String {
+    rules
+        .iter()
+        .filter(|rule| rule.enabled)
+        .fold(text.to_string(), |text, rule| match &rule.action {
+            TextRuleAction::Literal { find, replace } if !find.is_empty() => {
+                match RegexBuilder::new(&regex::escape(find))
+                    .case_insensitive(true)
+                    .build()
+                {
+                    Ok(re) => re.replace_all(&text, NoExpand(replace)).into_owned(),
+                    Err(_) => text,
+                }
+            }
+            TextRuleAction::Literal { .. } => text,
+            TextRuleAction::Regex { pattern, replace } => match Regex::new(pattern) {
+                Ok(re) => re.replace_all(&text, replace.as_str()).into_owned(),
+                Err(e) => {
+                    warn!(
+                        "Skipping text rule with invalid pattern '{}': {}",
+                        pattern, e
+                    );
+                    text
+                }
+            },
+            TextRuleAction::SpokenPunctuation => spoken_punctuation(&text),
+            TextRuleAction::Casing { casing } => apply_casing(&text, *casing),
+            TextRuleAction::Numbers => numbers(&text),
+        })
+}
+
+fn bare(word: &str) -> String {
+    word.trim_matches(TRIM).to_lowercase()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn spoken_mark(words: &[&str]) -> Option<(usize, &'static str, Attach)> {
+    SPOKEN.iter().find_map(|(phrase, mark, attach)| {
+        let matches = phrase.len() <= words.len()
+            && phrase
+                .iter()
+                .zip(words)
+                .all(|(spoken, word)| bare(word) == *spoken);
+        matches.then_some((phrase.len(), *mark, *attach))
+    })
+}
+
+fn spoken_punctuation(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut out = String::with_capacity(text.len());
+    // What goes before the next word: a space, nothing after a line break,
+    // or an opening mark
+    let mut prefix = String::new();
+    let mut capitalize_next = false;
+    let mut i = 0;
+    while i < words.len() {
+        if let Some((len, mark, attach)) = spoken_mark(&words[i..]) {
+            match attach {
+                Attach::Before => {
+                    let trimmed = out.trim_end_matches(MARKS).trim_end().len();
+                    out.truncate(trimmed);
+                    out.push_str(mark);
+                    capitalize_next = matches!(mark, "." | "?" | "!");
+                }
+                Attach::After => {
+                    if !out.is_empty() && !out.ends_with('\n') {
+                        prefix = " ".to_string();
+                    }
+                    prefix.push_str(mark);
+                }
+                Attach::Replace => {
+                    out.truncate(out.trim_end().len());
+                    out.push_str(mark);
+                    capitalize_next = true;
+                }
+            }
+            i += len;
+            continue;
+        }
+
+        if prefix.is_empty() && !out.is_empty() && !out.ends_with('\n') {
+            prefix.push(' ');
+        }
+        out.push_str(&prefix);
+        prefix.clear();
+        if capitalize_next {
+            out.push_str(&capitalize(words[i]));
+            capitalize_next = false;
+        } else {
+            out.push_str(words[i]);
+        }
+        i += 1;
+    }
+    out
+}
+
+fn apply_casing(text: &str, casing: TextCasing) -> String {
+    match casing {
+        TextCasing::Lower => text.to_lowercase(),
+        TextCasing::Upper => text.to_uppercase(),
+        TextCasing::Title => {
+            let mut out = String::with_capacity(text.len());
+            let mut start = true;
+            for c in text.chars() {
+                if start {
+                    out.extend(c.to_uppercase());
+                } else {
+                    out.push(c);
+                }
+                start = c.is_whitespace();
+            }
+            out
+        }
+        TextCasing::Sentence => {
+            let mut out = String::with_capacity(text.len());
+            let mut start = true;
+            for c in text.chars() {
+                if start && c.is_alphabetic() {
+                    out.extend(c.to_uppercase());
+                    start = false;
+                } else {
+                    out.push(c);
+                    if matches!(c, '.' | '?' | '!' | '\n') {
+                        start = true;
+                    } else if !c.is_whitespace() && !matches!(c, '"' | '(' | '\'') {
+                        start = false;
+                    }
+                }
+            }
+            out
+        }
+    }
+}
+
+/// Parses the longest run of number words at the start of `words`, returning
+/// how many words it took and the number. A run ends at a word followed by
+/// punctuation, which stays with the number.
+fn parse_number(words: &[&str]) -> Option<(usize, u64)> {
+    let mut total = 0u64;
+    let mut current = 0u64;
+    // The smallest scale used so far, which the next one has to be below
+    let mut last_scale = u64::MAX;
+    // Whether a units or tens word can still be added to `current`
+    let mut has_units = false;
+    let mut has_tens = false;
+    let mut parsed = None;
+
+    for (i, word) in words.iter().enumerate() {
+        let lower = bare(word);
+        let parts: Vec<&str> = lower.split('-').collect();
+        let value = match parts.as_slice() {
+            [tens, unit] => match (
+                TENS.iter().position(|t| t == tens),
+                UNITS.iter().position(|u| u == unit),
+            ) {
+                (Some(t), Some(u)) if (1..10).contains(&u) && !has_units && !has_tens => {
+                    current += (t as u64 + 2) * 10 + u as u64;
+                    has_units = true;
+                    has_tens = true;
+                    true
+                }
+                _ => false,
+            },
+            [single] => {
+                if let Some(u) = UNITS.iter().position(|u| u == single) {
+                    let teen = u >= 10;
+                    if has_units || (has_tens && (teen || u == 0)) {
+                        false
+                    } else {
+                        current += u as u64;
+                        has_units = true;
+                        has_tens |= teen;
+                        true
+                    }
+                } else if let Some(t) = TENS.iter().position(|t| t == single) {
+                    if has_units || has_tens {
+                        false
+                    } else {
+                        current += (t as u64 + 2) * 10;
+                        has_tens = true;
+                        true
+                    }
+                } else if *single == "hundred" {
+                    if current == 0 || current >= 100 {
+                        false
+                    } else {
+                        current *= 100;
+                        has_units = false;
+                        has_tens = false;
+                        true
+                    }
+                } else if let Some((_, scale)) = SCALES.iter().find(|(name, _)| name == single) {
+                    if current == 0 || *scale >= last_scale {
+                        false
+                    } else {
+                        total += current * scale;
+                        current = 0;
+                        last_scale = *scale;
+                        has_units = false;
+                        has_tens = false;
+                        true
+                    }
+                } else {
+                    // "one hundred and five", but only when a number follows
+                    *single == "and"
+                        && current % 100 == 0
+                        && total + current > 0
+                        && words.get(i + 1).is_some_and(|next| {
+                            let next = bare(next);
+                            UNITS.contains(&next.as_str())
+                                || TENS.iter().any(|t| next.starts_with(t))
+                        })
+                }
+            }
+            _ => false,
+        };
+        if !value {
+            break;
+        }
+        if lower != "and" {
+            parsed = Some((i + 1, total + current));
+        }
+        if word.ends_with(TRIM) {
+            break;
+        }
+    }
+    parsed
+}
+
+fn numbers(text: &str) -> String {
+    let words: Vec<&str> = text.split(' ').collect();
+    let mut out: Vec<String> = Vec::with_capacity(words.len());
+    let mut i = 0;
+    while i < words.len() {
+        match parse_number(&words[i..]) {
+            Some((len, value)) if len > 1 || value >= MIN_LONE_NUMBER => {
+                let last = words[i + len - 1];
+                let lead = &words[i][..words[i].len() - words[i].trim_start_matches(TRIM).len()];
+                let trail = &last[last.trim_end_matches(TRIM).len()..];
+                out.push(format!("{}{}{}", lead, value, trail));
+                i += len;
+            }
+            _ => {
+                out.push(words[i].to_string());
+                i += 1;
+            }
+        }
+    }
+    out.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(action: TextRuleAction) -> TextRule {
+        TextRule {
+            action,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn replaces_literals_and_patterns_in_order() {
+        let rules = vec![
+            rule(TextRuleAction::Literal {
+                find: "Handy App".into(),
+                replace: "Handy".into(),
+            }),
+            rule(TextRuleAction::Regex {
+                pattern: r"ticket (\d+)".into(),
+                replace: "#$1".into(),
+            }),
+            TextRule {
+                action: TextRuleAction::Casing {
+                    casing: TextCasing::Upper,
+                },
+                enabled: false,
+            },
+        ];
+        assert_eq!(
+            apply("the handy app fixed ticket 42", &rules),
+            "the Handy fixed #42"
+        );
+        assert!(validate(&[rule(TextRuleAction::Regex {
+            pattern: "(".into(),
+            replace: String::new(),
+        })])
+        .is_err());
+    }
+
+    #[test]
+    fn converts_spoken_punctuation() {
+        assert_eq!(
+            spoken_punctuation("Hello comma world period how are you question mark"),
+            "Hello, world. How are you?"
+        );
+        assert_eq!(
+            spoken_punctuation("Dear Sam, comma, new line thanks open paren again close paren"),
+            "Dear Sam,\nThanks (again)"
+        );
+    }
+
+    #[test]
+    fn applies_casing() {
+        assert_eq!(
+            apply_casing("hello there. how are you? fine", TextCasing::Sentence),
+            "Hello there. How are you? Fine"
+        );
+        assert_eq!(
+            apply_casing("the quick fox", TextCasing::Title),
+            "The Quick Fox"
+        );
+    }
+
+    #[test]
+    fn formats_numbers() {
+        assert_eq!(
+            numbers("I have twenty-five apples and one hundred and three pears"),
+            "I have 25 apples and 103 pears"
+        );
+        assert_eq!(
+            numbers("three thousand two hundred, one of them"),
+            "3200, one of them"
+        );
+        assert_eq!(numbers("twelve five"), "12 five");
+        assert_eq!(numbers("two million people"), "2000000 people");
+    }
+}
@@ -0,0 +1,519 @@
+//! The engines a recording can be transcribed with, behind one interface.
+//! The manager asks the registry for the provider the settings select and
+//! hands it the audio, so a new provider only has to implement
+//! `TranscriptionProvider` and be listed in `REMOTE_PROVIDERS`.
+//!
+//! Remote providers send their requests through the crate's `Engine`
+//! clients. A provider adds what the app needs around them: reading the
+//! settings, splitting long recordings, progress and retry events, and the
+//! segments returned for history.
+
+use crate::deepgram;
+use crate::managers::transcription::TranscriptionManager;
+use crate::openai_realtime::AudioFeed;
+use crate::settings::{AppSettings, CloudProvider};
+use anyhow::{anyhow, Result};
+use futures_util::future::BoxFuture;
+use futures_util::{stream, FutureExt, StreamExt};
+use handy_transcription::timing::TimedSegment;
+use handy_transcription::{
+    constants, fireworks, gladia, mistral, overlap_forced_cuts, skip_overlap, split_on_silence,
+    timing, wyoming, Engine, Request,
+};
+use log::info;
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+
+/// Remote recordings longer than this are sent in chunks, to stay under
+/// upload limits and finish sooner
+const CHUNK_MAX_MS: usize = 120_000;
+const CHUNK_MIN_MS: usize = 30_000;
+/// Audio repeated in the next chunk where one had to be cut mid-speech
+const CHUNK_OVERLAP_MS: usize = 1_000;
+/// Words compared where overlapping chunks meet, more than a second of
+/// speech holds
+const OVERLAP_WORDS: usize = 8;
+/// Chunks being transcribed at the same time
+const CHUNK_PARALLELISM: usize = 4;
+
+/// What a provider can do beyond turning a finished recording into text
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Capabilities {
+    /// Transcribes a dictation while it records, through `stream`
+    pub streaming: bool,
+    /// Labels who said what
+    pub diarization: bool,
+    /// Honors `translate_to_english`
+    pub translation: bool,
+}
+
+/// What a provider made of a recording
+pub struct TimedTranscript {
+    pub text: String,
+    /// Timing of the transcript, when the engine gives it
+    pub segments: Option<Vec<TimedSegment>>,
+}
+
+/// A recording to transcribe. The manager is passed along for providers that
+/// use its loaded model or its segmenting of long jobs.
+pub struct Job<'a> {
+    pub manager: &'a TranscriptionManager,
+    pub settings: &'a AppSettings,
+    pub audio: Vec<f32>,
+}
+
+pub trait TranscriptionProvider: Send + Sync {
+    /// Shown in logs
+    fn name(&self) -> &'static str;
+
+    /// Whether the settings select this provider
+    fn is_enabled(&self, settings: &AppSettings) -> bool;
+
+    fn capabilities(&self, settings: &AppSettings) -> Capabilities;
+
+    /// `host:port` the provider is reached at, for checking the network can
+    /// reach it. `None` for providers that don't use the network.
+    fn address(&self, _settings: &AppSettings) -> Option<String> {
+        None
+    }
+
+    fn transcribe<'a>(&'a self, job: Job<'a>) -> BoxFuture<'a, Result<TimedTranscript>>;
+
+    /// Streams a dictation while it records and returns its transcript. Only
+    /// called when `capabilities` reports streaming.
+    fn stream(
+        &self,
+        _app: &AppHandle,
+        _settings: &AppSettings,
+        _feed: &mut AudioFeed,
+    ) -> Result<String> {
+        Err(anyhow!("{} can't stream", self.name()))
+    }
+}
+
+/// Custom words to send to a remote provider as a hint
+pub fn vocabulary(settings: &AppSettings) -> &[String] {
+    if settings.boost_custom_words {
+        &settings.custom_words
+    } else {
+        &[]
+    }
+}
+
+/// What the user describes their dictations as, sent to engines that take a
+/// prompt
+pub fn prompt(settings: &AppSettings) -> Option<&str> {
+    Some(settings.transcription_prompt.trim()).filter(|prompt| !prompt.is_empty())
+}
+
+/// `samples` as the crate's engines take them, with the language, custom
+/// words and prompt from `settings`
+fn request<'a>(settings: &'a AppSettings, samples: &'a [f32]) -> Request<'a> {
+    Request {
+        samples,
+        language: Some(settings.selected_language.as_str()).filter(|language| *language != "auto"),
+        vocabulary: vocabulary(settings),
+        prompt: prompt(settings),
+    }
+}
+
+/// Whether `error` came from not reaching a remote provider at all, as
+/// opposed to the provider turning the request down. Dictations that fail
+/// this way are kept until it can be reached.
+pub fn is_unreachable(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return e.is_connect() || e.is_timeout();
+        }
+        cause.downcast_ref::<std::io::Error>().is_some_and(|e| {
+            matches!(
+                e.kind(),
+                ErrorKind::ConnectionRefused
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::NotConnected
+                    | ErrorKind::AddrNotAvailable
+                    | ErrorKind::TimedOut
+                    | ErrorKind::WouldBlock
+                    | ErrorKind::HostUnreachable
+                    | ErrorKind::NetworkUnreachable
+                    | ErrorKind::NetworkDown
+            )
+        })
+    })
+}
+
+/// Transcribes `audio` with `transcribe`, in chunks cut at pauses when it's
+/// longer than `CHUNK_MAX_MS`. Chunks go out `CHUNK_PARALLELISM` at a time
+/// and their transcripts are joined in order, leaving out the words repeated
+/// where chunks overlap.
+pub async fn transcribe_chunked<'a, F, Fut>(
+    manager: &TranscriptionManager,
+    audio: &'a [f32],
+    transcribe: F,
+) -> Result<String>
+where
+    F: Fn(&'a [f32]) -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    let sample_rate = constants::WHISPER_SAMPLE_RATE as usize;
+    if audio.len() <= sample_rate * CHUNK_MAX_MS / 1000 {
+        return transcribe(audio).await;
+    }
+
+    let segments = split_on_silence(audio, sample_rate, CHUNK_MIN_MS, CHUNK_MAX_MS);
+    let chunks = overlap_forced_cuts(audio, sample_rate, &segments, CHUNK_OVERLAP_MS);
+    let overlapping: Vec<bool> = chunks
+        .iter()
+        .zip(&segments)
+        .map(|(chunk, segment)| chunk.start != segment.start)
+        .collect();
+    let total = chunks.len();
+    info!(
+        "Sending the recording in {} chunks, {} at a time",
+        total, CHUNK_PARALLELISM
+    );
+
+    manager.start_progress(0, total);
+    let mut results = stream::iter(chunks.into_iter().map(|range| transcribe(&audio[range])))
+        .buffered(CHUNK_PARALLELISM);
+    let mut text = String::new();
+    let mut completed = 0;
+    while let Some(result) = results.next().await {
+        let part = result?;
+        let new = if overlapping[completed] {
+            skip_overlap(&text, &part, OVERLAP_WORDS)
+        } else {
+            part.trim()
+        };
+        if !new.is_empty() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(new);
+        }
+        completed += 1;
+        manager.report_progress(completed, total);
+    }
+    Ok(text)
+}
+
+/// Transcribes `job` with `engine` as `transcribe_chunked` does. Word
+/// timing is only kept when the recording went in one request, chunks are
+/// timed from their own start.
+async fn transcribe_timed(job: &Job<'_>, engine: &dyn Engine) -> Result<TimedTranscript> {
+    let whole = Mutex::new(None);
+    let settings = job.settings;
+    let text = transcribe_chunked(job.manager, &job.audio, |samples| {
+        let whole = &whole;
+        let single = samples.len() == job.audio.len();
+        async move {
+            let transcript = engine.transcribe(request(settings, samples)).await?;
+            if single {
+                *whole.lock().unwrap() = Some(transcript.segments);
+            }
+            Ok(transcript.text)
+        }
+    })
+    .await?;
+    let segments = whole.into_inner().unwrap();
+    Ok(TimedTranscript {
+        text,
+        segments: segments.filter(|s: &Vec<_>| !s.is_empty()),
+    })
+}
+
+/// The model loaded in the manager
+struct Local;
+
+impl TranscriptionProvider for Local {
+    fn name(&self) -> &'static str {
+        "local model"
+    }
+
+    fn is_enabled(&self, _settings: &AppSettings) -> bool {
+        true
+    }
+
+    fn capabilities(&self, _settings: &AppSettings) -> Capabilities {
+        // Whisper models translate, the others transcribe as spoken
+        Capabilities {
+            translation: true,
+            ..Capabilities::default()
+        }
+    }
+
+    fn transcribe<'a>(&'a self, job: Job<'a>) -> BoxFuture<'a, Result<TimedTranscript>> {
+        async move {
+            let result = job.manager.transcribe_local(job.settings, job.audio)?;
+            Ok(TimedTranscript {
+                text: result.text,
+                segments: timing::from_engine(result.segments),
+            })
+        }
+        .boxed()
+    }
+}
+
+struct Wyoming;
+
+impl TranscriptionProvider for Wyoming {
+    fn name(&self) -> &'static str {
+        "Wyoming"
+    }
+
+    fn is_enabled(&self, settings: &AppSettings) -> bool {
+        settings.wyoming_enabled
+    }
+
+    fn address(&self, settings: &AppSettings) -> Option<String> {
+        settings.wyoming_server.clone()
+    }
+
+    fn capabilities(&self, _settings: &AppSettings) -> Capabilities {
+        Capabilities::default()
+    }
+
+    fn transcribe<'a>(&'a self, job: Job<'a>) -> BoxFuture<'a, Result<TimedTranscript>> {
+        async move {
+            let address = job
+                .settings
+                .wyoming_server
+                .clone()
+                .ok_or_else(|| anyhow!("No Wyoming server configured"))?;
+            let client = wyoming::Client { address };
+            job.manager.transcribe_remote(
+                &client,
+                &client.address,
+                request(job.settings, &job.audio),
+            )
+        }
+        .boxed()
+    }
+}
+
+struct Deepgram;
+
+impl TranscriptionProvider for Deepgram {
+    fn name(&self) -> &'static str {
+        "Deepgram"
+    }
+
+    fn is_enabled(&self, settings: &AppSettings) -> bool {
+        settings.deepgram_enabled
+    }
+
+    fn address(&self, _settings: &AppSettings) -> Option<String> {
+        Some(handy_transcription::deepgram::HOST.to_string())
+    }
+
+    fn capabilities(&self, settings: &AppSettings) -> Capabilities {
+        Capabilities {
+            streaming: settings.deepgram.streaming,
+            diarization: settings.deepgram.diarize,
+            ..Capabilities::default()
+        }
+    }
+
+    fn transcribe<'a>(&'a self, job: Job<'a>) -> BoxFuture<'a, Result<TimedTranscript>> {
+        async move {
+            let on_retry = |retry, max_retries, delay| {
+                job.manager.report_retry(retry, max_retries, delay);
+            };
+            let client = deepgram::client(&job.settings.deepgram, &on_retry);
+            if job.settings.deepgram.diarize {
+                // Speakers are numbered anew in each request, so the
+                // recording can't be split
+                let transcript = client.transcribe(request(job.settings, &job.audio)).await?;
+                return Ok(TimedTranscript {
+                    text: transcript.text,
+                    segments: Some(transcript.segments).filter(|s| !s.is_empty()),
+                });
+            }
+            transcribe_timed(&job, &client).await
+        }
+        .boxed()
+    }
+
+    fn stream(
+        &self,
+        app: &AppHandle,
+        settings: &AppSettings,
+        feed: &mut AudioFeed,
+    ) -> Result<String> {
+        deepgram::stream(app, settings, feed)
+    }
+}
+
+/// Gladia, which uploads the recording and polls for its transcript
+struct Gladia;
+
+impl TranscriptionProvider for Gladia {
+    fn name(&self) -> &'static str {
+        "Gladia"
+    }
+
+    fn is_enabled(&self, settings: &AppSettings) -> bool {
+        settings.cloud_transcription.provider == Some(CloudProvider::Gladia)
+    }
+
+    fn address(&self, _settings: &AppSettings) -> Option<String> {
+        Some(gladia::HOST.to_string())
+    }
+
+    fn capabilities(&self, _settings: &AppSettings) -> Capabilities {
+        Capabilities::default()
+    }
+
+    fn transcribe<'a>(&'a self, job: Job<'a>) -> BoxFuture<'a, Result<TimedTranscript>> {
+        async move {
+            let client = gladia::Client {
+                config: gladia::Config {
+                    api_key: job
+                        .settings
+                        .cloud_transcription
+                        .api_key(CloudProvider::Gladia),
+                },
+            };
+            transcribe_timed(&job, &client).await
+        }
+        .boxed()
+    }
+}
+
+/// Fireworks AI's hosted Whisper models
+struct Fireworks;
+
+impl Fireworks {
+    fn config(settings: &AppSettings) -> fireworks::Config {
+        let cloud = &settings.cloud_transcription;
+        fireworks::Config {
+            api_key: cloud.api_key(CloudProvider::Fireworks),
+            model: cloud.model(CloudProvider::Fireworks),
+        }
+    }
+}
+
+impl TranscriptionProvider for Fireworks {
+    fn name(&self) -> &'static str {
+        "Fireworks AI"
+    }
+
+    fn is_enabled(&self, settings: &AppSettings) -> bool {
+        settings.cloud_transcription.provider == Some(CloudProvider::Fireworks)
+    }
+
+    fn address(&self, settings: &AppSettings) -> Option<String> {
+        Some(fireworks::host(fireworks::model(&Self::config(settings))))
+    }
+
+    fn capabilities(&self, _settings: &AppSettings) -> Capabilities {
+        Capabilities::default()
+    }
+
+    fn transcribe<'a>(&'a self, job: Job<'a>) -> BoxFuture<'a, Result<TimedTranscript>> {
+        async move {
+            let client = fireworks::Client {
+                config: Self::config(job.settings),
+            };
+            transcribe_timed(&job, &client).await
+        }
+        .boxed()
+    }
+}
+
+/// Mistral's Voxtral models, asked for a transcript over chat
+struct Mistral;
+
+impl TranscriptionProvider for Mistral {
+    fn name(&self) -> &'static str {
+        "Mistral"
+    }
+
+    fn is_enabled(&self, settings: &AppSettings) -> bool {
+        settings.cloud_transcription.provider == Some(CloudProvider::Mistral)
+    }
+
+    fn address(&self, _settings: &AppSettings) -> Option<String> {
+        Some(mistral::HOST.to_string())
+    }
+
+    fn capabilities(&self, _settings: &AppSettings) -> Capabilities {
+        Capabilities::default()
+    }
+
+    fn transcribe<'a>(&'a self, job: Job<'a>) -> BoxFuture<'a, Result<TimedTranscript>> {
+        async move {
+            let cloud = &job.settings.cloud_transcription;
+            let client = mistral::Client {
+                config: mistral::Config {
+                    api_key: cloud.api_key(CloudProvider::Mistral),
+                    model: cloud.model(CloudProvider::Mistral),
+                },
+            };
+            transcribe_timed(&job, &client).await
+        }
+        .boxed()
+    }
+}
+
+/// Remote providers in order of precedence. The settings enable one at a
+/// time, but if several are, the first wins.
+static REMOTE_PROVIDERS: Lazy<Vec<Arc<dyn TranscriptionProvider>>> = Lazy::new(|| {
+    vec![
+        Arc::new(Deepgram),
+        Arc::new(Gladia),
+        Arc::new(Fireworks),
+        Arc::new(Mistral),
+        Arc::new(Wyoming),
+    ]
+});
+
+static LOCAL_PROVIDER: Lazy<Arc<dyn TranscriptionProvider>> = Lazy::new(|| Arc::new(Local));
+
+/// The remote provider the settings select, if any
+pub fn remote(settings: &AppSettings) -> Option<Arc<dyn TranscriptionProvider>> {
+    REMOTE_PROVIDERS
+        .iter()
+        .find(|provider| provider.is_enabled(settings))
+        .cloned()
+}
+
+pub fn local() -> Arc<dyn TranscriptionProvider> {
+    LOCAL_PROVIDER.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_connection_failures_are_unreachable() {
+        let refused = anyhow::Error::new(std::io::Error::from(ErrorKind::ConnectionRefused))
+            .context("Couldn't connect to Wyoming server at localhost:10300");
+        assert!(is_unreachable(&refused));
+        let timed_out = anyhow::Error::new(std::io::Error::from(ErrorKind::WouldBlock));
+        assert!(is_unreachable(&timed_out));
+
+        assert!(!is_unreachable(&anyhow!(
+            "Deepgram returned 401 Unauthorized"
+        )));
+        let invalid = anyhow::Error::new(std::io::Error::from(ErrorKind::InvalidData));
+        assert!(!is_unreachable(&invalid));
+    }
+
+    #[test]
+    fn picks_the_selected_cloud_provider() {
+        let mut settings = crate::settings::get_default_settings();
+        assert!(remote(&settings).is_none());
+        settings.cloud_transcription.provider = Some(CloudProvider::Fireworks);
+        assert_eq!(remote(&settings).unwrap().name(), "Fireworks AI");
+        settings.cloud_transcription.provider = Some(CloudProvider::Gladia);
+        assert_eq!(remote(&settings).unwrap().name(), "Gladia");
+        settings.cloud_transcription.provider = Some(CloudProvider::Mistral);
+        assert_eq!(remote(&settings).unwrap().name(), "Mistral");
+    }
+}
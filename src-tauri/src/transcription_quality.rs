@@ -0,0 +1,107 @@
+//! How well each engine does for the user, from the thumbs up or down given
+//! to history entries, summed up per engine and language.
+
+use serde::Serialize;
+use specta::Type;
+use std::collections::HashMap;
+
+/// One rated dictation, as recorded in history
+#[derive(Clone, Debug)]
+pub struct RatingRecord {
+    /// Model ID, or the remote provider's name
+    pub engine: String,
+    pub language: Option<String>,
+    pub good: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Type, PartialEq)]
+pub struct QualityStats {
+    pub engine: String,
+    pub language: Option<String>,
+    pub up: usize,
+    pub down: usize,
+    /// Fraction of ratings that were thumbs up
+    pub approval: f32,
+}
+
+/// Ratings per engine and language, best approved first. Combinations with
+/// more ratings come first among equally approved ones.
+pub fn quality_report(records: &[RatingRecord]) -> Vec<QualityStats> {
+    let mut counts: HashMap<(&str, Option<&str>), (usize, usize)> = HashMap::new();
+    for record in records {
+        let (up, down) = counts
+            .entry((record.engine.as_str(), record.language.as_deref()))
+            .or_default();
+        if record.good {
+            *up += 1;
+        } else {
+            *down += 1;
+        }
+    }
+
+    let mut report: Vec<QualityStats> = counts
+        .into_iter()
+        .map(|((engine, language), (up, down))| QualityStats {
+            engine: engine.to_string(),
+            language: language.map(str::to_string),
+            up,
+            down,
+            approval: up as f32 / (up + down) as f32,
+        })
+        .collect();
+    report.sort_by(|a, b| {
+        b.approval
+            .total_cmp(&a.approval)
+            .then_with(|| (b.up + b.down).cmp(&(a.up + a.down)))
+            .then_with(|| a.engine.cmp(&b.engine))
+            .then_with(|| a.language.cmp(&b.language))
+    });
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(engine: &str, language: &str, good: bool) -> RatingRecord {
+        RatingRecord {
+            engine: engine.to_string(),
+            language: Some(language.to_string()),
+            good,
+        }
+    }
+
+    #[test]
+    fn groups_by_engine_and_language_best_first() {
+        let records = [
+            record("small", "en", true),
+            record("small", "en", false),
+            record("large", "en", true),
+            record("large", "en", true),
+            record("large", "de", true),
+            record("small", "de", false),
+        ];
+        let report = quality_report(&records);
+        let summary: Vec<_> = report
+            .iter()
+            .map(|s| {
+                (
+                    s.engine.as_str(),
+                    s.language.as_deref().unwrap(),
+                    s.up,
+                    s.down,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("large", "en", 2, 0),
+                ("large", "de", 1, 0),
+                ("small", "en", 1, 1),
+                ("small", "de", 0, 1),
+            ]
+        );
+        assert_eq!(report[2].approval, 0.5);
+    }
+}
@@ -1,17 +1,65 @@
-use crate::settings;
+use crate::settings::{self, TrayIndicator};
 use crate::tray_i18n::get_tray_translations;
+use log::warn;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 use tauri::image::Image;
 use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
 use tauri::tray::TrayIcon;
-use tauri::{AppHandle, Manager, Theme};
+use tauri::{AppHandle, Emitter, Manager, Theme};
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TrayIconState {
     Idle,
     Recording,
     Transcribing,
+    /// Shown briefly after a failed transcription, then back to idle
+    Error,
+}
+
+impl TrayIconState {
+    /// File name stem of the state's icon in a custom icon set
+    fn file_stem(&self) -> &'static str {
+        match self {
+            TrayIconState::Idle => "idle",
+            TrayIconState::Recording => "recording",
+            TrayIconState::Transcribing => "transcribing",
+            TrayIconState::Error => "error",
+        }
+    }
+
+    /// Color of the indicator dot, if the state has one
+    fn indicator_color(&self) -> Option<[u8; 3]> {
+        match self {
+            TrayIconState::Idle => None,
+            TrayIconState::Recording => Some([0xE5, 0x48, 0x4D]),
+            TrayIconState::Transcribing => Some([0x3B, 0x82, 0xF6]),
+            TrayIconState::Error => Some([0xF9, 0x73, 0x16]),
+        }
+    }
 }
 
+#[derive(Clone, Debug, Serialize)]
+pub struct TrayStateEvent {
+    pub state: TrayIconState,
+}
+
+const ERROR_DISPLAY: Duration = Duration::from_secs(3);
+const FLASH_INTERVAL: Duration = Duration::from_millis(500);
+
+static CURRENT_STATE: Mutex<TrayIconState> = Mutex::new(TrayIconState::Idle);
+/// Bumped on every state change so flash and error timers from an earlier
+/// state stop.
+static STATE_GENERATION: AtomicU64 = AtomicU64::new(0);
+/// Only the bundled monochrome icons work as macOS template images, colored
+/// icons and dots have to be shown as they are.
+static ICON_IS_TEMPLATE: AtomicBool = AtomicBool::new(true);
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum AppTheme {
     Dark,
@@ -42,37 +90,178 @@ pub fn get_current_theme(app: &AppHandle) -> AppTheme {
 pub fn get_icon_path(theme: AppTheme, state: TrayIconState) -> &'static str {
     match (theme, state) {
         // Dark theme uses light icons
-        (AppTheme::Dark, TrayIconState::Idle) => "resources/tray_idle.png",
+        (AppTheme::Dark, TrayIconState::Idle | TrayIconState::Error) => "resources/tray_idle.png",
         (AppTheme::Dark, TrayIconState::Recording) => "resources/tray_recording.png",
         (AppTheme::Dark, TrayIconState::Transcribing) => "resources/tray_transcribing.png",
         // Light theme uses dark icons
-        (AppTheme::Light, TrayIconState::Idle) => "resources/tray_idle_dark.png",
+        (AppTheme::Light, TrayIconState::Idle | TrayIconState::Error) => {
+            "resources/tray_idle_dark.png"
+        }
         (AppTheme::Light, TrayIconState::Recording) => "resources/tray_recording_dark.png",
         (AppTheme::Light, TrayIconState::Transcribing) => "resources/tray_transcribing_dark.png",
         // Colored theme uses pink icons (for Linux)
-        (AppTheme::Colored, TrayIconState::Idle) => "resources/handy.png",
+        (AppTheme::Colored, TrayIconState::Idle | TrayIconState::Error) => "resources/handy.png",
         (AppTheme::Colored, TrayIconState::Recording) => "resources/recording.png",
         (AppTheme::Colored, TrayIconState::Transcribing) => "resources/transcribing.png",
     }
 }
 
-pub fn change_tray_icon(app: &AppHandle, icon: TrayIconState) {
-    let tray = app.state::<TrayIcon>();
+/// The state's icon from the user's icon set, if they configured one and it
+/// has the file. Light themes prefer a `<state>_dark.png` variant.
+fn custom_icon(dir: &Path, theme: &AppTheme, state: &TrayIconState) -> Option<Image<'static>> {
+    let stem = state.file_stem();
+    let mut candidates = vec![dir.join(format!("{}.png", stem))];
+    if *theme == AppTheme::Light {
+        candidates.insert(0, dir.join(format!("{}_dark.png", stem)));
+    }
+
+    let path = candidates.into_iter().find(|path| path.is_file())?;
+    match Image::from_path(&path) {
+        Ok(image) => Some(image),
+        Err(e) => {
+            warn!("Failed to load tray icon {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Draws a filled dot into the bottom-right corner of an RGBA image, with a
+/// soft edge so it doesn't look jagged at menu bar sizes.
+fn draw_dot(rgba: &mut [u8], width: u32, height: u32, color: [u8; 3]) {
+    let radius = width.min(height) as f32 * 0.22;
+    let center_x = width as f32 - radius - 1.0;
+    let center_y = height as f32 - radius - 1.0;
+
+    for y in 0..height {
+        for x in 0..width {
+            let dx = x as f32 + 0.5 - center_x;
+            let dy = y as f32 + 0.5 - center_y;
+            let coverage = (radius + 0.5 - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+            if coverage == 0.0 {
+                continue;
+            }
+
+            let i = ((y * width + x) * 4) as usize;
+            for (channel, value) in color.iter().enumerate() {
+                let blended =
+                    rgba[i + channel] as f32 * (1.0 - coverage) + *value as f32 * coverage;
+                rgba[i + channel] = blended.round() as u8;
+            }
+            rgba[i + 3] = rgba[i + 3].max((coverage * 255.0).round() as u8);
+        }
+    }
+}
+
+/// The icon for `state`, and whether it can be shown as a template image.
+fn load_icon(app: &AppHandle, state: &TrayIconState, with_dot: bool) -> (Image<'static>, bool) {
+    let settings = settings::get_settings(app);
     let theme = get_current_theme(app);
 
-    let icon_path = get_icon_path(theme, icon.clone());
+    let custom = settings
+        .tray_icon_dir
+        .as_deref()
+        .and_then(|dir| custom_icon(Path::new(dir), &theme, state));
+    let has_custom = custom.is_some();
+    // The bundled set has no error artwork, so the dot is what shows it
+    let with_dot = with_dot || (*state == TrayIconState::Error && !has_custom);
 
-    let _ = tray.set_icon(Some(
+    let icon = custom.unwrap_or_else(|| {
         Image::from_path(
             app.path()
-                .resolve(icon_path, tauri::path::BaseDirectory::Resource)
+                .resolve(
+                    get_icon_path(theme, state.clone()),
+                    tauri::path::BaseDirectory::Resource,
+                )
                 .expect("failed to resolve"),
         )
-        .expect("failed to set icon"),
-    ));
+        .expect("failed to set icon")
+    });
+
+    match state.indicator_color().filter(|_| with_dot) {
+        Some(color) => {
+            let (width, height) = (icon.width(), icon.height());
+            let mut rgba = icon.rgba().to_vec();
+            draw_dot(&mut rgba, width, height, color);
+            (Image::new_owned(rgba, width, height), false)
+        }
+        None => (icon, !has_custom),
+    }
+}
+
+fn set_icon(app: &AppHandle, state: &TrayIconState, with_dot: bool) {
+    let (icon, is_template) = load_icon(app, state, with_dot);
+    let tray = app.state::<TrayIcon>();
+    let _ = tray.set_icon(Some(icon));
+    ICON_IS_TEMPLATE.store(is_template, Ordering::Relaxed);
+    let _ = tray.set_icon_as_template(is_template);
+}
+
+/// Shows `state` unless the state changed again since `generation`. The
+/// check and the update both run on the main thread, one update at a time,
+/// so a flash or an earlier change can't overwrite a newer icon.
+fn set_icon_for(app: &AppHandle, generation: u64, state: TrayIconState, with_dot: bool) {
+    let handle = app.clone();
+    let _ = app.run_on_main_thread(move || {
+        if STATE_GENERATION.load(Ordering::Relaxed) == generation {
+            set_icon(&handle, &state, with_dot);
+        }
+    });
+}
+
+pub fn change_tray_icon(app: &AppHandle, icon: TrayIconState) {
+    let generation = {
+        let mut current = CURRENT_STATE.lock().unwrap();
+        *current = icon.clone();
+        STATE_GENERATION.fetch_add(1, Ordering::Relaxed) + 1
+    };
+
+    let indicator = settings::get_settings(app).tray_indicator;
+    set_icon_for(
+        app,
+        generation,
+        icon.clone(),
+        indicator != TrayIndicator::Off,
+    );
 
     // Update menu based on state
     update_tray_menu(app, &icon, None);
+    let _ = app.emit(
+        "tray-state-changed",
+        TrayStateEvent {
+            state: icon.clone(),
+        },
+    );
+
+    if indicator == TrayIndicator::Flash && icon == TrayIconState::Recording {
+        let app = app.clone();
+        thread::spawn(move || {
+            let mut lit = true;
+            loop {
+                thread::sleep(FLASH_INTERVAL);
+                if STATE_GENERATION.load(Ordering::Relaxed) != generation {
+                    return;
+                }
+                lit = !lit;
+                set_icon_for(&app, generation, TrayIconState::Recording, lit);
+            }
+        });
+    }
+
+    if icon == TrayIconState::Error {
+        let app = app.clone();
+        thread::spawn(move || {
+            thread::sleep(ERROR_DISPLAY);
+            if STATE_GENERATION.load(Ordering::Relaxed) == generation {
+                change_tray_icon(&app, TrayIconState::Idle);
+            }
+        });
+    }
+}
+
+/// Redraws the icon for the current state, e.g. after the icon settings changed.
+pub fn refresh_tray_icon(app: &AppHandle) {
+    let state = CURRENT_STATE.lock().unwrap().clone();
+    change_tray_icon(app, state);
 }
 
 pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&str>) {
@@ -134,7 +323,7 @@ pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&
             )
             .expect("failed to create menu")
         }
-        TrayIconState::Idle => Menu::with_items(
+        TrayIconState::Idle | TrayIconState::Error => Menu::with_items(
             app,
             &[
                 &version_i,
@@ -150,5 +339,25 @@ pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&
 
     let tray = app.state::<TrayIcon>();
     let _ = tray.set_menu(Some(menu));
-    let _ = tray.set_icon_as_template(true);
+    let _ = tray.set_icon_as_template(ICON_IS_TEMPLATE.load(Ordering::Relaxed));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_dot_in_the_corner() {
+        let (width, height) = (32, 32);
+        let mut rgba = vec![0u8; (width * height * 4) as usize];
+        draw_dot(&mut rgba, width, height, [255, 0, 0]);
+
+        let pixel = |x: u32, y: u32| {
+            let i = ((y * width + x) * 4) as usize;
+            [rgba[i], rgba[i + 1], rgba[i + 2], rgba[i + 3]]
+        };
+        assert_eq!(pixel(24, 24), [255, 0, 0, 255]);
+        assert_eq!(pixel(4, 4), [0, 0, 0, 0]);
+        assert_eq!(pixel(24, 4), [0, 0, 0, 0]);
+    }
 }
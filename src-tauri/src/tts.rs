@@ -0,0 +1,151 @@
+use crate::accessibility::{self, Announcement};
+use crate::audio_feedback;
+use crate::notifications;
+use crate::settings::get_settings;
+use crate::shortcut;
+use crate::tray::{change_tray_icon, TrayIconState};
+use crate::utils;
+use anyhow::Result;
+use log::{debug, error, info};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Transcript waiting for the user to accept or cancel after it was read back.
+pub type ManagedPendingReadback = Mutex<Option<String>>;
+
+const DEFAULT_PIPER_BINARY: &str = "piper";
+
+/// Runs piper to synthesize `text` into a temporary WAV file.
+fn synthesize(app: &AppHandle, text: &str) -> Result<PathBuf> {
+    let settings = get_settings(app);
+    let voice = settings
+        .tts_voice_path
+        .ok_or_else(|| anyhow::anyhow!("No piper voice model configured"))?;
+    let binary = settings
+        .tts_piper_path
+        .unwrap_or_else(|| DEFAULT_PIPER_BINARY.to_string());
+
+    // The system temp dir is shared between OS users on Linux
+    let output = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| anyhow::anyhow!("Failed to get app cache dir: {}", e))?;
+    std::fs::create_dir_all(&output)?;
+    let output = output.join("readback.wav");
+
+    let mut child = Command::new(&binary)
+        .arg("--model")
+        .arg(&voice)
+        .arg("--output_file")
+        .arg(&output)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to launch piper '{}': {}", binary, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+
+    let result = child.wait_with_output()?;
+    if !result.status.success() {
+        return Err(anyhow::anyhow!(
+            "piper exited with {}: {}",
+            result.status,
+            String::from_utf8_lossy(&result.stderr).trim()
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Speaks `text` on the selected output device, blocking until playback ends.
+pub fn speak_blocking(app: &AppHandle, text: &str) -> Result<()> {
+    let path = synthesize(app, text)?;
+    audio_feedback::play_file_blocking(app, &path)
+        .map_err(|e| anyhow::anyhow!("Failed to play read-back audio: {}", e))?;
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+pub fn has_pending_readback(app: &AppHandle) -> bool {
+    app.state::<ManagedPendingReadback>()
+        .lock()
+        .map(|pending| pending.is_some())
+        .unwrap_or(false)
+}
+
+/// Holds `text` back from pasting and reads it aloud. The user then accepts it
+/// with the accept shortcut or drops it with the cancel shortcut.
+pub fn begin_readback(app: &AppHandle, text: String) {
+    *app.state::<ManagedPendingReadback>().lock().unwrap() = Some(text.clone());
+    shortcut::register_readback_shortcuts(app);
+    utils::hide_recording_overlay(app);
+
+    let app_clone = app.clone();
+    std::thread::spawn(move || {
+        match speak_blocking(&app_clone, &text) {
+            Ok(()) => {
+                // Dynamic shortcuts are disabled on Linux, so there is no way to confirm;
+                // treat the read-back as informational and paste once it finishes.
+                #[cfg(target_os = "linux")]
+                accept_readback(&app_clone);
+            }
+            Err(e) => {
+                error!("Read-back failed, pasting without confirmation: {}", e);
+                accept_readback(&app_clone);
+            }
+        }
+    });
+}
+
+/// Pastes the pending transcript, if any.
+pub fn accept_readback(app: &AppHandle) {
+    let Some(text) = app.state::<ManagedPendingReadback>().lock().unwrap().take() else {
+        return;
+    };
+    shortcut::unregister_readback_shortcuts(app);
+    info!("Read-back accepted");
+
+    let ah = app.clone();
+    app.run_on_main_thread(move || {
+        match utils::paste(text.clone(), ah.clone()) {
+            Ok(()) => {
+                notifications::notify_transcribed(&ah, &text);
+                accessibility::announce(
+                    &ah,
+                    Announcement::Transcribed(accessibility::word_count(&text)),
+                );
+            }
+            Err(e) => {
+                error!("Failed to paste transcription: {}", e);
+                accessibility::announce(&ah, Announcement::PasteFailed);
+                notifications::notify_paste_failed(&ah, text);
+            }
+        }
+        change_tray_icon(&ah, TrayIconState::Idle);
+    })
+    .unwrap_or_else(|e| {
+        error!("Failed to run paste on main thread: {:?}", e);
+        change_tray_icon(app, TrayIconState::Idle);
+    });
+}
+
+/// Drops the pending transcript without pasting it. Returns whether anything was pending.
+pub fn discard_readback(app: &AppHandle) -> bool {
+    let discarded = app
+        .state::<ManagedPendingReadback>()
+        .lock()
+        .unwrap()
+        .take()
+        .is_some();
+    if discarded {
+        shortcut::unregister_readback_shortcuts(app);
+        debug!("Read-back discarded");
+    }
+    discarded
+}
@@ -2,8 +2,9 @@ use crate::managers::audio::AudioRecordingManager;
 use crate::managers::transcription::TranscriptionManager;
 use crate::shortcut;
 use crate::ManagedToggleState;
-use log::{info, warn};
+use log::{error, info, warn};
 use std::sync::Arc;
+use std::time::Duration;
 use tauri::{AppHandle, Manager};
 
 // Re-export all utility modules for easy access
@@ -29,6 +30,14 @@ pub fn cancel_current_operation(app: &AppHandle) {
         warn!("Failed to lock toggle state manager during cancellation");
     }
 
+    // Drop any transcript still waiting on read-back or preview confirmation
+    crate::tts::discard_readback(app);
+    crate::preview::discard_preview(app);
+    crate::recording_guard::clear_queue(app);
+    crate::processing::cancel(app);
+    crate::openai_realtime::cancel(app);
+    crate::countdown::cancel();
+
     // Cancel any ongoing recording
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     audio_manager.cancel_recording();
@@ -36,6 +45,7 @@ pub fn cancel_current_operation(app: &AppHandle) {
     // Update tray icon and hide overlay
     change_tray_icon(app, crate::tray::TrayIconState::Idle);
     hide_recording_overlay(app);
+    crate::accessibility::announce(app, crate::accessibility::Announcement::Cancelled);
 
     // Unload model if immediate unload is enabled
     let tm = app.state::<Arc<TranscriptionManager>>();
@@ -44,6 +54,36 @@ pub fn cancel_current_operation(app: &AppHandle) {
     info!("Operation cancellation completed - returned to idle state");
 }
 
+/// Gives focus time to return to the previous app after the main window hides.
+pub const REFOCUS_DELAY: Duration = Duration::from_millis(300);
+
+pub fn hide_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let _ = app.hide();
+    }
+}
+
+/// Pastes `text` into the app the user was in before they clicked or typed in
+/// one of our windows. The window is hidden first to hand focus back.
+pub fn paste_after_refocus(app: &AppHandle, text: String) {
+    hide_main_window(app);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(REFOCUS_DELAY);
+        let ah = app.clone();
+        app.run_on_main_thread(move || {
+            if let Err(e) = paste(text, ah) {
+                error!("Failed to paste: {}", e);
+            }
+        })
+        .unwrap_or_else(|e| error!("Failed to run paste on main thread: {:?}", e));
+    });
+}
+
 /// Check if using the Wayland display server protocol
 #[cfg(target_os = "linux")]
 pub fn is_wayland() -> bool {
@@ -0,0 +1,328 @@
+//! Typing through a virtual keyboard, for targets that ignore the synthetic
+//! events the other paste methods send: VMs, remote desktop sessions and
+//! games that read the keyboard directly.
+//!
+//! On Linux this is a uinput device, which the system sees as a real USB
+//! keyboard. Key codes are sent for a US layout, so the target should use
+//! one too. Windows has no virtual HID device without a kernel driver, so
+//! hardware scan codes are sent with `SendInput` instead, which is what
+//! remote desktop clients, hypervisors and DirectInput games read. Text that
+//! can't be typed this way, like characters missing from the layout, is left
+//! to the caller to type another way.
+
+use log::debug;
+use std::time::Duration;
+
+/// Pause after each key, as games and remote sessions drop keys that come
+/// in faster than a person could type
+#[cfg_attr(not(any(target_os = "linux", target_os = "windows")), allow(dead_code))]
+const KEY_DELAY: Duration = Duration::from_millis(4);
+
+/// Linux key code for `c` on a US keyboard, and whether it needs Shift
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn us_key(c: char) -> Option<(u16, bool)> {
+    const LETTERS: [u16; 26] = [
+        30, 48, 46, 32, 18, 33, 34, 35, 23, 36, 37, 38, 50, 49, 24, 25, 16, 19, 31, 20, 22, 47, 17,
+        45, 21, 44,
+    ];
+    const DIGITS: [u16; 10] = [11, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    const SHIFTED_DIGITS: &str = ")!@#$%^&*(";
+    const SYMBOLS: &[(char, char, u16)] = &[
+        ('-', '_', 12),
+        ('=', '+', 13),
+        ('[', '{', 26),
+        (']', '}', 27),
+        (';', ':', 39),
+        ('\'', '"', 40),
+        ('`', '~', 41),
+        ('\\', '|', 43),
+        (',', '<', 51),
+        ('.', '>', 52),
+        ('/', '?', 53),
+    ];
+
+    match c {
+        'a'..='z' => Some((LETTERS[c as usize - 'a' as usize], false)),
+        'A'..='Z' => Some((LETTERS[c as usize - 'A' as usize], true)),
+        '0'..='9' => Some((DIGITS[c as usize - '0' as usize], false)),
+        ' ' => Some((57, false)),
+        '\n' => Some((28, false)),
+        '\t' => Some((15, false)),
+        _ => SHIFTED_DIGITS
+            .find(c)
+            .map(|digit| (DIGITS[digit], true))
+            .or_else(|| {
+                SYMBOLS.iter().find_map(|(plain, shifted, code)| {
+                    (c == *plain)
+                        .then_some((*code, false))
+                        .or((c == *shifted).then_some((*code, true)))
+                })
+            }),
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod uinput {
+    use super::{us_key, KEY_DELAY};
+    use log::info;
+    use std::fs::{File, OpenOptions};
+    use std::io::Write;
+    use std::os::fd::AsRawFd;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    const EV_SYN: u16 = 0;
+    const EV_KEY: u16 = 1;
+    const SYN_REPORT: u16 = 0;
+    const KEY_LEFTSHIFT: u16 = 42;
+    const BUS_USB: u16 = 0x03;
+    /// Highest key code the device declares, covering the main block
+    const MAX_KEY: u16 = 127;
+
+    const UI_SET_EVBIT: u64 = 0x4004_5564;
+    const UI_SET_KEYBIT: u64 = 0x4004_5565;
+    const UI_DEV_SETUP: u64 = 0x405c_5503;
+    const UI_DEV_CREATE: u64 = 0x5501;
+
+    /// Time for the desktop to pick up a new device before it's typed on
+    const SETTLE: Duration = Duration::from_millis(300);
+
+    #[repr(C)]
+    struct InputId {
+        bustype: u16,
+        vendor: u16,
+        product: u16,
+        version: u16,
+    }
+
+    #[repr(C)]
+    struct UinputSetup {
+        id: InputId,
+        name: [u8; 80],
+        ff_effects_max: u32,
+    }
+
+    #[repr(C)]
+    struct InputEvent {
+        time: libc::timeval,
+        kind: u16,
+        code: u16,
+        value: i32,
+    }
+
+    /// The device, created on first use and kept for the rest of the run
+    static DEVICE: Mutex<Option<File>> = Mutex::new(None);
+
+    fn ioctl(file: &File, request: u64, arg: libc::c_ulong) -> Result<(), String> {
+        // SAFETY: the requests used here take an int or a pointer to a
+        // `UinputSetup` that outlives the call
+        let result = unsafe { libc::ioctl(file.as_raw_fd(), request as _, arg) };
+        if result < 0 {
+            Err(format!(
+                "uinput ioctl failed: {}",
+                std::io::Error::last_os_error()
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn create() -> Result<File, String> {
+        let file = OpenOptions::new()
+            .write(true)
+            .open("/dev/uinput")
+            .map_err(|e| {
+                format!(
+                    "Can't open /dev/uinput ({}). Add yourself to the group that owns it, often \"input\", or load the uinput module.",
+                    e
+                )
+            })?;
+        ioctl(&file, UI_SET_EVBIT, EV_KEY as _)?;
+        for code in 1..=MAX_KEY {
+            ioctl(&file, UI_SET_KEYBIT, code as _)?;
+        }
+        let mut name = [0u8; 80];
+        let label = b"Handy virtual keyboard";
+        name[..label.len()].copy_from_slice(label);
+        let setup = UinputSetup {
+            id: InputId {
+                bustype: BUS_USB,
+                vendor: 0x1209,
+                product: 0x4841,
+                version: 1,
+            },
+            name,
+            ff_effects_max: 0,
+        };
+        ioctl(&file, UI_DEV_SETUP, &setup as *const _ as libc::c_ulong)?;
+        ioctl(&file, UI_DEV_CREATE, 0)?;
+        info!("Created uinput virtual keyboard");
+        std::thread::sleep(SETTLE);
+        Ok(file)
+    }
+
+    fn emit(file: &mut File, kind: u16, code: u16, value: i32) -> Result<(), String> {
+        let event = InputEvent {
+            time: libc::timeval {
+                tv_sec: 0,
+                tv_usec: 0,
+            },
+            kind,
+            code,
+            value,
+        };
+        // SAFETY: `InputEvent` is plain data laid out like `struct input_event`
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                &event as *const InputEvent as *const u8,
+                std::mem::size_of::<InputEvent>(),
+            )
+        };
+        file.write_all(bytes)
+            .map_err(|e| format!("Failed to write to uinput: {}", e))
+    }
+
+    fn key(file: &mut File, code: u16, down: bool) -> Result<(), String> {
+        emit(file, EV_KEY, code, down as i32)?;
+        emit(file, EV_SYN, SYN_REPORT, 0)
+    }
+
+    pub fn type_text(text: &str) -> Result<(), String> {
+        let keys: Vec<(u16, bool)> = text
+            .chars()
+            .map(|c| us_key(c).ok_or_else(|| format!("Can't type {:?} on the virtual keyboard", c)))
+            .collect::<Result<_, _>>()?;
+
+        let mut device = DEVICE.lock().unwrap();
+        if device.is_none() {
+            *device = Some(create()?);
+        }
+        let file = device.as_mut().unwrap();
+        for (code, shift) in keys {
+            if shift {
+                key(file, KEY_LEFTSHIFT, true)?;
+            }
+            key(file, code, true)?;
+            key(file, code, false)?;
+            if shift {
+                key(file, KEY_LEFTSHIFT, false)?;
+            }
+            std::thread::sleep(KEY_DELAY);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod scan_codes {
+    use super::KEY_DELAY;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        MapVirtualKeyW, SendInput, VkKeyScanW, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT,
+        KEYEVENTF_KEYUP, KEYEVENTF_SCANCODE, MAPVK_VK_TO_VSC, VIRTUAL_KEY,
+    };
+
+    const SCAN_LEFTSHIFT: u16 = 0x2A;
+    const SCAN_ENTER: u16 = 0x1C;
+
+    /// Scan code for `c` on the current layout, and whether it needs Shift.
+    /// Characters that need Ctrl or AltGr aren't typed this way.
+    fn scan_code(c: char) -> Option<(u16, bool)> {
+        if c == '\n' {
+            return Some((SCAN_ENTER, false));
+        }
+        let mut units = [0u16; 2];
+        if c.encode_utf16(&mut units).len() != 1 {
+            return None;
+        }
+        // SAFETY: plain Win32 calls without pointers
+        unsafe {
+            let scan = VkKeyScanW(units[0]);
+            let modifiers = (scan >> 8) & 0xff;
+            if scan == -1 || modifiers & !1 != 0 {
+                return None;
+            }
+            let code = MapVirtualKeyW((scan & 0xff) as u32, MAPVK_VK_TO_VSC) as u16;
+            (code != 0).then_some((code, modifiers & 1 == 1))
+        }
+    }
+
+    fn send(code: u16, up: bool) -> Result<(), String> {
+        let flags = if up {
+            KEYEVENTF_SCANCODE | KEYEVENTF_KEYUP
+        } else {
+            KEYEVENTF_SCANCODE
+        };
+        let input = INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: VIRTUAL_KEY(0),
+                    wScan: code,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        };
+        // SAFETY: one fully initialized INPUT of the size passed
+        let sent = unsafe { SendInput(&[input], std::mem::size_of::<INPUT>() as i32) };
+        if sent == 1 {
+            Ok(())
+        } else {
+            Err(format!(
+                "SendInput failed: {}",
+                std::io::Error::last_os_error()
+            ))
+        }
+    }
+
+    pub fn type_text(text: &str) -> Result<(), String> {
+        let keys: Vec<(u16, bool)> = text
+            .chars()
+            .map(|c| scan_code(c).ok_or_else(|| format!("Can't type {:?} with scan codes", c)))
+            .collect::<Result<_, _>>()?;
+        for (code, shift) in keys {
+            if shift {
+                send(SCAN_LEFTSHIFT, false)?;
+            }
+            send(code, false)?;
+            send(code, true)?;
+            if shift {
+                send(SCAN_LEFTSHIFT, true)?;
+            }
+            std::thread::sleep(KEY_DELAY);
+        }
+        Ok(())
+    }
+}
+
+/// Types `text` on the virtual keyboard. Nothing is typed if any of it can't
+/// be, so the caller can type all of it another way.
+pub fn type_text(text: &str) -> Result<(), String> {
+    debug!(
+        "Typing {} chars on the virtual keyboard",
+        text.chars().count()
+    );
+    #[cfg(target_os = "linux")]
+    return uinput::type_text(text);
+    #[cfg(target_os = "windows")]
+    return scan_codes::type_text(text);
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    Err("The virtual keyboard isn't available on this platform".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_us_layout_keys() {
+        assert_eq!(us_key('a'), Some((30, false)));
+        assert_eq!(us_key('Q'), Some((16, true)));
+        assert_eq!(us_key('0'), Some((11, false)));
+        assert_eq!(us_key('!'), Some((2, true)));
+        assert_eq!(us_key('?'), Some((53, true)));
+        assert_eq!(us_key('\''), Some((40, false)));
+        assert_eq!(us_key('é'), None);
+    }
+}
@@ -0,0 +1,373 @@
+//! Spoken editing commands. With them on, "new line", "new paragraph",
+//! "delete that" and "all caps on/off" in a dictation edit the text instead
+//! of being typed.
+//!
+//! "delete that" takes back the last thing said before it in the same
+//! dictation, or else the last text Handy typed, with Backspace. The texts
+//! typed most recently are kept for that, along with the app they went into,
+//! so nothing is deleted once the focus moved elsewhere. All caps stays on
+//! across dictations until it's turned off.
+//!
+//! Dictating just "correct X to Y" shortly after Handy typed something fixes
+//! the last X in that text instead: everything from it on is deleted with
+//! Backspace and typed again with Y in its place.
+
+use crate::clipboard;
+use crate::helpers::active_app::active_app;
+use crate::input::{self, EnigoState};
+use crate::settings::{get_settings, AppSettings};
+use enigo::Key;
+use log::{debug, error, info};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// How many typed texts "delete that" can take back, one at a time
+const UNDO_DEPTH: usize = 20;
+
+/// Punctuation the engine may have added to a spoken command
+const TRIM: &[char] = &[',', '.', '!', '?', ';', ':'];
+
+/// How long after typing something "correct X to Y" can still fix it
+const CORRECTION_WINDOW: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Command {
+    NewLine,
+    NewParagraph,
+    DeleteThat,
+    AllCapsOn,
+    AllCapsOff,
+}
+
+const COMMANDS: &[(&[&str], Command)] = &[
+    (&["new", "line"], Command::NewLine),
+    (&["new", "paragraph"], Command::NewParagraph),
+    (&["delete", "that"], Command::DeleteThat),
+    (&["scratch", "that"], Command::DeleteThat),
+    (&["all", "caps", "on"], Command::AllCapsOn),
+    (&["all", "caps", "off"], Command::AllCapsOff),
+];
+
+struct Typed {
+    app: Option<String>,
+    text: String,
+    at: Instant,
+}
+
+#[derive(Default)]
+pub struct VoiceCommandState {
+    /// Texts typed so far, the latest last
+    typed: Vec<Typed>,
+    all_caps: bool,
+}
+
+pub type ManagedVoiceCommands = Mutex<VoiceCommandState>;
+
+fn find_command(words: &[&str]) -> Option<(usize, Command)> {
+    COMMANDS.iter().find_map(|(phrase, command)| {
+        let matches = phrase.len() <= words.len()
+            && phrase
+                .iter()
+                .zip(words)
+                .all(|(spoken, word)| word.trim_matches(TRIM).eq_ignore_ascii_case(spoken));
+        matches.then_some((phrase.len(), *command))
+    })
+}
+
+/// Carries out the commands in `text`, returning the text to type and how
+/// many earlier typed texts "delete that" reaches back to.
+fn interpret(text: &str, all_caps: &mut bool) -> (usize, String) {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut out = String::with_capacity(text.len());
+    // Where each run of words and each line break starts in `out`, for
+    // "delete that" to take them back in turn
+    let mut edits: Vec<usize> = Vec::new();
+    let mut in_run = false;
+    let mut undo = 0;
+    let mut i = 0;
+    while i < words.len() {
+        let Some((len, command)) = find_command(&words[i..]) else {
+            if !in_run {
+                edits.push(out.len());
+                in_run = true;
+            }
+            if !out.is_empty() && !out.ends_with('\n') {
+                out.push(' ');
+            }
+            if *all_caps {
+                out.push_str(&words[i].to_uppercase());
+            } else {
+                out.push_str(words[i]);
+            }
+            i += 1;
+            continue;
+        };
+
+        match command {
+            Command::NewLine | Command::NewParagraph => {
+                out.truncate(out.trim_end().len());
+                edits.push(out.len());
+                out.push_str(if command == Command::NewLine {
+                    "\n"
+                } else {
+                    "\n\n"
+                });
+            }
+            Command::DeleteThat => match edits.pop() {
+                Some(start) => out.truncate(start),
+                None => undo += 1,
+            },
+            Command::AllCapsOn => *all_caps = true,
+            Command::AllCapsOff => *all_caps = false,
+        }
+        in_run = false;
+        i += len;
+    }
+    (undo, out)
+}
+
+/// The X and Y of a dictation that is only "correct X to Y"
+fn parse_correction(text: &str) -> Option<(String, String)> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (first, rest) = words.split_first()?;
+    if !first.trim_matches(TRIM).eq_ignore_ascii_case("correct") {
+        return None;
+    }
+    // The first "to" after at least one word, so "correct to to two" works
+    let to = rest
+        .iter()
+        .skip(1)
+        .position(|word| word.trim_matches(TRIM).eq_ignore_ascii_case("to"))?
+        + 1;
+    let from = rest[..to].join(" ");
+    let replacement = rest[to + 1..].join(" ");
+    let from = from.trim_matches(TRIM);
+    let replacement = replacement.trim_matches(TRIM);
+    if from.is_empty() || replacement.is_empty() {
+        return None;
+    }
+    Some((from.to_string(), replacement.to_string()))
+}
+
+fn same_letter(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// Replaces the last whole-word `from` in `typed`, ignoring case. Returns how
+/// many chars at the end of `typed` to delete and what to type in their
+/// place. The replacement is capitalized when the word it replaces was.
+fn plan_correction(typed: &str, from: &str, to: &str) -> Option<(usize, String)> {
+    let typed: Vec<char> = typed.chars().collect();
+    let from: Vec<char> = from.chars().collect();
+    if from.is_empty() || from.len() > typed.len() {
+        return None;
+    }
+    let is_word = |i: usize| typed.get(i).is_some_and(|c| c.is_alphanumeric());
+    let start = (0..=typed.len() - from.len()).rev().find(|&start| {
+        let end = start + from.len();
+        typed[start..end]
+            .iter()
+            .zip(&from)
+            .all(|(&a, &b)| same_letter(a, b))
+            && (start == 0 || !is_word(start - 1))
+            && !is_word(end)
+    })?;
+
+    let mut retype = String::new();
+    let mut replacement = to.chars();
+    if typed[start].is_uppercase() {
+        if let Some(first) = replacement.next() {
+            retype.extend(first.to_uppercase());
+        }
+    }
+    retype.extend(replacement);
+    retype.extend(&typed[start + from.len()..]);
+    Some((typed.len() - start, retype))
+}
+
+/// Carries out "correct X to Y" on the last text typed into the focused app,
+/// if it's recent enough and has an X. Returns whether it did.
+fn correct(app: &AppHandle, state: &mut VoiceCommandState, from: &str, to: &str) -> bool {
+    let Some(typed) = state.typed.last_mut() else {
+        return false;
+    };
+    if typed.at.elapsed() > CORRECTION_WINDOW || typed.app != active_app() {
+        return false;
+    }
+    let Some((chars, retype)) = plan_correction(&typed.text, from, to) else {
+        info!("Nothing to correct in the last typed text");
+        return false;
+    };
+
+    debug!("Correcting the last {} typed chars", chars);
+    let kept = typed.text.chars().count() - chars;
+    typed.text = typed
+        .text
+        .chars()
+        .take(kept)
+        .chain(retype.chars())
+        .collect();
+    let app_clone = app.clone();
+    let _ = app.run_on_main_thread(move || {
+        let Some(enigo_state) = app_clone.try_state::<EnigoState>() else {
+            return;
+        };
+        let Ok(mut enigo) = enigo_state.0.lock() else {
+            return;
+        };
+        for _ in 0..chars {
+            if let Err(e) = input::click_key(&mut enigo, Key::Backspace) {
+                error!("Failed to delete typed text: {}", e);
+                return;
+            }
+        }
+        let settings = get_settings(&app_clone);
+        let method = clipboard::effective_paste_method(&settings, active_app().as_deref());
+        if let Err(e) = clipboard::inject(&mut enigo, &retype, &app_clone, method) {
+            error!("Failed to type the correction: {}", e);
+        }
+    });
+    true
+}
+
+/// Runs the commands in `text` and returns what's left to type. Earlier text
+/// "delete that" reaches is deleted from the focused app right away.
+pub fn run(app: &AppHandle, settings: &AppSettings, text: &str) -> String {
+    if !settings.voice_commands {
+        return text.to_string();
+    }
+    let state = app.state::<ManagedVoiceCommands>();
+    let mut state = state.lock().unwrap();
+    if let Some((from, to)) = parse_correction(text) {
+        if correct(app, &mut state, &from, &to) {
+            return String::new();
+        }
+    }
+    let (undo, out) = interpret(text, &mut state.all_caps);
+
+    let target = active_app();
+    let mut chars = 0;
+    for _ in 0..undo {
+        match state.typed.last() {
+            Some(typed) if typed.app == target => {
+                chars += typed.text.chars().count();
+                state.typed.pop();
+            }
+            Some(_) => {
+                info!("Not deleting text typed into another app");
+                break;
+            }
+            None => break,
+        }
+    }
+    if chars > 0 {
+        debug!("Deleting the last {} typed chars", chars);
+        delete(app, chars);
+    }
+    out
+}
+
+fn delete(app: &AppHandle, chars: usize) {
+    let app_clone = app.clone();
+    let _ = app.run_on_main_thread(move || {
+        let Some(enigo_state) = app_clone.try_state::<EnigoState>() else {
+            return;
+        };
+        let Ok(mut enigo) = enigo_state.0.lock() else {
+            return;
+        };
+        for _ in 0..chars {
+            if let Err(e) = input::click_key(&mut enigo, Key::Backspace) {
+                error!("Failed to delete typed text: {}", e);
+                break;
+            }
+        }
+    });
+}
+
+/// Notes `text` as typed into the focused app, for "delete that".
+pub fn record(app: &AppHandle, text: &str) {
+    let Some(state) = app.try_state::<ManagedVoiceCommands>() else {
+        return;
+    };
+    let mut state = state.lock().unwrap();
+    state.typed.push(Typed {
+        app: active_app(),
+        text: text.to_string(),
+        at: Instant::now(),
+    });
+    if state.typed.len() > UNDO_DEPTH {
+        state.typed.remove(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_breaks_and_takes_back_what_was_said() {
+        let mut caps = false;
+        assert_eq!(
+            interpret("Dear Sam, new line. Thanks for the notes.", &mut caps),
+            (0, "Dear Sam,\nThanks for the notes.".to_string())
+        );
+        assert_eq!(
+            interpret("Hello there. Delete that. Hi new paragraph", &mut caps),
+            (0, "Hi\n\n".to_string())
+        );
+        assert_eq!(
+            interpret("delete that delete that", &mut caps),
+            (2, String::new())
+        );
+        assert_eq!(
+            interpret("one new line delete that two", &mut caps),
+            (0, "one two".to_string())
+        );
+    }
+
+    #[test]
+    fn corrections_replace_the_last_matching_word() {
+        assert_eq!(
+            parse_correction("Correct their to there."),
+            Some(("their".to_string(), "there".to_string()))
+        );
+        assert_eq!(
+            parse_correction("correct to to two"),
+            Some(("to".to_string(), "two".to_string()))
+        );
+        assert_eq!(parse_correction("correct me if I'm wrong"), None);
+        assert_eq!(parse_correction("please correct this to that"), None);
+
+        assert_eq!(
+            plan_correction("I saw their house and their car. ", "their", "there"),
+            Some((11, "there car. ".to_string()))
+        );
+        // Capitalized like the word it replaces, and only whole words match
+        assert_eq!(
+            plan_correction("Their going", "their", "they're"),
+            Some((11, "They're going".to_string()))
+        );
+        assert_eq!(plan_correction("theirs", "their", "there"), None);
+        assert_eq!(
+            plan_correction("meet at Café Ole", "café ole", "Cafe Olé"),
+            Some((8, "Cafe Olé".to_string()))
+        );
+    }
+
+    #[test]
+    fn all_caps_carries_over_until_turned_off() {
+        let mut caps = false;
+        assert_eq!(
+            interpret("this is all caps on important", &mut caps),
+            (0, "this is IMPORTANT".to_string())
+        );
+        assert!(caps);
+        assert_eq!(
+            interpret("still loud all caps off. quiet", &mut caps),
+            (0, "STILL LOUD quiet".to_string())
+        );
+        assert!(!caps);
+    }
+}
@@ -0,0 +1,271 @@
+//! Starting dictation by saying a wake word instead of pressing a shortcut.
+//!
+//! With it on, the microphone stays open like the always-on mode, and every
+//! frame heard between recordings goes to a `WakeWordDetector` trained on
+//! recordings of the user saying each phrase. When it hears one, that
+//! phrase's binding starts, so "Hey Handy" can dictate while another phrase
+//! translates. The recording begins with the pre-roll so words said
+//! straight after the phrase aren't lost, and a long silence stops it again.
+//! The recordings are WAV files in a folder per phrase in the app data
+//! folder.
+
+use crate::cli;
+use crate::managers::audio::AudioRecordingManager;
+use crate::settings::{get_settings, WakeWord, DICTATION_BINDING_PREFIX};
+use handy_transcription::wake::trim_to_speech;
+use handy_transcription::{read_wav_file, save_wav_file, WakeWordDetector};
+use log::{info, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Folder in the app data directory with a folder of recordings per phrase
+const SAMPLES_DIR: &str = "wake_word";
+/// More recordings barely help and make each check slower
+pub const MAX_SAMPLES: usize = 5;
+/// Recording binding a sample is recorded under, so no shortcut can stop it
+const SAMPLE_BINDING_ID: &str = "wake_word_sample";
+/// Input kept from before a wake word recording starts. The detector waits
+/// for a short pause after the phrase, anything said in it is in here.
+pub const PRE_ROLL: Duration = Duration::from_millis(600);
+/// Silence that ends a recording the wake word started
+const STOP_AFTER_SILENCE: Duration = Duration::from_secs(3);
+
+/// The detector with the binding each of its phrases starts, in order
+pub type ManagedWakeWord = Mutex<Option<(WakeWordDetector, Vec<String>)>>;
+
+/// Whether a wake word can start binding `binding_id`, which it can for
+/// the ones that dictate
+pub fn can_start(binding_id: &str) -> bool {
+    matches!(binding_id, "transcribe" | "translate")
+        || binding_id.starts_with(DICTATION_BINDING_PREFIX)
+}
+
+fn samples_root(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(SAMPLES_DIR))
+        .map_err(|e| format!("Failed to find the app data folder: {}", e))
+}
+
+/// Folder the recordings of wake word `id` are kept in. Ids come from the
+/// settings, so they're safe as folder names.
+fn samples_dir(app: &AppHandle, id: &str) -> Result<PathBuf, String> {
+    samples_root(app).map(|dir| dir.join(id))
+}
+
+fn wav_paths(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wav"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn sample_paths(app: &AppHandle, id: &str) -> Vec<PathBuf> {
+    samples_dir(app, id)
+        .map(|dir| wav_paths(&dir))
+        .unwrap_or_default()
+}
+
+/// How many recordings of wake word `id` there are
+pub fn sample_count(app: &AppHandle, id: &str) -> usize {
+    sample_paths(app, id).len()
+}
+
+/// The wake word `id` from the settings
+fn wake_word(app: &AppHandle, id: &str) -> Result<WakeWord, String> {
+    get_settings(app)
+        .wake_words
+        .into_iter()
+        .find(|wake_word| wake_word.id == id)
+        .ok_or_else(|| format!("Wake word with id '{}' not found", id))
+}
+
+/// Moves recordings from before there could be several wake words, which
+/// sat straight in the samples folder, into the first wake word's folder
+fn move_old_samples(app: &AppHandle, wake_words: &[WakeWord]) {
+    let (Ok(root), Some(first)) = (samples_root(app), wake_words.first()) else {
+        return;
+    };
+    let old = wav_paths(&root);
+    if old.is_empty() {
+        return;
+    }
+    let dir = root.join(&first.id);
+    if let Err(e) = fs::create_dir_all(&dir) {
+        warn!("Failed to create {}: {}", dir.display(), e);
+        return;
+    }
+    for path in old {
+        if let Some(name) = path.file_name() {
+            if let Err(e) = fs::rename(&path, dir.join(name)) {
+                warn!("Failed to move {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Rebuilds the detector from the settings and recordings, or removes it
+/// when the wake word is off
+pub fn reload(app: &AppHandle) {
+    let settings = get_settings(app);
+    move_old_samples(app, &settings.wake_words);
+    let detector = if settings.wake_word_enabled {
+        let recordings: Vec<Vec<Vec<f32>>> = settings
+            .wake_words
+            .iter()
+            .map(|wake_word| {
+                sample_paths(app, &wake_word.id)
+                    .into_iter()
+                    .filter_map(|path| match read_wav_file(&path) {
+                        Ok(samples) => Some(samples),
+                        Err(e) => {
+                            warn!("Skipping wake word sample {}: {}", path.display(), e);
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let phrases: Vec<(&[Vec<f32>], f32)> = recordings
+            .iter()
+            .zip(&settings.wake_words)
+            .map(|(recordings, wake_word)| (recordings.as_slice(), wake_word.sensitivity))
+            .collect();
+        let detector = WakeWordDetector::new(&phrases);
+        if detector.is_none() {
+            warn!("The wake word is on but has no recordings to listen for");
+        }
+        detector.map(|detector| {
+            let bindings = settings
+                .wake_words
+                .iter()
+                .map(|wake_word| wake_word.binding_id.clone())
+                .collect();
+            (detector, bindings)
+        })
+    } else {
+        None
+    };
+    *app.state::<ManagedWakeWord>().lock().unwrap() = detector;
+}
+
+/// Feeds a frame heard between recordings to the detector. Called on the
+/// audio thread, so anything slower happens on another one.
+pub fn listen(app: &AppHandle, frame: &[f32]) {
+    let state = app.state::<ManagedWakeWord>();
+    // A reload is in progress, skipping a frame is better than waiting
+    let Ok(mut listening) = state.try_lock() else {
+        return;
+    };
+    let Some((detector, bindings)) = listening.as_mut() else {
+        return;
+    };
+    let Some(binding_id) = detector
+        .push(frame)
+        .and_then(|phrase| bindings.get(phrase).cloned())
+    else {
+        return;
+    };
+
+    info!("Heard the wake word for {}", binding_id);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let rm = app.state::<Arc<AudioRecordingManager>>();
+        if rm.is_recording() {
+            return;
+        }
+        rm.arm_pre_roll();
+        cli::set_recording(&app, &binding_id, true, "wake word");
+    });
+}
+
+/// Stops a recording the wake word started once it's gone quiet for long
+/// enough. Called on every recording status tick.
+pub fn check_silence(app: &AppHandle) {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    if !rm.started_by_wake_word() {
+        return;
+    }
+    if rm
+        .silence()
+        .is_some_and(|silence| silence >= STOP_AFTER_SILENCE)
+    {
+        let Some(binding_id) = rm.active_binding() else {
+            return;
+        };
+        info!(
+            "No speech for {}s, stopping the wake word recording",
+            STOP_AFTER_SILENCE.as_secs()
+        );
+        cli::set_recording(app, &binding_id, false, "wake word");
+    }
+}
+
+/// Starts recording the user saying wake word `id`
+pub fn start_sample(app: &AppHandle, id: &str) -> Result<(), String> {
+    wake_word(app, id)?;
+    if sample_count(app, id) >= MAX_SAMPLES {
+        return Err(format!(
+            "There are already {} recordings, clear them to start over",
+            MAX_SAMPLES
+        ));
+    }
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    if !rm.try_start_recording(SAMPLE_BINDING_ID) {
+        return Err("Couldn't start recording, is another one running?".to_string());
+    }
+    // The VAD would cut the quiet start and end trimming relies on
+    rm.set_raw_capture(true);
+    Ok(())
+}
+
+/// Stops the sample recording of wake word `id` and keeps it if the phrase
+/// was heard in it. Returns how many recordings of it there are now.
+pub async fn finish_sample(app: &AppHandle, id: &str) -> Result<usize, String> {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    let samples = rm.stop_recording(SAMPLE_BINDING_ID);
+    rm.set_raw_capture(false);
+    let samples = samples.ok_or("The wake word wasn't being recorded")?;
+    let speech = trim_to_speech(&samples).ok_or("Nothing was heard in the recording")?;
+
+    wake_word(app, id)?;
+    let dir = samples_dir(app, id)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(format!("{}.wav", chrono::Utc::now().timestamp_millis()));
+    save_wav_file(&path, speech)
+        .await
+        .map_err(|e| format!("Failed to save the recording: {}", e))?;
+    reload(app);
+    Ok(sample_count(app, id))
+}
+
+/// Forgets the recordings of wake word `id`, so it can be recorded again
+pub fn clear_samples(app: &AppHandle, id: &str) -> Result<(), String> {
+    wake_word(app, id)?;
+    for path in sample_paths(app, id) {
+        fs::remove_file(&path)
+            .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+    }
+    reload(app);
+    Ok(())
+}
+
+/// Removes the folder of recordings of wake word `id`, before the wake
+/// word itself is removed
+pub fn remove_samples(app: &AppHandle, id: &str) -> Result<(), String> {
+    wake_word(app, id)?;
+    let dir = samples_dir(app, id)?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir)
+            .map_err(|e| format!("Failed to remove {}: {}", dir.display(), e))?;
+    }
+    Ok(())
+}
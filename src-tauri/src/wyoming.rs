@@ -0,0 +1,75 @@
+//! Transcription on a Wyoming server, through the client in
+//! `handy_transcription::wyoming`, and discovery of servers on the LAN, which
+//! advertise themselves over zeroconf as `_wyoming._tcp`.
+
+use crate::settings::AppSettings;
+use anyhow::Result;
+use log::info;
+use serde::Serialize;
+use specta::Type;
+use std::time::{Duration, Instant};
+
+pub use handy_transcription::wyoming::{describe, timed_segments, transcribe};
+
+const SERVICE_TYPE: &str = "_wyoming._tcp.local.";
+
+/// The Wyoming language for the selected language, or `None` to let the
+/// server detect it.
+pub fn language(settings: &AppSettings) -> Option<String> {
+    match settings.selected_language.as_str() {
+        "auto" => None,
+        other => Some(handy_transcription::wyoming::language(other)),
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct WyomingServer {
+    pub name: String,
+    /// `host:port` to connect to
+    pub address: String,
+}
+
+/// Browses zeroconf for Wyoming servers for `timeout`.
+pub fn discover(timeout: Duration) -> Result<Vec<WyomingServer>> {
+    use mdns_sd::{ServiceDaemon, ServiceEvent};
+
+    let daemon = ServiceDaemon::new()?;
+    let receiver = daemon.browse(SERVICE_TYPE)?;
+    let deadline = Instant::now() + timeout;
+    let mut servers: Vec<WyomingServer> = Vec::new();
+
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        let Ok(event) = receiver.recv_timeout(remaining) else {
+            break;
+        };
+        let ServiceEvent::ServiceResolved(info) = event else {
+            continue;
+        };
+        let Some(ip) = info
+            .get_addresses()
+            .iter()
+            .min_by_key(|ip| ip.is_ipv6())
+            .copied()
+        else {
+            continue;
+        };
+        let address = if ip.is_ipv6() {
+            format!("[{}]:{}", ip, info.get_port())
+        } else {
+            format!("{}:{}", ip, info.get_port())
+        };
+        if servers.iter().any(|s| s.address == address) {
+            continue;
+        }
+        let name = info
+            .get_fullname()
+            .trim_end_matches(SERVICE_TYPE)
+            .trim_end_matches('.')
+            .to_string();
+        info!("Discovered Wyoming server '{}' at {}", name, address);
+        servers.push(WyomingServer { name, address });
+    }
+
+    let _ = daemon.shutdown();
+    Ok(servers)
+}
@@ -3,8 +3,10 @@ import { Toaster } from "sonner";
 import "./App.css";
 import AccessibilityPermissions from "./components/AccessibilityPermissions";
 import Footer from "./components/footer";
+import { LiveAnnouncer } from "./components/shared";
 import Onboarding, { AccessibilityOnboarding } from "./components/onboarding";
 import { Sidebar, SidebarSection, SECTIONS_CONFIG } from "./components/Sidebar";
+import { useActionNotifications } from "./hooks/useActionNotifications";
 import { useSettings } from "./hooks/useSettings";
 import { useSettingsStore } from "./stores/settingsStore";
 import { commands } from "@/bindings";
@@ -31,6 +33,7 @@ function App() {
     (state) => state.refreshOutputDevices,
   );
   const hasCompletedPostOnboardingInit = useRef(false);
+  useActionNotifications();
 
   useEffect(() => {
     checkOnboardingStatus();
@@ -122,9 +125,13 @@ function App() {
               "bg-background border border-mid-gray/20 rounded-lg shadow-lg px-4 py-3 flex items-center gap-3 text-sm",
             title: "font-medium",
             description: "text-mid-gray",
+            actionButton:
+              "bg-logo-primary text-white rounded px-2 py-1 text-xs font-medium",
+            cancelButton: "bg-mid-gray/20 rounded px-2 py-1 text-xs font-medium",
           },
         }}
       />
+      <LiveAnnouncer />
       {/* Main content area that takes remaining space */}
       <div className="flex-1 flex overflow-hidden">
         <Sidebar
@@ -0,0 +1,52 @@
+import { listen } from "@tauri-apps/api/event";
+import React, { useEffect, useState } from "react";
+import "./CaptionsOverlay.css";
+import { commands, type CaptionLine } from "@/bindings";
+
+// Only the most recent lines fit in the overlay window
+const VISIBLE_LINES = 2;
+
+const CaptionsOverlay: React.FC = () => {
+  const [isVisible, setIsVisible] = useState(false);
+  const [lines, setLines] = useState<CaptionLine[]>([]);
+
+  useEffect(() => {
+    const setupEventListeners = async () => {
+      const unlistenShow = await listen("show-captions", async () => {
+        setLines((await commands.getCaptionLines()).slice(-VISIBLE_LINES));
+        setIsVisible(true);
+      });
+
+      const unlistenHide = await listen("hide-captions", () => {
+        setIsVisible(false);
+      });
+
+      const unlistenLine = await listen<CaptionLine>(
+        "caption-line",
+        (event) => {
+          setLines((prev) => [...prev, event.payload].slice(-VISIBLE_LINES));
+        },
+      );
+
+      return () => {
+        unlistenShow();
+        unlistenHide();
+        unlistenLine();
+      };
+    };
+
+    setupEventListeners();
+  }, []);
+
+  return (
+    <div className={`captions-overlay ${isVisible ? "fade-in" : ""}`}>
+      {lines.map((line) => (
+        <p key={line.id} className="caption-line">
+          {line.text}
+        </p>
+      ))}
+    </div>
+  );
+};
+
+export default CaptionsOverlay;
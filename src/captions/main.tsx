@@ -0,0 +1,9 @@
+import React from "react";
+import ReactDOM from "react-dom/client";
+import CaptionsOverlay from "./CaptionsOverlay";
+
+ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
+  <React.StrictMode>
+    <CaptionsOverlay />
+  </React.StrictMode>,
+);
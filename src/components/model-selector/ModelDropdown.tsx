@@ -118,7 +118,12 @@ const ModelDropdown: React.FC<ModelDropdownProps> = ({
                       {t("modelSelector.active")}
                     </div>
                   )}
-                  {currentModelId !== model.id && (
+                  {currentModelId !== model.id && model.is_shared && (
+                    <div className="text-xs text-text/40">
+                      {t("modelSelector.shared")}
+                    </div>
+                  )}
+                  {currentModelId !== model.id && !model.is_shared && (
                     <button
                       onClick={(e) => handleDeleteClick(e, model.id)}
                       className="text-red-400 hover:text-red-300 p-1 hover:bg-red-500/10 rounded transition-colors"
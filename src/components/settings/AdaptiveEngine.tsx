@@ -0,0 +1,112 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { useModelStore } from "../../stores/modelStore";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+// Empty value sends long recordings to the Wyoming server, if enabled
+const NO_MODEL = "";
+
+interface AdaptiveEngineProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const AdaptiveEngine: React.FC<AdaptiveEngineProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const { models } = useModelStore();
+
+    const enabled = getSetting("adaptive_engine") ?? false;
+    const thresholdSecs = getSetting("adaptive_threshold_secs") ?? 5;
+    const longModel = getSetting("adaptive_long_model") ?? null;
+    const wyomingEnabled = getSetting("wyoming_enabled") ?? false;
+    const deepgramEnabled = getSetting("deepgram_enabled") ?? false;
+
+    const modelOptions = [
+      {
+        value: NO_MODEL,
+        label: wyomingEnabled
+          ? t("settings.advanced.adaptiveEngine.longModel.wyoming")
+          : deepgramEnabled
+            ? t("settings.advanced.adaptiveEngine.longModel.deepgram")
+            : t("settings.advanced.adaptiveEngine.longModel.none"),
+      },
+      ...models
+        .filter((model) => model.is_downloaded)
+        .map((model) => ({ value: model.id, label: model.name })),
+    ];
+
+    const handleThresholdChange = (
+      event: React.ChangeEvent<HTMLInputElement>,
+    ) => {
+      const secs = parseInt(event.target.value, 10);
+      if (!isNaN(secs) && secs > 0) {
+        updateSetting("adaptive_threshold_secs", secs);
+      }
+    };
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(value) => updateSetting("adaptive_engine", value)}
+          isUpdating={isUpdating("adaptive_engine")}
+          label={t("settings.advanced.adaptiveEngine.label")}
+          description={t("settings.advanced.adaptiveEngine.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {enabled && (
+          <>
+            <SettingContainer
+              title={t("settings.advanced.adaptiveEngine.threshold.title")}
+              description={t(
+                "settings.advanced.adaptiveEngine.threshold.description",
+              )}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+              layout="horizontal"
+            >
+              <div className="flex items-center space-x-2">
+                <Input
+                  type="number"
+                  min="1"
+                  step="1"
+                  value={thresholdSecs}
+                  onChange={handleThresholdChange}
+                  disabled={isUpdating("adaptive_threshold_secs")}
+                  className="w-20"
+                />
+                <span className="text-sm text-text">
+                  {t("settings.advanced.adaptiveEngine.threshold.seconds")}
+                </span>
+              </div>
+            </SettingContainer>
+            <SettingContainer
+              title={t("settings.advanced.adaptiveEngine.longModel.title")}
+              description={t(
+                "settings.advanced.adaptiveEngine.longModel.description",
+              )}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            >
+              <Dropdown
+                options={modelOptions}
+                selectedValue={longModel ?? NO_MODEL}
+                onSelect={(value) =>
+                  updateSetting("adaptive_long_model", value || null)
+                }
+                disabled={isUpdating("adaptive_long_model")}
+              />
+            </SettingContainer>
+          </>
+        )}
+      </>
+    );
+  },
+);
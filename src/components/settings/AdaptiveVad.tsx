@@ -0,0 +1,30 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface AdaptiveVadProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const AdaptiveVad: React.FC<AdaptiveVadProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const adaptiveVadEnabled = getSetting("adaptive_vad") ?? true;
+
+    return (
+      <ToggleSwitch
+        checked={adaptiveVadEnabled}
+        onChange={(enabled) => updateSetting("adaptive_vad", enabled)}
+        isUpdating={isUpdating("adaptive_vad")}
+        label={t("settings.sound.adaptiveVad.label")}
+        description={t("settings.sound.adaptiveVad.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
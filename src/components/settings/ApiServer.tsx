@@ -0,0 +1,108 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { TextDisplay } from "../ui/TextDisplay";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+interface ApiServerProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/** The OpenAI-compatible transcription endpoint other apps can call. */
+export const ApiServer: React.FC<ApiServerProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, refreshSettings } =
+      useSettings();
+
+    const enabled = getSetting("api_server_enabled") ?? false;
+    const port = getSetting("api_server_port") ?? 8178;
+    const token = getSetting("api_server_token") ?? "";
+    const [localPort, setLocalPort] = useState(String(port));
+
+    useEffect(() => setLocalPort(String(port)), [port]);
+
+    const savePort = () => {
+      const value = parseInt(localPort, 10);
+      if (!isNaN(value) && value !== port) {
+        updateSetting("api_server_port", value);
+      } else {
+        setLocalPort(String(port));
+      }
+    };
+
+    const regenerate = async () => {
+      await commands.regenerateApiServerToken();
+      await refreshSettings();
+    };
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={async (value) => {
+            await updateSetting("api_server_enabled", value);
+            // Turning the server on the first time creates its token
+            await refreshSettings();
+          }}
+          isUpdating={isUpdating("api_server_enabled")}
+          label={t("settings.advanced.apiServer.label")}
+          description={t("settings.advanced.apiServer.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {enabled && (
+          <>
+            <SettingContainer
+              title={t("settings.advanced.apiServer.port.title")}
+              description={t("settings.advanced.apiServer.port.description")}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+              layout="stacked"
+            >
+              <Input
+                type="number"
+                min="1024"
+                max="65535"
+                value={localPort}
+                onChange={(e) => setLocalPort(e.target.value)}
+                onBlur={savePort}
+                disabled={isUpdating("api_server_port")}
+                className="w-28"
+              />
+              <div className="mt-2 text-sm text-mid-gray select-text">
+                <code>{`http://127.0.0.1:${port}/v1`}</code>
+              </div>
+            </SettingContainer>
+            <TextDisplay
+              label={t("settings.advanced.apiServer.token.title")}
+              description={t("settings.advanced.apiServer.token.description")}
+              value={token}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+              copyable
+              monospace
+            />
+            <SettingContainer
+              title={t("settings.advanced.apiServer.regenerate.title")}
+              description={t(
+                "settings.advanced.apiServer.regenerate.description",
+              )}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            >
+              <Button variant="secondary" size="sm" onClick={regenerate}>
+                {t("settings.advanced.apiServer.regenerate.button")}
+              </Button>
+            </SettingContainer>
+          </>
+        )}
+      </>
+    );
+  },
+);
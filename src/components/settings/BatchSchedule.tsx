@@ -0,0 +1,237 @@
+import React, { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type BatchJob } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+interface TranscriptionProgress {
+  percent: number;
+  current_chunk: number;
+  total: number;
+  eta_secs: number | null;
+  task: string | null;
+}
+
+interface BatchScheduleProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const toTimeValue = (minute: number | null | undefined) => {
+  if (minute == null) return "";
+  const hours = String(Math.floor(minute / 60)).padStart(2, "0");
+  const minutes = String(minute % 60).padStart(2, "0");
+  return `${hours}:${minutes}`;
+};
+
+const formatEta = (secs: number) => {
+  const whole = Math.round(secs);
+  return `${Math.floor(whole / 60)}:${String(whole % 60).padStart(2, "0")}`;
+};
+
+const fromTimeValue = (value: string) => {
+  const [hours, minutes] = value.split(":").map((part) => parseInt(part, 10));
+  return isNaN(hours) || isNaN(minutes) ? null : hours * 60 + minutes;
+};
+
+export const BatchSchedule: React.FC<BatchScheduleProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const [jobs, setJobs] = useState<BatchJob[]>([]);
+    const [isRunning, setIsRunning] = useState(false);
+    const [progress, setProgress] = useState<TranscriptionProgress | null>(
+      null,
+    );
+
+    const deferred = getSetting("batch_deferred") ?? false;
+    const windowStart = getSetting("batch_window_start");
+    const windowEnd = getSetting("batch_window_end");
+    const requireAcPower = getSetting("batch_require_ac_power") ?? false;
+    const idleMinutes = getSetting("batch_idle_minutes");
+
+    useEffect(() => {
+      commands.getBatchJobs().then(setJobs);
+      const unlistenPromise = listen<BatchJob[]>(
+        "batch-jobs-changed",
+        (event) => setJobs(event.payload),
+      );
+      const unlistenProgress = listen<TranscriptionProgress>(
+        "transcription-progress",
+        (event) => setProgress(event.payload.task ? event.payload : null),
+      );
+      return () => {
+        unlistenPromise.then((unlisten) => unlisten());
+        unlistenProgress.then((unlisten) => unlisten());
+      };
+    }, []);
+
+    const running =
+      progress && jobs.some((job) => job.path === progress.task)
+        ? progress
+        : null;
+
+    const handleIdleChange = (event: React.ChangeEvent<HTMLInputElement>) => {
+      const minutes = parseInt(event.target.value, 10);
+      updateSetting(
+        "batch_idle_minutes",
+        !isNaN(minutes) && minutes > 0 ? minutes : null,
+      );
+    };
+
+    const handleRunNow = async () => {
+      setIsRunning(true);
+      try {
+        const result = await commands.runBatchJobsNow();
+        if (result.status === "error") {
+          toast.error(result.error);
+        } else {
+          toast.success(
+            t("settings.advanced.batch.queue.done", { count: result.data }),
+          );
+        }
+      } finally {
+        setIsRunning(false);
+      }
+    };
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={deferred}
+          onChange={(enabled) => updateSetting("batch_deferred", enabled)}
+          isUpdating={isUpdating("batch_deferred")}
+          label={t("settings.advanced.batch.deferred.label")}
+          description={t("settings.advanced.batch.deferred.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {deferred && (
+          <>
+            <SettingContainer
+              title={t("settings.advanced.batch.window.title")}
+              description={t("settings.advanced.batch.window.description")}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+              layout="horizontal"
+            >
+              <div className="flex items-center space-x-2">
+                <Input
+                  type="time"
+                  value={toTimeValue(windowStart)}
+                  onChange={(e) =>
+                    updateSetting(
+                      "batch_window_start",
+                      fromTimeValue(e.target.value),
+                    )
+                  }
+                  disabled={isUpdating("batch_window_start")}
+                  variant="compact"
+                />
+                <span className="text-sm text-text">
+                  {t("settings.advanced.batch.window.to")}
+                </span>
+                <Input
+                  type="time"
+                  value={toTimeValue(windowEnd)}
+                  onChange={(e) =>
+                    updateSetting(
+                      "batch_window_end",
+                      fromTimeValue(e.target.value),
+                    )
+                  }
+                  disabled={isUpdating("batch_window_end")}
+                  variant="compact"
+                />
+              </div>
+            </SettingContainer>
+            <ToggleSwitch
+              checked={requireAcPower}
+              onChange={(enabled) =>
+                updateSetting("batch_require_ac_power", enabled)
+              }
+              isUpdating={isUpdating("batch_require_ac_power")}
+              label={t("settings.advanced.batch.acPower.label")}
+              description={t("settings.advanced.batch.acPower.description")}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            />
+            <SettingContainer
+              title={t("settings.advanced.batch.idle.title")}
+              description={t("settings.advanced.batch.idle.description")}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+              layout="horizontal"
+            >
+              <div className="flex items-center space-x-2">
+                <Input
+                  type="number"
+                  min="0"
+                  step="1"
+                  value={idleMinutes ?? ""}
+                  placeholder="0"
+                  onChange={handleIdleChange}
+                  disabled={isUpdating("batch_idle_minutes")}
+                  className="w-20"
+                />
+                <span className="text-sm text-text">
+                  {t("settings.advanced.batch.idle.minutes")}
+                </span>
+              </div>
+            </SettingContainer>
+          </>
+        )}
+        {jobs.length > 0 && (
+          <SettingContainer
+            title={t("settings.advanced.batch.queue.title")}
+            description={t("settings.advanced.batch.queue.description")}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          >
+            <div className="flex items-center space-x-2">
+              <span className="text-sm text-mid-gray">
+                {t("settings.advanced.batch.queue.count", {
+                  count: jobs.length,
+                })}
+              </span>
+              <Button
+                variant="secondary"
+                size="sm"
+                onClick={handleRunNow}
+                disabled={isRunning}
+              >
+                {t("settings.advanced.batch.queue.runNow")}
+              </Button>
+            </div>
+            {running && (
+              <div className="mt-2 space-y-1">
+                <div className="h-1 rounded bg-mid-gray/20 overflow-hidden">
+                  <div
+                    className="h-full bg-logo-primary transition-all"
+                    style={{ width: `${running.percent}%` }}
+                  />
+                </div>
+                <p className="text-xs text-mid-gray truncate">
+                  {t("settings.advanced.batch.queue.progress", {
+                    file: running.task?.split(/[\\/]/).pop(),
+                    current: running.current_chunk,
+                    total: running.total,
+                  })}
+                  {running.eta_secs !== null &&
+                    ` · ${t("settings.advanced.batch.queue.eta", {
+                      eta: formatEta(running.eta_secs),
+                    })}`}
+                </p>
+              </div>
+            )}
+          </SettingContainer>
+        )}
+      </>
+    );
+  },
+);
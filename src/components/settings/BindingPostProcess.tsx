@@ -0,0 +1,63 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type ShortcutBinding } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+// Shortcuts whose dictations are post-processed
+const DICTATION_BINDINGS = ["transcribe", "translate"];
+const DICTATION_BINDING_PREFIX = "dictate_";
+
+interface BindingPostProcessProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const BindingPostProcess: React.FC<BindingPostProcessProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { settings, refreshSettings } = useSettings();
+    const [updating, setUpdating] = useState<string | null>(null);
+
+    const bindings = Object.values(settings?.bindings ?? {})
+      .filter(
+        (binding): binding is ShortcutBinding =>
+          !!binding &&
+          (DICTATION_BINDINGS.includes(binding.id) ||
+            binding.id.startsWith(DICTATION_BINDING_PREFIX)),
+      )
+      .sort((a, b) => a.id.localeCompare(b.id));
+
+    const handleChange = async (id: string, skip: boolean) => {
+      setUpdating(id);
+      const result = await commands.changeBindingSkipPostProcess(id, skip);
+      if (result.status === "error") {
+        toast.error(result.error);
+      }
+      await refreshSettings();
+      setUpdating(null);
+    };
+
+    return (
+      <>
+        {bindings.map((binding) => (
+          <ToggleSwitch
+            key={binding.id}
+            checked={binding.skip_post_process ?? false}
+            onChange={(skip) => handleChange(binding.id, skip)}
+            isUpdating={updating === binding.id}
+            label={t("settings.postProcessing.bindingSkip.label", {
+              shortcut: t(
+                `settings.general.shortcut.bindings.${binding.id}.name`,
+                binding.name,
+              ),
+            })}
+            description={t("settings.postProcessing.bindingSkip.description")}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          />
+        ))}
+      </>
+    );
+  });
@@ -0,0 +1,78 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+// Shortcuts that dictate, and so can translate what they record
+const TRANSLATE_BINDINGS = ["transcribe", "translate"];
+
+interface BindingTranslationProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const BindingTranslation: React.FC<BindingTranslationProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { settings, refreshSettings } = useSettings();
+    const [updating, setUpdating] = useState<string | null>(null);
+
+    const options = [
+      {
+        value: "default",
+        label: t("settings.advanced.bindingTranslate.default"),
+      },
+      {
+        value: "always",
+        label: t("settings.advanced.bindingTranslate.always"),
+      },
+      {
+        value: "never",
+        label: t("settings.advanced.bindingTranslate.never"),
+      },
+    ];
+
+    const selected = (translate: boolean | null | undefined) =>
+      translate == null ? "default" : translate ? "always" : "never";
+
+    const handleSelect = async (id: string, value: string) => {
+      setUpdating(id);
+      const result = await commands.changeBindingTranslate(
+        id,
+        value === "default" ? null : value === "always",
+      );
+      if (result.status === "error") {
+        toast.error(result.error);
+      }
+      await refreshSettings();
+      setUpdating(null);
+    };
+
+    return (
+      <>
+        {TRANSLATE_BINDINGS.filter((id) => settings?.bindings?.[id]).map(
+          (id) => (
+            <SettingContainer
+              key={id}
+              title={t("settings.advanced.bindingTranslate.title", {
+                shortcut: t(`settings.general.shortcut.bindings.${id}.name`),
+              })}
+              description={t("settings.advanced.bindingTranslate.description")}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            >
+              <Dropdown
+                options={options}
+                selectedValue={selected(settings?.bindings?.[id]?.translate)}
+                onSelect={(value) => handleSelect(id, value)}
+                disabled={updating === id}
+              />
+            </SettingContainer>
+          ),
+        )}
+      </>
+    );
+  });
@@ -0,0 +1,71 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+interface CaptionSegmentationProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const CaptionSegmentation: React.FC<CaptionSegmentationProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const atPauses = getSetting("caption_segment_at_pauses") ?? false;
+    const maxSegmentSecs = getSetting("caption_max_segment_secs") ?? 20;
+
+    const handleMaxLengthChange = (
+      event: React.ChangeEvent<HTMLInputElement>,
+    ) => {
+      const seconds = parseInt(event.target.value, 10);
+      if (!isNaN(seconds) && seconds > 0) {
+        updateSetting("caption_max_segment_secs", seconds);
+      }
+    };
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={atPauses}
+          onChange={(enabled) =>
+            updateSetting("caption_segment_at_pauses", enabled)
+          }
+          isUpdating={isUpdating("caption_segment_at_pauses")}
+          label={t("settings.general.captionSegmentation.label")}
+          description={t("settings.general.captionSegmentation.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {atPauses && (
+          <SettingContainer
+            title={t("settings.general.captionSegmentation.maxLength.title")}
+            description={t(
+              "settings.general.captionSegmentation.maxLength.description",
+            )}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+            layout="horizontal"
+          >
+            <div className="flex items-center space-x-2">
+              <Input
+                type="number"
+                min="1"
+                step="1"
+                value={maxSegmentSecs}
+                onChange={handleMaxLengthChange}
+                disabled={isUpdating("caption_max_segment_secs")}
+                className="w-20"
+              />
+              <span className="text-sm text-text">
+                {t("settings.general.captionSegmentation.maxLength.seconds")}
+              </span>
+            </div>
+          </SettingContainer>
+        )}
+      </>
+    );
+  });
@@ -0,0 +1,117 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type CaptionTimestamps } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface CaptionsExportProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const CaptionsExport: React.FC<CaptionsExportProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const speakerLabel = getSetting("caption_speaker_label") ?? "Speaker 1";
+    const [localSpeaker, setLocalSpeaker] = useState(speakerLabel);
+
+    useEffect(() => setLocalSpeaker(speakerLabel), [speakerLabel]);
+
+    const timestampOptions = [
+      {
+        value: "off",
+        label: t("settings.general.captionsExport.timestamps.off"),
+      },
+      {
+        value: "every_line",
+        label: t("settings.general.captionsExport.timestamps.everyLine"),
+      },
+      {
+        value: "every_minute",
+        label: t("settings.general.captionsExport.timestamps.everyMinute"),
+      },
+      {
+        value: "every_five_minutes",
+        label: t(
+          "settings.general.captionsExport.timestamps.everyFiveMinutes",
+        ),
+      },
+    ];
+
+    const selectedTimestamps = (getSetting("caption_export_timestamps") ||
+      "every_line") as CaptionTimestamps;
+
+    const handleSpeakerBlur = () => {
+      if (localSpeaker.trim()) {
+        updateSetting("caption_speaker_label", localSpeaker.trim());
+      } else {
+        setLocalSpeaker(speakerLabel);
+      }
+    };
+
+    const handleCopy = async () => {
+      const result = await commands.copyCaptionSession();
+      if (result.status === "error") {
+        toast.error(result.error);
+      } else {
+        toast.success(t("settings.general.captionsExport.copied"));
+      }
+    };
+
+    const handleSave = async () => {
+      const result = await commands.saveCaptionSession();
+      if (result.status === "error") {
+        toast.error(result.error);
+      } else {
+        toast.success(
+          t("settings.general.captionsExport.saved", { path: result.data }),
+        );
+      }
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.general.captionsExport.title")}
+        description={t("settings.general.captionsExport.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="flex flex-wrap items-center gap-2">
+          <Dropdown
+            options={timestampOptions}
+            selectedValue={selectedTimestamps}
+            onSelect={(value) =>
+              updateSetting(
+                "caption_export_timestamps",
+                value as CaptionTimestamps,
+              )
+            }
+            disabled={isUpdating("caption_export_timestamps")}
+          />
+          <Input
+            type="text"
+            className="w-32"
+            value={localSpeaker}
+            onChange={(e) => setLocalSpeaker(e.target.value)}
+            onBlur={handleSpeakerBlur}
+            placeholder={t("settings.general.captionsExport.speaker")}
+            variant="compact"
+            disabled={isUpdating("caption_speaker_label")}
+          />
+          <Button variant="secondary" size="sm" onClick={handleCopy}>
+            {t("settings.general.captionsExport.copy")}
+          </Button>
+          <Button variant="secondary" size="sm" onClick={handleSave}>
+            {t("settings.general.captionsExport.save")}
+          </Button>
+        </div>
+      </SettingContainer>
+    );
+  },
+);
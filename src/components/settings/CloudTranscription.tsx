@@ -0,0 +1,135 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import type {
+  CloudProvider,
+  CloudTranscription as CloudConfig,
+} from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+const OFF = "__off__";
+
+interface Provider {
+  id: CloudProvider;
+  label: string;
+  /** Used when none is set, `null` for providers that pick it themselves */
+  model: string | null;
+}
+
+const PROVIDERS: Provider[] = [
+  { id: "gladia", label: "Gladia", model: null },
+  { id: "fireworks", label: "Fireworks AI", model: "whisper-v3" },
+  { id: "mistral", label: "Mistral Voxtral", model: "voxtral-small-latest" },
+];
+
+interface CloudTranscriptionProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/** Transcribing with a hosted API picked from a list, each with its own
+ * key. */
+export const CloudTranscription: React.FC<CloudTranscriptionProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, refreshSettings } =
+      useSettings();
+
+    const config: CloudConfig = getSetting("cloud_transcription") ?? {};
+    const provider = PROVIDERS.find((p) => p.id === config.provider);
+    const [apiKey, setApiKey] = useState("");
+    const [model, setModel] = useState("");
+    const updating = isUpdating("cloud_transcription");
+
+    useEffect(() => {
+      setApiKey(provider ? (config.api_keys?.[provider.id] ?? "") : "");
+      setModel(provider ? (config.models?.[provider.id] ?? "") : "");
+    }, [provider, config.api_keys, config.models]);
+
+    const save = async (next: CloudConfig) => {
+      await updateSetting("cloud_transcription", next);
+      // Picking a provider turns Deepgram and the Wyoming server off
+      await refreshSettings();
+    };
+
+    const saveAccount = () => {
+      if (!provider) {
+        return;
+      }
+      save({
+        ...config,
+        api_keys: { ...config.api_keys, [provider.id]: apiKey },
+        models: { ...config.models, [provider.id]: model },
+      });
+    };
+
+    const options = [
+      { value: OFF, label: t("settings.advanced.cloudTranscription.off") },
+      ...PROVIDERS.map((p) => ({ value: p.id, label: p.label })),
+    ];
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.cloudTranscription.provider.title")}
+          description={t(
+            "settings.advanced.cloudTranscription.provider.description",
+          )}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={options}
+            selectedValue={config.provider ?? OFF}
+            onSelect={(value) =>
+              save({
+                ...config,
+                provider: value === OFF ? null : (value as CloudProvider),
+              })
+            }
+            disabled={updating}
+          />
+        </SettingContainer>
+        {provider && (
+          <SettingContainer
+            title={t("settings.advanced.cloudTranscription.account.title")}
+            description={t(
+              "settings.advanced.cloudTranscription.account.description",
+            )}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+            layout="stacked"
+          >
+            <div className="flex items-center space-x-2">
+              <Input
+                type="password"
+                className="flex-1"
+                value={apiKey}
+                onChange={(e) => setApiKey(e.target.value)}
+                onBlur={saveAccount}
+                placeholder={t(
+                  "settings.advanced.cloudTranscription.account.apiKey",
+                )}
+                variant="compact"
+                disabled={updating}
+              />
+              {provider.model && (
+                <Input
+                  type="text"
+                  className="flex-1 font-mono"
+                  value={model}
+                  onChange={(e) => setModel(e.target.value)}
+                  onBlur={saveAccount}
+                  placeholder={provider.model}
+                  variant="compact"
+                  disabled={updating}
+                />
+              )}
+            </div>
+          </SettingContainer>
+        )}
+      </>
+    );
+  });
@@ -0,0 +1,28 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface CompressSilenceProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const CompressSilence: React.FC<CompressSilenceProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    return (
+      <ToggleSwitch
+        checked={getSetting("compress_silence") ?? false}
+        onChange={(enabled) => updateSetting("compress_silence", enabled)}
+        isUpdating={isUpdating("compress_silence")}
+        label={t("settings.advanced.compressSilence.label")}
+        description={t("settings.advanced.compressSilence.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
@@ -0,0 +1,70 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type ConcurrentRecording } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+// Shortcuts that record from the microphone
+const RECORDING_BINDINGS = ["transcribe", "translate", "toggle_captions"];
+const BEHAVIORS: ConcurrentRecording[] = ["ignore", "stop_and_start", "queue"];
+
+interface ConcurrentRecordingSettingProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const ConcurrentRecordingSetting: React.FC<ConcurrentRecordingSettingProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { settings, refreshSettings } = useSettings();
+    const [updating, setUpdating] = useState<string | null>(null);
+
+    const options = BEHAVIORS.map((behavior) => ({
+      value: behavior,
+      label: t(`settings.advanced.concurrentRecording.options.${behavior}`),
+    }));
+
+    const handleSelect = async (id: string, value: string) => {
+      setUpdating(id);
+      const result = await commands.changeBindingWhenBusy(
+        id,
+        value as ConcurrentRecording,
+      );
+      if (result.status === "error") {
+        toast.error(result.error);
+      }
+      await refreshSettings();
+      setUpdating(null);
+    };
+
+    return (
+      <>
+        {RECORDING_BINDINGS.filter((id) => settings?.bindings?.[id]).map(
+          (id) => (
+            <SettingContainer
+              key={id}
+              title={t("settings.advanced.concurrentRecording.title", {
+                shortcut: t(`settings.general.shortcut.bindings.${id}.name`),
+              })}
+              description={t(
+                "settings.advanced.concurrentRecording.description",
+              )}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            >
+              <Dropdown
+                options={options}
+                selectedValue={
+                  settings?.bindings?.[id]?.when_busy ?? "ignore"
+                }
+                onSelect={(value) => handleSelect(id, value)}
+                disabled={updating === id}
+              />
+            </SettingContainer>
+          ),
+        )}
+      </>
+    );
+  });
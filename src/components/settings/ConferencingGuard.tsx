@@ -0,0 +1,83 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import type { ConferencingGuard as ConferencingGuardMode } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface ConferencingGuardProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const ConferencingGuard: React.FC<ConferencingGuardProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const {
+      getSetting,
+      updateSetting,
+      isUpdating,
+      isLoading,
+      audioDevices,
+      refreshAudioDevices,
+    } = useSettings();
+
+    const mode = (getSetting("conferencing_guard") ||
+      "off") as ConferencingGuardMode;
+    const microphone = getSetting("conferencing_microphone") || "Default";
+
+    const modeOptions = [
+      { value: "off", label: t("settings.sound.conferencingGuard.off") },
+      { value: "warn", label: t("settings.sound.conferencingGuard.warn") },
+      {
+        value: "switch_microphone",
+        label: t("settings.sound.conferencingGuard.switchMicrophone"),
+      },
+    ];
+
+    const microphoneOptions = audioDevices.map((device) => ({
+      value: device.name,
+      label: device.name,
+    }));
+
+    return (
+      <SettingContainer
+        title={t("settings.sound.conferencingGuard.title")}
+        description={t("settings.sound.conferencingGuard.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="flex flex-wrap items-center gap-2">
+          <Dropdown
+            options={modeOptions}
+            selectedValue={mode}
+            onSelect={(value) =>
+              updateSetting(
+                "conferencing_guard",
+                value as ConferencingGuardMode,
+              )
+            }
+            disabled={isUpdating("conferencing_guard")}
+          />
+          {mode === "switch_microphone" && (
+            <Dropdown
+              options={microphoneOptions}
+              selectedValue={microphone}
+              onSelect={(value) =>
+                updateSetting("conferencing_microphone", value)
+              }
+              placeholder={t("settings.sound.conferencingGuard.microphone")}
+              disabled={
+                isUpdating("conferencing_microphone") ||
+                isLoading ||
+                audioDevices.length === 0
+              }
+              onRefresh={refreshAudioDevices}
+            />
+          )}
+        </div>
+      </SettingContainer>
+    );
+  },
+);
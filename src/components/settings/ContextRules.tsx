@@ -0,0 +1,254 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import type { ContextRule } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+const NO_PROMPT = "__none__";
+
+/** Dropdown values for a rule's optional on/off overrides */
+const fromOverride = (value: boolean | null | undefined) =>
+  value == null ? "default" : value ? "on" : "off";
+const toOverride = (value: string) =>
+  value === "default" ? null : value === "on";
+
+const isValidPattern = (pattern: string) => {
+  try {
+    new RegExp(pattern, "i");
+    return true;
+  } catch {
+    return false;
+  }
+};
+
+interface ContextRulesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const ContextRules: React.FC<ContextRulesProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const rules = getSetting("context_rules");
+    const prompts = getSetting("post_process_prompts") ?? [];
+    const [localRules, setLocalRules] = useState<ContextRule[]>(rules ?? []);
+    const updating = isUpdating("context_rules");
+
+    useEffect(() => setLocalRules(rules ?? []), [rules]);
+
+    const promptOptions = [
+      {
+        value: NO_PROMPT,
+        label: t("settings.postProcessing.contextRules.raw"),
+      },
+      ...prompts.map((prompt) => ({ value: prompt.id, label: prompt.name })),
+    ];
+
+    const overrideOptions = (key: string) => [
+      {
+        value: "default",
+        label: t(`settings.postProcessing.contextRules.${key}.default`),
+      },
+      {
+        value: "on",
+        label: t(`settings.postProcessing.contextRules.${key}.on`),
+      },
+      {
+        value: "off",
+        label: t(`settings.postProcessing.contextRules.${key}.off`),
+      },
+    ];
+
+    const enterOptions = [
+      {
+        value: "off",
+        label: t("settings.postProcessing.contextRules.pressEnter.off"),
+      },
+      {
+        value: "on",
+        label: t("settings.postProcessing.contextRules.pressEnter.on"),
+      },
+    ];
+
+    const saveEdit = (index: number, changes: Partial<ContextRule>) => {
+      const next = localRules.map((rule, i) =>
+        i === index ? { ...rule, ...changes } : rule,
+      );
+      setLocalRules(next);
+      save(next);
+    };
+
+    const save = (next: ContextRule[]) => {
+      if (next.every((rule) => isValidPattern(rule.title_pattern))) {
+        updateSetting("context_rules", next);
+      }
+    };
+
+    const edit = (index: number, changes: Partial<ContextRule>) =>
+      setLocalRules(
+        localRules.map((rule, i) =>
+          i === index ? { ...rule, ...changes } : rule,
+        ),
+      );
+
+    const addRule = () =>
+      save([
+        ...localRules,
+        {
+          id: `rule_${Date.now()}`,
+          name: "",
+          app: "",
+          title_pattern: "",
+          prompt_id: prompts[0]?.id ?? null,
+          transcription_prompt: null,
+          punctuation_restoration: null,
+          append_trailing_space: null,
+          press_enter: false,
+        },
+      ]);
+
+    return (
+      <SettingContainer
+        title={t("settings.postProcessing.contextRules.title")}
+        description={t("settings.postProcessing.contextRules.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-3">
+          {localRules.map((rule, index) => (
+            <div
+              key={rule.id}
+              className="space-y-2 p-2 rounded border border-mid-gray/20"
+            >
+              <div className="flex items-center space-x-2">
+                <Input
+                  type="text"
+                  className="flex-1"
+                  value={rule.name}
+                  onChange={(e) => edit(index, { name: e.target.value })}
+                  onBlur={() => save(localRules)}
+                  placeholder={t(
+                    "settings.postProcessing.contextRules.namePlaceholder",
+                  )}
+                  variant="compact"
+                  disabled={updating}
+                />
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() =>
+                    save(localRules.filter((_, i) => i !== index))
+                  }
+                  disabled={updating}
+                >
+                  {t("settings.postProcessing.contextRules.remove")}
+                </Button>
+              </div>
+              <div className="flex items-center space-x-2">
+                <Input
+                  type="text"
+                  className="w-32"
+                  value={rule.app}
+                  onChange={(e) => edit(index, { app: e.target.value })}
+                  onBlur={() => save(localRules)}
+                  placeholder={t(
+                    "settings.postProcessing.contextRules.appPlaceholder",
+                  )}
+                  variant="compact"
+                  disabled={updating}
+                />
+                <Input
+                  type="text"
+                  className="flex-1 font-mono"
+                  value={rule.title_pattern}
+                  onChange={(e) =>
+                    edit(index, { title_pattern: e.target.value })
+                  }
+                  onBlur={() => save(localRules)}
+                  placeholder={t(
+                    "settings.postProcessing.contextRules.titlePlaceholder",
+                  )}
+                  variant="compact"
+                  disabled={updating}
+                />
+              </div>
+              <Input
+                type="text"
+                className="w-full"
+                value={rule.transcription_prompt ?? ""}
+                onChange={(e) =>
+                  edit(index, {
+                    transcription_prompt: e.target.value || null,
+                  })
+                }
+                onBlur={() => save(localRules)}
+                placeholder={t(
+                  "settings.postProcessing.contextRules.transcriptionPromptPlaceholder",
+                )}
+                variant="compact"
+                disabled={updating}
+              />
+              {!isValidPattern(rule.title_pattern) && (
+                <p className="text-xs text-red-400">
+                  {t("settings.postProcessing.contextRules.invalidPattern")}
+                </p>
+              )}
+              <Dropdown
+                options={promptOptions}
+                selectedValue={rule.prompt_id ?? NO_PROMPT}
+                onSelect={(value) =>
+                  saveEdit(index, {
+                    prompt_id: value === NO_PROMPT ? null : value,
+                  })
+                }
+                disabled={updating}
+              />
+              <div className="flex flex-wrap items-center gap-2">
+                <Dropdown
+                  options={overrideOptions("punctuation")}
+                  selectedValue={fromOverride(rule.punctuation_restoration)}
+                  onSelect={(value) =>
+                    saveEdit(index, {
+                      punctuation_restoration: toOverride(value),
+                    })
+                  }
+                  disabled={updating}
+                />
+                <Dropdown
+                  options={overrideOptions("trailingSpace")}
+                  selectedValue={fromOverride(rule.append_trailing_space)}
+                  onSelect={(value) =>
+                    saveEdit(index, { append_trailing_space: toOverride(value) })
+                  }
+                  disabled={updating}
+                />
+                <Dropdown
+                  options={enterOptions}
+                  selectedValue={rule.press_enter ? "on" : "off"}
+                  onSelect={(value) =>
+                    saveEdit(index, { press_enter: value === "on" })
+                  }
+                  disabled={updating}
+                />
+              </div>
+            </div>
+          ))}
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={addRule}
+            disabled={updating}
+          >
+            {t("settings.postProcessing.contextRules.add")}
+          </Button>
+        </div>
+      </SettingContainer>
+    );
+  },
+);
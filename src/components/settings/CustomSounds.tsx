@@ -0,0 +1,122 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { PlayIcon } from "lucide-react";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { type SoundType, useSettingsStore } from "../../stores/settingsStore";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+const SOUND_TYPES: SoundType[] = [
+  "start",
+  "stop",
+  "error",
+  "done",
+  "countdown",
+];
+
+interface CustomSoundsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+  disabled?: boolean;
+}
+
+export const CustomSounds: React.FC<CustomSoundsProps> = ({
+  descriptionMode = "tooltip",
+  grouped = false,
+  disabled = false,
+}) => {
+  const { t } = useTranslation();
+  const customSounds = useSettingsStore((state) => state.customSounds);
+  const checkCustomSounds = useSettingsStore(
+    (state) => state.checkCustomSounds,
+  );
+  const playTestSound = useSettingsStore((state) => state.playTestSound);
+  const [soundType, setSoundType] = useState<SoundType>("start");
+  const [path, setPath] = useState("");
+
+  const options = SOUND_TYPES.map((type) => ({
+    value: type,
+    label: customSounds[type]
+      ? t("settings.sound.customSounds.customized", {
+          sound: t(`settings.sound.customSounds.types.${type}`),
+        })
+      : t(`settings.sound.customSounds.types.${type}`),
+  }));
+
+  const handleImport = async () => {
+    const result = await commands.importCustomSound(soundType, path);
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    setPath("");
+    await checkCustomSounds();
+  };
+
+  const handleRemove = async () => {
+    const result = await commands.removeCustomSound(soundType);
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    await checkCustomSounds();
+  };
+
+  return (
+    <SettingContainer
+      title={t("settings.sound.customSounds.title")}
+      description={t("settings.sound.customSounds.description")}
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+      layout="stacked"
+    >
+      <div className="flex items-center gap-2">
+        <Dropdown
+          options={options}
+          selectedValue={soundType}
+          onSelect={(value) => setSoundType(value as SoundType)}
+          disabled={disabled}
+        />
+        <Input
+          type="text"
+          className="flex-1 min-w-[160px]"
+          value={path}
+          onChange={(e) => setPath(e.target.value)}
+          placeholder="/path/to/sound.wav"
+          variant="compact"
+          disabled={disabled}
+        />
+        <Button
+          variant="secondary"
+          size="sm"
+          onClick={handleImport}
+          disabled={disabled || !path.trim()}
+        >
+          {t("settings.sound.customSounds.import")}
+        </Button>
+        {customSounds[soundType] && (
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={handleRemove}
+            disabled={disabled}
+          >
+            {t("settings.sound.customSounds.remove")}
+          </Button>
+        )}
+        <Button
+          variant="ghost"
+          size="sm"
+          onClick={() => playTestSound(soundType)}
+          disabled={disabled}
+          title={t("settings.sound.customSounds.preview")}
+        >
+          <PlayIcon className="h-4 w-4" />
+        </Button>
+      </div>
+    </SettingContainer>
+  );
+};
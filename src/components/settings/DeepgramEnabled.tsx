@@ -0,0 +1,35 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface DeepgramEnabledProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const DeepgramEnabled: React.FC<DeepgramEnabledProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, refreshSettings } =
+      useSettings();
+
+    const deepgramEnabled = getSetting("deepgram_enabled") ?? false;
+
+    return (
+      <ToggleSwitch
+        checked={deepgramEnabled}
+        onChange={async (enabled) => {
+          await updateSetting("deepgram_enabled", enabled);
+          // Turning Deepgram on turns the Wyoming server off
+          await refreshSettings();
+        }}
+        isUpdating={isUpdating("deepgram_enabled")}
+        label={t("settings.advanced.deepgram.enabled.label")}
+        description={t("settings.advanced.deepgram.enabled.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
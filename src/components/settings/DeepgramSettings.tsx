@@ -0,0 +1,159 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import type { DeepgramConfig, UploadCodec } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+const DEFAULT_CONFIG: DeepgramConfig = {
+  api_key: "",
+  model: "nova-3",
+  streaming: true,
+  upload_codec: "flac",
+  connect_timeout_secs: 10,
+  read_timeout_secs: 120,
+  max_retries: 3,
+  diarize: false,
+};
+
+const CODECS: UploadCodec[] = ["flac", "wav"];
+
+const NETWORK_FIELDS = [
+  "connect_timeout_secs",
+  "read_timeout_secs",
+  "max_retries",
+] as const;
+
+interface DeepgramSettingsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const DeepgramSettings: React.FC<DeepgramSettingsProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const config = getSetting("deepgram");
+    const [local, setLocal] = useState<DeepgramConfig>(
+      config ?? DEFAULT_CONFIG,
+    );
+    const updating = isUpdating("deepgram");
+
+    useEffect(() => setLocal(config ?? DEFAULT_CONFIG), [config]);
+
+    const save = () => updateSetting("deepgram", local);
+
+    const codecOptions = CODECS.map((codec) => ({
+      value: codec,
+      label: t(`settings.advanced.deepgram.uploadCodec.${codec}`),
+    }));
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.deepgram.account.title")}
+          description={t("settings.advanced.deepgram.account.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          layout="stacked"
+        >
+          <div className="flex items-center space-x-2">
+            <Input
+              type="password"
+              className="flex-1"
+              value={local.api_key}
+              onChange={(e) => setLocal({ ...local, api_key: e.target.value })}
+              onBlur={save}
+              placeholder={t("settings.advanced.deepgram.account.apiKey")}
+              variant="compact"
+              disabled={updating}
+            />
+            <Input
+              type="text"
+              className="flex-1 font-mono"
+              value={local.model}
+              onChange={(e) => setLocal({ ...local, model: e.target.value })}
+              onBlur={save}
+              placeholder={DEFAULT_CONFIG.model}
+              variant="compact"
+              disabled={updating}
+            />
+          </div>
+        </SettingContainer>
+        <ToggleSwitch
+          checked={local.streaming}
+          onChange={(streaming) =>
+            updateSetting("deepgram", { ...local, streaming })
+          }
+          isUpdating={updating}
+          label={t("settings.advanced.deepgram.streaming.label")}
+          description={t("settings.advanced.deepgram.streaming.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        <ToggleSwitch
+          checked={local.diarize ?? false}
+          onChange={(diarize) =>
+            updateSetting("deepgram", { ...local, diarize })
+          }
+          isUpdating={updating}
+          label={t("settings.advanced.deepgram.diarize.label")}
+          description={t("settings.advanced.deepgram.diarize.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        <SettingContainer
+          title={t("settings.advanced.deepgram.uploadCodec.title")}
+          description={t("settings.advanced.deepgram.uploadCodec.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={codecOptions}
+            selectedValue={local.upload_codec ?? "flac"}
+            onSelect={(value) =>
+              updateSetting("deepgram", {
+                ...local,
+                upload_codec: value as UploadCodec,
+              })
+            }
+            disabled={updating}
+          />
+        </SettingContainer>
+        <SettingContainer
+          title={t("settings.advanced.deepgram.network.title")}
+          description={t("settings.advanced.deepgram.network.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          layout="stacked"
+        >
+          <div className="flex items-center space-x-2">
+            {NETWORK_FIELDS.map((field) => (
+              <label
+                key={field}
+                className="flex flex-1 items-center space-x-2 text-sm"
+              >
+                <span>{t(`settings.advanced.deepgram.network.${field}`)}</span>
+                <Input
+                  type="number"
+                  min={field === "max_retries" ? 0 : 1}
+                  className="w-20"
+                  value={local[field] ?? DEFAULT_CONFIG[field]}
+                  onChange={(e) =>
+                    setLocal({ ...local, [field]: Number(e.target.value) })
+                  }
+                  onBlur={save}
+                  variant="compact"
+                  disabled={updating}
+                />
+              </label>
+            ))}
+          </div>
+        </SettingContainer>
+      </>
+    );
+  },
+);
@@ -0,0 +1,302 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  commands,
+  type BindingEngine,
+  type BindingOutput,
+  type ShortcutBinding,
+} from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { useModelStore } from "../../stores/modelStore";
+import { LANGUAGES } from "../../lib/constants/languages";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { HandyShortcut } from "./HandyShortcut";
+
+const DICTATION_BINDING_PREFIX = "dictate_";
+const FOLLOW = "__follow__";
+
+interface DictationBindingsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const DictationBindingOptions: React.FC<{
+  binding: ShortcutBinding;
+  descriptionMode: "inline" | "tooltip";
+  grouped: boolean;
+}> = ({ binding, descriptionMode, grouped }) => {
+  const { t } = useTranslation();
+  const { settings, refreshSettings } = useSettings();
+  const { models } = useModelStore();
+  const [words, setWords] = useState("");
+  const [outputFile, setOutputFile] = useState("");
+  const [updating, setUpdating] = useState(false);
+  // Output chosen as a file before there's one to write to
+  const [pendingOutput, setPendingOutput] = useState<BindingOutput | null>(
+    null,
+  );
+  const output = pendingOutput ?? binding.output ?? "paste";
+
+  useEffect(
+    () => setWords(binding.custom_words?.join(", ") ?? ""),
+    [binding.custom_words],
+  );
+  useEffect(
+    () => setOutputFile(binding.output_file ?? ""),
+    [binding.output_file],
+  );
+
+  const languageOptions = [
+    { value: FOLLOW, label: t("settings.general.dictationBindings.follow") },
+    ...LANGUAGES.map((language) => ({
+      value: language.value,
+      label: language.label,
+    })),
+  ];
+  const modelOptions = [
+    { value: FOLLOW, label: t("settings.general.dictationBindings.follow") },
+    ...models
+      .filter((model) => model.is_downloaded)
+      .map((model) => ({ value: model.id, label: model.name })),
+  ];
+
+  const engineOptions = [
+    { value: FOLLOW, label: t("settings.general.dictationBindings.follow") },
+    ...(["local", "wyoming", "deepgram"] as BindingEngine[]).map((engine) => ({
+      value: engine,
+      label: t(`settings.general.dictationBindings.engine.${engine}`),
+    })),
+  ];
+  const promptOptions = [
+    { value: FOLLOW, label: t("settings.general.dictationBindings.follow") },
+    ...(settings?.post_process_prompts ?? []).map((prompt) => ({
+      value: prompt.id,
+      label: prompt.name,
+    })),
+  ];
+  const outputOptions = (
+    ["paste", "clipboard", "file"] as BindingOutput[]
+  ).map((output) => ({
+    value: output,
+    label: t(`settings.general.dictationBindings.output.${output}`),
+  }));
+
+  const savePipeline = async (
+    changes: Partial<
+      Pick<ShortcutBinding, "engine" | "prompt_id" | "output" | "output_file">
+    >,
+  ) => {
+    const next = { ...binding, ...changes };
+    const output = next.output ?? "paste";
+    // Wait for a file before switching to writing to one
+    if (output === "file" && !next.output_file?.trim()) {
+      return;
+    }
+    setUpdating(true);
+    const result = await commands.changeBindingPipeline(
+      binding.id,
+      next.engine ?? null,
+      next.prompt_id ?? null,
+      output,
+      next.output_file ?? null,
+    );
+    if (result.status === "error") {
+      toast.error(result.error);
+    }
+    await refreshSettings();
+    setUpdating(false);
+  };
+
+  const selectOutput = (value: string) => {
+    const next = value as BindingOutput;
+    setPendingOutput(next === "file" && !binding.output_file ? next : null);
+    savePipeline({ output: next });
+  };
+
+  const saveOutputFile = () => {
+    setPendingOutput(null);
+    savePipeline({ output, output_file: outputFile.trim() || null });
+  };
+
+  const save = async (
+    changes: Partial<
+      Pick<ShortcutBinding, "language" | "model" | "custom_words">
+    >,
+  ) => {
+    const next = { ...binding, ...changes };
+    setUpdating(true);
+    const result = await commands.changeBindingDictation(
+      binding.id,
+      next.language ?? null,
+      next.model ?? null,
+      next.custom_words ?? null,
+    );
+    if (result.status === "error") {
+      toast.error(result.error);
+    }
+    await refreshSettings();
+    setUpdating(false);
+  };
+
+  const saveWords = () => {
+    const list = words
+      .split(",")
+      .map((word) => word.trim())
+      .filter(Boolean);
+    save({ custom_words: list.length > 0 ? list : null });
+  };
+
+  const remove = async () => {
+    setUpdating(true);
+    const result = await commands.removeDictationBinding(binding.id);
+    if (result.status === "error") {
+      toast.error(result.error);
+    }
+    await refreshSettings();
+    setUpdating(false);
+  };
+
+  return (
+    <SettingContainer
+      title={t("settings.general.dictationBindings.options")}
+      description={t("settings.general.dictationBindings.optionsDescription")}
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+      layout="stacked"
+    >
+      <div className="space-y-2">
+        <div className="flex items-center space-x-2">
+          <Dropdown
+            options={languageOptions}
+            selectedValue={binding.language ?? FOLLOW}
+            onSelect={(value) =>
+              save({ language: value === FOLLOW ? null : value })
+            }
+            disabled={updating}
+          />
+          <Dropdown
+            options={modelOptions}
+            selectedValue={binding.model ?? FOLLOW}
+            onSelect={(value) =>
+              save({ model: value === FOLLOW ? null : value })
+            }
+            disabled={updating}
+          />
+        </div>
+        <div className="flex items-center space-x-2">
+          <Dropdown
+            options={engineOptions}
+            selectedValue={binding.engine ?? FOLLOW}
+            onSelect={(value) =>
+              savePipeline({
+                engine: value === FOLLOW ? null : (value as BindingEngine),
+              })
+            }
+            disabled={updating}
+          />
+          <Dropdown
+            options={promptOptions}
+            selectedValue={binding.prompt_id ?? FOLLOW}
+            onSelect={(value) =>
+              savePipeline({ prompt_id: value === FOLLOW ? null : value })
+            }
+            disabled={updating || binding.skip_post_process}
+          />
+          <Dropdown
+            options={outputOptions}
+            selectedValue={output}
+            onSelect={selectOutput}
+            disabled={updating}
+          />
+        </div>
+        {output === "file" && (
+          <Input
+            type="text"
+            className="w-full"
+            value={outputFile}
+            onChange={(e) => setOutputFile(e.target.value)}
+            onBlur={saveOutputFile}
+            placeholder={t("settings.general.dictationBindings.outputFile")}
+            variant="compact"
+            disabled={updating}
+          />
+        )}
+        <div className="flex items-center space-x-2">
+          <Input
+            type="text"
+            className="flex-1"
+            value={words}
+            onChange={(e) => setWords(e.target.value)}
+            onBlur={saveWords}
+            placeholder={t("settings.general.dictationBindings.customWords")}
+            variant="compact"
+            disabled={updating}
+          />
+          <Button
+            variant="ghost"
+            size="sm"
+            onClick={remove}
+            disabled={updating}
+          >
+            {t("settings.general.dictationBindings.remove")}
+          </Button>
+        </div>
+      </div>
+    </SettingContainer>
+  );
+};
+
+/** Extra dictation shortcuts, each with its own language and model. */
+export const DictationBindings: React.FC<DictationBindingsProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { settings, refreshSettings } = useSettings();
+    const [adding, setAdding] = useState(false);
+
+    const bindings = Object.values(settings?.bindings ?? {})
+      .filter(
+        (binding): binding is ShortcutBinding =>
+          !!binding && binding.id.startsWith(DICTATION_BINDING_PREFIX),
+      )
+      .sort((a, b) => a.id.localeCompare(b.id));
+
+    const add = async () => {
+      setAdding(true);
+      const result = await commands.addDictationBinding();
+      if (result.status === "error") {
+        toast.error(result.error);
+      }
+      await refreshSettings();
+      setAdding(false);
+    };
+
+    return (
+      <>
+        {bindings.map((binding) => (
+          <React.Fragment key={binding.id}>
+            <HandyShortcut shortcutId={binding.id} grouped={grouped} />
+            <DictationBindingOptions
+              binding={binding}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            />
+          </React.Fragment>
+        ))}
+        <SettingContainer
+          title={t("settings.general.dictationBindings.title")}
+          description={t("settings.general.dictationBindings.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Button variant="secondary" size="sm" onClick={add} disabled={adding}>
+            {t("settings.general.dictationBindings.add")}
+          </Button>
+        </SettingContainer>
+      </>
+    );
+  },
+);
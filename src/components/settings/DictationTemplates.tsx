@@ -0,0 +1,100 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface DictationTemplatesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const DictationTemplates: React.FC<DictationTemplatesProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const [trigger, setTrigger] = useState("");
+    const [body, setBody] = useState("");
+
+    const templates = getSetting("dictation_templates") ?? [];
+    const updating = isUpdating("dictation_templates");
+
+    const addTemplate = () => {
+      if (!trigger.trim() || !body) return;
+      updateSetting("dictation_templates", [
+        ...templates.filter(
+          (template) =>
+            template.trigger.toLowerCase() !== trigger.trim().toLowerCase(),
+        ),
+        { trigger: trigger.trim(), body },
+      ]);
+      setTrigger("");
+      setBody("");
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.dictationTemplates.title")}
+        description={t("settings.advanced.dictationTemplates.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          {templates.map((template, index) => (
+            <div key={template.trigger} className="flex items-center space-x-2">
+              <span className="flex-1 text-sm truncate">
+                {t("settings.advanced.dictationTemplates.template", {
+                  trigger: template.trigger,
+                  body: template.body,
+                })}
+              </span>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() =>
+                  updateSetting(
+                    "dictation_templates",
+                    templates.filter((_, i) => i !== index),
+                  )
+                }
+                disabled={updating}
+              >
+                {t("settings.advanced.dictationTemplates.remove")}
+              </Button>
+            </div>
+          ))}
+          <div className="flex items-center space-x-2">
+            <Input
+              type="text"
+              className="w-40"
+              value={trigger}
+              onChange={(e) => setTrigger(e.target.value)}
+              placeholder={t("settings.advanced.dictationTemplates.trigger")}
+              variant="compact"
+              disabled={updating}
+            />
+            <Input
+              type="text"
+              className="flex-1"
+              value={body}
+              onChange={(e) => setBody(e.target.value)}
+              onKeyDown={(e) => e.key === "Enter" && addTemplate()}
+              placeholder={t("settings.advanced.dictationTemplates.body")}
+              variant="compact"
+              disabled={updating}
+            />
+            <Button
+              variant="secondary"
+              size="sm"
+              onClick={addTemplate}
+              disabled={updating || !trigger.trim() || !body}
+            >
+              {t("settings.advanced.dictationTemplates.add")}
+            </Button>
+          </div>
+        </div>
+      </SettingContainer>
+    );
+  });
@@ -0,0 +1,73 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface FileTranscriptionCacheProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const FileTranscriptionCache: React.FC<FileTranscriptionCacheProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const limit = getSetting("file_cache_limit") ?? 500;
+    const [draft, setDraft] = useState(limit.toString());
+    const [count, setCount] = useState(0);
+    const [clearing, setClearing] = useState(false);
+
+    useEffect(() => setDraft(limit.toString()), [limit]);
+    useEffect(() => {
+      commands.getFileCacheCount().then(setCount);
+    }, [limit]);
+
+    const save = () => {
+      const value = parseInt(draft, 10);
+      if (!isNaN(value) && value >= 0) {
+        updateSetting("file_cache_limit", value);
+      } else {
+        setDraft(limit.toString());
+      }
+    };
+
+    const clear = async () => {
+      setClearing(true);
+      await commands.clearFileTranscriptionCache();
+      setCount(await commands.getFileCacheCount());
+      setClearing(false);
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.fileCache.title")}
+        description={t("settings.advanced.fileCache.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <div className="flex items-center space-x-2">
+          <Input
+            type="number"
+            className="w-24"
+            min={0}
+            value={draft}
+            onChange={(e) => setDraft(e.target.value)}
+            onBlur={save}
+            variant="compact"
+            disabled={isUpdating("file_cache_limit")}
+          />
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={clear}
+            disabled={clearing || count === 0}
+          >
+            {t("settings.advanced.fileCache.clear", { count })}
+          </Button>
+        </div>
+      </SettingContainer>
+    );
+  });
@@ -0,0 +1,104 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { useModelStore } from "../../stores/modelStore";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface FineTunesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/** Imports community fine-tunes of the installed Whisper models. */
+export const FineTunes: React.FC<FineTunesProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { models, loadModels } = useModelStore();
+    const [path, setPath] = useState("");
+    const [name, setName] = useState("");
+    const [baseModel, setBaseModel] = useState<string | null>(null);
+    const [importing, setImporting] = useState(false);
+
+    const baseModels = models
+      .filter(
+        (model) =>
+          model.engine_type === "Whisper" &&
+          model.is_downloaded &&
+          !model.base_model,
+      )
+      .map((model) => ({ value: model.id, label: model.name }));
+
+    const handleImport = async () => {
+      if (!baseModel) return;
+      setImporting(true);
+      const result = await commands.importFineTune(path, name, baseModel);
+      setImporting(false);
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      toast.success(
+        t("settings.advanced.fineTunes.imported", { name: result.data.name }),
+      );
+      setPath("");
+      setName("");
+      await loadModels();
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.fineTunes.title")}
+        description={t("settings.advanced.fineTunes.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          <div className="flex items-center gap-2">
+            <Input
+              type="text"
+              className="flex-1"
+              value={path}
+              onChange={(e) => setPath(e.target.value)}
+              placeholder="/path/to/ggml-medical-small.bin"
+              variant="compact"
+              disabled={importing}
+            />
+            <Dropdown
+              options={baseModels}
+              selectedValue={baseModel}
+              onSelect={setBaseModel}
+              placeholder={t("settings.advanced.fineTunes.baseModel")}
+              disabled={importing || baseModels.length === 0}
+            />
+          </div>
+          <div className="flex items-center gap-2">
+            <Input
+              type="text"
+              className="flex-1"
+              value={name}
+              onChange={(e) => setName(e.target.value)}
+              placeholder={t("settings.advanced.fineTunes.namePlaceholder")}
+              variant="compact"
+              disabled={importing}
+            />
+            <Button
+              variant="secondary"
+              size="sm"
+              onClick={handleImport}
+              disabled={
+                importing || !path.trim() || !name.trim() || !baseModel
+              }
+            >
+              {t("settings.advanced.fineTunes.import")}
+            </Button>
+          </div>
+        </div>
+      </SettingContainer>
+    );
+  },
+);
@@ -0,0 +1,30 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface HallucinationFilterProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const HallucinationFilter: React.FC<HallucinationFilterProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const hallucinationFilterEnabled =
+      getSetting("hallucination_filter") ?? true;
+
+    return (
+      <ToggleSwitch
+        checked={hallucinationFilterEnabled}
+        onChange={(enabled) => updateSetting("hallucination_filter", enabled)}
+        isUpdating={isUpdating("hallucination_filter")}
+        label={t("settings.advanced.hallucinationFilter.label")}
+        description={t("settings.advanced.hallucinationFilter.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
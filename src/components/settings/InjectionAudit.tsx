@@ -0,0 +1,109 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type InjectionRecord } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+interface InjectionAuditProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const InjectionAudit: React.FC<InjectionAuditProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const [lookupText, setLookupText] = useState("");
+    const [match, setMatch] = useState<InjectionRecord | null | undefined>();
+
+    const auditEnabled = getSetting("injection_audit") ?? false;
+    const markerEnabled = getSetting("injection_marker") ?? false;
+
+    const handleLookup = async () => {
+      const result = await commands.identifyInjectedText(lookupText);
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      setMatch(result.data);
+    };
+
+    const describeMatch = (record: InjectionRecord) =>
+      t("settings.advanced.injectionAudit.lookup.found", {
+        time: new Date(record.timestamp_ms).toLocaleString(),
+        app: record.target_app ?? t("settings.advanced.injectionAudit.unknown"),
+        window:
+          record.window_title ?? t("settings.advanced.injectionAudit.unknown"),
+        method: record.method,
+      });
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={auditEnabled}
+          onChange={(enabled) => updateSetting("injection_audit", enabled)}
+          isUpdating={isUpdating("injection_audit")}
+          label={t("settings.advanced.injectionAudit.log.label")}
+          description={t("settings.advanced.injectionAudit.log.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {auditEnabled && (
+          <ToggleSwitch
+            checked={markerEnabled}
+            onChange={(enabled) => updateSetting("injection_marker", enabled)}
+            isUpdating={isUpdating("injection_marker")}
+            label={t("settings.advanced.injectionAudit.marker.label")}
+            description={t(
+              "settings.advanced.injectionAudit.marker.description",
+            )}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          />
+        )}
+        <SettingContainer
+          title={t("settings.advanced.injectionAudit.lookup.title")}
+          description={t("settings.advanced.injectionAudit.lookup.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          layout="stacked"
+        >
+          <div className="flex items-center space-x-2">
+            <Input
+              type="text"
+              className="flex-1"
+              value={lookupText}
+              onChange={(e) => {
+                setLookupText(e.target.value);
+                setMatch(undefined);
+              }}
+              placeholder={t(
+                "settings.advanced.injectionAudit.lookup.placeholder",
+              )}
+              variant="compact"
+            />
+            <Button
+              variant="secondary"
+              size="sm"
+              onClick={handleLookup}
+              disabled={lookupText.trim() === ""}
+            >
+              {t("settings.advanced.injectionAudit.lookup.button")}
+            </Button>
+          </div>
+          {match !== undefined && (
+            <p className="mt-2 text-sm text-mid-gray">
+              {match
+                ? describeMatch(match)
+                : t("settings.advanced.injectionAudit.lookup.notFound")}
+            </p>
+          )}
+        </SettingContainer>
+      </>
+    );
+  },
+);
@@ -0,0 +1,30 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface InputWatchdogProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const InputWatchdog: React.FC<InputWatchdogProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const inputWatchdogEnabled = getSetting("input_watchdog") ?? true;
+
+    return (
+      <ToggleSwitch
+        checked={inputWatchdogEnabled}
+        onChange={(enabled) => updateSetting("input_watchdog", enabled)}
+        isUpdating={isUpdating("input_watchdog")}
+        label={t("settings.sound.inputWatchdog.label")}
+        description={t("settings.sound.inputWatchdog.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
@@ -0,0 +1,50 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+const LOCK_MINUTES = [0, 2, 5, 10, 30];
+
+interface LanguageLockProps {
+  descriptionMode?: "tooltip" | "inline";
+  grouped?: boolean;
+}
+
+export const LanguageLock: React.FC<LanguageLockProps> = ({
+  descriptionMode = "inline",
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+
+  const lockMinutes = getSetting("language_lock_minutes") ?? 0;
+
+  const options = LOCK_MINUTES.map((minutes) => ({
+    value: minutes.toString(),
+    label:
+      minutes === 0
+        ? t("settings.advanced.languageLock.options.off")
+        : t("settings.advanced.languageLock.options.minutes", {
+            count: minutes,
+          }),
+  }));
+
+  return (
+    <SettingContainer
+      title={t("settings.advanced.languageLock.title")}
+      description={t("settings.advanced.languageLock.description")}
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+    >
+      <Dropdown
+        options={options}
+        selectedValue={lockMinutes.toString()}
+        onSelect={(value) =>
+          updateSetting("language_lock_minutes", parseInt(value, 10))
+        }
+        disabled={isUpdating("language_lock_minutes")}
+      />
+    </SettingContainer>
+  );
+};
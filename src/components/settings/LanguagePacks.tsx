@@ -0,0 +1,94 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type LanguagePack } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { useModelStore } from "../../stores/modelStore";
+import { Button } from "../ui/Button";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface LanguagePacksProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const LanguagePacks: React.FC<LanguagePacksProps> = ({
+  descriptionMode = "tooltip",
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const { getSetting, refreshSettings } = useSettings();
+  const { getModelInfo, loadModels, loadCurrentModel } = useModelStore();
+  const [packs, setPacks] = useState<LanguagePack[]>([]);
+  const [installing, setInstalling] = useState<string | null>(null);
+
+  const selectedLanguage = getSetting("selected_language");
+  const selectedModel = getSetting("selected_model");
+
+  useEffect(() => {
+    commands.getLanguagePacks().then((result) => {
+      if (result.status === "ok") setPacks(result.data);
+    });
+  }, []);
+
+  const handleInstall = async (pack: LanguagePack) => {
+    setInstalling(pack.id);
+    try {
+      const result = await commands.installLanguagePack(pack.id);
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      await Promise.all([refreshSettings(), loadModels(), loadCurrentModel()]);
+      const packsResult = await commands.getLanguagePacks();
+      if (packsResult.status === "ok") setPacks(packsResult.data);
+    } finally {
+      setInstalling(null);
+    }
+  };
+
+  const describe = (pack: LanguagePack) =>
+    [
+      pack.model_ids.map((id) => getModelInfo(id)?.name ?? id).join(", "),
+      t("settings.general.languagePacks.size", { size: pack.size_mb }),
+    ].join(" · ");
+
+  return (
+    <>
+      {packs.map((pack) => {
+        const isActive =
+          pack.is_installed &&
+          pack.language === selectedLanguage &&
+          pack.model_ids[0] === selectedModel;
+        return (
+          <SettingContainer
+            key={pack.id}
+            title={pack.name}
+            description={describe(pack)}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          >
+            {isActive ? (
+              <span className="text-sm text-mid-gray">
+                {t("settings.general.languagePacks.active")}
+              </span>
+            ) : (
+              <Button
+                variant="secondary"
+                size="sm"
+                onClick={() => handleInstall(pack)}
+                disabled={installing !== null}
+              >
+                {installing === pack.id
+                  ? t("settings.general.languagePacks.installing")
+                  : pack.is_installed
+                    ? t("settings.general.languagePacks.use")
+                    : t("settings.general.languagePacks.install")}
+              </Button>
+            )}
+          </SettingContainer>
+        );
+      })}
+    </>
+  );
+};
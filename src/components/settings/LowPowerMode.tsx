@@ -0,0 +1,30 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface LowPowerModeProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const LowPowerMode: React.FC<LowPowerModeProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const lowPowerEnabled = getSetting("low_power_enabled") ?? false;
+
+    return (
+      <ToggleSwitch
+        checked={lowPowerEnabled}
+        onChange={(enabled) => updateSetting("low_power_enabled", enabled)}
+        isUpdating={isUpdating("low_power_enabled")}
+        label={t("settings.advanced.lowPower.enabled.label")}
+        description={t("settings.advanced.lowPower.enabled.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
@@ -0,0 +1,102 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import type { ModelUnloadTimeout } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { useModelStore } from "../../stores/modelStore";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+// Empty value means "same as on AC power"
+const UNCHANGED = "";
+
+const TIMEOUTS: ModelUnloadTimeout[] = [
+  "never",
+  "immediately",
+  "min2",
+  "min5",
+  "min10",
+  "min15",
+  "hour1",
+];
+
+interface LowPowerRulesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const LowPowerRules: React.FC<LowPowerRulesProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const { models } = useModelStore();
+
+    const modelOptions = [
+      { value: UNCHANGED, label: t("settings.advanced.lowPower.unchanged") },
+      ...models
+        .filter((model) => model.is_downloaded)
+        .map((model) => ({ value: model.id, label: model.name })),
+    ];
+
+    const timeoutOptions = [
+      { value: UNCHANGED, label: t("settings.advanced.lowPower.unchanged") },
+      ...TIMEOUTS.map((timeout) => ({
+        value: timeout,
+        label: t(`settings.advanced.modelUnload.options.${timeout}`),
+      })),
+    ];
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.lowPower.model.title")}
+          description={t("settings.advanced.lowPower.model.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={modelOptions}
+            selectedValue={getSetting("low_power_model") ?? UNCHANGED}
+            onSelect={(value) =>
+              updateSetting("low_power_model", value || null)
+            }
+            disabled={isUpdating("low_power_model")}
+          />
+        </SettingContainer>
+        <ToggleSwitch
+          checked={getSetting("low_power_pause_always_on_microphone") ?? true}
+          onChange={(enabled) =>
+            updateSetting("low_power_pause_always_on_microphone", enabled)
+          }
+          isUpdating={isUpdating("low_power_pause_always_on_microphone")}
+          label={t("settings.advanced.lowPower.pauseMicrophone.label")}
+          description={t(
+            "settings.advanced.lowPower.pauseMicrophone.description",
+          )}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        <SettingContainer
+          title={t("settings.advanced.lowPower.unloadTimeout.title")}
+          description={t("settings.advanced.lowPower.unloadTimeout.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={timeoutOptions}
+            selectedValue={
+              getSetting("low_power_model_unload_timeout") ?? UNCHANGED
+            }
+            onSelect={(value) =>
+              updateSetting(
+                "low_power_model_unload_timeout",
+                (value || null) as ModelUnloadTimeout | null,
+              )
+            }
+            disabled={isUpdating("low_power_model_unload_timeout")}
+          />
+        </SettingContainer>
+      </>
+    );
+  },
+);
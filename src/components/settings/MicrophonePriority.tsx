@@ -0,0 +1,103 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface MicrophonePriorityProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const MicrophonePriority: React.FC<MicrophonePriorityProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, audioDevices } =
+      useSettings();
+
+    const priority = getSetting("microphone_priority") ?? [];
+    const connected = audioDevices.map((device) => device.name);
+    const updating = isUpdating("microphone_priority");
+
+    // Disconnected microphones keep their rank for when they come back
+    const addOptions = connected
+      .filter((name) => name !== "Default" && !priority.includes(name))
+      .map((name) => ({ value: name, label: name }));
+
+    const move = (index: number, offset: number) => {
+      const next = [...priority];
+      const [device] = next.splice(index, 1);
+      next.splice(index + offset, 0, device);
+      updateSetting("microphone_priority", next);
+    };
+
+    const remove = (index: number) =>
+      updateSetting(
+        "microphone_priority",
+        priority.filter((_, i) => i !== index),
+      );
+
+    return (
+      <SettingContainer
+        title={t("settings.sound.microphonePriority.title")}
+        description={t("settings.sound.microphonePriority.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          {priority.map((device, index) => (
+            <div key={device} className="flex items-center space-x-2">
+              <span className="w-5 text-sm text-mid-gray">{index + 1}.</span>
+              <span className="flex-1 text-sm truncate">
+                {device}
+                {!connected.includes(device) && (
+                  <span className="ml-2 text-mid-gray">
+                    {t("settings.sound.microphonePriority.disconnected")}
+                  </span>
+                )}
+              </span>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => move(index, -1)}
+                disabled={updating || index === 0}
+                aria-label={t("settings.sound.microphonePriority.moveUp")}
+              >
+                ↑
+              </Button>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => move(index, 1)}
+                disabled={updating || index === priority.length - 1}
+                aria-label={t("settings.sound.microphonePriority.moveDown")}
+              >
+                ↓
+              </Button>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => remove(index)}
+                disabled={updating}
+              >
+                {t("settings.sound.microphonePriority.remove")}
+              </Button>
+            </div>
+          ))}
+          {addOptions.length > 0 && (
+            <Dropdown
+              options={addOptions}
+              selectedValue={null}
+              onSelect={(device) =>
+                updateSetting("microphone_priority", [...priority, device])
+              }
+              placeholder={t("settings.sound.microphonePriority.add")}
+              disabled={updating}
+            />
+          )}
+        </div>
+      </SettingContainer>
+    );
+  });
@@ -36,9 +36,13 @@ export const MicrophoneSelector: React.FC<MicrophoneSelectorProps> = React.memo(
       await resetSetting("selected_microphone");
     };
 
+    // System audio is listed under a fixed name, shown translated
     const microphoneOptions = audioDevices.map((device) => ({
       value: device.name,
-      label: device.name,
+      label:
+        device.index === "system"
+          ? t("settings.sound.microphone.systemAudio")
+          : device.name,
     }));
 
     return (
@@ -0,0 +1,79 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+const CONCURRENT_DOWNLOADS = [1, 2, 3, 4];
+
+interface ModelDownloadsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const ModelDownloads: React.FC<ModelDownloadsProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const maxDownloads = getSetting("max_concurrent_downloads") ?? 2;
+    const limitKbps = getSetting("download_limit_kbps");
+
+    const handleLimitChange = (event: React.ChangeEvent<HTMLInputElement>) => {
+      const kbps = parseInt(event.target.value, 10);
+      updateSetting(
+        "download_limit_kbps",
+        !isNaN(kbps) && kbps > 0 ? kbps : null,
+      );
+    };
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.modelDownloads.concurrent.title")}
+          description={t(
+            "settings.advanced.modelDownloads.concurrent.description",
+          )}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={CONCURRENT_DOWNLOADS.map((count) => ({
+              value: String(count),
+              label: String(count),
+            }))}
+            selectedValue={String(maxDownloads)}
+            onSelect={(value) =>
+              updateSetting("max_concurrent_downloads", parseInt(value, 10))
+            }
+            disabled={isUpdating("max_concurrent_downloads")}
+          />
+        </SettingContainer>
+        <SettingContainer
+          title={t("settings.advanced.modelDownloads.limit.title")}
+          description={t("settings.advanced.modelDownloads.limit.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          layout="horizontal"
+        >
+          <div className="flex items-center space-x-2">
+            <Input
+              type="number"
+              min="0"
+              step="100"
+              value={limitKbps ?? ""}
+              placeholder="0"
+              onChange={handleLimitChange}
+              disabled={isUpdating("download_limit_kbps")}
+              className="w-24"
+            />
+            <span className="text-sm text-text">
+              {t("settings.advanced.modelDownloads.limit.unit")}
+            </span>
+          </div>
+        </SettingContainer>
+      </>
+    );
+  },
+);
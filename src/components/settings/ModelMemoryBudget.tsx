@@ -0,0 +1,77 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands, type MemoryUsage } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+const NO_LIMIT = "none";
+const BUDGETS_GB = [1, 2, 4, 8, 16];
+
+interface ModelMemoryBudgetProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/** The most memory the speech model may take, and what's in use now. */
+export const ModelMemoryBudget: React.FC<ModelMemoryBudgetProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const [usage, setUsage] = useState<MemoryUsage | null>(null);
+
+    const budget = getSetting("model_memory_budget_mb") ?? null;
+
+    useEffect(() => {
+      commands.getMemoryUsage().then(setUsage);
+    }, [budget]);
+
+    const options = [
+      {
+        value: NO_LIMIT,
+        label: t("settings.advanced.memoryBudget.noLimit"),
+      },
+      ...BUDGETS_GB.map((gb) => ({
+        value: String(gb * 1024),
+        label: t("settings.advanced.memoryBudget.gigabytes", { count: gb }),
+      })),
+    ];
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.memoryBudget.title")}
+        description={t("settings.advanced.memoryBudget.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <div className="flex flex-col items-end space-y-1">
+          <Dropdown
+            options={options}
+            selectedValue={budget === null ? NO_LIMIT : String(budget)}
+            onSelect={(value) =>
+              updateSetting(
+                "model_memory_budget_mb",
+                value === NO_LIMIT ? null : Number(value),
+              )
+            }
+            disabled={isUpdating("model_memory_budget_mb")}
+          />
+          {usage && (
+            <span className="text-xs text-mid-gray">
+              {usage.vram_mb !== null
+                ? t("settings.advanced.memoryBudget.usageWithVram", {
+                    ram: usage.process_ram_mb,
+                    vram: usage.vram_mb,
+                    available: usage.system_available_mb,
+                  })
+                : t("settings.advanced.memoryBudget.usage", {
+                    ram: usage.process_ram_mb,
+                    available: usage.system_available_mb,
+                  })}
+            </span>
+          )}
+        </div>
+      </SettingContainer>
+    );
+  },
+);
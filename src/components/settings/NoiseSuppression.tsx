@@ -0,0 +1,61 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Slider } from "../ui/Slider";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface NoiseSuppressionProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/**
+ * Denoising the live input, with how much of the original is mixed back.
+ * The strength is shared with the RNNoise preprocessing node, so it's also
+ * shown while that node is in the chain.
+ */
+export const NoiseSuppression: React.FC<NoiseSuppressionProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("noise_suppression") ?? false;
+    const strength = getSetting("noise_suppression_strength") ?? 1;
+    const chain = getSetting("preprocessing") ?? [];
+    const inChain = chain.some(
+      (step) => step.node === "rnnoise" && !step.bypass,
+    );
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(value) => updateSetting("noise_suppression", value)}
+          isUpdating={isUpdating("noise_suppression")}
+          label={t("settings.sound.noiseSuppression.label")}
+          description={t("settings.sound.noiseSuppression.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {(enabled || inChain) && (
+          <Slider
+            value={strength}
+            onChange={(value) =>
+              updateSetting("noise_suppression_strength", value)
+            }
+            min={0.1}
+            max={1}
+            step={0.05}
+            label={t("settings.sound.noiseSuppression.strength.title")}
+            description={t(
+              "settings.sound.noiseSuppression.strength.description",
+            )}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+            formatValue={(value) => `${Math.round(value * 100)}%`}
+          />
+        )}
+      </>
+    );
+  },
+);
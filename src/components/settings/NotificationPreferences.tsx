@@ -0,0 +1,83 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import type {
+  NotificationChannels,
+  NotificationPreferences as Preferences,
+} from "@/bindings";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+const EVENTS = [
+  ["errors", "errors"],
+  ["transcribed", "transcribed"],
+  ["fallback", "fallback"],
+  ["model_unloaded", "modelUnloaded"],
+] as const;
+
+const CHANNELS = ["sound", "toast", "overlay"] as const;
+
+interface NotificationPreferencesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/** Sound, toast and overlay toggles for each kind of notification. */
+export const NotificationPreferences: React.FC<NotificationPreferencesProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const preferences = getSetting("notification_preferences");
+    if (!preferences) {
+      return null;
+    }
+    const updating = isUpdating("notification_preferences");
+
+    const toggle = (
+      event: keyof Preferences,
+      channel: keyof NotificationChannels,
+    ) => {
+      const next: Preferences = { ...preferences };
+      next[event] = {
+        ...preferences[event],
+        [channel]: !preferences[event][channel],
+      };
+      updateSetting("notification_preferences", next);
+    };
+
+    return (
+      <>
+        {EVENTS.map(([event, key]) => (
+          <SettingContainer
+            key={event}
+            title={t(`settings.notifications.events.${key}.label`)}
+            description={t(`settings.notifications.events.${key}.description`)}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          >
+            <div className="flex items-center gap-1">
+              {CHANNELS.map((channel) => {
+                const enabled = preferences[event][channel];
+                return (
+                  <button
+                    key={channel}
+                    type="button"
+                    aria-pressed={enabled}
+                    disabled={updating}
+                    onClick={() => toggle(event, channel)}
+                    className={`px-2 py-1 text-xs rounded border transition-colors cursor-pointer disabled:opacity-50 ${
+                      enabled
+                        ? "bg-logo-primary/20 border-logo-primary"
+                        : "bg-mid-gray/10 border-mid-gray/20 text-mid-gray"
+                    }`}
+                  >
+                    {t(`settings.notifications.channels.${channel}`)}
+                  </button>
+                );
+              })}
+            </div>
+          </SettingContainer>
+        ))}
+      </>
+    );
+  });
@@ -0,0 +1,95 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import type { OverlayPlacement as Placement } from "@/bindings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Slider } from "../ui/Slider";
+import { useSettings } from "../../hooks/useSettings";
+
+interface OverlayPlacementProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/** Which monitor the overlay shows on, where, and how big. */
+export const OverlayPlacement: React.FC<OverlayPlacementProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, refreshSettings } =
+      useSettings();
+
+    const hidden = getSetting("overlay_position") === "none";
+    const placement = (getSetting("overlay_placement") ||
+      "follow_cursor") as Placement;
+    const scale = getSetting("overlay_scale") ?? 1;
+
+    const placementOptions = [
+      {
+        value: "follow_cursor",
+        label: t("settings.advanced.overlayPlacement.options.followCursor"),
+      },
+      {
+        value: "follow_focused_window",
+        label: t(
+          "settings.advanced.overlayPlacement.options.followFocusedWindow",
+        ),
+      },
+      {
+        value: "remembered",
+        label: t("settings.advanced.overlayPlacement.options.remembered"),
+      },
+    ];
+
+    const reset = async () => {
+      const result = await commands.resetOverlayPositions();
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      await refreshSettings();
+    };
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.overlayPlacement.title")}
+          description={t("settings.advanced.overlayPlacement.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <div className="flex items-center space-x-2">
+            {placement === "remembered" && (
+              <Button variant="ghost" size="sm" onClick={reset}>
+                {t("settings.advanced.overlayPlacement.reset")}
+              </Button>
+            )}
+            <Dropdown
+              options={placementOptions}
+              selectedValue={placement}
+              onSelect={(value) =>
+                updateSetting("overlay_placement", value as Placement)
+              }
+              disabled={hidden || isUpdating("overlay_placement")}
+            />
+          </div>
+        </SettingContainer>
+        <Slider
+          value={scale}
+          onChange={(value) => updateSetting("overlay_scale", value)}
+          min={0.75}
+          max={2}
+          step={0.25}
+          label={t("settings.advanced.overlayScale.title")}
+          description={t("settings.advanced.overlayScale.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          formatValue={(value) => `${Math.round(value * 100)}%`}
+          disabled={hidden}
+        />
+      </>
+    );
+  },
+);
@@ -37,7 +37,8 @@ export const PasteMethodSetting: React.FC<PasteMethodProps> = React.memo(
         },
       ];
 
-      // Add Shift+Insert and Ctrl+Shift+V options for Windows and Linux only
+      // Add Shift+Insert, Ctrl+Shift+V and the virtual keyboard for Windows
+      // and Linux only
       if (osType === "windows" || osType === "linux") {
         options.push(
           {
@@ -52,9 +53,21 @@ export const PasteMethodSetting: React.FC<PasteMethodProps> = React.memo(
               "settings.advanced.pasteMethod.options.clipboardShiftInsert",
             ),
           },
+          {
+            value: "virtual_keyboard",
+            label: t("settings.advanced.pasteMethod.options.virtualKeyboard"),
+          },
         );
       }
 
+      // AT-SPI only exists on Linux
+      if (osType === "linux") {
+        options.push({
+          value: "accessibility",
+          label: t("settings.advanced.pasteMethod.options.accessibility"),
+        });
+      }
+
       return options;
     };
 
@@ -0,0 +1,136 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { type as getOsType } from "@tauri-apps/plugin-os";
+import { toast } from "sonner";
+import {
+  commands,
+  type InjectionTestReport,
+  type PasteMethod,
+} from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { SettingContainer } from "../ui/SettingContainer";
+
+// Seconds to switch to the app being tested
+const TEST_DELAY_SECS = 5;
+
+const OPTION_KEYS: Record<PasteMethod, string> = {
+  ctrl_v: "clipboard",
+  direct: "direct",
+  none: "none",
+  ctrl_shift_v: "clipboardCtrlShiftV",
+  shift_insert: "clipboardShiftInsert",
+  virtual_keyboard: "virtualKeyboard",
+  accessibility: "accessibility",
+};
+
+interface PasteMethodTestProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const PasteMethodTest: React.FC<PasteMethodTestProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, refreshSettings } = useSettings();
+    const [testing, setTesting] = useState(false);
+    const [report, setReport] = useState<InjectionTestReport | null>(null);
+
+    const overrides = getSetting("app_paste_methods") ?? {};
+
+    const methodLabel = (method: PasteMethod) =>
+      t(`settings.advanced.pasteMethod.options.${OPTION_KEYS[method]}`, {
+        modifier: getOsType() === "macos" ? "Cmd" : "Ctrl",
+      });
+
+    const runTest = async () => {
+      setTesting(true);
+      setReport(null);
+      toast.info(
+        t("settings.advanced.pasteMethodTest.switchNow", {
+          seconds: TEST_DELAY_SECS,
+        }),
+      );
+      const result = await commands.testPasteMethods(TEST_DELAY_SECS);
+      setTesting(false);
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      setReport(result.data);
+      await refreshSettings();
+    };
+
+    const removeOverride = (app: string) => {
+      const next = { ...overrides };
+      delete next[app];
+      updateSetting("app_paste_methods", next);
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.pasteMethodTest.title")}
+        description={t("settings.advanced.pasteMethodTest.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <Button
+          variant="secondary"
+          size="sm"
+          onClick={runTest}
+          disabled={testing}
+        >
+          {testing
+            ? t("settings.advanced.pasteMethodTest.testing")
+            : t("settings.advanced.pasteMethodTest.button")}
+        </Button>
+        {report && (
+          <div className="mt-2 text-sm">
+            <p className="text-mid-gray">
+              {report.applied
+                ? t("settings.advanced.pasteMethodTest.applied", {
+                    app:
+                      report.app ??
+                      t("settings.advanced.pasteMethodTest.unknownApp"),
+                    method: methodLabel(report.applied),
+                  })
+                : t("settings.advanced.pasteMethodTest.noneWorked")}
+            </p>
+            <ul className="mt-1">
+              {report.results.map((result) => (
+                <li key={result.method}>
+                  {result.worked ? "✓" : "✗"} {methodLabel(result.method)}
+                  {result.error && (
+                    <span className="ml-2 text-mid-gray">{result.error}</span>
+                  )}
+                </li>
+              ))}
+            </ul>
+          </div>
+        )}
+        {Object.keys(overrides).length > 0 && (
+          <div className="mt-3 space-y-1">
+            <p className="text-sm font-medium">
+              {t("settings.advanced.pasteMethodTest.overrides")}
+            </p>
+            {Object.entries(overrides).map(([app, method]) => (
+              <div key={app} className="flex items-center space-x-2 text-sm">
+                <span className="flex-1 truncate">
+                  {app}: {method && methodLabel(method)}
+                </span>
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => removeOverride(app)}
+                >
+                  {t("settings.advanced.pasteMethodTest.remove")}
+                </Button>
+              </div>
+            ))}
+          </div>
+        )}
+      </SettingContainer>
+    );
+  },
+);
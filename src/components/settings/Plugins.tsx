@@ -0,0 +1,97 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands, type PluginInfo } from "@/bindings";
+import { Button } from "../ui/Button";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+interface PluginsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const Plugins: React.FC<PluginsProps> = ({
+  descriptionMode = "tooltip",
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const [plugins, setPlugins] = useState<PluginInfo[]>([]);
+  const [updating, setUpdating] = useState<string | null>(null);
+
+  useEffect(() => {
+    commands.getPlugins().then(setPlugins);
+  }, []);
+
+  const handleToggle = async (plugin: PluginInfo, enabled: boolean) => {
+    setUpdating(plugin.id);
+    const result = await commands.setPluginEnabled(plugin.id, enabled);
+    if (result.status === "ok") {
+      setPlugins((current) =>
+        current.map((p) => (p.id === plugin.id ? { ...p, enabled } : p)),
+      );
+    }
+    setUpdating(null);
+  };
+
+  const describe = (plugin: PluginInfo) => {
+    if (plugin.error) {
+      return t("settings.advanced.plugins.loadError", { error: plugin.error });
+    }
+    const capabilities =
+      plugin.capabilities.length > 0
+        ? plugin.capabilities
+            .map((c) => t(`settings.advanced.plugins.capabilities.${c}`))
+            .join(", ")
+        : t("settings.advanced.plugins.capabilities.none");
+    return [
+      plugin.description,
+      t(`settings.advanced.plugins.kinds.${plugin.kind}`),
+      t("settings.advanced.plugins.permissions", { capabilities }),
+    ]
+      .filter(Boolean)
+      .join(" · ");
+  };
+
+  return (
+    <>
+      <SettingContainer
+        title={t("settings.advanced.plugins.folder.title")}
+        description={t("settings.advanced.plugins.folder.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <div className="flex items-center gap-2">
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={() => commands.openPluginsDir()}
+          >
+            {t("settings.advanced.plugins.folder.open")}
+          </Button>
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={() => commands.reloadPlugins().then(setPlugins)}
+          >
+            {t("settings.advanced.plugins.folder.reload")}
+          </Button>
+        </div>
+      </SettingContainer>
+      {plugins.map((plugin) => (
+        <ToggleSwitch
+          key={plugin.id}
+          checked={plugin.enabled}
+          onChange={(enabled) => handleToggle(plugin, enabled)}
+          disabled={plugin.error !== null}
+          isUpdating={updating === plugin.id}
+          label={
+            plugin.version ? `${plugin.name} ${plugin.version}` : plugin.name
+          }
+          description={describe(plugin)}
+          descriptionMode="inline"
+          grouped={grouped}
+        />
+      ))}
+    </>
+  );
+};
@@ -0,0 +1,56 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface PostProcessTemperatureProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const PostProcessTemperature: React.FC<PostProcessTemperatureProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const temperature = getSetting("post_process_temperature") ?? null;
+    const [draft, setDraft] = useState(temperature?.toString() ?? "");
+
+    useEffect(() => setDraft(temperature?.toString() ?? ""), [temperature]);
+
+    const save = () => {
+      if (draft.trim() === "") {
+        updateSetting("post_process_temperature", null);
+        return;
+      }
+      const value = parseFloat(draft);
+      if (!isNaN(value) && value >= 0 && value <= 2) {
+        updateSetting("post_process_temperature", value);
+      } else {
+        setDraft(temperature?.toString() ?? "");
+      }
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.postProcessing.api.temperature.title")}
+        description={t("settings.postProcessing.api.temperature.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Input
+          type="number"
+          className="w-24"
+          min={0}
+          max={2}
+          step={0.1}
+          value={draft}
+          onChange={(e) => setDraft(e.target.value)}
+          onBlur={save}
+          placeholder={t("settings.postProcessing.api.temperature.default")}
+          variant="compact"
+          disabled={isUpdating("post_process_temperature")}
+        />
+      </SettingContainer>
+    );
+  });
@@ -0,0 +1,181 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { openUrl } from "@tauri-apps/plugin-opener";
+import { toast } from "sonner";
+import {
+  commands,
+  type DeviceAuthorization,
+  type OAuthConfig,
+} from "@/bindings";
+import { Button } from "../../ui/Button";
+import { Input } from "../../ui/Input";
+import { useSettings } from "../../../hooks/useSettings";
+
+interface OAuthSignInProps {
+  providerId: string;
+}
+
+const EMPTY_CONFIG: OAuthConfig = {
+  device_authorization_url: "",
+  token_url: "",
+  client_id: "",
+  scope: "",
+};
+
+const FIELDS: (keyof OAuthConfig)[] = [
+  "device_authorization_url",
+  "token_url",
+  "client_id",
+  "scope",
+];
+
+export const OAuthSignIn: React.FC<OAuthSignInProps> = ({ providerId }) => {
+  const { t } = useTranslation();
+  const { settings, refreshSettings } = useSettings();
+  const config = settings?.post_process_oauth?.[providerId];
+  const signedIn = !!settings?.post_process_oauth_tokens?.[providerId];
+
+  const [draft, setDraft] = useState<OAuthConfig>(config ?? EMPTY_CONFIG);
+  const [authorization, setAuthorization] =
+    useState<DeviceAuthorization | null>(null);
+  const [isBusy, setIsBusy] = useState(false);
+
+  useEffect(() => {
+    setDraft(config ?? EMPTY_CONFIG);
+  }, [config]);
+
+  const saveConfig = async (next: OAuthConfig | null) => {
+    const result = await commands.changePostProcessOauthSetting(
+      providerId,
+      next,
+    );
+    if (result.status === "error") {
+      toast.error(result.error);
+    }
+    await refreshSettings();
+  };
+
+  const handleSignIn = async () => {
+    setIsBusy(true);
+    try {
+      const started = await commands.startPostProcessSignIn(providerId);
+      if (started.status === "error") {
+        toast.error(started.error);
+        return;
+      }
+      setAuthorization(started.data);
+      await openUrl(
+        started.data.verification_uri_complete ??
+          started.data.verification_uri,
+      );
+      const finished = await commands.finishPostProcessSignIn(
+        providerId,
+        started.data,
+      );
+      if (finished.status === "error") {
+        toast.error(finished.error);
+      }
+      await refreshSettings();
+    } finally {
+      setAuthorization(null);
+      setIsBusy(false);
+    }
+  };
+
+  const handleSignOut = async () => {
+    const result = await commands.signOutPostProcess(providerId);
+    if (result.status === "error") {
+      toast.error(result.error);
+    }
+    await refreshSettings();
+  };
+
+  const isDirty = FIELDS.some(
+    (field) => (draft[field] ?? "").trim() !== (config?.[field] ?? ""),
+  );
+
+  return (
+    <div className="space-y-2">
+      {FIELDS.map((field) => (
+        <Input
+          key={field}
+          type="text"
+          className="w-full"
+          value={draft[field] ?? ""}
+          onChange={(e) => setDraft({ ...draft, [field]: e.target.value })}
+          placeholder={t(`settings.postProcessing.api.oauth.fields.${field}`)}
+          variant="compact"
+          disabled={isBusy}
+        />
+      ))}
+      <div className="flex items-center gap-2">
+        <Button
+          variant="secondary"
+          size="sm"
+          onClick={() =>
+            saveConfig({
+              device_authorization_url: draft.device_authorization_url.trim(),
+              token_url: draft.token_url.trim(),
+              client_id: draft.client_id.trim(),
+              scope: (draft.scope ?? "").trim(),
+            })
+          }
+          disabled={isBusy || !isDirty}
+        >
+          {t("settings.postProcessing.api.oauth.save")}
+        </Button>
+        {config && (
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={() => saveConfig(null)}
+            disabled={isBusy}
+          >
+            {t("settings.postProcessing.api.oauth.remove")}
+          </Button>
+        )}
+        {config &&
+          (signedIn ? (
+            <Button variant="secondary" size="sm" onClick={handleSignOut}>
+              {t("settings.postProcessing.api.oauth.signOut")}
+            </Button>
+          ) : (
+            <Button
+              variant="primary"
+              size="sm"
+              onClick={handleSignIn}
+              disabled={isBusy || isDirty}
+            >
+              {t("settings.postProcessing.api.oauth.signIn")}
+            </Button>
+          ))}
+        {config && (
+          <span className="text-sm text-mid-gray">
+            {signedIn
+              ? t("settings.postProcessing.api.oauth.signedIn")
+              : t("settings.postProcessing.api.oauth.signedOut")}
+          </span>
+        )}
+      </div>
+      {authorization && (
+        <div className="flex items-center gap-2 text-sm">
+          <span>
+            {t("settings.postProcessing.api.oauth.enterCode", {
+              url: authorization.verification_uri,
+            })}
+          </span>
+          <code className="px-2 py-1 rounded bg-mid-gray/10 font-mono select-all">
+            {authorization.user_code}
+          </code>
+          <Button
+            variant="ghost"
+            size="sm"
+            onClick={() => commands.cancelPostProcessSignIn()}
+          >
+            {t("settings.postProcessing.api.oauth.cancel")}
+          </Button>
+        </div>
+      )}
+    </div>
+  );
+};
@@ -18,6 +18,7 @@ type PostProcessProviderState = {
   apiKey: string;
   handleApiKeyChange: (value: string) => void;
   isApiKeyUpdating: boolean;
+  usesOAuth: boolean;
   model: string;
   handleModelChange: (value: string) => void;
   modelOptions: ModelOption[];
@@ -67,6 +68,7 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
   const baseUrl = selectedProvider?.base_url ?? "";
   const apiKey = settings?.post_process_api_keys?.[selectedProviderId] ?? "";
   const model = settings?.post_process_models?.[selectedProviderId] ?? "";
+  const usesOAuth = !!settings?.post_process_oauth?.[selectedProviderId];
 
   const providerOptions = useMemo<DropdownOption[]>(() => {
     return providers.map((provider) => ({
@@ -204,6 +206,7 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
     apiKey,
     handleApiKeyChange,
     isApiKeyUpdating,
+    usesOAuth,
     model,
     handleModelChange,
     modelOptions,
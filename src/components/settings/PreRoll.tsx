@@ -0,0 +1,42 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Slider } from "../ui/Slider";
+import { useSettings } from "../../hooks/useSettings";
+
+interface PreRollProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/** Audio kept from just before a recording, which needs the mic kept open. */
+export const PreRoll: React.FC<PreRollProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting } = useSettings();
+
+    const preRollMs = getSetting("pre_roll_ms") ?? 0;
+    const alwaysOn = getSetting("always_on_microphone") ?? false;
+
+    return (
+      <Slider
+        value={preRollMs}
+        onChange={(value) => updateSetting("pre_roll_ms", value)}
+        min={0}
+        max={2000}
+        step={250}
+        label={t("settings.debug.preRoll.title")}
+        description={t("settings.debug.preRoll.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        formatValue={(value) =>
+          value === 0
+            ? t("settings.debug.preRoll.off")
+            : t("settings.debug.preRoll.seconds", {
+                seconds: (value / 1000).toFixed(2),
+              })
+        }
+        disabled={!alwaysOn}
+      />
+    );
+  },
+);
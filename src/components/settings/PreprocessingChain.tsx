@@ -0,0 +1,126 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import type { PreprocessNode, PreprocessStep } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+const NODES: PreprocessNode[] = [
+  "high_pass",
+  "denoise",
+  "rnnoise",
+  "auto_gain",
+  "trim",
+];
+
+interface PreprocessingChainProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const PreprocessingChain: React.FC<PreprocessingChainProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const chain = getSetting("preprocessing") ?? [];
+    const updating = isUpdating("preprocessing");
+
+    const save = (next: PreprocessStep[]) =>
+      updateSetting("preprocessing", next);
+
+    const addOptions = NODES.filter(
+      (node) => !chain.some((step) => step.node === node),
+    ).map((node) => ({
+      value: node,
+      label: t(`settings.sound.preprocessing.nodes.${node}`),
+    }));
+
+    const move = (index: number, offset: number) => {
+      const next = [...chain];
+      const [step] = next.splice(index, 1);
+      next.splice(index + offset, 0, step);
+      save(next);
+    };
+
+    const toggleBypass = (index: number) =>
+      save(
+        chain.map((step, i) =>
+          i === index ? { ...step, bypass: !step.bypass } : step,
+        ),
+      );
+
+    return (
+      <SettingContainer
+        title={t("settings.sound.preprocessing.title")}
+        description={t("settings.sound.preprocessing.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          {chain.map((step, index) => (
+            <div key={step.node} className="flex items-center space-x-2">
+              <span className="w-5 text-sm text-mid-gray">{index + 1}.</span>
+              <span
+                className={`flex-1 text-sm truncate ${step.bypass ? "text-mid-gray line-through" : ""}`}
+              >
+                {t(`settings.sound.preprocessing.nodes.${step.node}`)}
+              </span>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => toggleBypass(index)}
+                disabled={updating}
+              >
+                {step.bypass
+                  ? t("settings.sound.preprocessing.enable")
+                  : t("settings.sound.preprocessing.bypass")}
+              </Button>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => move(index, -1)}
+                disabled={updating || index === 0}
+                aria-label={t("settings.sound.preprocessing.moveUp")}
+              >
+                ↑
+              </Button>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => move(index, 1)}
+                disabled={updating || index === chain.length - 1}
+                aria-label={t("settings.sound.preprocessing.moveDown")}
+              >
+                ↓
+              </Button>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => save(chain.filter((_, i) => i !== index))}
+                disabled={updating}
+              >
+                {t("settings.sound.preprocessing.remove")}
+              </Button>
+            </div>
+          ))}
+          {addOptions.length > 0 && (
+            <Dropdown
+              options={addOptions}
+              selectedValue={null}
+              onSelect={(node) =>
+                save([
+                  ...chain,
+                  { node: node as PreprocessNode, bypass: false },
+                ])
+              }
+              placeholder={t("settings.sound.preprocessing.add")}
+              disabled={updating}
+            />
+          )}
+        </div>
+      </SettingContainer>
+    );
+  });
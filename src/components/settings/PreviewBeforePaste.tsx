@@ -0,0 +1,65 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+interface PreviewBeforePasteProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const PreviewBeforePaste: React.FC<PreviewBeforePasteProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("preview_before_paste") ?? false;
+    const apps = (getSetting("preview_apps") ?? []).join(", ");
+    const [localApps, setLocalApps] = useState(apps);
+
+    useEffect(() => setLocalApps(apps), [apps]);
+
+    const saveApps = () =>
+      updateSetting(
+        "preview_apps",
+        localApps
+          .split(",")
+          .map((app) => app.trim())
+          .filter((app) => app !== ""),
+      );
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(value) => updateSetting("preview_before_paste", value)}
+          isUpdating={isUpdating("preview_before_paste")}
+          label={t("settings.advanced.preview.label")}
+          description={t("settings.advanced.preview.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {enabled && (
+          <SettingContainer
+            title={t("settings.advanced.preview.apps.title")}
+            description={t("settings.advanced.preview.apps.description")}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          >
+            <Input
+              type="text"
+              className="min-w-[240px]"
+              value={localApps}
+              onChange={(e) => setLocalApps(e.target.value)}
+              onBlur={saveApps}
+              placeholder={t("settings.advanced.preview.apps.placeholder")}
+              variant="compact"
+              disabled={isUpdating("preview_apps")}
+            />
+          </SettingContainer>
+        )}
+      </>
+    );
+  });
@@ -0,0 +1,101 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type ModelInfo } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+interface PunctuationRestorationProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const PunctuationRestoration: React.FC<PunctuationRestorationProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const [model, setModel] = useState<ModelInfo | null>(null);
+    const [path, setPath] = useState("");
+    const [importing, setImporting] = useState(false);
+
+    const enabled = getSetting("punctuation_restoration") ?? false;
+
+    const loadModel = async () => {
+      const result = await commands.getPunctuationModel();
+      if (result.status === "ok") {
+        setModel(result.data);
+      }
+    };
+
+    useEffect(() => {
+      loadModel();
+    }, []);
+
+    if (!model) {
+      return null;
+    }
+
+    if (model.is_downloaded) {
+      return (
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(value) => updateSetting("punctuation_restoration", value)}
+          isUpdating={isUpdating("punctuation_restoration")}
+          label={t("settings.advanced.punctuationRestoration.label")}
+          description={t(
+            "settings.advanced.punctuationRestoration.description",
+          )}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+      );
+    }
+
+    const handleImport = async () => {
+      setImporting(true);
+      const result = await commands.importPunctuationModel(path);
+      setImporting(false);
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      toast.success(t("settings.advanced.punctuationRestoration.imported"));
+      setPath("");
+      setModel(result.data);
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.punctuationRestoration.label")}
+        description={t(
+          "settings.advanced.punctuationRestoration.importDescription",
+        )}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="flex items-center gap-2">
+          <Input
+            type="text"
+            className="flex-1"
+            value={path}
+            onChange={(e) => setPath(e.target.value)}
+            placeholder="/path/to/punctuation-model"
+            variant="compact"
+            disabled={importing}
+          />
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={handleImport}
+            disabled={importing || !path.trim()}
+          >
+            {t("settings.advanced.punctuationRestoration.import")}
+          </Button>
+        </div>
+      </SettingContainer>
+    );
+  });
@@ -0,0 +1,64 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import type { AppSettings } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface PunctuationStyleProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+type StyleKey =
+  | "oxford_comma"
+  | "dash_style"
+  | "quote_style"
+  | "ellipsis_style";
+
+const STYLES: { key: StyleKey; name: string; values: string[] }[] = [
+  { key: "oxford_comma", name: "oxfordComma", values: ["add", "remove"] },
+  { key: "dash_style", name: "dash", values: ["em_dash", "spaced_en_dash"] },
+  { key: "quote_style", name: "quotes", values: ["curly", "straight"] },
+  { key: "ellipsis_style", name: "ellipsis", values: ["character", "dots"] },
+];
+
+const toCamelCase = (value: string) =>
+  value.replace(/_(\w)/g, (_, letter: string) => letter.toUpperCase());
+
+export const PunctuationStyle: React.FC<PunctuationStyleProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    return (
+      <>
+        {STYLES.map(({ key, name, values }) => {
+          const prefix = `settings.advanced.punctuationStyle.${name}`;
+          const options = ["keep", ...values].map((value) => ({
+            value,
+            label: t(`${prefix}.${toCamelCase(value)}`),
+          }));
+          return (
+            <SettingContainer
+              key={key}
+              title={t(`${prefix}.title`)}
+              description={t(`${prefix}.description`)}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            >
+              <Dropdown
+                options={options}
+                selectedValue={getSetting(key) ?? "keep"}
+                onSelect={(value) =>
+                  updateSetting(key, value as AppSettings[StyleKey])
+                }
+                disabled={isUpdating(key)}
+              />
+            </SettingContainer>
+          );
+        })}
+      </>
+    );
+  },
+);
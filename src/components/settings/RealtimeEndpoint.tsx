@@ -0,0 +1,77 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import type { RealtimeEndpoint as Endpoint } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+const DEFAULT_ENDPOINT: Endpoint = {
+  url: "wss://api.openai.com/v1/realtime?intent=transcription",
+  model: "gpt-4o-mini-transcribe",
+  api_key: "",
+};
+
+interface RealtimeEndpointProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const RealtimeEndpoint: React.FC<RealtimeEndpointProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const endpoint = getSetting("realtime_endpoint");
+    const [local, setLocal] = useState<Endpoint>(endpoint ?? DEFAULT_ENDPOINT);
+    const updating = isUpdating("realtime_endpoint");
+
+    useEffect(() => setLocal(endpoint ?? DEFAULT_ENDPOINT), [endpoint]);
+
+    const save = () => updateSetting("realtime_endpoint", local);
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.realtime.endpoint.title")}
+        description={t("settings.advanced.realtime.endpoint.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          <Input
+            type="text"
+            className="w-full font-mono"
+            value={local.url}
+            onChange={(e) => setLocal({ ...local, url: e.target.value })}
+            onBlur={save}
+            placeholder={DEFAULT_ENDPOINT.url}
+            variant="compact"
+            disabled={updating}
+          />
+          <div className="flex items-center space-x-2">
+            <Input
+              type="text"
+              className="flex-1"
+              value={local.model}
+              onChange={(e) => setLocal({ ...local, model: e.target.value })}
+              onBlur={save}
+              placeholder={t("settings.advanced.realtime.endpoint.model")}
+              variant="compact"
+              disabled={updating}
+            />
+            <Input
+              type="password"
+              className="flex-1"
+              value={local.api_key}
+              onChange={(e) => setLocal({ ...local, api_key: e.target.value })}
+              onBlur={save}
+              placeholder={t("settings.advanced.realtime.endpoint.apiKey")}
+              variant="compact"
+              disabled={updating}
+            />
+          </div>
+        </div>
+      </SettingContainer>
+    );
+  },
+);
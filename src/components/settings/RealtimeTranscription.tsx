@@ -0,0 +1,29 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface RealtimeTranscriptionProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const RealtimeTranscription: React.FC<RealtimeTranscriptionProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    return (
+      <ToggleSwitch
+        checked={getSetting("realtime_transcription") ?? false}
+        onChange={(enabled) =>
+          updateSetting("realtime_transcription", enabled)
+        }
+        isUpdating={isUpdating("realtime_transcription")}
+        label={t("settings.advanced.realtime.enabled.label")}
+        description={t("settings.advanced.realtime.enabled.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
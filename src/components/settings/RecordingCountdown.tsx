@@ -0,0 +1,71 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+// Shortcuts that can count down before they start recording
+const COUNTDOWN_BINDINGS = ["transcribe", "translate"];
+const COUNTDOWN_SECS = [0, 1, 2, 3, 5, 10];
+
+interface RecordingCountdownProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const RecordingCountdown: React.FC<RecordingCountdownProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { settings, refreshSettings } = useSettings();
+    const [updating, setUpdating] = useState<string | null>(null);
+
+    const options = COUNTDOWN_SECS.map((secs) => ({
+      value: String(secs),
+      label:
+        secs === 0
+          ? t("settings.advanced.countdown.off")
+          : t("settings.advanced.countdown.seconds", { count: secs }),
+    }));
+
+    const handleSelect = async (id: string, value: string) => {
+      setUpdating(id);
+      const result = await commands.changeBindingCountdown(
+        id,
+        parseInt(value, 10),
+      );
+      if (result.status === "error") {
+        toast.error(result.error);
+      }
+      await refreshSettings();
+      setUpdating(null);
+    };
+
+    return (
+      <>
+        {COUNTDOWN_BINDINGS.filter((id) => settings?.bindings?.[id]).map(
+          (id) => (
+            <SettingContainer
+              key={id}
+              title={t("settings.advanced.countdown.title", {
+                shortcut: t(`settings.general.shortcut.bindings.${id}.name`),
+              })}
+              description={t("settings.advanced.countdown.description")}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            >
+              <Dropdown
+                options={options}
+                selectedValue={String(
+                  settings?.bindings?.[id]?.countdown_secs ?? 0,
+                )}
+                onSelect={(value) => handleSelect(id, value)}
+                disabled={updating === id}
+              />
+            </SettingContainer>
+          ),
+        )}
+      </>
+    );
+  });
@@ -0,0 +1,113 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type RecordingMode, type ShortcutBinding } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Slider } from "../ui/Slider";
+
+// Shortcuts that record
+const RECORDING_BINDINGS = ["transcribe", "translate"];
+const DICTATION_BINDING_PREFIX = "dictate_";
+const FOLLOW = "__follow__";
+const MODES: RecordingMode[] = ["push_to_talk", "toggle", "hands_free"];
+
+interface RecordingModesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/** How each recording shortcut starts and stops, over push-to-talk. */
+export const RecordingModes: React.FC<RecordingModesProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { settings, getSetting, updateSetting, refreshSettings } =
+      useSettings();
+    const [updating, setUpdating] = useState<string | null>(null);
+
+    const pushToTalk = getSetting("push_to_talk") ?? false;
+    const silenceSecs = getSetting("hands_free_silence_secs") ?? 2;
+
+    const bindings = Object.values(settings?.bindings ?? {})
+      .filter(
+        (binding): binding is ShortcutBinding =>
+          !!binding &&
+          (RECORDING_BINDINGS.includes(binding.id) ||
+            binding.id.startsWith(DICTATION_BINDING_PREFIX)),
+      )
+      .sort((a, b) => a.id.localeCompare(b.id));
+
+    const modeOf = (binding: ShortcutBinding): RecordingMode =>
+      binding.recording_mode ?? (pushToTalk ? "push_to_talk" : "toggle");
+    const anyHandsFree = bindings.some(
+      (binding) => modeOf(binding) === "hands_free",
+    );
+
+    const options = [
+      {
+        value: FOLLOW,
+        label: t("settings.general.recordingMode.options.follow"),
+      },
+      ...MODES.map((mode) => ({
+        value: mode,
+        label: t(`settings.general.recordingMode.options.${mode}`),
+      })),
+    ];
+
+    const handleChange = async (id: string, value: string) => {
+      setUpdating(id);
+      const result = await commands.changeBindingRecordingMode(
+        id,
+        value === FOLLOW ? null : (value as RecordingMode),
+      );
+      if (result.status === "error") {
+        toast.error(result.error);
+      }
+      await refreshSettings();
+      setUpdating(null);
+    };
+
+    return (
+      <>
+        {bindings.map((binding) => (
+          <SettingContainer
+            key={binding.id}
+            title={t("settings.general.recordingMode.label", {
+              shortcut: t(
+                `settings.general.shortcut.bindings.${binding.id}.name`,
+                binding.name,
+              ),
+            })}
+            description={t("settings.general.recordingMode.description")}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          >
+            <Dropdown
+              options={options}
+              selectedValue={binding.recording_mode ?? FOLLOW}
+              onSelect={(value) => handleChange(binding.id, value)}
+              disabled={updating === binding.id}
+            />
+          </SettingContainer>
+        ))}
+        {anyHandsFree && (
+          <Slider
+            value={silenceSecs}
+            onChange={(value) =>
+              updateSetting("hands_free_silence_secs", Math.round(value))
+            }
+            min={1}
+            max={10}
+            step={1}
+            label={t("settings.general.handsFreeSilence.title")}
+            description={t("settings.general.handsFreeSilence.description")}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+            formatValue={(value) => `${value}s`}
+          />
+        )}
+      </>
+    );
+  },
+);
@@ -0,0 +1,72 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+interface ReleaseToCancelProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const ReleaseToCancel: React.FC<ReleaseToCancelProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("release_to_cancel") ?? false;
+    const silenceSecs = getSetting("release_to_cancel_silence_secs");
+
+    const handleSilenceChange = (
+      event: React.ChangeEvent<HTMLInputElement>,
+    ) => {
+      const secs = parseInt(event.target.value, 10);
+      updateSetting(
+        "release_to_cancel_silence_secs",
+        !isNaN(secs) && secs > 0 ? secs : null,
+      );
+    };
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(value) => updateSetting("release_to_cancel", value)}
+          isUpdating={isUpdating("release_to_cancel")}
+          label={t("settings.general.releaseToCancel.label")}
+          description={t("settings.general.releaseToCancel.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {enabled && (
+          <SettingContainer
+            title={t("settings.general.releaseToCancel.silence.title")}
+            description={t(
+              "settings.general.releaseToCancel.silence.description",
+            )}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+            layout="horizontal"
+          >
+            <div className="flex items-center space-x-2">
+              <Input
+                type="number"
+                min="0"
+                step="1"
+                value={silenceSecs ?? ""}
+                placeholder="0"
+                onChange={handleSilenceChange}
+                disabled={isUpdating("release_to_cancel_silence_secs")}
+                className="w-20"
+              />
+              <span className="text-sm text-text">
+                {t("settings.general.releaseToCancel.silence.seconds")}
+              </span>
+            </div>
+          </SettingContainer>
+        )}
+      </>
+    );
+  },
+);
@@ -0,0 +1,110 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+interface RemoteMicrophoneProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const RemoteMicrophone: React.FC<RemoteMicrophoneProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, refreshSettings } =
+      useSettings();
+    const [addresses, setAddresses] = useState<string[]>([]);
+
+    const enabled = getSetting("remote_microphone") ?? false;
+    const port = getSetting("remote_microphone_port") ?? 7010;
+    const token = getSetting("remote_microphone_token") ?? "";
+    const [localPort, setLocalPort] = useState(String(port));
+
+    useEffect(() => setLocalPort(String(port)), [port]);
+
+    useEffect(() => {
+      if (enabled) {
+        commands.getRemoteMicrophoneAddresses().then(setAddresses);
+      }
+    }, [enabled, port, token]);
+
+    const savePort = () => {
+      const value = parseInt(localPort, 10);
+      if (!isNaN(value) && value !== port) {
+        updateSetting("remote_microphone_port", value);
+      } else {
+        setLocalPort(String(port));
+      }
+    };
+
+    const regenerate = async () => {
+      await commands.regenerateRemoteMicrophoneToken();
+      await refreshSettings();
+    };
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={async (value) => {
+            await updateSetting("remote_microphone", value);
+            // Turning it on the first time creates the pairing token
+            await refreshSettings();
+          }}
+          isUpdating={isUpdating("remote_microphone")}
+          label={t("settings.sound.remoteMicrophone.label")}
+          description={t("settings.sound.remoteMicrophone.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {enabled && (
+          <>
+            <SettingContainer
+              title={t("settings.sound.remoteMicrophone.port.title")}
+              description={t(
+                "settings.sound.remoteMicrophone.port.description",
+              )}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+              layout="stacked"
+            >
+              <Input
+                type="number"
+                min="1024"
+                max="65535"
+                value={localPort}
+                onChange={(e) => setLocalPort(e.target.value)}
+                onBlur={savePort}
+                disabled={isUpdating("remote_microphone_port")}
+                className="w-28"
+              />
+              <ul className="mt-2 text-sm text-mid-gray select-text">
+                {addresses.map((address) => (
+                  <li key={address}>
+                    <code>{address}</code>
+                  </li>
+                ))}
+              </ul>
+            </SettingContainer>
+            <SettingContainer
+              title={t("settings.sound.remoteMicrophone.regenerate.title")}
+              description={t(
+                "settings.sound.remoteMicrophone.regenerate.description",
+              )}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            >
+              <Button variant="secondary" size="sm" onClick={regenerate}>
+                {t("settings.sound.remoteMicrophone.regenerate.button")}
+              </Button>
+            </SettingContainer>
+          </>
+        )}
+      </>
+    );
+  },
+);
@@ -0,0 +1,91 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { useModelStore } from "../../stores/modelStore";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+// Empty value means re-transcription is off
+const OFF = "";
+
+interface RetranscribeProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const Retranscribe: React.FC<RetranscribeProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const { models } = useModelStore();
+
+    const retranscribeModel = getSetting("retranscribe_model") ?? null;
+    const maxLatencyMs = getSetting("retranscribe_max_latency_ms") ?? 4000;
+
+    const modelOptions = [
+      { value: OFF, label: t("settings.advanced.retranscribe.off") },
+      ...models
+        .filter((model) => model.is_downloaded)
+        .map((model) => ({ value: model.id, label: model.name })),
+    ];
+
+    const handleLatencyChange = (
+      event: React.ChangeEvent<HTMLInputElement>,
+    ) => {
+      const seconds = parseFloat(event.target.value);
+      if (!isNaN(seconds) && seconds > 0) {
+        updateSetting(
+          "retranscribe_max_latency_ms",
+          Math.round(seconds * 1000),
+        );
+      }
+    };
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.retranscribe.model.title")}
+          description={t("settings.advanced.retranscribe.model.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={modelOptions}
+            selectedValue={retranscribeModel ?? OFF}
+            onSelect={(value) =>
+              updateSetting("retranscribe_model", value || null)
+            }
+            disabled={isUpdating("retranscribe_model")}
+          />
+        </SettingContainer>
+        {retranscribeModel && (
+          <SettingContainer
+            title={t("settings.advanced.retranscribe.maxLatency.title")}
+            description={t(
+              "settings.advanced.retranscribe.maxLatency.description",
+            )}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+            layout="horizontal"
+          >
+            <div className="flex items-center space-x-2">
+              <Input
+                type="number"
+                min="0.5"
+                step="0.5"
+                value={maxLatencyMs / 1000}
+                onChange={handleLatencyChange}
+                disabled={isUpdating("retranscribe_max_latency_ms")}
+                className="w-20"
+              />
+              <span className="text-sm text-text">
+                {t("settings.advanced.retranscribe.maxLatency.seconds")}
+              </span>
+            </div>
+          </SettingContainer>
+        )}
+      </>
+    );
+  },
+);
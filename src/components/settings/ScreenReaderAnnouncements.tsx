@@ -0,0 +1,34 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface ScreenReaderAnnouncementsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const ScreenReaderAnnouncements: React.FC<ScreenReaderAnnouncementsProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const announcementsEnabled =
+      getSetting("screen_reader_announcements") ?? true;
+
+    return (
+      <ToggleSwitch
+        checked={announcementsEnabled}
+        onChange={(enabled) =>
+          updateSetting("screen_reader_announcements", enabled)
+        }
+        isUpdating={isUpdating("screen_reader_announcements")}
+        label={t("settings.advanced.screenReaderAnnouncements.label")}
+        description={t(
+          "settings.advanced.screenReaderAnnouncements.description",
+        )}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
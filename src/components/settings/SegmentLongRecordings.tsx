@@ -0,0 +1,29 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface SegmentLongRecordingsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const SegmentLongRecordings: React.FC<SegmentLongRecordingsProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    return (
+      <ToggleSwitch
+        checked={getSetting("segment_long_recordings") ?? false}
+        onChange={(enabled) =>
+          updateSetting("segment_long_recordings", enabled)
+        }
+        isUpdating={isUpdating("segment_long_recordings")}
+        label={t("settings.advanced.segmentLongRecordings.label")}
+        description={t("settings.advanced.segmentLongRecordings.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
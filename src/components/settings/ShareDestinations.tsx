@@ -0,0 +1,152 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import type { ShareDestination, ShareKind } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Textarea } from "../ui/Textarea";
+
+const KINDS: ShareKind[] = ["email", "slack", "notion", "command"];
+
+interface ShareDestinationsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const ShareDestinations: React.FC<ShareDestinationsProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const destinations = getSetting("share_destinations");
+    const [local, setLocal] = useState<ShareDestination[]>(
+      destinations ?? [],
+    );
+    const updating = isUpdating("share_destinations");
+
+    useEffect(() => setLocal(destinations ?? []), [destinations]);
+
+    const kindOptions = KINDS.map((kind) => ({
+      value: kind,
+      label: t(`settings.advanced.share.kinds.${kind}`),
+    }));
+
+    const save = (next: ShareDestination[]) =>
+      updateSetting("share_destinations", next);
+
+    const edit = (index: number, changes: Partial<ShareDestination>) =>
+      setLocal(
+        local.map((destination, i) =>
+          i === index ? { ...destination, ...changes } : destination,
+        ),
+      );
+
+    const addDestination = () =>
+      save([
+        ...local,
+        {
+          id: `share_${Date.now()}`,
+          name: "",
+          kind: "email",
+          target: "",
+          token: "",
+          template: "{text}",
+        },
+      ]);
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.share.destinations.title")}
+        description={t("settings.advanced.share.destinations.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-3">
+          {local.map((destination, index) => (
+            <div
+              key={destination.id}
+              className="space-y-2 p-2 rounded border border-mid-gray/20"
+            >
+              <div className="flex items-center space-x-2">
+                <Input
+                  type="text"
+                  className="flex-1"
+                  value={destination.name}
+                  onChange={(e) => edit(index, { name: e.target.value })}
+                  onBlur={() => save(local)}
+                  placeholder={t("settings.advanced.share.namePlaceholder")}
+                  variant="compact"
+                  disabled={updating}
+                />
+                <Dropdown
+                  options={kindOptions}
+                  selectedValue={destination.kind}
+                  onSelect={(value) => {
+                    const next = local.map((d, i) =>
+                      i === index ? { ...d, kind: value as ShareKind } : d,
+                    );
+                    setLocal(next);
+                    save(next);
+                  }}
+                  disabled={updating}
+                />
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => save(local.filter((_, i) => i !== index))}
+                  disabled={updating}
+                >
+                  {t("settings.advanced.share.remove")}
+                </Button>
+              </div>
+              <Input
+                type="text"
+                className="w-full font-mono"
+                value={destination.target}
+                onChange={(e) => edit(index, { target: e.target.value })}
+                onBlur={() => save(local)}
+                placeholder={t(
+                  `settings.advanced.share.targetPlaceholder.${destination.kind}`,
+                )}
+                variant="compact"
+                disabled={updating}
+              />
+              {destination.kind === "notion" && (
+                <Input
+                  type="password"
+                  className="w-full"
+                  value={destination.token}
+                  onChange={(e) => edit(index, { token: e.target.value })}
+                  onBlur={() => save(local)}
+                  placeholder={t("settings.advanced.share.tokenPlaceholder")}
+                  variant="compact"
+                  disabled={updating}
+                />
+              )}
+              <Textarea
+                className="w-full"
+                value={destination.template}
+                onChange={(e) => edit(index, { template: e.target.value })}
+                onBlur={() => save(local)}
+                placeholder={t("settings.advanced.share.templatePlaceholder")}
+                variant="compact"
+                disabled={updating}
+              />
+            </div>
+          ))}
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={addDestination}
+            disabled={updating}
+          >
+            {t("settings.advanced.share.add")}
+          </Button>
+        </div>
+      </SettingContainer>
+    );
+  },
+);
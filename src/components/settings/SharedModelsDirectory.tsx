@@ -0,0 +1,65 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { useModelStore } from "../../stores/modelStore";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface SharedModelsDirectoryProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const SharedModelsDirectory: React.FC<SharedModelsDirectoryProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, refreshSettings, isUpdating } = useSettings();
+    const { loadModels } = useModelStore();
+    const configuredDir = getSetting("shared_models_dir") ?? "";
+    const [localDir, setLocalDir] = useState(configuredDir);
+    const [activeDir, setActiveDir] = useState<string | null>(null);
+
+    useEffect(() => setLocalDir(configuredDir), [configuredDir]);
+    useEffect(() => {
+      commands.getSharedModelsDir().then((result) => {
+        if (result.status === "ok") setActiveDir(result.data);
+      });
+    }, [configuredDir]);
+
+    const handleBlur = async () => {
+      if (localDir.trim() === configuredDir) return;
+      const result = await commands.changeSharedModelsDirSetting(localDir);
+      if (result.status === "error") {
+        toast.error(result.error);
+        setLocalDir(configuredDir);
+        return;
+      }
+      await Promise.all([refreshSettings(), loadModels()]);
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.sharedModels.title")}
+        description={
+          activeDir
+            ? t("settings.advanced.sharedModels.active", { path: activeDir })
+            : t("settings.advanced.sharedModels.description")
+        }
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Input
+          type="text"
+          className="min-w-[240px]"
+          value={localDir}
+          onChange={(e) => setLocalDir(e.target.value)}
+          onBlur={handleBlur}
+          placeholder={t("settings.advanced.sharedModels.placeholder")}
+          variant="compact"
+          disabled={isUpdating("shared_models_dir")}
+        />
+      </SettingContainer>
+    );
+  });
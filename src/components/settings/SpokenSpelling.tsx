@@ -0,0 +1,70 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+interface SpokenSpellingProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const SpokenSpelling: React.FC<SpokenSpellingProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("spoken_spelling") ?? false;
+    const trigger = getSetting("spelling_trigger") ?? "spell";
+    const [localTrigger, setLocalTrigger] = useState(trigger);
+
+    useEffect(() => setLocalTrigger(trigger), [trigger]);
+
+    const handleTriggerBlur = () => {
+      const word = localTrigger.trim();
+      if (word && !/\s/.test(word)) {
+        updateSetting("spelling_trigger", word);
+      } else {
+        setLocalTrigger(trigger);
+      }
+    };
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(enabled) => updateSetting("spoken_spelling", enabled)}
+          isUpdating={isUpdating("spoken_spelling")}
+          label={t("settings.advanced.spokenSpelling.label")}
+          description={t("settings.advanced.spokenSpelling.description", {
+            trigger,
+          })}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {enabled && (
+          <SettingContainer
+            title={t("settings.advanced.spokenSpelling.trigger.title")}
+            description={t(
+              "settings.advanced.spokenSpelling.trigger.description",
+            )}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+            layout="horizontal"
+          >
+            <Input
+              type="text"
+              className="w-32"
+              value={localTrigger}
+              onChange={(e) => setLocalTrigger(e.target.value)}
+              onBlur={handleTriggerBlur}
+              variant="compact"
+              disabled={isUpdating("spelling_trigger")}
+            />
+          </SettingContainer>
+        )}
+      </>
+    );
+  },
+);
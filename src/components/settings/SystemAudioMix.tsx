@@ -0,0 +1,32 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface SystemAudioMixProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const SystemAudioMix: React.FC<SystemAudioMixProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const mixEnabled = getSetting("system_audio_mix_microphone") ?? false;
+
+    return (
+      <ToggleSwitch
+        checked={mixEnabled}
+        onChange={(enabled) =>
+          updateSetting("system_audio_mix_microphone", enabled)
+        }
+        isUpdating={isUpdating("system_audio_mix_microphone")}
+        label={t("settings.sound.systemAudioMix.label")}
+        description={t("settings.sound.systemAudioMix.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
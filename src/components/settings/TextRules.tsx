@@ -0,0 +1,222 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  commands,
+  type TextCasing,
+  type TextRule,
+  type TextRuleAction,
+} from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+type RuleType = TextRuleAction["type"];
+
+const RULE_TYPES: RuleType[] = [
+  "literal",
+  "regex",
+  "spoken_punctuation",
+  "casing",
+  "numbers",
+];
+const CASINGS: TextCasing[] = ["lower", "upper", "sentence", "title"];
+
+interface TextRulesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const TextRules: React.FC<TextRulesProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, refreshSettings } = useSettings();
+    const [type, setType] = useState<RuleType>("literal");
+    const [find, setFind] = useState("");
+    const [replace, setReplace] = useState("");
+    const [casing, setCasing] = useState<TextCasing>("sentence");
+    const [updating, setUpdating] = useState(false);
+
+    const rules = getSetting("text_rules") ?? [];
+
+    const save = async (next: TextRule[]) => {
+      setUpdating(true);
+      const result = await commands.changeTextRulesSetting(next);
+      if (result.status === "error") {
+        toast.error(result.error);
+      }
+      await refreshSettings();
+      setUpdating(false);
+      return result.status === "ok";
+    };
+
+    const describe = (action: TextRuleAction) => {
+      switch (action.type) {
+        case "literal":
+        case "regex":
+          return t(`settings.advanced.textRules.describe.${action.type}`, {
+            find: action.type === "literal" ? action.find : action.pattern,
+            replace: action.replace,
+          });
+        case "casing":
+          return t("settings.advanced.textRules.describe.casing", {
+            casing: t(`settings.advanced.textRules.casings.${action.casing}`),
+          });
+        default:
+          return t(`settings.advanced.textRules.types.${action.type}`);
+      }
+    };
+
+    const newAction = (): TextRuleAction | null => {
+      switch (type) {
+        case "literal":
+          return find ? { type, find, replace } : null;
+        case "regex":
+          return find ? { type, pattern: find, replace } : null;
+        case "casing":
+          return { type, casing };
+        default:
+          return { type };
+      }
+    };
+
+    const addRule = async () => {
+      const action = newAction();
+      if (!action) return;
+      if (await save([...rules, { action, enabled: true }])) {
+        setFind("");
+        setReplace("");
+      }
+    };
+
+    const move = (index: number, offset: number) => {
+      const next = [...rules];
+      const [rule] = next.splice(index, 1);
+      next.splice(index + offset, 0, rule);
+      save(next);
+    };
+
+    const toggle = (index: number) =>
+      save(
+        rules.map((rule, i) =>
+          i === index ? { ...rule, enabled: !rule.enabled } : rule,
+        ),
+      );
+
+    const needsFind = type === "literal" || type === "regex";
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.textRules.title")}
+        description={t("settings.advanced.textRules.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          {rules.map((rule, index) => (
+            <div key={index} className="flex items-center space-x-2">
+              <span className="w-5 text-sm text-mid-gray">{index + 1}.</span>
+              <span
+                className={`flex-1 text-sm truncate ${rule.enabled ? "" : "text-mid-gray line-through"}`}
+              >
+                {describe(rule.action)}
+              </span>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => toggle(index)}
+                disabled={updating}
+              >
+                {rule.enabled
+                  ? t("settings.advanced.textRules.disable")
+                  : t("settings.advanced.textRules.enable")}
+              </Button>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => move(index, -1)}
+                disabled={updating || index === 0}
+                aria-label={t("settings.advanced.textRules.moveUp")}
+              >
+                ↑
+              </Button>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => move(index, 1)}
+                disabled={updating || index === rules.length - 1}
+                aria-label={t("settings.advanced.textRules.moveDown")}
+              >
+                ↓
+              </Button>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => save(rules.filter((_, i) => i !== index))}
+                disabled={updating}
+              >
+                {t("settings.advanced.textRules.remove")}
+              </Button>
+            </div>
+          ))}
+          <div className="flex items-center space-x-2">
+            <Dropdown
+              options={RULE_TYPES.map((value) => ({
+                value,
+                label: t(`settings.advanced.textRules.types.${value}`),
+              }))}
+              selectedValue={type}
+              onSelect={(value) => setType(value as RuleType)}
+              disabled={updating}
+            />
+            {needsFind && (
+              <>
+                <Input
+                  type="text"
+                  className="flex-1"
+                  value={find}
+                  onChange={(e) => setFind(e.target.value)}
+                  placeholder={t(`settings.advanced.textRules.find.${type}`)}
+                  variant="compact"
+                  disabled={updating}
+                />
+                <Input
+                  type="text"
+                  className="flex-1"
+                  value={replace}
+                  onChange={(e) => setReplace(e.target.value)}
+                  onKeyDown={(e) => e.key === "Enter" && addRule()}
+                  placeholder={t("settings.advanced.textRules.replace")}
+                  variant="compact"
+                  disabled={updating}
+                />
+              </>
+            )}
+            {type === "casing" && (
+              <Dropdown
+                options={CASINGS.map((value) => ({
+                  value,
+                  label: t(`settings.advanced.textRules.casings.${value}`),
+                }))}
+                selectedValue={casing}
+                onSelect={(value) => setCasing(value as TextCasing)}
+                disabled={updating}
+              />
+            )}
+            <Button
+              variant="secondary"
+              size="sm"
+              onClick={addRule}
+              disabled={updating || (needsFind && !find)}
+            >
+              {t("settings.advanced.textRules.add")}
+            </Button>
+          </div>
+        </div>
+      </SettingContainer>
+    );
+  },
+);
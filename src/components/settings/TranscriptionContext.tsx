@@ -0,0 +1,55 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+import type { TranscriptionContext } from "@/bindings";
+
+interface TranscriptionContextProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const TranscriptionContextSetting: React.FC<TranscriptionContextProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const contextOptions = [
+      {
+        value: "none",
+        label: t("settings.advanced.transcriptionContext.options.none"),
+      },
+      {
+        value: "previous_transcript",
+        label: t(
+          "settings.advanced.transcriptionContext.options.previousTranscript",
+        ),
+      },
+      {
+        value: "clipboard",
+        label: t("settings.advanced.transcriptionContext.options.clipboard"),
+      },
+    ];
+
+    const selectedContext = (getSetting("transcription_context") ||
+      "none") as TranscriptionContext;
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.transcriptionContext.title")}
+        description={t("settings.advanced.transcriptionContext.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Dropdown
+          options={contextOptions}
+          selectedValue={selectedContext}
+          onSelect={(value) =>
+            updateSetting("transcription_context", value as TranscriptionContext)
+          }
+          disabled={isUpdating("transcription_context")}
+        />
+      </SettingContainer>
+    );
+  });
@@ -0,0 +1,48 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Textarea } from "../ui/Textarea";
+
+// Whisper only looks at the end of a long prompt
+const MAX_PROMPT_CHARS = 400;
+
+interface TranscriptionPromptProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const TranscriptionPrompt: React.FC<TranscriptionPromptProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const prompt = getSetting("transcription_prompt") ?? "";
+    const [draft, setDraft] = useState(prompt);
+
+    useEffect(() => setDraft(prompt), [prompt]);
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.transcriptionPrompt.title")}
+        description={t("settings.advanced.transcriptionPrompt.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <Textarea
+          className="w-full"
+          variant="compact"
+          value={draft}
+          maxLength={MAX_PROMPT_CHARS}
+          onChange={(e) => setDraft(e.target.value)}
+          onBlur={() => {
+            if (draft !== prompt) {
+              updateSetting("transcription_prompt", draft);
+            }
+          }}
+          placeholder={t("settings.advanced.transcriptionPrompt.placeholder")}
+          disabled={isUpdating("transcription_prompt")}
+        />
+      </SettingContainer>
+    );
+  });
@@ -0,0 +1,85 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type TrayIndicator } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface TrayIconSettingsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const TrayIconSettings: React.FC<TrayIconSettingsProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, refreshSettings, isUpdating } =
+      useSettings();
+    const configuredDir = getSetting("tray_icon_dir") ?? "";
+    const [localDir, setLocalDir] = useState(configuredDir);
+
+    useEffect(() => setLocalDir(configuredDir), [configuredDir]);
+
+    const indicatorOptions = [
+      { value: "off", label: t("settings.advanced.trayIcon.indicator.off") },
+      { value: "dot", label: t("settings.advanced.trayIcon.indicator.dot") },
+      {
+        value: "flash",
+        label: t("settings.advanced.trayIcon.indicator.flash"),
+      },
+    ];
+
+    const selectedIndicator = (getSetting("tray_indicator") ||
+      "off") as TrayIndicator;
+
+    const handleDirBlur = async () => {
+      if (localDir.trim() === configuredDir) return;
+      const result = await commands.changeTrayIconDirSetting(localDir);
+      if (result.status === "error") {
+        toast.error(result.error);
+        setLocalDir(configuredDir);
+        return;
+      }
+      await refreshSettings();
+    };
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.trayIcon.indicator.title")}
+          description={t("settings.advanced.trayIcon.indicator.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={indicatorOptions}
+            selectedValue={selectedIndicator}
+            onSelect={(value) =>
+              updateSetting("tray_indicator", value as TrayIndicator)
+            }
+            disabled={isUpdating("tray_indicator")}
+          />
+        </SettingContainer>
+        <SettingContainer
+          title={t("settings.advanced.trayIcon.iconSet.title")}
+          description={t("settings.advanced.trayIcon.iconSet.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Input
+            type="text"
+            className="min-w-[240px]"
+            value={localDir}
+            onChange={(e) => setLocalDir(e.target.value)}
+            onBlur={handleDirBlur}
+            placeholder={t("settings.advanced.trayIcon.iconSet.placeholder")}
+            variant="compact"
+            disabled={isUpdating("tray_icon_dir")}
+          />
+        </SettingContainer>
+      </>
+    );
+  },
+);
@@ -0,0 +1,30 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface TtsReadbackProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const TtsReadback: React.FC<TtsReadbackProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const ttsReadbackEnabled = getSetting("tts_readback_enabled") ?? false;
+
+    return (
+      <ToggleSwitch
+        checked={ttsReadbackEnabled}
+        onChange={(enabled) => updateSetting("tts_readback_enabled", enabled)}
+        isUpdating={isUpdating("tts_readback_enabled")}
+        label={t("settings.advanced.ttsReadback.label")}
+        description={t("settings.advanced.ttsReadback.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
@@ -0,0 +1,63 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface TtsReadbackPathsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const TtsReadbackPaths: React.FC<TtsReadbackPathsProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const voicePath = getSetting("tts_voice_path") ?? "";
+    const piperPath = getSetting("tts_piper_path") ?? "";
+    const [localVoicePath, setLocalVoicePath] = useState(voicePath);
+    const [localPiperPath, setLocalPiperPath] = useState(piperPath);
+
+    useEffect(() => setLocalVoicePath(voicePath), [voicePath]);
+    useEffect(() => setLocalPiperPath(piperPath), [piperPath]);
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.ttsReadback.voicePath.title")}
+          description={t("settings.advanced.ttsReadback.voicePath.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Input
+            type="text"
+            className="min-w-[240px]"
+            value={localVoicePath}
+            onChange={(e) => setLocalVoicePath(e.target.value)}
+            onBlur={() => updateSetting("tts_voice_path", localVoicePath)}
+            placeholder="/path/to/voice.onnx"
+            variant="compact"
+            disabled={isUpdating("tts_voice_path")}
+          />
+        </SettingContainer>
+        <SettingContainer
+          title={t("settings.advanced.ttsReadback.piperPath.title")}
+          description={t("settings.advanced.ttsReadback.piperPath.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Input
+            type="text"
+            className="min-w-[240px]"
+            value={localPiperPath}
+            onChange={(e) => setLocalPiperPath(e.target.value)}
+            onBlur={() => updateSetting("tts_piper_path", localPiperPath)}
+            placeholder="piper"
+            variant="compact"
+            disabled={isUpdating("tts_piper_path")}
+          />
+        </SettingContainer>
+      </>
+    );
+  },
+);
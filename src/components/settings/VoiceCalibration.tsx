@@ -0,0 +1,135 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
+import { commands, type VoiceProfile } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { useModelStore } from "../../stores/modelStore";
+import { Button } from "../ui/Button";
+import { SettingContainer } from "../ui/SettingContainer";
+
+type Stage = "idle" | "recording" | "scoring";
+
+interface VoiceCalibrationProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const VoiceCalibration: React.FC<VoiceCalibrationProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { refreshSettings } = useSettings();
+    const { getModelInfo } = useModelStore();
+    const [stage, setStage] = useState<Stage>("idle");
+    const [scoring, setScoring] = useState<string | null>(null);
+    const [profile, setProfile] = useState<VoiceProfile | null>(null);
+
+    const paragraph = t("settings.sound.calibration.paragraph");
+
+    useEffect(() => {
+      const unlisten = listen<string>("calibration-progress", (event) =>
+        setScoring(event.payload),
+      );
+      return () => {
+        unlisten.then((fn) => fn());
+        commands.cancelVoiceCalibration();
+      };
+    }, []);
+
+    const start = async () => {
+      setProfile(null);
+      const result = await commands.startVoiceCalibration();
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      setStage("recording");
+    };
+
+    const finish = async () => {
+      setStage("scoring");
+      const result = await commands.finishVoiceCalibration(paragraph);
+      setStage("idle");
+      setScoring(null);
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      setProfile(result.data);
+      await refreshSettings();
+    };
+
+    const cancel = async () => {
+      await commands.cancelVoiceCalibration();
+      setStage("idle");
+    };
+
+    const modelName = (id: string) => getModelInfo(id)?.name ?? id;
+
+    return (
+      <SettingContainer
+        title={t("settings.sound.calibration.title")}
+        description={t("settings.sound.calibration.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        {stage === "recording" && (
+          <div className="mb-2 space-y-2 text-sm">
+            <p className="text-mid-gray">
+              {t("settings.sound.calibration.instructions")}
+            </p>
+            <p className="p-2 rounded border border-mid-gray/20">{paragraph}</p>
+          </div>
+        )}
+        <div className="flex items-center space-x-2">
+          {stage === "recording" ? (
+            <>
+              <Button variant="primary" size="sm" onClick={finish}>
+                {t("settings.sound.calibration.finish")}
+              </Button>
+              <Button variant="ghost" size="sm" onClick={cancel}>
+                {t("settings.sound.calibration.cancel")}
+              </Button>
+            </>
+          ) : (
+            <Button
+              variant="secondary"
+              size="sm"
+              onClick={start}
+              disabled={stage === "scoring"}
+            >
+              {stage === "scoring"
+                ? t("settings.sound.calibration.scoring", {
+                    model: scoring ? modelName(scoring) : "",
+                  })
+                : t("settings.sound.calibration.start")}
+            </Button>
+          )}
+        </div>
+        {profile && (
+          <div className="mt-2 text-sm">
+            <p className="text-mid-gray">
+              {t("settings.sound.calibration.result", {
+                threshold: profile.vad_threshold.toFixed(2),
+                gain: profile.input_gain.toFixed(2),
+              })}
+            </p>
+            {(profile.model_scores ?? []).length > 0 && (
+              <ul className="mt-1">
+                {(profile.model_scores ?? []).map((score) => (
+                  <li key={score.model_id}>
+                    {t("settings.sound.calibration.modelScore", {
+                      model: modelName(score.model_id),
+                      percent: Math.round(score.wer * 100),
+                    })}
+                  </li>
+                ))}
+              </ul>
+            )}
+          </div>
+        )}
+      </SettingContainer>
+    );
+  },
+);
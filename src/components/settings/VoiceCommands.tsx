@@ -0,0 +1,28 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface VoiceCommandsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const VoiceCommands: React.FC<VoiceCommandsProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    return (
+      <ToggleSwitch
+        checked={getSetting("voice_commands") ?? false}
+        onChange={(enabled) => updateSetting("voice_commands", enabled)}
+        isUpdating={isUpdating("voice_commands")}
+        label={t("settings.advanced.voiceCommands.label")}
+        description={t("settings.advanced.voiceCommands.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
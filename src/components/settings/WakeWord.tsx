@@ -0,0 +1,275 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  commands,
+  type ShortcutBinding,
+  type WakeWord as Phrase,
+} from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Slider } from "../ui/Slider";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+/** More recordings than this are refused, see `wake_word::MAX_SAMPLES` */
+const MAX_SAMPLES = 5;
+// Shortcuts a wake word can start, see `wake_word::can_start`
+const RECORDING_BINDINGS = ["transcribe", "translate"];
+const DICTATION_BINDING_PREFIX = "dictate_";
+
+interface WakeWordProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const WakeWordOptions: React.FC<{
+  phrase: Phrase;
+  bindings: ShortcutBinding[];
+  descriptionMode: "inline" | "tooltip";
+  grouped: boolean;
+}> = ({ phrase, bindings, descriptionMode, grouped }) => {
+  const { t } = useTranslation();
+  const { refreshSettings } = useSettings();
+  const [name, setName] = useState(phrase.name);
+  const [samples, setSamples] = useState(0);
+  const [recording, setRecording] = useState(false);
+  const [updating, setUpdating] = useState(false);
+
+  useEffect(() => setName(phrase.name), [phrase.name]);
+  useEffect(() => {
+    commands.getWakeWordSampleCount(phrase.id).then(setSamples);
+  }, [phrase.id]);
+
+  const bindingOptions = bindings.map((binding) => ({
+    value: binding.id,
+    label: t(
+      `settings.general.shortcut.bindings.${binding.id}.name`,
+      binding.name,
+    ),
+  }));
+
+  const save = async (
+    changes: Partial<Pick<Phrase, "name" | "binding_id" | "sensitivity">>,
+  ) => {
+    const next = { ...phrase, ...changes };
+    setUpdating(true);
+    const result = await commands.updateWakeWord(
+      phrase.id,
+      next.name,
+      next.binding_id,
+      next.sensitivity,
+    );
+    if (result.status === "error") {
+      toast.error(result.error);
+    }
+    await refreshSettings();
+    setUpdating(false);
+  };
+
+  const saveName = () => {
+    const trimmed = name.trim();
+    if (trimmed && trimmed !== phrase.name) {
+      save({ name: trimmed });
+    } else {
+      setName(phrase.name);
+    }
+  };
+
+  const start = async () => {
+    const result = await commands.startWakeWordSample(phrase.id);
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    setRecording(true);
+  };
+
+  const finish = async () => {
+    setRecording(false);
+    const result = await commands.finishWakeWordSample(phrase.id);
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    setSamples(result.data);
+  };
+
+  const clear = async () => {
+    const result = await commands.clearWakeWordSamples(phrase.id);
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    setSamples(0);
+  };
+
+  const remove = async () => {
+    setUpdating(true);
+    const result = await commands.deleteWakeWord(phrase.id);
+    if (result.status === "error") {
+      toast.error(result.error);
+    }
+    await refreshSettings();
+    setUpdating(false);
+  };
+
+  return (
+    <>
+      <SettingContainer
+        title={t("settings.general.wakeWord.phrase.title")}
+        description={t("settings.general.wakeWord.phrase.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          <div className="flex items-center space-x-2">
+            <Input
+              type="text"
+              className="flex-1"
+              value={name}
+              onChange={(e) => setName(e.target.value)}
+              onBlur={saveName}
+              placeholder={t("settings.general.wakeWord.phrase.name")}
+              variant="compact"
+              disabled={updating}
+            />
+            <Dropdown
+              options={bindingOptions}
+              selectedValue={phrase.binding_id}
+              onSelect={(value) => save({ binding_id: value })}
+              disabled={updating}
+            />
+            <Button
+              variant="ghost"
+              size="sm"
+              onClick={remove}
+              disabled={updating || recording}
+            >
+              {t("settings.general.wakeWord.phrase.remove")}
+            </Button>
+          </div>
+          {recording && (
+            <p className="text-sm text-mid-gray">
+              {t("settings.general.wakeWord.samples.instructions")}
+            </p>
+          )}
+          <div className="flex items-center space-x-2">
+            {recording ? (
+              <Button variant="primary" size="sm" onClick={finish}>
+                {t("settings.general.wakeWord.samples.finish")}
+              </Button>
+            ) : (
+              <Button
+                variant="secondary"
+                size="sm"
+                onClick={start}
+                disabled={samples >= MAX_SAMPLES}
+              >
+                {t("settings.general.wakeWord.samples.record")}
+              </Button>
+            )}
+            {samples > 0 && !recording && (
+              <Button variant="ghost" size="sm" onClick={clear}>
+                {t("settings.general.wakeWord.samples.clear")}
+              </Button>
+            )}
+            <span className="text-sm text-mid-gray">
+              {t("settings.general.wakeWord.samples.count", {
+                count: samples,
+              })}
+            </span>
+          </div>
+        </div>
+      </SettingContainer>
+      <Slider
+        value={phrase.sensitivity}
+        onChange={(value) => save({ sensitivity: value })}
+        min={0}
+        max={1}
+        step={0.05}
+        label={t("settings.general.wakeWord.sensitivity.title")}
+        description={t("settings.general.wakeWord.sensitivity.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        formatValue={(value) => `${Math.round(value * 100)}%`}
+      />
+    </>
+  );
+};
+
+/** Starting a recording by saying a phrase the user recorded. */
+export const WakeWord: React.FC<WakeWordProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { settings, getSetting, updateSetting, isUpdating, refreshSettings } =
+      useSettings();
+    const [adding, setAdding] = useState(false);
+
+    const enabled = getSetting("wake_word_enabled") ?? false;
+    const phrases = getSetting("wake_words") ?? [];
+    const bindings = Object.values(settings?.bindings ?? {})
+      .filter(
+        (binding): binding is ShortcutBinding =>
+          !!binding &&
+          (RECORDING_BINDINGS.includes(binding.id) ||
+            binding.id.startsWith(DICTATION_BINDING_PREFIX)),
+      )
+      .sort((a, b) => a.id.localeCompare(b.id));
+
+    const add = async () => {
+      setAdding(true);
+      const result = await commands.addWakeWord();
+      if (result.status === "error") {
+        toast.error(result.error);
+      }
+      await refreshSettings();
+      setAdding(false);
+    };
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(value) => updateSetting("wake_word_enabled", value)}
+          isUpdating={isUpdating("wake_word_enabled")}
+          label={t("settings.general.wakeWord.label")}
+          description={t("settings.general.wakeWord.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {enabled && (
+          <>
+            {phrases.map((phrase) => (
+              <WakeWordOptions
+                key={phrase.id}
+                phrase={phrase}
+                bindings={bindings}
+                descriptionMode={descriptionMode}
+                grouped={grouped}
+              />
+            ))}
+            <SettingContainer
+              title={t("settings.general.wakeWord.add.title")}
+              description={t("settings.general.wakeWord.add.description")}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            >
+              <Button
+                variant="secondary"
+                size="sm"
+                onClick={add}
+                disabled={adding}
+              >
+                {t("settings.general.wakeWord.add.button")}
+              </Button>
+            </SettingContainer>
+          </>
+        )}
+      </>
+    );
+  },
+);
@@ -0,0 +1,100 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface WordReplacementsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const WordReplacements: React.FC<WordReplacementsProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const [from, setFrom] = useState("");
+    const [to, setTo] = useState("");
+
+    const rules = getSetting("word_replacements") ?? [];
+    const updating = isUpdating("word_replacements");
+
+    const addRule = () => {
+      if (!from.trim() || !to.trim()) return;
+      updateSetting("word_replacements", [
+        ...rules.filter(
+          (rule) => rule.from.toLowerCase() !== from.trim().toLowerCase(),
+        ),
+        { from: from.trim(), to: to.trim() },
+      ]);
+      setFrom("");
+      setTo("");
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.wordReplacements.title")}
+        description={t("settings.advanced.wordReplacements.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          {rules.map((rule, index) => (
+            <div key={rule.from} className="flex items-center space-x-2">
+              <span className="flex-1 text-sm truncate">
+                {t("settings.advanced.wordReplacements.rule", {
+                  from: rule.from,
+                  to: rule.to,
+                })}
+              </span>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() =>
+                  updateSetting(
+                    "word_replacements",
+                    rules.filter((_, i) => i !== index),
+                  )
+                }
+                disabled={updating}
+              >
+                {t("settings.advanced.wordReplacements.remove")}
+              </Button>
+            </div>
+          ))}
+          <div className="flex items-center space-x-2">
+            <Input
+              type="text"
+              className="flex-1"
+              value={from}
+              onChange={(e) => setFrom(e.target.value)}
+              placeholder={t("settings.advanced.wordReplacements.from")}
+              variant="compact"
+              disabled={updating}
+            />
+            <Input
+              type="text"
+              className="flex-1"
+              value={to}
+              onChange={(e) => setTo(e.target.value)}
+              onKeyDown={(e) => e.key === "Enter" && addRule()}
+              placeholder={t("settings.advanced.wordReplacements.to")}
+              variant="compact"
+              disabled={updating}
+            />
+            <Button
+              variant="secondary"
+              size="sm"
+              onClick={addRule}
+              disabled={updating || !from.trim() || !to.trim()}
+            >
+              {t("settings.advanced.wordReplacements.add")}
+            </Button>
+          </div>
+        </div>
+      </SettingContainer>
+    );
+  },
+);
@@ -0,0 +1,35 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface WyomingEnabledProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const WyomingEnabled: React.FC<WyomingEnabledProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, refreshSettings } =
+      useSettings();
+
+    const wyomingEnabled = getSetting("wyoming_enabled") ?? false;
+
+    return (
+      <ToggleSwitch
+        checked={wyomingEnabled}
+        onChange={async (enabled) => {
+          await updateSetting("wyoming_enabled", enabled);
+          // Turning the Wyoming server on turns Deepgram off
+          await refreshSettings();
+        }}
+        isUpdating={isUpdating("wyoming_enabled")}
+        label={t("settings.advanced.wyoming.enabled.label")}
+        description={t("settings.advanced.wyoming.enabled.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
@@ -0,0 +1,108 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type WyomingServer as DiscoveredServer } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface WyomingServerProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const WyomingServer: React.FC<WyomingServerProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const address = getSetting("wyoming_server") ?? "";
+    const [localAddress, setLocalAddress] = useState(address);
+    const [discovered, setDiscovered] = useState<DiscoveredServer[]>([]);
+    const [isBusy, setIsBusy] = useState(false);
+
+    useEffect(() => setLocalAddress(address), [address]);
+
+    const handleDiscover = async () => {
+      setIsBusy(true);
+      const result = await commands.discoverWyomingServers();
+      setIsBusy(false);
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      setDiscovered(result.data);
+      if (result.data.length === 0) {
+        toast(t("settings.advanced.wyoming.server.noneFound"));
+      }
+    };
+
+    const handleCheck = async () => {
+      setIsBusy(true);
+      const result = await commands.checkWyomingServer(localAddress);
+      setIsBusy(false);
+      if (result.status === "error") {
+        toast.error(result.error);
+      } else {
+        toast.success(
+          t("settings.advanced.wyoming.server.connected", {
+            name: result.data,
+          }),
+        );
+      }
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.wyoming.server.title")}
+        description={t("settings.advanced.wyoming.server.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="flex items-center gap-2">
+          <Input
+            type="text"
+            className="flex-1 min-w-[160px]"
+            value={localAddress}
+            onChange={(e) => setLocalAddress(e.target.value)}
+            onBlur={() => updateSetting("wyoming_server", localAddress)}
+            placeholder="192.168.1.10:10300"
+            variant="compact"
+            disabled={isUpdating("wyoming_server")}
+          />
+          {discovered.length > 0 && (
+            <Dropdown
+              options={discovered.map((server) => ({
+                value: server.address,
+                label: `${server.name} (${server.address})`,
+              }))}
+              selectedValue={
+                discovered.some((s) => s.address === address) ? address : null
+              }
+              onSelect={(value) => updateSetting("wyoming_server", value)}
+              placeholder={t("settings.advanced.wyoming.server.pick")}
+            />
+          )}
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={handleDiscover}
+            disabled={isBusy}
+          >
+            {t("settings.advanced.wyoming.server.discover")}
+          </Button>
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={handleCheck}
+            disabled={isBusy || !localAddress.trim()}
+          >
+            {t("settings.advanced.wyoming.server.check")}
+          </Button>
+        </div>
+      </SettingContainer>
+    );
+  },
+);
@@ -1,35 +1,236 @@
-import React from "react";
+import React, { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
 import { useTranslation } from "react-i18next";
 import { ShowOverlay } from "../ShowOverlay";
+import { OverlayPlacement } from "../OverlayPlacement";
+import { TrayIconSettings } from "../TrayIconSettings";
+import { ConcurrentRecordingSetting } from "../ConcurrentRecordingSetting";
+import { RecordingCountdown } from "../RecordingCountdown";
+import { ModelDownloads } from "../ModelDownloads";
 import { TranslateToEnglish } from "../TranslateToEnglish";
+import { BindingTranslation } from "../BindingTranslation";
+import { CodeSwitching } from "../CodeSwitching";
+import { LanguageLock } from "../LanguageLock";
+import { TranscriptionContextSetting } from "../TranscriptionContext";
+import { Retranscribe } from "../Retranscribe";
+import { HallucinationFilter } from "../HallucinationFilter";
+import { PunctuationRestoration } from "../PunctuationRestoration";
+import { PunctuationStyle } from "../PunctuationStyle";
+import { SpokenSpelling } from "../SpokenSpelling";
+import { VoiceCommands } from "../VoiceCommands";
+import { AdaptiveEngine } from "../AdaptiveEngine";
+import { CompressSilence } from "../CompressSilence";
+import { SegmentLongRecordings } from "../SegmentLongRecordings";
+import { PreviewBeforePaste } from "../PreviewBeforePaste";
 import { ModelUnloadTimeoutSetting } from "../ModelUnloadTimeout";
+import { ModelMemoryBudget } from "../ModelMemoryBudget";
+import { SharedModelsDirectory } from "../SharedModelsDirectory";
+import { FineTunes } from "../FineTunes";
 import { CustomWords } from "../CustomWords";
+import { BoostCustomWords } from "../BoostCustomWords";
 import { SettingsGroup } from "../../ui/SettingsGroup";
 import { StartHidden } from "../StartHidden";
 import { AutostartToggle } from "../AutostartToggle";
 import { PasteMethodSetting } from "../PasteMethod";
+import { PasteMethodTest } from "../PasteMethodTest";
+import { ReplaceSelection } from "../ReplaceSelection";
+import { VerifyPaste } from "../VerifyPaste";
 import { ClipboardHandlingSetting } from "../ClipboardHandling";
+import { TtsReadback } from "../TtsReadback";
+import { TtsReadbackPaths } from "../TtsReadbackPaths";
+import { LowPowerMode } from "../LowPowerMode";
+import { ScreenReaderAnnouncements } from "../ScreenReaderAnnouncements";
+import { LowPowerRules } from "../LowPowerRules";
+import { BatchSchedule } from "../BatchSchedule";
+import { FileTranscriptionCache } from "../FileTranscriptionCache";
+import { InjectionAudit } from "../InjectionAudit";
+import { Plugins } from "../Plugins";
+import { DeepgramEnabled } from "../DeepgramEnabled";
+import { DeepgramSettings } from "../DeepgramSettings";
+import { CloudTranscription } from "../CloudTranscription";
+import { WordReplacements } from "../WordReplacements";
+import { TranscriptionPrompt } from "../TranscriptionPrompt";
+import { DictationTemplates } from "../DictationTemplates";
+import { TextRules } from "../TextRules";
+import { WyomingEnabled } from "../WyomingEnabled";
+import { WyomingServer } from "../WyomingServer";
+import { RealtimeTranscription } from "../RealtimeTranscription";
+import { RealtimeEndpoint } from "../RealtimeEndpoint";
+import { ShareDestinations } from "../ShareDestinations";
+import { ApiServer } from "../ApiServer";
+import { commands } from "@/bindings";
 import { useModelStore } from "../../../stores/modelStore";
+import { useSettings } from "../../../hooks/useSettings";
 
 export const AdvancedSettings: React.FC = () => {
   const { t } = useTranslation();
   const { currentModel, getModelInfo } = useModelStore();
+  const { getSetting } = useSettings();
+  const ttsReadbackEnabled = getSetting("tts_readback_enabled") ?? false;
   const currentModelInfo = getModelInfo(currentModel);
   const showTranslateToEnglish =
     currentModelInfo?.engine_type === "Whisper" && currentModel !== "turbo";
+  const showCodeSwitching = currentModelInfo?.engine_type === "Whisper";
+  const lowPowerEnabled = getSetting("low_power_enabled") ?? false;
+  const wyomingEnabled = getSetting("wyoming_enabled") ?? false;
+  const deepgramEnabled = getSetting("deepgram_enabled") ?? false;
+  const realtimeEnabled = getSetting("realtime_transcription") ?? false;
+  const showLanguageLock =
+    (showCodeSwitching || wyomingEnabled || deepgramEnabled) &&
+    getSetting("selected_language") === "auto";
+  const [onBattery, setOnBattery] = useState(false);
+
+  useEffect(() => {
+    commands.isOnBattery().then(setOnBattery);
+    const unlistenPromise = listen<boolean>("power-state-changed", (event) =>
+      setOnBattery(event.payload),
+    );
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
   return (
     <div className="max-w-3xl w-full mx-auto space-y-6">
       <SettingsGroup title={t("settings.advanced.title")}>
         <StartHidden descriptionMode="tooltip" grouped={true} />
         <AutostartToggle descriptionMode="tooltip" grouped={true} />
         <ShowOverlay descriptionMode="tooltip" grouped={true} />
+        <OverlayPlacement descriptionMode="tooltip" grouped={true} />
+        <TrayIconSettings descriptionMode="tooltip" grouped={true} />
+        <ConcurrentRecordingSetting descriptionMode="tooltip" grouped={true} />
+        <RecordingCountdown descriptionMode="tooltip" grouped={true} />
         <PasteMethodSetting descriptionMode="tooltip" grouped={true} />
+        <PasteMethodTest descriptionMode="tooltip" grouped={true} />
+        <ReplaceSelection descriptionMode="tooltip" grouped={true} />
+        <VerifyPaste descriptionMode="tooltip" grouped={true} />
         <ClipboardHandlingSetting descriptionMode="tooltip" grouped={true} />
         {showTranslateToEnglish && (
-          <TranslateToEnglish descriptionMode="tooltip" grouped={true} />
+          <>
+            <TranslateToEnglish descriptionMode="tooltip" grouped={true} />
+            <BindingTranslation descriptionMode="tooltip" grouped={true} />
+          </>
+        )}
+        {showCodeSwitching && (
+          <CodeSwitching descriptionMode="tooltip" grouped={true} />
         )}
+        {showLanguageLock && (
+          <LanguageLock descriptionMode="tooltip" grouped={true} />
+        )}
+        {showCodeSwitching && (
+          <TranscriptionContextSetting
+            descriptionMode="tooltip"
+            grouped={true}
+          />
+        )}
+        <Retranscribe descriptionMode="tooltip" grouped={true} />
+        <AdaptiveEngine descriptionMode="tooltip" grouped={true} />
+        <CompressSilence descriptionMode="tooltip" grouped={true} />
+        <SegmentLongRecordings descriptionMode="tooltip" grouped={true} />
+        <HallucinationFilter descriptionMode="tooltip" grouped={true} />
+        <PunctuationRestoration descriptionMode="tooltip" grouped={true} />
         <ModelUnloadTimeoutSetting descriptionMode="tooltip" grouped={true} />
+        <ModelMemoryBudget descriptionMode="tooltip" grouped={true} />
+        <SharedModelsDirectory descriptionMode="tooltip" grouped={true} />
+        <FineTunes descriptionMode="tooltip" grouped={true} />
+        <ModelDownloads descriptionMode="tooltip" grouped={true} />
         <CustomWords descriptionMode="tooltip" grouped />
+        <BoostCustomWords descriptionMode="tooltip" grouped={true} />
+        <TranscriptionPrompt descriptionMode="tooltip" grouped={true} />
+        <WordReplacements descriptionMode="tooltip" grouped={true} />
+        <DictationTemplates descriptionMode="tooltip" grouped={true} />
+        <TextRules descriptionMode="tooltip" grouped={true} />
+        <PreviewBeforePaste descriptionMode="tooltip" grouped={true} />
+        <TtsReadback descriptionMode="tooltip" grouped={true} />
+        {ttsReadbackEnabled && (
+          <TtsReadbackPaths descriptionMode="tooltip" grouped={true} />
+        )}
+        <ScreenReaderAnnouncements descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.advanced.punctuationStyle.title")}
+        description={t("settings.advanced.punctuationStyle.description")}
+      >
+        <PunctuationStyle descriptionMode="tooltip" grouped={true} />
+        <SpokenSpelling descriptionMode="tooltip" grouped={true} />
+        <VoiceCommands descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.advanced.lowPower.title")}
+        description={
+          onBattery
+            ? t("settings.advanced.lowPower.onBattery")
+            : t("settings.advanced.lowPower.onAc")
+        }
+      >
+        <LowPowerMode descriptionMode="tooltip" grouped={true} />
+        {lowPowerEnabled && (
+          <LowPowerRules descriptionMode="tooltip" grouped={true} />
+        )}
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.advanced.batch.title")}
+        description={t("settings.advanced.batch.description")}
+      >
+        <BatchSchedule descriptionMode="tooltip" grouped={true} />
+        <FileTranscriptionCache descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.advanced.injectionAudit.title")}
+        description={t("settings.advanced.injectionAudit.description")}
+      >
+        <InjectionAudit descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.advanced.wyoming.title")}
+        description={t("settings.advanced.wyoming.description")}
+      >
+        <WyomingEnabled descriptionMode="tooltip" grouped={true} />
+        {wyomingEnabled && (
+          <WyomingServer descriptionMode="tooltip" grouped={true} />
+        )}
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.advanced.deepgram.title")}
+        description={t("settings.advanced.deepgram.description")}
+      >
+        <DeepgramEnabled descriptionMode="tooltip" grouped={true} />
+        {deepgramEnabled && (
+          <DeepgramSettings descriptionMode="tooltip" grouped={true} />
+        )}
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.advanced.cloudTranscription.title")}
+        description={t("settings.advanced.cloudTranscription.description")}
+      >
+        <CloudTranscription descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.advanced.realtime.title")}
+        description={t("settings.advanced.realtime.description")}
+      >
+        <RealtimeTranscription descriptionMode="tooltip" grouped={true} />
+        {realtimeEnabled && (
+          <RealtimeEndpoint descriptionMode="tooltip" grouped={true} />
+        )}
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.advanced.plugins.title")}
+        description={t("settings.advanced.plugins.description")}
+      >
+        <Plugins descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.advanced.share.title")}
+        description={t("settings.advanced.share.description")}
+      >
+        <ShareDestinations descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.advanced.apiServer.title")}
+        description={t("settings.advanced.apiServer.groupDescription")}
+      >
+        <ApiServer descriptionMode="tooltip" grouped={true} />
       </SettingsGroup>
     </div>
   );
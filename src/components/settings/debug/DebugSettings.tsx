@@ -7,6 +7,7 @@ import { LogLevelSelector } from "./LogLevelSelector";
 import { SettingsGroup } from "../../ui/SettingsGroup";
 import { HistoryLimit } from "../HistoryLimit";
 import { AlwaysOnMicrophone } from "../AlwaysOnMicrophone";
+import { PreRoll } from "../PreRoll";
 import { SoundPicker } from "../SoundPicker";
 import { PostProcessingToggle } from "../PostProcessingToggle";
 import { MuteWhileRecording } from "../MuteWhileRecording";
@@ -40,6 +41,7 @@ export const DebugSettings: React.FC = () => {
           grouped={true}
         />
         <AlwaysOnMicrophone descriptionMode="tooltip" grouped={true} />
+        <PreRoll descriptionMode="tooltip" grouped={true} />
         <ClamshellMicrophoneSelector descriptionMode="tooltip" grouped={true} />
         <PostProcessingToggle descriptionMode="tooltip" grouped={true} />
         <MuteWhileRecording descriptionMode="tooltip" grouped={true} />
@@ -52,6 +54,9 @@ export const DebugSettings: React.FC = () => {
             disabled={pushToTalk}
           />
         )}
+        {!isLinux && (
+          <HandyShortcut shortcutId="accept_readback" grouped={true} />
+        )}
       </SettingsGroup>
     </div>
   );
@@ -1,19 +1,40 @@
 import React from "react";
 import { useTranslation } from "react-i18next";
 import { MicrophoneSelector } from "../MicrophoneSelector";
+import { MicrophonePriority } from "../MicrophonePriority";
+import { PreprocessingChain } from "../PreprocessingChain";
+import { RemoteMicrophone } from "../RemoteMicrophone";
+import { SystemAudioMix } from "../SystemAudioMix";
+import { VoiceCalibration } from "../VoiceCalibration";
+import { ConferencingGuard } from "../ConferencingGuard";
+import { InputWatchdog } from "../InputWatchdog";
+import { AdaptiveVad } from "../AdaptiveVad";
+import { NoiseSuppression } from "../NoiseSuppression";
+import { AutoGainControl } from "../AutoGainControl";
 import { LanguageSelector } from "../LanguageSelector";
+import { LanguagePacks } from "../LanguagePacks";
 import { HandyShortcut } from "../HandyShortcut";
+import { DictationBindings } from "../DictationBindings";
+import { CaptionsExport } from "../CaptionsExport";
+import { CaptionSegmentation } from "../CaptionSegmentation";
 import { SettingsGroup } from "../../ui/SettingsGroup";
 import { OutputDeviceSelector } from "../OutputDeviceSelector";
 import { PushToTalk } from "../PushToTalk";
+import { RecordingModes } from "../RecordingModes";
+import { ReleaseToCancel } from "../ReleaseToCancel";
+import { WakeWord } from "../WakeWord";
 import { AudioFeedback } from "../AudioFeedback";
 import { useSettings } from "../../../hooks/useSettings";
 import { useModelStore } from "../../../stores/modelStore";
 import { VolumeSlider } from "../VolumeSlider";
+import { NotificationPreferences } from "../NotificationPreferences";
+import { CustomSounds } from "../CustomSounds";
 
 export const GeneralSettings: React.FC = () => {
   const { t } = useTranslation();
-  const { audioFeedbackEnabled } = useSettings();
+  const { audioFeedbackEnabled, getSetting } = useSettings();
+  const pushToTalk = getSetting("push_to_talk") ?? false;
+  const systemAudio = getSetting("selected_microphone") === "System audio";
   const { currentModel, getModelInfo } = useModelStore();
   const currentModelInfo = getModelInfo(currentModel);
   const showLanguageSelector = currentModelInfo?.engine_type === "Whisper";
@@ -21,13 +42,41 @@ export const GeneralSettings: React.FC = () => {
     <div className="max-w-3xl w-full mx-auto space-y-6">
       <SettingsGroup title={t("settings.general.title")}>
         <HandyShortcut shortcutId="transcribe" grouped={true} />
+        <HandyShortcut shortcutId="translate" grouped={true} />
+        <DictationBindings descriptionMode="tooltip" grouped={true} />
+        <HandyShortcut shortcutId="toggle_captions" grouped={true} />
+        <CaptionsExport descriptionMode="tooltip" grouped={true} />
+        <CaptionSegmentation descriptionMode="tooltip" grouped={true} />
         {showLanguageSelector && (
           <LanguageSelector descriptionMode="tooltip" grouped={true} />
         )}
         <PushToTalk descriptionMode="tooltip" grouped={true} />
+        <RecordingModes descriptionMode="tooltip" grouped={true} />
+        {pushToTalk && (
+          <ReleaseToCancel descriptionMode="tooltip" grouped={true} />
+        )}
+        <WakeWord descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.general.languagePacks.title")}
+        description={t("settings.general.languagePacks.description")}
+      >
+        <LanguagePacks descriptionMode="tooltip" grouped={true} />
       </SettingsGroup>
       <SettingsGroup title={t("settings.sound.title")}>
         <MicrophoneSelector descriptionMode="tooltip" grouped={true} />
+        {systemAudio && (
+          <SystemAudioMix descriptionMode="tooltip" grouped={true} />
+        )}
+        <MicrophonePriority descriptionMode="tooltip" grouped={true} />
+        <PreprocessingChain descriptionMode="tooltip" grouped={true} />
+        <VoiceCalibration descriptionMode="tooltip" grouped={true} />
+        <RemoteMicrophone descriptionMode="tooltip" grouped={true} />
+        <ConferencingGuard descriptionMode="tooltip" grouped={true} />
+        <InputWatchdog descriptionMode="tooltip" grouped={true} />
+        <AdaptiveVad descriptionMode="tooltip" grouped={true} />
+        <NoiseSuppression descriptionMode="tooltip" grouped={true} />
+        <AutoGainControl descriptionMode="tooltip" grouped={true} />
         <AudioFeedback descriptionMode="tooltip" grouped={true} />
         <OutputDeviceSelector
           descriptionMode="tooltip"
@@ -35,6 +84,17 @@ export const GeneralSettings: React.FC = () => {
           disabled={!audioFeedbackEnabled}
         />
         <VolumeSlider disabled={!audioFeedbackEnabled} />
+        <CustomSounds
+          descriptionMode="tooltip"
+          grouped={true}
+          disabled={!audioFeedbackEnabled}
+        />
+      </SettingsGroup>
+      <SettingsGroup
+        title={t("settings.notifications.title")}
+        description={t("settings.notifications.description")}
+      >
+        <NotificationPreferences descriptionMode="tooltip" grouped={true} />
       </SettingsGroup>
     </div>
   );
@@ -0,0 +1,120 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebview } from "@tauri-apps/api/webview";
+import { FileAudio } from "lucide-react";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { Button } from "../../ui/Button";
+import { Input } from "../../ui/Input";
+
+interface TranscriptionProgress {
+  percent: number;
+  task: string | null;
+}
+
+const fileName = (path: string) => path.split(/[\\/]/).pop() || path;
+
+/** Transcribes audio and video files dropped on the window or typed in. */
+export const FileTranscription: React.FC = () => {
+  const { t } = useTranslation();
+  const [path, setPath] = useState("");
+  const [current, setCurrent] = useState<string | null>(null);
+  const [percent, setPercent] = useState<number | null>(null);
+  const [dragging, setDragging] = useState(false);
+
+  const transcribe = async (paths: string[]) => {
+    for (const file of paths) {
+      setCurrent(file);
+      setPercent(null);
+      const result = await commands.transcribeAudioFile(file);
+      if (result.status === "error") {
+        toast.error(
+          t("settings.history.files.failed", {
+            file: fileName(file),
+            error: result.error,
+          }),
+        );
+      } else {
+        toast.success(
+          t("settings.history.files.done", { file: fileName(file) }),
+        );
+      }
+    }
+    setCurrent(null);
+  };
+
+  useEffect(() => {
+    const unlistenDrop = getCurrentWebview().onDragDropEvent((event) => {
+      if (event.payload.type === "over" || event.payload.type === "enter") {
+        setDragging(true);
+      } else if (event.payload.type === "drop") {
+        setDragging(false);
+        if (event.payload.paths.length > 0) {
+          transcribe(event.payload.paths);
+        }
+      } else {
+        setDragging(false);
+      }
+    });
+    const unlistenProgress = listen<TranscriptionProgress>(
+      "transcription-progress",
+      (event) => setPercent(event.payload.task ? event.payload.percent : null),
+    );
+    return () => {
+      unlistenDrop.then((fn) => fn());
+      unlistenProgress.then((fn) => fn());
+    };
+  }, []);
+
+  const busy = current !== null;
+
+  return (
+    <div className="space-y-2">
+      <div className="px-4">
+        <h2 className="text-xs font-medium text-mid-gray uppercase tracking-wide">
+          {t("settings.history.files.title")}
+        </h2>
+        <p className="text-xs text-mid-gray mt-1">
+          {t("settings.history.files.description")}
+        </p>
+      </div>
+      <div
+        className={`border border-dashed rounded-lg px-4 py-4 space-y-3 transition-colors ${
+          dragging
+            ? "border-logo-primary bg-logo-primary/10"
+            : "border-mid-gray/40 bg-background"
+        }`}
+      >
+        <div className="flex items-center gap-2 text-sm text-mid-gray">
+          <FileAudio size={16} />
+          {current !== null
+            ? t("settings.history.files.transcribing", {
+                file: fileName(current),
+                percent: Math.round(percent ?? 0),
+              })
+            : t("settings.history.files.drop")}
+        </div>
+        <div className="flex items-center gap-2">
+          <Input
+            type="text"
+            value={path}
+            onChange={(e) => setPath(e.target.value)}
+            placeholder="/path/to/recording.mp3"
+            variant="compact"
+            className="flex-1"
+            disabled={busy}
+          />
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={() => transcribe([path.trim()])}
+            disabled={busy || !path.trim()}
+          >
+            {t("settings.history.files.transcribe")}
+          </Button>
+        </div>
+      </div>
+    </div>
+  );
+};
@@ -1,11 +1,34 @@
 import React, { useState, useEffect, useCallback } from "react";
 import { useTranslation } from "react-i18next";
-import { AudioPlayer } from "../../ui/AudioPlayer";
 import { Button } from "../../ui/Button";
-import { Copy, Star, Check, Trash2, FolderOpen } from "lucide-react";
+import { Input } from "../../ui/Input";
+import { Textarea } from "../../ui/Textarea";
+import { FileTranscription } from "./FileTranscription";
+import { PendingTranscriptions } from "./PendingTranscriptions";
+import { QualityReport } from "./QualityReport";
+import { TranscriptReview } from "./TranscriptReview";
+import {
+  Copy,
+  Star,
+  Check,
+  Trash2,
+  FolderOpen,
+  Share2,
+  Captions,
+  Pencil,
+  ThumbsUp,
+  ThumbsDown,
+} from "lucide-react";
 import { convertFileSrc } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { commands, type HistoryEntry } from "@/bindings";
+import {
+  commands,
+  type HistoryEntry,
+  type ShareDestination,
+  type SubtitleFormat,
+} from "@/bindings";
+import { useSettings } from "../../../hooks/useSettings";
+import { useCorrectionSuggestions } from "../../../hooks/useCorrectionSuggestions";
 import { formatDateTime } from "@/utils/dateFormat";
 
 interface OpenRecordingsButtonProps {
@@ -31,11 +54,21 @@ const OpenRecordingsButton: React.FC<OpenRecordingsButtonProps> = ({
 
 export const HistorySettings: React.FC = () => {
   const { t } = useTranslation();
+  const { getSetting } = useSettings();
+  const shareDestinations = getSetting("share_destinations") ?? [];
   const [historyEntries, setHistoryEntries] = useState<HistoryEntry[]>([]);
   const [loading, setLoading] = useState(true);
+  const [query, setQuery] = useState("");
 
   const loadHistoryEntries = useCallback(async () => {
     try {
+      if (query.trim()) {
+        const result = await commands.searchHistory(query, 0, 100);
+        if (result.status === "ok") {
+          setHistoryEntries(result.data.entries);
+        }
+        return;
+      }
       const result = await commands.getHistoryEntries();
       if (result.status === "ok") {
         setHistoryEntries(result.data);
@@ -45,7 +78,7 @@ export const HistorySettings: React.FC = () => {
     } finally {
       setLoading(false);
     }
-  }, []);
+  }, [query]);
 
   useEffect(() => {
     loadHistoryEntries();
@@ -144,33 +177,10 @@ export const HistorySettings: React.FC = () => {
     );
   }
 
-  if (historyEntries.length === 0) {
-    return (
-      <div className="max-w-3xl w-full mx-auto space-y-6">
-        <div className="space-y-2">
-          <div className="px-4 flex items-center justify-between">
-            <div>
-              <h2 className="text-xs font-medium text-mid-gray uppercase tracking-wide">
-                {t("settings.history.title")}
-              </h2>
-            </div>
-            <OpenRecordingsButton
-              onClick={openRecordingsFolder}
-              label={t("settings.history.openFolder")}
-            />
-          </div>
-          <div className="bg-background border border-mid-gray/20 rounded-lg overflow-visible">
-            <div className="px-4 py-3 text-center text-text/60">
-              {t("settings.history.empty")}
-            </div>
-          </div>
-        </div>
-      </div>
-    );
-  }
-
   return (
     <div className="max-w-3xl w-full mx-auto space-y-6">
+      <FileTranscription />
+      <PendingTranscriptions />
       <div className="space-y-2">
         <div className="px-4 flex items-center justify-between">
           <div>
@@ -178,26 +188,46 @@ export const HistorySettings: React.FC = () => {
               {t("settings.history.title")}
             </h2>
           </div>
-          <OpenRecordingsButton
-            onClick={openRecordingsFolder}
-            label={t("settings.history.openFolder")}
-          />
+          <div className="flex items-center gap-2">
+            <Input
+              type="search"
+              variant="compact"
+              value={query}
+              onChange={(event) => setQuery(event.target.value)}
+              placeholder={t("settings.history.search.placeholder")}
+              className="w-48"
+            />
+            <OpenRecordingsButton
+              onClick={openRecordingsFolder}
+              label={t("settings.history.openFolder")}
+            />
+          </div>
         </div>
         <div className="bg-background border border-mid-gray/20 rounded-lg overflow-visible">
-          <div className="divide-y divide-mid-gray/20">
-            {historyEntries.map((entry) => (
-              <HistoryEntryComponent
-                key={entry.id}
-                entry={entry}
-                onToggleSaved={() => toggleSaved(entry.id)}
-                onCopyText={() => copyToClipboard(entry.transcription_text)}
-                getAudioUrl={getAudioUrl}
-                deleteAudio={deleteAudioEntry}
-              />
-            ))}
-          </div>
+          {historyEntries.length === 0 ? (
+            <div className="px-4 py-3 text-center text-text/60">
+              {query.trim()
+                ? t("settings.history.search.noResults")
+                : t("settings.history.empty")}
+            </div>
+          ) : (
+            <div className="divide-y divide-mid-gray/20">
+              {historyEntries.map((entry) => (
+                <HistoryEntryComponent
+                  key={entry.id}
+                  entry={entry}
+                  onToggleSaved={() => toggleSaved(entry.id)}
+                  onCopyText={() => copyToClipboard(entry.transcription_text)}
+                  getAudioUrl={getAudioUrl}
+                  deleteAudio={deleteAudioEntry}
+                  shareDestinations={shareDestinations}
+                />
+              ))}
+            </div>
+          )}
         </div>
       </div>
+      <QualityReport />
     </div>
   );
 };
@@ -208,6 +238,7 @@ interface HistoryEntryProps {
   onCopyText: () => void;
   getAudioUrl: (fileName: string) => Promise<string | null>;
   deleteAudio: (id: number) => Promise<void>;
+  shareDestinations: ShareDestination[];
 }
 
 const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
@@ -216,10 +247,23 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
   onCopyText,
   getAudioUrl,
   deleteAudio,
+  shareDestinations,
 }) => {
   const { t, i18n } = useTranslation();
   const [audioUrl, setAudioUrl] = useState<string | null>(null);
   const [showCopied, setShowCopied] = useState(false);
+  const [showShare, setShowShare] = useState(false);
+  const [shareStatus, setShareStatus] = useState<string | null>(null);
+  const [showExport, setShowExport] = useState(false);
+  const [editing, setEditing] = useState(false);
+  const [draft, setDraft] = useState("");
+  // The transcript before it was edited, kept after saving to offer learning
+  // the corrections
+  const [editedFrom, setEditedFrom] = useState<string | null>(null);
+  const { suggestions, learn } = useCorrectionSuggestions(
+    editedFrom ?? "",
+    editedFrom === null ? "" : draft,
+  );
 
   useEffect(() => {
     const loadAudio = async () => {
@@ -244,12 +288,90 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
     }
   };
 
+  const handleShare = async (destination: ShareDestination) => {
+    setShowShare(false);
+    const result = await commands.shareHistoryEntry(entry.id, destination.id);
+    setShareStatus(
+      result.status === "ok"
+        ? t("settings.history.share.sent", { name: destination.name })
+        : t("settings.history.share.failed", { error: result.error }),
+    );
+    setTimeout(() => setShareStatus(null), 4000);
+  };
+
+  const handleExport = async (format: SubtitleFormat) => {
+    setShowExport(false);
+    const result = await commands.exportHistorySubtitles(entry.id, format);
+    setShareStatus(
+      result.status === "ok"
+        ? t("settings.history.subtitles.saved", { path: result.data })
+        : t("settings.history.subtitles.failed", { error: result.error }),
+    );
+    setTimeout(() => setShareStatus(null), 4000);
+  };
+
+  // Clicking the active thumb again clears the rating
+  const rate = async (good: boolean) => {
+    const rating = entry.rating === good ? null : good;
+    const result = await commands.rateHistoryEntry(entry.id, rating);
+    if (result.status === "error") {
+      console.error("Failed to rate entry:", result.error);
+    }
+  };
+
+  const startEditing = () => {
+    setDraft(entry.transcription_text);
+    setEditedFrom(entry.transcription_text);
+    setEditing(true);
+  };
+
+  const cancelEditing = () => {
+    setEditing(false);
+    setEditedFrom(null);
+  };
+
+  const saveEdit = async () => {
+    const result = await commands.updateHistoryEntryText(entry.id, draft);
+    if (result.status === "ok") {
+      setEditing(false);
+    } else {
+      console.error("Failed to save transcript:", result.error);
+    }
+  };
+
   const formattedDate = formatDateTime(String(entry.timestamp), i18n.language);
+  const usage = entry.resource_usage;
+  const usageSummary = usage
+    ? [
+        entry.engine,
+        t(`settings.history.usage.device.${usage.device}`),
+        t("settings.history.usage.time", {
+          seconds: (usage.inference_ms / 1000).toFixed(1),
+        }),
+        t("settings.history.usage.ram", { mb: usage.peak_ram_mb }),
+        usage.vram_mb != null &&
+          t("settings.history.usage.vram", { mb: usage.vram_mb }),
+        t("settings.history.usage.cpu", {
+          percent: Math.round(usage.cpu_percent),
+        }),
+      ]
+        .filter(Boolean)
+        .join(" · ")
+    : null;
 
   return (
     <div className="px-4 py-2 pb-5 flex flex-col gap-3">
       <div className="flex justify-between items-center">
-        <p className="text-sm font-medium">{formattedDate}</p>
+        <p className="text-sm font-medium">
+          {formattedDate}
+          {entry.duration_ms != null && (
+            <span className="ml-2 text-xs font-normal text-mid-gray">
+              {t("settings.history.duration", {
+                seconds: Math.round(entry.duration_ms / 1000),
+              })}
+            </span>
+          )}
+        </p>
         <div className="flex items-center gap-1">
           <button
             onClick={handleCopyText}
@@ -281,6 +403,59 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
               fill={entry.saved ? "currentColor" : "none"}
             />
           </button>
+          <button
+            onClick={() => rate(true)}
+            className={`p-2 transition-colors cursor-pointer ${
+              entry.rating === true
+                ? "text-logo-primary hover:text-logo-primary/80"
+                : "text-text/50 hover:text-logo-primary"
+            }`}
+            title={t("settings.history.rating.good")}
+          >
+            <ThumbsUp
+              width={16}
+              height={16}
+              fill={entry.rating === true ? "currentColor" : "none"}
+            />
+          </button>
+          <button
+            onClick={() => rate(false)}
+            className={`p-2 transition-colors cursor-pointer ${
+              entry.rating === false
+                ? "text-logo-primary hover:text-logo-primary/80"
+                : "text-text/50 hover:text-logo-primary"
+            }`}
+            title={t("settings.history.rating.bad")}
+          >
+            <ThumbsDown
+              width={16}
+              height={16}
+              fill={entry.rating === false ? "currentColor" : "none"}
+            />
+          </button>
+          <button
+            onClick={startEditing}
+            className="p-2 text-text/50 hover:text-logo-primary transition-colors cursor-pointer"
+            title={t("settings.history.edit.title")}
+          >
+            <Pencil width={16} height={16} />
+          </button>
+          <button
+            onClick={() => setShowExport(!showExport)}
+            className="p-2 text-text/50 hover:text-logo-primary transition-colors cursor-pointer"
+            title={t("settings.history.subtitles.title")}
+          >
+            <Captions width={16} height={16} />
+          </button>
+          {shareDestinations.length > 0 && (
+            <button
+              onClick={() => setShowShare(!showShare)}
+              className="p-2 text-text/50 hover:text-logo-primary transition-colors cursor-pointer"
+              title={t("settings.history.share.title")}
+            >
+              <Share2 width={16} height={16} />
+            </button>
+          )}
           <button
             onClick={handleDeleteEntry}
             className="text-text/50 hover:text-logo-primary transition-colors cursor-pointer"
@@ -290,10 +465,94 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
           </button>
         </div>
       </div>
-      <p className="italic text-text/90 text-sm pb-2 select-text cursor-text">
-        {entry.transcription_text}
-      </p>
-      {audioUrl && <AudioPlayer src={audioUrl} className="w-full" />}
+      {showShare && (
+        <div className="flex flex-wrap gap-2">
+          {shareDestinations.map((destination) => (
+            <Button
+              key={destination.id}
+              variant="secondary"
+              size="sm"
+              onClick={() => handleShare(destination)}
+            >
+              {destination.name ||
+                t(`settings.advanced.share.kinds.${destination.kind}`)}
+            </Button>
+          ))}
+        </div>
+      )}
+      {showExport && (
+        <div className="flex flex-wrap gap-2">
+          {(["srt", "vtt"] as const).map((format) => (
+            <Button
+              key={format}
+              variant="secondary"
+              size="sm"
+              onClick={() => handleExport(format)}
+            >
+              {t(`settings.history.subtitles.${format}`)}
+            </Button>
+          ))}
+        </div>
+      )}
+      {shareStatus && <p className="text-xs text-mid-gray">{shareStatus}</p>}
+      {editing ? (
+        <div className="flex flex-col gap-2">
+          <Textarea
+            className="w-full"
+            value={draft}
+            onChange={(e) => setDraft(e.target.value)}
+            variant="compact"
+          />
+          <div className="flex gap-2">
+            <Button
+              variant="primary"
+              size="sm"
+              onClick={saveEdit}
+              disabled={!draft.trim()}
+            >
+              {t("settings.history.edit.save")}
+            </Button>
+            <Button variant="secondary" size="sm" onClick={cancelEditing}>
+              {t("settings.history.edit.cancel")}
+            </Button>
+          </div>
+        </div>
+      ) : (
+        <p className="italic text-text/90 text-sm pb-2 select-text cursor-text whitespace-pre-line">
+          {entry.transcription_text}
+        </p>
+      )}
+      {suggestions.length > 0 && (
+        <div className="flex flex-wrap items-center gap-2">
+          <span className="text-xs text-mid-gray">
+            {t("settings.history.learn.prompt")}
+          </span>
+          {suggestions.map((suggestion) => (
+            <Button
+              key={suggestion.from}
+              variant="secondary"
+              size="sm"
+              onClick={() => learn(suggestion)}
+            >
+              {t("settings.history.learn.rule", {
+                from: suggestion.from,
+                to: suggestion.to,
+              })}
+            </Button>
+          ))}
+          {!editing && (
+            <Button
+              variant="ghost"
+              size="sm"
+              onClick={() => setEditedFrom(null)}
+            >
+              {t("settings.history.learn.dismiss")}
+            </Button>
+          )}
+        </div>
+      )}
+      {audioUrl && <TranscriptReview entryId={entry.id} audioUrl={audioUrl} />}
+      {usageSummary && <p className="text-xs text-mid-gray">{usageSummary}</p>}
     </div>
   );
 };
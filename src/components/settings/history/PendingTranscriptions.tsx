@@ -0,0 +1,98 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { Trash2 } from "lucide-react";
+import { toast } from "sonner";
+import { commands, type PendingTranscription } from "@/bindings";
+import { formatDateTime } from "@/utils/dateFormat";
+import { Button } from "../../ui/Button";
+
+/** Recordings kept while the transcription engine was unreachable. */
+export const PendingTranscriptions: React.FC = () => {
+  const { t, i18n } = useTranslation();
+  const [pending, setPending] = useState<PendingTranscription[]>([]);
+  const [retrying, setRetrying] = useState(false);
+
+  useEffect(() => {
+    commands.getPendingTranscriptions().then(setPending);
+    const unlisten = listen<PendingTranscription[]>(
+      "pending-transcriptions-changed",
+      (event) => setPending(event.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const retryNow = async () => {
+    setRetrying(true);
+    try {
+      const result = await commands.retryPendingTranscriptions();
+      if (result.status === "error") {
+        toast.error(result.error);
+      } else if (result.data === 0) {
+        toast.info(t("settings.history.pending.stillOffline"));
+      }
+    } finally {
+      setRetrying(false);
+    }
+  };
+
+  if (pending.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="space-y-2">
+      <div className="px-4 flex items-center justify-between">
+        <div>
+          <h2 className="text-xs font-medium text-mid-gray uppercase tracking-wide">
+            {t("settings.history.pending.title")}
+          </h2>
+          <p className="text-xs text-mid-gray mt-1">
+            {t("settings.history.pending.description")}
+          </p>
+        </div>
+        <Button
+          variant="secondary"
+          size="sm"
+          onClick={retryNow}
+          disabled={retrying}
+        >
+          {t("settings.history.pending.retry")}
+        </Button>
+      </div>
+      <div className="bg-background border border-mid-gray/20 rounded-lg divide-y divide-mid-gray/20">
+        {pending.map((job) => (
+          <div
+            key={job.id}
+            className="px-4 py-2 flex items-center justify-between text-sm"
+          >
+            <div>
+              <p>
+                {formatDateTime(String(job.queued_at), i18n.language)}
+                <span className="text-mid-gray">
+                  {" · "}
+                  {t("settings.history.pending.duration", {
+                    seconds: Math.round(job.duration_secs),
+                  })}
+                  {job.target_app && ` · ${job.target_app}`}
+                </span>
+              </p>
+              {job.last_error && (
+                <p className="text-xs text-red-400">{job.last_error}</p>
+              )}
+            </div>
+            <button
+              onClick={() => commands.removePendingTranscription(job.id)}
+              className="text-text/50 hover:text-logo-primary transition-colors cursor-pointer"
+              title={t("settings.history.pending.discard")}
+            >
+              <Trash2 width={16} height={16} />
+            </button>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+};
@@ -0,0 +1,67 @@
+import React, { useCallback, useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { commands, type QualityStats } from "@/bindings";
+
+/** Ratings per engine and language, from the thumbs given in history. */
+export const QualityReport: React.FC = () => {
+  const { t } = useTranslation();
+  const [report, setReport] = useState<QualityStats[]>([]);
+
+  const loadReport = useCallback(async () => {
+    const result = await commands.getQualityReport();
+    if (result.status === "ok") {
+      setReport(result.data);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadReport();
+    const unlisten = listen("history-updated", () => loadReport());
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [loadReport]);
+
+  if (report.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="space-y-2">
+      <div className="px-4">
+        <h2 className="text-xs font-medium text-mid-gray uppercase tracking-wide">
+          {t("settings.history.quality.title")}
+        </h2>
+        <p className="text-xs text-mid-gray mt-1">
+          {t("settings.history.quality.description")}
+        </p>
+      </div>
+      <div className="bg-background border border-mid-gray/20 rounded-lg divide-y divide-mid-gray/20">
+        {report.map((stats) => (
+          <div
+            key={`${stats.engine}-${stats.language}`}
+            className="px-4 py-2 flex items-center justify-between text-sm"
+          >
+            <span>
+              {stats.engine}
+              <span className="text-mid-gray">
+                {" · "}
+                {stats.language && stats.language !== "auto"
+                  ? stats.language
+                  : t("settings.history.quality.autoLanguage")}
+              </span>
+            </span>
+            <span className="text-xs text-mid-gray">
+              {t("settings.history.quality.summary", {
+                percent: Math.round(stats.approval * 100),
+                good: stats.up,
+                total: stats.up + stats.down,
+              })}
+            </span>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+};
@@ -0,0 +1,76 @@
+import React, { useRef, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands, type SpokenWord } from "@/bindings";
+import { AudioPlayer, type AudioPlayerHandle } from "../../ui/AudioPlayer";
+
+interface TranscriptReviewProps {
+  entryId: number;
+  audioUrl: string;
+}
+
+/**
+ * An entry's recording with speed control, and its words to follow along
+ * with and click to jump to.
+ */
+export const TranscriptReview: React.FC<TranscriptReviewProps> = ({
+  entryId,
+  audioUrl,
+}) => {
+  const { t } = useTranslation();
+  const playerRef = useRef<AudioPlayerHandle>(null);
+  const [words, setWords] = useState<SpokenWord[] | null>(null);
+  const [open, setOpen] = useState(false);
+  const [time, setTime] = useState(0);
+
+  const toggle = async () => {
+    if (!open && words === null) {
+      const result = await commands.getHistoryEntryWords(entryId);
+      setWords(result.status === "ok" ? result.data : []);
+    }
+    setOpen(!open);
+  };
+
+  return (
+    <div className="flex flex-col gap-2">
+      <AudioPlayer
+        ref={playerRef}
+        src={audioUrl}
+        className="w-full"
+        speedControl
+        onTimeChange={open ? setTime : undefined}
+      />
+      <button
+        onClick={toggle}
+        className="self-start text-xs text-mid-gray hover:text-logo-primary transition-colors cursor-pointer"
+      >
+        {open
+          ? t("settings.history.review.hide")
+          : t("settings.history.review.show")}
+      </button>
+      {open && words && words.length === 0 && (
+        <p className="text-xs text-mid-gray">
+          {t("settings.history.review.untimed")}
+        </p>
+      )}
+      {open && words && words.length > 0 && (
+        <p className="text-sm leading-relaxed">
+          {words.map((word, i) => {
+            const current = time >= word.start && time < word.end;
+            return (
+              <React.Fragment key={i}>
+                <span
+                  onClick={() => playerRef.current?.seek(word.start)}
+                  className={`rounded px-0.5 cursor-pointer transition-colors ${
+                    current ? "bg-logo-primary/30" : "hover:bg-mid-gray/20"
+                  }`}
+                >
+                  {word.text}
+                </span>{" "}
+              </React.Fragment>
+            );
+          })}
+        </p>
+      )}
+    </div>
+  );
+};
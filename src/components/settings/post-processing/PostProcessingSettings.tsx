@@ -18,8 +18,12 @@ import { ProviderSelect } from "../PostProcessingSettingsApi/ProviderSelect";
 import { BaseUrlField } from "../PostProcessingSettingsApi/BaseUrlField";
 import { ApiKeyField } from "../PostProcessingSettingsApi/ApiKeyField";
 import { ModelSelect } from "../PostProcessingSettingsApi/ModelSelect";
+import { OAuthSignIn } from "../PostProcessingSettingsApi/OAuthSignIn";
 import { usePostProcessProviderState } from "../PostProcessingSettingsApi/usePostProcessProviderState";
 import { useSettings } from "../../../hooks/useSettings";
+import { ContextRules } from "../ContextRules";
+import { PostProcessTemperature } from "../PostProcessTemperature";
+import { BindingPostProcess } from "../BindingPostProcess";
 
 const DisabledNotice: React.FC<{ children: React.ReactNode }> = ({
   children,
@@ -89,24 +93,36 @@ const PostProcessingSettingsApiComponent: React.FC = () => {
             </SettingContainer>
           )}
 
+          {!state.usesOAuth && (
+            <SettingContainer
+              title={t("settings.postProcessing.api.apiKey.title")}
+              description={t("settings.postProcessing.api.apiKey.description")}
+              descriptionMode="tooltip"
+              layout="horizontal"
+              grouped={true}
+            >
+              <div className="flex items-center gap-2">
+                <ApiKeyField
+                  value={state.apiKey}
+                  onBlur={state.handleApiKeyChange}
+                  placeholder={t(
+                    "settings.postProcessing.api.apiKey.placeholder",
+                  )}
+                  disabled={state.isApiKeyUpdating}
+                  className="min-w-[320px]"
+                />
+              </div>
+            </SettingContainer>
+          )}
+
           <SettingContainer
-            title={t("settings.postProcessing.api.apiKey.title")}
-            description={t("settings.postProcessing.api.apiKey.description")}
+            title={t("settings.postProcessing.api.oauth.title")}
+            description={t("settings.postProcessing.api.oauth.description")}
             descriptionMode="tooltip"
-            layout="horizontal"
+            layout="stacked"
             grouped={true}
           >
-            <div className="flex items-center gap-2">
-              <ApiKeyField
-                value={state.apiKey}
-                onBlur={state.handleApiKeyChange}
-                placeholder={t(
-                  "settings.postProcessing.api.apiKey.placeholder",
-                )}
-                disabled={state.isApiKeyUpdating}
-                className="min-w-[320px]"
-              />
-            </div>
+            <OAuthSignIn providerId={state.selectedProviderId} />
           </SettingContainer>
         </>
       )}
@@ -154,6 +170,10 @@ const PostProcessingSettingsApiComponent: React.FC = () => {
           </div>
         </SettingContainer>
       )}
+
+      {!state.isAppleProvider && (
+        <PostProcessTemperature descriptionMode="tooltip" grouped={true} />
+      )}
     </>
   );
 };
@@ -459,6 +479,11 @@ export const PostProcessingSettings: React.FC = () => {
       <SettingsGroup title={t("settings.postProcessing.prompts.title")}>
         <PostProcessingSettingsPrompts />
       </SettingsGroup>
+
+      <SettingsGroup>
+        <ContextRules descriptionMode="inline" grouped={true} />
+        <BindingPostProcess descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
     </div>
   );
 };
@@ -0,0 +1,44 @@
+import React, { useEffect, useState } from "react";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+
+// Visually hidden but still exposed to assistive technology
+const visuallyHidden: React.CSSProperties = {
+  position: "absolute",
+  width: 1,
+  height: 1,
+  margin: -1,
+  padding: 0,
+  overflow: "hidden",
+  clip: "rect(0, 0, 0, 0)",
+  whiteSpace: "nowrap",
+  border: 0,
+};
+
+/**
+ * Live region for the backend's screen reader announcements. macOS and
+ * Windows announce natively, so this only receives events on Linux, and only
+ * in the one window the backend picked.
+ */
+export const LiveAnnouncer: React.FC = () => {
+  const [message, setMessage] = useState("");
+
+  useEffect(() => {
+    const unlistenPromise = getCurrentWebviewWindow().listen<string>(
+      "accessibility-announcement",
+      (event) => {
+        // Clear first so repeating the same message is announced again
+        setMessage("");
+        requestAnimationFrame(() => setMessage(event.payload));
+      },
+    );
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  return (
+    <div role="status" aria-live="assertive" style={visuallyHidden}>
+      {message}
+    </div>
+  );
+};
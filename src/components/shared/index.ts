@@ -1,2 +1,3 @@
 export { default as ProgressBar } from "./ProgressBar";
 export type { ProgressData } from "./ProgressBar";
+export { LiveAnnouncer } from "./LiveAnnouncer";
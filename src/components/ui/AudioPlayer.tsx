@@ -1,220 +1,277 @@
-import React, { useState, useRef, useEffect, useCallback } from "react";
+import React, {
+  useState,
+  useRef,
+  useEffect,
+  useCallback,
+  forwardRef,
+  useImperativeHandle,
+} from "react";
 import { Play, Pause } from "lucide-react";
 
+// Playback speeds the speed button cycles through
+const SPEEDS = [0.5, 0.75, 1, 1.25, 1.5, 2];
+
 interface AudioPlayerProps {
   src: string;
   className?: string;
+  /** Shows a button for playing slower or faster, keeping the pitch */
+  speedControl?: boolean;
+  /** Called as the playhead moves, with the time in seconds */
+  onTimeChange?: (time: number) => void;
+}
+
+export interface AudioPlayerHandle {
+  /** Moves the playhead to `time` seconds and plays from there */
+  seek: (time: number) => void;
 }
 
-export const AudioPlayer: React.FC<AudioPlayerProps> = ({
-  src,
-  className = "",
-}) => {
-  const [isPlaying, setIsPlaying] = useState(false);
-  const [duration, setDuration] = useState(0);
-  const [currentTime, setCurrentTime] = useState(0);
-  const [isDragging, setIsDragging] = useState(false);
-
-  const audioRef = useRef<HTMLAudioElement>(null);
-  const animationRef = useRef<number>();
-  const dragTimeRef = useRef<number>(0);
-
-  // Use refs to avoid stale closures in animation loop
-  const isPlayingRef = useRef(false);
-  const isDraggingRef = useRef(false);
-
-  // Keep refs in sync with state
-  useEffect(() => {
-    isPlayingRef.current = isPlaying;
-  }, [isPlaying]);
-
-  useEffect(() => {
-    isDraggingRef.current = isDragging;
-  }, [isDragging]);
-
-  // Stable animation loop with no dependencies
-  const tick = useCallback(() => {
-    if (audioRef.current && !isDraggingRef.current) {
-      const time = audioRef.current.currentTime;
-      setCurrentTime(time);
-    }
-
-    if (isPlayingRef.current) {
-      animationRef.current = requestAnimationFrame(tick);
-    }
-  }, []); // Empty dependency array is key!
-
-  // Manage animation loop lifecycle
-  useEffect(() => {
-    if (isPlaying && !isDragging) {
-      // Only start if not already running
-      if (!animationRef.current) {
+export const AudioPlayer = forwardRef<AudioPlayerHandle, AudioPlayerProps>(
+  ({ src, className = "", speedControl = false, onTimeChange }, ref) => {
+    const [isPlaying, setIsPlaying] = useState(false);
+    const [duration, setDuration] = useState(0);
+    const [currentTime, setCurrentTime] = useState(0);
+    const [isDragging, setIsDragging] = useState(false);
+    const [speed, setSpeed] = useState(1);
+
+    const audioRef = useRef<HTMLAudioElement>(null);
+    const animationRef = useRef<number>();
+    const dragTimeRef = useRef<number>(0);
+
+    // Use refs to avoid stale closures in animation loop
+    const isPlayingRef = useRef(false);
+    const isDraggingRef = useRef(false);
+
+    useImperativeHandle(ref, () => ({
+      seek: (time: number) => {
+        const audio = audioRef.current;
+        if (!audio) return;
+        audio.currentTime = time;
+        setCurrentTime(time);
+        audio.play().catch((error) => console.error("Playback failed:", error));
+      },
+    }));
+
+    useEffect(() => {
+      onTimeChange?.(currentTime);
+    }, [currentTime, onTimeChange]);
+
+    useEffect(() => {
+      if (audioRef.current) {
+        // The default rate carries over when the source changes
+        audioRef.current.defaultPlaybackRate = speed;
+        audioRef.current.playbackRate = speed;
+      }
+    }, [speed]);
+
+    const cycleSpeed = () => {
+      setSpeed(SPEEDS[(SPEEDS.indexOf(speed) + 1) % SPEEDS.length]);
+    };
+
+    // Keep refs in sync with state
+    useEffect(() => {
+      isPlayingRef.current = isPlaying;
+    }, [isPlaying]);
+
+    useEffect(() => {
+      isDraggingRef.current = isDragging;
+    }, [isDragging]);
+
+    // Stable animation loop with no dependencies
+    const tick = useCallback(() => {
+      if (audioRef.current && !isDraggingRef.current) {
+        const time = audioRef.current.currentTime;
+        setCurrentTime(time);
+      }
+
+      if (isPlayingRef.current) {
         animationRef.current = requestAnimationFrame(tick);
       }
-    } else {
-      // Stop animation loop
-      if (animationRef.current) {
-        cancelAnimationFrame(animationRef.current);
-        animationRef.current = undefined;
+    }, []); // Empty dependency array is key!
+
+    // Manage animation loop lifecycle
+    useEffect(() => {
+      if (isPlaying && !isDragging) {
+        // Only start if not already running
+        if (!animationRef.current) {
+          animationRef.current = requestAnimationFrame(tick);
+        }
+      } else {
+        // Stop animation loop
+        if (animationRef.current) {
+          cancelAnimationFrame(animationRef.current);
+          animationRef.current = undefined;
+        }
+      }
+
+      return () => {
+        if (animationRef.current) {
+          cancelAnimationFrame(animationRef.current);
+          animationRef.current = undefined;
+        }
+      };
+    }, [isPlaying, isDragging, tick]);
+
+    // Audio event handlers
+    useEffect(() => {
+      const audio = audioRef.current;
+      if (!audio) return;
+
+      const handleLoadedMetadata = () => {
+        setDuration(audio.duration || 0);
+        setCurrentTime(0);
+      };
+
+      const handleEnded = () => {
+        setIsPlaying(false);
+        setCurrentTime(audio.duration || 0);
+      };
+
+      const handlePlay = () => setIsPlaying(true);
+      const handlePause = () => setIsPlaying(false);
+
+      audio.addEventListener("loadedmetadata", handleLoadedMetadata);
+      audio.addEventListener("ended", handleEnded);
+      audio.addEventListener("play", handlePlay);
+      audio.addEventListener("pause", handlePause);
+
+      return () => {
+        audio.removeEventListener("loadedmetadata", handleLoadedMetadata);
+        audio.removeEventListener("ended", handleEnded);
+        audio.removeEventListener("play", handlePlay);
+        audio.removeEventListener("pause", handlePause);
+      };
+    }, []);
+
+    // Global drag handlers
+    const handleMouseUp = useCallback(() => {
+      if (isDragging) {
+        setIsDragging(false);
+        if (audioRef.current) {
+          audioRef.current.currentTime = dragTimeRef.current;
+          setCurrentTime(dragTimeRef.current);
+        }
       }
-    }
+    }, [isDragging]);
+
+    useEffect(() => {
+      if (isDragging) {
+        document.addEventListener("mouseup", handleMouseUp);
+        document.addEventListener("touchend", handleMouseUp);
 
-    return () => {
-      if (animationRef.current) {
-        cancelAnimationFrame(animationRef.current);
-        animationRef.current = undefined;
+        return () => {
+          document.removeEventListener("mouseup", handleMouseUp);
+          document.removeEventListener("touchend", handleMouseUp);
+        };
+      }
+    }, [isDragging, handleMouseUp]);
+
+    const togglePlay = async () => {
+      const audio = audioRef.current;
+      if (!audio) return;
+
+      try {
+        if (isPlaying) {
+          audio.pause();
+        } else {
+          await audio.play();
+        }
+      } catch (error) {
+        console.error("Playback failed:", error);
       }
     };
-  }, [isPlaying, isDragging, tick]);
 
-  // Audio event handlers
-  useEffect(() => {
-    const audio = audioRef.current;
-    if (!audio) return;
+    const handleSeek = (e: React.ChangeEvent<HTMLInputElement>) => {
+      const newTime = parseFloat(e.target.value);
+      dragTimeRef.current = newTime;
+      setCurrentTime(newTime);
 
-    const handleLoadedMetadata = () => {
-      setDuration(audio.duration || 0);
-      setCurrentTime(0);
+      if (!isDragging && audioRef.current) {
+        audioRef.current.currentTime = newTime;
+      }
     };
 
-    const handleEnded = () => {
-      setIsPlaying(false);
-      setCurrentTime(audio.duration || 0);
+    const handleSliderMouseDown = () => {
+      setIsDragging(true);
     };
 
-    const handlePlay = () => setIsPlaying(true);
-    const handlePause = () => setIsPlaying(false);
+    const handleSliderTouchStart = () => {
+      setIsDragging(true);
+    };
 
-    audio.addEventListener("loadedmetadata", handleLoadedMetadata);
-    audio.addEventListener("ended", handleEnded);
-    audio.addEventListener("play", handlePlay);
-    audio.addEventListener("pause", handlePause);
+    const formatTime = (time: number): string => {
+      if (!isFinite(time)) return "0:00";
 
-    return () => {
-      audio.removeEventListener("loadedmetadata", handleLoadedMetadata);
-      audio.removeEventListener("ended", handleEnded);
-      audio.removeEventListener("play", handlePlay);
-      audio.removeEventListener("pause", handlePause);
+      const minutes = Math.floor(time / 60);
+      const seconds = Math.floor(time % 60);
+      return `${minutes}:${seconds.toString().padStart(2, "0")}`;
     };
-  }, []);
 
-  // Global drag handlers
-  const handleMouseUp = useCallback(() => {
-    if (isDragging) {
-      setIsDragging(false);
-      if (audioRef.current) {
-        audioRef.current.currentTime = dragTimeRef.current;
-        setCurrentTime(dragTimeRef.current);
-      }
-    }
-  }, [isDragging]);
+    // Fix playhead positioning with better edge case handling
+    const getProgressPercent = (): number => {
+      if (duration <= 0) return 0;
 
-  useEffect(() => {
-    if (isDragging) {
-      document.addEventListener("mouseup", handleMouseUp);
-      document.addEventListener("touchend", handleMouseUp);
-
-      return () => {
-        document.removeEventListener("mouseup", handleMouseUp);
-        document.removeEventListener("touchend", handleMouseUp);
-      };
-    }
-  }, [isDragging, handleMouseUp]);
+      // Handle the end case - if we're within 0.1 seconds of the end, show 100%
+      if (duration - currentTime < 0.1) return 100;
 
-  const togglePlay = async () => {
-    const audio = audioRef.current;
-    if (!audio) return;
+      const percent = (currentTime / duration) * 100;
+      return Math.min(100, Math.max(0, percent));
+    };
 
-    try {
-      if (isPlaying) {
-        audio.pause();
-      } else {
-        await audio.play();
-      }
-    } catch (error) {
-      console.error("Playback failed:", error);
-    }
-  };
-
-  const handleSeek = (e: React.ChangeEvent<HTMLInputElement>) => {
-    const newTime = parseFloat(e.target.value);
-    dragTimeRef.current = newTime;
-    setCurrentTime(newTime);
-
-    if (!isDragging && audioRef.current) {
-      audioRef.current.currentTime = newTime;
-    }
-  };
-
-  const handleSliderMouseDown = () => {
-    setIsDragging(true);
-  };
-
-  const handleSliderTouchStart = () => {
-    setIsDragging(true);
-  };
-
-  const formatTime = (time: number): string => {
-    if (!isFinite(time)) return "0:00";
-
-    const minutes = Math.floor(time / 60);
-    const seconds = Math.floor(time % 60);
-    return `${minutes}:${seconds.toString().padStart(2, "0")}`;
-  };
-
-  // Fix playhead positioning with better edge case handling
-  const getProgressPercent = (): number => {
-    if (duration <= 0) return 0;
-
-    // Handle the end case - if we're within 0.1 seconds of the end, show 100%
-    if (duration - currentTime < 0.1) return 100;
-
-    const percent = (currentTime / duration) * 100;
-    return Math.min(100, Math.max(0, percent));
-  };
-
-  const progressPercent = getProgressPercent();
-
-  return (
-    <div className={`flex items-center gap-3 ${className}`}>
-      <audio ref={audioRef} src={src} preload="metadata" />
-
-      <button
-        onClick={togglePlay}
-        className="transition-colors cursor-pointer text-text hover:text-logo-primary"
-        aria-label={isPlaying ? "Pause" : "Play"}
-      >
-        {isPlaying ? (
-          <Pause width={20} height={20} fill="currentColor" />
-        ) : (
-          <Play width={20} height={20} fill="currentColor" />
+    const progressPercent = getProgressPercent();
+
+    return (
+      <div className={`flex items-center gap-3 ${className}`}>
+        <audio ref={audioRef} src={src} preload="metadata" />
+
+        <button
+          onClick={togglePlay}
+          className="transition-colors cursor-pointer text-text hover:text-logo-primary"
+          aria-label={isPlaying ? "Pause" : "Play"}
+        >
+          {isPlaying ? (
+            <Pause width={20} height={20} fill="currentColor" />
+          ) : (
+            <Play width={20} height={20} fill="currentColor" />
+          )}
+        </button>
+
+        <div className="flex-1 flex items-center gap-2">
+          <span className="text-xs text-text/60 min-w-[30px] tabular-nums">
+            {formatTime(currentTime)}
+          </span>
+
+          <input
+            type="range"
+            min="0"
+            max={duration || 0}
+            step="0.01"
+            value={currentTime}
+            onChange={handleSeek}
+            onMouseDown={handleSliderMouseDown}
+            onTouchStart={handleSliderTouchStart}
+            className={`flex-1 h-1 rounded-lg appearance-none cursor-pointer focus:outline-none focus:ring-1 focus:ring-logo-primary ${progressPercent >= 99.5 ? "[&::-webkit-slider-thumb]:translate-x-0.5 [&::-moz-range-thumb]:translate-x-0.5" : ""}`}
+            style={{
+              background: `linear-gradient(to right, #FAA2CA 0%, #FAA2CA ${progressPercent}%, rgba(128, 128, 128, 0.2) ${progressPercent}%, rgba(128, 128, 128, 0.2) 100%)`,
+            }}
+          />
+
+          <span className="text-xs text-text/60 min-w-[30px] tabular-nums">
+            {formatTime(duration)}
+          </span>
+        </div>
+
+        {speedControl && (
+          <button
+            onClick={cycleSpeed}
+            className="text-xs font-semibold text-text/60 hover:text-logo-primary min-w-[36px] tabular-nums transition-colors cursor-pointer"
+            aria-label={`Playback speed ${speed}x`}
+          >
+            {speed}x
+          </button>
         )}
-      </button>
-
-      <div className="flex-1 flex items-center gap-2">
-        <span className="text-xs text-text/60 min-w-[30px] tabular-nums">
-          {formatTime(currentTime)}
-        </span>
-
-        <input
-          type="range"
-          min="0"
-          max={duration || 0}
-          step="0.01"
-          value={currentTime}
-          onChange={handleSeek}
-          onMouseDown={handleSliderMouseDown}
-          onTouchStart={handleSliderTouchStart}
-          className={`flex-1 h-1 rounded-lg appearance-none cursor-pointer focus:outline-none focus:ring-1 focus:ring-logo-primary ${progressPercent >= 99.5 ? "[&::-webkit-slider-thumb]:translate-x-0.5 [&::-moz-range-thumb]:translate-x-0.5" : ""}`}
-          style={{
-            background: `linear-gradient(to right, #FAA2CA 0%, #FAA2CA ${progressPercent}%, rgba(128, 128, 128, 0.2) ${progressPercent}%, rgba(128, 128, 128, 0.2) 100%)`,
-          }}
-        />
-
-        <span className="text-xs text-text/60 min-w-[30px] tabular-nums">
-          {formatTime(duration)}
-        </span>
       </div>
-    </div>
-  );
-};
+    );
+  },
+);
+
+AudioPlayer.displayName = "AudioPlayer";
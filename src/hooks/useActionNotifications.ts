@@ -0,0 +1,123 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { useSettingsStore } from "@/stores/settingsStore";
+
+type NotificationKind =
+  | "transcription_failed"
+  | "paste_failed"
+  | "conferencing_mic"
+  | "dead_input"
+  | "microphone_switched"
+  | "queued_offline"
+  | "pending_transcribed"
+  | "transcribed"
+  | "post_process_failed"
+  | "model_unloaded";
+type NotificationAction =
+  | "retry_transcription"
+  | "repaste"
+  | "copy_transcript"
+  | "switch_to_local";
+
+interface AppNotification {
+  kind: NotificationKind;
+  detail: string | null;
+  actions: NotificationAction[];
+}
+
+const runAction = async (action: NotificationAction) => {
+  const result =
+    action === "retry_transcription"
+      ? await commands.retryLastTranscription()
+      : action === "repaste"
+        ? await commands.repasteTranscript()
+        : action === "switch_to_local"
+          ? await commands.switchToLocalTranscription()
+          : await commands.copyUnpastedTranscript();
+  if (result.status === "error") {
+    toast.error(result.error);
+  }
+  // Switching turns the remote engines off in the settings
+  if (action === "switch_to_local") {
+    await useSettingsStore.getState().refreshSettings();
+  }
+};
+
+/**
+ * Shows backend notifications (`app-notification`) as toasts whose buttons
+ * call back into the matching command.
+ */
+export const useActionNotifications = () => {
+  const { t } = useTranslation();
+
+  useEffect(() => {
+    const unlistenPromise = listen<AppNotification>(
+      "app-notification",
+      (event) => {
+        const { kind, detail, actions } = event.payload;
+        const [primary, secondary] = actions.map((action) => ({
+          label: t(`notifications.actions.${action}`),
+          onClick: () => runAction(action),
+        }));
+
+        // Warnings about the microphone, with a fixed hint instead of a detail
+        if (kind === "conferencing_mic" || kind === "dead_input") {
+          toast.warning(t(`notifications.${kind}`, { app: detail }), {
+            description: t(`notifications.${kind}_hint`),
+          });
+          return;
+        }
+
+        if (kind === "queued_offline") {
+          toast.info(t("notifications.queued_offline"), {
+            description: t("notifications.queued_offline_hint"),
+          });
+          return;
+        }
+
+        if (kind === "pending_transcribed") {
+          toast.success(t("notifications.pending_transcribed"), {
+            description: detail ?? undefined,
+            duration: 10000,
+            action: primary,
+            cancel: secondary,
+          });
+          return;
+        }
+
+        if (kind === "transcribed") {
+          toast.success(t("notifications.transcribed"), {
+            description: detail ?? undefined,
+          });
+          return;
+        }
+
+        if (kind === "post_process_failed" || kind === "model_unloaded") {
+          toast.info(t(`notifications.${kind}`), {
+            description: t(`notifications.${kind}_hint`),
+          });
+          return;
+        }
+
+        if (kind === "microphone_switched") {
+          toast.info(t("notifications.microphone_switched", { mic: detail }));
+          return;
+        }
+
+        toast.error(t(`notifications.${kind}`), {
+          description: detail ?? undefined,
+          duration: 10000,
+          action: primary,
+          cancel: secondary,
+        });
+      },
+    );
+
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, [t]);
+};
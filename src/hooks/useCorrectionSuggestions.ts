@@ -0,0 +1,35 @@
+import { useEffect, useState } from "react";
+import { commands, type WordReplacement } from "@/bindings";
+
+/** Wait for typing to pause before comparing the texts */
+const SUGGEST_DELAY_MS = 400;
+
+/**
+ * Corrections made from `original` to `edited` that could be learned as
+ * replacement rules, and a way to learn one.
+ */
+export const useCorrectionSuggestions = (original: string, edited: string) => {
+  const [suggestions, setSuggestions] = useState<WordReplacement[]>([]);
+
+  useEffect(() => {
+    if (original === edited) {
+      setSuggestions([]);
+      return;
+    }
+    const timer = setTimeout(() => {
+      commands.suggestWordReplacements(original, edited).then(setSuggestions);
+    }, SUGGEST_DELAY_MS);
+    return () => clearTimeout(timer);
+  }, [original, edited]);
+
+  const learn = async (suggestion: WordReplacement) => {
+    const result = await commands.learnWordReplacement(suggestion);
+    if (result.status === "ok") {
+      setSuggestions((current) =>
+        current.filter((other) => other.from !== suggestion.from),
+      );
+    }
+  };
+
+  return { suggestions, learn };
+};
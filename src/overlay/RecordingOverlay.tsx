@@ -7,10 +7,51 @@ import {
   CancelIcon,
 } from "../components/icons";
 import "./RecordingOverlay.css";
-import { commands } from "@/bindings";
+import { commands, type RecordingMode } from "@/bindings";
+import { LiveAnnouncer } from "@/components/shared";
 import { syncLanguageFromSettings } from "@/i18n";
 
-type OverlayState = "recording" | "transcribing";
+type OverlayState = "recording" | "transcribing" | "notice";
+
+interface Notice {
+  kind: string;
+  detail: string | null;
+}
+
+interface RecordingStatus {
+  elapsed_secs: number;
+  speech_ratio: number;
+  estimated_words: number;
+  mode: RecordingMode | null;
+}
+
+interface CountdownTick {
+  binding_id: string;
+  remaining_secs: number;
+}
+
+interface TranscriptionProgress {
+  completed: number;
+  total: number;
+  percent: number;
+  current_chunk: number;
+  eta_secs: number | null;
+  task: string | null;
+}
+
+interface TranscriptionRetry {
+  retry: number;
+  max_retries: number;
+  delay_secs: number;
+}
+
+const formatElapsed = (secs: number) => {
+  const whole = Math.floor(secs);
+  return `${Math.floor(whole / 60)}:${String(whole % 60).padStart(2, "0")}`;
+};
+
+// The overlay only fits the last few words of a partial transcript
+const PARTIAL_CHARS = 40;
 
 const RecordingOverlay: React.FC = () => {
   const { t } = useTranslation();
@@ -18,6 +59,14 @@ const RecordingOverlay: React.FC = () => {
   const [state, setState] = useState<OverlayState>("recording");
   const [levels, setLevels] = useState<number[]>(Array(16).fill(0));
   const smoothedLevelsRef = useRef<number[]>(Array(16).fill(0));
+  const [status, setStatus] = useState<RecordingStatus | null>(null);
+  const [progress, setProgress] = useState<TranscriptionProgress | null>(null);
+  const [retry, setRetry] = useState<TranscriptionRetry | null>(null);
+  const [cancelArmed, setCancelArmed] = useState(false);
+  const [countdown, setCountdown] = useState<number | null>(null);
+  const [partial, setPartial] = useState("");
+  const [templateSlot, setTemplateSlot] = useState<string | null>(null);
+  const [notice, setNotice] = useState<Notice | null>(null);
 
   useEffect(() => {
     const setupEventListeners = async () => {
@@ -26,10 +75,29 @@ const RecordingOverlay: React.FC = () => {
         // Sync language from settings each time overlay is shown
         await syncLanguageFromSettings();
         const overlayState = event.payload as OverlayState;
+        if (overlayState === "recording") {
+          setStatus(null);
+        }
+        setCancelArmed(false);
+        setCountdown(null);
+        setPartial("");
+        setProgress(null);
+        setRetry(null);
         setState(overlayState);
         setIsVisible(true);
       });
 
+      // Listen for notifications shown in the overlay for a few seconds
+      const unlistenNotice = await listen<Notice>(
+        "show-notice",
+        async (event) => {
+          await syncLanguageFromSettings();
+          setNotice(event.payload);
+          setState("notice");
+          setIsVisible(true);
+        },
+      );
+
       // Listen for hide-overlay event from Rust
       const unlistenHide = await listen("hide-overlay", () => {
         setIsVisible(false);
@@ -49,11 +117,75 @@ const RecordingOverlay: React.FC = () => {
         setLevels(smoothed.slice(0, 9));
       });
 
+      // Listen for elapsed time and speech ratio while recording
+      const unlistenStatus = await listen<RecordingStatus>(
+        "recording-status",
+        (event) => {
+          setStatus(event.payload);
+        },
+      );
+
+      // Listen for progress on long transcriptions
+      const unlistenProgress = await listen<TranscriptionProgress>(
+        "transcription-progress",
+        (event) => {
+          setProgress(event.payload);
+        },
+      );
+
+      // Listen for remote requests being retried
+      const unlistenRetry = await listen<TranscriptionRetry>(
+        "transcription-retry",
+        (event) => {
+          setRetry(event.payload);
+        },
+      );
+
+      // Listen for the push-to-talk release-to-cancel gesture
+      const unlistenCancelArmed = await listen<boolean>(
+        "cancel-armed",
+        (event) => {
+          setCancelArmed(event.payload);
+        },
+      );
+
+      // Listen for the countdown before recording starts
+      const unlistenCountdown = await listen<CountdownTick>(
+        "recording-countdown",
+        (event) => {
+          setCountdown(event.payload.remaining_secs);
+        },
+      );
+
+      // Listen for partial transcripts from realtime transcription
+      const unlistenPartial = await listen<string>(
+        "transcription-partial",
+        (event) => {
+          setPartial(event.payload);
+        },
+      );
+
+      // Listen for the template slot the next dictation fills
+      const unlistenTemplateSlot = await listen<string | null>(
+        "template-slot",
+        (event) => {
+          setTemplateSlot(event.payload);
+        },
+      );
+
       // Cleanup function
       return () => {
         unlistenShow();
+        unlistenNotice();
         unlistenHide();
         unlistenLevel();
+        unlistenStatus();
+        unlistenProgress();
+        unlistenRetry();
+        unlistenCancelArmed();
+        unlistenCountdown();
+        unlistenPartial();
+        unlistenTemplateSlot();
       };
     };
 
@@ -61,7 +193,7 @@ const RecordingOverlay: React.FC = () => {
   }, []);
 
   const getIcon = () => {
-    if (state === "recording") {
+    if (state === "recording" || state === "notice") {
       return <MicrophoneIcon />;
     } else {
       return <TranscriptionIcon />;
@@ -69,31 +201,116 @@ const RecordingOverlay: React.FC = () => {
   };
 
   return (
-    <div className={`recording-overlay ${isVisible ? "fade-in" : ""}`}>
+    <div
+      className={`recording-overlay ${isVisible ? "fade-in" : ""}`}
+      data-tauri-drag-region
+    >
+      <LiveAnnouncer />
       <div className="overlay-left">{getIcon()}</div>
 
       <div className="overlay-middle">
-        {state === "recording" && (
-          <div className="bars-container">
-            {levels.map((v, i) => (
-              <div
-                key={i}
-                className="bar"
-                style={{
-                  height: `${Math.min(20, 4 + Math.pow(v, 0.7) * 16)}px`, // Cap at 20px max height
-                  transition: "height 60ms ease-out, opacity 120ms ease-out",
-                  opacity: Math.max(0.2, v * 1.7), // Minimum opacity for visibility
-                }}
-              />
-            ))}
+        {state === "recording" && countdown !== null && (
+          <div className="transcribing-text">{countdown}</div>
+        )}
+        {state === "recording" && countdown === null && cancelArmed && (
+          <div className="transcribing-text">
+            {t("overlay.releaseToCancel")}
           </div>
         )}
+        {state === "recording" &&
+          countdown === null &&
+          !cancelArmed &&
+          partial && (
+            <div className="partial-text">{partial.slice(-PARTIAL_CHARS)}</div>
+          )}
+        {state === "recording" &&
+          countdown === null &&
+          !cancelArmed &&
+          !partial &&
+          templateSlot && (
+            <div className="partial-text">
+              {t("overlay.templateSlot", { slot: templateSlot })}
+            </div>
+          )}
+        {state === "recording" &&
+          countdown === null &&
+          !cancelArmed &&
+          !partial &&
+          !templateSlot && (
+            <div className="bars-container">
+              {levels.map((v, i) => (
+                <div
+                  key={i}
+                  className="bar"
+                  style={{
+                    height: `${Math.min(20, 4 + Math.pow(v, 0.7) * 16)}px`, // Cap at 20px max height
+                    transition: "height 60ms ease-out, opacity 120ms ease-out",
+                    opacity: Math.max(0.2, v * 1.7), // Minimum opacity for visibility
+                  }}
+                />
+              ))}
+            </div>
+          )}
         {state === "transcribing" && (
-          <div className="transcribing-text">{t("overlay.transcribing")}</div>
+          <div className="transcribing-text">
+            {retry
+              ? t("overlay.retrying", {
+                  retry: retry.retry,
+                  max: retry.max_retries,
+                })
+              : progress && progress.total > 1
+                ? progress.eta_secs !== null
+                  ? t("overlay.transcribingEta", {
+                      percent: Math.round(progress.percent),
+                      eta: formatElapsed(progress.eta_secs),
+                    })
+                  : t("overlay.transcribingProgress", {
+                      percent: Math.round(progress.percent),
+                    })
+                : t("overlay.transcribing")}
+          </div>
+        )}
+        {state === "notice" && notice && (
+          <div className="partial-text">
+            {t(`notifications.${notice.kind}`, {
+              app: notice.detail,
+              mic: notice.detail,
+            })}
+          </div>
         )}
+        {state === "transcribing" &&
+          !retry &&
+          progress &&
+          progress.total > 1 && (
+            <div
+              className="progress-track"
+              title={t("overlay.chunk", {
+                current: progress.current_chunk,
+                total: progress.total,
+              })}
+            >
+              <div
+                className="progress-fill"
+                style={{ width: `${progress.percent}%` }}
+              />
+            </div>
+          )}
       </div>
 
       <div className="overlay-right">
+        {state === "recording" && status?.mode && (
+          <div
+            className="mode-badge"
+            title={t(`overlay.mode.${status.mode}.description`)}
+          >
+            {t(`overlay.mode.${status.mode}.label`)}
+          </div>
+        )}
+        {state === "recording" && status && (
+          <div className="elapsed-time">
+            {formatElapsed(status.elapsed_secs)}
+          </div>
+        )}
         {state === "recording" && (
           <div
             className="cancel-button"
@@ -0,0 +1,104 @@
+import { listen } from "@tauri-apps/api/event";
+import React, { useEffect, useRef, useState } from "react";
+import { useTranslation } from "react-i18next";
+import "./PreviewPopup.css";
+import { commands } from "@/bindings";
+import { syncLanguageFromSettings } from "@/i18n";
+import { useCorrectionSuggestions } from "@/hooks/useCorrectionSuggestions";
+
+const PreviewPopup: React.FC = () => {
+  const { t } = useTranslation();
+  const [text, setText] = useState("");
+  const [original, setOriginal] = useState("");
+  const textareaRef = useRef<HTMLTextAreaElement>(null);
+  const { suggestions, learn } = useCorrectionSuggestions(original, text);
+
+  const showText = (value: string) => {
+    setText(value);
+    setOriginal(value);
+    // Put the caret at the end, ready to fix the last words
+    requestAnimationFrame(() => {
+      const textarea = textareaRef.current;
+      if (!textarea) return;
+      textarea.focus();
+      textarea.setSelectionRange(value.length, value.length);
+    });
+  };
+
+  useEffect(() => {
+    // The first transcript can arrive before this window has loaded
+    commands.getPreviewText().then((pending) => {
+      if (pending !== null) showText(pending);
+    });
+
+    const unlistenPromise = listen<string>("show-preview", async (event) => {
+      await syncLanguageFromSettings();
+      showText(event.payload);
+    });
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  const confirm = () => commands.confirmPreview(text);
+  const cancel = () => commands.cancelPreview();
+
+  const handleKeyDown = (event: React.KeyboardEvent) => {
+    if (event.key === "Escape") {
+      event.preventDefault();
+      cancel();
+    } else if (event.key === "Enter" && (event.metaKey || event.ctrlKey)) {
+      event.preventDefault();
+      confirm();
+    }
+  };
+
+  return (
+    <div className="preview-popup" onKeyDown={handleKeyDown}>
+      <div className="preview-header" data-tauri-drag-region>
+        {t("preview.title")}
+      </div>
+      <textarea
+        ref={textareaRef}
+        className="preview-text"
+        value={text}
+        onChange={(event) => setText(event.target.value)}
+        spellCheck
+      />
+      {suggestions.length > 0 && (
+        <div className="preview-suggestions">
+          <span className="preview-suggestions-label">
+            {t("preview.learn")}
+          </span>
+          {suggestions.map((suggestion) => (
+            <button
+              key={suggestion.from}
+              className="preview-button"
+              onClick={() => learn(suggestion)}
+            >
+              {t("preview.learnRule", {
+                from: suggestion.from,
+                to: suggestion.to,
+              })}
+            </button>
+          ))}
+        </div>
+      )}
+      <div className="preview-actions">
+        <span className="preview-hint">{t("preview.hint")}</span>
+        <button className="preview-button" onClick={cancel}>
+          {t("preview.cancel")}
+        </button>
+        <button
+          className="preview-button primary"
+          onClick={confirm}
+          disabled={text.trim() === ""}
+        >
+          {t("preview.confirm")}
+        </button>
+      </div>
+    </div>
+  );
+};
+
+export default PreviewPopup;
@@ -1,8 +1,26 @@
 import { create } from "zustand";
 import { subscribeWithSelector } from "zustand/middleware";
-import type { AppSettings as Settings, AudioDevice } from "@/bindings";
+import type {
+  AppSettings as Settings,
+  AudioDevice,
+  CloudTranscription,
+  ContextRule,
+  CustomSounds,
+  DeepgramConfig,
+  DictationTemplate,
+  ModelUnloadTimeout,
+  NotificationPreferences,
+  PasteMethod,
+  PreprocessStep,
+  RealtimeEndpoint,
+  ShareDestination,
+  TextRule,
+  WordReplacement,
+} from "@/bindings";
 import { commands } from "@/bindings";
 
+export type SoundType = "start" | "stop" | "error" | "done" | "countdown";
+
 interface SettingsStore {
   settings: Settings | null;
   defaultSettings: Settings | null;
@@ -10,7 +28,7 @@ interface SettingsStore {
   isUpdating: Record<string, boolean>;
   audioDevices: AudioDevice[];
   outputDevices: AudioDevice[];
-  customSounds: { start: boolean; stop: boolean };
+  customSounds: CustomSounds;
   postProcessModelOptions: Record<string, string[]>;
 
   // Actions
@@ -28,7 +46,7 @@ interface SettingsStore {
   resetBinding: (id: string) => Promise<void>;
   getSetting: <K extends keyof Settings>(key: K) => Settings[K] | undefined;
   isUpdatingKey: (key: string) => boolean;
-  playTestSound: (soundType: "start" | "stop") => Promise<void>;
+  playTestSound: (soundType: SoundType) => Promise<void>;
   checkCustomSounds: () => Promise<void>;
   setPostProcessProvider: (providerId: string) => Promise<void>;
   updatePostProcessSetting: (
@@ -55,7 +73,7 @@ interface SettingsStore {
   setUpdating: (key: string, updating: boolean) => void;
   setAudioDevices: (devices: AudioDevice[]) => void;
   setOutputDevices: (devices: AudioDevice[]) => void;
-  setCustomSounds: (sounds: { start: boolean; stop: boolean }) => void;
+  setCustomSounds: (sounds: CustomSounds) => void;
 }
 
 // Note: Default settings are now fetched from Rust via commands.getDefaultSettings()
@@ -107,6 +125,9 @@ const settingUpdaters: {
     commands.changeSelectedLanguageSetting(value as string),
   overlay_position: (value) =>
     commands.changeOverlayPositionSetting(value as string),
+  overlay_placement: (value) =>
+    commands.changeOverlayPlacementSetting(value as string),
+  overlay_scale: (value) => commands.changeOverlayScaleSetting(value as number),
   debug_mode: (value) => commands.changeDebugModeSetting(value as boolean),
   custom_words: (value) => commands.updateCustomWords(value as string[]),
   word_correction_threshold: (value) =>
@@ -114,7 +135,11 @@ const settingUpdaters: {
   paste_method: (value) => commands.changePasteMethodSetting(value as string),
   clipboard_handling: (value) =>
     commands.changeClipboardHandlingSetting(value as string),
+  transcription_context: (value) =>
+    commands.changeTranscriptionContextSetting(value as string),
   history_limit: (value) => commands.updateHistoryLimit(value as number),
+  post_process_temperature: (value) =>
+    commands.changePostProcessTemperatureSetting(value as number | null),
   post_process_enabled: (value) =>
     commands.changePostProcessEnabledSetting(value as boolean),
   post_process_selected_prompt_id: (value) =>
@@ -123,8 +148,167 @@ const settingUpdaters: {
     commands.changeMuteWhileRecordingSetting(value as boolean),
   append_trailing_space: (value) =>
     commands.changeAppendTrailingSpaceSetting(value as boolean),
+  replace_selection: (value) =>
+    commands.changeReplaceSelectionSetting(value as boolean),
+  verify_paste: (value) => commands.changeVerifyPasteSetting(value as boolean),
+  hands_free_silence_secs: (value) =>
+    commands.changeHandsFreeSilenceSecsSetting(value as number),
   log_level: (value) => commands.setLogLevel(value as any),
   app_language: (value) => commands.changeAppLanguageSetting(value as string),
+  code_switching: (value) =>
+    commands.changeCodeSwitchingSetting(value as boolean),
+  retranscribe_model: (value) =>
+    commands.changeRetranscribeModelSetting(value as string | null),
+  retranscribe_max_latency_ms: (value) =>
+    commands.changeRetranscribeMaxLatencySetting(value as number),
+  language_lock_minutes: (value) =>
+    commands.changeLanguageLockSetting(value as number),
+  tts_readback_enabled: (value) =>
+    commands.changeTtsReadbackSetting(value as boolean),
+  tts_piper_path: (value) =>
+    commands.changeTtsPiperPathSetting((value as string | null) ?? ""),
+  tts_voice_path: (value) =>
+    commands.changeTtsVoicePathSetting((value as string | null) ?? ""),
+  low_power_enabled: (value) =>
+    commands.changeLowPowerEnabledSetting(value as boolean),
+  low_power_model: (value) =>
+    commands.changeLowPowerModelSetting(value as string | null),
+  low_power_pause_always_on_microphone: (value) =>
+    commands.changeLowPowerPauseAlwaysOnMicrophoneSetting(value as boolean),
+  low_power_model_unload_timeout: (value) =>
+    commands.changeLowPowerModelUnloadTimeoutSetting(
+      value as ModelUnloadTimeout | null,
+    ),
+  notification_preferences: (value) =>
+    commands.changeNotificationPreferencesSetting(
+      value as NotificationPreferences,
+    ),
+  screen_reader_announcements: (value) =>
+    commands.changeScreenReaderAnnouncementsSetting(value as boolean),
+  shared_models_dir: (value) =>
+    commands.changeSharedModelsDirSetting((value as string | null) ?? ""),
+  wyoming_enabled: (value) =>
+    commands.changeWyomingEnabledSetting(value as boolean),
+  wyoming_server: (value) =>
+    commands.changeWyomingServerSetting((value as string | null) ?? ""),
+  punctuation_restoration: (value) =>
+    commands.changePunctuationRestorationSetting(value as boolean),
+  caption_export_timestamps: (value) =>
+    commands.changeCaptionExportTimestampsSetting(value as string),
+  caption_speaker_label: (value) =>
+    commands.changeCaptionSpeakerLabelSetting(value as string),
+  spoken_spelling: (value) =>
+    commands.changeSpokenSpellingSetting(value as boolean),
+  spelling_trigger: (value) =>
+    commands.changeSpellingTriggerSetting(value as string),
+  voice_commands: (value) =>
+    commands.changeVoiceCommandsSetting(value as boolean),
+  tray_indicator: (value) =>
+    commands.changeTrayIndicatorSetting(value as string),
+  caption_segment_at_pauses: (value) =>
+    commands.changeCaptionSegmentAtPausesSetting(value as boolean),
+  batch_deferred: (value) =>
+    commands.changeBatchDeferredSetting(value as boolean),
+  batch_window_start: (value) =>
+    commands.changeBatchWindowStartSetting(value as number | null),
+  batch_window_end: (value) =>
+    commands.changeBatchWindowEndSetting(value as number | null),
+  batch_require_ac_power: (value) =>
+    commands.changeBatchRequireAcPowerSetting(value as boolean),
+  batch_idle_minutes: (value) =>
+    commands.changeBatchIdleMinutesSetting(value as number | null),
+  model_memory_budget_mb: (value) =>
+    commands.changeModelMemoryBudgetSetting(value as number | null),
+  caption_max_segment_secs: (value) =>
+    commands.changeCaptionMaxSegmentSecsSetting(value as number),
+  conferencing_guard: (value) =>
+    commands.changeConferencingGuardSetting(value as string),
+  conferencing_microphone: (value) =>
+    commands.setConferencingMicrophone(
+      (value as string) === "Default" ? "default" : (value as string),
+    ),
+  oxford_comma: (value) => commands.changeOxfordCommaSetting(value as string),
+  dash_style: (value) => commands.changeDashStyleSetting(value as string),
+  quote_style: (value) => commands.changeQuoteStyleSetting(value as string),
+  ellipsis_style: (value) =>
+    commands.changeEllipsisStyleSetting(value as string),
+  preview_before_paste: (value) =>
+    commands.changePreviewBeforePasteSetting(value as boolean),
+  preview_apps: (value) => commands.changePreviewAppsSetting(value as string[]),
+  injection_audit: (value) =>
+    commands.changeInjectionAuditSetting(value as boolean),
+  injection_marker: (value) =>
+    commands.changeInjectionMarkerSetting(value as boolean),
+  adaptive_engine: (value) =>
+    commands.changeAdaptiveEngineSetting(value as boolean),
+  adaptive_threshold_secs: (value) =>
+    commands.changeAdaptiveThresholdSetting(value as number),
+  adaptive_long_model: (value) =>
+    commands.changeAdaptiveLongModelSetting(value as string | null),
+  app_paste_methods: (value) =>
+    commands.changeAppPasteMethodsSetting(
+      value as Partial<{ [key in string]: PasteMethod }>,
+    ),
+  boost_custom_words: (value) =>
+    commands.changeBoostCustomWordsSetting(value as boolean),
+  context_rules: (value) =>
+    commands.changeContextRulesSetting(value as ContextRule[]),
+  release_to_cancel: (value) =>
+    commands.changeReleaseToCancelSetting(value as boolean),
+  release_to_cancel_silence_secs: (value) =>
+    commands.changeReleaseToCancelSilenceSecsSetting(value as number | null),
+  max_concurrent_downloads: (value) =>
+    commands.changeMaxConcurrentDownloadsSetting(value as number),
+  download_limit_kbps: (value) =>
+    commands.changeDownloadLimitKbpsSetting(value as number | null),
+  share_destinations: (value) =>
+    commands.changeShareDestinationsSetting(value as ShareDestination[]),
+  compress_silence: (value) =>
+    commands.changeCompressSilenceSetting(value as boolean),
+  segment_long_recordings: (value) =>
+    commands.changeSegmentLongRecordingsSetting(value as boolean),
+  realtime_transcription: (value) =>
+    commands.changeRealtimeTranscriptionSetting(value as boolean),
+  realtime_endpoint: (value) =>
+    commands.changeRealtimeEndpointSetting(value as RealtimeEndpoint),
+  deepgram_enabled: (value) =>
+    commands.changeDeepgramEnabledSetting(value as boolean),
+  deepgram: (value) => commands.changeDeepgramSetting(value as DeepgramConfig),
+  cloud_transcription: (value) =>
+    commands.changeCloudTranscriptionSetting(value as CloudTranscription),
+  preprocessing: (value) =>
+    commands.changePreprocessingSetting(value as PreprocessStep[]),
+  word_replacements: (value) =>
+    commands.changeWordReplacementsSetting(value as WordReplacement[]),
+  transcription_prompt: (value) =>
+    commands.changeTranscriptionPromptSetting(value as string),
+  dictation_templates: (value) =>
+    commands.changeDictationTemplatesSetting(value as DictationTemplate[]),
+  text_rules: (value) => commands.changeTextRulesSetting(value as TextRule[]),
+  file_cache_limit: (value) =>
+    commands.changeFileCacheLimitSetting(value as number),
+  microphone_priority: (value) =>
+    commands.setMicrophonePriority(value as string[]),
+  remote_microphone: (value) => commands.setRemoteMicrophone(value as boolean),
+  remote_microphone_port: (value) =>
+    commands.setRemoteMicrophonePort(value as number),
+  api_server_enabled: (value) => commands.setApiServerEnabled(value as boolean),
+  api_server_port: (value) => commands.setApiServerPort(value as number),
+  wake_word_enabled: (value) => commands.setWakeWordEnabled(value as boolean),
+  hallucination_filter: (value) =>
+    commands.changeHallucinationFilterSetting(value as boolean),
+  input_watchdog: (value) =>
+    commands.changeInputWatchdogSetting(value as boolean),
+  adaptive_vad: (value) => commands.changeAdaptiveVadSetting(value as boolean),
+  pre_roll_ms: (value) => commands.changePreRollSetting(value as number),
+  noise_suppression: (value) =>
+    commands.changeNoiseSuppressionSetting(value as boolean),
+  noise_suppression_strength: (value) =>
+    commands.changeNoiseSuppressionStrengthSetting(value as number),
+  auto_gain_control: (value) =>
+    commands.changeAutoGainControlSetting(value as boolean),
+  system_audio_mix_microphone: (value) =>
+    commands.changeSystemAudioMixMicrophoneSetting(value as boolean),
 };
 
 export const useSettingsStore = create<SettingsStore>()(
@@ -135,7 +319,13 @@ export const useSettingsStore = create<SettingsStore>()(
     isUpdating: {},
     audioDevices: [],
     outputDevices: [],
-    customSounds: { start: false, stop: false },
+    customSounds: {
+      start: false,
+      stop: false,
+      error: false,
+      done: false,
+      countdown: false,
+    },
     postProcessModelOptions: {},
 
     // Internal setters
@@ -165,6 +355,8 @@ export const useSettingsStore = create<SettingsStore>()(
             always_on_microphone: settings.always_on_microphone ?? false,
             selected_microphone: settings.selected_microphone ?? "Default",
             clamshell_microphone: settings.clamshell_microphone ?? "Default",
+            conferencing_microphone:
+              settings.conferencing_microphone ?? "Default",
             selected_output_device:
               settings.selected_output_device ?? "Default",
           };
@@ -222,7 +414,7 @@ export const useSettingsStore = create<SettingsStore>()(
     },
 
     // Play a test sound
-    playTestSound: async (soundType: "start" | "stop") => {
+    playTestSound: async (soundType: SoundType) => {
       try {
         await commands.playTestSound(soundType);
       } catch (error) {